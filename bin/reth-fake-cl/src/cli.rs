@@ -0,0 +1,116 @@
+//! `reth-fake-cl` command.
+
+use crate::{
+    driver::{auth_client, FakeCl, FakeClConfig},
+    rpc::{FakeClApiServer, FakeClRpc},
+};
+use alloy_primitives::Address;
+use alloy_rpc_types_engine::JwtSecret;
+use clap::Parser;
+use jsonrpsee::server::ServerBuilder;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_tracing::{RethTracer, Tracer};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+/// Default port of the control RPC.
+pub const DEFAULT_RPC_PORT: u16 = 8560;
+
+/// Drives a node through the engine API, producing blocks on a timer or on demand via RPC.
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+pub struct Command {
+    /// The engine RPC url of the node.
+    #[arg(
+        long = "engine-rpc-url",
+        value_name = "ENGINE_RPC_URL",
+        default_value = "http://localhost:8551"
+    )]
+    pub engine_rpc_url: String,
+
+    /// The chain the node is running, used to select the engine API version of each block.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = EthereumChainSpecParser::help_message(),
+        default_value = "dev",
+        value_parser = EthereumChainSpecParser::parser()
+    )]
+    pub chain: Arc<ChainSpec>,
+
+    /// Path to the JWT secret of the engine RPC.
+    #[arg(long = "jwt-secret", value_name = "PATH")]
+    pub jwt_secret: PathBuf,
+
+    /// Interval at which blocks are produced.
+    ///
+    /// If not set, blocks are only produced on demand via `fakecl_produceBlocks`.
+    #[arg(long = "block-time", value_parser = humantime::parse_duration)]
+    pub block_time: Option<Duration>,
+
+    /// Seconds between the timestamps of a block and its parent.
+    #[arg(long = "timestamp-increment", default_value_t = 1)]
+    pub timestamp_increment: u64,
+
+    /// Time the node gets to build a payload before it is requested.
+    #[arg(long = "build-time", value_parser = humantime::parse_duration, default_value = "200ms")]
+    pub build_time: Duration,
+
+    /// Fee recipient of the produced blocks.
+    #[arg(long = "fee-recipient", default_value_t = Address::ZERO)]
+    pub fee_recipient: Address,
+
+    /// Address of the control RPC.
+    #[arg(long = "rpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub rpc_addr: IpAddr,
+
+    /// Port of the control RPC.
+    #[arg(long = "rpc.port", default_value_t = DEFAULT_RPC_PORT)]
+    pub rpc_port: u16,
+}
+
+impl Command {
+    /// Execute the command.
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let _guard = RethTracer::new().init()?;
+
+        let secret = JwtSecret::from_file(&self.jwt_secret)?;
+        let client = auth_client(&self.engine_rpc_url, secret)?;
+        let config = FakeClConfig {
+            timestamp_increment: self.timestamp_increment,
+            build_time: self.build_time,
+            fee_recipient: self.fee_recipient,
+        };
+        let driver = Arc::new(Mutex::new(FakeCl::new(client, self.chain, config).await?));
+
+        let server =
+            ServerBuilder::default().build(SocketAddr::new(self.rpc_addr, self.rpc_port)).await?;
+        let addr = server.local_addr()?;
+        let handle = server.start(FakeClRpc::new(driver.clone()).into_rpc());
+        info!(target: "fake-cl", %addr, "Control RPC started");
+
+        let Some(block_time) = self.block_time else {
+            handle.stopped().await;
+            return Ok(())
+        };
+
+        let mut interval = tokio::time::interval(block_time);
+        loop {
+            interval.tick().await;
+            if let Err(err) = driver.lock().await.produce_blocks(1).await {
+                error!(target: "fake-cl", %err, "Failed to produce block");
+            }
+        }
+    }
+}
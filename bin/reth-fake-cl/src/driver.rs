@@ -0,0 +1,499 @@
+//! Engine API driver that advances the chain of a node.
+
+use alloy_eips::{eip4844::kzg_to_versioned_hash, eip7685::RequestsOrHash};
+use alloy_primitives::{keccak256, Address, FixedBytes, B256};
+use alloy_rpc_types_engine::{
+    ExecutionPayloadFieldV2, ExecutionPayloadInputV2, ExecutionPayloadV3, ForkchoiceState,
+    ForkchoiceUpdated, JwtSecret, PayloadAttributes, PayloadId, PayloadStatus,
+};
+use alloy_rpc_types_eth::{
+    Block, BlockNumberOrTag, Header, TransactionReceipt, TransactionRequest,
+};
+use eyre::OptionExt;
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder};
+use reth_chainspec::EthereumHardforks;
+use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_ethereum_engine_primitives::EthEngineTypes;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadAttributesBuilder};
+use reth_rpc_api::clients::{EngineApiClient, EngineEthApiClient};
+use reth_rpc_layer::AuthClientLayer;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tracing::{debug, info};
+
+/// Number of blocks between the head and the block that is reported as safe.
+pub const SAFE_BLOCK_DISTANCE: usize = 32;
+
+/// Number of blocks between the head and the block that is reported as finalized.
+pub const FINALIZED_BLOCK_DISTANCE: usize = 64;
+
+/// Creates a client for the engine API served at the given url that authenticates every request
+/// with a fresh JWT.
+pub fn auth_client(
+    url: &str,
+    secret: JwtSecret,
+) -> eyre::Result<impl ClientT + Clone + Send + Sync + 'static> {
+    let middleware = tower::ServiceBuilder::default().layer(AuthClientLayer::new(secret));
+    Ok(HttpClientBuilder::default().set_http_middleware(middleware).build(url)?)
+}
+
+/// A block on the canonical chain of the driven node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockRef {
+    /// Block number.
+    pub number: u64,
+    /// Block hash.
+    pub hash: B256,
+    /// Block timestamp.
+    pub timestamp: u64,
+}
+
+impl From<&Header> for BlockRef {
+    fn from(header: &Header) -> Self {
+        Self { number: header.number, hash: header.hash, timestamp: header.timestamp }
+    }
+}
+
+/// Returns the version of the engine API methods used to build and insert a block with the given
+/// timestamp.
+///
+/// Blocks before Shanghai are built and inserted with the V2 methods as well, which accept
+/// payloads without withdrawals.
+pub fn engine_version_at_timestamp(
+    chain_spec: &impl EthereumHardforks,
+    timestamp: u64,
+) -> EngineApiMessageVersion {
+    if chain_spec.is_osaka_active_at_timestamp(timestamp) {
+        EngineApiMessageVersion::V5
+    } else if chain_spec.is_prague_active_at_timestamp(timestamp) {
+        EngineApiMessageVersion::V4
+    } else if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+        EngineApiMessageVersion::V3
+    } else {
+        EngineApiMessageVersion::V2
+    }
+}
+
+/// Configuration of the [`FakeCl`].
+#[derive(Debug, Clone, Copy)]
+pub struct FakeClConfig {
+    /// Seconds between the timestamps of a block and its parent.
+    pub timestamp_increment: u64,
+    /// Time the node gets to build a payload before it is requested.
+    ///
+    /// Pending transactions are only included if the node finished a build within this time.
+    pub build_time: Duration,
+    /// Fee recipient of all produced blocks.
+    pub fee_recipient: Address,
+}
+
+impl Default for FakeClConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_increment: 1,
+            build_time: Duration::from_millis(200),
+            fee_recipient: Address::ZERO,
+        }
+    }
+}
+
+/// A fake consensus layer client that produces blocks on a node through the engine API.
+///
+/// Payload attributes are built like the ones of the [`LocalMiner`](reth_engine_local::LocalMiner),
+/// except for the fields that would be random: they are derived from the parent block, so the same
+/// sequence of calls produces the same blocks on a node started from the same state.
+///
+/// The engine API version is selected per block, based on the forks of the chain that are active
+/// at its timestamp.
+#[derive(Debug)]
+pub struct FakeCl<C, ChainSpec> {
+    /// Engine API client.
+    client: C,
+    /// Chain spec of the driven node, used to select the engine API version.
+    chain_spec: Arc<ChainSpec>,
+    /// Builds the fork specific payload attributes.
+    attributes_builder: LocalPayloadAttributesBuilder<ChainSpec>,
+    /// Configuration of produced blocks.
+    config: FakeClConfig,
+    /// Latest canonical blocks, at most [`FINALIZED_BLOCK_DISTANCE`] + 1 and at least one.
+    blocks: Vec<BlockRef>,
+    /// Number of injected reorgs, used to make replacement blocks differ from the orphaned ones.
+    reorgs: u64,
+}
+
+impl<C, ChainSpec> FakeCl<C, ChainSpec>
+where
+    C: ClientT + Sync,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    /// Creates a new driver that continues the chain from the current head of the node.
+    pub async fn new(
+        client: C,
+        chain_spec: Arc<ChainSpec>,
+        config: FakeClConfig,
+    ) -> eyre::Result<Self> {
+        let head = Self::header_by_number(&client, BlockNumberOrTag::Latest).await?;
+
+        let mut blocks = vec![BlockRef::from(&head)];
+        let first = head.number.saturating_sub(FINALIZED_BLOCK_DISTANCE as u64);
+        for number in (first..head.number).rev() {
+            let header = Self::header_by_number(&client, number.into()).await?;
+            blocks.insert(0, BlockRef::from(&header));
+        }
+
+        info!(target: "fake-cl", head = head.number, hash = %head.hash, "Starting from head");
+        Ok(Self {
+            client,
+            attributes_builder: LocalPayloadAttributesBuilder::new(chain_spec.clone()),
+            chain_spec,
+            config,
+            blocks,
+            reorgs: 0,
+        })
+    }
+
+    /// Returns the current head block.
+    pub fn head(&self) -> BlockRef {
+        *self.blocks.last().expect("at least 1 block exists")
+    }
+
+    /// Returns the current forkchoice state.
+    pub fn forkchoice_state(&self) -> ForkchoiceState {
+        forkchoice_state(&self.blocks)
+    }
+
+    /// Produces the given number of blocks on top of the current head and returns them.
+    pub async fn produce_blocks(&mut self, count: u64) -> eyre::Result<Vec<BlockRef>> {
+        let mut produced = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            produced.push(self.produce_block().await?);
+        }
+        self.update_forkchoice().await?;
+        Ok(produced)
+    }
+
+    /// Replaces the latest `depth` blocks with `depth` new blocks and returns the new blocks.
+    ///
+    /// Building on an ancestor of the canonical head requires the node to run with
+    /// `--engine.always-process-payload-attributes-on-canonical-head`.
+    pub async fn reorg(&mut self, depth: u64) -> eyre::Result<Vec<BlockRef>> {
+        let depth = depth as usize;
+        if depth == 0 {
+            eyre::bail!("reorg depth must be at least 1")
+        }
+        if depth >= self.blocks.len() {
+            eyre::bail!(
+                "cannot reorg {depth} blocks, only {} ancestors are known",
+                self.blocks.len() - 1
+            )
+        }
+        if depth > SAFE_BLOCK_DISTANCE {
+            eyre::bail!("cannot reorg more than {SAFE_BLOCK_DISTANCE} blocks without unwinding the safe block")
+        }
+
+        let orphaned = self.blocks.split_off(self.blocks.len() - depth);
+        self.reorgs += 1;
+        info!(target: "fake-cl", depth, from = %orphaned.last().expect("depth > 0").hash, "Injecting reorg");
+
+        let mut produced = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            match self.produce_block().await {
+                Ok(block) => produced.push(block),
+                Err(err) => {
+                    // restore the canonical chain the node is still on
+                    if produced.is_empty() {
+                        self.blocks.extend(orphaned);
+                    }
+                    return Err(err)
+                }
+            }
+        }
+        self.update_forkchoice().await?;
+        Ok(produced)
+    }
+
+    /// Builds a block on top of the current head and inserts it.
+    ///
+    /// The new block is recorded as the head, but only becomes canonical on the next forkchoice
+    /// update.
+    async fn produce_block(&mut self) -> eyre::Result<BlockRef> {
+        let parent = self.head();
+        let attributes =
+            payload_attributes(&self.attributes_builder, parent, self.reorgs, &self.config);
+        let timestamp = attributes.timestamp;
+        let parent_beacon_block_root = attributes.parent_beacon_block_root;
+        let version = engine_version_at_timestamp(self.chain_spec.as_ref(), timestamp);
+
+        let res = self.fork_choice_updated(Some(attributes), version).await?;
+        if !res.is_valid() {
+            eyre::bail!("invalid forkchoice state: {:?}", res.payload_status)
+        }
+        let payload_id = res.payload_id.ok_or_eyre(
+            "no payload id, the node has to run with \
+             --engine.always-process-payload-attributes-on-canonical-head to build on ancestors",
+        )?;
+
+        tokio::time::sleep(self.config.build_time).await;
+
+        let (hash, number, status) =
+            self.insert_payload(payload_id, parent_beacon_block_root, version).await?;
+        if !status.is_valid() {
+            eyre::bail!("invalid payload {hash}: {:?}", status.status)
+        }
+        debug!(target: "fake-cl", number, %hash, ?version, "Inserted block");
+
+        let block = BlockRef { number, hash, timestamp };
+        self.blocks.push(block);
+        if self.blocks.len() > FINALIZED_BLOCK_DISTANCE + 1 {
+            self.blocks.remove(0);
+        }
+        Ok(block)
+    }
+
+    /// Makes the current head canonical.
+    async fn update_forkchoice(&self) -> eyre::Result<()> {
+        let head = self.head();
+        let version = engine_version_at_timestamp(self.chain_spec.as_ref(), head.timestamp);
+        let res = self.fork_choice_updated(None, version).await?;
+        if !res.is_valid() {
+            eyre::bail!("invalid forkchoice state: {:?}", res.payload_status)
+        }
+        info!(target: "fake-cl", number = head.number, hash = %head.hash, "Updated head");
+        Ok(())
+    }
+
+    /// Sends the current forkchoice state with the given payload attributes.
+    async fn fork_choice_updated(
+        &self,
+        attributes: Option<PayloadAttributes>,
+        version: EngineApiMessageVersion,
+    ) -> eyre::Result<ForkchoiceUpdated> {
+        let state = self.forkchoice_state();
+        let res = match version {
+            EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+                EngineApiClient::<EthEngineTypes>::fork_choice_updated_v2(
+                    &self.client,
+                    state,
+                    attributes,
+                )
+                .await?
+            }
+            EngineApiMessageVersion::V3 |
+            EngineApiMessageVersion::V4 |
+            EngineApiMessageVersion::V5 => {
+                EngineApiClient::<EthEngineTypes>::fork_choice_updated_v3(
+                    &self.client,
+                    state,
+                    attributes,
+                )
+                .await?
+            }
+        };
+        Ok(res)
+    }
+
+    /// Fetches the payload with the given id and inserts it via `engine_newPayload`.
+    ///
+    /// Returns the hash and number of the payload and the status reported by the node.
+    async fn insert_payload(
+        &self,
+        payload_id: PayloadId,
+        parent_beacon_block_root: Option<B256>,
+        version: EngineApiMessageVersion,
+    ) -> eyre::Result<(B256, u64, PayloadStatus)> {
+        let client = &self.client;
+        let parent_beacon_block_root = parent_beacon_block_root.unwrap_or_default();
+        let (payload, versioned_hashes, requests) = match version {
+            EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+                let envelope =
+                    EngineApiClient::<EthEngineTypes>::get_payload_v2(client, payload_id).await?;
+                let payload = match envelope.execution_payload {
+                    ExecutionPayloadFieldV2::V1(payload) => {
+                        ExecutionPayloadInputV2 { execution_payload: payload, withdrawals: None }
+                    }
+                    ExecutionPayloadFieldV2::V2(payload) => ExecutionPayloadInputV2 {
+                        execution_payload: payload.payload_inner,
+                        withdrawals: Some(payload.withdrawals),
+                    },
+                };
+                let (hash, number) =
+                    (payload.execution_payload.block_hash, payload.execution_payload.block_number);
+                let status =
+                    EngineApiClient::<EthEngineTypes>::new_payload_v2(client, payload).await?;
+                return Ok((hash, number, status))
+            }
+            EngineApiMessageVersion::V3 => {
+                let envelope =
+                    EngineApiClient::<EthEngineTypes>::get_payload_v3(client, payload_id).await?;
+                let versioned_hashes = versioned_hashes(&envelope.blobs_bundle.commitments);
+                (envelope.execution_payload, versioned_hashes, None)
+            }
+            EngineApiMessageVersion::V4 => {
+                let envelope =
+                    EngineApiClient::<EthEngineTypes>::get_payload_v4(client, payload_id).await?;
+                let inner = envelope.envelope_inner;
+                let versioned_hashes = versioned_hashes(&inner.blobs_bundle.commitments);
+                (inner.execution_payload, versioned_hashes, Some(envelope.execution_requests))
+            }
+            EngineApiMessageVersion::V5 => {
+                let envelope =
+                    EngineApiClient::<EthEngineTypes>::get_payload_v5(client, payload_id).await?;
+                let versioned_hashes = versioned_hashes(&envelope.blobs_bundle.commitments);
+                (envelope.execution_payload, versioned_hashes, Some(envelope.execution_requests))
+            }
+        };
+
+        let (hash, number) = payload_hash_and_number(&payload);
+        let status = match requests {
+            None => {
+                EngineApiClient::<EthEngineTypes>::new_payload_v3(
+                    client,
+                    payload,
+                    versioned_hashes,
+                    parent_beacon_block_root,
+                )
+                .await?
+            }
+            Some(requests) => {
+                EngineApiClient::<EthEngineTypes>::new_payload_v4(
+                    client,
+                    payload,
+                    versioned_hashes,
+                    parent_beacon_block_root,
+                    RequestsOrHash::Requests(requests),
+                )
+                .await?
+            }
+        };
+        Ok((hash, number, status))
+    }
+
+    /// Fetches the header of the given block from the node.
+    async fn header_by_number(client: &C, number: BlockNumberOrTag) -> eyre::Result<Header> {
+        let block =
+            EngineEthApiClient::<TransactionRequest, Block, TransactionReceipt>::block_by_number(
+                client, number, false,
+            )
+            .await?;
+        Ok(block.ok_or_else(|| eyre::eyre!("block {number} not found"))?.header)
+    }
+}
+
+/// Returns the forkchoice state for the given chain of blocks, the last block being the head.
+fn forkchoice_state(blocks: &[BlockRef]) -> ForkchoiceState {
+    let ancestor = |distance: usize| blocks[blocks.len().saturating_sub(distance + 1)].hash;
+    ForkchoiceState {
+        head_block_hash: ancestor(0),
+        safe_block_hash: ancestor(SAFE_BLOCK_DISTANCE),
+        finalized_block_hash: ancestor(FINALIZED_BLOCK_DISTANCE),
+    }
+}
+
+/// Returns the payload attributes of the child of the given parent.
+///
+/// The fork specific fields are set by the given builder. `prev_randao` is derived from the parent
+/// hash and the number of injected reorgs, so blocks that replace orphaned blocks differ from them
+/// even if they contain the same transactions.
+fn payload_attributes(
+    builder: &impl PayloadAttributesBuilder<PayloadAttributes>,
+    parent: BlockRef,
+    reorgs: u64,
+    config: &FakeClConfig,
+) -> PayloadAttributes {
+    let mut attributes = builder.build(parent.timestamp + config.timestamp_increment);
+
+    let mut seed = parent.hash.to_vec();
+    seed.extend_from_slice(&reorgs.to_be_bytes());
+    attributes.prev_randao = keccak256(seed);
+    attributes.suggested_fee_recipient = config.fee_recipient;
+    if attributes.parent_beacon_block_root.is_some() {
+        attributes.parent_beacon_block_root = Some(B256::ZERO);
+    }
+    attributes
+}
+
+/// Returns the versioned hashes of the given blob commitments.
+fn versioned_hashes(commitments: &[FixedBytes<48>]) -> Vec<B256> {
+    commitments.iter().map(|commitment| kzg_to_versioned_hash(commitment.as_slice())).collect()
+}
+
+/// Returns the block hash and number of the payload.
+const fn payload_hash_and_number(payload: &ExecutionPayloadV3) -> (B256, u64) {
+    let inner = &payload.payload_inner.payload_inner;
+    (inner.block_hash, inner.block_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{Chain, ChainSpecBuilder, EthereumHardfork, ForkCondition, DEV};
+
+    fn chain(len: u64) -> Vec<BlockRef> {
+        (0..len)
+            .map(|number| BlockRef {
+                number,
+                hash: B256::with_last_byte(number as u8),
+                timestamp: number,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forkchoice_state_distances() {
+        let blocks = chain(1);
+        let state = forkchoice_state(&blocks);
+        assert_eq!(state.head_block_hash, blocks[0].hash);
+        assert_eq!(state.safe_block_hash, blocks[0].hash);
+        assert_eq!(state.finalized_block_hash, blocks[0].hash);
+
+        let blocks = chain(FINALIZED_BLOCK_DISTANCE as u64 + 1);
+        let state = forkchoice_state(&blocks);
+        assert_eq!(state.head_block_hash, blocks[64].hash);
+        assert_eq!(state.safe_block_hash, blocks[32].hash);
+        assert_eq!(state.finalized_block_hash, blocks[0].hash);
+    }
+
+    #[test]
+    fn deterministic_attributes() {
+        let config = FakeClConfig::default();
+        let builder = LocalPayloadAttributesBuilder::new(DEV.clone());
+        let parent = chain(2)[1];
+
+        let attributes = payload_attributes(&builder, parent, 0, &config);
+        assert_eq!(attributes, payload_attributes(&builder, parent, 0, &config));
+        assert_eq!(attributes.timestamp, 2);
+        assert_eq!(attributes.parent_beacon_block_root, Some(B256::ZERO));
+
+        let replacement = payload_attributes(&builder, parent, 1, &config);
+        assert_ne!(attributes.prev_randao, replacement.prev_randao);
+
+        let shanghai = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(Chain::dev())
+                .genesis(DEV.genesis.clone())
+                .shanghai_activated()
+                .build(),
+        );
+        let builder = LocalPayloadAttributesBuilder::new(shanghai);
+        let attributes = payload_attributes(&builder, parent, 0, &config);
+        assert_eq!(attributes.parent_beacon_block_root, None);
+    }
+
+    #[test]
+    fn engine_version_per_fork() {
+        let chain_spec = ChainSpecBuilder::default()
+            .chain(Chain::dev())
+            .genesis(DEV.genesis.clone())
+            .shanghai_activated()
+            .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(10))
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(20))
+            .with_fork(EthereumHardfork::Osaka, ForkCondition::Timestamp(30))
+            .build();
+
+        assert_eq!(engine_version_at_timestamp(&chain_spec, 9), EngineApiMessageVersion::V2);
+        assert_eq!(engine_version_at_timestamp(&chain_spec, 10), EngineApiMessageVersion::V3);
+        assert_eq!(engine_version_at_timestamp(&chain_spec, 25), EngineApiMessageVersion::V4);
+        assert_eq!(engine_version_at_timestamp(&chain_spec, 30), EngineApiMessageVersion::V5);
+    }
+}
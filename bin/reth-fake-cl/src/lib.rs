@@ -0,0 +1,22 @@
+//! # reth-fake-cl
+//!
+//! A fake consensus layer client that drives a node, usually a dev node, through the engine API.
+//!
+//! Blocks are produced on a timer or on demand via the `fakecl` RPC namespace, which also allows
+//! injecting reorgs of configurable depth. This makes it possible to run integration tests of
+//! downstream applications against a live node with deterministic block production.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod cli;
+pub mod driver;
+pub mod rpc;
+
+pub use driver::{auth_client, engine_version_at_timestamp, BlockRef, FakeCl, FakeClConfig};
+pub use rpc::{FakeClApiClient, FakeClApiServer, FakeClRpc};
@@ -0,0 +1,16 @@
+//! Binary of the `reth-fake-cl` engine API driver.
+
+use clap::Parser;
+use reth_cli_runner::CliRunner;
+use reth_fake_cl::cli::Command;
+
+fn main() {
+    // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
+    if std::env::var_os("RUST_BACKTRACE").is_none() {
+        std::env::set_var("RUST_BACKTRACE", "1");
+    }
+
+    // Run until either exit or sigint or sigterm
+    let runner = CliRunner::try_default_runtime().unwrap();
+    runner.run_command_until_exit(|ctx| Command::parse().execute(ctx)).unwrap();
+}
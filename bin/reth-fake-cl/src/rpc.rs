@@ -0,0 +1,64 @@
+//! RPC to control the [`FakeCl`].
+
+use crate::driver::{BlockRef, FakeCl};
+use jsonrpsee::{
+    core::{async_trait, client::ClientT, RpcResult},
+    proc_macros::rpc,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
+};
+use reth_chainspec::EthereumHardforks;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Methods to produce blocks and inject reorgs on demand.
+#[rpc(server, client, namespace = "fakecl")]
+pub trait FakeClApi {
+    /// Produces the given number of blocks and returns them.
+    #[method(name = "produceBlocks")]
+    async fn produce_blocks(&self, count: u64) -> RpcResult<Vec<BlockRef>>;
+
+    /// Replaces the latest `depth` blocks with `depth` new blocks and returns the new blocks.
+    #[method(name = "reorg")]
+    async fn reorg(&self, depth: u64) -> RpcResult<Vec<BlockRef>>;
+
+    /// Returns the current head block.
+    #[method(name = "head")]
+    async fn head(&self) -> RpcResult<BlockRef>;
+}
+
+/// Implementation of the [`FakeClApiServer`] that drives a shared [`FakeCl`].
+#[derive(Debug)]
+pub struct FakeClRpc<C, ChainSpec> {
+    driver: Arc<Mutex<FakeCl<C, ChainSpec>>>,
+}
+
+impl<C, ChainSpec> FakeClRpc<C, ChainSpec> {
+    /// Creates a new instance that drives the given [`FakeCl`].
+    pub const fn new(driver: Arc<Mutex<FakeCl<C, ChainSpec>>>) -> Self {
+        Self { driver }
+    }
+}
+
+#[async_trait]
+impl<C, ChainSpec> FakeClApiServer for FakeClRpc<C, ChainSpec>
+where
+    C: ClientT + Send + Sync + 'static,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    async fn produce_blocks(&self, count: u64) -> RpcResult<Vec<BlockRef>> {
+        self.driver.lock().await.produce_blocks(count).await.map_err(internal_err)
+    }
+
+    async fn reorg(&self, depth: u64) -> RpcResult<Vec<BlockRef>> {
+        self.driver.lock().await.reorg(depth).await.map_err(internal_err)
+    }
+
+    async fn head(&self) -> RpcResult<BlockRef> {
+        Ok(self.driver.lock().await.head())
+    }
+}
+
+/// Converts a driver error into an RPC error.
+fn internal_err(err: eyre::Report) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, format!("{err:#}"), None::<()>)
+}
@@ -0,0 +1,116 @@
+//! Tests driving a node with the fake consensus layer client.
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_engine::PayloadAttributes;
+use jsonrpsee::server::ServerBuilder;
+use reth_chainspec::{Chain, ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition, DEV};
+use reth_e2e_test_utils::{setup_engine, NodeHelperType};
+use reth_ethereum_engine_primitives::EthPayloadBuilderAttributes;
+use reth_fake_cl::{FakeCl, FakeClApiClient, FakeClApiServer, FakeClConfig, FakeClRpc};
+use reth_node_api::TreeConfig;
+use reth_node_ethereum::EthereumNode;
+use reth_provider::BlockNumReader;
+use reth_tasks::TaskManager;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::Mutex;
+
+fn eth_payload_attributes(timestamp: u64) -> EthPayloadBuilderAttributes {
+    let attributes = PayloadAttributes {
+        timestamp,
+        prev_randao: B256::ZERO,
+        suggested_fee_recipient: Address::ZERO,
+        withdrawals: Some(vec![]),
+        parent_beacon_block_root: Some(B256::ZERO),
+    };
+    EthPayloadBuilderAttributes::new(B256::ZERO, attributes)
+}
+
+/// Chain that activates Prague 2 seconds after genesis, so that the driver has to switch the engine
+/// API version after the first block.
+fn chain_spec() -> Arc<ChainSpec> {
+    Arc::new(
+        ChainSpecBuilder::default()
+            .chain(Chain::dev())
+            .genesis(DEV.genesis.clone())
+            .cancun_activated()
+            .with_fork(
+                EthereumHardfork::Prague,
+                ForkCondition::Timestamp(DEV.genesis.timestamp + 2),
+            )
+            .build(),
+    )
+}
+
+async fn setup_node(
+    chain_spec: Arc<ChainSpec>,
+) -> eyre::Result<(NodeHelperType<EthereumNode>, TaskManager)> {
+    let tree_config =
+        TreeConfig::default().with_always_process_payload_attributes_on_canonical_head(true);
+    let (mut nodes, tasks, _) =
+        setup_engine::<EthereumNode>(1, chain_spec, false, tree_config, eth_payload_attributes)
+            .await?;
+    Ok((nodes.pop().unwrap(), tasks))
+}
+
+fn config() -> FakeClConfig {
+    FakeClConfig { build_time: Duration::from_millis(50), ..Default::default() }
+}
+
+#[tokio::test]
+async fn can_produce_blocks_and_reorg() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = chain_spec();
+    let (node, _tasks) = setup_node(chain_spec.clone()).await?;
+    let client = node.auth_server_handle().http_client();
+    let mut fake_cl = FakeCl::new(client, chain_spec, config()).await?;
+
+    // the first block is inserted with the Cancun methods, the others with the Prague ones
+    let produced = fake_cl.produce_blocks(3).await?;
+    assert_eq!(produced.iter().map(|block| block.number).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(produced.iter().all(|block| block.timestamp == DEV.genesis.timestamp + block.number));
+    assert_eq!(node.inner.provider.best_block_number()?, 3);
+    for block in &produced {
+        assert_eq!(node.block_hash(block.number), block.hash);
+    }
+
+    let replaced = fake_cl.reorg(2).await?;
+    assert_eq!(replaced.iter().map(|block| block.number).collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!(fake_cl.head(), replaced[1]);
+    assert_eq!(node.inner.provider.best_block_number()?, 3);
+    assert_eq!(node.block_hash(1), produced[0].hash);
+    for (block, orphaned) in replaced.iter().zip(&produced[1..]) {
+        assert_ne!(block.hash, orphaned.hash);
+        assert_eq!(node.block_hash(block.number), block.hash);
+    }
+
+    assert!(fake_cl.reorg(0).await.is_err());
+    assert!(fake_cl.reorg(4).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_drive_node_over_rpc() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = chain_spec();
+    let (node, _tasks) = setup_node(chain_spec.clone()).await?;
+    let client = node.auth_server_handle().http_client();
+    let fake_cl = FakeCl::new(client, chain_spec, config()).await?;
+
+    let server = ServerBuilder::default().build("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    let _handle = server.start(FakeClRpc::new(Arc::new(Mutex::new(fake_cl))).into_rpc());
+    let rpc =
+        jsonrpsee::http_client::HttpClientBuilder::default().build(format!("http://{addr}"))?;
+
+    let produced = FakeClApiClient::produce_blocks(&rpc, 2).await?;
+    assert_eq!(FakeClApiClient::head(&rpc).await?, produced[1]);
+
+    let replaced = FakeClApiClient::reorg(&rpc, 1).await?;
+    assert_eq!(FakeClApiClient::head(&rpc).await?, replaced[0]);
+    assert_eq!(node.block_hash(2), replaced[0].hash);
+
+    Ok(())
+}
@@ -13,8 +13,8 @@ use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_metrics::{metrics::Gauge, Metrics};
 use reth_primitives_traits::{
-    BlockBody as _, IndexedTx, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
-    SignedTransaction,
+    BlockBody as _, InMemorySize, IndexedTx, NodePrimitives, RecoveredBlock, SealedBlock,
+    SealedHeader, SignedTransaction,
 };
 use reth_storage_api::StateProviderBox;
 use reth_trie::{updates::TrieUpdates, HashedPostState};
@@ -34,6 +34,8 @@ pub(crate) struct InMemoryStateMetrics {
     pub(crate) latest_block: Gauge,
     /// The number of blocks in the in-memory state.
     pub(crate) num_blocks: Gauge,
+    /// The estimated size of the blocks in the in-memory state in bytes.
+    pub(crate) size_bytes: Gauge,
 }
 
 /// Container type for in memory state data of the canonical chain.
@@ -93,6 +95,13 @@ impl<N: NodePrimitives> InMemoryState<N> {
             self.metrics.latest_block.set(*latest_block_number as f64);
         }
         self.metrics.num_blocks.set(numbers.len() as f64);
+        drop(numbers);
+        self.metrics.size_bytes.set(self.size() as f64);
+    }
+
+    /// Returns the estimated size of the canonical blocks in bytes.
+    pub(crate) fn size(&self) -> usize {
+        self.blocks.read().values().map(|state| state.size()).sum()
     }
 
     /// Returns the state for a given block hash.
@@ -336,16 +345,17 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             let mut old_blocks = blocks
                 .drain()
                 .filter(|(_, b)| b.block_ref().recovered_block().number() > persisted_height)
-                .map(|(_, b)| b.block.clone())
+                .map(|(_, b)| (b.block.clone(), b.size))
                 .collect::<Vec<_>>();
 
             // sort the blocks by number so we can insert them back in natural order (low -> high)
-            old_blocks.sort_unstable_by_key(|block| block.recovered_block().number());
+            old_blocks.sort_unstable_by_key(|(block, _)| block.recovered_block().number());
 
-            // re-insert the blocks in natural order and connect them to their parent blocks
-            for block in old_blocks {
+            // re-insert the blocks in natural order and connect them to their parent blocks, the
+            // sizes of the blocks don't change
+            for (block, size) in old_blocks {
                 let parent = blocks.get(&block.recovered_block().parent_hash()).cloned();
-                let block_state = BlockState::with_parent(block, parent);
+                let block_state = BlockState { block, parent, size };
                 let hash = block_state.hash();
                 let number = block_state.number();
 
@@ -379,6 +389,11 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.in_memory_state.head_state()
     }
 
+    /// Returns the estimated size in bytes of the canonical blocks that are kept in memory.
+    pub fn in_memory_size(&self) -> usize {
+        self.inner.in_memory_state.size()
+    }
+
     /// Returns the in memory pending state.
     pub fn pending_state(&self) -> Option<BlockState<N>> {
         self.inner.in_memory_state.pending_state()
@@ -570,20 +585,25 @@ pub struct BlockState<N: NodePrimitives = EthPrimitives> {
     block: ExecutedBlockWithTrieUpdates<N>,
     /// The block's parent block if it exists.
     parent: Option<Arc<BlockState<N>>>,
+    /// The estimated size of the executed block in bytes.
+    size: usize,
 }
 
 impl<N: NodePrimitives> BlockState<N> {
     /// [`BlockState`] constructor.
-    pub const fn new(block: ExecutedBlockWithTrieUpdates<N>) -> Self {
-        Self { block, parent: None }
+    pub fn new(block: ExecutedBlockWithTrieUpdates<N>) -> Self {
+        Self::with_parent(block, None)
     }
 
     /// [`BlockState`] constructor with parent.
-    pub const fn with_parent(
-        block: ExecutedBlockWithTrieUpdates<N>,
-        parent: Option<Arc<Self>>,
-    ) -> Self {
-        Self { block, parent }
+    pub fn with_parent(block: ExecutedBlockWithTrieUpdates<N>, parent: Option<Arc<Self>>) -> Self {
+        let size = block.size();
+        Self { block, parent, size }
+    }
+
+    /// Returns the estimated size of the executed block in bytes, excluding its parents.
+    pub const fn size(&self) -> usize {
+        self.size
     }
 
     /// Returns the hash and block of the on disk block this state can be traced back to.
@@ -767,6 +787,12 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
     }
 }
 
+impl<N: NodePrimitives> InMemorySize for ExecutedBlock<N> {
+    fn size(&self) -> usize {
+        self.recovered_block.size() + self.execution_output.size() + self.hashed_state.size()
+    }
+}
+
 /// Trie updates that result from calculating the state root for the block.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExecutedTrieUpdates {
@@ -870,6 +896,12 @@ impl<N: NodePrimitives> ExecutedBlockWithTrieUpdates<N> {
     }
 }
 
+impl<N: NodePrimitives> InMemorySize for ExecutedBlockWithTrieUpdates<N> {
+    fn size(&self) -> usize {
+        self.block.size() + self.trie_updates().map_or(0, InMemorySize::size)
+    }
+}
+
 /// Non-empty chain of blocks.
 #[derive(Debug)]
 pub enum NewCanonicalChain<N: NodePrimitives = EthPrimitives> {
@@ -1415,6 +1447,26 @@ mod tests {
         assert_eq!(chain[2].number(), 1);
     }
 
+    #[test]
+    fn test_canonical_in_memory_state_size() {
+        let mut parent_hash = B256::random();
+        let mut block_builder = TestBlockBuilder::eth();
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        assert_eq!(state.in_memory_size(), 0);
+
+        let mut blocks = Vec::new();
+        for i in 1..=3 {
+            let block = block_builder.get_executed_block_with_number(i, parent_hash);
+            parent_hash = block.recovered_block().hash();
+            state.update_blocks(Some(block.clone()), None);
+            blocks.push(block);
+        }
+        assert_eq!(state.in_memory_size(), blocks.iter().map(InMemorySize::size).sum::<usize>());
+
+        state.remove_persisted_blocks(blocks[1].recovered_block().num_hash());
+        assert_eq!(state.in_memory_size(), blocks[2].size());
+    }
+
     // ensures the pending block is not part of the canonical chain
     #[test]
     fn test_canonical_in_memory_state_canonical_chain_with_pending_block() {
@@ -9,7 +9,7 @@ use reth_storage_api::{
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, TrieInput, WitnessSink,
 };
 use revm_database::BundleState;
 use std::sync::OnceLock;
@@ -200,6 +200,16 @@ impl<N: NodePrimitives> StateProofProvider for MemoryOverlayStateProviderRef<'_,
         input.prepend_self(self.trie_input().clone());
         self.historical.witness(input, target)
     }
+
+    fn witness_into(
+        &self,
+        mut input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        input.prepend_self(self.trie_input().clone());
+        self.historical.witness_into(input, target, witness)
+    }
 }
 
 impl<N: NodePrimitives> HashedPostStateProvider for MemoryOverlayStateProviderRef<'_, N> {
@@ -222,6 +232,60 @@ impl<N: NodePrimitives> StateProvider for MemoryOverlayStateProviderRef<'_, N> {
 
         self.historical.storage(address, storage_key)
     }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        let mut missing = Vec::new();
+        for (index, address) in addresses.iter().enumerate() {
+            let account =
+                self.in_memory.iter().find_map(|block| block.execution_output.account(address));
+            if account.is_none() {
+                missing.push(index);
+            }
+            accounts.push(account.flatten());
+        }
+
+        if !missing.is_empty() {
+            let missing_addresses =
+                missing.iter().map(|&index| addresses[index]).collect::<Vec<_>>();
+            for (index, account) in
+                missing.into_iter().zip(self.historical.basic_accounts(&missing_addresses)?)
+            {
+                accounts[index] = account;
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    fn storage_batch(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        let mut values = Vec::with_capacity(slots.len());
+        let mut missing = Vec::new();
+        for (index, (address, storage_key)) in slots.iter().enumerate() {
+            let value = self
+                .in_memory
+                .iter()
+                .find_map(|block| block.execution_output.storage(address, (*storage_key).into()));
+            if value.is_none() {
+                missing.push(index);
+            }
+            values.push(value);
+        }
+
+        if !missing.is_empty() {
+            let missing_slots = missing.iter().map(|&index| slots[index]).collect::<Vec<_>>();
+            for (index, value) in
+                missing.into_iter().zip(self.historical.storage_batch(&missing_slots)?)
+            {
+                values[index] = value;
+            }
+        }
+
+        Ok(values)
+    }
 }
 
 impl<N: NodePrimitives> BytecodeReader for MemoryOverlayStateProviderRef<'_, N> {
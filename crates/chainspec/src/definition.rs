@@ -0,0 +1,411 @@
+//! Runtime-loadable custom chain definitions.
+//!
+//! A [`ChainDefinition`] describes a chain in terms of its genesis, a set of fork activation
+//! expressions, an optional deposit contract and the blob schedule. This allows spinning up
+//! devnets from a definition file without adding a new hardcoded [`ChainSpec`].
+
+use crate::{constants::MAINNET_DEPOSIT_CONTRACT, ChainSpec, DepositContract};
+use alloc::{collections::BTreeMap, string::String};
+use alloy_eips::eip7840::BlobParams;
+use alloy_genesis::{ChainConfig, Genesis};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use core::{fmt, str::FromStr};
+use reth_ethereum_forks::EthereumHardfork;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A custom chain definition that can be loaded at startup.
+///
+/// Fork activations are given as [`ForkActivation`] expressions, keyed by the (case-insensitive)
+/// hardfork name, e.g. `shanghai = "timestamp:0"`. They take precedence over the activations
+/// configured in the genesis config.
+///
+/// Blob schedule entries are keyed by hardfork name as well and are merged into the blob schedule
+/// of the genesis config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainDefinition {
+    /// The genesis of the chain.
+    pub genesis: Genesis,
+    /// Fork activations keyed by hardfork name.
+    #[serde(default)]
+    pub forks: BTreeMap<String, ForkActivation>,
+    /// The deposit contract of the chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_contract: Option<DepositContractDefinition>,
+    /// Blob parameters keyed by hardfork name.
+    #[serde(default)]
+    pub blob_schedule: BTreeMap<String, BlobParams>,
+}
+
+impl ChainDefinition {
+    /// Creates a new [`ChainDefinition`] for the given [`Genesis`] without any overrides.
+    pub const fn new(genesis: Genesis) -> Self {
+        Self {
+            genesis,
+            forks: BTreeMap::new(),
+            deposit_contract: None,
+            blob_schedule: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the activation of the given fork.
+    pub fn with_fork(mut self, fork: impl Into<String>, activation: ForkActivation) -> Self {
+        self.forks.insert(fork.into(), activation);
+        self
+    }
+
+    /// Sets the deposit contract.
+    pub const fn with_deposit_contract(
+        mut self,
+        deposit_contract: DepositContractDefinition,
+    ) -> Self {
+        self.deposit_contract = Some(deposit_contract);
+        self
+    }
+
+    /// Sets the blob parameters of the given fork.
+    pub fn with_blob_params(mut self, fork: impl Into<String>, params: BlobParams) -> Self {
+        self.blob_schedule.insert(fork.into(), params);
+        self
+    }
+
+    /// Applies the fork activations, deposit contract address and blob schedule to the genesis
+    /// config and returns the resulting [`Genesis`].
+    ///
+    /// Note: the deposit contract deployment block and topic can't be represented in the genesis,
+    /// use the [`ChainSpec`] conversion to retain them.
+    pub fn into_genesis(self) -> Result<Genesis, ChainDefinitionError> {
+        let Self { mut genesis, forks, deposit_contract, blob_schedule } = self;
+
+        for (fork, activation) in forks {
+            apply_fork_activation(&mut genesis.config, &fork, activation)?;
+        }
+
+        if let Some(deposit_contract) = deposit_contract {
+            genesis.config.deposit_contract_address = Some(deposit_contract.address);
+        }
+
+        for (fork, params) in blob_schedule {
+            genesis.config.blob_schedule.insert(fork.to_lowercase(), params);
+        }
+
+        Ok(genesis)
+    }
+}
+
+impl TryFrom<ChainDefinition> for ChainSpec {
+    type Error = ChainDefinitionError;
+
+    fn try_from(definition: ChainDefinition) -> Result<Self, Self::Error> {
+        let deposit_contract = definition.deposit_contract;
+        let mut spec = Self::from(definition.into_genesis()?);
+        if let Some(deposit_contract) = deposit_contract {
+            spec.deposit_contract = Some(deposit_contract.into());
+        }
+        Ok(spec)
+    }
+}
+
+/// Deposit contract configuration of a [`ChainDefinition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DepositContractDefinition {
+    /// The address of the deposit contract.
+    pub address: Address,
+    /// The deployment block of the deposit contract.
+    #[serde(default)]
+    pub block: BlockNumber,
+    /// The `DepositEvent` event signature, defaults to the mainnet deposit contract topic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<B256>,
+}
+
+impl From<DepositContractDefinition> for DepositContract {
+    fn from(value: DepositContractDefinition) -> Self {
+        Self::new(value.address, value.block, value.topic.unwrap_or(MAINNET_DEPOSIT_CONTRACT.topic))
+    }
+}
+
+/// A fork activation expression.
+///
+/// Supported expressions are:
+/// - `block:<number>`: activation at the given block number
+/// - `timestamp:<seconds>` (or `time:<seconds>`): activation at the given timestamp
+/// - `ttd:<total difficulty>[@<block>]`: activation once the terminal total difficulty has been
+///   reached, optionally with the merge netsplit block
+/// - `never`: the fork is not activated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForkActivation {
+    /// The fork is activated at the given block.
+    Block(BlockNumber),
+    /// The fork is activated at the given timestamp.
+    Timestamp(u64),
+    /// The fork is activated once the total difficulty has been reached.
+    Ttd {
+        /// The terminal total difficulty.
+        total_difficulty: U256,
+        /// The merge netsplit block, if known.
+        fork_block: Option<BlockNumber>,
+    },
+    /// The fork is never activated.
+    Never,
+}
+
+impl FromStr for ForkActivation {
+    type Err = ChainDefinitionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("never") {
+            return Ok(Self::Never)
+        }
+
+        let invalid = || ChainDefinitionError::InvalidForkActivation(s.into());
+        let (kind, value) = s.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim();
+        match kind.trim().to_lowercase().as_str() {
+            "block" => value.parse().map(Self::Block).map_err(|_| invalid()),
+            "timestamp" | "time" => value.parse().map(Self::Timestamp).map_err(|_| invalid()),
+            "ttd" => {
+                let (total_difficulty, fork_block) = match value.split_once('@') {
+                    Some((ttd, block)) => (ttd, Some(block.trim().parse().map_err(|_| invalid())?)),
+                    None => (value, None),
+                };
+                let total_difficulty = total_difficulty.trim().parse().map_err(|_| invalid())?;
+                Ok(Self::Ttd { total_difficulty, fork_block })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for ForkActivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block(block) => write!(f, "block:{block}"),
+            Self::Timestamp(timestamp) => write!(f, "timestamp:{timestamp}"),
+            Self::Ttd { total_difficulty, fork_block: Some(block) } => {
+                write!(f, "ttd:{total_difficulty}@{block}")
+            }
+            Self::Ttd { total_difficulty, fork_block: None } => write!(f, "ttd:{total_difficulty}"),
+            Self::Never => f.write_str("never"),
+        }
+    }
+}
+
+impl Serialize for ForkActivation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ForkActivation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Errors that can occur when converting a [`ChainDefinition`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ChainDefinitionError {
+    /// The fork activation expression could not be parsed.
+    #[error("invalid fork activation expression: {0}")]
+    InvalidForkActivation(String),
+    /// The hardfork is not known.
+    #[error("unknown hardfork: {0}")]
+    UnknownFork(String),
+    /// The activation kind is not supported by the hardfork.
+    #[error("hardfork {fork} can't be activated by {activation}")]
+    UnsupportedActivation {
+        /// The hardfork name.
+        fork: String,
+        /// The activation expression.
+        activation: ForkActivation,
+    },
+}
+
+/// Applies the fork activation to the matching field of the [`ChainConfig`].
+fn apply_fork_activation(
+    config: &mut ChainConfig,
+    fork: &str,
+    activation: ForkActivation,
+) -> Result<(), ChainDefinitionError> {
+    let unsupported =
+        || ChainDefinitionError::UnsupportedActivation { fork: fork.into(), activation };
+
+    // blob parameter only forks are not part of the hardfork enum
+    let bpo_time = match fork.to_lowercase().as_str() {
+        "bpo1" => Some(&mut config.bpo1_time),
+        "bpo2" => Some(&mut config.bpo2_time),
+        "bpo3" => Some(&mut config.bpo3_time),
+        "bpo4" => Some(&mut config.bpo4_time),
+        "bpo5" => Some(&mut config.bpo5_time),
+        _ => None,
+    };
+    if let Some(field) = bpo_time {
+        *field = match activation {
+            ForkActivation::Timestamp(timestamp) => Some(timestamp),
+            ForkActivation::Never => None,
+            _ => return Err(unsupported()),
+        };
+        return Ok(())
+    }
+
+    let hardfork = EthereumHardfork::from_str(fork)
+        .map_err(|_| ChainDefinitionError::UnknownFork(fork.into()))?;
+
+    if hardfork == EthereumHardfork::Paris {
+        match activation {
+            ForkActivation::Ttd { total_difficulty, fork_block } => {
+                config.terminal_total_difficulty = Some(total_difficulty);
+                config.terminal_total_difficulty_passed = true;
+                config.merge_netsplit_block = fork_block;
+            }
+            ForkActivation::Never => {
+                config.terminal_total_difficulty = None;
+                config.merge_netsplit_block = None;
+            }
+            _ => return Err(unsupported()),
+        }
+        return Ok(())
+    }
+
+    let time_field = match hardfork {
+        EthereumHardfork::Shanghai => Some(&mut config.shanghai_time),
+        EthereumHardfork::Cancun => Some(&mut config.cancun_time),
+        EthereumHardfork::Prague => Some(&mut config.prague_time),
+        EthereumHardfork::Osaka => Some(&mut config.osaka_time),
+        _ => None,
+    };
+    if let Some(field) = time_field {
+        *field = match activation {
+            ForkActivation::Timestamp(timestamp) => Some(timestamp),
+            ForkActivation::Never => None,
+            _ => return Err(unsupported()),
+        };
+        return Ok(())
+    }
+
+    let block = match activation {
+        ForkActivation::Block(block) => Some(block),
+        ForkActivation::Never => None,
+        _ => return Err(unsupported()),
+    };
+    match hardfork {
+        // frontier is always active at genesis
+        EthereumHardfork::Frontier if block == Some(0) => {}
+        EthereumHardfork::Homestead => config.homestead_block = block,
+        EthereumHardfork::Dao => {
+            config.dao_fork_block = block;
+            config.dao_fork_support = block.is_some();
+        }
+        EthereumHardfork::Tangerine => config.eip150_block = block,
+        EthereumHardfork::SpuriousDragon => {
+            config.eip155_block = block;
+            config.eip158_block = block;
+        }
+        EthereumHardfork::Byzantium => config.byzantium_block = block,
+        EthereumHardfork::Constantinople => config.constantinople_block = block,
+        EthereumHardfork::Petersburg => config.petersburg_block = block,
+        EthereumHardfork::Istanbul => config.istanbul_block = block,
+        EthereumHardfork::MuirGlacier => config.muir_glacier_block = block,
+        EthereumHardfork::Berlin => config.berlin_block = block,
+        EthereumHardfork::London => config.london_block = block,
+        EthereumHardfork::ArrowGlacier => config.arrow_glacier_block = block,
+        EthereumHardfork::GrayGlacier => config.gray_glacier_block = block,
+        _ => return Err(unsupported()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_ethereum_forks::{EthereumHardforks, ForkCondition};
+
+    #[test]
+    fn parse_fork_activation() {
+        assert_eq!("block:10".parse::<ForkActivation>().unwrap(), ForkActivation::Block(10));
+        assert_eq!(
+            "timestamp:1700000000".parse::<ForkActivation>().unwrap(),
+            ForkActivation::Timestamp(1700000000)
+        );
+        assert_eq!("time:5".parse::<ForkActivation>().unwrap(), ForkActivation::Timestamp(5));
+        assert_eq!(
+            "ttd:0x10@5".parse::<ForkActivation>().unwrap(),
+            ForkActivation::Ttd { total_difficulty: U256::from(16), fork_block: Some(5) }
+        );
+        assert_eq!("never".parse::<ForkActivation>().unwrap(), ForkActivation::Never);
+        assert!("block".parse::<ForkActivation>().is_err());
+        assert!("epoch:1".parse::<ForkActivation>().is_err());
+
+        for expr in ["block:10", "timestamp:5", "ttd:0@1", "ttd:17", "never"] {
+            assert_eq!(expr.parse::<ForkActivation>().unwrap().to_string(), expr);
+        }
+    }
+
+    #[test]
+    fn chain_definition_into_chain_spec() {
+        let s = r#"{
+            "genesis": {
+                "config": { "chainId": 1337 },
+                "alloc": {},
+                "difficulty": "0x0",
+                "gasLimit": "0x1c9c380"
+            },
+            "forks": {
+                "homestead": "block:0",
+                "london": "block:0",
+                "paris": "ttd:0@0",
+                "shanghai": "timestamp:0",
+                "Cancun": "timestamp:100"
+            },
+            "deposit_contract": {
+                "address": "0x4242424242424242424242424242424242424242",
+                "block": 5
+            },
+            "blob_schedule": {
+                "cancun": { "target": 4, "max": 8, "baseFeeUpdateFraction": 3338477 }
+            }
+        }"#;
+        let definition: ChainDefinition = serde_json::from_str(s).unwrap();
+        let spec = ChainSpec::try_from(definition).unwrap();
+
+        assert_eq!(spec.chain().id(), 1337);
+        assert_eq!(spec.fork(EthereumHardfork::London), ForkCondition::Block(0));
+        assert!(spec.is_shanghai_active_at_timestamp(0));
+        assert_eq!(spec.fork(EthereumHardfork::Cancun), ForkCondition::Timestamp(100));
+        assert_eq!(spec.blob_params.cancun.max_blob_count, 8);
+        assert_eq!(
+            spec.deposit_contract,
+            Some(DepositContract::new(
+                address!("0x4242424242424242424242424242424242424242"),
+                5,
+                MAINNET_DEPOSIT_CONTRACT.topic
+            ))
+        );
+    }
+
+    #[test]
+    fn unsupported_fork_activation() {
+        let definition = ChainDefinition::new(Genesis::default())
+            .with_fork("london", ForkActivation::Timestamp(1));
+        assert_eq!(
+            definition.into_genesis(),
+            Err(ChainDefinitionError::UnsupportedActivation {
+                fork: "london".into(),
+                activation: ForkActivation::Timestamp(1)
+            })
+        );
+
+        let definition =
+            ChainDefinition::new(Genesis::default()).with_fork("unknown", ForkActivation::Never);
+        assert_eq!(
+            definition.into_genesis(),
+            Err(ChainDefinitionError::UnknownFork("unknown".into()))
+        );
+    }
+}
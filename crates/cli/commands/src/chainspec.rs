@@ -0,0 +1,37 @@
+//! Helpers to load chain specifications from disk.
+
+use reth_chainspec::ChainDefinition;
+use reth_node_core::utils::parse_path;
+use std::{fs, path::Path};
+
+/// A helper to parse a [`ChainDefinition`] from disk.
+///
+/// The definition file is parsed as TOML if it has a `.toml` extension and as JSON otherwise. The
+/// `genesis` field of the definition can either be an inline genesis object or the path to a
+/// genesis json file, relative to the definition file.
+///
+/// Returns `Ok(None)` if the argument is not a path to a chain definition file, e.g. if it's a
+/// plain genesis json file, so that it can be handled by
+/// [`parse_genesis`](reth_cli::chainspec::parse_genesis) instead.
+pub fn parse_chain_definition(s: &str) -> eyre::Result<Option<ChainDefinition>> {
+    let path = parse_path(s)?;
+    let Ok(raw) = fs::read_to_string(&path) else { return Ok(None) };
+
+    let mut definition: serde_json::Value =
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")) {
+            toml::from_str(&raw)?
+        } else {
+            match serde_json::from_str(&raw) {
+                Ok(value) => value,
+                Err(_) => return Ok(None),
+            }
+        };
+
+    let Some(genesis) = definition.get_mut("genesis") else { return Ok(None) };
+    if let Some(genesis_path) = genesis.as_str() {
+        let genesis_path = path.parent().unwrap_or_else(|| Path::new("")).join(genesis_path);
+        *genesis = serde_json::from_str(&fs::read_to_string(genesis_path)?)?;
+    }
+
+    Ok(Some(serde_json::from_value(definition)?))
+}
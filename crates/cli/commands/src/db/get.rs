@@ -3,11 +3,13 @@ use alloy_primitives::{hex, BlockHash};
 use clap::Parser;
 use reth_db::{
     static_file::{
-        ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+        AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask,
+        ReceiptMask, StorageChangeSetMask, TransactionMask,
     },
     RawDupSort,
 };
 use reth_db_api::{
+    models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
     table::{Decompress, DupSort, Table},
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
@@ -75,6 +77,12 @@ impl Command {
                     StaticFileSegment::Receipts => {
                         (table_key::<tables::Receipts>(&key)?, <ReceiptMask<ReceiptTy<N>>>::MASK)
                     }
+                    StaticFileSegment::AccountChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, AccountChangeSetMask::MASK)
+                    }
+                    StaticFileSegment::StorageChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, StorageChangeSetMask::MASK)
+                    }
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -116,6 +124,18 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let changeset = StaticFileAccountChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let changeset = StaticFileStorageChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
                             }
                         }
                     }
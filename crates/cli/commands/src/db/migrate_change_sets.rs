@@ -0,0 +1,153 @@
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::BlockNumberAddress,
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileWriter},
+    DBProvider, DatabaseProviderFactory, DatabaseProviderRO, ProviderFactory,
+    PruneCheckpointWriter, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_prune::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages::StageId;
+use reth_static_file::segments::{self, Segment};
+use std::ops::RangeBounds;
+use tracing::info;
+
+/// Number of blocks whose changesets are deleted from the database in a single transaction.
+const DELETE_BATCH_BLOCKS: u64 = 10_000;
+
+/// The arguments for the `reth db migrate-change-sets` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Keep the migrated changesets in the database, so they're deleted by the pruner later on.
+    #[arg(long)]
+    keep_database: bool,
+}
+
+impl Command {
+    /// Execute `db migrate-change-sets` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        // Changesets above the finish checkpoint may still be read from the database by the
+        // hashing and history index stages.
+        let to_block = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Finish)?
+            .map(|checkpoint| checkpoint.block_number)
+            .ok_or_else(|| eyre::eyre!("Finish stage checkpoint not found, nothing to migrate"))?;
+
+        copy_to_static_files(&provider_factory, segments::AccountChangeSets, to_block)?;
+        copy_to_static_files(&provider_factory, segments::StorageChangeSets, to_block)?;
+
+        if self.keep_database {
+            info!(target: "reth::cli", "Migrated changesets are kept in the database");
+            return Ok(())
+        }
+
+        // Deleting all changesets in a single transaction would make it grow unbounded, so the
+        // rows are deleted in batches of blocks, each committed on its own.
+        let Some(from_block) = first_change_set_block(&provider_factory)? else {
+            info!(target: "reth::cli", "No changesets left in the database");
+            return Ok(())
+        };
+        let (mut deleted_accounts, mut deleted_storages) = (0, 0);
+        for batch_start in (from_block..=to_block).step_by(DELETE_BATCH_BLOCKS as usize) {
+            let batch_end = (batch_start + DELETE_BATCH_BLOCKS - 1).min(to_block);
+
+            let provider_rw = provider_factory.database_provider_rw()?;
+            deleted_accounts += delete_rows::<tables::AccountChangeSets, _>(
+                provider_rw.tx_ref(),
+                batch_start..=batch_end,
+            )?;
+            deleted_storages += delete_rows::<tables::StorageChangeSets, _>(
+                provider_rw.tx_ref(),
+                BlockNumberAddress::range(batch_start..=batch_end),
+            )?;
+
+            // The pruner doesn't have to go through the deleted rows again.
+            let checkpoint = PruneCheckpoint {
+                block_number: Some(batch_end),
+                tx_number: None,
+                prune_mode: PruneMode::before_inclusive(batch_end),
+            };
+            provider_rw.save_prune_checkpoint(PruneSegment::AccountChangeSets, checkpoint)?;
+            provider_rw.save_prune_checkpoint(PruneSegment::StorageChangeSets, checkpoint)?;
+            provider_rw.commit()?;
+
+            info!(target: "reth::cli", batch_end, to_block, deleted_accounts, deleted_storages, "Deleted migrated changesets from the database");
+        }
+
+        Ok(())
+    }
+}
+
+/// Copies the changesets of the segment up to `to_block` to static files, continuing from the
+/// highest block that is already in static files.
+fn copy_to_static_files<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    segment: impl Segment<DatabaseProviderRO<N::DB, N>>,
+    to_block: BlockNumber,
+) -> eyre::Result<()> {
+    let static_file_provider = provider_factory.static_file_provider();
+    let from_block = static_file_provider
+        .get_highest_static_file_block(segment.segment())
+        .map_or(0, |block| block + 1);
+    if from_block > to_block {
+        info!(target: "reth::cli", segment = %segment.segment(), "Changesets are already migrated");
+        return Ok(())
+    }
+
+    info!(target: "reth::cli", segment = %segment.segment(), from_block, to_block, "Migrating changesets to static files");
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    segment.copy_to_static_files(provider, from_block..=to_block)?;
+    static_file_provider.latest_writer(segment.segment())?.commit()?;
+
+    Ok(())
+}
+
+/// Returns the lowest block that still has account or storage changesets in the database.
+fn first_change_set_block<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+) -> eyre::Result<Option<BlockNumber>> {
+    let provider = provider_factory.provider()?;
+    let first_account = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?.first()?;
+    let first_storage = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?.first()?;
+    Ok(first_account
+        .map(|(block_number, _)| block_number)
+        .into_iter()
+        .chain(first_storage.map(|(key, _)| key.block_number()))
+        .min())
+}
+
+/// Deletes all rows of the table in the given key range, returning the number of deleted rows.
+fn delete_rows<T: Table, TX: DbTx + DbTxMut>(
+    tx: &TX,
+    range: impl RangeBounds<T::Key>,
+) -> eyre::Result<usize> {
+    let mut cursor = tx.cursor_write::<T>()?;
+    let mut walker = cursor.walk_range(range)?;
+    let mut deleted = 0;
+    while walker.next().transpose()?.is_some() {
+        walker.delete_current()?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_migrate_change_sets() {
+        assert!(!Command::try_parse_from(["reth"]).unwrap().keep_database);
+        assert!(Command::try_parse_from(["reth", "--keep-database"]).unwrap().keep_database);
+    }
+}
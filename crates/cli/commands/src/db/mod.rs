@@ -13,9 +13,13 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod migrate_change_sets;
+mod repair;
 mod stats;
 /// DB List TUI
 mod tui;
+mod unwind_table;
+mod verify;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -48,6 +52,14 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Unwinds a single table to the given block without unwinding the stages it depends on
+    UnwindTable(unwind_table::Command),
+    /// Verifies the static files against their checksum manifests
+    Verify(verify::Command),
+    /// Moves the account and storage changesets from the database to static files
+    MigrateChangeSets(migrate_change_sets::Command),
+    /// Detects and repairs known classes of database inconsistencies
+    Repair(repair::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -135,6 +147,23 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::UnwindTable(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Verify(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::MigrateChangeSets(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Repair(command) => {
+                let Environment { provider_factory, .. } =
+                    self.env.init::<N>(command.access_rights())?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,377 @@
+use crate::common::AccessRights;
+use alloy_primitives::{BlockNumber, TxNumber};
+use clap::{Parser, ValueEnum};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::StoredBlockBodyIndices,
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, DatabaseProviderFactory, ProviderFactory,
+    StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::{info, warn};
+
+/// The arguments for the `reth db repair` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The inconsistency to detect and repair.
+    #[arg(long, value_enum)]
+    issue: Issue,
+
+    /// Only report the detected inconsistencies, without repairing them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// A known class of database inconsistencies that can be repaired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Issue {
+    /// Entries of the `TransactionBlocks` table that are missing or don't match the
+    /// `BlockBodyIndices` table, which breaks looking up the block of a transaction.
+    TxIndexGap,
+    /// Entries of the `BlockBodyIndices` table whose transaction ranges are not contiguous, or
+    /// don't end at the highest transaction in static files.
+    BodyIndicesMismatch,
+}
+
+impl Command {
+    /// Returns the access rights to the database the command requires, a dry run only reads it.
+    pub const fn access_rights(&self) -> AccessRights {
+        if self.dry_run {
+            AccessRights::RO
+        } else {
+            AccessRights::RW
+        }
+    }
+
+    /// Execute `db repair` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let highest_tx = provider_factory
+            .static_file_provider()
+            .get_highest_static_file_tx(StaticFileSegment::Transactions);
+
+        if self.dry_run {
+            let provider = provider_factory.database_provider_ro()?;
+            if self.find_repair(provider.tx_ref(), highest_tx)?.is_some() {
+                info!(target: "reth::cli", "Dry run, the database was not repaired");
+            }
+            return Ok(())
+        }
+
+        let provider_rw = provider_factory.database_provider_rw()?;
+        if let Some(repair) = self.find_repair(provider_rw.tx_ref(), highest_tx)? {
+            repair.apply(provider_rw.tx_ref())?;
+            provider_rw.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Detects the inconsistencies of the issue and returns the repair, if they can be repaired.
+    fn find_repair<TX: DbTx>(
+        &self,
+        tx: &TX,
+        highest_tx: Option<TxNumber>,
+    ) -> eyre::Result<Option<Repair>> {
+        match self.issue {
+            Issue::TxIndexGap => find_tx_index_repair(tx),
+            Issue::BodyIndicesMismatch => find_body_indices_repair(tx, highest_tx),
+        }
+    }
+}
+
+/// The entries to write to repair the detected inconsistencies.
+#[derive(Debug)]
+enum Repair {
+    /// Fixes of the `TransactionBlocks` table.
+    TxIndex(Vec<TxIndexFix>),
+    /// Rewritten entries of the `BlockBodyIndices` table.
+    BodyIndices(Vec<(BlockNumber, StoredBlockBodyIndices)>),
+}
+
+impl Repair {
+    /// Writes the repaired entries.
+    fn apply<TX: DbTxMut>(self, tx: &TX) -> Result<(), DatabaseError> {
+        match self {
+            Self::TxIndex(fixes) => {
+                for fix in fixes {
+                    match fix {
+                        TxIndexFix::Missing { tx: tx_num, block } |
+                        TxIndexFix::Mismatch { tx: tx_num, block, .. } => {
+                            tx.put::<tables::TransactionBlocks>(tx_num, block)?
+                        }
+                        TxIndexFix::Stale { tx: tx_num, .. } => {
+                            tx.delete::<tables::TransactionBlocks>(tx_num, None)?;
+                        }
+                    }
+                }
+                info!(target: "reth::cli", "Repaired the transaction index");
+            }
+            Self::BodyIndices(fixes) => {
+                for (block, indices) in fixes {
+                    tx.put::<tables::BlockBodyIndices>(block, indices)?;
+                }
+                info!(target: "reth::cli", "Repaired the body indices, run `reth db repair --issue tx-index-gap` to repair the transaction index accordingly");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A fix of an entry of the `TransactionBlocks` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TxIndexFix {
+    /// The entry of the last transaction of a block is missing.
+    Missing { tx: TxNumber, block: BlockNumber },
+    /// The entry of the last transaction of a block points to another block.
+    Mismatch { tx: TxNumber, block: BlockNumber, found: BlockNumber },
+    /// The entry doesn't belong to the last transaction of any block.
+    Stale { tx: TxNumber, block: BlockNumber },
+}
+
+/// Detects entries of the `TransactionBlocks` table that don't match the `BlockBodyIndices` table.
+fn find_tx_index_repair<TX: DbTx>(tx: &TX) -> eyre::Result<Option<Repair>> {
+    let mut body_indices_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
+    let mut tx_blocks_cursor = tx.cursor_read::<tables::TransactionBlocks>()?;
+
+    // Every block with transactions has an entry keyed by its last transaction.
+    let expected = body_indices_cursor.walk(None)?.filter_map(|entry| match entry {
+        Ok((_, indices)) if indices.is_empty() => None,
+        Ok((block, indices)) => Some(Ok((indices.last_tx_num(), block))),
+        Err(err) => Some(Err(err)),
+    });
+    let fixes = tx_index_fixes(expected, tx_blocks_cursor.walk(None)?)?;
+
+    if fixes.is_empty() {
+        info!(target: "reth::cli", "No transaction index gaps found");
+        return Ok(None)
+    }
+
+    for fix in &fixes {
+        warn!(target: "reth::cli", ?fix, "Inconsistent transaction index entry");
+    }
+    info!(target: "reth::cli", fixes = fixes.len(), "Found transaction index inconsistencies");
+
+    Ok(Some(Repair::TxIndex(fixes)))
+}
+
+/// Compares the expected entries of the `TransactionBlocks` table with the actual ones, both
+/// ordered by transaction number, and returns the fixes needed to make them equal.
+fn tx_index_fixes(
+    mut expected: impl Iterator<Item = Result<(TxNumber, BlockNumber), DatabaseError>>,
+    mut actual: impl Iterator<Item = Result<(TxNumber, BlockNumber), DatabaseError>>,
+) -> Result<Vec<TxIndexFix>, DatabaseError> {
+    let mut fixes = Vec::new();
+    let mut next_expected = expected.next().transpose()?;
+    let mut next_actual = actual.next().transpose()?;
+
+    loop {
+        match (next_expected, next_actual) {
+            (None, None) => break,
+            (Some((tx, block)), next_actual)
+                if next_actual.is_none_or(|(actual_tx, _)| tx < actual_tx) =>
+            {
+                fixes.push(TxIndexFix::Missing { tx, block });
+                next_expected = expected.next().transpose()?;
+            }
+            (next_expected, Some((tx, block)))
+                if next_expected.is_none_or(|(expected_tx, _)| tx < expected_tx) =>
+            {
+                fixes.push(TxIndexFix::Stale { tx, block });
+                next_actual = actual.next().transpose()?;
+            }
+            (Some((tx, block)), Some((_, found))) => {
+                if block != found {
+                    fixes.push(TxIndexFix::Mismatch { tx, block, found });
+                }
+                next_expected = expected.next().transpose()?;
+                next_actual = actual.next().transpose()?;
+            }
+            (Some(_), None) | (None, Some(_)) => unreachable!("covered by the guards above"),
+        }
+    }
+
+    Ok(fixes)
+}
+
+/// Detects entries of the `BlockBodyIndices` table whose transaction ranges are not contiguous.
+///
+/// The transaction counts of the blocks are trusted, so the indices are only repaired if the
+/// recomputed ranges end at the highest transaction in static files. Otherwise, the last
+/// consistent block is reported, so that the node can be unwound to it.
+fn find_body_indices_repair<TX: DbTx>(
+    tx: &TX,
+    highest_tx: Option<TxNumber>,
+) -> eyre::Result<Option<Repair>> {
+    let mut body_indices_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
+    let check =
+        check_body_indices(body_indices_cursor.walk(None)?, highest_tx.map_or(0, |tx| tx + 1))?;
+
+    match check {
+        BodyIndicesCheck::Consistent => {
+            info!(target: "reth::cli", "No body indices mismatches found");
+            Ok(None)
+        }
+        BodyIndicesCheck::Repairable { fixes } => {
+            let (from_block, _) = fixes[0];
+            let (to_block, _) = fixes[fixes.len() - 1];
+            warn!(target: "reth::cli", from_block, to_block, fixes = fixes.len(), "Found misaligned body indices, the transaction counts match the transactions in static files");
+            Ok(Some(Repair::BodyIndices(fixes)))
+        }
+        BodyIndicesCheck::Unrepairable { last_consistent_block } => {
+            warn!(target: "reth::cli", ?last_consistent_block, "Found body indices mismatches that can't be repaired in place");
+            match last_consistent_block {
+                Some(block) => {
+                    info!(target: "reth::cli", "Unwind the node to the last consistent block with `reth stage unwind to-block {block}`")
+                }
+                None => {
+                    info!(target: "reth::cli", "No consistent block found, the node has to be re-synced")
+                }
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Result of [`check_body_indices`].
+#[derive(Debug, PartialEq, Eq)]
+enum BodyIndicesCheck {
+    /// The body indices are contiguous and end at the highest transaction.
+    Consistent,
+    /// The body indices of the blocks need to be rewritten with the given ones.
+    Repairable { fixes: Vec<(BlockNumber, StoredBlockBodyIndices)> },
+    /// The transaction counts don't match the transactions, or blocks are missing.
+    Unrepairable { last_consistent_block: Option<BlockNumber> },
+}
+
+/// Checks that the body indices, ordered by block number, are contiguous and end at `next_tx`.
+fn check_body_indices<I>(
+    body_indices: I,
+    next_tx: TxNumber,
+) -> Result<BodyIndicesCheck, DatabaseError>
+where
+    I: Iterator<Item = Result<(BlockNumber, StoredBlockBodyIndices), DatabaseError>>,
+{
+    let mut fixes = Vec::new();
+    let mut missing_block = false;
+    // The last block before the first inconsistency whose transactions are all present.
+    let mut last_consistent_block = None;
+    let mut consistent = true;
+    // The expected block number and first transaction of the next entry.
+    let mut expected = None;
+    for entry in body_indices {
+        let (block, indices) = entry?;
+        let (expected_block, expected_tx) = expected.unwrap_or((block, indices.first_tx_num));
+        if block != expected_block {
+            // Indices of a block are missing, so its transaction count is unknown.
+            missing_block = true;
+            break
+        }
+
+        if indices.first_tx_num != expected_tx {
+            fixes.push((
+                block,
+                StoredBlockBodyIndices { first_tx_num: expected_tx, tx_count: indices.tx_count },
+            ));
+        }
+        consistent &= fixes.is_empty() && indices.next_tx_num() <= next_tx;
+        if consistent {
+            last_consistent_block = Some(block);
+        }
+        expected = Some((block + 1, expected_tx + indices.tx_count));
+    }
+
+    let Some((_, expected_next_tx)) = expected else { return Ok(BodyIndicesCheck::Consistent) };
+    if !missing_block && expected_next_tx == next_tx {
+        return Ok(if fixes.is_empty() {
+            BodyIndicesCheck::Consistent
+        } else {
+            BodyIndicesCheck::Repairable { fixes }
+        })
+    }
+
+    Ok(BodyIndicesCheck::Unrepairable { last_consistent_block })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(first_tx_num: TxNumber, tx_count: u64) -> StoredBlockBodyIndices {
+        StoredBlockBodyIndices { first_tx_num, tx_count }
+    }
+
+    #[test]
+    fn parse_repair() {
+        let cmd = Command::try_parse_from(["reth", "--issue", "tx-index-gap"]).unwrap();
+        assert_eq!(cmd.issue, Issue::TxIndexGap);
+        assert!(!cmd.dry_run);
+
+        let cmd =
+            Command::try_parse_from(["reth", "--issue", "body-indices-mismatch", "--dry-run"])
+                .unwrap();
+        assert_eq!(cmd.issue, Issue::BodyIndicesMismatch);
+        assert!(cmd.dry_run);
+
+        assert!(Command::try_parse_from(["reth"]).is_err());
+    }
+
+    #[test]
+    fn detect_tx_index_fixes() {
+        let expected = [(1, 0), (4, 2), (6, 3), (9, 5)];
+        let actual = [(1, 0), (5, 2), (6, 4), (9, 5), (12, 6)];
+        let fixes = tx_index_fixes(expected.into_iter().map(Ok), actual.into_iter().map(Ok));
+        assert_eq!(
+            fixes.unwrap(),
+            vec![
+                TxIndexFix::Missing { tx: 4, block: 2 },
+                TxIndexFix::Stale { tx: 5, block: 2 },
+                TxIndexFix::Mismatch { tx: 6, block: 3, found: 4 },
+                TxIndexFix::Stale { tx: 12, block: 6 },
+            ]
+        );
+
+        let fixes = tx_index_fixes(expected.into_iter().map(Ok), expected.into_iter().map(Ok));
+        assert!(fixes.unwrap().is_empty());
+    }
+
+    #[test]
+    fn check_body_indices_consistency() {
+        let consistent = [(0, indices(0, 2)), (1, indices(2, 0)), (2, indices(2, 3))];
+        assert_eq!(
+            check_body_indices(consistent.into_iter().map(Ok), 5).unwrap(),
+            BodyIndicesCheck::Consistent
+        );
+
+        // The transaction counts add up to the transactions, so the ranges can be rewritten.
+        let misaligned = [(0, indices(0, 2)), (1, indices(3, 1)), (2, indices(4, 2))];
+        assert_eq!(
+            check_body_indices(misaligned.into_iter().map(Ok), 5).unwrap(),
+            BodyIndicesCheck::Repairable { fixes: vec![(1, indices(2, 1)), (2, indices(3, 2))] }
+        );
+
+        // The transaction counts don't add up to the transactions.
+        assert_eq!(
+            check_body_indices(misaligned.into_iter().map(Ok), 6).unwrap(),
+            BodyIndicesCheck::Unrepairable { last_consistent_block: Some(0) }
+        );
+        assert_eq!(
+            check_body_indices(consistent.into_iter().map(Ok), 4).unwrap(),
+            BodyIndicesCheck::Unrepairable { last_consistent_block: Some(1) }
+        );
+
+        // Indices of block 1 are missing.
+        let missing = [(0, indices(0, 2)), (2, indices(2, 3))];
+        assert_eq!(
+            check_body_indices(missing.into_iter().map(Ok), 5).unwrap(),
+            BodyIndicesCheck::Unrepairable { last_consistent_block: Some(0) }
+        );
+    }
+}
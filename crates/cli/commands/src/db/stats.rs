@@ -1,19 +1,26 @@
 use crate::{common::CliNodeTypes, db::checksum::ChecksumViewer};
 use clap::Parser;
 use comfy_table::{Cell, Row, Table as ComfyTable};
-use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
-use reth_db_api::{database::Database, TableViewer, Tables};
+use reth_cli_util::parse_duration_from_secs;
+use reth_db::{static_file::iter_static_files, DatabaseEnv};
+use reth_db_api::{
+    stats::{StorageStats, StorageStatsHistory},
+    TableViewer, Tables,
+};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
 use reth_node_builder::{NodePrimitives, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::dirs::{ChainPath, DataDirPath};
-use reth_provider::providers::{ProviderNodeTypes, StaticFileProvider};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    StorageStatsProvider,
+};
 use reth_static_file_types::SegmentRangeInclusive;
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
+use tracing::warn;
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db stats` command
@@ -34,6 +41,19 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Keep running and print the database table statistics every given number of seconds.
+    ///
+    /// Every run of this command records a sample in `db-stats-history.json` in the data
+    /// directory, which is used to compute the daily growth rate of each table.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = parse_duration_from_secs,
+        num_args = 0..=1,
+        default_missing_value = "60"
+    )]
+    watch: Option<Duration>,
 }
 
 impl Command {
@@ -49,19 +69,32 @@ impl Command {
             println!("\n");
         }
 
-        let static_files_stats_table = self.static_files_stats_table::<N::Primitives>(data_dir)?;
+        let static_files_stats_table =
+            self.static_files_stats_table::<N::Primitives>(data_dir.clone())?;
         println!("{static_files_stats_table}");
 
         println!("\n");
 
-        let db_stats_table = self.db_stats_table(tool)?;
+        let db_stats_table = self.db_stats_table(&data_dir, tool)?;
         println!("{db_stats_table}");
 
+        if let Some(interval) = self.watch {
+            loop {
+                std::thread::sleep(interval);
+
+                println!("\n");
+
+                let db_stats_table = self.db_stats_table(&data_dir, tool)?;
+                println!("{db_stats_table}");
+            }
+        }
+
         Ok(())
     }
 
     fn db_stats_table<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
         &self,
+        data_dir: &ChainPath<DataDirPath>,
         tool: &DbTool<N>,
     ) -> eyre::Result<ComfyTable> {
         let mut table = ComfyTable::new();
@@ -73,72 +106,58 @@ impl Command {
             "Leaf Pages",
             "Overflow Pages",
             "Total Size",
+            "Entries / Day",
+            "Size / Day",
         ]);
 
-        tool.provider_factory.db_ref().view(|tx| {
-            let mut db_tables = Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
-            db_tables.sort();
-            let mut total_size = 0;
-            for db_table in db_tables {
-                let table_db = tx.inner.open_db(Some(db_table)).wrap_err("Could not open db.")?;
-
-                let stats = tx
-                    .inner
-                    .db_stat(&table_db)
-                    .wrap_err(format!("Could not find table: {db_table}"))?;
-
-                // Defaults to 16KB right now but we should
-                // re-evaluate depending on the DB we end up using
-                // (e.g. REDB does not have these options as configurable intentionally)
-                let page_size = stats.page_size() as usize;
-                let leaf_pages = stats.leaf_pages();
-                let branch_pages = stats.branch_pages();
-                let overflow_pages = stats.overflow_pages();
-                let num_pages = leaf_pages + branch_pages + overflow_pages;
-                let table_size = page_size * num_pages;
-
-                total_size += table_size;
-                let mut row = Row::new();
-                row.add_cell(Cell::new(db_table))
-                    .add_cell(Cell::new(stats.entries()))
-                    .add_cell(Cell::new(branch_pages))
-                    .add_cell(Cell::new(leaf_pages))
-                    .add_cell(Cell::new(overflow_pages))
-                    .add_cell(Cell::new(human_bytes(table_size as f64)));
-                table.add_row(row);
-            }
+        let stats = tool.provider_factory.storage_stats()?;
+        let history = record_stats_history(&data_dir.db_stats_history(), stats.clone());
 
-            let max_widths = table.column_max_content_widths();
-            let mut separator = Row::new();
-            for width in max_widths {
-                separator.add_cell(Cell::new("-".repeat(width as usize)));
-            }
-            table.add_row(separator);
+        for db_table in stats.tables.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+            let growth = history.daily_growth(&db_table.name);
 
             let mut row = Row::new();
-            row.add_cell(Cell::new("Tables"))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(human_bytes(total_size as f64)));
+            row.add_cell(Cell::new(&db_table.name))
+                .add_cell(Cell::new(db_table.entries))
+                .add_cell(Cell::new(db_table.branch_pages))
+                .add_cell(Cell::new(db_table.leaf_pages))
+                .add_cell(Cell::new(db_table.overflow_pages))
+                .add_cell(Cell::new(human_bytes(db_table.size() as f64)))
+                .add_cell(Cell::new(
+                    growth.map_or("N/A".to_string(), |growth| {
+                        format!("{:+.0}", growth.entries_per_day)
+                    }),
+                ))
+                .add_cell(Cell::new(growth.map_or("N/A".to_string(), |growth| {
+                    format_growth_bytes(growth.bytes_per_day)
+                })));
             table.add_row(row);
+        }
 
-            let freelist = tx.inner.env().freelist()?;
-            let pagesize = tx.inner.db_stat(&mdbx::Database::freelist_db())?.page_size() as usize;
-            let freelist_size = freelist * pagesize;
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
 
-            let mut row = Row::new();
-            row.add_cell(Cell::new("Freelist"))
-                .add_cell(Cell::new(freelist))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(human_bytes(freelist_size as f64)));
-            table.add_row(row);
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Tables"))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(stats.tables_size() as f64)));
+        table.add_row(row);
 
-            Ok::<(), eyre::Report>(())
-        })??;
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Freelist"))
+            .add_cell(Cell::new(stats.freelist_pages))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(stats.freelist_size() as f64)));
+        table.add_row(row);
 
         Ok(table)
     }
@@ -366,3 +385,32 @@ impl Command {
         Ok(table)
     }
 }
+
+/// Records the given statistics in the history file at `path` and returns the updated history.
+///
+/// Failing to read or write the history file is not fatal, growth rates are then only computed
+/// from the samples that are available.
+fn record_stats_history(path: &Path, stats: StorageStats) -> StorageStatsHistory {
+    let mut history = if path.exists() {
+        fs::read_json_file(path).unwrap_or_else(|err| {
+            warn!(target: "reth::cli", %err, "Failed to read database stats history, starting a new one");
+            StorageStatsHistory::default()
+        })
+    } else {
+        StorageStatsHistory::default()
+    };
+
+    history.record(stats);
+
+    if let Err(err) = fs::write_json_file(path, &history) {
+        warn!(target: "reth::cli", %err, "Failed to persist database stats history");
+    }
+
+    history
+}
+
+/// Formats a signed daily growth in bytes.
+fn format_growth_bytes(bytes_per_day: f64) -> String {
+    let sign = if bytes_per_day < 0.0 { "-" } else { "+" };
+    format!("{sign}{}", human_bytes(bytes_per_day.abs()))
+}
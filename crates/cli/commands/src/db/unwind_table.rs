@@ -0,0 +1,71 @@
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_db_api::Tables;
+use reth_provider::{
+    providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter,
+};
+use reth_stages::{stages::UnwindTable, StageCheckpoint};
+use tracing::info;
+
+/// The arguments for the `reth db unwind-table` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table to unwind.
+    ///
+    /// Only tables that are derived by a single stage from data that is kept on unwind are
+    /// supported: `AccountsHistory`, `StoragesHistory`, `HashedAccounts`, `HashedStorages`,
+    /// `TransactionSenders`, `TransactionHashNumbers`, `SenderTransactions` and
+    /// `ContractDeployments`.
+    table: Tables,
+
+    /// The block number to unwind the table to.
+    #[arg(long)]
+    to: BlockNumber,
+}
+
+impl Command {
+    /// Execute `db unwind-table` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let table = UnwindTable::try_from(self.table)
+            .map_err(|table| eyre::eyre!("Table {table} can not be unwound on its own"))?;
+        let stage_id = table.stage_id();
+
+        let provider_rw = provider_factory.database_provider_rw()?;
+        let checkpoint = provider_rw.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        eyre::ensure!(
+            checkpoint.block_number > self.to,
+            "Stage {stage_id} is at block {}, nothing to unwind",
+            checkpoint.block_number
+        );
+
+        info!(target: "reth::cli", table = %self.table, from = checkpoint.block_number, to = self.to, "Unwinding table");
+        table.unwind(&provider_rw, self.to + 1..=checkpoint.block_number)?;
+
+        // Reset the checkpoint of the stage that populates the table, so that the next pipeline
+        // run rebuilds the unwound entries.
+        provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(self.to))?;
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", %stage_id, checkpoint = self.to, "Unwound table, stage checkpoint was reset");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unwind_table() {
+        let cmd = Command::try_parse_from(["reth", "AccountsHistory", "--to", "100"]).unwrap();
+        assert_eq!(cmd.table, Tables::AccountsHistory);
+        assert_eq!(cmd.to, 100);
+
+        assert!(Command::try_parse_from(["reth", "AccountsHistory"]).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use clap::Parser;
+use reth_nippy_jar::NippyJarError;
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::{ProviderFactory, StaticFileProviderFactory};
+use tracing::{error, info, warn};
+
+/// The arguments for the `reth db verify` command
+#[derive(Parser, Debug)]
+pub struct Command;
+
+impl Command {
+    /// Execute `db verify` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let mut verified = 0;
+        let mut missing = 0;
+        let mut corrupted = 0;
+
+        for (path, result) in provider_factory.static_file_provider().verify_checksums()? {
+            match result {
+                Ok(()) => {
+                    info!(path = %path.display(), "Verified static file");
+                    verified += 1;
+                }
+                Err(NippyJarError::MissingChecksums(_)) => {
+                    warn!(path = %path.display(), "Static file has no checksum manifest");
+                    missing += 1;
+                }
+                Err(err) => {
+                    error!(path = %path.display(), %err, "Static file verification failed");
+                    corrupted += 1;
+                }
+            }
+        }
+
+        info!(verified, missing, corrupted, "Finished verifying static files");
+
+        if corrupted > 0 {
+            eyre::bail!("{corrupted} static files failed verification")
+        }
+
+        Ok(())
+    }
+}
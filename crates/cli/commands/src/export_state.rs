@@ -0,0 +1,131 @@
+//! Command exporting the state at a block to a state dump file.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db_common::export::{export_state_dump, read_state_dump_progress};
+use reth_provider::{BlockNumReader, DatabaseProviderFactory, HeaderProvider, ProviderError};
+use std::{
+    fs::OpenOptions,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Exports the state at a block to a state dump file.
+#[derive(Debug, Parser)]
+pub struct ExportStateCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// JSONL file to write the state dump to.
+    ///
+    /// The first line is { "root": \<state-root\> }, every following line is an account with
+    /// its balance, nonce, code and storage, ordered by address. This is the format read by the
+    /// 'init-state' command.
+    #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
+    state: PathBuf,
+
+    /// The block to export the state at.
+    ///
+    /// Defaults to the latest block. The state of an earlier block is reconstructed from the
+    /// changesets, which must not be pruned.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    at: Option<BlockNumber>,
+
+    /// Resume an interrupted export to the same file.
+    ///
+    /// Accounts that are already completely written to the file are kept, a partially written
+    /// account is discarded.
+    #[arg(long)]
+    resume: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportStateCommand<C> {
+    /// Execute `export-state` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        // the export walks the whole state in a single transaction
+        let provider =
+            provider_factory.database_provider_ro()?.disable_long_read_transaction_safety();
+
+        let block = match self.at {
+            Some(block) => block,
+            None => provider.last_block_number()?,
+        };
+        let state_root = provider
+            .header_by_number(block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+            .state_root();
+
+        let resume_after = if self.state.exists() {
+            if !self.resume {
+                return Err(eyre::eyre!(
+                    "State dump file {} already exists, pass --resume to continue the export",
+                    self.state.display()
+                ))
+            }
+
+            let progress =
+                read_state_dump_progress(BufReader::new(reth_fs_util::open(&self.state)?))?;
+            if progress.root.is_some_and(|root| root != state_root) {
+                return Err(eyre::eyre!(
+                    "State dump file {} was not exported at block {block}",
+                    self.state.display()
+                ))
+            }
+
+            // discard the partially written line, or everything if no account was written yet
+            let file = OpenOptions::new().append(true).open(&self.state)?;
+            file.set_len(progress.last_address.map_or(0, |_| progress.len))?;
+            progress.last_address
+        } else {
+            None
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(&self.state)?;
+
+        info!(target: "reth::cli", block, path = %self.state.display(), ?resume_after, "Exporting state");
+
+        let exported = export_state_dump(&provider, block, BufWriter::new(file), resume_after)?;
+
+        info!(target: "reth::cli", block, exported, path = %self.state.display(), "Exported state");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ExportStateCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_state() {
+        let cmd = ExportStateCommand::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "state.jsonl",
+            "--at",
+            "100",
+            "--resume",
+        ])
+        .unwrap();
+        assert_eq!(cmd.state, PathBuf::from("state.jsonl"));
+        assert_eq!(cmd.at, Some(100));
+        assert!(cmd.resume);
+    }
+}
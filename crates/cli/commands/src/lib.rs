@@ -8,12 +8,14 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod chainspec;
 pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod download;
 pub mod dump_genesis;
 pub mod export_era;
+pub mod export_state;
 pub mod import;
 pub mod import_core;
 pub mod import_era;
@@ -11,7 +11,7 @@ use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
         DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, ShutdownArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -69,6 +69,13 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Verify all static files against their checksum manifests before starting the node.
+    ///
+    /// This reads all static files and can take a while. Static files that were created without
+    /// a checksum manifest are skipped.
+    #[arg(long)]
+    pub verify_static_files: bool,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -113,6 +120,10 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All shutdown related arguments with --shutdown prefix
+    #[command(flatten, next_help_heading = "Shutdown")]
+    pub shutdown: ShutdownArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -157,6 +168,7 @@ where
             metrics,
             instance,
             with_unused_ports,
+            verify_static_files,
             network,
             rpc,
             txpool,
@@ -168,6 +180,7 @@ where
             ext,
             engine,
             era,
+            shutdown,
         } = self;
 
         // set up node config
@@ -187,6 +200,8 @@ where
             pruning,
             engine,
             era,
+            shutdown,
+            verify_static_files,
         };
 
         let data_dir = node_config.datadir();
@@ -25,7 +25,7 @@ pub struct Command {
     #[arg(long, default_value = "")]
     pub node_key: String,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|publicip|stun|extip:\<IP\>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -18,6 +18,7 @@ use reth_node_core::{
 };
 
 pub mod bootnode;
+pub mod replay;
 pub mod rlpx;
 
 /// `reth p2p` command
@@ -85,6 +86,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Bootnode(command) => {
                 command.execute().await?;
             }
+            Subcommands::Replay(command) => {
+                command.execute::<N::NetworkPrimitives>()?;
+            }
         }
 
         Ok(())
@@ -99,6 +103,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Body { args, .. } => Some(&args.chain),
             Subcommands::Rlpx(_) => None,
             Subcommands::Bootnode(_) => None,
+            Subcommands::Replay(_) => None,
         }
     }
 }
@@ -126,6 +131,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     Rlpx(rlpx::Command),
     /// Bootnode command
     Bootnode(bootnode::Command),
+    /// Analyze the messages recorded with `--p2p-capture`
+    Replay(replay::Command),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -188,6 +195,7 @@ impl<C: ChainSpecParser> DownloadArgs<C> {
         let p2p_secret_key = get_secret_key(&secret_key_path)?;
         let rlpx_socket = (self.network.addr, self.network.port).into();
         let boot_nodes = self.chain.bootnodes().unwrap_or_default();
+        let protocol_capture = self.network.protocol_capture()?;
 
         let net = NetworkConfigBuilder::<N::NetworkPrimitives>::new(p2p_secret_key)
             .peer_config(config.peers_config_with_basic_nodes_from_file(None))
@@ -196,6 +204,10 @@ impl<C: ChainSpecParser> DownloadArgs<C> {
             .apply(|builder| {
                 self.network.discovery.apply_to_builder(builder, rlpx_socket, boot_nodes)
             })
+            .apply(|builder| match protocol_capture {
+                Some(capture) => builder.protocol_capture(capture),
+                None => builder,
+            })
             .build_with_noop_provider(self.chain.clone())
             .manager()
             .await?;
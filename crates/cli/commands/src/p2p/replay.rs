@@ -0,0 +1,315 @@
+//! Replay subcommand of P2P Debugging tool.
+
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_eth_wire::{
+    capture::{capture_files, CaptureDirection, CaptureRecord},
+    EthMessageID, NetworkPrimitives,
+};
+use reth_network_peers::PeerId;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// Analyzes the `eth` messages recorded with `--p2p-capture`.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The capture directory.
+    dir: PathBuf,
+
+    /// Only analyze the messages exchanged with this peer.
+    #[arg(long, value_name = "PEER_ID")]
+    peer: Option<PeerId>,
+
+    /// Print every captured message in addition to the summary.
+    #[arg(long)]
+    print: bool,
+
+    /// Decode the printed messages and print their content.
+    #[arg(long, requires = "print")]
+    decode: bool,
+}
+
+impl Command {
+    /// Execute `p2p replay` command.
+    pub fn execute<N: NetworkPrimitives>(self) -> eyre::Result<()> {
+        let files = capture_files(&self.dir)?;
+        if files.is_empty() {
+            eyre::bail!("No capture files found in {}", self.dir.display())
+        }
+
+        let mut stats = ReplayStats::default();
+        let mut invalid_lines = 0;
+        for path in files {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let Some(record) = CaptureRecord::from_line(&line?) else {
+                    invalid_lines += 1;
+                    continue
+                };
+                if self.peer.is_some_and(|peer| peer != record.peer_id) {
+                    continue
+                }
+                if self.print {
+                    print_record(&record);
+                    if self.decode {
+                        match record.decode_message::<N>() {
+                            Ok(message) => println!("{message:?}"),
+                            Err(err) => println!("failed to decode message: {err}"),
+                        }
+                    }
+                }
+                stats.add(&record);
+            }
+        }
+
+        if invalid_lines > 0 {
+            println!("Skipped {invalid_lines} invalid lines");
+        }
+        println!("{} messages with {} peers", stats.total, stats.peers.len());
+        println!("{}", stats.messages_table());
+        println!("{}", stats.peers_table());
+
+        Ok(())
+    }
+}
+
+fn print_record(record: &CaptureRecord) {
+    let request_id = record.request_id.map(|id| format!(" request_id={id}")).unwrap_or_default();
+    let latency = record.latency_ms.map(|ms| format!(" latency={ms}ms")).unwrap_or_default();
+    println!(
+        "{} {} {:<3} {:?} size={}{request_id}{latency}",
+        record.timestamp_ms,
+        record.peer_id,
+        record.direction,
+        record.message_id,
+        record.size(),
+    );
+}
+
+/// Aggregated statistics of a message type in one direction.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct MessageStats {
+    count: u64,
+    bytes: u64,
+    /// Number of responses with a known latency.
+    latencies: u64,
+    /// Sum of the known latencies.
+    total_latency_ms: u64,
+    max_latency_ms: u64,
+}
+
+/// Aggregated statistics of the messages exchanged with a peer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct PeerStats {
+    messages: u64,
+    bytes_in: u64,
+    bytes_out: u64,
+    /// Requests that haven't been answered in the capture, keyed by direction and request id.
+    pending_requests: HashSet<(CaptureDirection, u64)>,
+}
+
+/// Statistics of a capture.
+#[derive(Debug, Default)]
+struct ReplayStats {
+    total: u64,
+    messages: BTreeMap<(u8, &'static str), (EthMessageID, MessageStats)>,
+    peers: HashMap<PeerId, PeerStats>,
+}
+
+impl ReplayStats {
+    fn add(&mut self, record: &CaptureRecord) {
+        self.total += 1;
+
+        let (_, stats) = self
+            .messages
+            .entry((record.message_id.to_u8(), record.direction.as_str()))
+            .or_insert_with(|| (record.message_id, MessageStats::default()));
+        stats.count += 1;
+        stats.bytes += record.size() as u64;
+        if let Some(latency_ms) = record.latency_ms {
+            stats.latencies += 1;
+            stats.total_latency_ms += latency_ms;
+            stats.max_latency_ms = stats.max_latency_ms.max(latency_ms);
+        }
+
+        let peer = self.peers.entry(record.peer_id).or_default();
+        peer.messages += 1;
+        match record.direction {
+            CaptureDirection::Inbound => peer.bytes_in += record.size() as u64,
+            CaptureDirection::Outbound => peer.bytes_out += record.size() as u64,
+        }
+        if let Some(request_id) = record.request_id {
+            if is_request(record.message_id) {
+                peer.pending_requests.insert((record.direction, request_id));
+            } else {
+                let request_direction = match record.direction {
+                    CaptureDirection::Inbound => CaptureDirection::Outbound,
+                    CaptureDirection::Outbound => CaptureDirection::Inbound,
+                };
+                peer.pending_requests.remove(&(request_direction, request_id));
+            }
+        }
+    }
+
+    fn messages_table(&self) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            "Message",
+            "Direction",
+            "Count",
+            "Total Size",
+            "Avg Size",
+            "Avg Latency",
+            "Max Latency",
+        ]);
+
+        for ((_, direction), (message_id, stats)) in &self.messages {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format!("{message_id:?}")))
+                .add_cell(Cell::new(direction))
+                .add_cell(Cell::new(stats.count))
+                .add_cell(Cell::new(human_bytes(stats.bytes as f64)))
+                .add_cell(Cell::new(human_bytes(stats.bytes as f64 / stats.count as f64)));
+            if stats.latencies > 0 {
+                row.add_cell(Cell::new(format!("{}ms", stats.total_latency_ms / stats.latencies)))
+                    .add_cell(Cell::new(format!("{}ms", stats.max_latency_ms)));
+            } else {
+                row.add_cell(Cell::new("N/A")).add_cell(Cell::new("N/A"));
+            }
+            table.add_row(row);
+        }
+
+        table
+    }
+
+    fn peers_table(&self) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Peer", "Messages", "Received", "Sent", "Unanswered Requests"]);
+
+        let mut peers = self.peers.iter().collect::<Vec<_>>();
+        peers.sort_unstable_by_key(|(_, stats)| std::cmp::Reverse(stats.messages));
+        for (peer_id, stats) in peers {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(peer_id))
+                .add_cell(Cell::new(stats.messages))
+                .add_cell(Cell::new(human_bytes(stats.bytes_in as f64)))
+                .add_cell(Cell::new(human_bytes(stats.bytes_out as f64)))
+                .add_cell(Cell::new(stats.pending_requests.len()));
+            table.add_row(row);
+        }
+
+        table
+    }
+}
+
+/// Returns true if the message is a request of a request-response pair.
+const fn is_request(message_id: EthMessageID) -> bool {
+    matches!(
+        message_id,
+        EthMessageID::GetBlockHeaders |
+            EthMessageID::GetBlockBodies |
+            EthMessageID::GetPooledTransactions |
+            EthMessageID::GetNodeData |
+            EthMessageID::GetReceipts
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::EthVersion;
+
+    fn record(
+        peer_id: PeerId,
+        direction: CaptureDirection,
+        message_id: EthMessageID,
+        request_id: Option<u64>,
+        latency_ms: Option<u64>,
+    ) -> CaptureRecord {
+        CaptureRecord {
+            timestamp_ms: 0,
+            peer_id,
+            direction,
+            version: EthVersion::Eth68,
+            message_id,
+            request_id,
+            latency_ms,
+            payload: vec![0; 100].into(),
+        }
+    }
+
+    #[test]
+    fn parse_replay_command() {
+        let peer = PeerId::random();
+        let command = Command::try_parse_from([
+            "reth",
+            "capture",
+            "--peer",
+            &peer.to_string(),
+            "--print",
+            "--decode",
+        ])
+        .unwrap();
+        assert_eq!(command.dir, PathBuf::from("capture"));
+        assert_eq!(command.peer, Some(peer));
+        assert!(command.print);
+        assert!(command.decode);
+
+        // decoding only applies to printed messages
+        assert!(Command::try_parse_from(["reth", "capture", "--decode"]).is_err());
+    }
+
+    #[test]
+    fn aggregates_records() {
+        let peer = PeerId::random();
+        let mut stats = ReplayStats::default();
+        stats.add(&record(
+            peer,
+            CaptureDirection::Outbound,
+            EthMessageID::GetBlockHeaders,
+            Some(1),
+            None,
+        ));
+        stats.add(&record(
+            peer,
+            CaptureDirection::Outbound,
+            EthMessageID::GetBlockHeaders,
+            Some(2),
+            None,
+        ));
+        stats.add(&record(
+            peer,
+            CaptureDirection::Inbound,
+            EthMessageID::BlockHeaders,
+            Some(1),
+            Some(30),
+        ));
+        stats.add(&record(
+            peer,
+            CaptureDirection::Inbound,
+            EthMessageID::BlockHeaders,
+            Some(3),
+            Some(10),
+        ));
+
+        assert_eq!(stats.total, 4);
+        let (_, headers) = &stats.messages[&(EthMessageID::BlockHeaders.to_u8(), "in")];
+        assert_eq!(headers.count, 2);
+        assert_eq!(headers.bytes, 200);
+        assert_eq!(headers.total_latency_ms, 40);
+        assert_eq!(headers.max_latency_ms, 30);
+
+        let peer_stats = &stats.peers[&peer];
+        assert_eq!(peer_stats.messages, 4);
+        assert_eq!(peer_stats.bytes_in, 200);
+        assert_eq!(peer_stats.bytes_out, 200);
+        // only the request with id 2 is unanswered
+        assert_eq!(peer_stats.pending_requests, HashSet::from([(CaptureDirection::Outbound, 2)]));
+    }
+}
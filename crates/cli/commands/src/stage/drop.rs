@@ -42,17 +42,21 @@ impl<C: ChainSpecParser> Command<C> {
 
         let tool = DbTool::new(provider_factory)?;
 
-        let static_file_segment = match self.stage {
-            StageEnum::Headers => Some(StaticFileSegment::Headers),
-            StageEnum::Bodies => Some(StaticFileSegment::Transactions),
-            StageEnum::Execution => Some(StaticFileSegment::Receipts),
-            _ => None,
+        let static_file_segments: &[StaticFileSegment] = match self.stage {
+            StageEnum::Headers => &[StaticFileSegment::Headers],
+            StageEnum::Bodies => &[StaticFileSegment::Transactions],
+            StageEnum::Execution => &[
+                StaticFileSegment::Receipts,
+                StaticFileSegment::AccountChangeSets,
+                StaticFileSegment::StorageChangeSets,
+            ],
+            _ => &[],
         };
 
         // Delete static file segment data before inserting the genesis header below
-        if let Some(static_file_segment) = static_file_segment {
-            let static_file_provider = tool.provider_factory.static_file_provider();
-            let static_files = iter_static_files(static_file_provider.directory())?;
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+        for &static_file_segment in static_file_segments {
             if let Some(segment_static_files) = static_files.get(&static_file_segment) {
                 // Delete static files from the highest to the lowest block range
                 for (block_range, _) in segment_static_files
@@ -103,6 +107,7 @@ impl<C: ChainSpecParser> Command<C> {
                 tx.clear::<tables::StorageChangeSets>()?;
                 tx.clear::<tables::Bytecodes>()?;
                 tx.clear::<tables::Receipts<ReceiptTy<N>>>()?;
+                tx.clear::<tables::BlockRequests>()?;
 
                 reset_prune_checkpoint(tx, PruneSegment::Receipts)?;
                 reset_prune_checkpoint(tx, PruneSegment::ContractLogs)?;
@@ -156,6 +161,23 @@ impl<C: ChainSpecParser> Command<C> {
                 reset_stage_checkpoint(tx, StageId::TransactionLookup)?;
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
+            StageEnum::SenderTxs => {
+                tx.clear::<tables::SenderTransactions>()?;
+                tx.clear::<tables::RecipientTransactions>()?;
+                // The index is opt-in, so remove the checkpoint entirely to stop maintaining it.
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexSenderTransactions.to_string(),
+                    None,
+                )?;
+            }
+            StageEnum::ContractDeployments => {
+                tx.clear::<tables::ContractDeployments>()?;
+                // The index is opt-in, so remove the checkpoint entirely to stop maintaining it.
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexContractDeployments.to_string(),
+                    None,
+                )?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
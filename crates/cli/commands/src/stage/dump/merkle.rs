@@ -105,6 +105,8 @@ fn unwind_and_copy<N: ProviderNodeTypes>(
         ExecutionStageThresholds {
             max_blocks: Some(u64::MAX),
             max_changes: None,
+            max_changeset_bytes: None,
+            max_trie_updates: None,
             max_cumulative_gas: None,
             max_duration: None,
         },
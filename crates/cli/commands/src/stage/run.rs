@@ -36,8 +36,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexContractDeploymentsStage, IndexSenderTransactionsStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -198,7 +198,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     (
                         Box::new(HeaderStage::new(
                             provider_factory.clone(),
-                            ReverseHeadersDownloaderBuilder::new(config.stages.headers)
+                            ReverseHeadersDownloaderBuilder::new(config.stages.headers.clone())
                                 .build(fetch_client, consensus.clone()),
                             rx,
                             etl_config,
@@ -263,6 +263,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                         ExecutionStageThresholds {
                             max_blocks: Some(batch_size),
                             max_changes: None,
+                            max_changeset_bytes: None,
+                            max_trie_updates: None,
                             max_cumulative_gas: None,
                             max_duration: None,
                         },
@@ -316,6 +318,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::SenderTxs => (
+                    Box::new(IndexSenderTransactionsStage::new(
+                        config.stages.index_sender_transactions,
+                        etl_config,
+                    )),
+                    None,
+                ),
+                StageEnum::ContractDeployments => (
+                    Box::new(IndexContractDeploymentsStage::new(
+                        config.stages.index_contract_deployments,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -7,6 +7,7 @@ use crate::{
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table as ComfyTable};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_config::Config;
@@ -22,7 +23,7 @@ use reth_provider::{
 use reth_stages::{
     sets::{DefaultStages, OfflineStages},
     stages::ExecutionStage,
-    ExecutionStageThresholds, Pipeline, StageSet,
+    ExecutionStageThresholds, Pipeline, StageSet, StageUnwindPlan,
 };
 use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
@@ -42,6 +43,11 @@ pub struct Command<C: ChainSpecParser> {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Only report which tables and static file segments each stage would modify, and how many
+    /// entries would be removed, without unwinding anything.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -60,6 +66,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         let components = components(provider_factory.chain_spec());
 
+        if self.dry_run {
+            info!(target: "reth::cli", ?target, "Planning a pipeline unwind.");
+
+            let pipeline =
+                self.build_pipeline(config, provider_factory, components.evm_config().clone())?;
+            let plans = pipeline.plan_unwind(target)?;
+            println!("{}", unwind_plan_table(&plans));
+
+            return Ok(())
+        }
+
         let highest_static_file_block = provider_factory
             .static_file_provider()
             .get_highest_static_files()
@@ -154,6 +171,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     ExecutionStageThresholds {
                         max_blocks: None,
                         max_changes: None,
+                        max_changeset_bytes: None,
+                        max_trie_updates: None,
                         max_cumulative_gas: None,
                         max_duration: None,
                     },
@@ -171,6 +190,45 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     }
 }
 
+/// Renders the planned unwind of all stages as a table.
+fn unwind_plan_table(plans: &[StageUnwindPlan]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Stage", "Checkpoint", "Table / Segment", "Entries"]);
+
+    for StageUnwindPlan { stage_id, checkpoint, plan } in plans {
+        let stage_row = |target: String, entries: String| {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(stage_id))
+                .add_cell(Cell::new(checkpoint.block_number))
+                .add_cell(Cell::new(target))
+                .add_cell(Cell::new(entries));
+            row
+        };
+
+        let Some(plan) = plan else {
+            table.add_row(stage_row(String::new(), "skipped".to_string()));
+            continue
+        };
+        if plan.is_empty() {
+            table.add_row(stage_row(String::new(), "-".to_string()));
+        }
+        for table_plan in &plan.tables {
+            let entries =
+                table_plan.entries.map_or_else(|| "unknown".to_string(), |e| e.to_string());
+            table.add_row(stage_row(table_plan.table.to_string(), entries));
+        }
+        for segment_plan in &plan.static_file_segments {
+            table.add_row(stage_row(
+                format!("{} (static files)", segment_plan.segment),
+                segment_plan.rows.to_string(),
+            ));
+        }
+    }
+
+    table
+}
+
 impl<C: ChainSpecParser> Command<C> {
     /// Return the underlying chain being used to run this command
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
@@ -242,5 +300,15 @@ mod tests {
             "100",
         ]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--dry-run",
+            "num-blocks",
+            "100",
+        ]);
+        assert!(cmd.dry_run);
     }
 }
@@ -10,7 +10,10 @@
 
 //! Entrypoint for running commands.
 
-use reth_tasks::{TaskExecutor, TaskManager};
+use reth_tasks::{
+    runtime::CONSENSUS_ENGINE_TASK, DedicatedThreadsRuntime, TaskExecutor, TaskManager,
+    TokioRuntime,
+};
 use std::{future::Future, pin::pin, sync::mpsc, time::Duration};
 use tracing::{debug, error, trace};
 
@@ -143,8 +146,14 @@ struct AsyncCliRunner {
 impl AsyncCliRunner {
     /// Given a tokio [`Runtime`](tokio::runtime::Runtime), creates additional context required to
     /// execute commands asynchronously.
+    ///
+    /// The consensus engine is run on a dedicated thread, so it isn't delayed by other tasks.
     fn new(tokio_runtime: tokio::runtime::Runtime) -> Self {
-        let task_manager = TaskManager::new(tokio_runtime.handle().clone());
+        let handle = tokio_runtime.handle().clone();
+        let runtime =
+            DedicatedThreadsRuntime::new(handle.clone(), TokioRuntime::new(handle.clone()))
+                .with_task(CONSENSUS_ENGINE_TASK);
+        let task_manager = TaskManager::with_runtime(handle, runtime);
         let task_executor = task_manager.executor();
         Self { context: CliContext { task_executor }, task_manager, tokio_runtime }
     }
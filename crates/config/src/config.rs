@@ -1,6 +1,6 @@
 //! Configuration files.
 use reth_network_types::{PeersConfig, SessionsConfig};
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneModes, PruneSchedule};
 use reth_stages_types::ExecutionStageThresholds;
 use std::{
     path::{Path, PathBuf},
@@ -125,6 +125,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Sender Transactions stage configuration.
+    pub index_sender_transactions: IndexSenderTransactionsConfig,
+    /// Index Contract Deployments stage configuration.
+    pub index_contract_deployments: IndexContractDeploymentsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -170,7 +174,7 @@ impl EraConfig {
 }
 
 /// Header stage configuration.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct HeadersConfig {
@@ -185,6 +189,17 @@ pub struct HeadersConfig {
     /// Maximum amount of responses to buffer internally.
     /// The response contains multiple headers.
     pub downloader_max_buffered_responses: usize,
+    /// Maximum amount of responses to spill to disk once the internal buffer is full.
+    ///
+    /// Spilling allows the downloader to keep requests in flight on high latency networks while
+    /// waiting for a slow response. Disabled if 0.
+    ///
+    /// Default: 0
+    pub downloader_max_spilled_responses: usize,
+    /// Directory to spill responses to.
+    ///
+    /// If unset, a new directory in the temp directory of the system is used.
+    pub downloader_spill_directory: Option<PathBuf>,
     /// The maximum number of headers to request from a peer at a time.
     pub downloader_request_limit: u64,
     /// The maximum number of headers to download before committing progress to the database.
@@ -199,6 +214,8 @@ impl Default for HeadersConfig {
             downloader_max_concurrent_requests: 100,
             downloader_min_concurrent_requests: 5,
             downloader_max_buffered_responses: 100,
+            downloader_max_spilled_responses: 0,
+            downloader_spill_directory: None,
         }
     }
 }
@@ -267,6 +284,12 @@ pub struct ExecutionConfig {
     pub max_blocks: Option<u64>,
     /// The maximum number of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The maximum estimated size in bytes of changesets to keep in memory before the execution
+    /// stage commits.
+    pub max_changeset_bytes: Option<u64>,
+    /// The maximum number of changed accounts and storage slots (resulting in trie updates) to
+    /// keep in memory before the execution stage commits.
+    pub max_trie_updates: Option<u64>,
     /// The maximum cumulative amount of gas to process before the execution stage commits.
     pub max_cumulative_gas: Option<u64>,
     /// The maximum time spent on blocks processing before the execution stage commits.
@@ -278,6 +301,9 @@ pub struct ExecutionConfig {
         )
     )]
     pub max_duration: Option<Duration>,
+    /// Whether the execution stage writes the hashed state of executed blocks, sparing the hashing
+    /// stages a separate pass over the plain state.
+    pub write_hashed_state: bool,
 }
 
 impl Default for ExecutionConfig {
@@ -285,10 +311,13 @@ impl Default for ExecutionConfig {
         Self {
             max_blocks: Some(500_000),
             max_changes: Some(5_000_000),
+            max_changeset_bytes: None,
+            max_trie_updates: None,
             // 50k full blocks of 30M gas
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            write_hashed_state: false,
         }
     }
 }
@@ -298,6 +327,8 @@ impl From<ExecutionConfig> for ExecutionStageThresholds {
         Self {
             max_blocks: config.max_blocks,
             max_changes: config.max_changes,
+            max_changeset_bytes: config.max_changeset_bytes,
+            max_trie_updates: config.max_trie_updates,
             max_cumulative_gas: config.max_cumulative_gas,
             max_duration: config.max_duration,
         }
@@ -426,6 +457,46 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Index Sender Transactions stage configuration.
+///
+/// The stage maintains an index of the transactions sent by each address. It relies on the
+/// transaction senders, so it should not be combined with full pruning of sender recovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexSenderTransactionsConfig {
+    /// Whether the index is built. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexSenderTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Index Contract Deployments stage configuration.
+///
+/// The stage maintains an index of the contracts deployed with each code hash. It relies on the
+/// account changesets and history, so it should not be combined with pruning of account history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexContractDeploymentsConfig {
+    /// Whether the index is built. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexContractDeploymentsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -436,18 +507,25 @@ pub struct PruneConfig {
     /// Pruning configuration for every part of the data that can be pruned.
     #[cfg_attr(feature = "serde", serde(alias = "parts"))]
     pub segments: PruneModes,
+    /// Time windows during which the pruner is allowed to run.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "PruneSchedule::is_unrestricted"))]
+    pub schedule: PruneSchedule,
 }
 
 impl Default for PruneConfig {
     fn default() -> Self {
-        Self { block_interval: DEFAULT_BLOCK_INTERVAL, segments: PruneModes::none() }
+        Self {
+            block_interval: DEFAULT_BLOCK_INTERVAL,
+            segments: PruneModes::none(),
+            schedule: PruneSchedule::default(),
+        }
     }
 }
 
 impl PruneConfig {
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
-        self.segments.receipts.is_some() || !self.segments.receipts_log_filter.is_empty()
+        self.segments.has_receipts_pruning()
     }
 
     /// Merges another `PruneConfig` into this one, taking values from the other config if and only
@@ -465,7 +543,9 @@ impl PruneConfig {
                     storage_history,
                     bodies_history,
                     receipts_log_filter,
+                    receipts_log_topic_filter,
                 },
+            schedule,
         } = other;
 
         // Merge block_interval, only update if it's the default interval
@@ -484,6 +564,16 @@ impl PruneConfig {
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
         }
+
+        if self.segments.receipts_log_topic_filter.is_empty() &&
+            !receipts_log_topic_filter.is_empty()
+        {
+            self.segments.receipts_log_topic_filter = receipts_log_topic_filter;
+        }
+
+        if self.schedule == PruneSchedule::default() {
+            self.schedule = schedule;
+        }
     }
 }
 
@@ -510,9 +600,11 @@ where
 mod tests {
     use super::{Config, EXTENSION};
     use crate::PruneConfig;
-    use alloy_primitives::Address;
+    use alloy_primitives::{Address, B256};
     use reth_network_peers::TrustedPeer;
-    use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
+    use reth_prune_types::{
+        PruneMode, PruneModes, PruneSchedule, ReceiptsLogPruneConfig, ReceiptsLogTopicPruneConfig,
+    };
     use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -1005,7 +1097,9 @@ receipts = 'full'
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                receipts_log_topic_filter: Default::default(),
             },
+            schedule: PruneSchedule::default(),
         };
 
         let config2 = PruneConfig {
@@ -1021,10 +1115,20 @@ receipts = 'full'
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                receipts_log_topic_filter: ReceiptsLogTopicPruneConfig(BTreeMap::from([(
+                    B256::random(),
+                    PruneMode::Distance(1000),
+                )])),
+            },
+            schedule: PruneSchedule {
+                windows: vec!["01:00-05:00".parse().unwrap()],
+                max_run_duration: None,
+                emergency_distance: Some(10_000),
             },
         };
 
         let original_filter = config1.segments.receipts_log_filter.clone();
+        let topic_filter = config2.segments.receipts_log_topic_filter.clone();
         config1.merge(Some(config2));
 
         // Check that the configuration has been merged. Any configuration present in config1
@@ -1036,6 +1140,32 @@ receipts = 'full'
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.segments.receipts_log_topic_filter, topic_filter);
+        assert_eq!(config1.schedule.emergency_distance, Some(10_000));
+    }
+
+    #[test]
+    fn test_prune_schedule_config() {
+        let s = r"#
+[prune]
+block_interval = 5
+
+[prune.schedule]
+windows = ['01:00-05:00', '22:30-23:30']
+max_run_duration = '30m'
+emergency_distance = 100000
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let schedule = conf.prune.unwrap().schedule;
+        assert_eq!(schedule.windows.len(), 2);
+        assert_eq!(schedule.max_run_duration, Some(Duration::from_secs(30 * 60)));
+        assert_eq!(schedule.emergency_distance, Some(100_000));
+
+        let s = r"#
+[prune.schedule]
+windows = ['25:00-05:00']
+#";
+        assert!(toml::from_str::<Config>(s).is_err());
     }
 
     #[test]
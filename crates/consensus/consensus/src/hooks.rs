@@ -0,0 +1,186 @@
+use crate::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use alloc::{fmt::Debug, sync::Arc, vec::Vec};
+use alloy_consensus::Header;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+
+/// An additional header check that is applied on top of the rules of a [`HeaderValidator`].
+///
+/// This is useful for chains that deviate slightly from the mainnet rules, e.g. by enforcing a
+/// custom format of the header's extra data or a different maximum gas limit step, without having
+/// to reimplement the whole consensus. Hooks are registered with [`HookedConsensus::with_hook`].
+#[auto_impl::auto_impl(&, Arc)]
+pub trait HeaderValidationHook<H = Header>: Debug + Send + Sync {
+    /// Validates the header on its own.
+    ///
+    /// This is called after [`HeaderValidator::validate_header`] of the wrapped consensus
+    /// succeeded.
+    fn validate_header(&self, _header: &SealedHeader<H>) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+
+    /// Validates the header against its parent.
+    ///
+    /// This is called after [`HeaderValidator::validate_header_against_parent`] of the wrapped
+    /// consensus succeeded.
+    ///
+    /// **This is not called for the genesis block**.
+    fn validate_header_against_parent(
+        &self,
+        _header: &SealedHeader<H>,
+        _parent: &SealedHeader<H>,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+}
+
+/// A consensus implementation that wraps another consensus and additionally applies the registered
+/// [`HeaderValidationHook`]s during header validation.
+///
+/// Since the node uses the same consensus instance for both the live validation of new blocks
+/// and the header validation of the pipeline, the hooks apply to all headers the node accepts.
+#[derive(Debug, Clone)]
+pub struct HookedConsensus<C, H = Header> {
+    /// The wrapped consensus.
+    inner: C,
+    /// The registered hooks, in the order they are applied.
+    hooks: Vec<Arc<dyn HeaderValidationHook<H>>>,
+}
+
+impl<C, H> HookedConsensus<C, H> {
+    /// Creates a new [`HookedConsensus`] without any hooks.
+    pub const fn new(inner: C) -> Self {
+        Self { inner, hooks: Vec::new() }
+    }
+
+    /// Registers a hook that is applied after the hooks registered before.
+    pub fn with_hook(mut self, hook: impl HeaderValidationHook<H> + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Returns the wrapped consensus.
+    pub const fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C, H> HeaderValidator<H> for HookedConsensus<C, H>
+where
+    C: HeaderValidator<H>,
+    H: Debug + Send + Sync,
+{
+    fn validate_header(&self, header: &SealedHeader<H>) -> Result<(), ConsensusError> {
+        self.inner.validate_header(header)?;
+        self.hooks.iter().try_for_each(|hook| hook.validate_header(header))
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<H>,
+        parent: &SealedHeader<H>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_header_against_parent(header, parent)?;
+        self.hooks.iter().try_for_each(|hook| hook.validate_header_against_parent(header, parent))
+    }
+}
+
+impl<B, C> Consensus<B> for HookedConsensus<C, B::Header>
+where
+    B: Block,
+    C: Consensus<B>,
+{
+    type Error = C::Error;
+
+    fn validate_body_against_header(
+        &self,
+        body: &B::Body,
+        header: &SealedHeader<B::Header>,
+    ) -> Result<(), Self::Error> {
+        self.inner.validate_body_against_header(body, header)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), Self::Error> {
+        self.inner.validate_block_pre_execution(block)
+    }
+}
+
+impl<N, C> FullConsensus<N> for HookedConsensus<C, N::BlockHeader>
+where
+    N: NodePrimitives,
+    C: FullConsensus<N>,
+{
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution(block, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noop::NoopConsensus;
+    use alloc::string::ToString;
+
+    /// Rejects headers with more than 4 bytes of extra data.
+    #[derive(Debug)]
+    struct MaxExtraData;
+
+    impl HeaderValidationHook for MaxExtraData {
+        fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+            if header.extra_data.len() > 4 {
+                return Err(ConsensusError::ExtraDataExceedsMax { len: header.extra_data.len() })
+            }
+            Ok(())
+        }
+    }
+
+    /// Rejects gas limit changes of more than 1000 gas.
+    #[derive(Debug)]
+    struct MaxGasLimitStep;
+
+    impl HeaderValidationHook for MaxGasLimitStep {
+        fn validate_header_against_parent(
+            &self,
+            header: &SealedHeader,
+            parent: &SealedHeader,
+        ) -> Result<(), ConsensusError> {
+            if header.gas_limit.abs_diff(parent.gas_limit) > 1000 {
+                return Err(ConsensusError::Other("gas limit step too large".to_string()))
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn applies_hooks() {
+        let consensus =
+            HookedConsensus::new(NoopConsensus).with_hook(MaxExtraData).with_hook(MaxGasLimitStep);
+
+        let parent =
+            SealedHeader::seal_slow(Header { gas_limit: 30_000_000, ..Default::default() });
+        let header = |extra_data: &[u8], gas_limit| {
+            SealedHeader::seal_slow(Header {
+                extra_data: extra_data.to_vec().into(),
+                gas_limit,
+                ..Default::default()
+            })
+        };
+
+        let valid = header(b"reth", 30_001_000);
+        assert_eq!(consensus.validate_header(&valid), Ok(()));
+        assert_eq!(consensus.validate_header_against_parent(&valid, &parent), Ok(()));
+
+        let invalid = header(b"too long", 30_000_000);
+        assert_eq!(
+            consensus.validate_header(&invalid),
+            Err(ConsensusError::ExtraDataExceedsMax { len: 8 })
+        );
+
+        let invalid = header(b"", 29_998_999);
+        assert!(consensus.validate_header_against_parent(&invalid, &parent).is_err());
+    }
+}
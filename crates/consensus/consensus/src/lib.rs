@@ -25,6 +25,9 @@ use reth_primitives_traits::{
 /// A consensus implementation that does nothing.
 pub mod noop;
 
+/// A consensus implementation that applies additional header validation hooks.
+pub mod hooks;
+
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking consensus
 pub mod test_utils;
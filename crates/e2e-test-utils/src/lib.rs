@@ -4,6 +4,7 @@ use node::NodeTestContext;
 use reth_chainspec::{ChainSpec, EthChainSpec};
 use reth_db::{test_utils::TempDatabase, DatabaseEnv};
 use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_network::transport::Transport;
 use reth_network_api::test_utils::PeersHandleProvider;
 use reth_node_builder::{
     components::NodeComponentsBuilder,
@@ -46,6 +47,8 @@ mod rpc;
 pub mod test_rlp_utils;
 
 /// Creates the initial setup with `num_nodes` started and interconnected.
+///
+/// The nodes are connected in memory, see [`Transport::Memory`].
 pub async fn setup<N>(
     num_nodes: usize,
     chain_spec: Arc<N::ChainSpec>,
@@ -67,6 +70,7 @@ where
 
     let network_config = NetworkArgs {
         discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+        transport: Transport::Memory,
         ..NetworkArgs::default()
     };
 
@@ -137,6 +141,8 @@ where
 }
 
 /// Creates the initial setup with `num_nodes` started and optionally interconnected.
+///
+/// The nodes are connected in memory, see [`Transport::Memory`].
 pub async fn setup_engine_with_connection<N>(
     num_nodes: usize,
     chain_spec: Arc<N::ChainSpec>,
@@ -159,6 +165,7 @@ where
 
     let network_config = NetworkArgs {
         discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+        transport: Transport::Memory,
         ..NetworkArgs::default()
     };
 
@@ -11,12 +11,14 @@ use tracing::debug;
 
 pub mod engine_api;
 pub mod fork;
+pub mod network;
 pub mod node_ops;
 pub mod produce_blocks;
 pub mod reorg;
 
 pub use engine_api::{ExpectedPayloadStatus, SendNewPayload, SendNewPayloads};
 pub use fork::{CreateFork, ForkBase, SetForkBase, SetForkBaseFromBlockInfo, ValidateFork};
+pub use network::{HealPartition, PartitionNetwork, SetEngineLatency};
 pub use node_ops::{
     AssertChainTip, CaptureBlockOnNode, CompareNodeChainTips, SelectActiveNode, ValidateBlockTag,
     WaitForSync,
@@ -0,0 +1,208 @@
+//! Network fault injection actions for multi-node testing.
+//!
+//! These actions operate on the p2p connections between nodes (via the `admin` namespace) and on
+//! the simulated consensus layer, so that broadcasting actions such as
+//! [`BroadcastNextNewPayload`](crate::testsuite::actions::BroadcastNextNewPayload) respect the
+//! injected faults.
+
+use crate::testsuite::{Action, Environment};
+use eyre::Result;
+use futures_util::future::BoxFuture;
+use reth_network_peers::{AnyNode, NodeRecord};
+use reth_node_api::EngineTypes;
+use reth_rpc_api::clients::AdminApiClient;
+use std::{collections::HashSet, time::Duration};
+use tokio::time::{sleep, timeout};
+use tracing::debug;
+
+/// Action that splits the network into isolated groups of nodes.
+///
+/// Nodes in different groups are disconnected from each other at the p2p level and the simulated
+/// consensus layer stops delivering engine API messages across group boundaries. Nodes that are
+/// not listed in any group are isolated from all other nodes.
+#[derive(Debug)]
+pub struct PartitionNetwork {
+    /// Groups of node indices that can still reach each other
+    pub groups: Vec<Vec<usize>>,
+}
+
+impl PartitionNetwork {
+    /// Create a new `PartitionNetwork` action
+    pub const fn new(groups: Vec<Vec<usize>>) -> Self {
+        Self { groups }
+    }
+
+    /// Create a partition that isolates a single node from the rest of the network
+    pub fn isolate(node_idx: usize, node_count: usize) -> Self {
+        let rest = (0..node_count).filter(|idx| *idx != node_idx).collect();
+        Self::new(vec![vec![node_idx], rest])
+    }
+}
+
+impl<Engine> Action<Engine> for PartitionNetwork
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut seen = HashSet::new();
+            for idx in self.groups.iter().flatten() {
+                if *idx >= env.node_count() {
+                    return Err(eyre::eyre!(
+                        "Node index {} out of bounds (have {} nodes)",
+                        idx,
+                        env.node_count()
+                    ));
+                }
+                if !seen.insert(*idx) {
+                    return Err(eyre::eyre!("Node {} is listed in more than one group", idx));
+                }
+            }
+
+            env.network_partition = Some(self.groups.clone());
+
+            let records = node_records(env).await?;
+            for (a, b) in node_pairs(env.node_count()) {
+                if env.is_reachable(a, b) {
+                    continue
+                }
+
+                // remove the peer on both sides so that neither node redials the other
+                AdminApiClient::remove_peer(&env.node_clients[a].rpc, AnyNode::from(records[b]))
+                    .await?;
+                AdminApiClient::remove_peer(&env.node_clients[b].rpc, AnyNode::from(records[a]))
+                    .await?;
+                debug!("Partitioned node {} from node {}", a, b);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Action that removes a partition previously created by [`PartitionNetwork`].
+///
+/// All nodes are reconnected to each other and the action waits until every pair that was
+/// separated by the partition has re-established a p2p session.
+#[derive(Debug)]
+pub struct HealPartition {
+    /// Maximum time to wait for the nodes to reconnect
+    pub timeout_secs: u64,
+}
+
+impl Default for HealPartition {
+    fn default() -> Self {
+        Self { timeout_secs: 30 }
+    }
+}
+
+impl HealPartition {
+    /// Create a new `HealPartition` action
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum time to wait for the nodes to reconnect
+    pub const fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for HealPartition
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if env.network_partition.is_none() {
+                debug!("No network partition to heal");
+                return Ok(())
+            }
+
+            let records = node_records(env).await?;
+            let separated: Vec<_> =
+                node_pairs(env.node_count()).filter(|(a, b)| !env.is_reachable(*a, *b)).collect();
+            env.network_partition = None;
+
+            for (a, b) in &separated {
+                AdminApiClient::add_peer(&env.node_clients[*a].rpc, records[*b]).await?;
+            }
+
+            let result = timeout(Duration::from_secs(self.timeout_secs), async {
+                for (a, b) in &separated {
+                    loop {
+                        let peers = AdminApiClient::peers(&env.node_clients[*a].rpc).await?;
+                        if peers.iter().any(|peer| {
+                            peer.enode.parse::<NodeRecord>().is_ok_and(|r| r.id == records[*b].id)
+                        }) {
+                            debug!("Node {} reconnected to node {}", a, b);
+                            break
+                        }
+                        sleep(Duration::from_millis(100)).await;
+                    }
+                }
+                Ok::<(), eyre::Error>(())
+            })
+            .await;
+
+            match result {
+                Ok(res) => res,
+                Err(_) => Err(eyre::eyre!(
+                    "Timed out after {}s waiting for nodes to reconnect",
+                    self.timeout_secs
+                )),
+            }
+        })
+    }
+}
+
+/// Action that delays all engine API messages delivered to a node by the simulated consensus
+/// layer.
+#[derive(Debug)]
+pub struct SetEngineLatency {
+    /// Node index to apply the latency to
+    pub node_idx: usize,
+    /// Delay applied before each engine API message sent to the node
+    pub latency: Duration,
+}
+
+impl SetEngineLatency {
+    /// Create a new `SetEngineLatency` action
+    pub const fn new(node_idx: usize, latency: Duration) -> Self {
+        Self { node_idx, latency }
+    }
+
+    /// Create a new `SetEngineLatency` action that removes any latency from the node
+    pub const fn clear(node_idx: usize) -> Self {
+        Self::new(node_idx, Duration::ZERO)
+    }
+}
+
+impl<Engine> Action<Engine> for SetEngineLatency
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            env.node_state_mut(self.node_idx)?.engine_latency = self.latency;
+            debug!("Set engine latency of node {} to {:?}", self.node_idx, self.latency);
+            Ok(())
+        })
+    }
+}
+
+/// Returns all unordered pairs of node indices.
+fn node_pairs(node_count: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..node_count).flat_map(move |a| (a + 1..node_count).map(move |b| (a, b)))
+}
+
+/// Fetches the node record of every node in the environment.
+async fn node_records<Engine: EngineTypes>(env: &Environment<Engine>) -> Result<Vec<NodeRecord>> {
+    let mut records = Vec::with_capacity(env.node_count());
+    for client in &env.node_clients {
+        let info = AdminApiClient::node_info(&client.rpc).await?;
+        records.push(info.enode.parse::<NodeRecord>()?);
+    }
+    Ok(records)
+}
@@ -152,8 +152,13 @@ where
                 .current_block_info()
                 .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
 
-            // simple round-robin selection based on next block number
-            let next_producer_idx = ((latest_info.number + 1) % num_clients as u64) as usize;
+            // simple round-robin selection based on next block number, restricted to the nodes
+            // the active node can reach if the network is partitioned
+            let candidates: Vec<_> = (0..num_clients)
+                .filter(|idx| env.is_reachable(env.active_node_idx, *idx))
+                .collect();
+            let next_producer_idx =
+                candidates[((latest_info.number + 1) % candidates.len() as u64) as usize];
 
             env.last_producer_idx = Some(next_producer_idx);
             debug!(
@@ -368,7 +373,14 @@ where
                 fork_choice_state.head_block_hash
             );
 
+            let source_idx = env.active_node_idx;
             for (idx, client) in env.node_clients.iter().enumerate() {
+                if !env.is_reachable(source_idx, idx) {
+                    debug!("Client {}: unreachable due to network partition, skipping", idx);
+                    continue
+                }
+                sleep(env.node_states[idx].engine_latency).await;
+
                 match EngineApiClient::<Engine>::fork_choice_updated_v3(
                     &client.engine.http_client(),
                     fork_choice_state,
@@ -687,6 +699,12 @@ where
                 let mut first_valid_seen = false;
 
                 for (idx, client) in env.node_clients.iter().enumerate() {
+                    if !env.is_reachable(env.active_node_idx, idx) {
+                        debug!("Node {}: unreachable due to network partition, skipping", idx);
+                        continue
+                    }
+                    sleep(env.node_states[idx].engine_latency).await;
+
                     let engine = client.engine.http_client();
 
                     // Broadcast the execution payload
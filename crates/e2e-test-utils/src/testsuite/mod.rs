@@ -10,7 +10,7 @@ use jsonrpsee::http_client::HttpClient;
 use reth_engine_local::LocalPayloadAttributesBuilder;
 use reth_node_api::{EngineTypes, NodeTypes, PayloadTypes};
 use reth_payload_builder::PayloadId;
-use std::{collections::HashMap, marker::PhantomData};
+use std::{collections::HashMap, marker::PhantomData, time::Duration};
 pub mod actions;
 pub mod setup;
 use crate::testsuite::setup::Setup;
@@ -103,6 +103,8 @@ where
     pub latest_payload_envelope: Option<I::ExecutionPayloadEnvelopeV3>,
     /// Fork base block number for validation (if this node is currently on a fork)
     pub current_fork_base: Option<u64>,
+    /// Delay applied before each engine API message broadcast to this node
+    pub engine_latency: Duration,
 }
 
 impl<I> Default for NodeState<I>
@@ -121,6 +123,7 @@ where
             latest_payload_executed: None,
             latest_payload_envelope: None,
             current_fork_base: None,
+            engine_latency: Duration::ZERO,
         }
     }
 }
@@ -141,6 +144,7 @@ where
             .field("latest_payload_executed", &self.latest_payload_executed)
             .field("latest_payload_envelope", &"<ExecutionPayloadEnvelopeV3>")
             .field("current_fork_base", &self.current_fork_base)
+            .field("engine_latency", &self.engine_latency)
             .finish()
     }
 }
@@ -169,6 +173,8 @@ where
     pub block_registry: HashMap<String, (BlockInfo, usize)>,
     /// Currently active node index for backward compatibility with single-node actions
     pub active_node_idx: usize,
+    /// Groups of nodes that can reach each other, if the network is currently partitioned
+    pub network_partition: Option<Vec<Vec<usize>>>,
 }
 
 impl<I> Default for Environment<I>
@@ -186,6 +192,7 @@ where
             slots_to_finalized: 0,
             block_registry: HashMap::new(),
             active_node_idx: 0,
+            network_partition: None,
         }
     }
 }
@@ -238,6 +245,13 @@ where
         Ok(())
     }
 
+    /// Returns whether messages from one node can reach another under the current network
+    /// partition.
+    pub fn is_reachable(&self, from: usize, to: usize) -> bool {
+        let Some(groups) = &self.network_partition else { return true };
+        from == to || groups.iter().any(|group| group.contains(&from) && group.contains(&to))
+    }
+
     /// Initialize node states when nodes are created
     pub fn initialize_node_states(&mut self, node_count: usize) {
         self.node_states = (0..node_count).map(|_| NodeState::default()).collect();
@@ -9,8 +9,9 @@ use reth_e2e_test_utils::{
     testsuite::{
         actions::{
             Action, AssertChainTip, AssertMineBlock, CaptureBlock, CaptureBlockOnNode,
-            CompareNodeChainTips, CreateFork, MakeCanonical, ProduceBlocks, ReorgTo,
-            SelectActiveNode, UpdateBlockInfo,
+            CompareNodeChainTips, CreateFork, HealPartition, MakeCanonical, PartitionNetwork,
+            ProduceBlocks, ReorgTo, SelectActiveNode, SetEngineLatency, UpdateBlockInfo,
+            WaitForSync,
         },
         setup::{NetworkSetup, Setup},
         Environment, TestBuilder,
@@ -18,7 +19,7 @@ use reth_e2e_test_utils::{
 };
 use reth_node_api::TreeConfig;
 use reth_node_ethereum::{EthEngineTypes, EthereumNode};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tempfile::TempDir;
 use tracing::debug;
 
@@ -349,3 +350,50 @@ async fn test_testsuite_multinode_block_production() -> Result<()> {
 
     Ok(())
 }
+
+/// Multi-node test injecting a network partition and engine latency.
+///
+/// While partitioned, blocks produced on node 0 are not delivered to node 1. After the partition
+/// is healed node 1 catches up with node 0 over p2p.
+#[tokio::test]
+async fn test_testsuite_multinode_partition_and_heal() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let setup = Setup::default()
+        .with_chain_spec(Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(
+                    serde_json::from_str(include_str!(
+                        "../../../../crates/e2e-test-utils/src/testsuite/assets/genesis.json"
+                    ))
+                    .unwrap(),
+                )
+                .cancun_activated()
+                .build(),
+        ))
+        .with_network(NetworkSetup::multi_node(2))
+        .with_tree_config(TreeConfig::default().with_state_root_fallback(true));
+
+    let test = TestBuilder::new()
+        .with_setup(setup)
+        .with_action(SelectActiveNode::new(0))
+        .with_action(SetEngineLatency::new(1, Duration::from_millis(50)))
+        .with_action(ProduceBlocks::<EthEngineTypes>::new(1))
+        .with_action(MakeCanonical::new())
+        .with_action(CompareNodeChainTips::expect_same(0, 1))
+        // isolate node 1, blocks produced on node 0 must not reach it
+        .with_action(PartitionNetwork::isolate(1, 2))
+        .with_action(ProduceBlocks::<EthEngineTypes>::new(2))
+        .with_action(MakeCanonical::new())
+        .with_action(CompareNodeChainTips::expect_different(0, 1))
+        // heal the partition and let node 1 sync the missing blocks from node 0
+        .with_action(HealPartition::new())
+        .with_action(SetEngineLatency::clear(1))
+        .with_action(MakeCanonical::new())
+        .with_action(WaitForSync::new(0, 1));
+
+    test.run::<EthereumNode>().await?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::witness::{re_execute_block, ReExecutedBlock};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_tracing::tracing::{info, warn};
+use reth_trie::updates::TrieUpdates;
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Summary of an invalid block, written next to the dumped block data.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InvalidBlockSummary {
+    /// The number of the invalid block.
+    number: BlockNumber,
+    /// The hash of the invalid block.
+    hash: B256,
+    /// The hash of the parent block.
+    parent_hash: B256,
+    /// The state root in the header of the invalid block.
+    header_state_root: B256,
+    /// The state root computed after executing the block, if it was computed.
+    computed_state_root: Option<B256>,
+    /// The gas used in the header of the invalid block.
+    header_gas_used: u64,
+    /// The gas used by the execution of the block.
+    execution_gas_used: u64,
+}
+
+/// Dumps the offending block, its execution witness and the state it touched to disk, so that
+/// consensus bugs can be triaged without access to the node that hit them.
+///
+/// For each invalid block, the following files are written to the `<number>_<hash>` directory
+/// inside the output directory:
+/// - `summary.json`: block identifiers, state roots and gas usage of the header and the execution
+/// - `block.json`: the invalid block, including the recovered senders
+/// - `receipts.json`: the receipts produced by the execution of the block
+/// - `witness.json`: the execution witness of all state accessed by the block
+/// - `touched_state.json`: the original and changed values of all accounts and storage slots
+///   modified by the block
+#[derive(Debug)]
+pub struct InvalidBlockDumpHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the dumps to.
+    output_directory: PathBuf,
+}
+
+impl<P, E> InvalidBlockDumpHook<P, E> {
+    /// Creates a new dump hook.
+    pub const fn new(provider: P, evm_config: E, output_directory: PathBuf) -> Self {
+        Self { provider, evm_config, output_directory }
+    }
+}
+
+impl<P, E, N> InvalidBlockDumpHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+    ) -> eyre::Result<PathBuf> {
+        let directory = self.output_directory.join(format!("{}_{}", block.number(), block.hash()));
+        fs::create_dir_all(&directory)?;
+
+        let summary = InvalidBlockSummary {
+            number: block.number(),
+            hash: block.hash(),
+            parent_hash: parent_header.hash(),
+            header_state_root: block.state_root(),
+            computed_state_root: trie_updates.map(|(_, root)| root),
+            header_gas_used: block.gas_used(),
+            execution_gas_used: output.gas_used,
+        };
+        save_file(&directory, "summary.json", &summary)?;
+        save_file(&directory, "block.json", block)?;
+        save_file(&directory, "receipts.json", &output.receipts)?;
+        save_file(&directory, "touched_state.json", &output.state)?;
+
+        // The witness requires re-executing the block, so it's written last to keep the other
+        // files if the re-execution fails.
+        let ReExecutedBlock { witness, .. } =
+            re_execute_block(&self.provider, &self.evm_config, parent_header, block)?;
+        save_file(&directory, "witness.json", &witness)?;
+
+        Ok(directory)
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockDumpHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        match self.on_invalid_block(parent_header, block, output, trie_updates) {
+            Ok(directory) => {
+                info!(target: "engine::invalid_block_hooks::dump", number = block.number(), hash = %block.hash(), directory = %directory.display(), "Dumped invalid block")
+            }
+            Err(err) => {
+                warn!(target: "engine::invalid_block_hooks::dump", %err, "Failed to invoke hook")
+            }
+        }
+    }
+}
+
+/// Serializes the value as JSON into a file with the given name in the directory.
+fn save_file<T: Serialize + ?Sized>(
+    directory: &Path,
+    filename: &str,
+    value: &T,
+) -> eyre::Result<()> {
+    File::create(directory.join(filename))?.write_all(serde_json::to_string(value)?.as_bytes())?;
+    Ok(())
+}
@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod dump;
 mod witness;
 
+pub use dump::InvalidBlockDumpHook;
 pub use witness::InvalidBlockWitnessHook;
@@ -5,11 +5,13 @@ use pretty_assertions::Comparison;
 use reth_engine_primitives::InvalidBlockHook;
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
-use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_provider::{
+    BlockExecutionOutput, ChainSpecProvider, StateProviderBox, StateProviderFactory,
+};
 use reth_revm::{database::StateProviderDatabase, db::BundleState, state::AccountInfo};
 use reth_rpc_api::DebugApiClient;
 use reth_tracing::tracing::warn;
-use reth_trie::{updates::TrieUpdates, HashedStorage};
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use revm_bytecode::Bytecode;
 use revm_database::states::{
     reverts::{AccountInfoRevert, RevertToSlot},
@@ -107,6 +109,100 @@ impl BundleStateSorted {
     }
 }
 
+/// The result of re-executing a block on top of the state of its parent.
+pub(crate) struct ReExecutedBlock {
+    /// The execution witness of all state accessed during execution.
+    pub(crate) witness: ExecutionWitness,
+    /// The bundle state produced by the re-execution.
+    pub(crate) bundle_state: BundleState,
+    /// The hashed state of all accounts and storage slots accessed during execution.
+    pub(crate) hashed_state: HashedPostState,
+    /// The state provider of the parent block.
+    pub(crate) state_provider: StateProviderBox,
+}
+
+/// Re-executes the given block on top of the state of its parent and generates an execution
+/// witness for all state accessed during execution.
+pub(crate) fn re_execute_block<P, E, N>(
+    provider: &P,
+    evm_config: &E,
+    parent_header: &SealedHeader<N::BlockHeader>,
+    block: &RecoveredBlock<N::Block>,
+) -> eyre::Result<ReExecutedBlock>
+where
+    P: StateProviderFactory,
+    E: ConfigureEvm<Primitives = N>,
+    N: NodePrimitives,
+{
+    // TODO(alexey): unify with `DebugApi::debug_execution_witness`
+
+    let mut executor = evm_config.batch_executor(StateProviderDatabase::new(
+        provider.state_by_block_hash(parent_header.hash())?,
+    ));
+
+    executor.execute_one(block)?;
+
+    // Take the bundle state
+    let mut db = executor.into_state();
+    let bundle_state = db.take_bundle();
+
+    // Initialize a map of preimages.
+    let mut state_preimages = Vec::default();
+
+    // Get codes
+    let codes = db
+        .cache
+        .contracts
+        .values()
+        .map(|code| code.original_bytes())
+        .chain(
+            // cache state does not have all the contracts, especially when
+            // a contract is created within the block
+            // the contract only exists in bundle state, therefore we need
+            // to include them as well
+            bundle_state.contracts.values().map(|code| code.original_bytes()),
+        )
+        .collect();
+
+    // Grab all account proofs for the data accessed during block execution.
+    //
+    // Note: We grab *all* accounts in the cache here, as the `BundleState` prunes
+    // referenced accounts + storage slots.
+    let mut hashed_state = db.database.hashed_post_state(&bundle_state);
+    for (address, account) in db.cache.accounts {
+        let hashed_address = keccak256(address);
+        hashed_state
+            .accounts
+            .insert(hashed_address, account.account.as_ref().map(|a| a.info.clone().into()));
+
+        let storage = hashed_state
+            .storages
+            .entry(hashed_address)
+            .or_insert_with(|| HashedStorage::new(account.status.was_destroyed()));
+
+        if let Some(account) = account.account {
+            state_preimages.push(alloy_rlp::encode(address).into());
+
+            for (slot, value) in account.storage {
+                let slot = B256::from(slot);
+                let hashed_slot = keccak256(slot);
+                storage.storage.insert(hashed_slot, value);
+
+                state_preimages.push(alloy_rlp::encode(slot).into());
+            }
+        }
+    }
+
+    // Generate an execution witness for the aggregated state of accessed accounts.
+    // Destruct the cache database to retrieve the state provider.
+    let state_provider = db.database.into_inner();
+    let state = state_provider.witness(Default::default(), hashed_state.clone())?;
+
+    let witness = ExecutionWitness { state, codes, keys: state_preimages, ..Default::default() };
+
+    Ok(ReExecutedBlock { witness, bundle_state, hashed_state, state_provider })
+}
+
 /// Generates a witness for the given block and saves it to a file.
 #[derive(Debug)]
 pub struct InvalidBlockWitnessHook<P, E> {
@@ -149,76 +245,13 @@ where
     where
         N: NodePrimitives,
     {
-        // TODO(alexey): unify with `DebugApi::debug_execution_witness`
-
-        let mut executor = self.evm_config.batch_executor(StateProviderDatabase::new(
-            self.provider.state_by_block_hash(parent_header.hash())?,
-        ));
-
-        executor.execute_one(block)?;
-
-        // Take the bundle state
-        let mut db = executor.into_state();
-        let mut bundle_state = db.take_bundle();
-
-        // Initialize a map of preimages.
-        let mut state_preimages = Vec::default();
-
-        // Get codes
-        let codes = db
-            .cache
-            .contracts
-            .values()
-            .map(|code| code.original_bytes())
-            .chain(
-                // cache state does not have all the contracts, especially when
-                // a contract is created within the block
-                // the contract only exists in bundle state, therefore we need
-                // to include them as well
-                bundle_state.contracts.values().map(|code| code.original_bytes()),
-            )
-            .collect();
-
-        // Grab all account proofs for the data accessed during block execution.
-        //
-        // Note: We grab *all* accounts in the cache here, as the `BundleState` prunes
-        // referenced accounts + storage slots.
-        let mut hashed_state = db.database.hashed_post_state(&bundle_state);
-        for (address, account) in db.cache.accounts {
-            let hashed_address = keccak256(address);
-            hashed_state
-                .accounts
-                .insert(hashed_address, account.account.as_ref().map(|a| a.info.clone().into()));
-
-            let storage = hashed_state
-                .storages
-                .entry(hashed_address)
-                .or_insert_with(|| HashedStorage::new(account.status.was_destroyed()));
-
-            if let Some(account) = account.account {
-                state_preimages.push(alloy_rlp::encode(address).into());
-
-                for (slot, value) in account.storage {
-                    let slot = B256::from(slot);
-                    let hashed_slot = keccak256(slot);
-                    storage.storage.insert(hashed_slot, value);
-
-                    state_preimages.push(alloy_rlp::encode(slot).into());
-                }
-            }
-        }
-
-        // Generate an execution witness for the aggregated state of accessed accounts.
-        // Destruct the cache database to retrieve the state provider.
-        let state_provider = db.database.into_inner();
-        let state = state_provider.witness(Default::default(), hashed_state.clone())?;
+        let ReExecutedBlock { witness, mut bundle_state, hashed_state, state_provider } =
+            re_execute_block(&self.provider, &self.evm_config, parent_header, block)?;
 
         // Write the witness to the output directory.
-        let response =
-            ExecutionWitness { state, codes, keys: state_preimages, ..Default::default() };
         let re_executed_witness_path = self.save_file(
             format!("{}_{}.witness.re_executed.json", block.number(), block.hash()),
-            &response,
+            &witness,
         )?;
         if let Some(healthy_node_client) = &self.healthy_node_client {
             // Compare the witness against the healthy node.
@@ -236,9 +269,9 @@ where
             )?;
 
             // If the witnesses are different, write the diff to the output directory.
-            if response != healthy_node_witness {
+            if witness != healthy_node_witness {
                 let filename = format!("{}_{}.witness.diff", block.number(), block.hash());
-                let diff_path = self.save_diff(filename, &response, &healthy_node_witness)?;
+                let diff_path = self.save_diff(filename, &witness, &healthy_node_witness)?;
                 warn!(
                     target: "engine::invalid_block_hooks::witness",
                     diff_path = %diff_path.display(),
@@ -0,0 +1,89 @@
+use crate::{EngineApiValidator, PayloadValidator};
+use reth_errors::ConsensusError;
+use reth_payload_primitives::{
+    EngineApiMessageVersion, EngineObjectValidationError, InvalidPayloadAttributesError,
+    NewPayloadError, PayloadAttributesValidator, PayloadOrAttributes, PayloadTypes,
+};
+use reth_primitives_traits::{Block, RecoveredBlock};
+use reth_trie_common::HashedPostState;
+
+/// Wraps a validator of the engine API and additionally applies a [`PayloadAttributesValidator`]
+/// to the payload attributes after the checks of the wrapped validator passed.
+///
+/// This allows enforcing custom rules on the payload attributes of any chain without replacing
+/// its validator.
+#[derive(Debug, Clone)]
+pub struct WithAttributesValidator<V, A> {
+    /// The wrapped validator.
+    inner: V,
+    /// The additional validation of the payload attributes.
+    attributes_validator: A,
+}
+
+impl<V, A> WithAttributesValidator<V, A> {
+    /// Creates a new validator that applies `attributes_validator` on top of `inner`.
+    pub const fn new(inner: V, attributes_validator: A) -> Self {
+        Self { inner, attributes_validator }
+    }
+
+    /// Returns the wrapped validator.
+    pub const fn inner(&self) -> &V {
+        &self.inner
+    }
+}
+
+impl<Types, V, A> PayloadValidator<Types> for WithAttributesValidator<V, A>
+where
+    Types: PayloadTypes,
+    V: PayloadValidator<Types>,
+    A: Send + Sync + Unpin + 'static,
+{
+    type Block = V::Block;
+
+    fn ensure_well_formed_payload(
+        &self,
+        payload: Types::ExecutionData,
+    ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
+        self.inner.ensure_well_formed_payload(payload)
+    }
+
+    fn validate_block_post_execution_with_hashed_state(
+        &self,
+        state_updates: &HashedPostState,
+        block: &RecoveredBlock<Self::Block>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution_with_hashed_state(state_updates, block)
+    }
+
+    fn validate_payload_attributes_against_header(
+        &self,
+        attr: &Types::PayloadAttributes,
+        header: &<Self::Block as Block>::Header,
+    ) -> Result<(), InvalidPayloadAttributesError> {
+        self.inner.validate_payload_attributes_against_header(attr, header)
+    }
+}
+
+impl<Types, V, A> EngineApiValidator<Types> for WithAttributesValidator<V, A>
+where
+    Types: PayloadTypes,
+    V: EngineApiValidator<Types>,
+    A: PayloadAttributesValidator<Types::PayloadAttributes> + Unpin,
+{
+    fn validate_version_specific_fields(
+        &self,
+        version: EngineApiMessageVersion,
+        payload_or_attrs: PayloadOrAttributes<'_, Types::ExecutionData, Types::PayloadAttributes>,
+    ) -> Result<(), EngineObjectValidationError> {
+        self.inner.validate_version_specific_fields(version, payload_or_attrs)
+    }
+
+    fn ensure_well_formed_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &Types::PayloadAttributes,
+    ) -> Result<(), EngineObjectValidationError> {
+        self.inner.ensure_well_formed_attributes(version, attributes)?;
+        self.attributes_validator.validate_payload_attributes(version, attributes)
+    }
+}
@@ -0,0 +1,126 @@
+//! Tracking of the engine API usage of the consensus client.
+
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+use alloy_rpc_types_engine::ClientVersionV1;
+use parking_lot::Mutex;
+use std::time::Instant;
+
+/// The engine API usage of the consensus client, as observed by the execution client.
+///
+/// This helps to debug mismatches between the consensus and execution client, for example a
+/// consensus client that still uses the methods of a previous fork after a hardfork.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineClientInfo {
+    /// The capabilities announced by the consensus client in its latest
+    /// `engine_exchangeCapabilities` call, `None` if it never exchanged capabilities.
+    pub capabilities: Option<Vec<String>>,
+    /// The announced capabilities that are not supported by the execution client.
+    pub unsupported_capabilities: Vec<String>,
+    /// The capabilities of the execution client that were not announced by the consensus client.
+    pub missing_capabilities: Vec<String>,
+    /// The version the consensus client identified with in `engine_getClientVersionV1`.
+    pub client_version: Option<ClientVersionV1>,
+    /// Usage of the versioned engine API methods, by method name.
+    pub methods: BTreeMap<String, EngineMethodUsage>,
+}
+
+/// Usage of a versioned engine API method by the consensus client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineMethodUsage {
+    /// Number of calls of the method.
+    pub calls: u64,
+    /// Number of calls for which the method version was deprecated by the active fork.
+    pub deprecated_calls: u64,
+    /// Seconds elapsed since the latest call.
+    pub secs_since_last_call: u64,
+}
+
+/// A cloneable handle that records the engine API usage of the consensus client.
+///
+/// All clones share the same [`EngineClientInfo`].
+#[derive(Debug, Clone, Default)]
+pub struct EngineClientTracker {
+    inner: Arc<Mutex<EngineClientTrackerInner>>,
+}
+
+#[derive(Debug, Default)]
+struct EngineClientTrackerInner {
+    info: EngineClientInfo,
+    /// Time of the latest call, by method name.
+    last_calls: BTreeMap<String, Instant>,
+}
+
+impl EngineClientTracker {
+    /// Records the capabilities announced by the consensus client, given the capabilities
+    /// supported by the execution client.
+    pub fn on_exchange_capabilities(&self, capabilities: Vec<String>, supported: &[String]) {
+        let info = &mut self.inner.lock().info;
+        info.unsupported_capabilities =
+            capabilities.iter().filter(|cap| !supported.contains(cap)).cloned().collect();
+        info.missing_capabilities =
+            supported.iter().filter(|cap| !capabilities.contains(cap)).cloned().collect();
+        info.missing_capabilities.sort_unstable();
+        info.capabilities = Some(capabilities);
+    }
+
+    /// Records the version the consensus client identified with.
+    pub fn on_client_version(&self, client: ClientVersionV1) {
+        self.inner.lock().info.client_version = Some(client);
+    }
+
+    /// Records a call of a versioned engine API method.
+    ///
+    /// Returns `true` if the method version is deprecated and this is the first deprecated call
+    /// of the method, so the caller can warn about it once.
+    pub fn on_call(&self, method: &str, deprecated: bool) -> bool {
+        let mut inner = self.inner.lock();
+        inner.last_calls.insert(method.into(), Instant::now());
+        let usage = inner.info.methods.entry(method.into()).or_default();
+        usage.calls += 1;
+        if deprecated {
+            usage.deprecated_calls += 1;
+        }
+        deprecated && usage.deprecated_calls == 1
+    }
+
+    /// Returns the recorded engine API usage of the consensus client.
+    pub fn info(&self) -> EngineClientInfo {
+        let inner = self.inner.lock();
+        let mut info = inner.info.clone();
+        for (method, usage) in &mut info.methods {
+            usage.secs_since_last_call =
+                inner.last_calls.get(method).map(|at| at.elapsed().as_secs()).unwrap_or_default();
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn track_client_info() {
+        let tracker = EngineClientTracker::default();
+        let supported = vec!["engine_newPayloadV3".to_string(), "engine_newPayloadV4".to_string()];
+        tracker.clone().on_exchange_capabilities(
+            vec!["engine_newPayloadV3".to_string(), "engine_newPayloadV5".to_string()],
+            &supported,
+        );
+
+        assert!(!tracker.on_call("engine_newPayloadV4", false));
+        assert!(tracker.on_call("engine_newPayloadV3", true));
+        assert!(!tracker.on_call("engine_newPayloadV3", true));
+
+        let info = tracker.info();
+        assert_eq!(info.unsupported_capabilities, vec!["engine_newPayloadV5".to_string()]);
+        assert_eq!(info.missing_capabilities, vec!["engine_newPayloadV4".to_string()]);
+        assert_eq!(info.methods["engine_newPayloadV3"].calls, 2);
+        assert_eq!(info.methods["engine_newPayloadV3"].deprecated_calls, 2);
+        assert_eq!(info.methods["engine_newPayloadV4"].deprecated_calls, 0);
+        assert_eq!(info.methods["engine_newPayloadV4"].secs_since_last_call, 0);
+    }
+}
@@ -9,6 +9,23 @@ pub const DEFAULT_MEMORY_BLOCK_BUFFER_TARGET: u64 = 2;
 /// Default maximum concurrency for proof tasks
 pub const DEFAULT_MAX_PROOF_TASK_CONCURRENCY: u64 = 256;
 
+/// Default minimum distance between the local tip and the target block that triggers a backfill
+/// sync instead of downloading the missing blocks for live sync.
+///
+/// E.g.: Local head `block.number` is 100 and the forkchoice head `block.number` is 133 (more than
+/// an epoch has slots), then this exceeds the threshold at which the pipeline should be used to
+/// backfill this gap.
+pub const DEFAULT_BACKFILL_THRESHOLD: u64 = alloy_eips::merge::EPOCH_SLOTS;
+
+/// Default number of blocks added to the backfill threshold right after a backfill sync finished.
+pub const DEFAULT_BACKFILL_HYSTERESIS: u64 = 8;
+
+/// Default number of fork choice head changes kept in the fork choice history of the engine tree.
+pub const DEFAULT_FORK_CHOICE_HISTORY_SIZE: usize = 256;
+
+/// Default maximum number of blocks with a deferred state root verification in progress.
+pub const DEFAULT_DEFERRED_STATE_ROOT_WINDOW: u64 = 4;
+
 /// Default number of reserved CPU cores for non-reth processes.
 ///
 /// This will be deducted from the thread count of main reth global threadpool.
@@ -36,6 +53,44 @@ pub fn has_enough_parallelism() -> bool {
     false
 }
 
+/// Thresholds that control when the engine tree switches from live sync to a backfill sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillThresholds {
+    /// Minimum distance between the local tip and the target block that triggers a backfill sync.
+    pub threshold: u64,
+    /// Number of blocks added to the threshold after a backfill sync finished, until the node is
+    /// synced to the head of the consensus layer again.
+    ///
+    /// This prevents switching back and forth between live sync and backfill sync if the
+    /// consensus layer keeps sending blocks slightly ahead of the local tip while catching up.
+    pub hysteresis: u64,
+}
+
+impl BackfillThresholds {
+    /// Returns the effective threshold, including the hysteresis if a backfill sync finished
+    /// recently.
+    pub const fn effective_threshold(&self, after_backfill: bool) -> u64 {
+        if after_backfill {
+            self.threshold.saturating_add(self.hysteresis)
+        } else {
+            self.threshold
+        }
+    }
+
+    /// Returns true if the distance between the local tip and the block exceeds the effective
+    /// threshold.
+    pub const fn is_exceeded(&self, local_tip: u64, block: u64, after_backfill: bool) -> bool {
+        block > local_tip && block - local_tip > self.effective_threshold(after_backfill)
+    }
+}
+
+impl Default for BackfillThresholds {
+    fn default() -> Self {
+        Self { threshold: DEFAULT_BACKFILL_THRESHOLD, hysteresis: DEFAULT_BACKFILL_HYSTERESIS }
+    }
+}
+
 /// The configuration of the engine tree.
 #[derive(Debug, Clone)]
 pub struct TreeConfig {
@@ -47,6 +102,11 @@ pub struct TreeConfig {
     ///
     /// Note: this should be less than or equal to `persistence_threshold`.
     memory_block_buffer_target: u64,
+    /// Maximum estimated size in bytes of the canonical blocks kept only in memory.
+    ///
+    /// If set, persistence is triggered once the in-memory blocks exceed this budget, even if
+    /// `persistence_threshold` is not reached yet.
+    in_memory_budget: Option<u64>,
     /// Number of pending blocks that cannot be executed due to missing parent and
     /// are kept in cache.
     block_buffer_limit: u32,
@@ -95,6 +155,22 @@ pub struct TreeConfig {
     /// where immediate payload regeneration is desired despite the head not changing or moving to
     /// an ancestor.
     always_process_payload_attributes_on_canonical_head: bool,
+    /// Thresholds that control when to switch from live sync to a backfill sync.
+    backfill_thresholds: BackfillThresholds,
+    /// Number of canonical head changes kept in the fork choice history.
+    fork_choice_history_size: usize,
+    /// File the fork choice history is persisted to, if any.
+    #[cfg(feature = "std")]
+    fork_choice_history_path: Option<std::path::PathBuf>,
+    /// Whether to accept executed payloads before their state root is verified.
+    ///
+    /// The state root is then verified in the background and the block is reverted if it doesn't
+    /// match. This trades safety for latency and must only be used with a trusted consensus
+    /// layer.
+    deferred_state_root: bool,
+    /// Maximum number of blocks with a deferred state root verification in progress. Once
+    /// reached, state roots are verified before the payload is accepted.
+    deferred_state_root_window: u64,
 }
 
 impl Default for TreeConfig {
@@ -102,6 +178,7 @@ impl Default for TreeConfig {
         Self {
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            in_memory_budget: None,
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
@@ -117,6 +194,12 @@ impl Default for TreeConfig {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            backfill_thresholds: BackfillThresholds::default(),
+            fork_choice_history_size: DEFAULT_FORK_CHOICE_HISTORY_SIZE,
+            #[cfg(feature = "std")]
+            fork_choice_history_path: None,
+            deferred_state_root: false,
+            deferred_state_root_window: DEFAULT_DEFERRED_STATE_ROOT_WINDOW,
         }
     }
 }
@@ -146,6 +229,7 @@ impl TreeConfig {
         Self {
             persistence_threshold,
             memory_block_buffer_target,
+            in_memory_budget: None,
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
@@ -161,6 +245,15 @@ impl TreeConfig {
             precompile_cache_disabled,
             state_root_fallback,
             always_process_payload_attributes_on_canonical_head,
+            backfill_thresholds: BackfillThresholds {
+                threshold: DEFAULT_BACKFILL_THRESHOLD,
+                hysteresis: DEFAULT_BACKFILL_HYSTERESIS,
+            },
+            fork_choice_history_size: DEFAULT_FORK_CHOICE_HISTORY_SIZE,
+            #[cfg(feature = "std")]
+            fork_choice_history_path: None,
+            deferred_state_root: false,
+            deferred_state_root_window: DEFAULT_DEFERRED_STATE_ROOT_WINDOW,
         }
     }
 
@@ -174,6 +267,11 @@ impl TreeConfig {
         self.memory_block_buffer_target
     }
 
+    /// Return the in-memory budget in bytes, if any.
+    pub const fn in_memory_budget(&self) -> Option<u64> {
+        self.in_memory_budget
+    }
+
     /// Return the block buffer limit.
     pub const fn block_buffer_limit(&self) -> u32 {
         self.block_buffer_limit
@@ -257,6 +355,76 @@ impl TreeConfig {
         self.always_process_payload_attributes_on_canonical_head
     }
 
+    /// Returns the thresholds that control when to switch from live sync to a backfill sync.
+    pub const fn backfill_thresholds(&self) -> BackfillThresholds {
+        self.backfill_thresholds
+    }
+
+    /// Setter for the thresholds that control when to switch from live sync to a backfill sync.
+    pub const fn with_backfill_thresholds(
+        mut self,
+        backfill_thresholds: BackfillThresholds,
+    ) -> Self {
+        self.backfill_thresholds = backfill_thresholds;
+        self
+    }
+
+    /// Returns the number of canonical head changes kept in the fork choice history.
+    pub const fn fork_choice_history_size(&self) -> usize {
+        self.fork_choice_history_size
+    }
+
+    /// Setter for the number of canonical head changes kept in the fork choice history.
+    pub const fn with_fork_choice_history_size(mut self, fork_choice_history_size: usize) -> Self {
+        self.fork_choice_history_size = fork_choice_history_size;
+        self
+    }
+
+    /// Returns the file the fork choice history is persisted to, if any.
+    #[cfg(feature = "std")]
+    pub fn fork_choice_history_path(&self) -> Option<&std::path::Path> {
+        self.fork_choice_history_path.as_deref()
+    }
+
+    /// Setter for the file the fork choice history is persisted to.
+    ///
+    /// The history is loaded from this file on startup and every recorded head change is
+    /// appended to it.
+    #[cfg(feature = "std")]
+    pub fn with_fork_choice_history_path(
+        mut self,
+        fork_choice_history_path: Option<std::path::PathBuf>,
+    ) -> Self {
+        self.fork_choice_history_path = fork_choice_history_path;
+        self
+    }
+
+    /// Returns whether payloads are accepted before their state root is verified.
+    pub const fn deferred_state_root(&self) -> bool {
+        self.deferred_state_root
+    }
+
+    /// Setter for whether payloads are accepted before their state root is verified.
+    pub const fn with_deferred_state_root(mut self, deferred_state_root: bool) -> Self {
+        self.deferred_state_root = deferred_state_root;
+        self
+    }
+
+    /// Returns the maximum number of blocks with a deferred state root verification in progress.
+    pub const fn deferred_state_root_window(&self) -> u64 {
+        self.deferred_state_root_window
+    }
+
+    /// Setter for the maximum number of blocks with a deferred state root verification in
+    /// progress.
+    pub const fn with_deferred_state_root_window(
+        mut self,
+        deferred_state_root_window: u64,
+    ) -> Self {
+        self.deferred_state_root_window = deferred_state_root_window;
+        self
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -272,6 +440,12 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for the in-memory budget in bytes.
+    pub const fn with_in_memory_budget(mut self, in_memory_budget: Option<u64>) -> Self {
+        self.in_memory_budget = in_memory_budget;
+        self
+    }
+
     /// Setter for block buffer limit.
     pub const fn with_block_buffer_limit(mut self, block_buffer_limit: u32) -> Self {
         self.block_buffer_limit = block_buffer_limit;
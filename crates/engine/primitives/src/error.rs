@@ -45,3 +45,8 @@ impl BeaconForkChoiceUpdateError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Thrown when the engine task is unavailable/stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("beacon consensus engine task stopped")]
+pub struct EngineUnavailable;
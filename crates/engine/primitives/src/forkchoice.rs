@@ -211,6 +211,31 @@ impl AsRef<B256> for ForkchoiceStateHash {
     }
 }
 
+/// A change of the canonical head, caused by a forkchoice update or a new canonical block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceHistoryEntry {
+    /// Hash of the canonical head before the change.
+    pub old_head: B256,
+    /// Number of the canonical head before the change.
+    pub old_head_number: u64,
+    /// Hash of the canonical head after the change.
+    pub new_head: B256,
+    /// Number of the canonical head after the change.
+    pub new_head_number: u64,
+    /// Number of canonical blocks that were reorged out, `0` if the new head extends the old one.
+    pub reorg_depth: u64,
+    /// Unix timestamp in seconds of the change.
+    pub timestamp: u64,
+}
+
+impl ForkchoiceHistoryEntry {
+    /// Returns true if the head change reorged out canonical blocks.
+    pub const fn is_reorg(&self) -> bool {
+        self.reorg_depth > 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
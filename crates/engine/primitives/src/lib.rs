@@ -25,17 +25,27 @@ use serde::{de::DeserializeOwned, Serialize};
 pub use reth_evm::{ConfigureEngineEvm, ExecutableTxIterator};
 pub use reth_payload_primitives::ExecutionPayload;
 
+mod attributes_validator;
+pub use attributes_validator::WithAttributesValidator;
+
 mod error;
 pub use error::*;
 
 mod forkchoice;
-pub use forkchoice::{ForkchoiceStateHash, ForkchoiceStateTracker, ForkchoiceStatus};
+pub use forkchoice::{
+    ForkchoiceHistoryEntry, ForkchoiceStateHash, ForkchoiceStateTracker, ForkchoiceStatus,
+};
 
 #[cfg(feature = "std")]
 mod message;
 #[cfg(feature = "std")]
 pub use message::*;
 
+#[cfg(feature = "std")]
+mod client_info;
+#[cfg(feature = "std")]
+pub use client_info::{EngineClientInfo, EngineClientTracker, EngineMethodUsage};
+
 mod event;
 pub use event::*;
 
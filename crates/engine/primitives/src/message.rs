@@ -1,6 +1,9 @@
 use crate::{
-    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    error::BeaconForkChoiceUpdateError, BackfillThresholds, BeaconOnNewPayloadError,
+    EngineClientTracker, EngineUnavailable, ExecutionPayload, ForkchoiceHistoryEntry,
+    ForkchoiceStatus,
 };
+use alloy_primitives::B256;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -164,6 +167,28 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Message to manually start a backfill sync to the given block.
+    TriggerBackfill {
+        /// The hash of the block to sync to.
+        target: B256,
+        /// The sender for returning whether the backfill sync was started.
+        tx: oneshot::Sender<bool>,
+    },
+    /// Message to update the thresholds that control when to switch from live sync to a backfill
+    /// sync.
+    BackfillThresholds {
+        /// The new thresholds, or `None` to keep the current ones.
+        update: Option<BackfillThresholds>,
+        /// The sender for returning the thresholds in effect before the update.
+        tx: oneshot::Sender<BackfillThresholds>,
+    },
+    /// Message to fetch the most recent canonical head changes.
+    ForkchoiceHistory {
+        /// The maximum number of entries to return.
+        limit: usize,
+        /// The sender for returning the entries, most recent first.
+        tx: oneshot::Sender<Vec<ForkchoiceHistoryEntry>>,
+    },
 }
 
 impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
@@ -187,6 +212,15 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload_attrs.is_some()
                 )
             }
+            Self::TriggerBackfill { target, .. } => {
+                write!(f, "TriggerBackfill {{ target: {target} }}")
+            }
+            Self::BackfillThresholds { update, .. } => {
+                write!(f, "BackfillThresholds {{ update: {update:?} }}")
+            }
+            Self::ForkchoiceHistory { limit, .. } => {
+                write!(f, "ForkchoiceHistory {{ limit: {limit} }}")
+            }
         }
     }
 }
@@ -200,6 +234,8 @@ where
     Payload: PayloadTypes,
 {
     to_engine: UnboundedSender<BeaconEngineMessage<Payload>>,
+    /// Records the engine API usage of the consensus client, shared by all clones of the handle.
+    client_tracker: Option<EngineClientTracker>,
 }
 
 impl<Payload> ConsensusEngineHandle<Payload>
@@ -208,7 +244,18 @@ where
 {
     /// Creates a new beacon consensus engine handle.
     pub const fn new(to_engine: UnboundedSender<BeaconEngineMessage<Payload>>) -> Self {
-        Self { to_engine }
+        Self { to_engine, client_tracker: None }
+    }
+
+    /// Records the engine API usage of the consensus client with the given tracker.
+    pub fn with_client_tracker(mut self, client_tracker: EngineClientTracker) -> Self {
+        self.client_tracker = Some(client_tracker);
+        self
+    }
+
+    /// Returns the tracker of the engine API usage of the consensus client, if configured.
+    pub const fn client_tracker(&self) -> Option<&EngineClientTracker> {
+        self.client_tracker.as_ref()
     }
 
     /// Sends a new payload message to the beacon consensus engine and waits for a response.
@@ -240,6 +287,50 @@ where
             .await?)
     }
 
+    /// Requests the engine to start a backfill sync to the given block.
+    ///
+    /// Returns `false` if the engine is already running or about to start a backfill sync.
+    pub async fn trigger_backfill(&self, target: B256) -> Result<bool, EngineUnavailable> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::TriggerBackfill { target, tx });
+        rx.await.map_err(|_| EngineUnavailable)
+    }
+
+    /// Returns the thresholds that control when the engine switches from live sync to a backfill
+    /// sync.
+    pub async fn backfill_thresholds(&self) -> Result<BackfillThresholds, EngineUnavailable> {
+        self.send_backfill_thresholds(None).await
+    }
+
+    /// Updates the thresholds that control when the engine switches from live sync to a backfill
+    /// sync and returns the previous ones.
+    pub async fn set_backfill_thresholds(
+        &self,
+        thresholds: BackfillThresholds,
+    ) -> Result<BackfillThresholds, EngineUnavailable> {
+        self.send_backfill_thresholds(Some(thresholds)).await
+    }
+
+    async fn send_backfill_thresholds(
+        &self,
+        update: Option<BackfillThresholds>,
+    ) -> Result<BackfillThresholds, EngineUnavailable> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::BackfillThresholds { update, tx });
+        rx.await.map_err(|_| EngineUnavailable)
+    }
+
+    /// Returns up to `limit` of the most recent canonical head changes and reorgs, most recent
+    /// first.
+    pub async fn fork_choice_history(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<ForkchoiceHistoryEntry>, EngineUnavailable> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::ForkchoiceHistory { limit, tx });
+        rx.await.map_err(|_| EngineUnavailable)
+    }
+
     /// Sends a forkchoice update message to the beacon consensus engine and returns the receiver to
     /// wait for a response.
     fn send_fork_choice_updated(
@@ -12,7 +12,7 @@ use reth_provider::{
 use reth_revm::db::BundleState;
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput, WitnessSink,
 };
 use revm_primitives::map::DefaultHashBuilder;
 use std::time::Duration;
@@ -228,6 +228,15 @@ impl<S: StateProofProvider> StateProofProvider for CachedStateProvider<S> {
     ) -> ProviderResult<Vec<alloy_primitives::Bytes>> {
         self.state_provider.witness(input, target)
     }
+
+    fn witness_into(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        self.state_provider.witness_into(input, target, witness)
+    }
 }
 
 impl<S: StorageRootProvider> StorageRootProvider for CachedStateProvider<S> {
@@ -0,0 +1,137 @@
+use crate::tree::payload_processor::executor::WorkloadExecutor;
+use alloy_eips::eip1898::BlockWithParent;
+use alloy_primitives::{map::HashSet, B256};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_trie::updates::TrieUpdates;
+use std::{
+    panic::AssertUnwindSafe,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+/// The result of a state root verification that ran in the background.
+#[derive(Debug)]
+pub(crate) struct DeferredStateRootOutcome {
+    /// The block the state root was computed for.
+    pub(crate) block: BlockWithParent,
+    /// The computed state root and trie updates.
+    pub(crate) state_root: ProviderResult<(B256, TrieUpdates)>,
+}
+
+/// Tracks the blocks that were accepted before their state root was verified.
+///
+/// The state roots are computed on the blocking pool of the [`WorkloadExecutor`] and the outcomes
+/// are collected with [`DeferredStateRoots::try_recv`].
+#[derive(Debug)]
+pub(crate) struct DeferredStateRoots {
+    /// Hashes of the blocks with a verification in progress.
+    pending: HashSet<B256>,
+    /// Sender that is handed to the verification tasks.
+    tx: Sender<DeferredStateRootOutcome>,
+    /// Receiver of the verification outcomes.
+    rx: Receiver<DeferredStateRootOutcome>,
+}
+
+impl Default for DeferredStateRoots {
+    fn default() -> Self {
+        let (tx, rx) = channel();
+        Self { pending: HashSet::default(), tx, rx }
+    }
+}
+
+impl DeferredStateRoots {
+    /// Returns the number of blocks with a verification in progress.
+    pub(crate) fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if there are no verifications in progress.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns true if the state root of the block hasn't been verified yet.
+    pub(crate) fn is_pending(&self, hash: &B256) -> bool {
+        self.pending.contains(hash)
+    }
+
+    /// Spawns a blocking task that computes the state root and trie updates of the block with
+    /// `compute_state_root`.
+    ///
+    /// A panic of the computation is reported as an error, so the block doesn't stay pending.
+    pub(crate) fn spawn<F>(
+        &mut self,
+        executor: &WorkloadExecutor,
+        block: BlockWithParent,
+        compute_state_root: F,
+    ) where
+        F: FnOnce() -> ProviderResult<(B256, TrieUpdates)> + Send + 'static,
+    {
+        let tx = self.tx.clone();
+        executor.spawn_blocking(move || {
+            let state_root = std::panic::catch_unwind(AssertUnwindSafe(compute_state_root))
+                .unwrap_or_else(|_| {
+                    Err(ProviderError::other(std::io::Error::other(
+                        "deferred state root computation panicked",
+                    )))
+                });
+            let _ = tx.send(DeferredStateRootOutcome { block, state_root });
+        });
+        self.pending.insert(block.block.hash);
+    }
+
+    /// Returns the next finished verification, if any.
+    pub(crate) fn try_recv(&mut self) -> Option<DeferredStateRootOutcome> {
+        let outcome = self.rx.try_recv().ok()?;
+        self.pending.remove(&outcome.block.block.hash);
+        Some(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use std::time::Duration;
+
+    fn recv(deferred: &mut DeferredStateRoots) -> DeferredStateRootOutcome {
+        loop {
+            if let Some(outcome) = deferred.try_recv() {
+                break outcome
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn collects_outcomes() {
+        let executor = WorkloadExecutor::default();
+        let mut deferred = DeferredStateRoots::default();
+        let block =
+            BlockWithParent { parent: B256::random(), block: BlockNumHash::new(1, B256::random()) };
+        let state_root = B256::random();
+
+        deferred.spawn(&executor, block, move || Ok((state_root, TrieUpdates::default())));
+        assert_eq!(deferred.len(), 1);
+        assert!(deferred.is_pending(&block.block.hash));
+
+        let outcome = recv(&mut deferred);
+        assert_eq!(outcome.block, block);
+        assert_eq!(outcome.state_root.unwrap().0, state_root);
+        assert!(deferred.is_empty());
+    }
+
+    #[test]
+    fn panic_resolves_as_error() {
+        let executor = WorkloadExecutor::default();
+        let mut deferred = DeferredStateRoots::default();
+        let block =
+            BlockWithParent { parent: B256::random(), block: BlockNumHash::new(1, B256::random()) };
+
+        deferred.spawn(&executor, block, || panic!("state root"));
+
+        let outcome = recv(&mut deferred);
+        assert_eq!(outcome.block, block);
+        assert!(outcome.state_root.is_err());
+        assert!(deferred.is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use alloy_eips::BlockNumHash;
+use reth_engine_primitives::ForkchoiceHistoryEntry;
+use reth_fs_util::FsPathError;
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// Keeps a bounded log of the most recent canonical head changes and reorgs.
+///
+/// The log can be persisted to a file, so that it survives restarts: the entries are loaded from
+/// the file on startup and every recorded head change is appended to it as a JSON line. The file
+/// is rewritten with only the retained entries once it holds twice as many lines as entries are
+/// kept.
+#[derive(Debug)]
+pub(crate) struct ForkchoiceHistory {
+    /// The recorded head changes, oldest first.
+    entries: VecDeque<ForkchoiceHistoryEntry>,
+    /// Maximum number of entries to keep.
+    max_entries: usize,
+    /// The file the entries are persisted to, if any.
+    file: Option<HistoryFile>,
+}
+
+impl ForkchoiceHistory {
+    /// Creates a new history that keeps at most `max_entries` head changes in memory.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        Self { entries: VecDeque::with_capacity(max_entries), max_entries, file: None }
+    }
+
+    /// Creates a new history that keeps at most `max_entries` head changes and persists them to
+    /// the file at the given path, starting with the entries that are already recorded there.
+    ///
+    /// Falls back to an in-memory history if the file can't be loaded or written.
+    pub(crate) fn with_file(max_entries: usize, path: impl Into<PathBuf>) -> Self {
+        let mut history = Self::new(max_entries);
+        let path = path.into();
+        if max_entries == 0 {
+            return history
+        }
+
+        if let Err(err) = history.load(&path) {
+            warn!(target: "engine::tree", %err, ?path, "Failed to load fork choice history");
+        }
+        match HistoryFile::rewrite(path, &history.entries) {
+            Ok(file) => history.file = Some(file),
+            Err(err) => {
+                warn!(target: "engine::tree", %err, "Failed to persist fork choice history")
+            }
+        }
+        history
+    }
+
+    /// Loads the entries recorded in the file at the given path, if it exists.
+    fn load(&mut self, path: &Path) -> Result<(), FsPathError> {
+        if !path.exists() {
+            return Ok(())
+        }
+
+        for line in reth_fs_util::read_to_string(path)?.lines() {
+            match serde_json::from_str(line) {
+                Ok(entry) => self.push(entry),
+                // a partially written last line is expected after a crash
+                Err(err) => warn!(target: "engine::tree", %err, "Skipping invalid history entry"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a change of the canonical head from `old_head` to `new_head` that reorged out
+    /// `reorg_depth` canonical blocks.
+    ///
+    /// Does nothing if the head didn't change.
+    pub(crate) fn record(
+        &mut self,
+        old_head: BlockNumHash,
+        new_head: BlockNumHash,
+        reorg_depth: u64,
+    ) {
+        if old_head == new_head || self.max_entries == 0 {
+            return
+        }
+
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        let entry = ForkchoiceHistoryEntry {
+            old_head: old_head.hash,
+            old_head_number: old_head.number,
+            new_head: new_head.hash,
+            new_head_number: new_head.number,
+            reorg_depth,
+            timestamp,
+        };
+        self.push(entry);
+        self.persist(&entry);
+    }
+
+    /// Appends the entry to the history, evicting the oldest entry if the history is full.
+    fn push(&mut self, entry: ForkchoiceHistoryEntry) {
+        if self.entries.len() == self.max_entries {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Appends the entry to the history file, if any, and compacts the file if it grew too large.
+    ///
+    /// Persistence is disabled if the file can't be written.
+    fn persist(&mut self, entry: &ForkchoiceHistoryEntry) {
+        let Some(mut file) = self.file.take() else { return };
+
+        let res = if file.lines >= self.max_entries * 2 {
+            HistoryFile::rewrite(file.path, &self.entries)
+        } else {
+            file.append(entry).map(|()| file)
+        };
+        match res {
+            Ok(file) => self.file = Some(file),
+            Err(err) => {
+                warn!(target: "engine::tree", %err, "Failed to persist fork choice history")
+            }
+        }
+    }
+
+    /// Returns up to `limit` of the most recent entries, most recent first.
+    pub(crate) fn recent(&self, limit: usize) -> Vec<ForkchoiceHistoryEntry> {
+        self.entries.iter().rev().take(limit).copied().collect()
+    }
+}
+
+/// The file a [`ForkchoiceHistory`] is persisted to, opened for appending.
+#[derive(Debug)]
+struct HistoryFile {
+    /// Path of the file.
+    path: PathBuf,
+    /// The file, opened in append mode.
+    file: File,
+    /// Number of entries in the file.
+    lines: usize,
+}
+
+impl HistoryFile {
+    /// Atomically replaces the file at the given path with the given entries and opens it for
+    /// appending.
+    fn rewrite<'a>(
+        path: PathBuf,
+        entries: impl IntoIterator<Item = &'a ForkchoiceHistoryEntry>,
+    ) -> Result<Self, FsPathError> {
+        let mut lines = 0;
+        reth_fs_util::atomic_write_file(&path, |file| {
+            let mut buf = Vec::new();
+            for entry in entries {
+                write_line(&mut buf, entry)?;
+                lines += 1;
+            }
+            file.write_all(&buf)
+        })?;
+        let file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .map_err(|err| FsPathError::open(err, &path))?;
+        Ok(Self { path, file, lines })
+    }
+
+    /// Appends the entry to the file.
+    fn append(&mut self, entry: &ForkchoiceHistoryEntry) -> Result<(), FsPathError> {
+        let mut buf = Vec::new();
+        write_line(&mut buf, entry)
+            .and_then(|()| self.file.write_all(&buf))
+            .map_err(|err| FsPathError::write(err, &self.path))?;
+        self.lines += 1;
+        Ok(())
+    }
+}
+
+/// Writes the entry as a single JSON line.
+fn write_line(buf: &mut Vec<u8>, entry: &ForkchoiceHistoryEntry) -> io::Result<()> {
+    serde_json::to_writer(&mut *buf, entry)?;
+    buf.push(b'\n');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn head(number: u64) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(number as u8))
+    }
+
+    #[test]
+    fn keeps_most_recent_entries() {
+        let mut history = ForkchoiceHistory::new(2);
+        history.record(head(1), head(1), 0);
+        assert!(history.recent(10).is_empty());
+
+        history.record(head(1), head(2), 0);
+        history.record(head(2), head(3), 0);
+        history.record(head(3), head(2), 1);
+
+        let entries = history.recent(10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].new_head_number, 2);
+        assert!(entries[0].is_reorg());
+        assert_eq!(entries[1].new_head_number, 3);
+        assert!(!entries[1].is_reorg());
+
+        assert_eq!(history.recent(1), entries[..1]);
+    }
+
+    #[test]
+    fn restores_persisted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fork-choice-history.jsonl");
+
+        let mut history = ForkchoiceHistory::with_file(3, &path);
+        for number in 1..10 {
+            history.record(head(number), head(number + 1), 0);
+        }
+        history.record(head(10), head(9), 1);
+        let entries = history.recent(10);
+        assert_eq!(entries.len(), 3);
+
+        // the file is compacted and doesn't grow beyond twice the history size
+        let lines = reth_fs_util::read_to_string(&path).unwrap().lines().count();
+        assert!(lines <= 6, "{lines}");
+
+        // a partially written entry is skipped
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"oldHead\"").unwrap();
+
+        let history = ForkchoiceHistory::with_file(3, &path);
+        assert_eq!(history.recent(10), entries);
+        assert!(history.recent(1)[0].is_reorg());
+    }
+}
@@ -10,7 +10,7 @@ use reth_provider::{
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput, WitnessSink,
 };
 use std::{
     sync::atomic::{AtomicU64, Ordering},
@@ -191,6 +191,23 @@ impl<S: StateProvider> StateProvider for InstrumentedStateProvider<S> {
         self.record_storage_fetch(start.elapsed());
         res
     }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let start = Instant::now();
+        let res = self.state_provider.basic_accounts(addresses);
+        self.total_account_fetch_latency.add_duration(start.elapsed());
+        res
+    }
+
+    fn storage_batch(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        let start = Instant::now();
+        let res = self.state_provider.storage_batch(slots);
+        self.total_storage_fetch_latency.add_duration(start.elapsed());
+        res
+    }
 }
 
 impl<S: BytecodeReader> BytecodeReader for InstrumentedStateProvider<S> {
@@ -251,6 +268,15 @@ impl<S: StateProofProvider> StateProofProvider for InstrumentedStateProvider<S>
     ) -> ProviderResult<Vec<alloy_primitives::Bytes>> {
         self.state_provider.witness(input, target)
     }
+
+    fn witness_into(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        self.state_provider.witness_into(input, target, witness)
+    }
 }
 
 impl<S: StorageRootProvider> StorageRootProvider for InstrumentedStateProvider<S> {
@@ -28,6 +28,8 @@ pub(crate) struct TreeMetrics {
     pub reorgs: Counter,
     /// The latest reorg depth
     pub latest_reorg_depth: Gauge,
+    /// Histogram of reorg depths
+    pub reorg_depth: Histogram,
 }
 
 /// Metrics for the `EngineApi`.
@@ -69,6 +71,10 @@ pub(crate) struct BlockValidationMetrics {
     pub(crate) state_root_storage_tries_updated_total: Counter,
     /// Total number of times the parallel state root computation fell back to regular.
     pub(crate) state_root_parallel_fallback_total: Counter,
+    /// Total number of blocks that were accepted before their state root was verified.
+    pub(crate) state_root_deferred_total: Counter,
+    /// Total number of deferred state root verifications that found a mismatch.
+    pub(crate) state_root_deferred_mismatch_total: Counter,
     /// Histogram of state root duration
     pub(crate) state_root_histogram: Histogram,
     /// Latest state root duration
@@ -6,16 +6,18 @@ use crate::{
     tree::{error::InsertPayloadError, metrics::EngineApiMetrics, payload_validator::TreeCtx},
 };
 use alloy_consensus::BlockHeader;
-use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
+use alloy_eips::{eip1898::BlockWithParent, BlockNumHash, NumHash};
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
+use deferred_state_root::{DeferredStateRootOutcome, DeferredStateRoots};
 use error::{InsertBlockError, InsertBlockFatalError};
+use forkchoice_history::ForkchoiceHistory;
 use persistence_state::CurrentPersistenceAction;
 use reth_chain_state::{
     CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates,
-    MemoryOverlayStateProvider, NewCanonicalChain,
+    NewCanonicalChain,
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
@@ -28,11 +30,14 @@ use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, NewPayloadError, PayloadBuilderAttributes, PayloadTypes,
 };
-use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_primitives_traits::{
+    Block, InMemorySize, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
+};
 use reth_provider::{
-    providers::ConsistentDbView, BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, ProviderError, StateProviderBox, StateProviderFactory, StateReader,
-    StateRootProvider, TransactionVariant,
+    providers::ConsistentDbView, BlockNumReader, BlockReader, ChangeSetReader, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, OverlayStateProvider, ProviderError,
+    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageChangeSetReader,
+    TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ControlFlow;
@@ -55,7 +60,9 @@ use tracing::*;
 
 mod block_buffer;
 mod cached_state;
+mod deferred_state_root;
 pub mod error;
+mod forkchoice_history;
 mod instrumented_state;
 mod invalid_headers;
 mod metrics;
@@ -80,17 +87,6 @@ use reth_trie::KeccakKeyHasher;
 
 pub mod state;
 
-/// The largest gap for which the tree will be used to sync individual blocks by downloading them.
-///
-/// This is the default threshold, and represents the distance (gap) from the local head to a
-/// new (canonical) block, e.g. the forkchoice head block. If the block distance from the local head
-/// exceeds this threshold, the pipeline will be used to backfill the gap more efficiently.
-///
-/// E.g.: Local head `block.number` is 100 and the forkchoice head `block.number` is 133 (more than
-/// an epoch has slots), then this exceeds the threshold at which the pipeline should be used to
-/// backfill this gap.
-pub(crate) const MIN_BLOCKS_FOR_PIPELINE_RUN: u64 = EPOCH_SLOTS;
-
 /// A builder for creating state providers that can be used across threads.
 #[derive(Clone, Debug)]
 pub struct StateProviderBuilder<N: NodePrimitives, P> {
@@ -122,7 +118,7 @@ where
     pub fn build(&self) -> ProviderResult<StateProviderBox> {
         let mut provider = self.provider_factory.state_by_block_hash(self.historical)?;
         if let Some(overlay) = self.overlay.clone() {
-            provider = Box::new(MemoryOverlayStateProvider::new(provider, overlay))
+            provider = OverlayStateProvider::with_trie_updates(provider, overlay).boxed()
         }
         Ok(provider)
     }
@@ -142,6 +138,8 @@ pub struct EngineApiTreeState<N: NodePrimitives> {
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Blocks that were accepted before their state root was verified.
+    deferred_state_roots: DeferredStateRoots,
 }
 
 impl<N: NodePrimitives> EngineApiTreeState<N> {
@@ -156,6 +154,7 @@ impl<N: NodePrimitives> EngineApiTreeState<N> {
             buffer: BlockBuffer::new(block_buffer_limit),
             tree_state: TreeState::new(canonical_block, engine_kind),
             forkchoice_state_tracker: ForkchoiceStateTracker::default(),
+            deferred_state_roots: DeferredStateRoots::default(),
         }
     }
 }
@@ -244,6 +243,9 @@ where
     persistence_state: PersistenceState,
     /// Flag indicating the state of the node's backfill synchronization process.
     backfill_sync_state: BackfillSyncState,
+    /// Whether a backfill sync finished and the node hasn't caught up with the consensus layer
+    /// since, in which case the backfill threshold is raised by the configured hysteresis.
+    backfill_hysteresis_active: bool,
     /// Keeps track of the state of the canonical chain that isn't persisted yet.
     /// This is intended to be accessed from external sources, such as rpc.
     canonical_in_memory_state: CanonicalInMemoryState<N>,
@@ -252,6 +254,8 @@ where
     payload_builder: PayloadBuilderHandle<T>,
     /// Configuration settings.
     config: TreeConfig,
+    /// Log of the most recent canonical head changes and reorgs.
+    fork_choice_history: ForkchoiceHistory,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
     /// The engine API variant of this handler
@@ -276,6 +280,7 @@ where
             .field("persistence", &self.persistence)
             .field("persistence_state", &self.persistence_state)
             .field("backfill_sync_state", &self.backfill_sync_state)
+            .field("backfill_hysteresis_active", &self.backfill_hysteresis_active)
             .field("canonical_in_memory_state", &self.canonical_in_memory_state)
             .field("payload_builder", &self.payload_builder)
             .field("config", &self.config)
@@ -296,8 +301,9 @@ where
         + HashedPostStateProvider
         + Clone
         + 'static,
-    <P as DatabaseProviderFactory>::Provider:
-        BlockReader<Block = N::Block, Header = N::BlockHeader>,
+    <P as DatabaseProviderFactory>::Provider: BlockReader<Block = N::Block, Header = N::BlockHeader>
+        + ChangeSetReader
+        + StorageChangeSetReader,
     C: ConfigureEvm<Primitives = N> + 'static,
     T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>,
     V: EngineValidator<T>,
@@ -329,9 +335,14 @@ where
             persistence,
             persistence_state,
             backfill_sync_state: BackfillSyncState::Idle,
+            backfill_hysteresis_active: false,
             state,
             canonical_in_memory_state,
             payload_builder,
+            fork_choice_history: match config.fork_choice_history_path() {
+                Some(path) => ForkchoiceHistory::with_file(config.fork_choice_history_size(), path),
+                None => ForkchoiceHistory::new(config.fork_choice_history_size()),
+            },
             config,
             metrics: Default::default(),
             incoming_tx,
@@ -420,6 +431,11 @@ where
                 }
             }
 
+            if let Err(err) = self.on_deferred_state_roots() {
+                error!(target: "engine::tree", %err, "Verifying deferred state roots failed");
+                return
+            }
+
             if let Err(err) = self.advance_persistence() {
                 error!(target: "engine::tree", %err, "Advancing persistence failed");
                 return
@@ -917,8 +933,8 @@ where
 
     /// Attempts to receive the next engine request.
     ///
-    /// If there's currently no persistence action in progress, this will block until a new request
-    /// is received. If there's a persistence action in progress, this will try to receive the
+    /// If there's currently no persistence action or deferred state root verification in progress,
+    /// this will block until a new request is received. Otherwise, this will try to receive the
     /// next request with a timeout to not block indefinitely and return `Ok(None)` if no request is
     /// received in time.
     ///
@@ -927,7 +943,7 @@ where
     fn try_recv_engine_message(
         &self,
     ) -> Result<Option<FromEngine<EngineApiRequest<T, N>, N::Block>>, RecvError> {
-        if self.persistence_state.in_progress() {
+        if self.persistence_state.in_progress() || !self.state.deferred_state_roots.is_empty() {
             // try to receive the next request with a timeout to not block indefinitely
             match self.incoming.recv_timeout(std::time::Duration::from_millis(500)) {
                 Ok(msg) => Ok(Some(msg)),
@@ -1025,6 +1041,115 @@ where
         Ok(())
     }
 
+    /// Handles the outcomes of the finished deferred state root verifications.
+    ///
+    /// If the state root matches, the computed trie updates are attached to the block. Otherwise
+    /// the block and all of its descendants are removed from the tree and marked as invalid. If
+    /// the block is canonical, the canonical head is reverted to its parent.
+    fn on_deferred_state_roots(&mut self) -> ProviderResult<()> {
+        while let Some(DeferredStateRootOutcome { block, state_root }) =
+            self.state.deferred_state_roots.try_recv()
+        {
+            let Some(executed) = self.state.tree_state.executed_block_by_hash(block.block.hash)
+            else {
+                // the block was removed from the tree in the meantime
+                continue
+            };
+            let expected = executed.recovered_block().state_root();
+
+            let (state_root, trie_updates) = match state_root {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    warn!(target: "engine::tree", %err, block=?block.block, "Deferred state root computation failed, computing it again");
+                    let hashed_state = executed.hashed_state().clone();
+                    self.state_provider_builder(block.parent)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block.parent.into()))?
+                        .build()?
+                        .state_root_with_updates(hashed_state)?
+                }
+            };
+
+            if state_root == expected {
+                debug!(target: "engine::tree", block=?block.block, "Verified deferred state root");
+                if let Some(executed) =
+                    self.state.tree_state.blocks_by_hash.get_mut(&block.block.hash)
+                {
+                    executed.trie.set_present(Arc::new(trie_updates));
+                }
+                continue
+            }
+
+            error!(target: "engine::tree", block=?block.block, got=?state_root, ?expected, "Deferred state root mismatch, reverting block");
+            self.metrics.block_validation.state_root_deferred_mismatch_total.increment(1);
+            self.revert_invalid_block(block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes an invalid block that was already accepted and all of its descendants from the
+    /// tree and marks them as invalid.
+    ///
+    /// If the block is canonical, the canonical chain is reorged back to its parent. The parent is
+    /// part of both the old and the new chain, so that listeners receive a regular reorg
+    /// notification that reverts the invalid blocks.
+    fn revert_invalid_block(&mut self, block: BlockWithParent) -> ProviderResult<()> {
+        if self.state.tree_state.is_canonical(block.block.hash) {
+            // collect the canonical blocks from the head down to the invalid block
+            let mut old = Vec::new();
+            let mut current_hash = self.state.tree_state.canonical_block_hash();
+            while current_hash != block.parent {
+                let executed = self
+                    .state
+                    .tree_state
+                    .executed_block_by_hash(current_hash)
+                    .ok_or(ProviderError::BlockHashNotFound(current_hash))?;
+                current_hash = executed.recovered_block().parent_hash();
+                old.push(executed.block.clone());
+            }
+
+            let parent = match self.state.tree_state.executed_block_by_hash(block.parent) {
+                Some(parent) => parent.clone(),
+                None => {
+                    // the parent is already persisted
+                    let parent = self
+                        .canonical_block_by_hash(block.parent)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block.parent.into()))?;
+                    let trie = self
+                        .state
+                        .tree_state
+                        .persisted_trie_updates
+                        .get(&block.parent)
+                        .map(|(_, trie)| ExecutedTrieUpdates::Present(trie.clone()))
+                        .unwrap_or(ExecutedTrieUpdates::Missing);
+                    ExecutedBlockWithTrieUpdates { block: parent, trie }
+                }
+            };
+            old.push(parent.block.clone());
+            old.reverse();
+
+            self.on_canonical_chain_update(NewCanonicalChain::Reorg { new: vec![parent], old });
+        }
+
+        let removed = self.state.tree_state.remove_with_descendants(block.block.hash);
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+
+        self.state.invalid_headers.insert(block);
+        for descendant in removed.iter().skip(1) {
+            self.state
+                .invalid_headers
+                .insert_with_invalid_ancestor(descendant.recovered_block().hash(), block);
+        }
+
+        if let Some(invalid) = removed.into_iter().next() {
+            self.emit_event(ConsensusEngineEvent::InvalidBlock(Box::new(
+                invalid.recovered_block().sealed_block().clone(),
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Handles a message from the engine.
     fn on_engine_message(
         &mut self,
@@ -1084,6 +1209,12 @@ where
                                         .forkchoice_state_tracker
                                         .set_latest(state, res.outcome.forkchoice_status());
 
+                                    // caught up with the CL, no need for the raised backfill
+                                    // threshold anymore
+                                    if res.outcome.forkchoice_status().is_valid() {
+                                        self.backfill_hysteresis_active = false;
+                                    }
+
                                     // emit an event about the handled FCU
                                     self.emit_event(ConsensusEngineEvent::ForkchoiceUpdated(
                                         state,
@@ -1126,6 +1257,21 @@ where
                                 // handle the event if any
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
+                            BeaconEngineMessage::TriggerBackfill { target, tx } => {
+                                let _ = tx.send(self.on_trigger_backfill(target));
+                            }
+                            BeaconEngineMessage::BackfillThresholds { update, tx } => {
+                                let current = self.config.backfill_thresholds();
+                                if let Some(thresholds) = update {
+                                    debug!(target: "engine::tree", ?thresholds, "updating backfill thresholds");
+                                    self.config =
+                                        self.config.clone().with_backfill_thresholds(thresholds);
+                                }
+                                let _ = tx.send(current);
+                            }
+                            BeaconEngineMessage::ForkchoiceHistory { limit, tx } => {
+                                let _ = tx.send(self.fork_choice_history.recent(limit));
+                            }
                         }
                     }
                 }
@@ -1158,6 +1304,7 @@ where
     ) -> Result<(), InsertBlockFatalError> {
         debug!(target: "engine::tree", "received backfill sync finished event");
         self.backfill_sync_state = BackfillSyncState::Idle;
+        self.backfill_hysteresis_active = true;
 
         // Pipeline unwound, memorize the invalid block and wait for CL for next sync target.
         let backfill_height = if let ControlFlow::Unwind { bad_block, target } = &ctrl {
@@ -1329,10 +1476,26 @@ where
         );
     }
 
+    /// Handles a manual request to start a backfill sync to the given block.
+    ///
+    /// Returns true if the backfill sync was started, which is only possible if backfill is idle
+    /// and no persistence task is running.
+    fn on_trigger_backfill(&mut self, target: B256) -> bool {
+        if !self.backfill_sync_state.is_idle() || self.persistence_state.in_progress() {
+            debug!(target: "engine::tree", %target, backfill_sync_state=?self.backfill_sync_state, "skipping manual backfill trigger");
+            return false
+        }
+
+        debug!(target: "engine::tree", %target, "manually triggering backfill sync");
+        self.emit_event(EngineApiEvent::BackfillAction(BackfillAction::Start(target.into())));
+        true
+    }
+
     /// Returns true if the canonical chain length minus the last persisted
-    /// block is greater than or equal to the persistence threshold and
-    /// backfill is not running.
-    pub const fn should_persist(&self) -> bool {
+    /// block is greater than or equal to the persistence threshold, or the
+    /// in-memory blocks exceed the configured memory budget, and backfill is
+    /// not running.
+    pub fn should_persist(&self) -> bool {
         if !self.backfill_sync_state.is_idle() {
             // can't persist if backfill is running
             return false
@@ -1340,13 +1503,25 @@ where
 
         let min_block = self.persistence_state.last_persisted_block.number;
         self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
-            self.config.persistence_threshold()
+            self.config.persistence_threshold() ||
+            self.exceeds_in_memory_budget()
+    }
+
+    /// Returns true if the estimated size of the canonical in-memory blocks exceeds the
+    /// configured in-memory budget.
+    fn exceeds_in_memory_budget(&self) -> bool {
+        self.config
+            .in_memory_budget()
+            .is_some_and(|budget| self.canonical_in_memory_state.in_memory_size() as u64 > budget)
     }
 
     /// Returns a batch of consecutive canonical blocks to persist in the range
     /// `(last_persisted_number .. canonical_head - threshold]`. The expected
     /// order is oldest -> newest.
     ///
+    /// If an in-memory budget is configured, older blocks are included as well until the
+    /// blocks that remain in memory fit into the budget.
+    ///
     /// For those blocks that didn't have the trie updates calculated, runs the state root
     /// calculation, and saves the trie updates.
     ///
@@ -1367,13 +1542,21 @@ where
         let target_number =
             canonical_head_number.saturating_sub(self.config.memory_block_buffer_target());
 
-        debug!(target: "engine::tree", ?last_persisted_number, ?canonical_head_number, ?target_number, ?current_hash, "Returning canonical blocks to persist");
+        let in_memory_budget = self.config.in_memory_budget();
+        let mut retained_size = 0u64;
+
+        debug!(target: "engine::tree", ?last_persisted_number, ?canonical_head_number, ?target_number, ?in_memory_budget, ?current_hash, "Returning canonical blocks to persist");
         while let Some(block) = self.state.tree_state.blocks_by_hash.get(&current_hash) {
             if block.recovered_block().number() <= last_persisted_number {
                 break;
             }
 
-            if block.recovered_block().number() <= target_number {
+            let exceeds_budget = in_memory_budget.is_some_and(|budget| {
+                retained_size = retained_size.saturating_add(block.size() as u64);
+                retained_size > budget
+            });
+
+            if block.recovered_block().number() <= target_number || exceeds_budget {
                 blocks_to_persist.push(block.clone());
             }
 
@@ -1383,6 +1566,14 @@ where
         // Reverse the order so that the oldest block comes first
         blocks_to_persist.reverse();
 
+        // Blocks are only persisted once their state root is verified
+        if let Some(unverified) = blocks_to_persist.iter().position(|block| {
+            self.state.deferred_state_roots.is_pending(&block.recovered_block().hash())
+        }) {
+            debug!(target: "engine::tree", block = ?blocks_to_persist[unverified].recovered_block().num_hash(), "Waiting for deferred state root verification before persisting");
+            blocks_to_persist.truncate(unverified);
+        }
+
         // Calculate missing trie updates
         for block in &mut blocks_to_persist {
             if block.trie.is_present() {
@@ -1713,10 +1904,17 @@ where
     /// Returns true if the distance from the local tip to the block is greater than the configured
     /// threshold.
     ///
+    /// The threshold is raised by the configured hysteresis after a backfill sync finished, until
+    /// the node caught up with the consensus layer again.
+    ///
     /// If the `local_tip` is greater than the `block`, then this will return false.
     #[inline]
     const fn exceeds_backfill_run_threshold(&self, local_tip: u64, block: u64) -> bool {
-        block > local_tip && block - local_tip > MIN_BLOCKS_FOR_PIPELINE_RUN
+        self.config.backfill_thresholds().is_exceeded(
+            local_tip,
+            block,
+            self.backfill_hysteresis_active,
+        )
     }
 
     /// Returns how far the local tip is from the given block. If the local tip is at the same
@@ -1862,7 +2060,14 @@ where
         let start = Instant::now();
 
         // update the tracked canonical head
-        self.state.tree_state.set_canonical_head(chain_update.tip().num_hash());
+        let old_head = *self.state.tree_state.canonical_head();
+        let new_head = chain_update.tip().num_hash();
+        self.state.tree_state.set_canonical_head(new_head);
+        self.fork_choice_history.record(
+            old_head,
+            new_head,
+            chain_update.reorged_block_count() as u64,
+        );
 
         let tip = chain_update.tip().clone_sealed_header();
         let notification = chain_update.to_chain_notification();
@@ -1917,6 +2122,7 @@ where
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
         self.metrics.tree.latest_reorg_depth.set(old_chain_length as f64);
+        self.metrics.tree.reorg_depth.record(old_chain_length as f64);
     }
 
     /// This reinserts any blocks in the new chain that do not already exist in the tree
@@ -2180,13 +2386,16 @@ where
     ///    block.
     /// 3. Once in-memory blocks are collected and optionally filtered, we compute the
     ///    [`HashedPostState`] from them.
-    fn compute_trie_input<TP: DBProvider + BlockNumReader>(
+    fn compute_trie_input<TP>(
         &self,
         persisting_kind: PersistingKind,
         provider: TP,
         parent_hash: B256,
         allocated_trie_input: Option<TrieInput>,
-    ) -> ProviderResult<TrieInput> {
+    ) -> ProviderResult<TrieInput>
+    where
+        TP: DBProvider + BlockNumReader + ChangeSetReader + StorageChangeSetReader,
+    {
         // get allocated trie input or use a default trie input
         let mut input = allocated_trie_input.unwrap_or_default();
 
@@ -2238,16 +2447,11 @@ where
 
         // Retrieve revert state for historical block.
         let revert_state = if block_number == best_block_number {
-            // We do not check against the `last_block_number` here because
-            // `HashedPostState::from_reverts` only uses the database tables, and not static files.
             debug!(target: "engine::tree", block_number, best_block_number, "Empty revert state");
             HashedPostState::default()
         } else {
-            let revert_state = HashedPostState::from_reverts::<KeccakKeyHasher>(
-                provider.tx_ref(),
-                block_number + 1,
-            )
-            .map_err(ProviderError::from)?;
+            let revert_state =
+                HashedPostState::from_reverts::<KeccakKeyHasher>(&provider, block_number + 1)?;
             debug!(
                 target: "engine::tree",
                 block_number,
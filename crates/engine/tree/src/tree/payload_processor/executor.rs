@@ -44,7 +44,7 @@ impl WorkloadExecutor {
 
     /// Shorthand for [`Runtime::spawn_blocking`]
     #[track_caller]
-    pub(super) fn spawn_blocking<F, R>(&self, func: F) -> JoinHandle<R>
+    pub(crate) fn spawn_blocking<F, R>(&self, func: F) -> JoinHandle<R>
     where
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
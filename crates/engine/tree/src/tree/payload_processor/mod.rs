@@ -117,6 +117,11 @@ where
     N: NodePrimitives,
     Evm: ConfigureEvm<Primitives = N> + 'static,
 {
+    /// Returns the executor used to spawn the background tasks.
+    pub(super) const fn executor(&self) -> &WorkloadExecutor {
+        &self.executor
+    }
+
     /// Spawns all background tasks and returns a handle connected to the tasks.
     ///
     /// - Transaction prewarming task
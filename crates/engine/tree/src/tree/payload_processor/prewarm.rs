@@ -8,13 +8,15 @@ use crate::tree::{
     precompile_cache::{CachedPrecompile, PrecompileCacheMap},
     ExecutionEnv, StateProviderBuilder,
 };
+use alloy_consensus::Transaction;
+use alloy_eips::eip2930::AccessList;
 use alloy_evm::Database;
 use alloy_primitives::{keccak256, map::B256Set, B256};
 use metrics::{Gauge, Histogram};
 use reth_evm::{execute::ExecutableTxFor, ConfigureEvm, Evm, EvmFor, SpecFor};
 use reth_metrics::Metrics;
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
-use reth_provider::{BlockReader, StateProviderFactory, StateReader};
+use reth_provider::{AccountReader, BlockReader, StateProvider, StateProviderFactory, StateReader};
 use reth_revm::{database::StateProviderDatabase, db::BundleState, state::EvmState};
 use reth_trie::MultiProofTargets;
 use std::{
@@ -78,6 +80,9 @@ where
     }
 
     /// Spawns all pending transactions as blocking tasks by first chunking them.
+    ///
+    /// Access lists of the transactions are handed to a separate task as soon as the transactions
+    /// arrive, so their state is fetched while the transactions are still queued for execution.
     fn spawn_all(
         &self,
         pending: mpsc::Receiver<impl ExecutableTxFor<Evm> + Send + 'static>,
@@ -91,7 +96,23 @@ where
             let mut handles = Vec::new();
             let (done_tx, done_rx) = mpsc::channel();
             let mut executing = 0;
+            let mut access_lists_tx = None;
             while let Ok(executable) = pending.recv() {
+                if let Some(access_list) =
+                    executable.tx().access_list().filter(|access_list| !access_list.is_empty())
+                {
+                    let access_lists_tx = access_lists_tx.get_or_insert_with(|| {
+                        let (tx, rx) = mpsc::channel();
+                        let sender = actions_tx.clone();
+                        let ctx = ctx.clone();
+                        executor.spawn_blocking(move || {
+                            ctx.prefetch_access_lists(rx, sender);
+                        });
+                        tx
+                    });
+                    let _ = access_lists_tx.send(access_list.clone());
+                }
+
                 let task_idx = executing % max_concurrency;
 
                 if handles.len() <= task_idx {
@@ -113,6 +134,7 @@ where
             }
 
             // drop handle and wait for all tasks to finish and drop theirs
+            drop(access_lists_tx);
             drop(done_tx);
             drop(handles);
             while done_rx.recv().is_ok() {}
@@ -338,6 +360,64 @@ where
         // send a message to the main task to flag that we're done
         let _ = done_tx.send(());
     }
+
+    /// Accepts an [`mpsc::Receiver`] of transaction access lists and loads the listed accounts
+    /// and storage slots into the execution cache. Streams [`PrewarmTaskEvent::Outcome`] messages
+    /// with the access list entries as proof targets, so the trie nodes are fetched as well.
+    ///
+    /// Access lists are known before execution, which allows overlapping the I/O of
+    /// access-list-heavy blocks with transaction execution.
+    fn prefetch_access_lists(
+        self,
+        access_lists: mpsc::Receiver<AccessList>,
+        sender: Sender<PrewarmTaskEvent>,
+    ) {
+        let Self { cache, cache_metrics, provider, metrics, terminate_execution, .. } = self;
+
+        let state_provider = match provider.build() {
+            Ok(provider) => provider,
+            Err(err) => {
+                trace!(
+                    target: "engine::tree",
+                    %err,
+                    "Failed to build state provider in access list prefetch thread"
+                );
+                return
+            }
+        };
+        let state_provider =
+            CachedStateProvider::new_with_caches(state_provider, cache, cache_metrics);
+
+        while let Ok(access_list) = access_lists.recv() {
+            if terminate_execution.load(Ordering::Relaxed) {
+                break
+            }
+
+            let start = Instant::now();
+            for item in access_list.iter() {
+                let res = state_provider.basic_account(&item.address).and_then(|_| {
+                    item.storage_keys
+                        .iter()
+                        .try_for_each(|key| state_provider.storage(item.address, *key).map(drop))
+                });
+                if let Err(err) = res {
+                    trace!(
+                        target: "engine::tree",
+                        %err,
+                        address=%item.address,
+                        "Failed to prefetch access list state",
+                    );
+                    return
+                }
+            }
+            metrics.access_list_prefetch_duration.record(start.elapsed());
+
+            let (targets, storage_targets) = multiproof_targets_from_access_list(&access_list);
+            metrics.access_list_storage_targets.record(storage_targets as f64);
+
+            let _ = sender.send(PrewarmTaskEvent::Outcome { proof_targets: Some(targets) });
+        }
+    }
 }
 
 /// Returns a set of [`MultiProofTargets`] and the total amount of storage targets, based on the
@@ -375,6 +455,23 @@ fn multiproof_targets_from_state(state: EvmState) -> (MultiProofTargets, usize)
     (targets, storage_targets)
 }
 
+/// Returns a set of [`MultiProofTargets`] and the total amount of storage targets, based on the
+/// given access list.
+fn multiproof_targets_from_access_list(access_list: &AccessList) -> (MultiProofTargets, usize) {
+    let mut targets = MultiProofTargets::with_capacity(access_list.len());
+    let mut storage_targets = 0;
+    for item in access_list.iter() {
+        let storage_set = targets.entry(keccak256(item.address)).or_default();
+        for key in &item.storage_keys {
+            if storage_set.insert(keccak256(key)) {
+                storage_targets += 1;
+            }
+        }
+    }
+
+    (targets, storage_targets)
+}
+
 /// The events the pre-warm task can handle.
 pub(super) enum PrewarmTaskEvent {
     /// Forcefully terminate all remaining transaction execution.
@@ -411,6 +508,33 @@ pub(crate) struct PrewarmMetrics {
     pub(crate) execution_duration: Histogram,
     /// A histogram for prefetch targets per transaction prewarming
     pub(crate) prefetch_storage_targets: Histogram,
+    /// A histogram of duration for loading the state of a transaction access list
+    pub(crate) access_list_prefetch_duration: Histogram,
+    /// A histogram for prefetch storage targets per transaction access list
+    pub(crate) access_list_storage_targets: Histogram,
     /// A histogram of duration for cache saving
     pub(crate) cache_saving_duration: Gauge,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::AccessListItem;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn access_list_targets() {
+        let address = Address::with_last_byte(1);
+        let access_list = AccessList(vec![
+            AccessListItem { address, storage_keys: vec![B256::ZERO, B256::with_last_byte(1)] },
+            AccessListItem { address, storage_keys: vec![B256::ZERO] },
+            AccessListItem { address: Address::with_last_byte(2), storage_keys: vec![] },
+        ]);
+
+        let (targets, storage_targets) = multiproof_targets_from_access_list(&access_list);
+        assert_eq!(storage_targets, 2);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[&keccak256(address)].len(), 2);
+        assert!(targets[&keccak256(Address::with_last_byte(2))].is_empty());
+    }
+}
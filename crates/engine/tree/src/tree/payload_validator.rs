@@ -35,9 +35,9 @@ use reth_primitives_traits::{
     AlloyBlockHeader, BlockTy, GotExpected, NodePrimitives, RecoveredBlock, SealedHeader,
 };
 use reth_provider::{
-    BlockExecutionOutput, BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory,
-    ExecutionOutcome, HashedPostStateProvider, ProviderError, StateProvider, StateProviderFactory,
-    StateReader, StateRootProvider,
+    BlockExecutionOutput, BlockNumReader, BlockReader, ChangeSetReader, DBProvider,
+    DatabaseProviderFactory, ExecutionOutcome, HashedPostStateProvider, ProviderError,
+    StateProvider, StateProviderFactory, StateReader, StateRootProvider, StorageChangeSetReader,
 };
 use reth_revm::db::State;
 use reth_trie::{updates::TrieUpdates, HashedPostState, KeccakKeyHasher, TrieInput};
@@ -174,7 +174,7 @@ where
 impl<N, P, Evm, V> BasicEngineValidator<P, Evm, V>
 where
     N: NodePrimitives,
-    P: DatabaseProviderFactory<Provider: BlockReader>
+    P: DatabaseProviderFactory<Provider: BlockReader + ChangeSetReader + StorageChangeSetReader>
         + BlockReader<Header = N::BlockHeader>
         + StateProviderFactory
         + StateReader
@@ -361,13 +361,22 @@ where
         //    accounting for the prefix sets.
         let has_ancestors_with_missing_trie_updates =
             self.has_ancestors_with_missing_trie_updates(input.block_with_parent(), ctx.state());
-        let mut use_state_root_task = run_parallel_state_root &&
+        //
+        // In deferred mode the state root is verified in the background after the block has been
+        // accepted, as long as the window of unverified blocks isn't exhausted, so the state root
+        // task isn't needed.
+        let defer_state_root = self.config.deferred_state_root() &&
+            (ctx.state().deferred_state_roots.len() as u64) <
+                self.config.deferred_state_root_window();
+        let mut use_state_root_task = !defer_state_root &&
+            run_parallel_state_root &&
             self.config.use_state_root_task() &&
             !has_ancestors_with_missing_trie_updates;
 
         debug!(
             target: "engine::tree",
             block=?block_num_hash,
+            defer_state_root,
             run_parallel_state_root,
             has_ancestors_with_missing_trie_updates,
             use_state_root_task,
@@ -482,6 +491,34 @@ where
             return Err(InsertBlockError::new(block.into_sealed_block(), err.into()).into())
         }
 
+        if defer_state_root {
+            debug!(target: "engine::tree", block=?block_num_hash, "Deferring state root verification");
+            self.metrics.block_validation.state_root_deferred_total.increment(1);
+
+            // terminate prewarming task with good state output
+            handle.terminate_caching(Some(output.state.clone()));
+
+            let state_root_input = hashed_state.clone();
+            ctx.state_mut().deferred_state_roots.spawn(
+                self.payload_processor.executor(),
+                block.block_with_parent(),
+                move || state_provider.state_root_with_updates(state_root_input),
+            );
+
+            // The trie updates are attached once the state root is verified.
+            return Ok(ExecutedBlockWithTrieUpdates {
+                block: ExecutedBlock {
+                    recovered_block: Arc::new(block),
+                    execution_output: Arc::new(ExecutionOutcome::from((
+                        output,
+                        block_num_hash.number,
+                    ))),
+                    hashed_state: Arc::new(hashed_state),
+                },
+                trie: ExecutedTrieUpdates::Missing,
+            })
+        }
+
         debug!(target: "engine::tree", block=?block_num_hash, "Calculating block state root");
 
         let root_time = Instant::now();
@@ -810,14 +847,17 @@ where
     ///    block.
     /// 3. Once in-memory blocks are collected and optionally filtered, we compute the
     ///    [`HashedPostState`] from them.
-    fn compute_trie_input<TP: DBProvider + BlockNumReader>(
+    fn compute_trie_input<TP>(
         &self,
         persisting_kind: PersistingKind,
         provider: TP,
         parent_hash: B256,
         state: &EngineApiTreeState<N>,
         allocated_trie_input: Option<TrieInput>,
-    ) -> ProviderResult<TrieInput> {
+    ) -> ProviderResult<TrieInput>
+    where
+        TP: DBProvider + BlockNumReader + ChangeSetReader + StorageChangeSetReader,
+    {
         // get allocated trie input or use a default trie input
         let mut input = allocated_trie_input.unwrap_or_default();
 
@@ -868,16 +908,11 @@ where
 
         // Retrieve revert state for historical block.
         let revert_state = if block_number == best_block_number {
-            // We do not check against the `last_block_number` here because
-            // `HashedPostState::from_reverts` only uses the database tables, and not static files.
             debug!(target: "engine::tree", block_number, best_block_number, "Empty revert state");
             HashedPostState::default()
         } else {
-            let revert_state = HashedPostState::from_reverts::<KeccakKeyHasher>(
-                provider.tx_ref(),
-                block_number + 1,
-            )
-            .map_err(ProviderError::from)?;
+            let revert_state =
+                HashedPostState::from_reverts::<KeccakKeyHasher>(&provider, block_number + 1)?;
             debug!(
                 target: "engine::tree",
                 block_number,
@@ -956,7 +991,7 @@ pub trait EngineValidator<
 
 impl<N, Types, P, Evm, V> EngineValidator<Types> for BasicEngineValidator<P, Evm, V>
 where
-    P: DatabaseProviderFactory<Provider: BlockReader>
+    P: DatabaseProviderFactory<Provider: BlockReader + ChangeSetReader + StorageChangeSetReader>
         + BlockReader<Header = N::BlockHeader>
         + StateProviderFactory
         + StateReader
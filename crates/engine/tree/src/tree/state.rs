@@ -172,6 +172,24 @@ impl<N: NodePrimitives> TreeState<N> {
         Some((executed, children))
     }
 
+    /// Removes the block with the given hash and all of its descendants.
+    ///
+    /// Returns the removed blocks, parents before their children.
+    pub(crate) fn remove_with_descendants(
+        &mut self,
+        hash: B256,
+    ) -> Vec<ExecutedBlockWithTrieUpdates<N>> {
+        let mut removed = Vec::new();
+        let mut blocks_to_remove = VecDeque::from([hash]);
+        while let Some(block) = blocks_to_remove.pop_front() {
+            if let Some((executed, children)) = self.remove_by_hash(block) {
+                blocks_to_remove.extend(children);
+                removed.push(executed);
+            }
+        }
+        removed
+    }
+
     /// Returns whether or not the hash is part of the canonical chain.
     pub(crate) fn is_canonical(&self, hash: B256) -> bool {
         let mut current_block = self.current_canonical_head.hash;
@@ -496,6 +514,33 @@ mod tests {
         assert_eq!(tree_state.blocks_by_number[&5].len(), 2);
     }
 
+    #[test]
+    fn test_tree_state_remove_with_descendants() {
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..6).collect();
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+        let fork_block_3 = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+        tree_state.insert_executed(fork_block_3.clone());
+
+        let removed = tree_state.remove_with_descendants(blocks[2].recovered_block().hash());
+        assert_eq!(
+            removed.iter().map(|block| block.recovered_block().hash()).collect::<Vec<_>>(),
+            blocks[2..].iter().map(|block| block.recovered_block().hash()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(tree_state.blocks_by_hash.len(), 3);
+        assert!(tree_state.blocks_by_hash.contains_key(&fork_block_3.recovered_block().hash()));
+        assert_eq!(
+            tree_state.parent_to_child.get(&blocks[1].recovered_block().hash()),
+            Some(&HashSet::from_iter([fork_block_3.recovered_block().hash()]))
+        );
+        assert!(!tree_state.blocks_by_number.contains_key(&4));
+    }
+
     #[tokio::test]
     async fn test_tree_state_remove_before() {
         let start_num_hash = BlockNumHash::default();
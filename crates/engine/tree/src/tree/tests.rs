@@ -1,5 +1,5 @@
 use super::*;
-use crate::persistence::PersistenceAction;
+use crate::{persistence::PersistenceAction, tree::payload_processor::executor::WorkloadExecutor};
 use alloy_consensus::Header;
 use alloy_primitives::{
     map::{HashMap, HashSet},
@@ -8,15 +8,19 @@ use alloy_primitives::{
 use alloy_rlp::Decodable;
 use alloy_rpc_types_engine::{ExecutionData, ExecutionPayloadSidecar, ExecutionPayloadV1};
 use assert_matches::assert_matches;
-use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
+use reth_chain_state::{test_utils::TestBlockBuilder, BlockState, CanonStateNotification};
 use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
-use reth_engine_primitives::{EngineApiValidator, ForkchoiceStatus, NoopInvalidBlockHook};
+use reth_engine_primitives::{
+    BackfillThresholds, EngineApiValidator, ForkchoiceStatus, NoopInvalidBlockHook,
+    DEFAULT_BACKFILL_THRESHOLD,
+};
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_ethereum_primitives::{Block, EthPrimitives};
 use reth_evm_ethereum::MockEvmConfig;
 use reth_primitives_traits::Block as _;
 use reth_provider::{test_utils::MockEthProvider, ExecutionOutcome};
+use reth_stages_api::PipelineTarget;
 use reth_trie::HashedPostState;
 use std::{
     collections::BTreeMap,
@@ -781,6 +785,37 @@ async fn test_get_canonical_blocks_to_persist() {
     );
 }
 
+#[tokio::test]
+async fn test_get_canonical_blocks_to_persist_in_memory_budget() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec);
+    let mut test_block_builder = TestBlockBuilder::eth();
+
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..10).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    test_harness.tree.persistence_state.last_persisted_block = blocks[3].recovered_block.num_hash();
+
+    // the threshold is not reached and only block 4 is below the buffer target
+    test_harness.tree.config =
+        TreeConfig::default().with_persistence_threshold(100).with_memory_block_buffer_target(5);
+    assert!(!test_harness.tree.should_persist());
+    let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist().unwrap();
+    assert_eq!(
+        blocks_to_persist.iter().map(|b| b.recovered_block().number).collect::<Vec<_>>(),
+        vec![4]
+    );
+
+    // the budget only fits the two newest blocks, so everything below them is persisted
+    let budget = (blocks[8].size() + blocks[9].size()) as u64;
+    test_harness.tree.config = test_harness.tree.config.clone().with_in_memory_budget(Some(budget));
+    assert!(test_harness.tree.should_persist());
+    let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist().unwrap();
+    assert_eq!(
+        blocks_to_persist.iter().map(|b| b.recovered_block().number).collect::<Vec<_>>(),
+        vec![4, 5, 6, 7]
+    );
+}
+
 #[tokio::test]
 async fn test_engine_tree_fcu_missing_head() {
     let chain_spec = MAINNET.clone();
@@ -824,7 +859,7 @@ async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
     // extend main chain with enough blocks to trigger pipeline run but don't insert them
     let main_chain = test_harness
         .block_builder
-        .create_fork(base_chain[0].recovered_block(), MIN_BLOCKS_FOR_PIPELINE_RUN + 10);
+        .create_fork(base_chain[0].recovered_block(), DEFAULT_BACKFILL_THRESHOLD + 10);
 
     let main_chain_last_hash = main_chain.last().unwrap().hash();
     test_harness.send_fcu(main_chain_last_hash, ForkchoiceStatus::Syncing).await;
@@ -832,7 +867,7 @@ async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
     test_harness.check_fcu(main_chain_last_hash, ForkchoiceStatus::Syncing).await;
 
     // create event for backfill finished
-    let backfill_finished_block_number = MIN_BLOCKS_FOR_PIPELINE_RUN + 1;
+    let backfill_finished_block_number = DEFAULT_BACKFILL_THRESHOLD + 1;
     let backfill_finished = FromOrchestrator::BackfillSyncFinished(ControlFlow::Continue {
         block_number: backfill_finished_block_number,
     });
@@ -867,3 +902,220 @@ async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
         _ => panic!("Unexpected event: {event:#?}"),
     }
 }
+
+#[tokio::test]
+async fn test_engine_tree_manual_backfill_trigger() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..2).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks);
+    let target = B256::random();
+
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::TriggerBackfill { target, tx }.into(),
+        ))
+        .unwrap();
+    assert!(rx.await.unwrap());
+    assert_eq!(test_harness.tree.backfill_sync_state, BackfillSyncState::Pending);
+
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    assert_matches!(
+        event,
+        EngineApiEvent::BackfillAction(BackfillAction::Start(PipelineTarget::Sync(hash))) if hash == target
+    );
+
+    // backfill is already pending
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::TriggerBackfill { target, tx }.into(),
+        ))
+        .unwrap();
+    assert!(!rx.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_engine_tree_backfill_thresholds() {
+    let mut test_harness = TestHarness::new(MAINNET.clone());
+    let thresholds = BackfillThresholds { threshold: 64, hysteresis: 16 };
+
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::BackfillThresholds { update: Some(thresholds), tx }.into(),
+        ))
+        .unwrap();
+    assert_eq!(rx.await.unwrap(), BackfillThresholds::default());
+    assert_eq!(test_harness.tree.config.backfill_thresholds(), thresholds);
+
+    assert!(!test_harness.tree.exceeds_backfill_run_threshold(100, 164));
+    assert!(test_harness.tree.exceeds_backfill_run_threshold(100, 165));
+
+    // the threshold is raised after a backfill sync
+    test_harness.tree.backfill_hysteresis_active = true;
+    assert!(!test_harness.tree.exceeds_backfill_run_threshold(100, 180));
+    assert!(test_harness.tree.exceeds_backfill_run_threshold(100, 181));
+    assert!(!test_harness.tree.exceeds_backfill_run_threshold(200, 100));
+}
+
+#[tokio::test]
+async fn test_engine_tree_records_reorg_in_fork_choice_history() {
+    let chain_spec = MAINNET.clone();
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..5).collect();
+    let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks.clone());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fork-choice-history.jsonl");
+    test_harness.tree.fork_choice_history =
+        ForkchoiceHistory::with_file(test_harness.tree.config.fork_choice_history_size(), &path);
+
+    // fork off block 2 and make the fork canonical, which reorgs out blocks 3 and 4
+    let fork_block_3 =
+        test_block_builder.get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+    let fork_block_4 =
+        test_block_builder.get_executed_block_with_number(4, fork_block_3.recovered_block().hash());
+    test_harness.tree.state.tree_state.insert_executed(fork_block_3);
+    test_harness.tree.state.tree_state.insert_executed(fork_block_4.clone());
+    test_harness.tree.make_canonical(fork_block_4.recovered_block().hash()).unwrap();
+
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::ForkchoiceHistory { limit: 10, tx }.into(),
+        ))
+        .unwrap();
+    let history = rx.await.unwrap();
+    assert_eq!(history.len(), 1);
+    let entry = history[0];
+    assert_eq!(entry.old_head, blocks[4].recovered_block().hash());
+    assert_eq!(entry.new_head, fork_block_4.recovered_block().hash());
+    assert_eq!(entry.new_head_number, 4);
+    assert_eq!(entry.reorg_depth, 2);
+
+    // the history survives a restart
+    let restored = ForkchoiceHistory::with_file(10, &path);
+    assert_eq!(restored.recent(10), history);
+}
+
+/// Waits until all deferred state root verifications of the tree are handled.
+fn handle_deferred_state_roots(test_harness: &mut TestHarness) {
+    while !test_harness.tree.state.deferred_state_roots.is_empty() {
+        test_harness.tree.on_deferred_state_roots().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+}
+
+#[tokio::test]
+async fn test_deferred_state_root_mismatch_reverts_block() {
+    let chain_spec = MAINNET.clone();
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..5).collect();
+    let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks.clone());
+    let mut canon_notifications =
+        test_harness.tree.canonical_in_memory_state.subscribe_canon_state();
+
+    // block 3 turns out to have an invalid state root
+    let invalid = blocks[3].recovered_block().block_with_parent();
+    test_harness.tree.state.deferred_state_roots.spawn(
+        &WorkloadExecutor::default(),
+        invalid,
+        || Ok((B256::random(), Default::default())),
+    );
+    handle_deferred_state_roots(&mut test_harness);
+
+    // the canonical chain is reverted to the parent of the invalid block
+    let parent = blocks[2].recovered_block().num_hash();
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &parent);
+    assert_eq!(test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(), parent);
+    for block in &blocks[3..] {
+        assert!(test_harness
+            .tree
+            .state
+            .tree_state
+            .executed_block_by_hash(block.recovered_block().hash())
+            .is_none());
+    }
+
+    // listeners are notified about the revert
+    let notification = canon_notifications.try_recv().unwrap();
+    assert_matches!(notification, CanonStateNotification::Reorg { .. });
+    assert_eq!(notification.tip().num_hash(), parent);
+    assert_eq!(
+        notification.reverted().unwrap().blocks().keys().copied().collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    assert_matches!(
+        event,
+        EngineApiEvent::BeaconConsensus(ConsensusEngineEvent::CanonicalChainCommitted(header, _))
+            if header.num_hash() == parent
+    );
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    assert_matches!(
+        event,
+        EngineApiEvent::BeaconConsensus(ConsensusEngineEvent::InvalidBlock(block))
+            if block.hash() == invalid.block.hash
+    );
+
+    // the invalid block and its descendants are reported as invalid
+    test_harness.send_fcu(blocks[3].recovered_block().hash(), ForkchoiceStatus::Invalid).await;
+    test_harness.send_fcu(blocks[4].recovered_block().hash(), ForkchoiceStatus::Invalid).await;
+}
+
+#[tokio::test]
+async fn test_deferred_state_root_gates_persistence() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec);
+    let mut test_block_builder = TestBlockBuilder::eth();
+
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..10).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    test_harness.tree.persistence_state.last_persisted_block = blocks[3].recovered_block.num_hash();
+    test_harness.tree.config =
+        TreeConfig::default().with_persistence_threshold(4).with_memory_block_buffer_target(3);
+
+    // blocks 4 and 5 would be persisted, but the state root of block 5 isn't verified yet
+    let (release_tx, release_rx) = channel::<()>();
+    let pending = blocks[5].recovered_block().block_with_parent();
+    let state_root = blocks[5].recovered_block().state_root;
+    test_harness.tree.state.deferred_state_roots.spawn(
+        &WorkloadExecutor::default(),
+        pending,
+        move || {
+            let _ = release_rx.recv();
+            Ok((state_root, Default::default()))
+        },
+    );
+    test_harness.tree.state.tree_state.blocks_by_hash.get_mut(&pending.block.hash).unwrap().trie =
+        ExecutedTrieUpdates::Missing;
+
+    let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist().unwrap();
+    assert_eq!(
+        blocks_to_persist.iter().map(|block| block.recovered_block().number).collect::<Vec<_>>(),
+        vec![4]
+    );
+
+    // once verified, the block is persisted with the computed trie updates
+    release_tx.send(()).unwrap();
+    handle_deferred_state_roots(&mut test_harness);
+    assert!(test_harness
+        .tree
+        .state
+        .tree_state
+        .executed_block_by_hash(pending.block.hash)
+        .unwrap()
+        .trie
+        .is_present());
+
+    let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist().unwrap();
+    assert_eq!(
+        blocks_to_persist.iter().map(|block| block.recovered_block().number).collect::<Vec<_>>(),
+        vec![4, 5, 6]
+    );
+}
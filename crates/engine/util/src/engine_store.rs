@@ -86,6 +86,10 @@ impl EngineMessageStore {
                     })?,
                 )?;
             }
+            // only messages of the consensus layer are stored
+            BeaconEngineMessage::TriggerBackfill { .. } |
+            BeaconEngineMessage::BackfillThresholds { .. } |
+            BeaconEngineMessage::ForkchoiceHistory { .. } => {}
         };
         Ok(())
     }
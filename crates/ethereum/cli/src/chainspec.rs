@@ -1,5 +1,6 @@
 use reth_chainspec::{ChainSpec, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
+use reth_cli_commands::chainspec::parse_chain_definition;
 use std::sync::Arc;
 
 /// Chains supported by reth. First value should be used as the default.
@@ -7,8 +8,9 @@ pub const SUPPORTED_CHAINS: &[&str] = &["mainnet", "sepolia", "holesky", "hoodi"
 
 /// Clap value parser for [`ChainSpec`]s.
 ///
-/// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// The value parser matches either a known chain, the path to a chain definition file (see
+/// [`parse_chain_definition`]), the path to a json file, or a json formatted string in-memory. The
+/// json needs to be a Genesis struct.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         "mainnet" => MAINNET.clone(),
@@ -16,7 +18,10 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         "holesky" => HOLESKY.clone(),
         "hoodi" => HOODI.clone(),
         "dev" => DEV.clone(),
-        _ => Arc::new(parse_genesis(s)?.into()),
+        _ => match parse_chain_definition(s)? {
+            Some(definition) => Arc::new(definition.try_into()?),
+            None => Arc::new(parse_genesis(s)?.into()),
+        },
     })
 }
 
@@ -101,4 +106,46 @@ mod tests {
         assert!(spec.is_prague_active_at_timestamp(0));
         assert!(spec.is_osaka_active_at_timestamp(0));
     }
+
+    #[test]
+    fn parse_chain_definition_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("genesis.json"),
+            r#"{"config":{"chainId":1337},"alloc":{},"difficulty":"0x0","gasLimit":"0x1c9c380"}"#,
+        )
+        .unwrap();
+        let definition = temp_dir.path().join("chain.toml");
+        std::fs::write(
+            &definition,
+            r#"
+genesis = "genesis.json"
+
+[forks]
+london = "block:0"
+paris = "ttd:0@0"
+shanghai = "timestamp:0"
+cancun = "timestamp:1000"
+
+[deposit_contract]
+address = "0x4242424242424242424242424242424242424242"
+
+[blob_schedule.cancun]
+target = 4
+max = 8
+baseFeeUpdateFraction = 3338477
+"#,
+        )
+        .unwrap();
+
+        let spec =
+            <EthereumChainSpecParser as ChainSpecParser>::parse(definition.to_str().unwrap())
+                .unwrap();
+        assert_eq!(spec.chain.id(), 1337);
+        assert!(spec.is_shanghai_active_at_timestamp(0));
+        assert!(!spec.is_cancun_active_at_timestamp(999));
+        assert!(spec.is_cancun_active_at_timestamp(1000));
+        assert_eq!(spec.blob_params.cancun.max_blob_count, 8);
+        assert!(spec.deposit_contract.is_some());
+    }
 }
@@ -14,7 +14,7 @@ use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET}
 use reth_ethereum_primitives::{Block, BlockBody, Transaction};
 use reth_evm::{
     execute::{BasicBlockExecutor, Executor},
-    ConfigureEvm,
+    ConfigureEvm, ReexecuteBlock,
 };
 use reth_evm_ethereum::EthEvmConfig;
 use reth_execution_types::BlockExecutionResult;
@@ -24,9 +24,10 @@ use reth_primitives_traits::{
 use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
 use revm::{
     database::{CacheDB, EmptyDB, TransitionState},
+    interpreter::{CallInputs, CallOutcome},
     primitives::address,
     state::{AccountInfo, Bytecode, EvmState},
-    Database,
+    Database, Inspector,
 };
 use std::sync::{mpsc, Arc};
 
@@ -826,3 +827,72 @@ fn test_balance_increment_not_duplicated() {
         );
     }
 }
+
+#[test]
+fn reexecute_block_with_inspector() {
+    #[derive(Default)]
+    struct CallCounter {
+        calls: usize,
+    }
+
+    impl<CTX> Inspector<CTX> for CallCounter {
+        fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+            self.calls += 1;
+            None
+        }
+    }
+
+    let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+    let sender_key_pair = generators::generate_key(&mut generators::rng());
+    let sender_address = public_key_to_address(sender_key_pair.public_key());
+    let recipient = address!("0x1000000000000000000000000000000000000000");
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        sender_address,
+        AccountInfo { nonce: 0, balance: U256::from(ETH_TO_WEI), ..Default::default() },
+    );
+
+    let header = Header {
+        timestamp: 1,
+        number: 1,
+        gas_limit: 1_000_000,
+        base_fee_per_gas: Some(1),
+        ..Header::default()
+    };
+    let transactions = (0..2)
+        .map(|nonce| {
+            sign_tx_with_key_pair(
+                sender_key_pair,
+                Transaction::Legacy(TxLegacy {
+                    chain_id: Some(chain_spec.chain.id()),
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(recipient),
+                    value: U256::from(1),
+                    input: Bytes::new(),
+                }),
+            )
+        })
+        .collect();
+    let block = Block {
+        header,
+        body: BlockBody { transactions, withdrawals: Some(vec![].into()), ..Default::default() },
+    }
+    .try_into_recovered()
+    .unwrap();
+
+    let evm_config = EthEvmConfig::new(chain_spec);
+    let mut inspector = CallCounter::default();
+    let output = evm_config.reexecute_block(db, &block, &mut inspector).unwrap();
+
+    assert_eq!(inspector.calls, 2);
+    assert_eq!(output.result.receipts.len(), 2);
+    assert_eq!(output.result.gas_used, 42_000);
+    assert_eq!(
+        output.state.account(&recipient).unwrap().info.as_ref().unwrap().balance,
+        U256::from(2)
+    );
+}
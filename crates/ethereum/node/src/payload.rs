@@ -1,5 +1,6 @@
 //! Payload component configuration for the Ethereum node.
 
+use reth_basic_payload_builder::PayloadDaLimits;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
@@ -47,12 +48,20 @@ where
         let conf = ctx.payload_builder_config();
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
+        let da_limits = PayloadDaLimits {
+            max_da_tx_size: conf.max_da_tx_size(),
+            max_da_block_size: conf.max_da_block_size(),
+            target_blob_count: conf.target_blob_count(),
+        };
 
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            EthereumBuilderConfig::new()
+                .with_gas_limit(gas_limit)
+                .with_da_limits(da_limits)
+                .with_blob_backlog_blocks(conf.blob_backlog_blocks()),
         ))
     }
 }
@@ -1,10 +1,11 @@
 use crate::utils::eth_payload_attributes;
 use alloy_genesis::Genesis;
+use futures::StreamExt;
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::{
     node::NodeTestContext, setup, transaction::TransactionTestContext, wallet::Wallet,
 };
-use reth_node_builder::{NodeBuilder, NodeHandle};
+use reth_node_builder::{events::NodeEvent, NodeBuilder, NodeHandle};
 use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
 use reth_node_ethereum::EthereumNode;
 use reth_tasks::TaskManager;
@@ -46,6 +47,41 @@ async fn can_run_eth_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_subscribe_to_node_events() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+        eth_payload_attributes,
+    )
+    .await?;
+
+    let mut node = nodes.pop().unwrap();
+    let mut events = node.inner.node_events();
+
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(1, wallet.inner).await;
+    node.rpc.inject_tx(raw_tx).await?;
+    let payload = node.advance_block().await?;
+
+    loop {
+        if let Some(NodeEvent::CanonicalHead { head, .. }) = events.next().await {
+            assert_eq!(head, payload.block().num_hash());
+            break
+        }
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 #[cfg(unix)]
 async fn can_run_eth_node_with_auth_engine_api_over_ipc() -> eyre::Result<()> {
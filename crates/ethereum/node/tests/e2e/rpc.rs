@@ -1,18 +1,30 @@
 use crate::utils::eth_payload_attributes;
-use alloy_eips::eip2718::Encodable2718;
-use alloy_primitives::{Address, B256, U256};
-use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
+use alloy_eips::{
+    eip2718::Encodable2718, eip2930::AccessListResult, eip7702::Authorization, BlockId,
+};
+use alloy_primitives::{bytes, Address, Bytes, B256, U256};
+use alloy_provider::{
+    network::{EthereumWallet, TransactionBuilder, TransactionBuilder7702},
+    Provider, ProviderBuilder, SendableTx,
+};
 use alloy_rpc_types_beacon::relay::{
     BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
     SignedBidSubmissionV3, SignedBidSubmissionV4,
 };
 use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
-use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_eth::{Index, TransactionRequest};
+use alloy_rpc_types_trace::parity::{
+    Action, CallAction, Delta, LocalizedTransactionTrace, TraceResultsWithTransactionHash,
+    TraceType,
+};
+use alloy_signer::SignerSync;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, EthChainSpec, MAINNET};
-use reth_e2e_test_utils::setup_engine;
+use reth_e2e_test_utils::{setup_engine, wallet::Wallet};
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
+use reth_rpc_api::RethEngineApiClient;
+use serde_json::json;
 use std::sync::Arc;
 
 alloy_sol_types::sol! {
@@ -280,3 +292,351 @@ async fn test_flashbots_validate_v4() -> eyre::Result<()> {
         .is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_debug_trace_call_overrides() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, _wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().connect_http(node.rpc_url());
+
+    // returns `sload(0) + block.number`
+    let target = Address::random();
+    let code = bytes!("0x600054430160005260206000f3");
+    let state_overrides = json!({
+        target.to_string(): {
+            "code": code,
+            "stateDiff": { B256::ZERO.to_string(): B256::with_last_byte(42) }
+        }
+    });
+    let block_overrides = json!({ "number": "0x100" });
+    let expected_output = B256::from(U256::from(42 + 0x100));
+    let request = TransactionRequest::default().to(target);
+
+    let trace_call = |tracer: serde_json::Value| {
+        let mut opts = tracer;
+        opts["stateOverrides"] = state_overrides.clone();
+        opts["blockOverrides"] = block_overrides.clone();
+        let request = request.clone();
+        let provider = provider.clone();
+        async move {
+            provider
+                .raw_request::<_, serde_json::Value>(
+                    "debug_traceCall".into(),
+                    (request, "latest", opts),
+                )
+                .await
+        }
+    };
+    let output = |frame: &serde_json::Value| frame["output"].as_str().unwrap().parse::<B256>();
+    let prestate_slot = |frame: &serde_json::Value| {
+        let (_, account) = frame
+            .as_object()
+            .unwrap()
+            .iter()
+            .find(|(address, _)| address.parse::<Address>().unwrap() == target)
+            .unwrap();
+        account["storage"][B256::ZERO.to_string()].as_str().unwrap().parse::<B256>()
+    };
+
+    let frame = trace_call(json!({ "tracer": "callTracer" })).await?;
+    assert_eq!(output(&frame)?, expected_output);
+
+    let frame = trace_call(json!({ "tracer": "prestateTracer" })).await?;
+    assert_eq!(prestate_slot(&frame)?, B256::with_last_byte(42));
+
+    let frame = trace_call(json!({
+        "tracer": "muxTracer",
+        "tracerConfig": { "callTracer": {}, "prestateTracer": {} }
+    }))
+    .await?;
+    assert_eq!(output(&frame["callTracer"])?, expected_output);
+    assert_eq!(prestate_slot(&frame["prestateTracer"])?, B256::with_last_byte(42));
+
+    // the default struct logger
+    let frame = trace_call(json!({})).await?;
+    assert_eq!(frame["returnValue"].as_str().unwrap().parse::<B256>()?, expected_output);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_access_list_overrides_and_authorizations() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .prague_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, _wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new().connect_http(node.rpc_url());
+    let signers = Wallet::new(2).with_chain_id(chain_spec.chain().id()).wallet_gen();
+
+    // returns `balance(coinbase)`
+    let target = Address::random();
+    let coinbase = Address::random();
+    let state_overrides = json!({ target.to_string(): { "code": bytes!("0x413100") } });
+    let block_overrides = json!({ "feeRecipient": coinbase });
+
+    let create_access_list = |request: TransactionRequest| {
+        let provider = provider.clone();
+        let state_overrides = state_overrides.clone();
+        let block_overrides = block_overrides.clone();
+        async move {
+            provider
+                .raw_request::<_, AccessListResult>(
+                    "eth_createAccessList".into(),
+                    (request, "latest", state_overrides, block_overrides),
+                )
+                .await
+        }
+    };
+
+    // the overridden coinbase is touched by the call
+    let result =
+        create_access_list(TransactionRequest::default().from(signers[0].address()).to(target))
+            .await?;
+    assert!(result.error.is_none());
+    assert!(result.access_list.iter().any(|item| item.address == coinbase));
+
+    // the same code executed through an EIP-7702 delegation
+    let authority = &signers[1];
+    let auth = Authorization {
+        chain_id: U256::from(chain_spec.chain().id()),
+        address: target,
+        nonce: provider.get_transaction_count(authority.address()).await?,
+    };
+    let sig = authority.sign_hash_sync(&auth.signature_hash())?;
+    let request = TransactionRequest::default()
+        .from(signers[0].address())
+        .to(authority.address())
+        .with_authorization_list(vec![auth.into_signed(sig)]);
+
+    let result = create_access_list(request).await?;
+    assert!(result.error.is_none());
+    assert!(result.access_list.iter().any(|item| item.address == coinbase));
+    assert!(result.access_list.iter().all(|item| item.address != authority.address()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trace_get_and_replay_block_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    // calls `callee` and stops
+    let callee = Address::random();
+    let runtime_code = [
+        bytes!("0x60006000600060006000"),
+        bytes!("0x73"),
+        Bytes::copy_from_slice(callee.as_slice()),
+        bytes!("0x5af100"),
+    ]
+    .concat();
+    let init_code = [bytes!("0x6022600c60003960226000f3").to_vec(), runtime_code.clone()].concat();
+
+    let deploy = provider
+        .send_transaction(TransactionRequest::default().with_deploy_code(init_code))
+        .await?;
+    node.advance_block().await?;
+    let contract = deploy.get_receipt().await?.contract_address.unwrap();
+
+    let call = provider.send_transaction(TransactionRequest::default().to(contract)).await?;
+    node.advance_block().await?;
+    let receipt = call.get_receipt().await?;
+    let tx_hash = receipt.transaction_hash;
+
+    let trace_get = |indices: Vec<Index>| {
+        let provider = provider.clone();
+        async move {
+            provider
+                .raw_request::<_, Option<LocalizedTransactionTrace>>(
+                    "trace_get".into(),
+                    (tx_hash, indices),
+                )
+                .await
+        }
+    };
+
+    // the top level call
+    let trace = trace_get(vec![]).await?.unwrap();
+    assert!(trace.trace.trace_address.is_empty());
+    assert_eq!(trace.trace.subtraces, 1);
+
+    // the subcall to the callee
+    let trace = trace_get(vec![Index::from(0)]).await?.unwrap();
+    assert_eq!(trace.trace.trace_address, vec![0]);
+    assert!(matches!(trace.trace.action, Action::Call(CallAction { to, .. }) if to == callee));
+
+    assert!(trace_get(vec![Index::from(1)]).await?.is_none());
+    assert!(trace_get(vec![Index::from(0), Index::from(0)]).await?.is_none());
+
+    let replayed = provider
+        .raw_request::<_, Vec<TraceResultsWithTransactionHash>>(
+            "trace_replayBlockTransactions".into(),
+            (
+                BlockId::number(receipt.block_number.unwrap()),
+                [TraceType::StateDiff, TraceType::VmTrace],
+            ),
+        )
+        .await?;
+    let replayed = replayed.iter().find(|trace| trace.transaction_hash == tx_hash).unwrap();
+    assert_eq!(replayed.full_trace.vm_trace.as_ref().unwrap().code, runtime_code);
+    let sender = replayed.full_trace.state_diff.as_ref().unwrap().get(&receipt.from).unwrap();
+    assert!(matches!(sender.nonce, Delta::Changed(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_payload_stats() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let pending = provider
+        .send_transaction(TransactionRequest::default().to(Address::random()).value(U256::from(1)))
+        .await?;
+    let block_hash = node.advance_block().await?.block().hash();
+    assert_eq!(pending.get_receipt().await?.block_hash, Some(block_hash));
+
+    let client = node.auth_server_handle().http_client();
+    let stats = RethEngineApiClient::reth_payload_stats(&client).await?;
+    let stats = stats.iter().find(|stats| !stats.active).unwrap();
+    assert!(stats.iterations >= 1);
+    assert!(stats.transactions_considered >= 1);
+    assert_eq!(stats.transactions_included, 1);
+    assert!(stats.cumulative_fees > U256::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_raw_transaction_conditional() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec.clone(),
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    let SendableTx::Envelope(tx) =
+        provider.fill(TransactionRequest::default().to(Address::ZERO)).await?
+    else {
+        unreachable!()
+    };
+    let tx: Bytes = tx.encoded_2718().into();
+
+    // the storage of the account doesn't match
+    let account = Address::random();
+    let condition =
+        json!({ "knownAccounts": { account.to_string(): { "0x00": B256::with_last_byte(1) } } });
+    let err = provider
+        .raw_request::<_, B256>("eth_sendRawTransactionConditional".into(), (&tx, condition))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("transaction conditional rejected"));
+
+    // the pending block is past the max block number
+    let condition = json!({ "blockNumberMax": "0x0" });
+    let err = provider
+        .raw_request::<_, B256>("eth_sendRawTransactionConditional".into(), (&tx, condition))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("transaction conditional rejected"));
+
+    let condition = json!({
+        "knownAccounts": { account.to_string(): { "0x00": B256::ZERO } },
+        "blockNumberMin": "0x1",
+    });
+    let hash = provider
+        .raw_request::<_, B256>("eth_sendRawTransactionConditional".into(), (&tx, condition))
+        .await?;
+    assert!(provider.get_transaction_by_hash(hash).await?.is_some());
+
+    Ok(())
+}
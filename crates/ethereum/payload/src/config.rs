@@ -1,4 +1,5 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use reth_basic_payload_builder::PayloadDaLimits;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
 
 /// Settings for the Ethereum builder.
@@ -9,6 +10,14 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Data availability limits enforced on built payloads.
+    pub da_limits: PayloadDaLimits,
+    /// Number of blocks within which the blob demand of the pool should be included when building
+    /// payloads at the target blob count of the [`PayloadDaLimits`].
+    ///
+    /// If the projected blob demand exceeds what can be included at the target within these
+    /// blocks, the target is lifted and payloads include blobs up to the protocol limit.
+    pub blob_backlog_blocks: Option<u64>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +29,12 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            da_limits: PayloadDaLimits::new(),
+            blob_backlog_blocks: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +49,19 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Configures the data availability limits enforced on built payloads.
+    pub const fn with_da_limits(mut self, da_limits: PayloadDaLimits) -> Self {
+        self.da_limits = da_limits;
+        self
+    }
+
+    /// Configures the number of blocks within which the blob demand of the pool should be
+    /// included at the target blob count.
+    pub const fn with_blob_backlog_blocks(mut self, blob_backlog_blocks: Option<u64>) -> Self {
+        self.blob_backlog_blocks = blob_backlog_blocks;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -10,10 +10,11 @@
 #![allow(clippy::useless_let_if_seq)]
 
 use alloy_consensus::Transaction;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
-    is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
-    PayloadConfig,
+    is_better_payload, BuildArguments, BuildOutcome, DaLimitExceeded, MissingPayloadBehaviour,
+    PayloadBuilder, PayloadConfig,
 };
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
@@ -31,8 +32,8 @@ use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
-    BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
-    ValidPoolTransaction,
+    BestTransactions, BestTransactionsAttributes, BlobFeeProjection, PoolTransaction,
+    TransactionPool, ValidPoolTransaction,
 };
 use revm::context_interface::Block as _;
 use std::sync::Arc;
@@ -147,7 +148,7 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
 {
-    let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+    let BuildArguments { mut cached_reads, config, cancel, best_payload, counters } = args;
     let PayloadConfig { parent_header, attributes } = config;
 
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
@@ -192,13 +193,36 @@ where
     // blob sidecars if any.
     let mut blob_sidecars = BlobSidecars::Empty;
 
-    let mut block_blob_count = 0;
-
     let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
+    let mut da_limits = builder_config.da_limits;
+    if let (Some(blob_params), Some(target_blob_count), Some(blocks)) =
+        (blob_params, da_limits.target_blob_count, builder_config.blob_backlog_blocks)
+    {
+        // lift the blob target if the blob demand of the pool can't be included at the target
+        // within the configured number of blocks
+        let projection = BlobFeeProjection::new(
+            blob_params,
+            parent_header.excess_blob_gas.unwrap_or_default(),
+            parent_header.blob_gas_used.unwrap_or_default(),
+            pool.blob_gas_demand(),
+            target_blob_count * DATA_GAS_PER_BLOB,
+            blocks,
+        );
+        if projection.remaining_blob_gas() > 0 {
+            debug!(target: "payload_builder", id=%attributes.id, backlog=projection.remaining_blob_gas(), "lifting blob target to reduce blob backlog");
+            da_limits.target_blob_count = None;
+        }
+    }
+
+    // tracks blob count and data availability usage of the payload
+    let mut da_budget = da_limits.budget(max_blob_count);
+
     while let Some(pool_tx) = best_txs.next() {
+        counters.inc_transactions_considered();
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -219,30 +243,37 @@ where
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
-        // There's only limited amount of blob space available per block, so we need to check if
-        // the EIP-4844 can still fit in the block
-        let mut blob_tx_sidecar = None;
-        if let Some(blob_tx) = tx.as_eip4844() {
-            let tx_blob_count = blob_tx.tx().blob_versioned_hashes.len() as u64;
-
-            if block_blob_count + tx_blob_count > max_blob_count {
-                // we can't fit this _blob_ transaction into the block, so we mark it as
-                // invalid, which removes its dependent transactions from
-                // the iterator. This is similar to the gas limit condition
-                // for regular transactions above.
-                trace!(target: "payload_builder", tx=?tx.hash(), ?block_blob_count, "skipping blob transaction because it would exceed the max blob count per block");
-                best_txs.mark_invalid(
-                    &pool_tx,
+        let tx_da_size = pool_tx.encoded_length() as u64;
+        let tx_blob_count =
+            tx.as_eip4844().map_or(0, |blob_tx| blob_tx.tx().blob_versioned_hashes.len() as u64);
+
+        // There's only limited amount of blob space and data availability budget per block, so we
+        // need to check if the transaction can still fit in the block
+        if let Err(err) = da_budget.check(tx_da_size, tx_blob_count) {
+            // we can't fit this transaction into the block, so we mark it as invalid, which
+            // removes its dependent transactions from the iterator. This is similar to the gas
+            // limit condition for regular transactions above.
+            trace!(target: "payload_builder", tx=?tx.hash(), %err, "skipping transaction because it would exceed the payload DA limits");
+            let err = match err {
+                DaLimitExceeded::BlobCount { total, limit } => {
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
-                            have: block_blob_count + tx_blob_count,
-                            permitted: max_blob_count,
+                            have: total,
+                            permitted: limit,
                         },
-                    ),
-                );
-                continue
-            }
+                    )
+                }
+                DaLimitExceeded::TxDaSize { size, limit } |
+                DaLimitExceeded::BlockDaSize { total: size, limit } => {
+                    InvalidPoolTransactionError::OversizedData(size as usize, limit as usize)
+                }
+            };
+            best_txs.mark_invalid(&pool_tx, err);
+            continue
+        }
 
+        let mut blob_tx_sidecar = None;
+        if tx.is_eip4844() {
             let blob_sidecar_result = 'sidecar: {
                 let Some(sidecar) =
                     pool.get_blob(*tx.hash()).map_err(PayloadBuilderError::other)?
@@ -297,14 +328,12 @@ where
             Err(err) => return Err(PayloadBuilderError::evm(err)),
         };
 
-        // add to the total blob gas used if the transaction successfully executed
-        if let Some(blob_tx) = tx.as_eip4844() {
-            block_blob_count += blob_tx.tx().blob_versioned_hashes.len() as u64;
+        // add to the total blob count and DA size if the transaction successfully executed
+        da_budget.record(tx_da_size, tx_blob_count);
 
-            // if we've reached the max blob count, we can skip blob txs entirely
-            if block_blob_count == max_blob_count {
-                best_txs.skip_blobs();
-            }
+        // if we've reached the blob limit, we can skip blob txs entirely
+        if tx_blob_count > 0 && da_budget.is_blob_limit_reached() {
+            best_txs.skip_blobs();
         }
 
         // update and add to total fees
@@ -529,6 +529,8 @@ mod compact {
 
     pub use flags::ReceiptFlags;
 
+    /// The logs bloom is not part of the stored encoding, it is recomputed from the logs when
+    /// needed (see [`TxReceipt::bloom`]).
     impl<T: Compact> Compact for Receipt<T> {
         fn to_compact<B>(&self, buf: &mut B) -> usize
         where
@@ -1,6 +1,9 @@
 //! Helper type that represents one of two possible executor types
 
-use crate::{execute::Executor, Database, OnStateHook};
+use crate::{
+    execute::{Executor, StateSizeHint},
+    Database, OnStateHook,
+};
 
 // re-export Either
 pub use futures_util::future::Either;
@@ -79,4 +82,11 @@ where
             Self::Right(b) => b.size_hint(),
         }
     }
+
+    fn state_size_hint(&self) -> StateSizeHint {
+        match self {
+            Self::Left(a) => a.state_size_hint(),
+            Self::Right(b) => b.state_size_hint(),
+        }
+    }
 }
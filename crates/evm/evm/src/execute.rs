@@ -129,6 +129,25 @@ pub trait Executor<DB: Database>: Sized {
     ///
     /// This is used to optimize DB commits depending on the size of the state.
     fn size_hint(&self) -> usize;
+
+    /// A breakdown of the batch's tracked state size by resource.
+    ///
+    /// By default, all tracked changes are accounted as state changes.
+    fn state_size_hint(&self) -> StateSizeHint {
+        StateSizeHint { state_changes: self.size_hint(), ..Default::default() }
+    }
+}
+
+/// Breakdown of the state tracked by an [`Executor`] batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateSizeHint {
+    /// Number of changed accounts and storage slots, each of them results in a hashed state and
+    /// trie update once the batch is committed.
+    pub state_changes: usize,
+    /// Number of account and storage changeset entries tracked for the batch.
+    pub changeset_entries: usize,
+    /// Number of new contract bytecodes.
+    pub contracts: usize,
 }
 
 /// Helper type for the output of executing a block.
@@ -549,6 +568,15 @@ where
     fn size_hint(&self) -> usize {
         self.db.bundle_state.size_hint()
     }
+
+    fn state_size_hint(&self) -> StateSizeHint {
+        let bundle = &self.db.bundle_state;
+        StateSizeHint {
+            state_changes: bundle.state_size,
+            changeset_entries: bundle.reverts_size,
+            contracts: bundle.contracts.len(),
+        }
+    }
 }
 
 /// A helper trait marking a 'static type that can be converted into an [`ExecutableTx`] for block
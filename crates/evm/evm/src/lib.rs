@@ -47,6 +47,12 @@ pub use aliases::*;
 mod engine;
 pub use engine::{ConfigureEngineEvm, ExecutableTxIterator};
 
+mod reexecute;
+pub use reexecute::ReexecuteBlock;
+
+mod simulation;
+pub use simulation::apply_simulated_block_system_calls;
+
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
@@ -0,0 +1,53 @@
+//! Re-execution of historical blocks with custom inspectors.
+
+use crate::{ConfigureEvm, Database, InspectorFor};
+use alloy_evm::block::BlockExecutor;
+use reth_execution_errors::BlockExecutionError;
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::{BlockTy, ReceiptTy, RecoveredBlock};
+use revm::database::{states::bundle_state::BundleRetention, State};
+
+/// Re-executes historical blocks with a custom inspector.
+///
+/// This is implemented for every [`ConfigureEvm`] and wires up the EVM environment and execution
+/// context of the block exactly like regular block execution does, including the pre and post
+/// execution system calls and state changes. This makes it easy to inspect historical blocks,
+/// e.g. from an `ExEx` or a custom RPC method:
+///
+/// ```ignore
+/// let db = StateProviderDatabase::new(provider.history_by_block_hash(block.parent_hash())?);
+/// let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+/// let output = evm_config.reexecute_block(db, &block, &mut inspector)?;
+/// ```
+pub trait ReexecuteBlock: ConfigureEvm {
+    /// Re-executes the block on top of the given database with the given inspector.
+    ///
+    /// The database must point to the state of the parent block. The inspector is invoked for all
+    /// transactions and system calls of the block, pass it as `&mut` to access it afterwards.
+    ///
+    /// Returns the receipts and the state changes of the block.
+    fn reexecute_block<DB, I>(
+        &self,
+        db: DB,
+        block: &RecoveredBlock<BlockTy<Self::Primitives>>,
+        inspector: I,
+    ) -> Result<BlockExecutionOutput<ReceiptTy<Self::Primitives>>, BlockExecutionError>
+    where
+        DB: Database,
+        I: for<'a> InspectorFor<Self, &'a mut State<DB>>,
+    {
+        let mut state =
+            State::builder().with_database(db).with_bundle_update().without_state_clear().build();
+
+        let evm =
+            self.evm_with_env_and_inspector(&mut state, self.evm_env(block.header()), inspector);
+        let ctx = self.context_for_block(block);
+        let result = self.create_executor(evm, ctx).execute_block(block.transactions_recovered())?;
+
+        state.merge_transitions(BundleRetention::Reverts);
+
+        Ok(BlockExecutionOutput { result, state: state.take_bundle() })
+    }
+}
+
+impl<T: ConfigureEvm> ReexecuteBlock for T {}
@@ -0,0 +1,36 @@
+//! Helpers to prepare the state for simulations of blocks that are not part of the chain.
+
+use crate::{system_calls::SystemCaller, Evm};
+use alloy_primitives::B256;
+use reth_ethereum_forks::EthereumHardforks;
+use reth_execution_errors::BlockExecutionError;
+use revm::DatabaseCommit;
+
+/// Applies the pre-block system calls of a simulated block to the state of the given [`Evm`].
+///
+/// A simulated block, e.g. the pending block of an `eth_call`, is executed on top of the state of
+/// its parent, which doesn't include the changes the block's system calls make to the
+/// [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935) history storage contract and the
+/// [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) beacon roots contract. Without them,
+/// contracts that read the latest block hash or beacon root behave differently than they would in
+/// the actual block.
+///
+/// The number and timestamp of the simulated block are taken from the block environment of the
+/// [`Evm`]. The beacon roots contract call is skipped if no `parent_beacon_block_root` is given.
+pub fn apply_simulated_block_system_calls<Spec, E>(
+    spec: Spec,
+    evm: &mut E,
+    parent_hash: B256,
+    parent_beacon_block_root: Option<B256>,
+) -> Result<(), BlockExecutionError>
+where
+    Spec: EthereumHardforks,
+    E: Evm<DB: DatabaseCommit>,
+{
+    let mut system_caller = SystemCaller::new(spec);
+    system_caller.apply_blockhashes_contract_call(parent_hash, evm)?;
+    if parent_beacon_block_root.is_some() {
+        system_caller.apply_beacon_root_contract_call(parent_beacon_block_root, evm)?;
+    }
+    Ok(())
+}
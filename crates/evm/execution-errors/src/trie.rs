@@ -196,6 +196,9 @@ pub enum TrieWitnessError {
     /// Missing account.
     #[error("missing account {_0}")]
     MissingAccount(B256),
+    /// Failed to record a witness node.
+    #[error("failed to record witness node: {_0}")]
+    Sink(Box<dyn core::error::Error + Send + Sync>),
 }
 
 impl From<SparseStateTrieErrorKind> for TrieWitnessError {
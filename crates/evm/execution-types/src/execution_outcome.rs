@@ -2,7 +2,8 @@ use crate::{BlockExecutionOutput, BlockExecutionResult};
 use alloc::{vec, vec::Vec};
 use alloy_eips::eip7685::Requests;
 use alloy_primitives::{logs_bloom, map::HashMap, Address, BlockNumber, Bloom, Log, B256, U256};
-use reth_primitives_traits::{Account, Bytecode, Receipt, StorageEntry};
+use core::mem::size_of_val;
+use reth_primitives_traits::{Account, Bytecode, InMemorySize, Receipt, StorageEntry};
 use reth_trie_common::{HashedPostState, KeyHasher};
 use revm::{
     database::{states::BundleState, BundleAccount},
@@ -382,6 +383,48 @@ impl<T: Receipt<Log = Log>> ExecutionOutcome<T> {
     }
 }
 
+impl<T: InMemorySize> InMemorySize for ExecutionOutcome<T> {
+    fn size(&self) -> usize {
+        let state = self
+            .bundle
+            .state
+            .iter()
+            .map(|(address, account)| {
+                size_of_val(address) +
+                    size_of_val(account) +
+                    account
+                        .storage
+                        .iter()
+                        .map(|(key, slot)| size_of_val(key) + size_of_val(slot))
+                        .sum::<usize>()
+            })
+            .sum::<usize>();
+        let contracts = self
+            .bundle
+            .contracts
+            .iter()
+            .map(|(hash, code)| size_of_val(hash) + size_of_val(code) + code.len())
+            .sum::<usize>();
+        let reverts = self
+            .bundle
+            .reverts
+            .iter()
+            .flatten()
+            .map(|(address, revert)| {
+                size_of_val(address) +
+                    size_of_val(revert) +
+                    revert
+                        .storage
+                        .iter()
+                        .map(|(key, slot)| size_of_val(key) + size_of_val(slot))
+                        .sum::<usize>()
+            })
+            .sum::<usize>();
+        let receipts = self.receipts.iter().flatten().map(InMemorySize::size).sum::<usize>();
+        state + contracts + reverts + receipts
+    }
+}
+
 impl ExecutionOutcome {
     /// Returns the ethereum receipt root for all recorded receipts.
     ///
@@ -123,11 +123,15 @@ where
             // Seal the block back and save it
             blocks.push(block);
             // Check if we should commit now
+            let state_size = executor.state_size_hint();
             if self.thresholds.is_end_of_batch(
                 block_number - *self.range.start() + 1,
                 executor.size_hint() as u64,
                 cumulative_gas,
                 batch_start.elapsed(),
+            ) || self.thresholds.is_resource_limit_reached(
+                state_size.changeset_entries as u64,
+                state_size.state_changes as u64,
             ) {
                 break
             }
@@ -0,0 +1,36 @@
+/// Errors that can occur when loading or executing a WASM `ExEx` module.
+#[derive(Debug, thiserror::Error)]
+pub enum WasmExExError {
+    /// The module file could not be read.
+    #[error("failed to read module: {0}")]
+    Io(#[from] std::io::Error),
+    /// The module could not be compiled, instantiated or executed.
+    #[error(transparent)]
+    Wasm(#[from] wasmi::Error),
+    /// The module does not export a required item.
+    #[error("module does not export `{0}`")]
+    MissingExport(&'static str),
+    /// The module exhausted its fuel budget for the notification.
+    #[error("module ran out of fuel")]
+    OutOfFuel,
+    /// The notification could not be written to the memory of the module.
+    #[error("failed to write {len} bytes to module memory at {ptr}")]
+    MemoryOutOfBounds {
+        /// The pointer returned by `alloc`.
+        ptr: u32,
+        /// The length of the notification.
+        len: usize,
+    },
+    /// The notification is too large for the 32-bit address space of the module.
+    #[error("notification of {0} bytes is too large")]
+    NotificationTooLarge(usize),
+    /// The notification could not be serialized.
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+    /// The module trapped on an earlier notification, its state may be inconsistent.
+    #[error("module trapped on an earlier notification")]
+    Trapped,
+    /// The module failed to handle the notification.
+    #[error("module failed to handle notification with status {0}")]
+    Failed(u32),
+}
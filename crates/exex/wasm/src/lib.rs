@@ -0,0 +1,98 @@
+//! WASM runtime for sandboxed execution extensions.
+//!
+//! Execution extensions compiled to `WebAssembly` are loaded at runtime and executed in a sandbox,
+//! so that they can be deployed without recompiling the node. Every module is instantiated in its
+//! own store with a fuel budget per notification and a limit on its linear memory.
+//!
+//! ## Module interface
+//!
+//! A module must export:
+//!
+//! - `memory`: the linear memory of the module.
+//! - `alloc(len: i32) -> i32`: allocates `len` bytes and returns a pointer to them.
+//! - `on_notification(ptr: i32, len: i32) -> i32`: handles the JSON encoded
+//!   [`ExExNotification`](reth_exex::ExExNotification) written to `ptr`. A return value other than
+//!   `0` signals that the notification could not be handled.
+//!
+//! A module can import:
+//!
+//! - `reth.log(ptr: i32, len: i32)`: logs the UTF-8 message at `ptr` at debug level, truncated
+//!   to 4 KiB.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! # use reth_exex_wasm::{run_wasm_exex, WasmExEx, WasmExExConfig};
+//! # use reth_exex::ExExContext;
+//! # use reth_node_api::FullNodeComponents;
+//! async fn wasm_exex<Node: FullNodeComponents>(ctx: ExExContext<Node>) -> eyre::Result<()>
+//! # where
+//! #     reth_exex::ExExNotification<reth_node_api::PrimitivesTy<Node::Types>>: serde::Serialize,
+//! {
+//!     let module = WasmExEx::from_file("indexer.wasm", WasmExExConfig::default())?;
+//!     run_wasm_exex(ctx, vec![module]).await
+//! }
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod error;
+mod runtime;
+
+pub use error::WasmExExError;
+pub use runtime::{
+    WasmExEx, WasmExExConfig, DEFAULT_WASM_EXEX_FUEL_PER_NOTIFICATION,
+    DEFAULT_WASM_EXEX_MAX_MEMORY_BYTES,
+};
+
+use futures::TryStreamExt;
+use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_node_api::{FullNodeComponents, PrimitivesTy};
+use serde::Serialize;
+use tracing::error;
+
+/// Feeds the notifications of the `ExEx` to the given WASM modules.
+///
+/// A module that fails to handle a notification, e.g. because it ran out of fuel, is disabled and
+/// doesn't receive any further notifications, so a faulty module can't stall the node. The modules
+/// are executed on the blocking thread pool, so they don't block the async runtime.
+pub async fn run_wasm_exex<Node>(
+    mut ctx: ExExContext<Node>,
+    mut modules: Vec<WasmExEx>,
+) -> eyre::Result<()>
+where
+    Node: FullNodeComponents,
+    ExExNotification<PrimitivesTy<Node::Types>>: Serialize,
+{
+    while let Some(notification) = ctx.notifications.try_next().await? {
+        let payload = serde_json::to_vec(&notification)?;
+        modules = tokio::task::spawn_blocking(move || {
+            modules.retain_mut(|module| match module.on_serialized_notification(&payload) {
+                Ok(()) => true,
+                Err(err) => {
+                    error!(
+                        target: "exex::wasm",
+                        module = %module.name(),
+                        %err,
+                        "Disabling WASM module"
+                    );
+                    false
+                }
+            });
+            modules
+        })
+        .await?;
+
+        if let Some(committed_chain) = notification.committed_chain() {
+            ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))?;
+        }
+    }
+
+    Ok(())
+}
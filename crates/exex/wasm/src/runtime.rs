@@ -0,0 +1,287 @@
+use crate::WasmExExError;
+use reth_exex::ExExNotification;
+use reth_node_api::NodePrimitives;
+use serde::Serialize;
+use std::path::Path;
+use tracing::{debug, trace};
+use wasmi::{
+    Caller, Config, Engine, Extern, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TrapCode, TypedFunc,
+};
+
+/// Default fuel budget of a module per notification.
+///
+/// This is in the order of a second of execution.
+pub const DEFAULT_WASM_EXEX_FUEL_PER_NOTIFICATION: u64 = 1_000_000_000;
+
+/// Default limit of the linear memory of a module, 256 MiB.
+pub const DEFAULT_WASM_EXEX_MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Messages passed to the `reth.log` host function are truncated to 4 KiB.
+const MAX_LOG_MESSAGE_BYTES: usize = 4 * 1024;
+
+/// Resource limits of a [`WasmExEx`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmExExConfig {
+    /// Fuel the module may consume to handle a single notification.
+    ///
+    /// Roughly, every executed instruction consumes one unit of fuel.
+    pub fuel_per_notification: u64,
+    /// Maximum size of the linear memory of the module in bytes.
+    pub max_memory_bytes: usize,
+}
+
+impl Default for WasmExExConfig {
+    fn default() -> Self {
+        Self {
+            fuel_per_notification: DEFAULT_WASM_EXEX_FUEL_PER_NOTIFICATION,
+            max_memory_bytes: DEFAULT_WASM_EXEX_MAX_MEMORY_BYTES,
+        }
+    }
+}
+
+/// The host state of a module.
+#[derive(Debug)]
+struct HostState {
+    /// Name of the module, used for logging.
+    name: String,
+    /// Resource limits of the store.
+    limits: StoreLimits,
+}
+
+/// An instantiated WASM `ExEx` module.
+///
+/// See the [crate] docs for the interface the module has to implement.
+///
+/// A trap, e.g. because the module ran out of fuel, can interrupt the module in the middle of
+/// updating its state. The module is therefore marked as failed and rejects all further
+/// notifications with [`WasmExExError::Trapped`].
+#[derive(Debug)]
+pub struct WasmExEx {
+    config: WasmExExConfig,
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<u32, u32>,
+    on_notification: TypedFunc<(u32, u32), u32>,
+    /// Whether the module trapped on an earlier notification.
+    trapped: bool,
+}
+
+impl WasmExEx {
+    /// Compiles and instantiates the module from its binary or text representation.
+    pub fn new(
+        name: impl Into<String>,
+        wasm: impl AsRef<[u8]>,
+        config: WasmExExConfig,
+    ) -> Result<Self, WasmExExError> {
+        let mut engine_config = Config::default();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config);
+        let module = Module::new(&engine, wasm.as_ref())?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(config.max_memory_bytes).build();
+        let mut store = Store::new(&engine, HostState { name: name.into(), limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(config.fuel_per_notification)?;
+
+        let mut linker = Linker::new(&engine);
+        linker.func_wrap("reth", "log", log).map_err(wasmi::Error::from)?;
+        let instance = linker.instantiate_and_start(&mut store, &module).map_err(out_of_fuel)?;
+
+        let memory =
+            instance.get_memory(&store, "memory").ok_or(WasmExExError::MissingExport("memory"))?;
+        let alloc = instance
+            .get_typed_func(&store, "alloc")
+            .map_err(|_| WasmExExError::MissingExport("alloc"))?;
+        let on_notification = instance
+            .get_typed_func(&store, "on_notification")
+            .map_err(|_| WasmExExError::MissingExport("on_notification"))?;
+
+        Ok(Self { config, store, memory, alloc, on_notification, trapped: false })
+    }
+
+    /// Reads the module from the given file and instantiates it.
+    ///
+    /// The module is named after the file stem.
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        config: WasmExExConfig,
+    ) -> Result<Self, WasmExExError> {
+        let path = path.as_ref();
+        let name = path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+        let wasm = std::fs::read(path)?;
+        Self::new(name, wasm, config)
+    }
+
+    /// Returns the name of the module.
+    pub fn name(&self) -> &str {
+        &self.store.data().name
+    }
+
+    /// Returns `true` if the module trapped on an earlier notification and can't be used anymore.
+    pub const fn is_trapped(&self) -> bool {
+        self.trapped
+    }
+
+    /// Returns the resource limits of the module.
+    pub const fn config(&self) -> &WasmExExConfig {
+        &self.config
+    }
+
+    /// Feeds the notification to the module.
+    pub fn on_notification<N>(
+        &mut self,
+        notification: &ExExNotification<N>,
+    ) -> Result<(), WasmExExError>
+    where
+        N: NodePrimitives,
+        ExExNotification<N>: Serialize,
+    {
+        self.on_serialized_notification(&serde_json::to_vec(notification)?)
+    }
+
+    /// Feeds the JSON encoded notification to the module.
+    pub fn on_serialized_notification(&mut self, notification: &[u8]) -> Result<(), WasmExExError> {
+        if self.trapped {
+            return Err(WasmExExError::Trapped)
+        }
+
+        let len = u32::try_from(notification.len())
+            .map_err(|_| WasmExExError::NotificationTooLarge(notification.len()))?;
+        self.store.set_fuel(self.config.fuel_per_notification)?;
+
+        let ptr = self.alloc.call(&mut self.store, len).map_err(|err| {
+            self.trapped = true;
+            out_of_fuel(err)
+        })?;
+        self.memory
+            .write(&mut self.store, ptr as usize, notification)
+            .map_err(|_| WasmExExError::MemoryOutOfBounds { ptr, len: notification.len() })?;
+
+        let status = self.on_notification.call(&mut self.store, (ptr, len)).map_err(|err| {
+            self.trapped = true;
+            out_of_fuel(err)
+        })?;
+        trace!(
+            target: "exex::wasm",
+            module = %self.name(),
+            fuel_consumed = self.config.fuel_per_notification - self.store.get_fuel()?,
+            "Handled notification"
+        );
+
+        if status != 0 {
+            return Err(WasmExExError::Failed(status))
+        }
+        Ok(())
+    }
+}
+
+/// The `reth.log` host function.
+///
+/// Messages longer than [`MAX_LOG_MESSAGE_BYTES`] are truncated.
+fn log(caller: Caller<'_, HostState>, ptr: u32, len: u32) -> Result<(), wasmi::Error> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmi::Error::new("module does not export `memory`"))?;
+    let message = memory
+        .data(&caller)
+        .get(ptr as usize..)
+        .and_then(|data| data.get(..(len as usize).min(MAX_LOG_MESSAGE_BYTES)))
+        .ok_or_else(|| wasmi::Error::new("log message out of bounds"))?;
+    debug!(
+        target: "exex::wasm",
+        module = %caller.data().name,
+        truncated = len as usize > MAX_LOG_MESSAGE_BYTES,
+        message = %String::from_utf8_lossy(message)
+    );
+    Ok(())
+}
+
+/// Maps traps caused by an exhausted fuel budget to [`WasmExExError::OutOfFuel`].
+fn out_of_fuel(err: wasmi::Error) -> WasmExExError {
+    if err.as_trap_code() == Some(TrapCode::OutOfFuel) {
+        return WasmExExError::OutOfFuel
+    }
+    err.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::Chain;
+    use std::sync::Arc;
+
+    /// Counts the notifications that start with `{` at address 12, and loops forever on empty
+    /// ones.
+    const COUNTER: &str = r#"
+        (module
+            (import "reth" "log" (func $log (param i32 i32)))
+            (memory (export "memory") 2)
+            (global $next (mut i32) (i32.const 16))
+            (data (i32.const 0) "notification")
+            (func (export "alloc") (param $len i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $len)))
+                (local.get $ptr))
+            (func (export "on_notification") (param $ptr i32) (param $len i32) (result i32)
+                (global.set $next (i32.const 16))
+                (if (i32.eqz (local.get $len))
+                    (then (loop $spin (br $spin))))
+                (if (i32.ne (i32.load8_u (local.get $ptr)) (i32.const 123))
+                    (then (return (i32.const 1))))
+                (call $log (i32.const 0) (i32.const 12))
+                (i32.store (i32.const 12) (i32.add (i32.load (i32.const 12)) (i32.const 1)))
+                (i32.const 0)))
+    "#;
+
+    fn count(exex: &WasmExEx) -> i32 {
+        let data = exex.memory.data(&exex.store);
+        i32::from_le_bytes(data[12..16].try_into().unwrap())
+    }
+
+    #[test]
+    fn handles_notifications() {
+        let mut exex = WasmExEx::new("counter", COUNTER, WasmExExConfig::default()).unwrap();
+        let notification: ExExNotification =
+            ExExNotification::ChainCommitted { new: Arc::new(Chain::default()) };
+
+        exex.on_notification(&notification).unwrap();
+        exex.on_notification(&notification.clone().into_inverted()).unwrap();
+        assert_eq!(count(&exex), 2);
+
+        assert!(matches!(exex.on_serialized_notification(b"[]"), Err(WasmExExError::Failed(1))));
+        assert_eq!(count(&exex), 2);
+    }
+
+    #[test]
+    fn enforces_fuel_limit() {
+        let config = WasmExExConfig { fuel_per_notification: 10_000, ..Default::default() };
+        let mut exex = WasmExEx::new("counter", COUNTER, config).unwrap();
+
+        exex.on_serialized_notification(b"{}").unwrap();
+        assert_eq!(count(&exex), 1);
+
+        assert!(matches!(exex.on_serialized_notification(&[]), Err(WasmExExError::OutOfFuel)));
+        assert!(exex.is_trapped());
+        // the module may have been interrupted mid-update, so it's not used anymore
+        assert!(matches!(exex.on_serialized_notification(b"{}"), Err(WasmExExError::Trapped)));
+        assert_eq!(count(&exex), 1);
+    }
+
+    #[test]
+    fn enforces_memory_limit() {
+        // the module requires two pages of 64 KiB
+        let config = WasmExExConfig { max_memory_bytes: 64 * 1024, ..Default::default() };
+        assert!(matches!(WasmExEx::new("counter", COUNTER, config), Err(WasmExExError::Wasm(_))));
+    }
+
+    #[test]
+    fn requires_exports() {
+        assert!(matches!(
+            WasmExEx::new("empty", "(module (memory (export \"memory\") 1))", Default::default()),
+            Err(WasmExExError::MissingExport("alloc"))
+        ));
+    }
+}
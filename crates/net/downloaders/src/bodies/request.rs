@@ -233,6 +233,7 @@ where
             if let Some(fut) = this.fut.as_mut() {
                 match ready!(fut.poll_unpin(cx)) {
                     Ok(response) => {
+                        this.metrics.received_responses.increment(1);
                         let peer_id = response.peer_id();
                         if let Err(error) = this.on_block_response(response) {
                             this.on_error(error, Some(peer_id));
@@ -1,6 +1,9 @@
 //! A headers downloader that can handle multiple requests concurrently.
 
-use super::task::TaskDownloader;
+use super::{
+    spill::{default_spill_directory, HeadersSpillBuffer},
+    task::TaskDownloader,
+};
 use crate::metrics::HeaderDownloaderMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
@@ -9,7 +12,7 @@ use futures::{stream::Stream, FutureExt};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use rayon::prelude::*;
 use reth_config::config::HeadersConfig;
-use reth_consensus::HeaderValidator;
+use reth_consensus::{ConsensusError, HeaderValidator};
 use reth_network_p2p::{
     error::{DownloadError, DownloadResult, PeerRequestResult},
     headers::{
@@ -26,12 +29,13 @@ use std::{
     cmp::{Ordering, Reverse},
     collections::{binary_heap::PeekMut, BinaryHeap},
     future::Future,
+    path::PathBuf,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
 };
 use thiserror::Error;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 
 /// A heuristic that is used to determine the number of requests that should be prepared for a peer.
 /// This should ensure that there are always requests lined up for peers to handle while the
@@ -100,6 +104,10 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     in_progress_queue: FuturesUnordered<HeadersRequestFuture<H::Output>>,
     /// Buffered, unvalidated responses
     buffered_responses: BinaryHeap<OrderedHeadersResponse<H::Header>>,
+    /// Unvalidated responses that were spilled to disk because `buffered_responses` was full.
+    ///
+    /// `None` if spilling is disabled.
+    spilled_responses: Option<HeadersSpillBuffer<H::Header>>,
     /// Buffered, _sorted_ and validated headers ready to be returned.
     ///
     /// Note: headers are sorted from high to low
@@ -259,26 +267,49 @@ where
         headers: Vec<H::Header>,
         peer_id: PeerId,
     ) -> Result<(), ReverseHeadersDownloaderError<H::Header>> {
-        let mut validated = Vec::with_capacity(headers.len());
-
-        let sealed_headers =
+        let mut validated =
             headers.into_par_iter().map(SealedHeader::seal_slow).collect::<Vec<_>>();
-        for parent in sealed_headers {
-            // Validate that the header is the parent header of the last validated header.
-            if let Some(validated_header) =
-                validated.last().or_else(|| self.lowest_validated_header())
-            {
-                if let Err(error) = self.validate(validated_header, &parent) {
+
+        // The highest header must be the parent header of the lowest validated header, or the sync
+        // target if no header was validated yet.
+        if let Some(highest) = validated.first() {
+            if let Some(validated_header) = self.lowest_validated_header() {
+                if let Err(error) = self.validate(validated_header, highest) {
                     trace!(target: "downloaders::headers", %error ,"Failed to validate header");
                     return Err(
                         HeadersResponseError { request, peer_id: Some(peer_id), error }.into()
                     )
                 }
             } else {
-                self.validate_sync_target(&parent, request.clone(), peer_id)?;
+                self.validate_sync_target(highest, request.clone(), peer_id)?;
             }
+        }
+
+        // Link the headers by their parent hashes first, so that the consensus rules below are only
+        // checked for a contiguous chain.
+        if let Some(error) = validated.windows(2).find_map(|pair| {
+            let (header, parent) = (&pair[0], &pair[1]);
+            (header.parent_hash() != parent.hash()).then(|| DownloadError::HeaderValidation {
+                hash: header.hash(),
+                number: header.number(),
+                error: Box::new(ConsensusError::ParentHashMismatch(
+                    GotExpected { got: header.parent_hash(), expected: parent.hash() }.into(),
+                )),
+            })
+        }) {
+            trace!(target: "downloaders::headers", %error ,"Failed to validate header");
+            return Err(HeadersResponseError { request, peer_id: Some(peer_id), error }.into())
+        }
 
-            validated.push(parent);
+        // The consensus rules of a header only depend on its parent, so all headers are validated
+        // in parallel. The error of the highest invalid header is returned.
+        let consensus = &self.consensus;
+        if let Some(error) = validated
+            .par_windows(2)
+            .find_map_first(|pair| validate_header_download(consensus, &pair[0], &pair[1]).err())
+        {
+            trace!(target: "downloaders::headers", %error ,"Failed to validate header");
+            return Err(HeadersResponseError { request, peer_id: Some(peer_id), error }.into())
         }
 
         // If the last (smallest) validated header attaches to the local head, validate it.
@@ -386,6 +417,7 @@ where
                 let (peer_id, mut headers) = res.split();
 
                 // update total downloaded metric
+                self.metrics.received_responses.increment(1);
                 self.metrics.total_downloaded.increment(headers.len() as u64);
 
                 // sort headers from highest to lowest block number
@@ -465,6 +497,7 @@ where
                 let (peer_id, mut headers) = res.split();
 
                 // update total downloaded metric
+                self.metrics.received_responses.increment(1);
                 self.metrics.total_downloaded.increment(headers.len() as u64);
 
                 trace!(target: "downloaders::headers", len=%headers.len(), "Received headers response");
@@ -519,13 +552,8 @@ where
                         .map(Err::<(), ReverseHeadersDownloaderError<H::Header>>)
                         .transpose()?;
                 } else if highest.number() > self.existing_local_block_number() {
-                    self.metrics.buffered_responses.increment(1.);
                     // can't validate yet
-                    self.buffered_responses.push(OrderedHeadersResponse {
-                        headers,
-                        request,
-                        peer_id,
-                    })
+                    self.buffer_response(OrderedHeadersResponse { headers, request, peer_id });
                 }
 
                 Ok(())
@@ -539,6 +567,49 @@ where
         }
     }
 
+    /// Returns true if more responses can be buffered, either in memory or on disk.
+    fn has_buffer_capacity(&self) -> bool {
+        self.buffered_responses.len() < self.max_buffered_responses ||
+            self.spilled_responses.as_ref().is_some_and(|spilled| spilled.has_capacity())
+    }
+
+    /// Buffers a response that can't be validated yet.
+    ///
+    /// If the in-memory buffer is full, the response is spilled to disk if possible.
+    fn buffer_response(&mut self, response: OrderedHeadersResponse<H::Header>) {
+        if self.buffered_responses.len() >= self.max_buffered_responses {
+            if let Some(spilled) =
+                self.spilled_responses.as_mut().filter(|spilled| spilled.has_capacity())
+            {
+                let OrderedHeadersResponse { headers, request, peer_id } = response;
+                match spilled.spill(&headers, request.clone(), peer_id) {
+                    Ok(()) => {
+                        self.metrics.spilled_responses.set(spilled.len() as f64);
+                        return
+                    }
+                    Err(error) => {
+                        warn!(
+                            target: "downloaders::headers",
+                            %error,
+                            directory = %spilled.directory().display(),
+                            "Failed to spill headers response"
+                        );
+                        self.metrics.buffered_responses.increment(1.);
+                        self.buffered_responses.push(OrderedHeadersResponse {
+                            headers,
+                            request,
+                            peer_id,
+                        });
+                        return
+                    }
+                }
+            }
+        }
+
+        self.metrics.buffered_responses.increment(1.);
+        self.buffered_responses.push(response);
+    }
+
     fn penalize_peer(&self, peer_id: Option<PeerId>, error: &DownloadError) {
         // Penalize the peer for bad response
         if let Some(peer_id) = peer_id {
@@ -567,6 +638,30 @@ where
     /// Returns an error if the next expected response was popped, but failed validation.
     fn try_validate_buffered(&mut self) -> Option<ReverseHeadersDownloaderError<H::Header>> {
         loop {
+            // Check to see if the next value was spilled to disk
+            if let Some(spilled) = self.spilled_responses.as_mut() {
+                // responses above the next expected block number are outdated
+                spilled.remove_above(self.next_chain_tip_block_number);
+                let next_response = spilled.take(self.next_chain_tip_block_number);
+                self.metrics.spilled_responses.set(spilled.len() as f64);
+
+                if let Some((request, peer_id, headers)) = next_response {
+                    match headers {
+                        Ok(headers) => {
+                            if let Err(err) = self.process_next_headers(request, headers, peer_id) {
+                                return Some(err)
+                            }
+                        }
+                        Err(error) => {
+                            warn!(target: "downloaders::headers", %error, "Failed to read spilled headers response");
+                            self.submit_request(request, Priority::High);
+                            return None
+                        }
+                    }
+                    continue
+                }
+            }
+
             // Check to see if we've already received the next value
             let next_response = self.buffered_responses.peek_mut()?;
             let next_block_number = next_response.block_number();
@@ -627,10 +722,14 @@ where
         self.lowest_validated_header.take();
         self.queued_validated_headers = Vec::new();
         self.buffered_responses = BinaryHeap::new();
+        if let Some(spilled) = self.spilled_responses.as_mut() {
+            spilled.clear();
+        }
         self.in_progress_queue.clear();
 
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
+        self.metrics.spilled_responses.set(0.);
     }
 
     /// Splits off the next batch of headers
@@ -872,7 +971,7 @@ where
             let concurrent_request_limit = this.concurrent_request_limit();
             // populate requests
             while this.in_progress_queue.len() < concurrent_request_limit &&
-                this.buffered_responses.len() < this.max_buffered_responses
+                this.has_buffer_capacity()
             {
                 if let Some(request) = this.next_request() {
                     trace!(
@@ -1117,17 +1216,22 @@ pub struct ReverseHeadersDownloaderBuilder {
     max_concurrent_requests: usize,
     /// How many responses to buffer
     max_buffered_responses: usize,
+    /// How many responses to spill to disk once the buffer is full
+    max_spilled_responses: usize,
+    /// The directory to spill responses to
+    spill_directory: Option<PathBuf>,
 }
 
 impl ReverseHeadersDownloaderBuilder {
     /// Creates a new [`ReverseHeadersDownloaderBuilder`] with configurations based on the provided
     /// [`HeadersConfig`].
     pub fn new(config: HeadersConfig) -> Self {
-        Self::default()
+        Self { spill_directory: config.downloader_spill_directory, ..Self::default() }
             .request_limit(config.downloader_request_limit)
             .min_concurrent_requests(config.downloader_min_concurrent_requests)
             .max_concurrent_requests(config.downloader_max_concurrent_requests)
             .max_buffered_responses(config.downloader_max_buffered_responses)
+            .max_spilled_responses(config.downloader_max_spilled_responses)
             .stream_batch_size(config.commit_threshold as usize)
     }
 }
@@ -1142,6 +1246,8 @@ impl Default for ReverseHeadersDownloaderBuilder {
             max_concurrent_requests: 100,
             min_concurrent_requests: 5,
             max_buffered_responses: 100,
+            max_spilled_responses: 0,
+            spill_directory: None,
         }
     }
 }
@@ -1194,6 +1300,25 @@ impl ReverseHeadersDownloaderBuilder {
         self
     }
 
+    /// How many responses to spill to disk once the internal buffer is full.
+    ///
+    /// Spilled responses don't count towards [`Self::max_buffered_responses`], so the
+    /// [`ReverseHeadersDownloader`] can keep sending requests while it waits for a slow response,
+    /// without using more memory. Spilling is disabled if this is `0`, which is the default.
+    pub const fn max_spilled_responses(mut self, max_spilled_responses: usize) -> Self {
+        self.max_spilled_responses = max_spilled_responses;
+        self
+    }
+
+    /// Set the directory to spill responses to.
+    ///
+    /// Spilled responses are removed once they're validated or the [`ReverseHeadersDownloader`] is
+    /// dropped. By default, a new directory in the temp directory of the system is used.
+    pub fn spill_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.spill_directory = Some(directory.into());
+        self
+    }
+
     /// Build [`ReverseHeadersDownloader`] with provided consensus
     /// and header client implementations
     pub fn build<H>(
@@ -1210,6 +1335,8 @@ impl ReverseHeadersDownloaderBuilder {
             min_concurrent_requests,
             max_concurrent_requests,
             max_buffered_responses,
+            max_spilled_responses,
+            spill_directory,
         } = self;
         ReverseHeadersDownloader {
             consensus,
@@ -1229,6 +1356,12 @@ impl ReverseHeadersDownloaderBuilder {
             sync_target_request: None,
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
+            spilled_responses: (max_spilled_responses > 0).then(|| {
+                HeadersSpillBuffer::new(
+                    spill_directory.unwrap_or_else(default_spill_directory),
+                    max_spilled_responses,
+                )
+            }),
             queued_validated_headers: Default::default(),
             metrics: Default::default(),
         }
@@ -1439,6 +1572,35 @@ mod tests {
         assert_eq!(heap.pop().unwrap().block_number(), lo);
     }
 
+    #[test]
+    fn process_next_headers_rejects_unlinked_headers() {
+        let client = Arc::new(TestHeadersClient::default());
+
+        let p3 = SealedHeader::default();
+        let mut p2 = child_header(&p3).unseal();
+        let p1 = child_header(&SealedHeader::seal_slow(p2.clone()));
+        let p0 = child_header(&p1);
+        // break the link between p1 and p2
+        p2.parent_hash = B256::random();
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        downloader.update_local_head(p3);
+        downloader.update_sync_target(SyncTarget::Tip(p0.hash()));
+
+        let request = HeadersRequest::falling(p0.number.into(), 3);
+        let headers = vec![p0.unseal(), p1.clone().unseal(), p2];
+        let err = downloader.process_next_headers(request, headers, PeerId::random()).unwrap_err();
+        assert_matches!(
+            err,
+            ReverseHeadersDownloaderError::Response(err) if matches!(
+                err.error,
+                DownloadError::HeaderValidation { number, .. } if number == p1.number
+            )
+        );
+        assert!(downloader.queued_validated_headers.is_empty());
+    }
+
     #[tokio::test]
     async fn download_at_fork_head() {
         reth_tracing::init_test_tracing();
@@ -1558,4 +1720,41 @@ mod tests {
 
         assert!(downloader.next().await.is_none());
     }
+
+    #[tokio::test]
+    async fn download_with_spilled_responses() {
+        reth_tracing::init_test_tracing();
+        let mut headers = vec![SealedHeader::default()];
+        for _ in 0..8 {
+            headers.push(child_header(headers.last().unwrap()));
+        }
+        let local_head = headers.first().unwrap().clone();
+        let tip = headers.last().unwrap().clone();
+
+        let spill_directory = tempfile::tempdir().unwrap();
+        let config = HeadersConfig {
+            commit_threshold: 8,
+            downloader_request_limit: 1,
+            downloader_max_buffered_responses: 1,
+            downloader_max_spilled_responses: 4,
+            downloader_spill_directory: Some(spill_directory.path().to_path_buf()),
+            ..Default::default()
+        };
+        let client = Arc::new(TestHeadersClient::default());
+        let mut downloader = ReverseHeadersDownloaderBuilder::new(config)
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        assert_eq!(
+            downloader.spilled_responses.as_ref().unwrap().directory(),
+            spill_directory.path()
+        );
+        downloader.update_local_head(local_head);
+        downloader.update_sync_target(SyncTarget::Tip(tip.hash()));
+
+        client.extend(headers.iter().rev().map(|h| h.as_ref().clone())).await;
+
+        let downloaded = downloader.next().await.unwrap();
+        assert_eq!(downloaded, Ok(headers.into_iter().skip(1).rev().collect::<Vec<_>>()));
+        assert_eq!(downloader.spilled_responses.as_ref().unwrap().len(), 0);
+        assert!(downloader.next().await.is_none());
+    }
 }
@@ -0,0 +1,166 @@
+//! A disk buffer for out of order headers responses.
+
+use alloy_rlp::{Decodable, Encodable};
+use reth_network_p2p::headers::client::HeadersRequest;
+use reth_network_peers::PeerId;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tracing::trace;
+
+/// Counter to give each default spill directory of this process a unique name.
+static SPILL_DIRECTORY_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a new, unique directory in the temp directory of the system to spill responses to.
+pub(crate) fn default_spill_directory() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "reth-headers-spill-{}-{}",
+        std::process::id(),
+        SPILL_DIRECTORY_ID.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// A headers response that was spilled to disk.
+#[derive(Debug)]
+struct SpilledResponse {
+    request: HeadersRequest,
+    peer_id: PeerId,
+    path: PathBuf,
+}
+
+/// Buffers out of order headers responses on disk once the in-memory buffer of the
+/// [`ReverseHeadersDownloader`](super::reverse_headers::ReverseHeadersDownloader) is full.
+///
+/// Each response is written to its own file as an RLP encoded list of headers, keyed by the block
+/// number the response starts at.
+#[derive(Debug)]
+pub(crate) struct HeadersSpillBuffer<H> {
+    /// The directory to write the responses to.
+    directory: PathBuf,
+    /// Maximum amount of responses to spill.
+    max_responses: usize,
+    /// The spilled responses by the block number they start at.
+    responses: BTreeMap<u64, SpilledResponse>,
+    _header: PhantomData<H>,
+}
+
+impl<H> HeadersSpillBuffer<H> {
+    /// Creates a new buffer that spills up to `max_responses` responses to the given directory.
+    pub(crate) const fn new(directory: PathBuf, max_responses: usize) -> Self {
+        Self { directory, max_responses, responses: BTreeMap::new(), _header: PhantomData }
+    }
+
+    /// Returns the directory responses are spilled to.
+    pub(crate) fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the number of spilled responses.
+    pub(crate) fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns true if another response can be spilled.
+    pub(crate) fn has_capacity(&self) -> bool {
+        self.responses.len() < self.max_responses
+    }
+
+    /// Discards all spilled responses that start above the given block number.
+    ///
+    /// Returns the number of discarded responses.
+    pub(crate) fn remove_above(&mut self, block_number: u64) -> usize {
+        let stale = self.responses.split_off(&block_number.saturating_add(1));
+        for response in stale.values() {
+            let _ = fs::remove_file(&response.path);
+        }
+        stale.len()
+    }
+
+    /// Discards all spilled responses.
+    pub(crate) fn clear(&mut self) {
+        for response in std::mem::take(&mut self.responses).into_values() {
+            let _ = fs::remove_file(&response.path);
+        }
+    }
+}
+
+impl<H> HeadersSpillBuffer<H>
+where
+    H: Encodable + Decodable,
+{
+    /// Writes the response to disk.
+    pub(crate) fn spill(
+        &mut self,
+        headers: &[H],
+        request: HeadersRequest,
+        peer_id: PeerId,
+    ) -> io::Result<()> {
+        let block_number = request.start.as_number().expect("is number");
+        fs::create_dir_all(&self.directory)?;
+        let path = self.directory.join(format!("{block_number}.rlp"));
+
+        let mut buf = Vec::new();
+        alloy_rlp::encode_list(headers, &mut buf);
+        fs::write(&path, buf)?;
+
+        trace!(target: "downloaders::headers", block_number, path = %path.display(), "Spilled headers response");
+        self.responses.insert(block_number, SpilledResponse { request, peer_id, path });
+        Ok(())
+    }
+
+    /// Removes the spilled response that starts at the given block number and reads its headers
+    /// from disk.
+    ///
+    /// The request is returned even if the headers could not be read, so it can be re-submitted.
+    pub(crate) fn take(
+        &mut self,
+        block_number: u64,
+    ) -> Option<(HeadersRequest, PeerId, io::Result<Vec<H>>)> {
+        let SpilledResponse { request, peer_id, path } = self.responses.remove(&block_number)?;
+        let headers = fs::read(&path)
+            .and_then(|bytes| Vec::<H>::decode(&mut bytes.as_slice()).map_err(io::Error::other));
+        let _ = fs::remove_file(&path);
+        Some((request, peer_id, headers))
+    }
+}
+
+impl<H> Drop for HeadersSpillBuffer<H> {
+    fn drop(&mut self) {
+        self.clear();
+        // only removed if there are no other files in the directory
+        let _ = fs::remove_dir(&self.directory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+
+    #[test]
+    fn spill_and_take() {
+        let mut buffer = HeadersSpillBuffer::<Header>::new(default_spill_directory(), 2);
+        let headers = |start: u64| {
+            (0..3).map(|i| Header { number: start - i, ..Default::default() }).collect::<Vec<_>>()
+        };
+        let peer_id = PeerId::random();
+
+        buffer.spill(&headers(10), HeadersRequest::falling(10.into(), 3), peer_id).unwrap();
+        buffer.spill(&headers(7), HeadersRequest::falling(7.into(), 3), peer_id).unwrap();
+        assert!(!buffer.has_capacity());
+
+        assert!(buffer.take(4).is_none());
+        let (request, response_peer_id, response) = buffer.take(7).unwrap();
+        assert_eq!(request.start, 7.into());
+        assert_eq!(response_peer_id, peer_id);
+        assert_eq!(response.unwrap(), headers(7));
+        assert_eq!(buffer.len(), 1);
+
+        assert_eq!(buffer.remove_above(9), 1);
+        assert_eq!(buffer.len(), 0);
+    }
+}
@@ -43,6 +43,8 @@ pub struct BodyDownloaderMetrics {
     /// Out-of-order request happen when the new download range start for bodies downloader
     /// is less than the last block number returned from the stream.
     pub out_of_order_requests: Counter,
+    /// Number of responses received from peers
+    pub received_responses: Counter,
     /// Number of timeout errors while requesting items
     pub timeout_errors: Counter,
     /// Number of validation errors while requesting items
@@ -99,6 +101,9 @@ pub struct HeaderDownloaderMetrics {
     /// The number of responses (can contain more than 1 item) in the internal buffer of the
     /// downloader.
     pub buffered_responses: Gauge,
+    /// The number of responses (can contain more than 1 item) that were spilled to disk because
+    /// the internal buffer of the downloader was full.
+    pub spilled_responses: Gauge,
     /// The number of blocks the internal buffer of the
     /// downloader.
     /// These are bodies that have been received, but cannot be committed yet because they're
@@ -114,6 +119,8 @@ pub struct HeaderDownloaderMetrics {
     /// Out-of-order request happen when the headers downloader `SyncTarget::Tip`
     /// hash is different from the previous sync target hash.
     pub out_of_order_requests: Counter,
+    /// Number of responses received from peers
+    pub received_responses: Counter,
     /// Number of timeout errors while requesting items
     pub timeout_errors: Counter,
     /// Number of validation errors while requesting items
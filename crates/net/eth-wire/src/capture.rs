@@ -0,0 +1,647 @@
+//! Opt-in capture of the `eth` protocol messages exchanged with peers.
+//!
+//! Every message that passes through an [`EthStream`](crate::EthStream) with an attached
+//! [`ProtocolCapture`] is recorded in its encoded form together with its peer, direction, `eth`
+//! version and, for responses, the latency since the matching request. The sessions only hand the
+//! records to a dedicated writer thread, which appends them as lines to a rotating set of files in
+//! the capture directory. The captured messages can be decoded and analyzed with
+//! `reth p2p replay`.
+
+use crate::{
+    message::MessageError, EthMessage, EthMessageID, EthVersion, NetworkPrimitives, ProtocolMessage,
+};
+use alloy_primitives::{hex, Bytes};
+use alloy_rlp::Decodable;
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, trace};
+
+/// The default maximum size of a capture file before it's rotated, 100MB.
+pub const DEFAULT_MAX_CAPTURE_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// The default number of capture files that are kept, including the one that is written to.
+pub const DEFAULT_MAX_CAPTURE_FILES: usize = 5;
+
+/// The name of the capture file that is written to.
+///
+/// Rotated files get a numeric suffix, e.g. `capture.log.1`, with higher numbers being older.
+pub const CAPTURE_FILE_NAME: &str = "capture.log";
+
+/// Maximum number of requests that are tracked for the latency of their responses.
+///
+/// Requests that are never answered would otherwise accumulate.
+const MAX_PENDING_REQUESTS: usize = 10_000;
+
+/// Maximum number of messages that are buffered for the writer thread.
+///
+/// Messages are dropped if the writer can't keep up, so that sessions never block on the capture.
+const CAPTURE_CHANNEL_SIZE: usize = 16_384;
+
+/// Configuration of a [`ProtocolCapture`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureConfig {
+    /// The directory the capture files are written to.
+    pub dir: PathBuf,
+    /// The maximum size of a capture file in bytes before it's rotated.
+    pub max_file_size: u64,
+    /// The number of capture files that are kept, including the one that is written to.
+    pub max_files: usize,
+}
+
+impl CaptureConfig {
+    /// Creates a new config that writes to the given directory with the default limits.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_file_size: DEFAULT_MAX_CAPTURE_FILE_SIZE,
+            max_files: DEFAULT_MAX_CAPTURE_FILES,
+        }
+    }
+
+    /// Sets the maximum size of a capture file in bytes.
+    pub const fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Sets the number of capture files that are kept.
+    pub const fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+}
+
+/// The direction of a captured message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptureDirection {
+    /// The message was received from the peer.
+    Inbound,
+    /// The message was sent to the peer.
+    Outbound,
+}
+
+impl CaptureDirection {
+    /// Returns the string representation used in the capture files.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Inbound => "in",
+            Self::Outbound => "out",
+        }
+    }
+
+    /// Returns the direction of the request a message in this direction responds to.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Inbound => Self::Outbound,
+            Self::Outbound => Self::Inbound,
+        }
+    }
+}
+
+impl fmt::Display for CaptureDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// A single captured protocol message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Time the message was captured, in milliseconds since the unix epoch.
+    pub timestamp_ms: u64,
+    /// The peer the message was exchanged with.
+    pub peer_id: PeerId,
+    /// Whether the message was received or sent.
+    pub direction: CaptureDirection,
+    /// The `eth` version of the session, required to decode the message.
+    pub version: EthVersion,
+    /// The id of the message.
+    pub message_id: EthMessageID,
+    /// The request id of request and response messages.
+    pub request_id: Option<u64>,
+    /// The time between the request and this response in milliseconds, if this is a response to
+    /// a captured request.
+    pub latency_ms: Option<u64>,
+    /// The encoded message, including the message id.
+    pub payload: Bytes,
+}
+
+impl CaptureRecord {
+    /// Returns the encoded size of the message in bytes.
+    pub fn size(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Decodes the captured message.
+    ///
+    /// Messages of other subprotocols that are multiplexed with `eth` can't be decoded.
+    pub fn decode_message<N: NetworkPrimitives>(&self) -> Result<EthMessage<N>, MessageError> {
+        ProtocolMessage::decode_message(self.version, &mut self.payload.as_ref())
+            .map(|msg| msg.message)
+    }
+
+    /// Returns the record as a line of a capture file, without the trailing newline.
+    ///
+    /// The fields are tab separated, absent optional fields are written as `-`.
+    pub fn to_line(&self) -> String {
+        let optional =
+            |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp_ms,
+            self.peer_id,
+            self.direction,
+            u8::from(self.version),
+            self.message_id.to_u8(),
+            optional(self.request_id),
+            optional(self.latency_ms),
+            hex::encode(&self.payload),
+        )
+    }
+
+    /// Parses a record from a line of a capture file.
+    ///
+    /// Returns `None` if the line isn't a valid record.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let optional = |field: &str| match field {
+            "-" => Some(None),
+            field => field.parse().ok().map(Some),
+        };
+
+        let mut fields = line.split('\t');
+        let timestamp_ms = fields.next()?.parse().ok()?;
+        let peer_id = fields.next()?.parse().ok()?;
+        let direction = match fields.next()? {
+            "in" => CaptureDirection::Inbound,
+            "out" => CaptureDirection::Outbound,
+            _ => return None,
+        };
+        let version = EthVersion::try_from(fields.next()?.parse::<u8>().ok()?).ok()?;
+        let message_id = fields.next()?.parse::<u8>().ok()?;
+        let request_id = optional(fields.next()?)?;
+        let latency_ms = optional(fields.next()?)?;
+        let payload = hex::decode(fields.next()?).ok()?.into();
+        if fields.next().is_some() {
+            return None
+        }
+
+        Some(Self {
+            timestamp_ms,
+            peer_id,
+            direction,
+            version,
+            message_id: EthMessageID::decode(&mut &[message_id][..]).ok()?,
+            request_id,
+            latency_ms,
+            payload,
+        })
+    }
+}
+
+/// Returns the capture files in the given directory, oldest first.
+pub fn capture_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut rotated = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+        if let Some(index) = name
+            .strip_prefix(CAPTURE_FILE_NAME)
+            .and_then(|suffix| suffix.strip_prefix('.'))
+            .and_then(|index| index.parse::<usize>().ok())
+        {
+            rotated.push((index, path));
+        }
+    }
+    rotated.sort_unstable_by_key(|(index, _)| std::cmp::Reverse(*index));
+
+    let mut files = rotated.into_iter().map(|(_, path)| path).collect::<Vec<_>>();
+    let current = dir.join(CAPTURE_FILE_NAME);
+    if current.exists() {
+        files.push(current);
+    }
+    Ok(files)
+}
+
+/// Writes lines to the capture file, rotating it once it exceeds the maximum size.
+#[derive(Debug)]
+struct RotatingWriter {
+    config: CaptureConfig,
+    file: BufWriter<File>,
+    /// The number of bytes in the current file.
+    written: u64,
+}
+
+impl RotatingWriter {
+    fn new(config: CaptureConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+        let file = Self::open(&config.dir)?;
+        let written = file.metadata()?.len();
+        Ok(Self { config, file: BufWriter::new(file), written })
+    }
+
+    fn open(dir: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(dir.join(CAPTURE_FILE_NAME))
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.written > 0 && self.written + len > self.config.max_file_size {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{line}")?;
+        self.written += len;
+        Ok(())
+    }
+
+    /// Shifts the rotated files by one, dropping the oldest, and starts a new capture file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let dir = &self.config.dir;
+        let rotated = |index: usize| dir.join(format!("{CAPTURE_FILE_NAME}.{index}"));
+
+        let max_rotated = self.config.max_files.saturating_sub(1);
+        if max_rotated == 0 {
+            fs::remove_file(dir.join(CAPTURE_FILE_NAME))?;
+        } else {
+            for index in (1..max_rotated).rev() {
+                match fs::rename(rotated(index), rotated(index + 1)) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+            }
+            fs::rename(dir.join(CAPTURE_FILE_NAME), rotated(1))?;
+        }
+
+        self.file = BufWriter::new(Self::open(dir)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// A message handed from a session to the writer thread.
+#[derive(Debug)]
+struct CapturedMessage {
+    captured_at: Instant,
+    timestamp_ms: u64,
+    peer_id: PeerId,
+    direction: CaptureDirection,
+    version: EthVersion,
+    message_id: EthMessageID,
+    request_id: Option<u64>,
+    kind: MessageKind,
+    payload: Bytes,
+}
+
+/// Commands handled by the writer thread.
+#[derive(Debug)]
+enum CaptureCommand {
+    /// Write the message to the capture file.
+    Record(Box<CapturedMessage>),
+    /// Flush the capture file and notify the sender.
+    Flush(SyncSender<()>),
+}
+
+/// Owns the capture files and tracks the pending requests of all sessions.
+#[derive(Debug)]
+struct CaptureWriter {
+    writer: RotatingWriter,
+    /// The time requests were captured at, keyed by peer, direction and request id.
+    pending_requests: HashMap<(PeerId, CaptureDirection, u64), Instant>,
+}
+
+impl CaptureWriter {
+    /// Writes the received messages until all [`ProtocolCapture`] handles are dropped.
+    fn run(mut self, commands: Receiver<CaptureCommand>) {
+        for command in commands {
+            match command {
+                CaptureCommand::Record(message) => {
+                    if let Err(err) = self.write(*message) {
+                        debug!(target: "net::capture", %err, "Failed to write capture record");
+                    }
+                }
+                CaptureCommand::Flush(done) => {
+                    if let Err(err) = self.writer.file.flush() {
+                        debug!(target: "net::capture", %err, "Failed to flush capture file");
+                    }
+                    let _ = done.send(());
+                }
+            }
+        }
+        let _ = self.writer.file.flush();
+    }
+
+    fn write(&mut self, message: CapturedMessage) -> io::Result<()> {
+        let CapturedMessage {
+            captured_at,
+            timestamp_ms,
+            peer_id,
+            direction,
+            version,
+            message_id,
+            request_id,
+            kind,
+            payload,
+        } = message;
+
+        let mut latency_ms = None;
+        if let Some(request_id) = request_id {
+            match kind {
+                MessageKind::Request => {
+                    if self.pending_requests.len() < MAX_PENDING_REQUESTS {
+                        self.pending_requests.insert((peer_id, direction, request_id), captured_at);
+                    }
+                }
+                MessageKind::Response => {
+                    latency_ms = self
+                        .pending_requests
+                        .remove(&(peer_id, direction.opposite(), request_id))
+                        .map(|requested_at| (captured_at - requested_at).as_millis() as u64);
+                }
+                MessageKind::Other => {}
+            }
+        }
+
+        let record = CaptureRecord {
+            timestamp_ms,
+            peer_id,
+            direction,
+            version,
+            message_id,
+            request_id,
+            latency_ms,
+            payload,
+        };
+        self.writer.write_line(&record.to_line())
+    }
+}
+
+/// A shared handle that records protocol messages to rotating capture files.
+///
+/// This is cheap to clone, all clones send to the same writer thread, which exits once all handles
+/// are dropped.
+#[derive(Debug, Clone)]
+pub struct ProtocolCapture {
+    commands: SyncSender<CaptureCommand>,
+}
+
+impl ProtocolCapture {
+    /// Creates the capture directory and spawns the thread that writes the capture files.
+    pub fn new(config: CaptureConfig) -> io::Result<Self> {
+        let writer = CaptureWriter {
+            writer: RotatingWriter::new(config)?,
+            pending_requests: HashMap::new(),
+        };
+        let (commands, rx) = mpsc::sync_channel(CAPTURE_CHANNEL_SIZE);
+        thread::Builder::new().name("p2p-capture".to_string()).spawn(move || writer.run(rx))?;
+        Ok(Self { commands })
+    }
+
+    /// Records an encoded message that was exchanged with the given peer.
+    pub fn record_message<N: NetworkPrimitives>(
+        &self,
+        peer_id: PeerId,
+        direction: CaptureDirection,
+        version: EthVersion,
+        message: &EthMessage<N>,
+        payload: Bytes,
+    ) {
+        let kind = if message.is_request() {
+            MessageKind::Request
+        } else if message.is_response() {
+            MessageKind::Response
+        } else {
+            MessageKind::Other
+        };
+        self.record(
+            peer_id,
+            direction,
+            version,
+            message.message_id(),
+            request_id(message),
+            kind,
+            payload,
+        )
+    }
+
+    /// Records an encoded message that isn't a request or response, e.g. a broadcast.
+    pub fn record_other(
+        &self,
+        peer_id: PeerId,
+        direction: CaptureDirection,
+        version: EthVersion,
+        message_id: EthMessageID,
+        payload: Bytes,
+    ) {
+        self.record(peer_id, direction, version, message_id, None, MessageKind::Other, payload)
+    }
+
+    /// Blocks until all messages recorded so far are written to the capture file.
+    pub fn flush(&self) {
+        let (done, rx) = mpsc::sync_channel(1);
+        if self.commands.send(CaptureCommand::Flush(done)).is_ok() {
+            let _ = rx.recv();
+        }
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn record(
+        &self,
+        peer_id: PeerId,
+        direction: CaptureDirection,
+        version: EthVersion,
+        message_id: EthMessageID,
+        request_id: Option<u64>,
+        kind: MessageKind,
+        payload: Bytes,
+    ) {
+        let message = CapturedMessage {
+            captured_at: Instant::now(),
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            peer_id,
+            direction,
+            version,
+            message_id,
+            request_id,
+            kind,
+            payload,
+        };
+        match self.commands.try_send(CaptureCommand::Record(Box::new(message))) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                trace!(target: "net::capture", %peer_id, ?message_id, "Capture channel full, dropping message");
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                debug!(target: "net::capture", "Capture writer stopped");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MessageKind {
+    Request,
+    Response,
+    Other,
+}
+
+/// Returns the request id of request and response messages.
+const fn request_id<N: NetworkPrimitives>(message: &EthMessage<N>) -> Option<u64> {
+    let id = match message {
+        EthMessage::GetBlockHeaders(pair) => pair.request_id,
+        EthMessage::BlockHeaders(pair) => pair.request_id,
+        EthMessage::GetBlockBodies(pair) => pair.request_id,
+        EthMessage::BlockBodies(pair) => pair.request_id,
+        EthMessage::GetPooledTransactions(pair) => pair.request_id,
+        EthMessage::PooledTransactions(pair) => pair.request_id,
+        EthMessage::GetNodeData(pair) => pair.request_id,
+        EthMessage::NodeData(pair) => pair.request_id,
+        EthMessage::GetReceipts(pair) => pair.request_id,
+        EthMessage::Receipts(pair) => pair.request_id,
+        EthMessage::Receipts69(pair) => pair.request_id,
+        EthMessage::Status(_) |
+        EthMessage::NewBlockHashes(_) |
+        EthMessage::NewBlock(_) |
+        EthMessage::Transactions(_) |
+        EthMessage::NewPooledTransactionHashes66(_) |
+        EthMessage::NewPooledTransactionHashes68(_) |
+        EthMessage::BlockRangeUpdate(_) |
+        EthMessage::Other(_) => return None,
+    };
+    Some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{message::RequestPair, BlockHeaders, EthNetworkPrimitives, GetBlockHeaders};
+
+    fn read_records(dir: &Path) -> Vec<CaptureRecord> {
+        capture_files(dir)
+            .unwrap()
+            .into_iter()
+            .flat_map(|path| {
+                fs::read_to_string(path)
+                    .unwrap()
+                    .lines()
+                    .map(|line| CaptureRecord::from_line(line).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn encode(message: EthMessage<EthNetworkPrimitives>) -> Bytes {
+        alloy_rlp::encode(ProtocolMessage::from(message)).into()
+    }
+
+    #[test]
+    fn records_encoded_messages_and_latency() {
+        let dir = tempfile::tempdir().unwrap();
+        let capture = ProtocolCapture::new(CaptureConfig::new(dir.path())).unwrap();
+        let peer_id = PeerId::random();
+        let version = EthVersion::Eth68;
+
+        let request = EthMessage::<EthNetworkPrimitives>::GetBlockHeaders(RequestPair {
+            request_id: 7,
+            message: GetBlockHeaders {
+                start_block: 1u64.into(),
+                limit: 1,
+                skip: 0,
+                direction: Default::default(),
+            },
+        });
+        let response = EthMessage::<EthNetworkPrimitives>::BlockHeaders(RequestPair {
+            request_id: 7,
+            message: BlockHeaders::default(),
+        });
+        capture.record_message(
+            peer_id,
+            CaptureDirection::Outbound,
+            version,
+            &request,
+            encode(request.clone()),
+        );
+        // a response in the same direction doesn't answer the request
+        capture.record_message(
+            peer_id,
+            CaptureDirection::Outbound,
+            version,
+            &response,
+            encode(response.clone()),
+        );
+        capture.record_message(
+            peer_id,
+            CaptureDirection::Inbound,
+            version,
+            &response,
+            encode(response.clone()),
+        );
+        capture.record_other(
+            peer_id,
+            CaptureDirection::Inbound,
+            version,
+            EthMessageID::Other(0x20),
+            Bytes::from_static(&[0x20, 0xc0]),
+        );
+        capture.flush();
+
+        let records = read_records(dir.path());
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].message_id, EthMessageID::GetBlockHeaders);
+        assert_eq!(records[0].request_id, Some(7));
+        assert_eq!(records[0].latency_ms, None);
+        assert_eq!(records[0].decode_message::<EthNetworkPrimitives>().unwrap(), request);
+        assert_eq!(records[1].latency_ms, None);
+        assert_eq!(records[2].peer_id, peer_id);
+        assert_eq!(records[2].direction, CaptureDirection::Inbound);
+        assert_eq!(records[2].version, version);
+        assert!(records[2].latency_ms.is_some());
+        assert_eq!(records[2].decode_message::<EthNetworkPrimitives>().unwrap(), response);
+        assert_eq!(records[3].message_id, EthMessageID::Other(0x20));
+        assert_eq!(records[3].request_id, None);
+        assert_eq!(records[3].size(), 2);
+    }
+
+    #[test]
+    fn rotates_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = CaptureConfig::new(dir.path()).with_max_file_size(1).with_max_files(3);
+        let capture = ProtocolCapture::new(config).unwrap();
+        let peer_id = PeerId::random();
+
+        for size in 0..5 {
+            capture.record_other(
+                peer_id,
+                CaptureDirection::Outbound,
+                EthVersion::Eth68,
+                EthMessageID::NewBlock,
+                vec![0; size].into(),
+            );
+        }
+        capture.flush();
+
+        // every record ends up in its own file and only the three most recent are kept
+        let files = capture_files(dir.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("capture.log.2"),
+                dir.path().join("capture.log.1"),
+                dir.path().join("capture.log"),
+            ]
+        );
+        let sizes = read_records(dir.path()).iter().map(CaptureRecord::size).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![2, 3, 4]);
+    }
+}
@@ -70,6 +70,22 @@ impl EthStreamError {
         }
         None
     }
+
+    /// Returns true if the error was caused by a message of the peer that could not be decoded.
+    ///
+    /// These errors are not necessarily caused by a malicious peer, but frequently by buggy
+    /// clients.
+    pub const fn is_malformed_message(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidMessage(_) |
+                Self::MessageTooBig(_) |
+                Self::TransactionHashesInvalidLenOfFields { .. } |
+                Self::P2PStreamError(
+                    P2PStreamError::Rlp(_) | P2PStreamError::MessageTooBig { .. }
+                )
+        )
+    }
 }
 
 impl From<io::Error> for EthStreamError {
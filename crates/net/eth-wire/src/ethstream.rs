@@ -5,12 +5,13 @@
 //! Handles handshaking, message processing, and RLP serialization.
 
 use crate::{
+    capture::{CaptureDirection, ProtocolCapture},
     errors::{EthHandshakeError, EthStreamError},
     handshake::EthereumEthHandshake,
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, ProtocolMessage,
-    UnifiedStatus,
+    CanDisconnect, DisconnectReason, EthMessage, EthMessageID, EthNetworkPrimitives, EthVersion,
+    ProtocolMessage, UnifiedStatus,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use alloy_rlp::Encodable;
@@ -18,6 +19,7 @@ use futures::{ready, Sink, SinkExt};
 use pin_project::pin_project;
 use reth_eth_wire_types::{NetworkPrimitives, RawCapabilityMessage};
 use reth_ethereum_forks::ForkFilter;
+use reth_network_peers::PeerId;
 use std::{
     future::Future,
     pin::Pin,
@@ -182,6 +184,8 @@ pub struct EthStream<S, N = EthNetworkPrimitives> {
     eth: EthStreamInner<N>,
     #[pin]
     inner: S,
+    /// Records the exchanged messages if capturing is enabled, together with the remote peer.
+    capture: Option<(ProtocolCapture, PeerId)>,
 }
 
 impl<S, N: NetworkPrimitives> EthStream<S, N> {
@@ -189,7 +193,12 @@ impl<S, N: NetworkPrimitives> EthStream<S, N> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { eth: EthStreamInner::new(version), inner }
+        Self { eth: EthStreamInner::new(version), inner, capture: None }
+    }
+
+    /// Records all messages exchanged with the given peer to the [`ProtocolCapture`].
+    pub fn set_capture(&mut self, capture: ProtocolCapture, peer_id: PeerId) {
+        self.capture = Some((capture, peer_id));
     }
 
     /// Returns the eth version.
@@ -228,9 +237,18 @@ where
         &mut self,
         item: EthBroadcastMessage<N>,
     ) -> Result<(), EthStreamError> {
-        self.inner.start_send_unpin(Bytes::from(alloy_rlp::encode(
-            ProtocolBroadcastMessage::from(item),
-        )))?;
+        let message_id = item.message_id();
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolBroadcastMessage::from(item)));
+        if let Some((capture, peer_id)) = &self.capture {
+            capture.record_other(
+                *peer_id,
+                CaptureDirection::Outbound,
+                self.eth.version(),
+                message_id,
+                bytes.clone().into(),
+            );
+        }
+        self.inner.start_send_unpin(bytes)?;
 
         Ok(())
     }
@@ -241,6 +259,15 @@ where
         msg.id.encode(&mut bytes);
         bytes.extend_from_slice(&msg.payload);
 
+        if let Some((capture, peer_id)) = &self.capture {
+            capture.record_other(
+                *peer_id,
+                CaptureDirection::Outbound,
+                self.eth.version(),
+                EthMessageID::Other(msg.id as u8),
+                Bytes::copy_from_slice(&bytes).into(),
+            );
+        }
         self.inner.start_send_unpin(bytes.into())?;
         Ok(())
     }
@@ -259,7 +286,22 @@ where
         let res = ready!(this.inner.poll_next(cx));
 
         match res {
-            Some(Ok(bytes)) => Poll::Ready(Some(this.eth.decode_message(bytes))),
+            Some(Ok(bytes)) => {
+                let payload = this.capture.is_some().then(|| Bytes::copy_from_slice(&bytes));
+                let res = this.eth.decode_message(bytes);
+                if let (Some((capture, peer_id)), Some(payload), Ok(msg)) =
+                    (this.capture.as_ref(), payload, &res)
+                {
+                    capture.record_message(
+                        *peer_id,
+                        CaptureDirection::Inbound,
+                        this.eth.version(),
+                        msg,
+                        payload.into(),
+                    );
+                }
+                Poll::Ready(Some(res))
+            }
             Some(Err(err)) => Poll::Ready(Some(Err(err.into()))),
             None => Poll::Ready(None),
         }
@@ -292,9 +334,19 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
         }
 
-        self.project()
-            .inner
-            .start_send(Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item))))?;
+        let this = self.project();
+        let message = ProtocolMessage::from(item);
+        let bytes = Bytes::from(alloy_rlp::encode(&message));
+        if let Some((capture, peer_id)) = this.capture.as_ref() {
+            capture.record_message(
+                *peer_id,
+                CaptureDirection::Outbound,
+                this.eth.version(),
+                &message.message,
+                bytes.clone().into(),
+            );
+        }
+        this.inner.start_send(bytes)?;
 
         Ok(())
     }
@@ -0,0 +1,110 @@
+//! Decoding fuzzing of the `eth-wire` crate with arbitrary and malformed input.
+//!
+//! Messages are received from untrusted peers, so decoding must never panic, no matter how the
+//! input is malformed.
+
+use alloy_rlp::{Decodable, Encodable};
+use reth_eth_wire::{EthNetworkPrimitives, EthVersion, P2PMessage, ProtocolMessage};
+
+/// Decodes the input as `eth` message of every protocol version.
+///
+/// A message that decodes successfully has to decode to the same message again after it was
+/// re-encoded.
+fn decode_eth_message(input: &[u8]) {
+    for version in EthVersion::ALL_VERSIONS {
+        let Ok(message) =
+            ProtocolMessage::<EthNetworkPrimitives>::decode_message(*version, &mut &input[..])
+        else {
+            continue
+        };
+
+        let mut encoded = Vec::new();
+        message.encode(&mut encoded);
+        let decoded =
+            ProtocolMessage::<EthNetworkPrimitives>::decode_message(*version, &mut &encoded[..])
+                .unwrap();
+        assert_eq!(message, decoded, "eth/{version} message changed after re-encoding");
+    }
+}
+
+/// Decodes the input as `p2p` message.
+fn decode_p2p_message(input: &[u8]) {
+    let Ok(message) = P2PMessage::decode(&mut &input[..]) else { return };
+
+    let mut encoded = Vec::new();
+    message.encode(&mut encoded);
+    assert_eq!(message, P2PMessage::decode(&mut &encoded[..]).unwrap());
+}
+
+#[cfg(test)]
+#[expect(missing_docs)]
+pub mod fuzz_decode {
+    use crate::{decode_eth_message, decode_p2p_message};
+    use alloy_rlp::Encodable;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use reth_eth_wire::{
+        BlockHeaders, EthMessage, EthNetworkPrimitives, GetBlockHeaders, NewBlockHashes,
+        NewPooledTransactionHashes68, ProtocolMessage, RequestPair, Transactions,
+    };
+    use test_fuzz::test_fuzz;
+
+    /// Fuzzes decoding of arbitrary bytes as `eth` message.
+    #[test_fuzz]
+    fn fuzz_eth_message(input: Vec<u8>) {
+        decode_eth_message(&input)
+    }
+
+    /// Fuzzes decoding of arbitrary bytes as `p2p` message.
+    #[test_fuzz]
+    fn fuzz_p2p_message(input: Vec<u8>) {
+        decode_p2p_message(&input)
+    }
+
+    /// Returns the encoding of valid `eth` messages.
+    fn valid_eth_message() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            arb::<Transactions>().prop_map(EthMessage::Transactions),
+            arb::<NewBlockHashes>().prop_map(EthMessage::NewBlockHashes),
+            any::<NewPooledTransactionHashes68>()
+                .prop_map(EthMessage::NewPooledTransactionHashes68),
+            (any::<u64>(), arb::<GetBlockHeaders>()).prop_map(|(request_id, message)| {
+                EthMessage::GetBlockHeaders(RequestPair { request_id, message })
+            }),
+            (any::<u64>(), arb::<BlockHeaders>()).prop_map(|(request_id, message)| {
+                EthMessage::BlockHeaders(RequestPair { request_id, message })
+            }),
+        ]
+        .prop_map(|message: EthMessage<EthNetworkPrimitives>| {
+            let mut encoded = Vec::new();
+            ProtocolMessage::from(message).encode(&mut encoded);
+            encoded
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn decode_arbitrary_bytes(input in proptest::collection::vec(any::<u8>(), 0..1024)) {
+            decode_eth_message(&input);
+            decode_p2p_message(&input);
+        }
+
+        #[test]
+        fn decode_truncated_message(encoded in valid_eth_message(), len in any::<prop::sample::Index>()) {
+            let len = len.index(encoded.len());
+            decode_eth_message(&encoded[..len]);
+        }
+
+        #[test]
+        fn decode_corrupted_message(
+            mut encoded in valid_eth_message(),
+            corruptions in proptest::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+        ) {
+            for (index, byte) in corruptions {
+                let index = index.index(encoded.len());
+                encoded[index] ^= byte;
+            }
+            decode_eth_message(&encoded);
+        }
+    }
+}
@@ -13,8 +13,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod net_if;
+pub mod stun;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
+pub use stun::{StunError, DEFAULT_STUN_SERVERS};
 
 use std::{
     fmt,
@@ -25,9 +27,9 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
-use tracing::{debug, error};
+use tracing::debug;
 
-use crate::net_if::resolve_net_if_ip;
+use crate::{net_if::resolve_net_if_ip, stun::resolve_stun_addr};
 #[cfg(feature = "serde")]
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 
@@ -48,6 +50,8 @@ pub enum NatResolver {
     Upnp,
     /// Resolve external IP via a network request.
     PublicIp,
+    /// Resolve external IP via a STUN binding request.
+    Stun,
     /// Use the given [`IpAddr`]
     ExternalIp(IpAddr),
     /// Resolve external IP via the network interface.
@@ -77,6 +81,7 @@ impl fmt::Display for NatResolver {
             Self::Any => f.write_str("any"),
             Self::Upnp => f.write_str("upnp"),
             Self::PublicIp => f.write_str("publicip"),
+            Self::Stun => f.write_str("stun"),
             Self::ExternalIp(ip) => write!(f, "extip:{ip}"),
             Self::NetIf => f.write_str("netif"),
             Self::None => f.write_str("none"),
@@ -104,6 +109,7 @@ impl FromStr for NatResolver {
             "upnp" => Self::Upnp,
             "none" => Self::None,
             "publicip" | "public-ip" => Self::PublicIp,
+            "stun" => Self::Stun,
             "netif" => Self::NetIf,
             s => {
                 let Some(ip) = s.strip_prefix("extip:") else {
@@ -200,9 +206,17 @@ pub async fn external_ip() -> Option<IpAddr> {
 }
 
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
+///
+/// If the external IP can't be resolved via a network request, STUN is used as a fallback.
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => {
+            match resolve_external_ip().await {
+                Some(ip) => Some(ip),
+                None => resolve_external_ip_stun().await,
+            }
+        }
+        NatResolver::Stun => resolve_external_ip_stun().await,
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -230,6 +244,20 @@ async fn resolve_external_ip() -> Option<IpAddr> {
         .map(|(ip, _)| ip)
 }
 
+async fn resolve_external_ip_stun() -> Option<IpAddr> {
+    let futures = DEFAULT_STUN_SERVERS.iter().copied().map(resolve_stun_addr).map(Box::pin);
+    futures_util::future::select_ok(futures)
+        .await
+        .inspect_err(|err| {
+            debug!(target: "net::nat",
+                %err,
+                stun_servers=?DEFAULT_STUN_SERVERS,
+                "Failed to resolve external IP from any STUN server");
+        })
+        .ok()
+        .map(|(addr, _)| addr.ip())
+}
+
 async fn resolve_external_ip_url_res(url: &str) -> Result<IpAddr, ()> {
     resolve_external_ip_url(url).await.ok_or(())
 }
@@ -271,6 +299,8 @@ mod tests {
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
+        assert_eq!(NatResolver::Stun, "stun".parse().unwrap());
+        assert_eq!(NatResolver::Stun.to_string(), "stun");
 
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
@@ -0,0 +1,245 @@
+//! Resolution of the external IP address via STUN.
+//!
+//! Sends a STUN binding request, see [RFC 5389](https://datatracker.ietf.org/doc/html/rfc5389),
+//! to a public STUN server, which answers with the address the request was received from.
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// Public STUN servers that are queried for the external IP address.
+pub const DEFAULT_STUN_SERVERS: &[&str] =
+    &["stun.l.google.com:19302", "stun1.l.google.com:19302", "stun.cloudflare.com:3478"];
+
+/// How long to wait for the response of a STUN server.
+const STUN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The magic cookie every STUN message carries.
+const MAGIC_COOKIE: u32 = 0x2112_A442;
+
+/// Message type of a binding request.
+const BINDING_REQUEST: u16 = 0x0001;
+
+/// Message type of a successful binding response.
+const BINDING_SUCCESS_RESPONSE: u16 = 0x0101;
+
+/// Attribute type of the `MAPPED-ADDRESS` attribute.
+const MAPPED_ADDRESS: u16 = 0x0001;
+
+/// Attribute type of the `XOR-MAPPED-ADDRESS` attribute.
+const XOR_MAPPED_ADDRESS: u16 = 0x0020;
+
+/// Length of the STUN message header.
+const HEADER_LEN: usize = 20;
+
+/// Address family of an IPv4 address in a mapped address attribute.
+const FAMILY_IPV4: u8 = 0x01;
+
+/// Address family of an IPv6 address in a mapped address attribute.
+const FAMILY_IPV6: u8 = 0x02;
+
+/// Error when resolving the external address via STUN.
+#[derive(Debug, thiserror::Error)]
+pub enum StunError {
+    /// Failed to communicate with the STUN server.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The STUN server did not respond in time.
+    #[error("STUN request timed out")]
+    Timeout,
+    /// The STUN server responded with a malformed or unexpected message.
+    #[error("invalid STUN response")]
+    InvalidResponse,
+    /// The response of the STUN server carries no mapped address.
+    #[error("STUN response contains no mapped address")]
+    MissingAddress,
+}
+
+/// Resolves the external address of this node with a binding request to the given STUN server.
+pub async fn resolve_stun_addr(server: &str) -> Result<SocketAddr, StunError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect(server).await?;
+
+    let transaction_id: [u8; 12] = rand::random();
+    socket.send(&binding_request(transaction_id)).await?;
+
+    let mut buf = [0u8; 512];
+    let len = tokio::time::timeout(STUN_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| StunError::Timeout)??;
+
+    parse_binding_response(&buf[..len], transaction_id)
+}
+
+/// Encodes a binding request without attributes.
+fn binding_request(transaction_id: [u8; 12]) -> [u8; HEADER_LEN] {
+    let mut request = [0u8; HEADER_LEN];
+    request[..2].copy_from_slice(&BINDING_REQUEST.to_be_bytes());
+    // the message length stays zero, since the request has no attributes
+    request[4..8].copy_from_slice(&MAGIC_COOKIE.to_be_bytes());
+    request[8..].copy_from_slice(&transaction_id);
+    request
+}
+
+/// Decodes the mapped address from the binding response to the request with the given
+/// transaction id.
+///
+/// The `XOR-MAPPED-ADDRESS` attribute is preferred over the `MAPPED-ADDRESS` attribute that is
+/// only sent by older servers.
+fn parse_binding_response(
+    response: &[u8],
+    transaction_id: [u8; 12],
+) -> Result<SocketAddr, StunError> {
+    if response.len() < HEADER_LEN ||
+        response[..2] != BINDING_SUCCESS_RESPONSE.to_be_bytes() ||
+        response[4..8] != MAGIC_COOKIE.to_be_bytes() ||
+        response[8..HEADER_LEN] != transaction_id
+    {
+        return Err(StunError::InvalidResponse)
+    }
+
+    let len = u16::from_be_bytes([response[2], response[3]]) as usize;
+    let mut attributes =
+        response.get(HEADER_LEN..HEADER_LEN + len).ok_or(StunError::InvalidResponse)?;
+
+    // the key of the XOR-MAPPED-ADDRESS is the magic cookie followed by the transaction id
+    let mut xor_key = [0u8; 16];
+    xor_key.copy_from_slice(&response[4..HEADER_LEN]);
+
+    let mut mapped_addr = None;
+    while attributes.len() >= 4 {
+        let kind = u16::from_be_bytes([attributes[0], attributes[1]]);
+        let value_len = u16::from_be_bytes([attributes[2], attributes[3]]) as usize;
+        let value = attributes.get(4..4 + value_len).ok_or(StunError::InvalidResponse)?;
+
+        match kind {
+            XOR_MAPPED_ADDRESS => return parse_address(value, Some(&xor_key)),
+            MAPPED_ADDRESS => mapped_addr = Some(parse_address(value, None)?),
+            _ => {}
+        }
+
+        // attribute values are padded to a multiple of 4 bytes
+        let padded_len = (4 + value_len).next_multiple_of(4);
+        attributes = attributes.get(padded_len..).unwrap_or_default();
+    }
+
+    mapped_addr.ok_or(StunError::MissingAddress)
+}
+
+/// Decodes the value of a `MAPPED-ADDRESS` attribute, or of a `XOR-MAPPED-ADDRESS` attribute if
+/// the `xor_key` is set.
+fn parse_address(value: &[u8], xor_key: Option<&[u8; 16]>) -> Result<SocketAddr, StunError> {
+    let xor = |bytes: &mut [u8]| {
+        if let Some(key) = xor_key {
+            bytes.iter_mut().zip(key).for_each(|(byte, key)| *byte ^= key);
+        }
+    };
+
+    if value.len() < 4 {
+        return Err(StunError::InvalidResponse)
+    }
+    let family = value[1];
+    let mut port = [value[2], value[3]];
+    xor(&mut port);
+    let address = &value[4..];
+
+    let ip = match (family, address.len()) {
+        (FAMILY_IPV4, 4) => {
+            let mut octets = [0u8; 4];
+            octets.copy_from_slice(address);
+            xor(&mut octets);
+            IpAddr::V4(Ipv4Addr::from(octets))
+        }
+        (FAMILY_IPV6, 16) => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(address);
+            xor(&mut octets);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return Err(StunError::InvalidResponse),
+    };
+
+    Ok(SocketAddr::new(ip, u16::from_be_bytes(port)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding_response(transaction_id: [u8; 12], attributes: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (kind, value) in attributes {
+            body.extend_from_slice(&kind.to_be_bytes());
+            body.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            body.extend_from_slice(value);
+            body.resize(body.len().next_multiple_of(4), 0);
+        }
+
+        let mut response = binding_request(transaction_id).to_vec();
+        response[..2].copy_from_slice(&BINDING_SUCCESS_RESPONSE.to_be_bytes());
+        response[2..4].copy_from_slice(&(body.len() as u16).to_be_bytes());
+        response.extend_from_slice(&body);
+        response
+    }
+
+    #[test]
+    fn parse_xor_mapped_address() {
+        let transaction_id = [7u8; 12];
+        let addr: SocketAddr = "203.0.113.7:30303".parse().unwrap();
+
+        // XOR-MAPPED-ADDRESS with the port and ip xor-ed with the magic cookie
+        let cookie = MAGIC_COOKIE.to_be_bytes();
+        let mut value = vec![0, FAMILY_IPV4];
+        value.extend_from_slice(&(addr.port() ^ (MAGIC_COOKIE >> 16) as u16).to_be_bytes());
+        let IpAddr::V4(ip) = addr.ip() else { unreachable!() };
+        value.extend(ip.octets().iter().zip(cookie).map(|(byte, key)| byte ^ key));
+
+        let response = binding_response(
+            transaction_id,
+            &[
+                (MAPPED_ADDRESS, vec![0, FAMILY_IPV4, 0, 1, 10, 0, 0, 1]),
+                (XOR_MAPPED_ADDRESS, value),
+            ],
+        );
+        assert_eq!(parse_binding_response(&response, transaction_id).unwrap(), addr);
+    }
+
+    #[test]
+    fn parse_mapped_address() {
+        let transaction_id = [1u8; 12];
+        let response = binding_response(
+            transaction_id,
+            &[
+                (0x8022, b"reth".to_vec()),
+                (MAPPED_ADDRESS, vec![0, FAMILY_IPV4, 0x76, 0x5f, 10, 0, 0, 1]),
+            ],
+        );
+        assert_eq!(
+            parse_binding_response(&response, transaction_id).unwrap(),
+            "10.0.0.1:30303".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_foreign_transaction() {
+        let response = binding_response([1u8; 12], &[]);
+        assert!(matches!(
+            parse_binding_response(&response, [2u8; 12]),
+            Err(StunError::InvalidResponse)
+        ));
+        assert!(matches!(
+            parse_binding_response(&response, [1u8; 12]),
+            Err(StunError::MissingAddress)
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn get_stun_addr() {
+        let addr = resolve_stun_addr(DEFAULT_STUN_SERVERS[0]).await;
+        dbg!(addr.unwrap());
+    }
+}
@@ -32,4 +32,4 @@ pub use peers::{
     state::PeerConnectionState,
     ConnectionsConfig, Peer, PeersConfig,
 };
-pub use session::{SessionLimits, SessionsConfig};
+pub use session::{BandwidthLimits, SessionLimits, SessionsConfig};
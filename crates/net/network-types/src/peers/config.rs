@@ -3,7 +3,7 @@
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
-    path::Path,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -131,6 +131,15 @@ pub struct PeersConfig {
     /// Connect to or accept from trusted nodes only?
     #[cfg_attr(feature = "serde", serde(alias = "connect_trusted_nodes_only"))]
     pub trusted_nodes_only: bool,
+    /// File the trusted nodes that are added or removed at runtime are persisted to.
+    ///
+    /// The nodes persisted by a previous run are trusted in addition to the
+    /// [`Self::trusted_nodes`], see [`Self::with_trusted_nodes_file`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub trusted_nodes_file: Option<PathBuf>,
+    /// Trusted nodes loaded from the [`Self::trusted_nodes_file`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_trusted_nodes: Vec<TrustedPeer>,
     /// Interval to update trusted nodes DNS resolution
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub trusted_nodes_resolution_interval: Duration,
@@ -166,6 +175,16 @@ pub struct PeersConfig {
     /// This acts as an IP based rate limit.
     #[cfg_attr(feature = "serde", serde(default, with = "humantime_serde"))]
     pub incoming_ip_throttle_duration: Duration,
+    /// Number of malformed messages after which a peer is quarantined.
+    ///
+    /// Malformed messages are frequently caused by buggy clients, so instead of banning the peer
+    /// it is disconnected and not dialed again for the [`Self::quarantine_duration`].
+    ///
+    /// Note: this does not apply to trusted and static peers.
+    pub malformed_message_threshold: u8,
+    /// How long to quarantine peers that repeatedly sent malformed messages.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub quarantine_duration: Duration,
 }
 
 impl Default for PeersConfig {
@@ -180,10 +199,15 @@ impl Default for PeersConfig {
             backoff_durations: Default::default(),
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
+            trusted_nodes_file: None,
+            persisted_trusted_nodes: Default::default(),
             trusted_nodes_resolution_interval: Duration::from_secs(60 * 60),
             basic_nodes: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
+            malformed_message_threshold: 3,
+            // 30min
+            quarantine_duration: Duration::from_secs(60 * 30),
         }
     }
 }
@@ -201,6 +225,18 @@ impl PeersConfig {
         self
     }
 
+    /// Configure after how many malformed messages a peer is quarantined
+    pub const fn with_malformed_message_threshold(mut self, threshold: u8) -> Self {
+        self.malformed_message_threshold = threshold;
+        self
+    }
+
+    /// Configure how long to quarantine peers that repeatedly sent malformed messages
+    pub const fn with_quarantine_duration(mut self, quarantine_duration: Duration) -> Self {
+        self.quarantine_duration = quarantine_duration;
+        self
+    }
+
     /// Configure how long to refill outbound slots
     pub const fn with_refill_slots_interval(mut self, interval: Duration) -> Self {
         self.refill_slots_interval = interval;
@@ -301,6 +337,25 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Sets the file the trusted nodes that are added or removed at runtime are persisted to, and
+    /// reads the nodes persisted by a previous run from it. Ignored if None.
+    pub fn with_trusted_nodes_file(
+        mut self,
+        optional_file: Option<PathBuf>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        match std::fs::File::open(&file_path) {
+            Ok(file) => {
+                info!(target: "net::peers", file = %file_path.display(), "Loading trusted peers");
+                self.persisted_trusted_nodes = serde_json::from_reader(io::BufReader::new(file))?;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => Err(e)?,
+        }
+        self.trusted_nodes_file = Some(file_path);
+        Ok(self)
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
@@ -308,6 +363,7 @@ impl PeersConfig {
             refill_slots_interval: Duration::from_millis(100),
             backoff_durations: PeerBackoffDurations::test(),
             ban_duration: Duration::from_millis(200),
+            quarantine_duration: Duration::from_millis(200),
             ..Default::default()
         }
     }
@@ -50,6 +50,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// Outbound bandwidth limits for responses to requests of peers.
+    ///
+    /// By default, no limits will be enforced.
+    pub bandwidth_limits: BandwidthLimits,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +73,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            bandwidth_limits: Default::default(),
         }
     }
 }
@@ -100,6 +105,12 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Sets the outbound bandwidth limits for responses to requests of peers.
+    pub const fn with_bandwidth_limits(mut self, limits: BandwidthLimits) -> Self {
+        self.bandwidth_limits = limits;
+        self
+    }
 }
 
 /// Limits for sessions.
@@ -144,6 +155,60 @@ impl SessionLimits {
     }
 }
 
+/// Outbound bandwidth limits for responses to requests of peers, in bytes per second.
+///
+/// Only responses are limited, so that serving historical data to peers can't starve the
+/// propagation of new blocks and transactions. Each limit allows bursts of up to one second worth
+/// of data.
+///
+/// By default, no bandwidth limits will be enforced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandwidthLimits {
+    /// Maximum outbound bandwidth for all responses to a single peer.
+    pub per_peer: Option<u64>,
+    /// Maximum outbound bandwidth for block bodies responses to all peers.
+    pub block_bodies: Option<u64>,
+    /// Maximum outbound bandwidth for receipts responses to all peers.
+    pub receipts: Option<u64>,
+    /// Maximum outbound bandwidth for pooled transactions responses to all peers.
+    pub pooled_transactions: Option<u64>,
+}
+
+impl BandwidthLimits {
+    /// Sets the maximum outbound bandwidth for all responses to a single peer.
+    pub const fn with_per_peer(mut self, bytes_per_sec: u64) -> Self {
+        self.per_peer = Some(bytes_per_sec);
+        self
+    }
+
+    /// Sets the maximum outbound bandwidth for block bodies responses to all peers.
+    pub const fn with_block_bodies(mut self, bytes_per_sec: u64) -> Self {
+        self.block_bodies = Some(bytes_per_sec);
+        self
+    }
+
+    /// Sets the maximum outbound bandwidth for receipts responses to all peers.
+    pub const fn with_receipts(mut self, bytes_per_sec: u64) -> Self {
+        self.receipts = Some(bytes_per_sec);
+        self
+    }
+
+    /// Sets the maximum outbound bandwidth for pooled transactions responses to all peers.
+    pub const fn with_pooled_transactions(mut self, bytes_per_sec: u64) -> Self {
+        self.pooled_transactions = Some(bytes_per_sec);
+        self
+    }
+
+    /// Returns true if no limits are configured.
+    pub const fn is_unlimited(&self) -> bool {
+        self.per_peer.is_none() &&
+            self.block_bodies.is_none() &&
+            self.receipts.is_none() &&
+            self.pooled_transactions.is_none()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
 //! Peer sessions configuration.
 
 pub mod config;
-pub use config::{SessionLimits, SessionsConfig};
+pub use config::{BandwidthLimits, SessionLimits, SessionsConfig};
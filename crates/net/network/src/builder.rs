@@ -4,15 +4,18 @@ use std::fmt::Debug;
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap_requests::{SnapProtocolHandler, SnapRequestHandler},
     transactions::{
         config::{StrictEthAnnouncementFilter, TransactionPropagationKind},
-        policy::NetworkPolicies,
+        policy::{NetworkPolicies, TransactionPolicies},
+        pool_sync::PoolSyncProtocolHandler,
         TransactionPropagationPolicy, TransactionsManager, TransactionsManagerConfig,
     },
     NetworkHandle, NetworkManager,
 };
 use reth_eth_wire::{EthNetworkPrimitives, NetworkPrimitives};
 use reth_network_api::test_utils::PeersHandleProvider;
+use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
 
@@ -20,6 +23,10 @@ use tokio::sync::mpsc;
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// The max channel capacity of the `SnapRequestHandler`, analogous to
+/// [`ETH_REQUEST_CHANNEL_CAPACITY`].
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
 /// A builder that can configure all components of the network.
 #[expect(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth, N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -72,6 +79,20 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Creates a new [`SnapRequestHandler`] and registers the `snap/1` sub-protocol that forwards
+    /// incoming requests of peers to it.
+    ///
+    /// The returned handler must be spawned for requests to be served.
+    pub fn snap_request_handler<Client>(
+        &mut self,
+        client: Client,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> SnapRequestHandler<Client> {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        self.network.add_rlpx_sub_protocol(SnapProtocolHandler::new(tx));
+        SnapRequestHandler::new(client, task_spawner, rx)
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -124,3 +145,22 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 }
+
+impl<Pool, Eth, N, PBundle> NetworkBuilder<TransactionsManager<Pool, N, PBundle>, Eth, N>
+where
+    Pool: TransactionPool + Unpin + 'static,
+    N: NetworkPrimitives,
+    PBundle: TransactionPolicies,
+{
+    /// Registers the `poolsync/1` sub-protocol, which exchanges summaries of the transaction pool
+    /// with newly connected peers and announces the transactions they are missing.
+    pub fn pool_sync(mut self) -> Self {
+        let handler = PoolSyncProtocolHandler::new(
+            self.transactions.pool().clone(),
+            self.network.handle().clone(),
+            self.transactions.handle(),
+        );
+        self.network.add_rlpx_sub_protocol(handler);
+        self
+    }
+}
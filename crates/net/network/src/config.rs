@@ -4,6 +4,7 @@ use crate::{
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
     transactions::TransactionsManagerConfig,
+    transport::Transport,
     NetworkHandle, NetworkManager,
 };
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
@@ -11,6 +12,7 @@ use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOV
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{
+    capture::ProtocolCapture,
     handshake::{EthHandshake, EthRlpxHandshake},
     EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives,
     UnifiedStatus,
@@ -90,6 +92,10 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// This can be overridden to support custom handshake logic via the
     /// [`NetworkConfigBuilder`].
     pub handshake: Arc<dyn EthRlpxHandshake>,
+    /// Records the `eth` messages exchanged with peers, if set.
+    pub protocol_capture: Option<ProtocolCapture>,
+    /// The transport peer connections are established over.
+    pub transport: Transport,
 }
 
 // === impl NetworkConfig ===
@@ -217,6 +223,10 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The Ethereum P2P handshake, see also:
     /// <https://github.com/ethereum/devp2p/blob/master/rlpx.md#initial-handshake>.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// Records the `eth` messages exchanged with peers, if set.
+    protocol_capture: Option<ProtocolCapture>,
+    /// The transport peer connections are established over.
+    transport: Transport,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -257,6 +267,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config: Default::default(),
             nat: None,
             handshake: Arc::new(EthHandshake::default()),
+            protocol_capture: None,
+            transport: Default::default(),
         }
     }
 
@@ -565,6 +577,20 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Records all `eth` messages exchanged with peers to the given [`ProtocolCapture`].
+    pub fn protocol_capture(mut self, capture: ProtocolCapture) -> Self {
+        self.protocol_capture = Some(capture);
+        self
+    }
+
+    /// Sets the transport peer connections are established over.
+    ///
+    /// Defaults to [`Transport::Tcp`].
+    pub const fn transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -597,6 +623,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config,
             nat,
             handshake,
+            protocol_capture,
+            transport,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -665,6 +693,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config,
             nat,
             handshake,
+            protocol_capture,
+            transport,
         }
     }
 }
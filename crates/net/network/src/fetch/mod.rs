@@ -4,7 +4,11 @@ mod client;
 
 pub use client::FetchClient;
 
-use crate::{message::BlockRequest, session::BlockRangeInfo};
+use crate::{
+    message::BlockRequest,
+    metrics::{PeerRequestMetrics, StateFetcherMetrics},
+    session::BlockRangeInfo,
+};
 use alloy_primitives::B256;
 use futures::StreamExt;
 use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
@@ -27,10 +31,23 @@ use std::{
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::debug;
 
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
 type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
 
+/// Number of recent requests the timeout rate of a peer is computed over.
+///
+/// Once a peer handled this many requests, its outcomes are halved so that older requests weigh
+/// less.
+const REQUEST_OUTCOMES_WINDOW: u64 = 64;
+
+/// Minimum number of recent requests before a peer can be demoted.
+const DEMOTION_MIN_REQUESTS: u64 = 8;
+
+/// Percentage of timed out recent requests above which a peer is demoted.
+const DEMOTION_TIMEOUT_RATE_PERCENT: u64 = 50;
+
 /// Manages data fetching operations.
 ///
 /// This type is hooked into the staged sync pipeline and delegates download request to available
@@ -55,6 +72,8 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Metrics for the block requests.
+    metrics: StateFetcherMetrics,
 }
 
 // === impl StateSyncer ===
@@ -71,6 +90,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            metrics: Default::default(),
         }
     }
 
@@ -91,6 +111,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 best_number,
                 timeout,
                 last_response_likely_bad: false,
+                outcomes: Default::default(),
+                metrics: PeerRequestMetrics::new_with_labels(&[("peer_id", peer_id.to_string())]),
                 range_info,
             },
         );
@@ -103,7 +125,12 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     ///
     /// This cancels also inflight request and sends an error to the receiver.
     pub(crate) fn on_session_closed(&mut self, peer: &PeerId) {
-        self.peers.remove(peer);
+        if let Some(peer) = self.peers.remove(peer) {
+            if peer.outcomes.is_demoted() {
+                peer.metrics.demoted.set(0);
+                self.metrics.demoted_peers.decrement(1);
+            }
+        }
         if let Some(req) = self.inflight_headers_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
@@ -134,14 +161,22 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
+    /// prioritizing those that are not demoted, those with the lowest timeout/latency and those
+    /// that recently responded with adequate data.
     fn next_best_peer(&self) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
         let mut best_peer = idle.next()?;
 
         for maybe_better in idle {
+            // demoted peers are only used if no other peer is available
+            if best_peer.1.outcomes.is_demoted() != maybe_better.1.outcomes.is_demoted() {
+                if best_peer.1.outcomes.is_demoted() {
+                    best_peer = maybe_better;
+                }
+                continue
+            }
+
             // replace best peer if our current best peer sent us a bad response last time
             if best_peer.1.last_response_likely_bad && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
@@ -271,6 +306,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
 
+        self.record_outcome(peer_id, &res);
+
         if let Some(resp) = resp {
             // delegate the response
             let _ = resp.response.send(res.map(|h| (peer_id, h).into()));
@@ -301,6 +338,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
 
+        self.record_outcome(peer_id, &res);
+
         if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
@@ -315,6 +354,44 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         None
     }
 
+    /// Records the outcome of a request to the peer and demotes the peer if too many of its recent
+    /// requests timed out.
+    fn record_outcome<T>(&mut self, peer_id: PeerId, res: &RequestResult<T>) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        match res {
+            Ok(_) => peer.metrics.successful_requests.increment(1),
+            Err(RequestError::Timeout) => peer.metrics.timed_out_requests.increment(1),
+            Err(_) => peer.metrics.failed_requests.increment(1),
+        }
+
+        let was_demoted = peer.outcomes.is_demoted();
+        peer.outcomes.record(res);
+        match (was_demoted, peer.outcomes.is_demoted()) {
+            (false, true) => {
+                debug!(
+                    target: "net::fetch",
+                    ?peer_id,
+                    outcomes = ?peer.outcomes,
+                    "Demoting peer with high request timeout rate"
+                );
+                peer.metrics.demoted.set(1);
+                self.metrics.peer_demotions.increment(1);
+                self.metrics.demoted_peers.increment(1);
+            }
+            (true, false) => {
+                debug!(
+                    target: "net::fetch",
+                    ?peer_id,
+                    outcomes = ?peer.outcomes,
+                    "Peer is no longer demoted"
+                );
+                peer.metrics.demoted.set(0);
+                self.metrics.demoted_peers.decrement(1);
+            }
+            _ => {}
+        }
+    }
+
     /// Returns a new [`FetchClient`] that can send requests to this type.
     pub(crate) fn client(&self) -> FetchClient<N> {
         FetchClient {
@@ -350,6 +427,10 @@ struct Peer {
     /// downloaded), but we still want to avoid requesting from the same peer again if it has the
     /// lowest timeout.
     last_response_likely_bad: bool,
+    /// Tracks the outcomes of the recent requests to the peer.
+    outcomes: RequestOutcomes,
+    /// Metrics for the requests to the peer.
+    metrics: PeerRequestMetrics,
     /// Tracks the range info for the peer.
     #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
@@ -361,6 +442,49 @@ impl Peer {
     }
 }
 
+/// Tracks the outcomes of the recent requests to a peer.
+///
+/// A peer is demoted if too many of its recent requests timed out. Demoted peers are only used if
+/// no other peer is available.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RequestOutcomes {
+    /// Number of requests that were answered.
+    successes: u64,
+    /// Number of requests that timed out.
+    timeouts: u64,
+    /// Number of requests that failed for other reasons than a timeout.
+    failures: u64,
+}
+
+impl RequestOutcomes {
+    /// Records the outcome of a request.
+    const fn record<T>(&mut self, res: &RequestResult<T>) {
+        match res {
+            Ok(_) => self.successes += 1,
+            Err(RequestError::Timeout) => self.timeouts += 1,
+            Err(_) => self.failures += 1,
+        }
+
+        if self.total() >= REQUEST_OUTCOMES_WINDOW {
+            self.successes /= 2;
+            self.timeouts /= 2;
+            self.failures /= 2;
+        }
+    }
+
+    /// Returns the number of recent requests.
+    const fn total(&self) -> u64 {
+        self.successes + self.timeouts + self.failures
+    }
+
+    /// Returns `true` if the timeout rate of the recent requests exceeds the demotion threshold.
+    const fn is_demoted(&self) -> bool {
+        let total = self.total();
+        total >= DEMOTION_MIN_REQUESTS &&
+            self.timeouts * 100 > total * DEMOTION_TIMEOUT_RATE_PERCENT
+    }
+}
+
 /// Tracks the state of an individual peer
 #[derive(Debug)]
 enum PeerState {
@@ -479,6 +603,7 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
     use std::future::poll_fn;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -527,6 +652,78 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(), None);
     }
 
+    #[tokio::test]
+    async fn test_demote_timed_out_peer() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(10)), None);
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+
+        for _ in 0..DEMOTION_MIN_REQUESTS {
+            fetcher.on_block_headers_response(peer1, Err(RequestError::Timeout));
+        }
+        assert!(fetcher.peers[&peer1].outcomes.is_demoted());
+        // peer1 has the lower timeout, but is demoted
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+
+        // demoted peers are still used if no other peer is available
+        fetcher.on_pending_disconnect(&peer2);
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+
+        // the peer recovers once enough requests succeed
+        for _ in 0..DEMOTION_MIN_REQUESTS {
+            fetcher.on_block_bodies_response(peer1, Ok(vec![Default::default()]));
+        }
+        assert!(!fetcher.peers[&peer1].outcomes.is_demoted());
+    }
+
+    #[tokio::test]
+    async fn test_per_peer_request_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+
+        metrics::with_local_recorder(&recorder, || {
+            let manager = PeersManager::new(PeersConfig::default());
+            let mut fetcher =
+                StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+            fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
+            fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None);
+
+            fetcher.on_block_headers_response(peer1, Err(RequestError::Timeout));
+            fetcher.on_block_headers_response(peer1, Err(RequestError::Timeout));
+            fetcher.on_block_headers_response(peer2, Ok(vec![]));
+            fetcher.on_block_bodies_response(peer2, Err(RequestError::BadResponse));
+        });
+
+        let counter = |name: &str, peer_id: PeerId| {
+            snapshotter
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .find_map(|(key, _, _, value)| {
+                    let key = key.key();
+                    let is_peer = key.labels().any(|label| {
+                        label.key() == "peer_id" && label.value() == peer_id.to_string()
+                    });
+                    (key.name() == name && is_peer).then_some(value)
+                })
+                .map(|value| match value {
+                    DebugValue::Counter(count) => count,
+                    value => panic!("unexpected metric value {value:?}"),
+                })
+        };
+        assert_eq!(counter("network.block_fetcher.peer.timed_out_requests", peer1), Some(2));
+        assert_eq!(counter("network.block_fetcher.peer.successful_requests", peer1), Some(0));
+        assert_eq!(counter("network.block_fetcher.peer.successful_requests", peer2), Some(1));
+        assert_eq!(counter("network.block_fetcher.peer.failed_requests", peer2), Some(1));
+    }
+
     #[tokio::test]
     async fn test_peer_prioritization() {
         let manager = PeersManager::new(PeersConfig::default());
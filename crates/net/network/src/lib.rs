@@ -129,7 +129,9 @@ pub mod import;
 pub mod message;
 pub mod peers;
 pub mod protocol;
+pub mod snap_requests;
 pub mod transactions;
+pub mod transport;
 
 mod budget;
 mod builder;
@@ -145,7 +147,7 @@ mod state;
 mod swarm;
 mod trusted_peers_resolver;
 
-pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
+pub use reth_eth_wire::{capture, DisconnectReason, HelloMessageWithProtocols};
 pub use reth_eth_wire_types::{primitives, EthNetworkPrimitives, NetworkPrimitives};
 pub use reth_network_api::{
     events, BlockDownloaderProvider, DiscoveredEvent, DiscoveryEvent, NetworkEvent,
@@ -1,5 +1,6 @@
 //! Contains connection-oriented interfaces.
 
+use crate::transport::{MemoryListener, PeerStream};
 use futures::{ready, Stream, StreamExt};
 use std::{
     io,
@@ -9,16 +10,17 @@ use std::{
 };
 use tokio::net::{TcpListener, TcpStream};
 
-/// A tcp connection listener.
+/// A connection listener.
 ///
-/// Listens for incoming connections.
+/// Listens for incoming tcp connections, or for in-memory connections if it was bound with
+/// [`ConnectionListener::bind_memory`].
 #[must_use = "Transport does nothing unless polled."]
 #[derive(Debug)]
 pub struct ConnectionListener {
     /// Local address of the listener stream.
     local_address: SocketAddr,
-    /// The active tcp listener for incoming connections.
-    incoming: TcpListenerStream,
+    /// The active listener for incoming connections.
+    incoming: Incoming,
 }
 
 impl ConnectionListener {
@@ -29,19 +31,35 @@ impl ConnectionListener {
         Ok(Self::new(listener, local_addr))
     }
 
+    /// Creates a new listener for incoming in-memory connections.
+    ///
+    /// See [`Transport::Memory`](crate::transport::Transport::Memory).
+    pub fn bind_memory(addr: SocketAddr) -> io::Result<Self> {
+        let listener = MemoryListener::bind(addr)?;
+        Ok(Self { local_address: listener.local_address(), incoming: Incoming::Memory(listener) })
+    }
+
     /// Creates a new connection listener stream.
     pub(crate) const fn new(listener: TcpListener, local_address: SocketAddr) -> Self {
-        Self { local_address, incoming: TcpListenerStream { inner: listener } }
+        Self { local_address, incoming: Incoming::Tcp(TcpListenerStream { inner: listener }) }
     }
 
     /// Polls the type to make progress.
     pub fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ListenerEvent> {
         let this = self.get_mut();
-        match ready!(this.incoming.poll_next_unpin(cx)) {
+        let incoming = match &mut this.incoming {
+            Incoming::Tcp(incoming) => ready!(incoming.poll_next_unpin(cx)).map(|res| {
+                res.map(|(stream, remote_addr)| {
+                    if let Err(err) = stream.set_nodelay(true) {
+                        tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
+                    }
+                    (stream.into(), remote_addr)
+                })
+            }),
+            Incoming::Memory(incoming) => ready!(incoming.poll_accept(cx)).map(Ok),
+        };
+        match incoming {
             Some(Ok((stream, remote_addr))) => {
-                if let Err(err) = stream.set_nodelay(true) {
-                    tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
-                }
                 Poll::Ready(ListenerEvent::Incoming { stream, remote_addr })
             }
             Some(Err(err)) => Poll::Ready(ListenerEvent::Error(err)),
@@ -62,7 +80,7 @@ pub enum ListenerEvent {
     /// Received a new incoming.
     Incoming {
         /// Accepted connection
-        stream: TcpStream,
+        stream: PeerStream,
         /// Address of the remote peer.
         remote_addr: SocketAddr,
     },
@@ -80,6 +98,15 @@ pub enum ListenerEvent {
     Error(io::Error),
 }
 
+/// The listener of a [`ConnectionListener`].
+#[derive(Debug)]
+enum Incoming {
+    /// Accepts tcp connections.
+    Tcp(TcpListenerStream),
+    /// Accepts in-memory connections.
+    Memory(MemoryListener),
+}
+
 /// A stream of incoming [`TcpStream`]s.
 #[derive(Debug)]
 struct TcpListenerStream {
@@ -33,6 +33,7 @@ use crate::{
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
+    transport::Transport,
     FetchClient, NetworkBuilder,
 };
 use futures::{Future, StreamExt};
@@ -250,14 +251,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             transactions_manager_config: _,
             nat,
             handshake,
+            protocol_capture,
+            transport,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
-        let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
-            NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr))
-        })?;
+        let incoming = match transport {
+            Transport::Tcp => ConnectionListener::bind(listener_addr).await,
+            Transport::Memory => ConnectionListener::bind_memory(listener_addr),
+        }
+        .map_err(|err| NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr)))?;
 
         // retrieve the tcp address of the socket
         let listener_addr = incoming.local_address();
@@ -304,6 +309,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             fork_filter,
             extra_protocols,
             handshake,
+            protocol_capture,
+            transport,
         );
 
         let state = NetworkState::new(
@@ -967,10 +974,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 self.update_pending_connection_metrics();
             }
             SwarmEvent::BadMessage { peer_id } => {
-                self.swarm
-                    .state_mut()
-                    .peers_mut()
-                    .apply_reputation_change(&peer_id, ReputationChangeKind::BadMessage);
+                self.swarm.state_mut().peers_mut().on_malformed_message(&peer_id);
                 self.metrics.invalid_messages_received.increment(1);
             }
             SwarmEvent::ProtocolBreach { peer_id } => {
@@ -226,6 +226,30 @@ pub struct TransactionFetcherMetrics {
     pub(crate) duration_fill_request_from_hashes_pending_fetch: Gauge,
 }
 
+/// Metrics for the block requests of the [`StateFetcher`](crate::fetch::StateFetcher).
+#[derive(Metrics)]
+#[metrics(scope = "network.block_fetcher")]
+pub struct StateFetcherMetrics {
+    /// Number of times a peer was demoted because too many of its requests timed out.
+    pub(crate) peer_demotions: Counter,
+    /// Number of connected peers that are currently demoted.
+    pub(crate) demoted_peers: Gauge,
+}
+
+/// Metrics for the block requests sent to a single peer, labeled by the peer id.
+#[derive(Metrics)]
+#[metrics(scope = "network.block_fetcher.peer")]
+pub struct PeerRequestMetrics {
+    /// Number of block requests that were answered by the peer.
+    pub(crate) successful_requests: Counter,
+    /// Number of block requests that timed out.
+    pub(crate) timed_out_requests: Counter,
+    /// Number of block requests that failed for other reasons than a timeout.
+    pub(crate) failed_requests: Counter,
+    /// Whether the peer is currently demoted, `1` if it is and `0` otherwise.
+    pub(crate) demoted: Gauge,
+}
+
 /// Measures the duration of executing the given code block. The duration is added to the given
 /// accumulator value passed as a mutable reference.
 #[macro_export]
@@ -331,6 +355,31 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the `SnapRequestHandler`
+#[derive(Metrics, Clone)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) snap_account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) snap_storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) snap_bytecodes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) snap_trie_nodes_requests_received_total: Counter,
+
+    /// Number of snap requests that were answered with an empty response because the requested
+    /// state root is not available
+    pub(crate) snap_unavailable_state_root_total: Counter,
+
+    /// Duration in seconds of call to poll
+    /// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+    pub(crate) acc_duration_poll_snap_req_handler: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
@@ -12,12 +12,12 @@ use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
 use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
 use reth_network_types::{
     is_connection_failed_reputation,
     peers::{
         config::PeerBackoffDurations,
-        reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
+        reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
     ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
@@ -27,6 +27,7 @@ use std::{
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    path::PathBuf,
     task::{Context, Poll},
     time::Duration,
 };
@@ -37,6 +38,7 @@ use tokio::{
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{trace, warn};
+use url::Host;
 
 /// Maintains the state of _all_ the peers known to the network.
 ///
@@ -56,6 +58,8 @@ pub struct PeersManager {
     /// A resolver used to periodically resolve DNS names for trusted peers. This updates the
     /// peer's address when the DNS records change.
     trusted_peers_resolver: TrustedPeersResolver,
+    /// The trusted peers that are persisted to a file, if configured.
+    persisted_trusted_peers: Option<PersistedTrustedPeers>,
     /// Copy of the sender half, so new [`PeersHandle`] can be created on demand.
     manager_tx: mpsc::UnboundedSender<PeerCommand>,
     /// Receiver half of the command channel.
@@ -90,6 +94,10 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// Number of malformed messages after which a peer is quarantined.
+    malformed_message_threshold: u8,
+    /// How long to quarantine peers that repeatedly sent malformed messages.
+    quarantine_duration: Duration,
 }
 
 impl PeersManager {
@@ -102,12 +110,16 @@ impl PeersManager {
             ban_list,
             ban_duration,
             backoff_durations,
-            trusted_nodes,
+            mut trusted_nodes,
             trusted_nodes_only,
+            trusted_nodes_file,
+            persisted_trusted_nodes,
             trusted_nodes_resolution_interval,
             basic_nodes,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            malformed_message_threshold,
+            quarantine_duration,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -115,6 +127,15 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
+        // the persisted trusted nodes are trusted in addition to the configured ones
+        for node in &persisted_trusted_nodes {
+            if !trusted_nodes.iter().any(|trusted| trusted.id == node.id) {
+                trusted_nodes.push(node.clone());
+            }
+        }
+        let persisted_trusted_peers = trusted_nodes_file
+            .map(|path| PersistedTrustedPeers { path, peers: persisted_trusted_nodes });
+
         let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
@@ -145,6 +166,7 @@ impl PeersManager {
                 trusted_nodes,
                 tokio::time::interval(trusted_nodes_resolution_interval), // 1 hour
             ),
+            persisted_trusted_peers,
             manager_tx,
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
@@ -161,6 +183,8 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            malformed_message_threshold,
+            quarantine_duration,
         }
     }
 
@@ -412,7 +436,9 @@ impl PeersManager {
 
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             peer.backed_off = true;
-            self.backed_off_peers.insert(peer_id, until);
+            // never shorten an existing backoff, e.g. of a quarantined peer
+            let backoff_until = self.backed_off_peers.entry(peer_id).or_insert(until);
+            *backoff_until = (*backoff_until).max(until);
         }
     }
 
@@ -565,9 +591,80 @@ impl PeersManager {
         peer_id: &PeerId,
         err: &EthStreamError,
     ) {
+        if err.is_malformed_message() && self.record_malformed_message(peer_id) {
+            // the session is already closed, so the peer only needs to be backed off
+            self.quarantine_peer(*peer_id);
+        }
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::Dropped)
     }
 
+    /// Called when the peer sent a message that is malformed or invalid in the context of the
+    /// session.
+    ///
+    /// Malformed messages are frequently caused by buggy clients rather than malicious peers, so
+    /// instead of banning the peer, its reputation is lowered, which deprioritizes it for outbound
+    /// connections. Once the peer sent too many malformed messages, it is disconnected and
+    /// quarantined.
+    pub(crate) fn on_malformed_message(&mut self, peer_id: &PeerId) {
+        if !self.record_malformed_message(peer_id) {
+            return
+        }
+
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if peer.state.is_connected() {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+        self.quarantine_peer(*peer_id);
+    }
+
+    /// Records a malformed message of the peer and returns `true` if the peer should be
+    /// quarantined.
+    ///
+    /// Trusted and static peers are never quarantined, but receive the regular reputation change.
+    fn record_malformed_message(&mut self, peer_id: &PeerId) -> bool {
+        let Some(peer) = self.peers.get_mut(peer_id) else { return false };
+
+        if peer.is_trusted() || peer.is_static() {
+            self.apply_reputation_change(peer_id, ReputationChangeKind::BadMessage);
+            return false
+        }
+
+        // lower the reputation, but not below the ban threshold, repeated quarantines are handled
+        // by the backoff counter instead
+        let reputation_change =
+            self.reputation_weights.change(ReputationChangeKind::BadMessage).as_i32();
+        let min_reputation = peer.reputation.min(BANNED_REPUTATION);
+        peer.reputation = peer.reputation.saturating_add(reputation_change).max(min_reputation);
+
+        peer.malformed_messages = peer.malformed_messages.saturating_add(1);
+        if peer.malformed_messages < self.malformed_message_threshold {
+            return false
+        }
+
+        peer.malformed_messages = 0;
+        true
+    }
+
+    /// Backs off the peer for the configured quarantine duration.
+    ///
+    /// Every quarantine counts as severe backoff, so that a peer that is quarantined too often is
+    /// removed from the set.
+    fn quarantine_peer(&mut self, peer_id: PeerId) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        trace!(target: "net::peers", ?peer_id, "quarantining peer after malformed messages");
+
+        peer.severe_backoff_counter = peer.severe_backoff_counter.saturating_add(1);
+        if peer.severe_backoff_counter > self.max_backoff_count {
+            peer.remove_after_disconnect = true;
+        }
+        self.backoff_peer_until(peer_id, std::time::Instant::now() + self.quarantine_duration);
+    }
+
     /// Called when an attempt to create an _outgoing_ pending session failed while setting up a tcp
     /// connection.
     pub(crate) fn on_outgoing_connection_failure(
@@ -774,6 +871,19 @@ impl PeersManager {
 
         if kind.is_trusted() {
             self.trusted_peer_ids.insert(peer_id);
+            self.persist_trusted_peer(peer_id, addr);
+        }
+    }
+
+    /// Persists the trusted peer that was added at runtime, if a trusted peers file is configured.
+    fn persist_trusted_peer(&mut self, peer_id: PeerId, addr: PeerAddr) {
+        if let Some(persisted) = &mut self.persisted_trusted_peers {
+            persisted.insert(NodeRecord::new_with_ports(
+                addr.tcp().ip(),
+                addr.tcp().port(),
+                addr.udp().map(|addr| addr.port()),
+                peer_id,
+            ));
         }
     }
 
@@ -859,11 +969,21 @@ impl PeersManager {
 
         if kind.is_trusted() {
             self.trusted_peer_ids.insert(peer_id);
+            self.persist_trusted_peer(peer_id, addr);
         }
     }
 
     /// Removes the tracked node from the trusted set.
+    ///
+    /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then an active session with the
+    /// node is terminated.
     pub(crate) fn remove_peer_from_trusted_set(&mut self, peer_id: PeerId) {
+        // the peer might only be known by its id, see `Self::add_trusted_peer_id`
+        self.trusted_peer_ids.remove(&peer_id);
+        if let Some(persisted) = &mut self.persisted_trusted_peers {
+            persisted.remove(peer_id);
+        }
+
         let Entry::Occupied(mut entry) = self.peers.entry(peer_id) else { return };
         if !entry.get().is_trusted() {
             return
@@ -872,7 +992,14 @@ impl PeersManager {
         let peer = entry.get_mut();
         peer.kind = PeerKind::Basic;
 
-        self.trusted_peer_ids.remove(&peer_id);
+        if self.trusted_nodes_only && peer.state.is_connected() {
+            trace!(target: "net::peers", ?peer_id, "disconnecting untrusted peer");
+            peer.state.disconnect();
+            self.queued_actions.push_back(PeerAction::Disconnect {
+                peer_id,
+                reason: Some(DisconnectReason::DisconnectRequested),
+            })
+        }
     }
 
     /// Returns the idle peer with the highest reputation.
@@ -1044,6 +1171,65 @@ impl PeersManager {
     }
 }
 
+/// The trusted peers that are persisted to a file, so that runtime changes to the trusted set
+/// survive restarts.
+///
+/// This only tracks the peers loaded from the file and the peers added or removed at runtime, the
+/// configured trusted peers are not written to the file.
+#[derive(Debug)]
+struct PersistedTrustedPeers {
+    /// The file the peers are written to.
+    path: PathBuf,
+    /// The persisted peers.
+    peers: Vec<TrustedPeer>,
+}
+
+impl PersistedTrustedPeers {
+    /// Adds the peer and writes the file if the persisted peers changed.
+    ///
+    /// A peer that is persisted with a DNS name is kept as is, if its ports are unchanged, so that
+    /// the name isn't replaced with the address it resolved to.
+    fn insert(&mut self, record: NodeRecord) {
+        let peer = TrustedPeer::from(record);
+        match self.peers.iter_mut().find(|persisted| persisted.id == peer.id) {
+            Some(persisted) => {
+                let unchanged = persisted.tcp_port == peer.tcp_port &&
+                    persisted.udp_port == peer.udp_port &&
+                    (persisted.host == peer.host || matches!(persisted.host, Host::Domain(_)));
+                if unchanged {
+                    return
+                }
+                *persisted = peer;
+            }
+            None => self.peers.push(peer),
+        }
+        self.write();
+    }
+
+    /// Removes the peer and writes the file if it was persisted.
+    fn remove(&mut self, peer_id: PeerId) {
+        let len = self.peers.len();
+        self.peers.retain(|persisted| persisted.id != peer_id);
+        if self.peers.len() != len {
+            self.write();
+        }
+    }
+
+    /// Writes the peers to the file.
+    fn write(&self) {
+        let res = self
+            .path
+            .parent()
+            .map(reth_fs_util::create_dir_all)
+            .transpose()
+            .and_then(|_| reth_fs_util::write_json_file(&self.path, &self.peers));
+        match res {
+            Ok(()) => trace!(target: "net::peers", path=?self.path, "Wrote trusted peers to file"),
+            Err(err) => warn!(target: "net::peers", %err, "Failed to write trusted peers to file"),
+        }
+    }
+}
+
 impl Default for PeersManager {
     fn default() -> Self {
         Self::new(Default::default())
@@ -2050,6 +2236,100 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_quarantine_on_malformed_messages() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        peers.on_active_outgoing_established(peer);
+
+        for _ in 1..peers.malformed_message_threshold {
+            peers.on_malformed_message(&peer);
+        }
+        {
+            let p = peers.peers.get(&peer).unwrap();
+            assert_eq!(p.state, PeerConnectionState::Out);
+            assert!(p.reputation < DEFAULT_REPUTATION);
+            assert!(!p.is_backed_off());
+        }
+
+        peers.on_malformed_message(&peer);
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        {
+            let p = peers.peers.get(&peer).unwrap();
+            assert_eq!(p.malformed_messages, 0);
+            assert!(p.is_backed_off());
+            assert!(!p.is_banned());
+        }
+        assert!(!peers.ban_list.is_banned_peer(&peer));
+
+        // repeated malformed messages never ban the peer
+        for _ in 0..10 * peers.malformed_message_threshold {
+            peers.on_malformed_message(&peer);
+        }
+        assert!(!peers.peers.get(&peer).unwrap().is_banned());
+        assert!(!peers.ban_list.is_banned_peer(&peer));
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_on_active_drop_with_malformed_message() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let config = PeersConfig::test().with_malformed_message_threshold(1);
+        let mut peers = PeersManager::new(config);
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        peers.on_active_outgoing_established(peer);
+
+        peers.on_active_session_dropped(&socket_addr, &peer, &EthStreamError::MessageTooBig(0));
+
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.state, PeerConnectionState::Idle);
+        assert_eq!(p.severe_backoff_counter, 1);
+        assert!(p.is_backed_off());
+        assert!(!p.is_banned());
+
+        tokio::time::sleep(peers.quarantine_duration).await;
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn test_reputation_management() {
         let peer = PeerId::random();
@@ -2412,6 +2692,92 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_remove_trusted_peer_with_trusted_nodes_only() {
+        let trusted_peer = PeerId::random();
+        let config = PeersConfig::test()
+            .with_trusted_nodes(vec![TrustedPeer {
+                host: Host::Ipv4(Ipv4Addr::new(127, 0, 1, 2)),
+                tcp_port: 8008,
+                udp_port: 8008,
+                id: trusted_peer,
+            }])
+            .with_trusted_nodes_only(true);
+        let mut peers = PeersManager::new(config);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, trusted_peer);
+            }
+            _ => unreachable!(),
+        }
+        peers.on_active_outgoing_established(trusted_peer);
+
+        peers.remove_peer_from_trusted_set(trusted_peer);
+        assert!(!peers.trusted_peer_ids.contains(&trusted_peer));
+        assert!(!peers.peers[&trusted_peer].is_trusted());
+
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, reason } => {
+                assert_eq!(peer_id, trusted_peer);
+                assert_eq!(reason, Some(DisconnectReason::DisconnectRequested));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_trusted_peers_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trusted-peers.json");
+
+        let configured = TrustedPeer {
+            host: Host::Ipv4(Ipv4Addr::new(127, 0, 1, 1)),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: PeerId::random(),
+        };
+        let persisted = TrustedPeer {
+            host: Host::Domain("localhost".to_string()),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: PeerId::random(),
+        };
+        reth_fs_util::write_json_file(&path, &vec![persisted.clone()]).unwrap();
+
+        let config = PeersConfig::test()
+            .with_trusted_nodes(vec![configured.clone()])
+            .with_trusted_nodes_file(Some(path.clone()))
+            .unwrap();
+        let mut peers = PeersManager::new(config);
+        assert!(peers.peers[&configured.id].is_trusted());
+        assert!(peers.peers[&persisted.id].is_trusted());
+        let read_peers = || reth_fs_util::read_json_file::<Vec<TrustedPeer>>(&path).unwrap();
+
+        // adding a peer at runtime writes it next to the persisted one, but not the configured one
+        let added = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        peers.add_trusted_peer(added, PeerAddr::from_tcp(socket_addr));
+        let ids = read_peers().into_iter().map(|peer| peer.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![persisted.id, added]);
+
+        // re-adding the persisted peer by its resolved address keeps its DNS name
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8008);
+        peers.add_trusted_peer(persisted.id, PeerAddr::from_tcp(socket_addr));
+        assert_eq!(read_peers()[0], persisted);
+
+        // removing a peer at runtime removes it from the file
+        peers.remove_peer_from_trusted_set(persisted.id);
+        let ids = read_peers().into_iter().map(|peer| peer.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![added]);
+
+        // the peers are restored on restart
+        let config = PeersConfig::test().with_trusted_nodes_file(Some(path.clone())).unwrap();
+        let peers = PeersManager::new(config);
+        assert!(peers.peers[&added].is_trusted());
+        assert!(!peers.peers.contains_key(&persisted.id));
+    }
+
     #[tokio::test]
     async fn test_incoming_with_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
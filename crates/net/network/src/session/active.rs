@@ -14,6 +14,7 @@ use std::{
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::SessionBandwidthLimiter,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
         BlockRangeInfo, EthVersion, SessionId,
@@ -128,6 +129,8 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// Optional interval for sending periodic range updates to the remote peer (eth69+)
     /// Recommended frequency is ~2 minutes per spec
     pub(crate) range_update_interval: Option<Interval>,
+    /// Shapes the outbound bandwidth of the responses to the remote peer.
+    pub(crate) bandwidth_limiter: SessionBandwidthLimiter,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -619,7 +622,8 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
 
             // Send messages by advancing the sink and queuing in buffered messages
             while this.conn.poll_ready_unpin(cx).is_ready() {
-                if let Some(msg) = this.queued_outgoing.pop_front() {
+                let next = this.queued_outgoing.pop_within_limits(&mut this.bandwidth_limiter);
+                if let Ok(Some(msg)) = next {
                     progress = true;
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
@@ -632,6 +636,10 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         return this.close_on_error(err, cx)
                     }
                 } else {
+                    if let Err(delay) = next {
+                        // all queued messages are throttled responses
+                        this.bandwidth_limiter.wake_after(delay, cx);
+                    }
                     // no more messages to send over the wire
                     break
                 }
@@ -893,6 +901,35 @@ impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
         self.messages.pop_front().inspect(|_| self.count.decrement(1))
     }
 
+    /// Removes the first message that can be sent within the bandwidth limits of the session.
+    ///
+    /// Throttled responses stay queued, so they can't hold back other messages, e.g. block
+    /// broadcasts. Returns the time until the next throttled response can be sent if all queued
+    /// messages are throttled.
+    pub(crate) fn pop_within_limits(
+        &mut self,
+        limiter: &mut SessionBandwidthLimiter,
+    ) -> Result<Option<OutgoingMessage<N>>, Duration> {
+        if limiter.is_unlimited() {
+            return Ok(self.pop_front())
+        }
+
+        let now = Instant::now();
+        let mut delay = None;
+        for (idx, msg) in self.messages.iter().enumerate() {
+            match limiter.try_send(msg, now) {
+                Ok(()) => {
+                    self.count.decrement(1);
+                    return Ok(self.messages.remove(idx))
+                }
+                Err(wait_time) => {
+                    delay = Some(delay.map_or(wait_time, |d: Duration| d.min(wait_time)))
+                }
+            }
+        }
+        delay.map_or(Ok(None), Err)
+    }
+
     pub(crate) fn shrink_to_fit(&mut self) {
         self.messages.shrink_to_fit();
     }
@@ -985,9 +1022,10 @@ mod tests {
 
             tokio::task::spawn(start_pending_incoming_session(
                 Arc::new(EthHandshake::default()),
+                None,
                 disconnect_rx,
                 session_id,
-                stream,
+                stream.into(),
                 pending_sessions_tx,
                 remote_addr,
                 self.secret_key,
@@ -1046,6 +1084,10 @@ mod tests {
                             alloy_primitives::B256::ZERO,
                         ),
                         range_update_interval: None,
+                        bandwidth_limiter: SessionBandwidthLimiter::new(
+                            &Default::default(),
+                            Default::default(),
+                        ),
                     }
                 }
                 ev => {
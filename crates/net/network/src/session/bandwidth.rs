@@ -0,0 +1,221 @@
+//! Outbound bandwidth shaping for responses to requests of peers.
+
+use crate::session::active::OutgoingMessage;
+use alloy_rlp::Encodable;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use reth_eth_wire::{EthMessage, NetworkPrimitives};
+use reth_network_types::BandwidthLimits;
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::Context,
+    time::{Duration, Instant},
+};
+use tokio::time::Sleep;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// A token bucket that refills at a fixed rate of bytes per second and holds up to one second
+/// worth of bytes.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The refill rate in bytes per second.
+    rate: u64,
+    /// The available bytes.
+    ///
+    /// This is negative if a message exceeded the available bytes, in which case no more messages
+    /// are allowed until it's refilled.
+    available: i64,
+    /// The last time the bucket was refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    const fn new(rate: u64, now: Instant) -> Self {
+        Self { rate, available: rate as i64, last_refill: now }
+    }
+
+    /// Refills the bucket with the bytes accrued since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = (elapsed.as_nanos() * self.rate as u128 / NANOS_PER_SEC) as i64;
+        if refill > 0 {
+            self.available = self.available.saturating_add(refill).min(self.rate as i64);
+            self.last_refill = now;
+        }
+    }
+
+    /// Returns the time until the bucket has capacity again, or `None` if it has capacity.
+    fn wait_time(&mut self, now: Instant) -> Option<Duration> {
+        self.refill(now);
+        (self.available <= 0).then(|| {
+            let missing = (1 - self.available) as u128;
+            Duration::from_nanos((missing * NANOS_PER_SEC).div_ceil(self.rate.max(1) as u128) as u64)
+        })
+    }
+
+    /// Takes the given number of bytes from the bucket.
+    const fn consume(&mut self, bytes: usize) {
+        self.available = self.available.saturating_sub(bytes as i64);
+    }
+}
+
+/// The classes of responses with limits shared by all sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseClass {
+    BlockBodies,
+    Receipts,
+    PooledTransactions,
+}
+
+impl ResponseClass {
+    /// Returns the class of the response, if it's a response with a shared limit.
+    const fn of<N: NetworkPrimitives>(msg: &EthMessage<N>) -> Option<Self> {
+        match msg {
+            EthMessage::BlockBodies(_) => Some(Self::BlockBodies),
+            EthMessage::Receipts(_) | EthMessage::Receipts69(_) => Some(Self::Receipts),
+            EthMessage::PooledTransactions(_) => Some(Self::PooledTransactions),
+            _ => None,
+        }
+    }
+}
+
+/// The [`BandwidthLimits`] for the responses to all peers, shared by all sessions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SharedBandwidthLimits {
+    block_bodies: Option<Arc<Mutex<TokenBucket>>>,
+    receipts: Option<Arc<Mutex<TokenBucket>>>,
+    pooled_transactions: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl SharedBandwidthLimits {
+    /// Creates the shared buckets for the configured limits.
+    pub(crate) fn new(limits: &BandwidthLimits) -> Self {
+        let now = Instant::now();
+        let bucket =
+            |rate: Option<u64>| rate.map(|rate| Arc::new(Mutex::new(TokenBucket::new(rate, now))));
+        Self {
+            block_bodies: bucket(limits.block_bodies),
+            receipts: bucket(limits.receipts),
+            pooled_transactions: bucket(limits.pooled_transactions),
+        }
+    }
+
+    const fn bucket(&self, class: ResponseClass) -> Option<&Arc<Mutex<TokenBucket>>> {
+        match class {
+            ResponseClass::BlockBodies => self.block_bodies.as_ref(),
+            ResponseClass::Receipts => self.receipts.as_ref(),
+            ResponseClass::PooledTransactions => self.pooled_transactions.as_ref(),
+        }
+    }
+}
+
+/// Shapes the outbound bandwidth of the responses of a single session.
+///
+/// Responses are subject to the per-peer limit of the session and the shared limit of their
+/// [`ResponseClass`]. All other messages are never throttled.
+#[derive(Debug)]
+pub(crate) struct SessionBandwidthLimiter {
+    /// The limit for all responses to the peer of this session.
+    per_peer: Option<TokenBucket>,
+    /// The limits shared by all sessions.
+    shared: SharedBandwidthLimits,
+    /// Timer to wake up the session once a throttled response can be sent.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl SessionBandwidthLimiter {
+    /// Creates a new limiter for a session.
+    pub(crate) fn new(limits: &BandwidthLimits, shared: SharedBandwidthLimits) -> Self {
+        Self {
+            per_peer: limits.per_peer.map(|rate| TokenBucket::new(rate, Instant::now())),
+            shared,
+            timer: None,
+        }
+    }
+
+    /// Returns true if no limits are enforced.
+    pub(crate) const fn is_unlimited(&self) -> bool {
+        self.per_peer.is_none() &&
+            self.shared.block_bodies.is_none() &&
+            self.shared.receipts.is_none() &&
+            self.shared.pooled_transactions.is_none()
+    }
+
+    /// Checks whether the message can be sent now and takes its size from the buckets if so.
+    ///
+    /// Returns the time until the message can be sent if it's throttled.
+    pub(crate) fn try_send<N: NetworkPrimitives>(
+        &mut self,
+        msg: &OutgoingMessage<N>,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let OutgoingMessage::Eth(msg) = msg else { return Ok(()) };
+        if !msg.is_response() {
+            return Ok(())
+        }
+
+        let mut shared = ResponseClass::of(msg)
+            .and_then(|class| self.shared.bucket(class))
+            .map(|bucket| bucket.lock());
+        if self.per_peer.is_none() && shared.is_none() {
+            return Ok(())
+        }
+
+        let wait_time = self
+            .per_peer
+            .as_mut()
+            .and_then(|bucket| bucket.wait_time(now))
+            .max(shared.as_mut().and_then(|bucket| bucket.wait_time(now)));
+        if let Some(wait_time) = wait_time {
+            return Err(wait_time)
+        }
+
+        let len = msg.length();
+        if let Some(bucket) = &mut self.per_peer {
+            bucket.consume(len);
+        }
+        if let Some(bucket) = &mut shared {
+            bucket.consume(len);
+        }
+        Ok(())
+    }
+
+    /// Arms the timer to wake up the session after the given duration.
+    pub(crate) fn wake_after(&mut self, duration: Duration, cx: &mut Context<'_>) {
+        let deadline = tokio::time::Instant::now() + duration;
+        match &mut self.timer {
+            Some(timer) => timer.as_mut().reset(deadline),
+            None => self.timer = Some(Box::pin(tokio::time::sleep_until(deadline))),
+        }
+        if self.timer.as_mut().expect("is set").poll_unpin(cx).is_ready() {
+            cx.waker().wake_by_ref();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1000, now);
+        assert_eq!(bucket.wait_time(now), None);
+
+        // messages that exceed the available bytes are allowed, but throttle the next ones
+        bucket.consume(1500);
+        assert_eq!(bucket.wait_time(now), Some(Duration::from_millis(501)));
+        assert_eq!(
+            bucket.wait_time(now + Duration::from_millis(250)),
+            Some(Duration::from_millis(251))
+        );
+        assert_eq!(bucket.wait_time(now + Duration::from_millis(501)), None);
+
+        // refills up to one second worth of bytes
+        bucket.refill(now + Duration::from_secs(10));
+        assert_eq!(bucket.available, 1000);
+    }
+}
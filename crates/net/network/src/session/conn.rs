@@ -1,5 +1,6 @@
 //! Connection types for a session
 
+use crate::transport::PeerStream;
 use futures::{Sink, Stream};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
@@ -13,14 +14,13 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::net::TcpStream;
 
 /// The type of the underlying peer network connection.
-pub type EthPeerConnection<N> = EthStream<P2PStream<ECIESStream<TcpStream>>, N>;
+pub type EthPeerConnection<N> = EthStream<P2PStream<ECIESStream<PeerStream>>, N>;
 
 /// Various connection types that at least support the ETH protocol.
 pub type EthSatelliteConnection<N = EthNetworkPrimitives> =
-    RlpxSatelliteStream<ECIESStream<TcpStream>, EthStream<ProtocolProxy, N>>;
+    RlpxSatelliteStream<ECIESStream<PeerStream>, EthStream<ProtocolProxy, N>>;
 
 /// Connection types that support the ETH protocol.
 ///
@@ -49,7 +49,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
 
     /// Consumes this type and returns the wrapped [`P2PStream`].
     #[inline]
-    pub(crate) fn into_inner(self) -> P2PStream<ECIESStream<TcpStream>> {
+    pub(crate) fn into_inner(self) -> P2PStream<ECIESStream<PeerStream>> {
         match self {
             Self::EthOnly(conn) => conn.into_inner(),
             Self::Satellite(conn) => conn.into_inner(),
@@ -58,7 +58,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
 
     /// Returns mutable access to the underlying stream.
     #[inline]
-    pub(crate) fn inner_mut(&mut self) -> &mut P2PStream<ECIESStream<TcpStream>> {
+    pub(crate) fn inner_mut(&mut self) -> &mut P2PStream<ECIESStream<PeerStream>> {
         match self {
             Self::EthOnly(conn) => conn.inner_mut(),
             Self::Satellite(conn) => conn.inner_mut(),
@@ -67,7 +67,7 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
 
     /// Returns access to the underlying stream.
     #[inline]
-    pub(crate) const fn inner(&self) -> &P2PStream<ECIESStream<TcpStream>> {
+    pub(crate) const fn inner(&self) -> &P2PStream<ECIESStream<PeerStream>> {
         match self {
             Self::EthOnly(conn) => conn.inner(),
             Self::Satellite(conn) => conn.inner(),
@@ -1,6 +1,7 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
@@ -12,22 +13,24 @@ use crate::{
     metrics::SessionManagerMetrics,
     protocol::{IntoRlpxSubProtocol, OnNotSupported, RlpxSubProtocolHandlers, RlpxSubProtocols},
     session::active::ActiveSession,
+    transport::{PeerStream, Transport},
 };
 use active::QueuedOutgoingMessages;
+use bandwidth::{SessionBandwidthLimiter, SharedBandwidthLimits};
 use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    errors::EthStreamError, handshake::EthRlpxHandshake, multiplex::RlpxProtocolMultiplexer,
-    BlockRangeUpdate, Capabilities, DisconnectReason, EthStream, EthVersion,
-    HelloMessageWithProtocols, NetworkPrimitives, UnauthedP2PStream, UnifiedStatus,
-    HANDSHAKE_TIMEOUT,
+    capture::ProtocolCapture, errors::EthStreamError, handshake::EthRlpxHandshake,
+    multiplex::RlpxProtocolMultiplexer, BlockRangeUpdate, Capabilities, DisconnectReason,
+    EthStream, EthVersion, HelloMessageWithProtocols, NetworkPrimitives, UnauthedP2PStream,
+    UnifiedStatus, HANDSHAKE_TIMEOUT,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::SessionsConfig;
+use reth_network_types::{BandwidthLimits, SessionsConfig};
 use reth_tasks::TaskSpawner;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -41,7 +44,6 @@ use std::{
 };
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    net::TcpStream,
     sync::{mpsc, mpsc::error::TrySendError, oneshot},
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -117,9 +119,17 @@ pub struct SessionManager<N: NetworkPrimitives> {
     metrics: SessionManagerMetrics,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// Records the `eth` messages of all sessions, if enabled.
+    protocol_capture: Option<ProtocolCapture>,
+    /// The transport outgoing connections are established over.
+    transport: Transport,
     /// Shared local range information that gets propagated to active sessions.
     /// This represents the range of blocks that this node can serve to other peers.
     local_range_info: BlockRangeInfo,
+    /// The outbound bandwidth limits for responses.
+    bandwidth_limits: BandwidthLimits,
+    /// The bandwidth limits shared by all active sessions.
+    shared_bandwidth_limits: SharedBandwidthLimits,
 }
 
 // === impl SessionManager ===
@@ -136,6 +146,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
         handshake: Arc<dyn EthRlpxHandshake>,
+        protocol_capture: Option<ProtocolCapture>,
+        transport: Transport,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -170,7 +182,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             disconnections_counter: Default::default(),
             metrics: Default::default(),
             handshake,
+            protocol_capture,
+            transport,
             local_range_info,
+            bandwidth_limits: config.bandwidth_limits,
+            shared_bandwidth_limits: SharedBandwidthLimits::new(&config.bandwidth_limits),
         }
     }
 
@@ -246,13 +262,13 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         transition
     }
 
-    /// An incoming TCP connection was received. This starts the authentication process to turn this
+    /// An incoming connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
     /// Returns an error if the configured limit has been reached.
     pub(crate) fn on_incoming(
         &mut self,
-        stream: TcpStream,
+        stream: PeerStream,
         remote_addr: SocketAddr,
     ) -> Result<SessionId, ExceedsSessionLimit> {
         self.counter.ensure_pending_inbound()?;
@@ -281,6 +297,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             pending_events.clone(),
             start_pending_incoming_session(
                 self.handshake.clone(),
+                self.protocol_capture.clone(),
                 disconnect_rx,
                 session_id,
                 stream,
@@ -323,6 +340,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 pending_events.clone(),
                 start_pending_outbound_session(
                     self.handshake.clone(),
+                    self.protocol_capture.clone(),
+                    self.transport,
                     disconnect_rx,
                     pending_events,
                     session_id,
@@ -409,7 +428,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     /// simply drop the incoming connection.
     pub(crate) fn try_disconnect_incoming_connection(
         &self,
-        stream: TcpStream,
+        stream: PeerStream,
         reason: DisconnectReason,
     ) {
         if !self.disconnections_counter.has_capacity() {
@@ -571,6 +590,10 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     range_info: None,
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
+                    bandwidth_limiter: SessionBandwidthLimiter::new(
+                        &self.bandwidth_limits,
+                        self.shared_bandwidth_limits.clone(),
+                    ),
                 };
 
                 self.spawn(session);
@@ -875,9 +898,10 @@ pub(crate) async fn pending_session_with_timeout<F, N: NetworkPrimitives>(
 #[expect(clippy::too_many_arguments)]
 pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     handshake: Arc<dyn EthRlpxHandshake>,
+    protocol_capture: Option<ProtocolCapture>,
     disconnect_rx: oneshot::Receiver<()>,
     session_id: SessionId,
-    stream: TcpStream,
+    stream: PeerStream,
     events: mpsc::Sender<PendingSessionEvent<N>>,
     remote_addr: SocketAddr,
     secret_key: SecretKey,
@@ -888,6 +912,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
 ) {
     authenticate(
         handshake,
+        protocol_capture,
         disconnect_rx,
         events,
         stream,
@@ -908,6 +933,8 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
 #[expect(clippy::too_many_arguments)]
 async fn start_pending_outbound_session<N: NetworkPrimitives>(
     handshake: Arc<dyn EthRlpxHandshake>,
+    protocol_capture: Option<ProtocolCapture>,
+    transport: Transport,
     disconnect_rx: oneshot::Receiver<()>,
     events: mpsc::Sender<PendingSessionEvent<N>>,
     session_id: SessionId,
@@ -919,13 +946,8 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
-    let stream = match TcpStream::connect(remote_addr).await {
-        Ok(stream) => {
-            if let Err(err) = stream.set_nodelay(true) {
-                tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
-            }
-            stream
-        }
+    let stream = match transport.connect(remote_addr).await {
+        Ok(stream) => stream,
         Err(error) => {
             let _ = events
                 .send(PendingSessionEvent::OutgoingConnectionError {
@@ -940,6 +962,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     };
     authenticate(
         handshake,
+        protocol_capture,
         disconnect_rx,
         events,
         stream,
@@ -959,9 +982,10 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
 #[expect(clippy::too_many_arguments)]
 async fn authenticate<N: NetworkPrimitives>(
     handshake: Arc<dyn EthRlpxHandshake>,
+    protocol_capture: Option<ProtocolCapture>,
     disconnect_rx: oneshot::Receiver<()>,
     events: mpsc::Sender<PendingSessionEvent<N>>,
-    stream: TcpStream,
+    stream: PeerStream,
     session_id: SessionId,
     remote_addr: SocketAddr,
     secret_key: SecretKey,
@@ -991,6 +1015,7 @@ async fn authenticate<N: NetworkPrimitives>(
 
     let auth = authenticate_stream(
         handshake,
+        protocol_capture,
         unauthed,
         session_id,
         remote_addr,
@@ -1044,7 +1069,8 @@ async fn get_ecies_stream<Io: AsyncRead + AsyncWrite + Unpin>(
 #[expect(clippy::too_many_arguments)]
 async fn authenticate_stream<N: NetworkPrimitives>(
     handshake: Arc<dyn EthRlpxHandshake>,
-    stream: UnauthedP2PStream<ECIESStream<TcpStream>>,
+    protocol_capture: Option<ProtocolCapture>,
+    stream: UnauthedP2PStream<ECIESStream<PeerStream>>,
     session_id: SessionId,
     remote_addr: SocketAddr,
     local_addr: Option<SocketAddr>,
@@ -1122,7 +1148,10 @@ async fn authenticate_stream<N: NetworkPrimitives>(
             .await
         {
             Ok(their_status) => {
-                let eth_stream = EthStream::new(eth_version, p2p_stream);
+                let mut eth_stream = EthStream::new(eth_version, p2p_stream);
+                if let Some(capture) = protocol_capture {
+                    eth_stream.set_capture(capture, their_hello.id);
+                }
                 (eth_stream.into(), their_status)
             }
             Err(err) => {
@@ -1150,7 +1179,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
                 .ok();
         }
 
-        let (multiplex_stream, their_status) =
+        let (mut multiplex_stream, their_status) =
             match multiplex_stream.into_eth_satellite_stream(status, fork_filter).await {
                 Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
                 Err(err) => {
@@ -1163,6 +1192,10 @@ async fn authenticate_stream<N: NetworkPrimitives>(
                 }
             };
 
+        if let Some(capture) = protocol_capture {
+            multiplex_stream.primary_mut().set_capture(capture, their_hello.id);
+        }
+
         (multiplex_stream.into(), their_status)
     };
 
@@ -0,0 +1,903 @@
+//! Serves `snap` protocol requests from the persisted state.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    eth_requests::SOFT_RESPONSE_LIMIT,
+    metered_poll_nested_stream_with_budget,
+    metrics::SnapRequestHandlerMetrics,
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+};
+use alloy_primitives::{
+    bytes::BytesMut, map::B256Set, Bytes, B256, KECCAK256_EMPTY as KECCAK_EMPTY, U256,
+};
+use alloy_rlp::RlpEncodable;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_eth_wire_types::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SnapProtocolMessage,
+    StorageData, StorageRangesMessage, TrieNodesMessage, TriePath,
+};
+use reth_network_api::Direction;
+use reth_network_peers::PeerId;
+use reth_storage_api::{
+    errors::provider::ProviderResult, BytecodeReader, HashedStateRangeProvider,
+    HashedStateRangeProviderFactory, StateProviderFactory,
+};
+use reth_tasks::TaskSpawner;
+use reth_trie_common::{MultiProofTargets, Nibbles, TrieAccount, EMPTY_ROOT_HASH};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError, Receiver},
+    oneshot,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, trace};
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/snap/handler.go#L34-L52>
+
+/// Maximum number of contract codes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Number of accounts or storage slots that are read from the database at once while serving a
+/// range request.
+const RANGE_READ_BATCH_SIZE: usize = 128;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Requests are only served for the latest persisted state. Requests for any other state root,
+/// including the state of blocks that are only kept in memory, are answered with empty responses,
+/// which peers treat as the state being unavailable.
+///
+/// Requests are served on blocking tasks, so that reading the state doesn't block the handling
+/// of further incoming requests.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// Reads the responses from the state.
+    reader: SnapStateReader<C>,
+    /// Spawns the blocking tasks that serve the requests.
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Incoming requests from the [`SnapConnection`]s.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(
+        client: C,
+        task_spawner: Box<dyn TaskSpawner>,
+        incoming: Receiver<IncomingSnapRequest>,
+    ) -> Self {
+        Self {
+            reader: SnapStateReader { client, metrics: Default::default() },
+            task_spawner,
+            incoming_requests: ReceiverStream::new(incoming),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: HashedStateRangeProviderFactory + StateProviderFactory + Clone + 'static,
+{
+    /// Serves a request on a blocking task and sends the response.
+    ///
+    /// If serving the request fails, the `empty` response is sent.
+    fn spawn_response<R, F>(
+        &self,
+        peer_id: PeerId,
+        response: oneshot::Sender<R>,
+        empty: R,
+        serve: F,
+    ) where
+        R: Send + 'static,
+        F: FnOnce(&SnapStateReader<C>) -> ProviderResult<R> + Send + 'static,
+    {
+        let reader = self.reader.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = serve(&reader).unwrap_or_else(|err| {
+                debug!(target: "net::snap", %peer_id, %err, "Failed to serve snap request");
+                empty
+            });
+            let _ = response.send(result);
+        }));
+    }
+
+    fn on_incoming_request(&self, incoming: IncomingSnapRequest) {
+        let metrics = &self.reader.metrics;
+        match incoming {
+            IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                metrics.snap_account_range_requests_received_total.increment(1);
+                let empty = AccountRangeMessage {
+                    request_id: request.request_id,
+                    accounts: Vec::new(),
+                    proof: Vec::new(),
+                };
+                self.spawn_response(peer_id, response, empty, move |reader| {
+                    reader.get_account_range_response(request)
+                })
+            }
+            IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                metrics.snap_storage_ranges_requests_received_total.increment(1);
+                let empty = StorageRangesMessage {
+                    request_id: request.request_id,
+                    slots: Vec::new(),
+                    proof: Vec::new(),
+                };
+                self.spawn_response(peer_id, response, empty, move |reader| {
+                    reader.get_storage_ranges_response(request)
+                })
+            }
+            IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                metrics.snap_bytecodes_requests_received_total.increment(1);
+                let empty = ByteCodesMessage { request_id: request.request_id, codes: Vec::new() };
+                self.spawn_response(peer_id, response, empty, move |reader| {
+                    reader.get_bytecodes_response(request)
+                })
+            }
+            IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                metrics.snap_trie_nodes_requests_received_total.increment(1);
+                let empty = TrieNodesMessage { request_id: request.request_id, nodes: Vec::new() };
+                self.spawn_response(peer_id, response, empty, move |reader| {
+                    reader.get_trie_nodes_response(request)
+                })
+            }
+        }
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: HashedStateRangeProviderFactory + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| this.on_incoming_request(incoming),
+        );
+
+        this.reader.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Reads the responses to `snap` requests from the state.
+#[derive(Debug, Clone)]
+struct SnapStateReader<C> {
+    /// The client type that can interact with the state.
+    client: C,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+impl<C> SnapStateReader<C>
+where
+    C: HashedStateRangeProviderFactory + StateProviderFactory,
+{
+    /// Returns a provider over the persisted state if its state root is the given root.
+    ///
+    /// A response is read entirely through the returned provider, so that it's consistent with
+    /// the requested root even if more blocks are persisted while it's being served.
+    fn provider_at(&self, root_hash: B256) -> ProviderResult<Option<C::Provider>> {
+        let provider = self.client.persisted_state_provider()?;
+        if provider.persisted_state_root()? == root_hash {
+            return Ok(Some(provider))
+        }
+        self.metrics.snap_unavailable_state_root_total.increment(1);
+        Ok(None)
+    }
+
+    /// Returns the requested range of accounts and the proof for the range boundaries.
+    fn get_account_range_response(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<AccountRangeMessage> {
+        let GetAccountRangeMessage {
+            request_id,
+            root_hash,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+
+        let mut response =
+            AccountRangeMessage { request_id, accounts: Vec::new(), proof: Vec::new() };
+        let Some(provider) = self.provider_at(root_hash)? else { return Ok(response) };
+
+        let size_limit = response_size_limit(response_bytes);
+        let mut total_bytes = 0;
+        let mut next = Some(starting_hash);
+
+        'range: while let Some(start) = next {
+            let accounts = provider.hashed_account_range(start, RANGE_READ_BATCH_SIZE)?;
+            next = next_range_start(&accounts);
+
+            for (hash, account) in accounts {
+                let body = encode_slim_account(&account);
+                total_bytes += B256::len_bytes() + body.len();
+                response.accounts.push(AccountData { hash, body });
+
+                // the first account at or after the limit is included to prove the end of the
+                // range
+                if hash >= limit_hash || total_bytes >= size_limit {
+                    break 'range
+                }
+            }
+        }
+
+        let mut targets = B256Set::from_iter([starting_hash]);
+        targets.extend(response.accounts.last().map(|account| account.hash));
+        let proof = provider.persisted_multiproof(MultiProofTargets::accounts(targets))?;
+        response.proof =
+            proof.account_subtree.into_nodes_sorted().into_iter().map(|(_, node)| node).collect();
+
+        Ok(response)
+    }
+
+    /// Returns the requested storage ranges.
+    ///
+    /// The starting and limit hash only apply to the first requested account. A proof is only
+    /// included if the storage of the last returned account is incomplete.
+    fn get_storage_ranges_response(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage> {
+        let GetStorageRangesMessage {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+
+        let mut response =
+            StorageRangesMessage { request_id, slots: Vec::new(), proof: Vec::new() };
+        let Some(provider) = self.provider_at(root_hash)? else { return Ok(response) };
+
+        let size_limit = response_size_limit(response_bytes);
+        let mut total_bytes = 0;
+
+        for (idx, hashed_address) in account_hashes.into_iter().enumerate() {
+            if total_bytes >= size_limit {
+                break
+            }
+
+            let (origin, limit) = if idx == 0 {
+                (starting_hash, limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            let mut next = Some(origin);
+
+            'range: while let Some(start) = next {
+                let storage =
+                    provider.hashed_storage_range(hashed_address, start, RANGE_READ_BATCH_SIZE)?;
+                next = next_range_start(&storage);
+
+                for (hash, value) in storage {
+                    if total_bytes >= size_limit {
+                        aborted = true;
+                        break 'range
+                    }
+
+                    let data = Bytes::from(alloy_rlp::encode(value));
+                    total_bytes += B256::len_bytes() + data.len();
+                    slots.push(StorageData { hash, data });
+
+                    if hash >= limit {
+                        break 'range
+                    }
+                }
+            }
+
+            let last = slots.last().map(|slot| slot.hash);
+            if !slots.is_empty() {
+                response.slots.push(slots);
+            }
+
+            // a partial storage range must be proven, after which no further accounts are served
+            if !origin.is_zero() || (aborted && last.is_some()) {
+                let mut targets = B256Set::from_iter([origin]);
+                targets.extend(last);
+                let mut proof = provider.persisted_multiproof(
+                    MultiProofTargets::account_with_slots(hashed_address, targets),
+                )?;
+                if let Some(storage) = proof.storages.remove(&hashed_address) {
+                    response.proof = storage
+                        .subtree
+                        .into_nodes_sorted()
+                        .into_iter()
+                        .map(|(_, node)| node)
+                        .collect();
+                }
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested contract codes.
+    fn get_bytecodes_response(
+        &self,
+        request: GetByteCodesMessage,
+    ) -> ProviderResult<ByteCodesMessage> {
+        let GetByteCodesMessage { request_id, hashes, response_bytes } = request;
+
+        let size_limit = response_size_limit(response_bytes);
+        let mut total_bytes = 0;
+        let mut codes = Vec::new();
+
+        let state = self.client.latest()?;
+        for hash in hashes.into_iter().take(MAX_CODE_LOOKUPS) {
+            if hash == KECCAK_EMPTY {
+                codes.push(Bytes::new());
+                continue
+            }
+
+            // unknown codes are skipped, the requester matches the codes by hash
+            let Some(code) = state.bytecode_by_hash(&hash)? else { continue };
+            let code = code.original_bytes();
+            total_bytes += code.len();
+            codes.push(code);
+
+            if total_bytes >= size_limit {
+                break
+            }
+        }
+
+        Ok(ByteCodesMessage { request_id, codes })
+    }
+
+    /// Returns the requested trie nodes.
+    ///
+    /// Nodes that don't exist are returned as empty bytes.
+    fn get_trie_nodes_response(
+        &self,
+        request: GetTrieNodesMessage,
+    ) -> ProviderResult<TrieNodesMessage> {
+        let GetTrieNodesMessage { request_id, root_hash, paths, response_bytes } = request;
+
+        let mut response = TrieNodesMessage { request_id, nodes: Vec::new() };
+        let Some(provider) = self.provider_at(root_hash)? else { return Ok(response) };
+
+        // resolve all requested paths with a single multiproof
+        let mut lookups = Vec::new();
+        let mut targets = MultiProofTargets::default();
+        for TriePath { account_path, slot_paths } in paths {
+            if slot_paths.is_empty() {
+                // a single path refers to a node of the account trie
+                let Some(path) = decode_compact_path(&account_path) else { break };
+                targets.entry(padded_key(&path)).or_default();
+                lookups.push((None, path));
+            } else {
+                // otherwise the account path is the hashed address of the storage trie
+                let Ok(hashed_address) = B256::try_from(account_path.as_ref()) else { break };
+                for slot_path in slot_paths.into_iter().take(MAX_TRIE_NODE_LOOKUPS - lookups.len())
+                {
+                    let Some(path) = decode_compact_path(&slot_path) else { break };
+                    targets.entry(hashed_address).or_default().insert(padded_key(&path));
+                    lookups.push((Some(hashed_address), path));
+                }
+            }
+
+            if lookups.len() >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+        }
+
+        if lookups.is_empty() {
+            return Ok(response)
+        }
+
+        let proof = provider.persisted_multiproof(targets)?;
+        let size_limit = response_size_limit(response_bytes);
+        let mut total_bytes = 0;
+
+        for (hashed_address, path) in lookups {
+            let node = match hashed_address {
+                None => proof.account_subtree.get(&path),
+                Some(hashed_address) => proof
+                    .storages
+                    .get(&hashed_address)
+                    .and_then(|storage| storage.subtree.get(&path)),
+            };
+            let node = node.cloned().unwrap_or_default();
+            total_bytes += node.len();
+            response.nodes.push(node);
+
+            if total_bytes >= size_limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+/// All `snap` requests delegated by the [`SnapConnection`]s.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the account range.
+        peer_id: PeerId,
+        /// The requested account range.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<AccountRangeMessage>,
+    },
+    /// Request storage ranges from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage ranges.
+        peer_id: PeerId,
+        /// The requested storage ranges.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<StorageRangesMessage>,
+    },
+    /// Request contract codes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the contract codes.
+        peer_id: PeerId,
+        /// The requested contract code hashes.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the contract codes.
+        response: oneshot::Sender<ByteCodesMessage>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The requested trie node paths.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<TrieNodesMessage>,
+    },
+}
+
+/// The [`ProtocolHandler`] of the `snap/1` protocol that forwards incoming requests to the
+/// [`SnapRequestHandler`].
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the [`SnapRequestHandler`]'s channel.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new protocol handler that forwards requests to the given channel.
+    pub const fn new(to_request_handler: mpsc::Sender<IncomingSnapRequest>) -> Self {
+        Self { to_request_handler }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+}
+
+/// The [`ConnectionHandler`] of the `snap/1` protocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    /// Sender half of the [`SnapRequestHandler`]'s channel.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// A `snap/1` connection to a peer that serves the peer's requests.
+///
+/// Responses are yielded in the order in which they are resolved by the [`SnapRequestHandler`].
+#[must_use = "Streams do nothing unless polled."]
+pub struct SnapConnection {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// The raw connection to the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the [`SnapRequestHandler`]'s channel.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Responses that are currently being resolved by the [`SnapRequestHandler`].
+    pending_responses: FuturesUnordered<BoxFuture<'static, Option<SnapProtocolMessage>>>,
+}
+
+impl SnapConnection {
+    /// Forwards a decoded message of the peer to the [`SnapRequestHandler`].
+    fn on_message(&self, message: SnapProtocolMessage) {
+        let peer_id = self.peer_id;
+        let (request, response) = match message {
+            SnapProtocolMessage::GetAccountRange(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::AccountRange)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetStorageRanges(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::StorageRanges)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetByteCodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::ByteCodes)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetTrieNodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::TrieNodes)).boxed(),
+                )
+            }
+            message => {
+                // responses are ignored since no requests are sent over this connection
+                trace!(target: "net::snap", %peer_id, id=?message.message_id(), "Ignoring unsolicited snap response");
+                return
+            }
+        };
+
+        match self.to_request_handler.try_send(request) {
+            Ok(()) => self.pending_responses.push(response),
+            Err(TrySendError::Full(_)) => {
+                debug!(target: "net::snap", %peer_id, "SnapRequestHandler channel is full!");
+            }
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(BytesMut::from(response.encode().as_ref())))
+                }
+                continue
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            let Some((&id, mut payload)) = msg.split_first() else { return Poll::Ready(None) };
+            match SnapProtocolMessage::decode(id, &mut payload) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    // disconnect peers that send malformed messages
+                    debug!(target: "net::snap", peer_id=%this.peer_id, %err, "Failed to decode snap message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An account in the slim format used by the `snap` protocol, where the empty storage root and the
+/// empty code hash are replaced by empty bytes.
+#[derive(RlpEncodable)]
+struct SlimAccount<'a> {
+    nonce: u64,
+    balance: U256,
+    storage_root: &'a [u8],
+    code_hash: &'a [u8],
+}
+
+/// Encodes the account in the slim format.
+fn encode_slim_account(account: &TrieAccount) -> Bytes {
+    let storage_root: &[u8] =
+        if account.storage_root == EMPTY_ROOT_HASH { &[] } else { account.storage_root.as_slice() };
+    let code_hash: &[u8] =
+        if account.code_hash == KECCAK_EMPTY { &[] } else { account.code_hash.as_slice() };
+    alloy_rlp::encode(SlimAccount {
+        nonce: account.nonce,
+        balance: account.balance,
+        storage_root,
+        code_hash,
+    })
+    .into()
+}
+
+/// Returns the maximum response size for the requested number of bytes.
+fn response_size_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// Returns the key to continue reading a range at, if the given batch was full.
+fn next_range_start<T>(batch: &[(B256, T)]) -> Option<B256> {
+    if batch.len() < RANGE_READ_BATCH_SIZE {
+        return None
+    }
+    let (last, _) = batch.last()?;
+    U256::from_be_bytes(last.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Decodes a hex-prefix encoded trie path.
+///
+/// Returns `None` if the path is empty or longer than a full key.
+fn decode_compact_path(compact: &[u8]) -> Option<Nibbles> {
+    let (&flag, rest) = compact.split_first()?;
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // odd length paths store the first nibble in the flag byte
+    if flag & 0x10 != 0 {
+        nibbles.push(flag & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles(nibbles))
+}
+
+/// Returns the key that is prefixed by the given path, padded with zeros.
+fn padded_key(path: &Nibbles) -> B256 {
+    B256::right_padding_from(&path.pack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{hex, keccak256};
+    use reth_primitives_traits::{Account, SealedHeader};
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory,
+        LatestStateProviderRef, StateRootProvider, StateWriter, StaticFileProviderFactory,
+        StaticFileSegment, StaticFileWriter, TrieWriter,
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use reth_trie_common::{root::storage_root_unsorted, HashedPostState, HashedStorage};
+
+    #[test]
+    fn decode_hex_prefix_paths() {
+        // even length
+        assert_eq!(decode_compact_path(&hex!("00")), Some(Nibbles::default()));
+        assert_eq!(decode_compact_path(&hex!("0012")), Some(Nibbles::from_nibbles([1, 2])));
+        // odd length
+        assert_eq!(decode_compact_path(&hex!("1a")), Some(Nibbles::from_nibbles([0xa])));
+        assert_eq!(
+            decode_compact_path(&hex!("1abc")),
+            Some(Nibbles::from_nibbles([0xa, 0xb, 0xc]))
+        );
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(decode_compact_path(&[0; 34]), None);
+
+        assert_eq!(
+            padded_key(&Nibbles::from_nibbles([0xa, 0xb, 0xc])),
+            B256::right_padding_from(&hex!("abc0"))
+        );
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let account = TrieAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        assert_eq!(encode_slim_account(&account), Bytes::from_static(&hex!("c401028080")));
+
+        let account = TrieAccount { storage_root: B256::repeat_byte(1), ..account };
+        let encoded = encode_slim_account(&account);
+        assert_eq!(encoded.len(), 1 + 2 + 33 + 1);
+    }
+
+    #[test]
+    fn range_continuation() {
+        let batch = vec![(B256::ZERO, ()); RANGE_READ_BATCH_SIZE - 1];
+        assert_eq!(next_range_start(&batch), None);
+
+        let mut batch = vec![(B256::ZERO, ()); RANGE_READ_BATCH_SIZE];
+        assert_eq!(next_range_start(&batch), Some(B256::with_last_byte(1)));
+
+        batch.last_mut().unwrap().0 = B256::repeat_byte(0xff);
+        assert_eq!(next_range_start(&batch), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serve_account_range_from_database() {
+        let factory = create_test_provider_factory();
+
+        // the first storage trie is large enough for its root node to be stored, the second one
+        // isn't and the last account has no storage
+        let accounts = (1..=3u8)
+            .map(|byte| {
+                let account =
+                    Account { nonce: byte as u64, balance: U256::from(byte), bytecode_hash: None };
+                (B256::repeat_byte(byte), account)
+            })
+            .collect::<Vec<_>>();
+        let storages = [
+            (
+                accounts[0].0,
+                (1..=256u64)
+                    .map(|slot| (keccak256(B256::from(U256::from(slot))), U256::from(slot)))
+                    .collect::<Vec<_>>(),
+            ),
+            (accounts[1].0, vec![(B256::with_last_byte(1), U256::from(1))]),
+        ];
+        let hashed_state = HashedPostState {
+            accounts: accounts.iter().map(|(hash, account)| (*hash, Some(*account))).collect(),
+            storages: storages
+                .iter()
+                .map(|(hash, slots)| (*hash, HashedStorage::from_iter(false, slots.clone())))
+                .collect(),
+        };
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.write_hashed_state(&hashed_state.into_sorted()).unwrap();
+        let (state_root, trie_updates) = LatestStateProviderRef::new(&provider_rw)
+            .state_root_with_updates(HashedPostState::default())
+            .unwrap();
+        provider_rw.write_trie_updates(&trie_updates).unwrap();
+        let header = SealedHeader::seal_slow(Header { state_root, ..Default::default() });
+        let mut writer =
+            provider_rw.static_file_provider().latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(header.header(), U256::ZERO, &header.hash()).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+        provider_rw.commit().unwrap();
+
+        let (tx, rx) = mpsc::channel(1);
+        let handler = SnapRequestHandler::new(
+            BlockchainProvider::new(factory).unwrap(),
+            TokioTaskExecutor::default().boxed(),
+            rx,
+        );
+        tokio::spawn(handler);
+
+        let get_account_range = |root_hash| {
+            let tx = tx.clone();
+            async move {
+                let (response, rx) = oneshot::channel();
+                let request = GetAccountRangeMessage {
+                    request_id: 1,
+                    root_hash,
+                    starting_hash: B256::ZERO,
+                    limit_hash: B256::repeat_byte(0xff),
+                    response_bytes: SOFT_RESPONSE_LIMIT as u64,
+                };
+                tx.send(IncomingSnapRequest::GetAccountRange {
+                    peer_id: PeerId::random(),
+                    request,
+                    response,
+                })
+                .await
+                .unwrap();
+                rx.await.unwrap()
+            }
+        };
+
+        let response = get_account_range(state_root).await;
+        let expected = accounts
+            .iter()
+            .map(|(hash, account)| {
+                let storage_root = storages
+                    .iter()
+                    .find(|(address, _)| address == hash)
+                    .map(|(_, slots)| storage_root_unsorted(slots.clone()))
+                    .unwrap_or(EMPTY_ROOT_HASH);
+                AccountData {
+                    hash: *hash,
+                    body: encode_slim_account(&account.into_trie_account(storage_root)),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(response.accounts, expected);
+        assert!(!response.proof.is_empty());
+
+        // any other state is unavailable
+        let response = get_account_range(B256::random()).await;
+        assert!(response.accounts.is_empty());
+        assert!(response.proof.is_empty());
+    }
+}
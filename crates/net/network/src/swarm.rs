@@ -33,11 +33,11 @@ use tracing::{debug, trace};
 ///
 /// Following diagram displays the dataflow contained in the [`Swarm`]
 ///
-/// The [`ConnectionListener`] yields incoming [`TcpStream`]s from peers that are spawned as session
-/// tasks. After a successful `RLPx` authentication, the task is ready to accept ETH requests or
-/// broadcast messages. A task listens for messages from the [`SessionManager`] which include
-/// broadcast messages like `Transactions` or internal commands, for example to disconnect the
-/// session.
+/// The [`ConnectionListener`] yields incoming [`PeerStream`](crate::transport::PeerStream)s from
+/// peers that are spawned as session tasks. After a successful `RLPx` authentication, the task is
+/// ready to accept ETH requests or broadcast messages. A task listens for messages from the
+/// [`SessionManager`] which include broadcast messages like `Transactions` or internal commands,
+/// for example to disconnect the session.
 ///
 /// The [`NetworkState`] keeps track of all connected and discovered peers and can initiate outgoing
 /// connections. For each active session, the [`NetworkState`] keeps a sender half of the ETH
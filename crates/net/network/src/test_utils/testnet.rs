@@ -10,6 +10,7 @@ use crate::{
         policy::NetworkPolicies,
         TransactionsHandle, TransactionsManager, TransactionsManagerConfig,
     },
+    transport::Transport,
     NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
 use futures::{FutureExt, StreamExt};
@@ -713,6 +714,12 @@ where
         Self { config, client, secret_key }
     }
 
+    /// Sets the transport the peer connects to other peers over.
+    pub const fn with_transport(mut self, transport: Transport) -> Self {
+        self.config.transport = transport;
+        self
+    }
+
     fn network_config_builder(secret_key: SecretKey) -> NetworkConfigBuilder {
         NetworkConfigBuilder::new(secret_key)
             .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
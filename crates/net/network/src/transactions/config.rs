@@ -1,7 +1,8 @@
 use std::{fmt::Debug, marker::PhantomData, str::FromStr};
 
 use super::{
-    PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+    PeerMetadata, DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS,
+    DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER, DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
@@ -25,6 +26,16 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Max byte size of a transaction that is broadcast in full. Larger transactions, and blob
+    /// transactions of any size, are only announced by hash.
+    #[cfg_attr(feature = "serde", serde(default = "default_max_full_broadcast_transaction_size"))]
+    pub max_full_broadcast_transaction_size: usize,
+    /// Max number of trusted peers, opted in with
+    /// [`TransactionsHandle::set_blob_prefill`](super::TransactionsHandle::set_blob_prefill),
+    /// whose announced blob transactions are requested immediately instead of being buffered
+    /// while the peer is busy.
+    #[cfg_attr(feature = "serde", serde(default = "default_max_blob_prefill_peers"))]
+    pub max_blob_prefill_peers: usize,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -33,10 +44,22 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            max_full_broadcast_transaction_size: DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
+            max_blob_prefill_peers: DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS,
         }
     }
 }
 
+#[cfg(feature = "serde")]
+const fn default_max_full_broadcast_transaction_size() -> usize {
+    DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST
+}
+
+#[cfg(feature = "serde")]
+const fn default_max_blob_prefill_peers() -> usize {
+    DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS
+}
+
 /// Determines how new pending transactions are propagated to other peers in full.
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
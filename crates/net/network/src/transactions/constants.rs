@@ -53,6 +53,16 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default max byte size of a transaction that is broadcast in full to peers. Larger
+    /// transactions are only announced by hash, and fetched by the peers that don't have them yet.
+    ///
+    /// Default is 4 KiB, the same as geth's.
+    pub const DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST: usize = 4 * 1024;
+
+    /// Default max number of opted-in trusted peers whose announced blob transactions are fetched
+    /// right away, even if the peer is busy with other requests.
+    pub const DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS: usize = 4;
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
@@ -589,6 +589,38 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         &mut self,
         new_announced_hashes: RequestTxHashes,
         peer: &PeerMetadata<N>,
+    ) -> Option<RequestTxHashes> {
+        let max_inflight_requests_per_peer = self.info.max_inflight_requests_per_peer;
+        self.request_transactions_from_peer_with_limit(
+            new_announced_hashes,
+            peer,
+            max_inflight_requests_per_peer,
+        )
+    }
+
+    /// Requests announced blob transactions from a peer that opted in to blob prefill. Same as
+    /// [`request_transactions_from_peer`](Self::request_transactions_from_peer), but allows one
+    /// more inflight request to the peer than the configured per peer limit, so that blob
+    /// transactions don't wait for the peer to become idle.
+    pub fn prefill_transactions_from_peer(
+        &mut self,
+        new_announced_hashes: RequestTxHashes,
+        peer: &PeerMetadata<N>,
+    ) -> Option<RequestTxHashes> {
+        let max_inflight_requests_per_peer =
+            self.info.max_inflight_requests_per_peer.saturating_add(1);
+        self.request_transactions_from_peer_with_limit(
+            new_announced_hashes,
+            peer,
+            max_inflight_requests_per_peer,
+        )
+    }
+
+    fn request_transactions_from_peer_with_limit(
+        &mut self,
+        new_announced_hashes: RequestTxHashes,
+        peer: &PeerMetadata<N>,
+        max_inflight_requests_per_peer: u8,
     ) -> Option<RequestTxHashes> {
         let peer_id: PeerId = peer.request_tx.peer_id;
         let conn_eth_version = peer.version;
@@ -614,12 +646,12 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
             return Some(new_announced_hashes)
         };
 
-        if *inflight_count >= self.info.max_inflight_requests_per_peer {
+        if *inflight_count >= max_inflight_requests_per_peer {
             trace!(target: "net::tx",
                 peer_id=format!("{peer_id:#}"),
                 hashes=?*new_announced_hashes,
                 %conn_eth_version,
-                max_concurrent_tx_reqs_per_peer=self.info.max_inflight_requests_per_peer,
+                max_concurrent_tx_reqs_per_peer=max_inflight_requests_per_peer,
                 "limit for concurrent `GetPooledTransactions` requests per peer reached"
             );
             return Some(new_announced_hashes)
@@ -8,6 +8,8 @@ pub mod constants;
 pub mod fetcher;
 /// Defines the [`TransactionPolicies`] trait for aggregating transaction-related policies.
 pub mod policy;
+/// Sub-protocol for exchanging pool summaries with newly connected peers.
+pub mod pool_sync;
 
 pub use self::constants::{
     tx_fetcher::DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
@@ -36,6 +38,7 @@ use crate::{
     },
     NetworkHandle, TxTypesCounter,
 };
+use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
 use alloy_primitives::{TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
@@ -137,6 +140,17 @@ impl<N: NetworkPrimitives> TransactionsHandle<N> {
         self.send(TransactionsCommand::PropagateHashesTo(hashes, peer))
     }
 
+    /// Announces the transaction hashes to a specific peer, skipping the hashes the peer has
+    /// already seen.
+    ///
+    /// If the peer's session is not yet active, the hashes are announced once it is.
+    pub(crate) fn announce_hashes_to(&self, hashes: Vec<TxHash>, peer: PeerId) {
+        if hashes.is_empty() {
+            return
+        }
+        self.send(TransactionsCommand::AnnounceHashesTo(hashes, peer))
+    }
+
     /// Request the active peer IDs from the [`TransactionsManager`].
     pub async fn get_active_peers(&self) -> Result<HashSet<PeerId>, RecvError> {
         let (tx, rx) = oneshot::channel();
@@ -165,6 +179,18 @@ impl<N: NetworkPrimitives> TransactionsHandle<N> {
         self.send(TransactionsCommand::PropagateTransactions(transactions))
     }
 
+    /// Manually propagate the given transaction hashes to all peers, including the peers that
+    /// have already seen them.
+    ///
+    /// This is intended for re-broadcasting transactions that remain pending, for example local
+    /// transactions that were not included for a number of blocks.
+    pub fn propagate_transactions_forced(&self, transactions: Vec<TxHash>) {
+        if transactions.is_empty() {
+            return
+        }
+        self.send(TransactionsCommand::PropagateTransactionsForced(transactions))
+    }
+
     /// Manually propagate the given transactions to all peers.
     ///
     /// It's up to the [`TransactionsManager`] whether the transactions are sent as hashes or in
@@ -181,6 +207,16 @@ impl<N: NetworkPrimitives> TransactionsHandle<N> {
         self.send(TransactionsCommand::BroadcastTransactions(transactions))
     }
 
+    /// Opts a trusted peer in or out of blob prefill.
+    ///
+    /// Blob transactions announced by an opted-in trusted peer are requested right away, even if
+    /// the peer is busy with other `GetPooledTransactions` requests, instead of being buffered
+    /// until the peer becomes idle. At most
+    /// [`TransactionsManagerConfig::max_blob_prefill_peers`] peers are prefilled from.
+    pub fn set_blob_prefill(&self, peer_id: PeerId, enabled: bool) {
+        self.send(TransactionsCommand::SetBlobPrefill { peer_id, enabled })
+    }
+
     /// Request the transaction hashes known by specific peers.
     pub async fn get_transaction_hashes(
         &self,
@@ -320,6 +356,10 @@ pub struct TransactionsManager<
     bad_imports: LruCache<TxHash>,
     /// All the connected peers.
     peers: HashMap<PeerId, PeerMetadata<N>>,
+    /// Peers that opted in to blob prefill, see [`TransactionsHandle::set_blob_prefill`].
+    blob_prefill_peers: HashSet<PeerId>,
+    /// Hashes to announce to peers whose session is not yet active.
+    pending_session_announcements: HashMap<PeerId, Vec<TxHash>>,
     /// Send half for the command channel.
     ///
     /// This is kept so that a new [`TransactionsHandle`] can be created at any time.
@@ -418,6 +458,8 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             ),
             bad_imports: LruCache::new(DEFAULT_MAX_COUNT_BAD_IMPORTS),
             peers: Default::default(),
+            blob_prefill_peers: Default::default(),
+            pending_session_announcements: Default::default(),
             command_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
             pending_transactions: ReceiverStream::new(pending),
@@ -437,6 +479,11 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
         TransactionsHandle { manager_tx: self.command_tx.clone() }
     }
 
+    /// Returns the transaction pool.
+    pub const fn pool(&self) -> &Pool {
+        &self.pool
+    }
+
     /// Returns `true` if [`TransactionsManager`] has capacity to request pending hashes. Returns
     /// `false` if [`TransactionsManager`] is operating close to full capacity.
     fn has_capacity_for_fetching_pending_hashes(&self) -> bool {
@@ -450,6 +497,17 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
         self.metrics.reported_bad_transactions.increment(1);
     }
 
+    /// Returns `true` if blob transactions announced by the given peer should be requested right
+    /// away, i.e. the peer opted in to blob prefill, is trusted and within
+    /// [`TransactionsManagerConfig::max_blob_prefill_peers`].
+    fn is_blob_prefill_target(&self, peer_id: &PeerId) -> bool {
+        self.blob_prefill_peers
+            .iter()
+            .filter(|id| self.peers.get(id).is_some_and(|peer| peer.peer_kind.is_trusted()))
+            .take(self.config.max_blob_prefill_peers)
+            .any(|id| id == peer_id)
+    }
+
     fn report_peer(&self, peer_id: PeerId, kind: ReputationChangeKind) {
         trace!(target: "net::tx", ?peer_id, ?kind, "reporting reputation change");
         self.network.reputation_change(peer_id, kind);
@@ -767,6 +825,42 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
         // only send request for hashes to idle peer, otherwise buffer hashes storing peer as
         // fallback
         if !self.transaction_fetcher.is_idle(&peer_id) {
+            // blob transactions announced by a prefill peer are requested right away
+            if self.is_blob_prefill_target(&peer_id) {
+                let blob_hashes = RequestTxHashes::new(
+                    valid_announcement_data
+                        .iter()
+                        .filter(|(_, metadata)| {
+                            metadata.is_some_and(|(ty, _)| ty == EIP4844_TX_TYPE_ID)
+                        })
+                        .map(|(hash, _)| *hash)
+                        .collect(),
+                );
+
+                if !blob_hashes.is_empty() {
+                    valid_announcement_data.retain(|hash, _| !blob_hashes.contains(hash));
+
+                    trace!(target: "net::tx",
+                        peer_id=format!("{peer_id:#}"),
+                        hashes=?*blob_hashes,
+                        %client,
+                        "prefilling blob transactions announced by busy peer"
+                    );
+
+                    let Some(peer) = self.peers.get(&peer_id) else { return };
+                    if let Some(failed_to_request_hashes) =
+                        self.transaction_fetcher.prefill_transactions_from_peer(blob_hashes, peer)
+                    {
+                        self.transaction_fetcher
+                            .buffer_hashes(failed_to_request_hashes, Some(peer_id));
+                    }
+                }
+
+                if valid_announcement_data.is_empty() {
+                    return
+                }
+            }
+
             // load message version before announcement data is destructed in packing
             let msg_version = valid_announcement_data.msg_version();
             let (hashes, _version) = valid_announcement_data.into_request_hashes();
@@ -863,7 +957,7 @@ where
 
         trace!(target: "net::tx", num_hashes=?hashes.len(), "Start propagating transactions");
 
-        self.propagate_all(hashes);
+        self.propagate_all(hashes, PropagationMode::Basic);
     }
 
     /// Propagate the full transactions to a specific peer.
@@ -881,7 +975,10 @@ where
         let mut propagated = PropagatedTransactions::default();
 
         // filter all transactions unknown to the peer
-        let mut full_transactions = FullTransactionsBuilder::new(peer.version);
+        let mut full_transactions = FullTransactionsBuilder::new(
+            peer.version,
+            self.config.max_full_broadcast_transaction_size,
+        );
 
         let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
 
@@ -1004,6 +1101,23 @@ where
         self.pool.on_propagated(propagated);
     }
 
+    /// Announces the transaction hashes the peer hasn't seen yet.
+    ///
+    /// If the peer's session is not yet active, the hashes are queued until it is.
+    fn announce_hashes_to(&mut self, hashes: Vec<TxHash>, peer_id: PeerId) {
+        if !self.peers.contains_key(&peer_id) {
+            trace!(target: "net::tx", ?peer_id, "Queueing announcement until session is active");
+            self.pending_session_announcements.entry(peer_id).or_default().extend(hashes);
+            return
+        }
+
+        for hashes in
+            hashes.chunks(SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE)
+        {
+            self.propagate_hashes_to(hashes.to_vec(), peer_id, PropagationMode::Basic)
+        }
+    }
+
     /// Propagate the transactions to all connected peers either as full objects or hashes.
     ///
     /// The message for new pooled hashes depends on the negotiated version of the stream.
@@ -1033,7 +1147,10 @@ where
             let mut builder = if peer_idx > max_num_full {
                 PropagateTransactionsBuilder::pooled(peer.version)
             } else {
-                PropagateTransactionsBuilder::full(peer.version)
+                PropagateTransactionsBuilder::full(
+                    peer.version,
+                    self.config.max_full_broadcast_transaction_size,
+                )
             };
 
             if propagation_mode.is_forced() {
@@ -1106,14 +1223,14 @@ where
     ///
     /// This fetches all transaction from the pool, including the 4844 blob transactions but
     /// __without__ their sidecar, because 4844 transactions are only ever announced as hashes.
-    fn propagate_all(&mut self, hashes: Vec<TxHash>) {
+    fn propagate_all(&mut self, hashes: Vec<TxHash>, propagation_mode: PropagationMode) {
         if self.peers.is_empty() {
             // nothing to propagate
             return
         }
         let propagated = self.propagate_transactions(
             self.pool.get_all(hashes).into_iter().map(PropagateTransaction::pool_tx).collect(),
-            PropagationMode::Basic,
+            propagation_mode,
         );
 
         // notify pool so events get fired
@@ -1158,6 +1275,9 @@ where
             TransactionsCommand::PropagateHashesTo(hashes, peer) => {
                 self.propagate_hashes_to(hashes, peer, PropagationMode::Forced)
             }
+            TransactionsCommand::AnnounceHashesTo(hashes, peer) => {
+                self.announce_hashes_to(hashes, peer)
+            }
             TransactionsCommand::GetActivePeers(tx) => {
                 let peers = self.peers.keys().copied().collect::<HashSet<_>>();
                 tx.send(peers).ok();
@@ -1169,7 +1289,12 @@ where
                     self.pool.on_propagated(propagated);
                 }
             }
-            TransactionsCommand::PropagateTransactions(txs) => self.propagate_all(txs),
+            TransactionsCommand::PropagateTransactions(txs) => {
+                self.propagate_all(txs, PropagationMode::Basic)
+            }
+            TransactionsCommand::PropagateTransactionsForced(txs) => {
+                self.propagate_all(txs, PropagationMode::Forced)
+            }
             TransactionsCommand::BroadcastTransactions(txs) => {
                 let propagated = self.propagate_transactions(txs, PropagationMode::Forced);
                 self.pool.on_propagated(propagated);
@@ -1190,6 +1315,13 @@ where
                 let sender = self.peers.get(&peer_id).map(|peer| peer.request_tx.clone());
                 peer_request_sender.send(sender).ok();
             }
+            TransactionsCommand::SetBlobPrefill { peer_id, enabled } => {
+                if enabled {
+                    self.blob_prefill_peers.insert(peer_id);
+                } else {
+                    self.blob_prefill_peers.remove(&peer_id);
+                }
+            }
         }
     }
 
@@ -1260,11 +1392,18 @@ where
                 if let Some(mut peer) = peer {
                     self.policies.propagation_policy_mut().on_session_closed(&mut peer);
                 }
+                self.pending_session_announcements.remove(&peer_id);
                 self.transaction_fetcher.remove_peer(&peer_id);
             }
             NetworkEvent::ActivePeerSession { info, messages } => {
                 // process active peer session and broadcast available transaction from the pool
+                let peer_id = info.peer_id;
                 self.handle_peer_session(info, messages);
+
+                // announce the hashes that were requested before the session was active
+                if let Some(hashes) = self.pending_session_announcements.remove(&peer_id) {
+                    self.announce_hashes_to(hashes, peer_id);
+                }
             }
             NetworkEvent::Peer(PeerEvent::SessionEstablished(info)) => {
                 let peer_id = info.peer_id;
@@ -1716,9 +1855,10 @@ impl<T> PropagateTransactionsBuilder<T> {
         Self::Pooled(PooledTransactionsHashesBuilder::new(version))
     }
 
-    /// Create a builder that sends transactions in full and records transactions that don't fit.
-    fn full(version: EthVersion) -> Self {
-        Self::Full(FullTransactionsBuilder::new(version))
+    /// Create a builder that sends transactions up to the given byte size in full and records
+    /// transactions that are larger or don't fit.
+    fn full(version: EthVersion, max_transaction_size: usize) -> Self {
+        Self::Full(FullTransactionsBuilder::new(version, max_transaction_size))
     }
 
     /// Returns true if no transactions are recorded.
@@ -1767,12 +1907,14 @@ struct PropagateTransactions<T> {
 
 /// Helper type for constructing the full transaction message that enforces the
 /// [`DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE`] for full transaction broadcast
-/// and enforces other propagation rules for EIP-4844 and tracks those transactions that can't be
-/// broadcasted in full.
+/// and enforces other propagation rules for EIP-4844 and large transactions and tracks those
+/// transactions that can't be broadcasted in full.
 #[derive(Debug, Clone)]
 struct FullTransactionsBuilder<T> {
     /// The soft limit to enforce for a single broadcast message of full transactions.
     total_size: usize,
+    /// Max byte size of a single transaction that is broadcast in full.
+    max_transaction_size: usize,
     /// All transactions to be broadcasted.
     transactions: Vec<Arc<T>>,
     /// Transactions that didn't fit into the broadcast message
@@ -1780,10 +1922,12 @@ struct FullTransactionsBuilder<T> {
 }
 
 impl<T> FullTransactionsBuilder<T> {
-    /// Create a builder for the negotiated version of the peer's session
-    fn new(version: EthVersion) -> Self {
+    /// Create a builder for the negotiated version of the peer's session, that broadcasts
+    /// transactions up to the given byte size in full.
+    fn new(version: EthVersion, max_transaction_size: usize) -> Self {
         Self {
             total_size: 0,
+            max_transaction_size,
             pooled: PooledTransactionsHashesBuilder::new(version),
             transactions: vec![],
         }
@@ -1816,8 +1960,9 @@ impl<T: SignedTransaction> FullTransactionsBuilder<T> {
     /// message. The same pattern is followed in filling a [`GetPooledTransactions`] request in
     /// [`TransactionFetcher::fill_request_from_hashes_pending_fetch`].
     ///
-    /// If the transaction is unsuitable for broadcast or would exceed the softlimit, it is appended
-    /// to list of pooled transactions, (e.g. 4844 transactions).
+    /// If the transaction is unsuitable for broadcast, is larger than the max transaction size or
+    /// would exceed the softlimit, it is appended to list of pooled transactions, (e.g. 4844
+    /// transactions).
     /// See also [`SignedTransaction::is_broadcastable_in_full`].
     fn push(&mut self, transaction: &PropagateTransaction<T>) {
         // Do not send full 4844 transaction hashes to peers.
@@ -1833,6 +1978,13 @@ impl<T: SignedTransaction> FullTransactionsBuilder<T> {
             return
         }
 
+        // Large transactions are only announced, so that peers that already received them from
+        // another peer don't receive them again in full.
+        if transaction.size > self.max_transaction_size {
+            self.pooled.push(transaction);
+            return
+        }
+
         let new_size = self.total_size + transaction.size;
         if new_size > DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE &&
             self.total_size > 0
@@ -2000,12 +2152,17 @@ enum TransactionsCommand<N: NetworkPrimitives = EthNetworkPrimitives> {
     PropagateHash(B256),
     /// Propagate transaction hashes to a specific peer.
     PropagateHashesTo(Vec<B256>, PeerId),
+    /// Announce transaction hashes to a specific peer once its session is active, skipping the
+    /// hashes it has already seen.
+    AnnounceHashesTo(Vec<TxHash>, PeerId),
     /// Request the list of active peer IDs from the [`TransactionsManager`].
     GetActivePeers(oneshot::Sender<HashSet<PeerId>>),
     /// Propagate a collection of full transactions to a specific peer.
     PropagateTransactionsTo(Vec<TxHash>, PeerId),
     /// Propagate a collection of hashes to all peers.
     PropagateTransactions(Vec<TxHash>),
+    /// Propagate a collection of hashes to all peers, regardless of whether they have seen them.
+    PropagateTransactionsForced(Vec<TxHash>),
     /// Propagate a collection of broadcastable transactions in full to all peers.
     BroadcastTransactions(Vec<PropagateTransaction<N::BroadcastedTransaction>>),
     /// Request transaction hashes known by specific peers from the [`TransactionsManager`].
@@ -2018,6 +2175,8 @@ enum TransactionsCommand<N: NetworkPrimitives = EthNetworkPrimitives> {
         peer_id: PeerId,
         peer_request_sender: oneshot::Sender<Option<PeerRequestSender<PeerRequest<N>>>>,
     },
+    /// Opts a trusted peer in or out of blob prefill.
+    SetBlobPrefill { peer_id: PeerId, enabled: bool },
 }
 
 /// All events related to transactions emitted by the network.
@@ -2113,8 +2272,9 @@ mod tests {
         sync::{NetworkSyncUpdater, SyncState},
     };
     use reth_storage_api::noop::NoopProvider;
-    use reth_transaction_pool::test_utils::{
-        testing_pool, MockTransaction, MockTransactionFactory, TestPool,
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, MockTransactionFactory, TestPool},
+        TransactionEvent,
     };
     use secp256k1::SecretKey;
     use std::{
@@ -2742,6 +2902,66 @@ mod tests {
         assert_eq!(tx_fetcher.active_peers.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_prefill_blob_txns_from_busy_peer() {
+        reth_tracing::init_test_tracing();
+
+        let mut tx_manager = new_tx_manager().await.0;
+
+        let peer_id_1 = PeerId::new([1; 64]);
+        let peer_id_2 = PeerId::new([2; 64]);
+        let eth_version = EthVersion::Eth68;
+        let max_inflight_requests_per_peer =
+            tx_manager.transaction_fetcher.info.max_inflight_requests_per_peer;
+
+        let (peer_1, mut to_mock_session_rx_1) = new_mock_session(peer_id_1, eth_version);
+        let (peer_2, mut to_mock_session_rx_2) = new_mock_session(peer_id_2, eth_version);
+        tx_manager.peers.insert(peer_id_1, peer_1);
+        tx_manager.peers.insert(peer_id_2, peer_2);
+
+        // both peers are busy, only peer_1 opts in to blob prefill
+        tx_manager
+            .transaction_fetcher
+            .active_peers
+            .insert(peer_id_1, max_inflight_requests_per_peer);
+        tx_manager
+            .transaction_fetcher
+            .active_peers
+            .insert(peer_id_2, max_inflight_requests_per_peer);
+        tx_manager
+            .on_command(TransactionsCommand::SetBlobPrefill { peer_id: peer_id_1, enabled: true });
+
+        let blob_hash_1 = B256::from_slice(&[1; 32]);
+        let blob_hash_2 = B256::from_slice(&[2; 32]);
+        let eip1559_hash = B256::from_slice(&[3; 32]);
+
+        let msg = NewPooledTransactionHashes::Eth68(NewPooledTransactionHashes68 {
+            types: vec![EIP4844_TX_TYPE_ID, 2],
+            sizes: vec![131_300, 150],
+            hashes: vec![blob_hash_1, eip1559_hash],
+        });
+        tx_manager.on_new_pooled_transaction_hashes(peer_id_1, msg);
+
+        // blob hash is requested from peer_1 right away, the other hash is buffered
+        let req = to_mock_session_rx_1
+            .try_recv()
+            .expect("peer_1 session should receive request for blob hash");
+        let PeerRequest::GetPooledTransactions { request, .. } = req else { unreachable!() };
+        assert_eq!(request.0, vec![blob_hash_1]);
+        assert_eq!(tx_manager.transaction_fetcher.num_pending_hashes(), 1);
+
+        let msg = NewPooledTransactionHashes::Eth68(NewPooledTransactionHashes68 {
+            types: vec![EIP4844_TX_TYPE_ID],
+            sizes: vec![131_300],
+            hashes: vec![blob_hash_2],
+        });
+        tx_manager.on_new_pooled_transaction_hashes(peer_id_2, msg);
+
+        // peer_2 didn't opt in, so its blob hash is buffered
+        assert!(to_mock_session_rx_2.try_recv().is_err());
+        assert_eq!(tx_manager.transaction_fetcher.num_pending_hashes(), 2);
+    }
+
     #[test]
     fn test_transaction_builder_empty() {
         let mut builder =
@@ -2762,7 +2982,7 @@ mod tests {
     #[test]
     fn test_transaction_builder_large() {
         let mut builder =
-            PropagateTransactionsBuilder::<TransactionSigned>::full(EthVersion::Eth68);
+            PropagateTransactionsBuilder::<TransactionSigned>::full(EthVersion::Eth68, usize::MAX);
         assert!(builder.is_empty());
 
         let mut factory = MockTransactionFactory::default();
@@ -2788,10 +3008,36 @@ mod tests {
         assert_eq!(txs.len(), 1);
     }
 
+    #[test]
+    fn test_transaction_builder_announce_above_size() {
+        let mut builder = PropagateTransactionsBuilder::<TransactionSigned>::full(
+            EthVersion::Eth68,
+            DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
+        );
+
+        let mut factory = MockTransactionFactory::default();
+        let mut tx = factory.create_eip1559();
+        tx.transaction.set_size(DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST);
+        builder.push(&PropagateTransaction::pool_tx(Arc::new(tx)));
+
+        let mut tx = factory.create_eip1559();
+        tx.transaction.set_size(DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST + 1);
+        let large = PropagateTransaction::pool_tx(Arc::new(tx));
+        builder.push(&large);
+
+        let txs = builder.build();
+        assert_eq!(txs.full.unwrap().len(), 1);
+        let pooled = txs.pooled.unwrap();
+        assert_eq!(pooled.len(), 1);
+        assert!(pooled.iter_hashes().any(|hash| hash == large.tx_hash()));
+    }
+
     #[test]
     fn test_transaction_builder_eip4844() {
-        let mut builder =
-            PropagateTransactionsBuilder::<TransactionSigned>::full(EthVersion::Eth68);
+        let mut builder = PropagateTransactionsBuilder::<TransactionSigned>::full(
+            EthVersion::Eth68,
+            DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
+        );
         assert!(builder.is_empty());
 
         let mut factory = MockTransactionFactory::default();
@@ -2867,6 +3113,92 @@ mod tests {
         assert!(propagated.0.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_announce_hashes_before_active_session() {
+        reth_tracing::init_test_tracing();
+
+        let (mut tx_manager, network) = new_tx_manager().await;
+        network.handle().update_sync_state(SyncState::Idle);
+
+        let active_session = |peer_id| {
+            let (tx, _rx) = mpsc::channel::<PeerRequest>(1);
+            let info = SessionInfo {
+                peer_id,
+                remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+                client_version: Arc::from(""),
+                capabilities: Arc::new(vec![].into()),
+                status: Arc::new(Default::default()),
+                version: EthVersion::Eth68,
+                peer_kind: PeerKind::Basic,
+            };
+            NetworkEvent::ActivePeerSession { info, messages: PeerRequestSender::new(peer_id, tx) }
+        };
+
+        let pooled = MockTransaction::eip1559();
+        let pooled_hash = *pooled.get_hash();
+        tx_manager
+            .pool
+            .add_transaction(reth_transaction_pool::TransactionOrigin::External, pooled.clone())
+            .await
+            .unwrap();
+
+        // the session of peer 1 is active and the pooled transaction is announced on connect
+        let peer_id_1 = PeerId::random();
+        tx_manager.on_network_event(active_session(peer_id_1));
+
+        let new = pooled.next();
+        let new_hash = *new.get_hash();
+        tx_manager
+            .pool
+            .add_transaction(reth_transaction_pool::TransactionOrigin::External, new)
+            .await
+            .unwrap();
+
+        let mut pooled_events = tx_manager.pool.transaction_event_listener(pooled_hash).unwrap();
+        let mut new_events = tx_manager.pool.transaction_event_listener(new_hash).unwrap();
+
+        // only the transaction that wasn't announced on connect is announced again
+        tx_manager.on_command(TransactionsCommand::AnnounceHashesTo(
+            vec![pooled_hash, new_hash],
+            peer_id_1,
+        ));
+        assert!(pooled_events.next().now_or_never().is_none());
+        assert!(matches!(
+            new_events.next().now_or_never(),
+            Some(Some(TransactionEvent::Propagated(kinds)))
+                if kinds.as_slice() == [PropagateKind::Hash(peer_id_1)]
+        ));
+
+        // the session of peer 2 is not active yet, so the hashes are queued
+        let peer_id_2 = PeerId::random();
+        tx_manager.on_command(TransactionsCommand::AnnounceHashesTo(
+            vec![pooled_hash, new_hash],
+            peer_id_2,
+        ));
+        assert_eq!(
+            tx_manager.pending_session_announcements.get(&peer_id_2),
+            Some(&vec![pooled_hash, new_hash])
+        );
+
+        // both transactions are announced on connect, so the queued hashes aren't sent twice
+        tx_manager.on_network_event(active_session(peer_id_2));
+        assert!(tx_manager.pending_session_announcements.is_empty());
+        let peer = tx_manager.peers.get(&peer_id_2).unwrap();
+        assert!(peer.seen_transactions.contains(&pooled_hash));
+        assert!(peer.seen_transactions.contains(&new_hash));
+        assert!(pooled_events.next().now_or_never().is_none());
+        assert!(new_events.next().now_or_never().is_none());
+
+        // queued hashes are dropped if the session closes before it's active
+        let peer_id_3 = PeerId::random();
+        tx_manager.on_command(TransactionsCommand::AnnounceHashesTo(vec![new_hash], peer_id_3));
+        tx_manager.on_network_event(NetworkEvent::Peer(PeerEvent::SessionClosed {
+            peer_id: peer_id_3,
+            reason: None,
+        }));
+        assert!(tx_manager.pending_session_announcements.is_empty());
+    }
+
     #[tokio::test]
     async fn test_relaxed_filter_ignores_unknown_tx_types() {
         reth_tracing::init_test_tracing();
@@ -0,0 +1,406 @@
+//! Mempool sync sub-protocol that lets newly connected peers exchange summaries of their pools.
+//!
+//! When a connection is established, both peers send a [`PoolSummary`] that contains the hash
+//! prefixes of all transactions in their pool that are allowed to be propagated. Each peer then
+//! announces the hashes of the transactions the other peer is missing via the regular
+//! `NewPooledTransactionHashes` message of the `eth` protocol, which the other peer fetches like
+//! any other announced transaction.
+//!
+//! This allows a restarted node to repopulate its pool from its peers right away, instead of
+//! waiting for new transactions to be gossiped.
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    transactions::TransactionsHandle,
+    NetworkHandle,
+};
+use alloy_primitives::{bytes::BytesMut, Bytes, TxHash};
+use alloy_rlp::{Decodable, Encodable};
+use futures::{Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol, Capability,
+    NetworkPrimitives,
+};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::sync::SyncStateProvider;
+use reth_transaction_pool::TransactionPool;
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tracing::{debug, trace};
+
+/// Number of bytes of a transaction hash that are included in a [`PoolSummary`].
+pub const HASH_PREFIX_LEN: usize = 4;
+
+/// Maximum number of hash prefixes in a [`PoolSummary`].
+///
+/// Transactions beyond this limit are not included in the summary of the pool, and summaries of
+/// peers that exceed it are rejected.
+pub const MAX_POOL_SUMMARY_PREFIXES: usize = 64 * 1024;
+
+/// Message ID of the [`PoolSummary`] message.
+const POOL_SUMMARY_MESSAGE_ID: u8 = 0x00;
+
+/// Returns the `poolsync/1` capability.
+pub const fn pool_sync_protocol() -> Protocol {
+    Protocol::new(Capability::new_static("poolsync", 1), 1)
+}
+
+/// A compact summary of the transactions in a pool.
+///
+/// The summary consists of the sorted [`HASH_PREFIX_LEN`] byte prefixes of the transaction hashes.
+/// Since only prefixes are compared, a transaction the peer is missing is not announced to it if
+/// its prefix collides with one of the peer's transactions. Such transactions still reach the peer
+/// through regular gossip.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolSummary {
+    /// Sorted and deduplicated hash prefixes.
+    prefixes: Vec<[u8; HASH_PREFIX_LEN]>,
+}
+
+impl PoolSummary {
+    /// Creates the summary of the given transaction hashes.
+    ///
+    /// Only the first [`MAX_POOL_SUMMARY_PREFIXES`] hashes are included.
+    pub fn new(hashes: impl IntoIterator<Item = TxHash>) -> Self {
+        Self::from_prefixes(
+            hashes.into_iter().take(MAX_POOL_SUMMARY_PREFIXES).map(|hash| hash_prefix(&hash)),
+        )
+    }
+
+    fn from_prefixes(prefixes: impl IntoIterator<Item = [u8; HASH_PREFIX_LEN]>) -> Self {
+        let mut prefixes = prefixes.into_iter().collect::<Vec<_>>();
+        prefixes.sort_unstable();
+        prefixes.dedup();
+        Self { prefixes }
+    }
+
+    /// Returns the number of hash prefixes in the summary.
+    pub const fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// Returns `true` if the summary is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// Returns `true` if the summary contains the prefix of the given hash.
+    pub fn contains(&self, hash: &TxHash) -> bool {
+        self.prefixes.binary_search(&hash_prefix(hash)).is_ok()
+    }
+
+    /// Returns all given hashes that are not contained in the summary.
+    pub fn missing(&self, hashes: impl IntoIterator<Item = TxHash>) -> Vec<TxHash> {
+        hashes.into_iter().filter(|hash| !self.contains(hash)).collect()
+    }
+
+    /// Encodes the summary as message, including the message ID.
+    pub fn encode_message(&self) -> BytesMut {
+        let prefixes = Bytes::from(self.prefixes.concat());
+        let mut buf = BytesMut::with_capacity(1 + prefixes.length());
+        buf.extend_from_slice(&[POOL_SUMMARY_MESSAGE_ID]);
+        prefixes.encode(&mut buf);
+        buf
+    }
+
+    /// Decodes the summary from a message, including the message ID.
+    pub fn decode_message(msg: &[u8]) -> Result<Self, PoolSyncError> {
+        let Some((&id, mut payload)) = msg.split_first() else {
+            return Err(PoolSyncError::EmptyMessage)
+        };
+        if id != POOL_SUMMARY_MESSAGE_ID {
+            return Err(PoolSyncError::UnknownMessage(id))
+        }
+
+        let prefixes = Bytes::decode(&mut payload)?;
+        if prefixes.len() % HASH_PREFIX_LEN != 0 {
+            return Err(PoolSyncError::InvalidSummaryLength(prefixes.len()))
+        }
+        let len = prefixes.len() / HASH_PREFIX_LEN;
+        if len > MAX_POOL_SUMMARY_PREFIXES {
+            return Err(PoolSyncError::SummaryTooLarge(len))
+        }
+
+        Ok(Self::from_prefixes(
+            prefixes.chunks_exact(HASH_PREFIX_LEN).map(|chunk| chunk.try_into().unwrap()),
+        ))
+    }
+}
+
+/// Errors when decoding `poolsync` messages.
+#[derive(Debug, thiserror::Error)]
+pub enum PoolSyncError {
+    /// The message is empty.
+    #[error("empty message")]
+    EmptyMessage,
+    /// The message ID is unknown.
+    #[error("unknown message id {0}")]
+    UnknownMessage(u8),
+    /// The hash prefixes of the summary are not a multiple of [`HASH_PREFIX_LEN`].
+    #[error("invalid summary length {0}")]
+    InvalidSummaryLength(usize),
+    /// The summary contains more than [`MAX_POOL_SUMMARY_PREFIXES`] hash prefixes.
+    #[error("summary with {0} hash prefixes exceeds the limit")]
+    SummaryTooLarge(usize),
+    /// The message could not be decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+}
+
+/// The [`ProtocolHandler`] of the `poolsync/1` protocol.
+///
+/// The protocol is not offered if transaction gossip is disabled.
+#[derive(Debug, Clone)]
+pub struct PoolSyncProtocolHandler<Pool, N: NetworkPrimitives> {
+    /// The transaction pool to summarize.
+    pool: Pool,
+    /// Handle to the network.
+    network: NetworkHandle<N>,
+    /// Handle to the [`TransactionsManager`](super::TransactionsManager) that announces the
+    /// transactions missing in a peer's pool.
+    transactions: TransactionsHandle<N>,
+}
+
+impl<Pool, N: NetworkPrimitives> PoolSyncProtocolHandler<Pool, N> {
+    /// Creates a new protocol handler.
+    pub const fn new(
+        pool: Pool,
+        network: NetworkHandle<N>,
+        transactions: TransactionsHandle<N>,
+    ) -> Self {
+        Self { pool, network, transactions }
+    }
+}
+
+impl<Pool, N> PoolSyncProtocolHandler<Pool, N>
+where
+    Pool: Clone,
+    N: NetworkPrimitives,
+{
+    fn connection_handler(&self) -> Option<PoolSyncConnectionHandler<Pool, N>> {
+        if self.network.tx_gossip_disabled() {
+            return None
+        }
+        Some(PoolSyncConnectionHandler {
+            pool: self.pool.clone(),
+            network: self.network.clone(),
+            transactions: self.transactions.clone(),
+        })
+    }
+}
+
+impl<Pool, N> ProtocolHandler for PoolSyncProtocolHandler<Pool, N>
+where
+    Pool: TransactionPool + Unpin + 'static,
+    N: NetworkPrimitives,
+{
+    type ConnectionHandler = PoolSyncConnectionHandler<Pool, N>;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+}
+
+/// The [`ConnectionHandler`] of the `poolsync/1` protocol.
+#[derive(Debug)]
+pub struct PoolSyncConnectionHandler<Pool, N: NetworkPrimitives> {
+    /// The transaction pool to summarize.
+    pool: Pool,
+    /// Handle to the network.
+    network: NetworkHandle<N>,
+    /// Handle to the [`TransactionsManager`](super::TransactionsManager).
+    transactions: TransactionsHandle<N>,
+}
+
+impl<Pool, N> ConnectionHandler for PoolSyncConnectionHandler<Pool, N>
+where
+    Pool: TransactionPool + Unpin + 'static,
+    N: NetworkPrimitives,
+{
+    type Connection = PoolSyncConnection<Pool, N>;
+
+    fn protocol(&self) -> Protocol {
+        pool_sync_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        // the pool is of no use to peers while the node is syncing
+        let syncing = self.network.is_initially_syncing();
+        let summary = (!syncing).then(|| PoolSummary::new(self.pool.pooled_transaction_hashes()));
+        trace!(target: "net::tx::pool_sync", %peer_id, summary_len=?summary.as_ref().map(PoolSummary::len), "Established pool sync connection");
+
+        PoolSyncConnection {
+            peer_id,
+            conn,
+            pool: self.pool,
+            network: self.network,
+            transactions: self.transactions,
+            pending_summary: summary,
+            received_summary: false,
+        }
+    }
+}
+
+/// A `poolsync/1` connection to a peer.
+///
+/// The connection first sends the summary of the local pool. Once the summary of the peer is
+/// received, the transactions it is missing are announced to it. The connection stays idle
+/// afterwards.
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled."]
+pub struct PoolSyncConnection<Pool, N: NetworkPrimitives> {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// The raw connection to the peer.
+    conn: ProtocolConnection,
+    /// The transaction pool to compare the peer's summary against.
+    pool: Pool,
+    /// Handle to the network.
+    network: NetworkHandle<N>,
+    /// Handle to the [`TransactionsManager`](super::TransactionsManager).
+    transactions: TransactionsHandle<N>,
+    /// The summary of the local pool that has yet to be sent.
+    pending_summary: Option<PoolSummary>,
+    /// Whether the summary of the peer was already received.
+    received_summary: bool,
+}
+
+impl<Pool, N> PoolSyncConnection<Pool, N>
+where
+    Pool: TransactionPool,
+    N: NetworkPrimitives,
+{
+    /// Announces the transactions of the local pool that are missing in the peer's pool.
+    fn on_summary(&mut self, summary: PoolSummary) {
+        if self.received_summary {
+            trace!(target: "net::tx::pool_sync", peer_id=%self.peer_id, "Ignoring repeated pool summary");
+            return
+        }
+        self.received_summary = true;
+
+        if self.network.is_initially_syncing() {
+            return
+        }
+
+        let missing = summary.missing(self.pool.pooled_transaction_hashes());
+        debug!(target: "net::tx::pool_sync", peer_id=%self.peer_id, summary_len=summary.len(), missing=missing.len(), "Received pool summary");
+
+        // the summary can arrive before the session is active in the transactions manager, and
+        // hashes already announced on connect are skipped
+        self.transactions.announce_hashes_to(missing, self.peer_id);
+    }
+}
+
+impl<Pool, N> Stream for PoolSyncConnection<Pool, N>
+where
+    Pool: TransactionPool + Unpin,
+    N: NetworkPrimitives,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(summary) = this.pending_summary.take() {
+            return Poll::Ready(Some(summary.encode_message()))
+        }
+
+        loop {
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            match PoolSummary::decode_message(&msg) {
+                Ok(summary) => this.on_summary(summary),
+                Err(err) => {
+                    // disconnect peers that send malformed messages
+                    debug!(target: "net::tx::pool_sync", peer_id=%this.peer_id, %err, "Failed to decode pool sync message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the prefix of the hash that is included in a [`PoolSummary`].
+fn hash_prefix(hash: &TxHash) -> [u8; HASH_PREFIX_LEN] {
+    hash.0[..HASH_PREFIX_LEN].try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn summary_roundtrip() {
+        let hashes = (0..100u32).map(|i| B256::right_padding_from(&i.to_be_bytes()));
+        let summary = PoolSummary::new(hashes);
+        assert_eq!(summary.len(), 100);
+
+        let encoded = summary.encode_message();
+        assert_eq!(encoded[0], POOL_SUMMARY_MESSAGE_ID);
+        assert_eq!(PoolSummary::decode_message(&encoded).unwrap(), summary);
+    }
+
+    #[test]
+    fn summary_missing_hashes() {
+        let known = B256::repeat_byte(0x11);
+        let colliding = B256::right_padding_from(&[0x11; HASH_PREFIX_LEN]);
+        let unknown = B256::repeat_byte(0x22);
+        assert_eq!(hash_prefix(&known), hash_prefix(&colliding));
+
+        let summary = PoolSummary::new([known]);
+        assert!(summary.contains(&known));
+        assert!(!summary.contains(&unknown));
+        assert_eq!(summary.missing([known, colliding, unknown]), vec![unknown]);
+    }
+
+    #[test]
+    fn reject_invalid_summaries() {
+        assert!(matches!(PoolSummary::decode_message(&[]), Err(PoolSyncError::EmptyMessage)));
+        assert!(matches!(
+            PoolSummary::decode_message(&[0x01, 0x80]),
+            Err(PoolSyncError::UnknownMessage(0x01))
+        ));
+
+        let mut msg = BytesMut::new();
+        msg.extend_from_slice(&[POOL_SUMMARY_MESSAGE_ID]);
+        Bytes::from_static(&[0; HASH_PREFIX_LEN + 1]).encode(&mut msg);
+        assert!(matches!(
+            PoolSummary::decode_message(&msg),
+            Err(PoolSyncError::InvalidSummaryLength(5))
+        ));
+
+        let mut msg = BytesMut::new();
+        msg.extend_from_slice(&[POOL_SUMMARY_MESSAGE_ID]);
+        Bytes::from(vec![0; (MAX_POOL_SUMMARY_PREFIXES + 1) * HASH_PREFIX_LEN]).encode(&mut msg);
+        assert!(matches!(
+            PoolSummary::decode_message(&msg),
+            Err(PoolSyncError::SummaryTooLarge(_))
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Transports the network can establish peer connections over.
+
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU16, Ordering},
+        LazyLock,
+    },
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf},
+    net::TcpStream,
+    sync::mpsc,
+};
+
+/// Size of the buffer of each direction of an in-memory connection.
+const MEMORY_CONNECTION_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Listeners of all networks of this process that use [`Transport::Memory`], by port.
+static MEMORY_LISTENERS: LazyLock<Mutex<HashMap<u16, mpsc::UnboundedSender<MemoryConnection>>>> =
+    LazyLock::new(Default::default);
+
+/// The next port to hand out for in-memory addresses.
+static NEXT_MEMORY_PORT: AtomicU16 = AtomicU16::new(1);
+
+/// An accepted in-memory connection and the address of the dialer.
+type MemoryConnection = (PeerStream, SocketAddr);
+
+/// The transport used to establish peer connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// Peers are connected over TCP.
+    #[default]
+    Tcp,
+    /// Peers are connected over in-process pipes.
+    ///
+    /// This can only connect to networks of the same process that also use this transport, which
+    /// is useful to run several nodes in one test without touching the host network. Listener
+    /// addresses are never bound: a network is identified by the port of its listener address
+    /// alone, and a listener port of `0` is replaced with a port that is unique in this process.
+    ///
+    /// Discovery is not affected by this and should be disabled.
+    Memory,
+}
+
+impl Transport {
+    /// Opens a connection to the peer listening on the given address.
+    pub(crate) async fn connect(self, remote_addr: SocketAddr) -> io::Result<PeerStream> {
+        match self {
+            Self::Tcp => {
+                let stream = TcpStream::connect(remote_addr).await?;
+                if let Err(err) = stream.set_nodelay(true) {
+                    tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
+                }
+                Ok(PeerStream::Tcp(stream))
+            }
+            Self::Memory => {
+                let listener = MEMORY_LISTENERS.lock().get(&remote_addr.port()).cloned();
+                let Some(listener) = listener else {
+                    return Err(io::ErrorKind::ConnectionRefused.into())
+                };
+                let local_addr =
+                    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), next_memory_port());
+                let (local, remote) = tokio::io::duplex(MEMORY_CONNECTION_BUFFER_SIZE);
+                listener
+                    .send((
+                        PeerStream::Memory { stream: remote, local_addr: remote_addr },
+                        local_addr,
+                    ))
+                    .map_err(|_| io::Error::from(io::ErrorKind::ConnectionRefused))?;
+                Ok(PeerStream::Memory { stream: local, local_addr })
+            }
+        }
+    }
+}
+
+/// A connection to a peer over one of the [`Transport`]s.
+#[derive(Debug)]
+pub enum PeerStream {
+    /// A TCP connection.
+    Tcp(TcpStream),
+    /// An in-memory connection.
+    Memory {
+        /// This end of the connection.
+        stream: DuplexStream,
+        /// The address of this end of the connection.
+        local_addr: SocketAddr,
+    },
+}
+
+impl PeerStream {
+    /// Returns the local address of the connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Self::Tcp(stream) => stream.local_addr(),
+            Self::Memory { local_addr, .. } => Ok(*local_addr),
+        }
+    }
+}
+
+impl From<TcpStream> for PeerStream {
+    fn from(stream: TcpStream) -> Self {
+        Self::Tcp(stream)
+    }
+}
+
+impl AsyncRead for PeerStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            Self::Memory { stream, .. } => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for PeerStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            Self::Memory { stream, .. } => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            Self::Memory { stream, .. } => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            Self::Memory { stream, .. } => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Accepts in-memory connections dialed via [`Transport::Memory`].
+///
+/// The listener is reachable until it is dropped.
+#[derive(Debug)]
+pub(crate) struct MemoryListener {
+    /// The address the listener is reachable at.
+    local_address: SocketAddr,
+    /// Incoming connections.
+    incoming: mpsc::UnboundedReceiver<MemoryConnection>,
+}
+
+impl MemoryListener {
+    /// Registers a listener for the given address.
+    ///
+    /// If the port is `0`, an unused port is picked.
+    pub(crate) fn bind(mut addr: SocketAddr) -> io::Result<Self> {
+        let mut listeners = MEMORY_LISTENERS.lock();
+        if addr.port() == 0 {
+            let port = std::iter::repeat_with(next_memory_port)
+                .take(u16::MAX as usize)
+                .find(|port| !listeners.contains_key(port))
+                .ok_or_else(|| io::Error::from(io::ErrorKind::AddrNotAvailable))?;
+            addr.set_port(port);
+        } else if listeners.contains_key(&addr.port()) {
+            return Err(io::ErrorKind::AddrInUse.into())
+        }
+
+        let (tx, incoming) = mpsc::unbounded_channel();
+        listeners.insert(addr.port(), tx);
+        Ok(Self { local_address: addr, incoming })
+    }
+
+    /// Returns the address the listener is reachable at.
+    pub(crate) const fn local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    /// Polls for the next incoming connection.
+    pub(crate) fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<Option<MemoryConnection>> {
+        self.incoming.poll_recv(cx)
+    }
+}
+
+impl Drop for MemoryListener {
+    fn drop(&mut self) {
+        MEMORY_LISTENERS.lock().remove(&self.local_address.port());
+    }
+}
+
+/// Returns the next non-zero in-memory port.
+fn next_memory_port() -> u16 {
+    loop {
+        let port = NEXT_MEMORY_PORT.fetch_add(1, Ordering::Relaxed);
+        if port != 0 {
+            return port
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn memory_transport_connects() {
+        let mut listener =
+            MemoryListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0)).unwrap();
+        let listener_addr = listener.local_address();
+        assert_ne!(listener_addr.port(), 0);
+
+        // the ip of the listener address is irrelevant
+        let dial_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listener_addr.port());
+        let mut outgoing = Transport::Memory.connect(dial_addr).await.unwrap();
+        let (mut incoming, remote_addr) =
+            std::future::poll_fn(|cx| listener.poll_accept(cx)).await.unwrap();
+        assert_eq!(outgoing.local_addr().unwrap(), remote_addr);
+
+        outgoing.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        incoming.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+
+        assert_eq!(
+            MemoryListener::bind(listener_addr).unwrap_err().kind(),
+            io::ErrorKind::AddrInUse
+        );
+        drop(listener);
+        assert_eq!(
+            Transport::Memory.connect(dial_addr).await.unwrap_err().kind(),
+            io::ErrorKind::ConnectionRefused
+        );
+    }
+}
@@ -7,6 +7,7 @@ use reth_discv4::Discv4Config;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, HeadersDirection};
 use reth_network::{
     test_utils::{NetworkEventStream, PeerConfig, Testnet},
+    transport::Transport,
     BlockDownloaderProvider, NetworkConfigBuilder, NetworkEvent, NetworkEventListenerProvider,
     NetworkManager, PeersConfig,
 };
@@ -87,6 +88,21 @@ async fn test_establish_connections() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_establish_memory_connections() {
+    reth_tracing::init_test_tracing();
+    let mut net = Testnet::default();
+    let configs = (0..3).map(|_| PeerConfig::default().with_transport(Transport::Memory));
+    net.extend_peer_with_config(configs).await.unwrap();
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    for peer in handle.peers() {
+        assert_eq!(peer.network().num_connected_peers(), 2);
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_already_connected() {
     reth_tracing::init_test_tracing();
@@ -55,6 +55,40 @@ async fn test_tx_gossip() {
     assert_eq!(received, hash);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_forced_tx_propagation() {
+    reth_tracing::init_test_tracing();
+
+    let provider = MockEthProvider::default();
+    let net = Testnet::create_with(2, provider.clone()).await;
+
+    let net = net.with_eth_pool();
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let peer0 = &handle.peers()[0];
+    let peer1 = &handle.peers()[1];
+
+    let peer0_pool = peer0.pool().unwrap();
+    let peer1_pool = peer1.pool().unwrap();
+    let mut peer1_tx_listener = peer1_pool.pending_transactions_listener();
+
+    let mut tx_gen = TransactionGenerator::new(rand::rng());
+    let tx = tx_gen.gen_eip1559_pooled();
+    provider.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(100_000_000)));
+
+    let AddedTransactionOutcome { hash, .. } =
+        peer0_pool.add_external_transaction(tx).await.unwrap();
+    assert_eq!(peer1_tx_listener.recv().await.unwrap(), hash);
+
+    // peer1 dropped the transaction, but peer0 still considers it seen by peer1, so only a forced
+    // propagation sends it again
+    peer1_pool.remove_transactions(vec![hash]);
+    peer0.transactions().unwrap().propagate_transactions_forced(vec![hash]);
+
+    assert_eq!(peer1_tx_listener.recv().await.unwrap(), hash);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_tx_propagation_policy_trusted_only() {
     reth_tracing::init_test_tracing();
@@ -12,7 +12,7 @@ use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::FullProvider;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{shutdown::GracefulShutdown, TaskExecutor};
 use reth_tokio_util::EventSender;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use std::{fmt::Debug, future::Future, marker::PhantomData};
@@ -118,6 +118,11 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Shutdown signal that fires once the engine has been drained during the node's shutdown.
+    ///
+    /// Add-ons that write in-memory state to disk on shutdown should wait for this signal instead
+    /// of the task executor's shutdown signal.
+    pub flush_signal: GracefulShutdown,
 }
 
 /// Customizable node add-on types.
@@ -8,9 +8,11 @@ use crate::{
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     BlockReaderFor, DebugNode, DebugNodeLauncher, EngineNodeLauncher, LaunchNode, Node,
+    ShutdownCoordinator,
 };
 use alloy_eips::eip4844::env_settings::EnvKzgSettings;
-use futures::Future;
+use eyre::WrapErr;
+use futures::{Future, StreamExt};
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
@@ -35,7 +37,9 @@ use reth_provider::{
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    PoolConfig, PoolEvent, PoolTransaction, SequencedPoolEvent, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::{fmt::Debug, sync::Arc};
 use tracing::{info, trace, warn};
@@ -233,6 +237,11 @@ impl<DB, ChainSpec: EthChainSpec> NodeBuilder<DB, ChainSpec> {
     /// Preconfigure the builder with the context to launch the node.
     ///
     /// This provides the task executor and the data directory for the node.
+    ///
+    /// Multiple nodes, e.g. a rollup node and its L1 node, can be co-hosted in one process by
+    /// launching them with the same task executor. Their configs should be made
+    /// [`co_hosted`](NodeConfig::co_hosted) with distinct instances, so that they don't share
+    /// ports or data directories.
     pub const fn with_launch_context(self, task_executor: TaskExecutor) -> WithLaunchContext<Self> {
         WithLaunchContext { builder: self, task_executor }
     }
@@ -261,6 +270,7 @@ impl<DB, ChainSpec: EthChainSpec> NodeBuilder<DB, ChainSpec> {
         let path = reth_node_core::dirs::MaybePlatformPath::<DataDirPath>::from(datadir.into());
         self.config = self.config.with_datadir_args(reth_node_core::args::DatadirArgs {
             datadir: path.clone(),
+            namespace: self.config.datadir.namespace.clone(),
             ..Default::default()
         });
 
@@ -701,6 +711,8 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) provider: Node::Provider,
     /// The executor of the node.
     pub(crate) executor: TaskExecutor,
+    /// Coordinates the graceful shutdown of the node.
+    pub(crate) shutdown: ShutdownCoordinator,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
 }
@@ -711,9 +723,10 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
+        shutdown: ShutdownCoordinator,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self { head, provider, executor, shutdown, config_container }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -743,6 +756,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         &self.executor
     }
 
+    /// Returns the coordinator of the node's graceful shutdown.
+    ///
+    /// Components that write state to disk on shutdown should wait for
+    /// [`ShutdownCoordinator::flush_signal`].
+    pub const fn shutdown(&self) -> &ShutdownCoordinator {
+        &self.shutdown
+    }
+
     /// Returns the chain spec of the node.
     pub fn chain_spec(&self) -> Arc<<Node::Types as NodeTypes>::ChainSpec> {
         self.provider().chain_spec()
@@ -821,10 +842,39 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         Node::Provider: BlockReaderFor<N>,
         Policy: TransactionPropagationPolicy + Debug,
     {
-        let (handle, network, txpool, eth) = builder
-            .transactions_with_policy(pool, tx_config, propagation_policy)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+        let mut builder = builder;
+        if self.config().network.enable_snap_server {
+            let snap = builder
+                .snap_request_handler(self.provider().clone(), Box::new(self.executor.clone()));
+            self.executor.spawn_critical("p2p snap request handler", Box::pin(snap));
+        }
+
+        // local transactions that remain pending are announced again to all peers
+        let rebroadcasts =
+            self.config().txpool.local_rebroadcast_blocks.is_some().then(|| pool.pool_events());
+
+        let mut builder = builder.transactions_with_policy(pool, tx_config, propagation_policy);
+        if self.config().network.enable_pool_sync {
+            builder = builder.pool_sync();
+        }
+
+        let (handle, network, txpool, eth) =
+            builder.request_handler(self.provider().clone()).split_with_handle();
+
+        if let Some(mut rebroadcasts) = rebroadcasts {
+            let transactions = txpool.handle();
+            self.executor.spawn(Box::pin(async move {
+                while let Some(event) = rebroadcasts.next().await {
+                    if let Ok(SequencedPoolEvent {
+                        event: PoolEvent::Rebroadcast { tx_hash, .. },
+                        ..
+                    }) = event
+                    {
+                        transactions.propagate_transactions_forced(vec![tx_hash]);
+                    }
+                }
+            }));
+        }
 
         self.executor.spawn_critical("p2p txpool", Box::pin(txpool));
         self.executor.spawn_critical("p2p eth request handler", Box::pin(eth));
@@ -902,7 +952,7 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
     {
         let secret_key = self.network_secret(&self.config().datadir())?;
         let default_peers_path = self.config().datadir().known_peers();
-        let builder = self
+        let mut builder = self
             .config()
             .network
             .network_config(
@@ -914,6 +964,12 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
             .with_task_executor(Box::new(self.executor.clone()))
             .set_head(self.head);
 
+        if let Some(capture) =
+            self.config().network.protocol_capture().wrap_err("failed to start the p2p capture")?
+        {
+            builder = builder.protocol_capture(capture);
+        }
+
         Ok(builder)
     }
 }
@@ -924,6 +980,7 @@ impl<Node: FullNodeTypes> std::fmt::Debug for BuilderContext<Node> {
             .field("head", &self.head)
             .field("provider", &std::any::type_name::<Node::Provider>())
             .field("executor", &self.executor)
+            .field("shutdown", &self.shutdown)
             .field("config", &self.config())
             .finish()
     }
@@ -1,9 +1,12 @@
 //! Consensus component for the node builder.
-use reth_consensus::{ConsensusError, FullConsensus};
-use reth_node_api::PrimitivesTy;
+use reth_consensus::{
+    hooks::{HeaderValidationHook, HookedConsensus},
+    ConsensusError, FullConsensus,
+};
+use reth_node_api::{HeaderTy, PrimitivesTy};
 
 use crate::{BuilderContext, FullNodeTypes};
-use std::future::Future;
+use std::{future::Future, sync::Arc};
 
 /// A type that knows how to build the consensus implementation.
 pub trait ConsensusBuilder<Node: FullNodeTypes>: Send {
@@ -37,3 +40,40 @@ where
         self(ctx)
     }
 }
+
+/// A [`ConsensusBuilder`] that wraps the consensus built by another [`ConsensusBuilder`] in a
+/// [`HookedConsensus`], applying the registered [`HeaderValidationHook`]s to every header the node
+/// validates.
+#[derive(Debug, Clone)]
+pub struct HookedConsensusBuilder<CB, H> {
+    /// The builder of the wrapped consensus.
+    inner: CB,
+    /// The hooks to register with the consensus.
+    hooks: Vec<Arc<dyn HeaderValidationHook<H>>>,
+}
+
+impl<CB, H> HookedConsensusBuilder<CB, H> {
+    /// Creates a new [`HookedConsensusBuilder`] without any hooks.
+    pub const fn new(inner: CB) -> Self {
+        Self { inner, hooks: Vec::new() }
+    }
+
+    /// Registers a hook that is applied after the hooks registered before.
+    pub fn with_hook(mut self, hook: impl HeaderValidationHook<H> + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+}
+
+impl<Node, CB> ConsensusBuilder<Node> for HookedConsensusBuilder<CB, HeaderTy<Node::Types>>
+where
+    Node: FullNodeTypes,
+    CB: ConsensusBuilder<Node>,
+{
+    type Consensus = HookedConsensus<CB::Consensus, HeaderTy<Node::Types>>;
+
+    async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
+        let consensus = self.inner.build_consensus(ctx).await?;
+        Ok(self.hooks.into_iter().fold(HookedConsensus::new(consensus), HookedConsensus::with_hook))
+    }
+}
@@ -13,6 +13,7 @@ mod execute;
 mod network;
 mod payload;
 mod pool;
+mod precompiles;
 
 pub use builder::*;
 pub use consensus::*;
@@ -20,6 +21,7 @@ pub use execute::*;
 pub use network::*;
 pub use payload::*;
 pub use pool::*;
+pub use precompiles::*;
 
 use crate::{ConfigureEvm, FullNodeTypes};
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -2,7 +2,8 @@
 
 use alloy_primitives::Address;
 use reth_chain_state::CanonStateSubscriptions;
-use reth_node_api::TxTy;
+use reth_chainspec::EthereumHardforks;
+use reth_node_api::{NodeTypes, TxTy};
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
     TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
@@ -126,8 +127,9 @@ impl<'a, Node: FullNodeTypes, V> TxPoolBuilder<'a, Node, V> {
     }
 }
 
-impl<'a, Node: FullNodeTypes, V> TxPoolBuilder<'a, Node, TransactionValidationTaskExecutor<V>>
+impl<'a, Node, V> TxPoolBuilder<'a, Node, TransactionValidationTaskExecutor<V>>
 where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec: EthereumHardforks>>,
     V: TransactionValidator + Clone + 'static,
     V::Transaction:
         PoolTransaction<Consensus = TxTy<Node::Types>> + reth_transaction_pool::EthPoolTransaction,
@@ -209,9 +211,11 @@ where
                 transactions_path,
             );
 
-        ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+        // the backup is written once the engine stopped processing blocks during shutdown
+        let shutdown = ctx.shutdown().flush_signal();
+        ctx.task_executor().spawn_critical_with_shutdown_signal(
             "local transactions backup task",
-            |shutdown| {
+            |_| {
                 reth_transaction_pool::maintain::backup_local_transactions_task(
                     shutdown,
                     pool,
@@ -230,7 +234,7 @@ fn spawn_pool_maintenance_task<Node, Pool>(
     pool_config: &PoolConfig,
 ) -> eyre::Result<()>
 where
-    Node: FullNodeTypes,
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec: EthereumHardforks>>,
     Pool: reth_transaction_pool::TransactionPoolExt + Clone + 'static,
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
@@ -247,6 +251,8 @@ where
             reth_transaction_pool::maintain::MaintainPoolConfig {
                 max_tx_lifetime: pool_config.max_queued_lifetime,
                 no_local_exemptions: pool_config.local_transactions_config.no_exemptions,
+                local_rebroadcast_blocks: pool_config.local_rebroadcast_blocks,
+                local_tx_lifetime: pool_config.local_lifetime,
                 ..Default::default()
             },
         ),
@@ -262,7 +268,7 @@ fn spawn_maintenance_tasks<Node, Pool>(
     pool_config: &PoolConfig,
 ) -> eyre::Result<()>
 where
-    Node: FullNodeTypes,
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec: EthereumHardforks>>,
     Pool: reth_transaction_pool::TransactionPoolExt + Clone + 'static,
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
@@ -0,0 +1,193 @@
+//! Custom precompiles for the EVM of the node.
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address,
+};
+use reth_engine_tree::tree::precompile_cache::{CachedPrecompile, PrecompileCache};
+use reth_evm::{
+    precompiles::{DynPrecompile, PrecompileInput, PrecompilesMap},
+    revm::{inspector::NoOpInspector, precompile::PrecompileResult, Inspector},
+    Database, Evm, EvmEnv, EvmFactory,
+};
+use std::sync::Arc;
+
+/// Additional precompiles to register in the EVM of the node.
+///
+/// This allows chains that only differ from their base chain by a set of precompiles to reuse the
+/// existing EVM configuration instead of implementing their own [`EvmFactory`]:
+///
+/// ```ignore
+/// let precompiles = CustomPrecompiles::default()
+///     .with_precompile(address!("0x0000000000000000000000000000000000000100"), my_precompile)
+///     // cache the results of the KZG point evaluation precompile
+///     .with_cache(address!("0x000000000000000000000000000000000000000a"));
+///
+/// let evm_config = EthEvmConfig::new_with_evm_factory(
+///     ctx.chain_spec(),
+///     precompiles.evm_factory(EthEvmFactory::default()),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CustomPrecompiles {
+    /// The precompiles to register, in order of registration.
+    precompiles: Vec<(Address, DynPrecompile)>,
+    /// The addresses of the precompiles to cache the results of.
+    cached: HashSet<Address>,
+}
+
+impl CustomPrecompiles {
+    /// Registers a pure precompile at the given address.
+    ///
+    /// The output of the precompile must only depend on the input data and the gas limit. An
+    /// existing precompile at the same address is replaced.
+    pub fn with_precompile<F>(self, address: Address, precompile: F) -> Self
+    where
+        F: Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    {
+        self.with_dyn_precompile(
+            address,
+            DynPrecompile::new(move |input: PrecompileInput<'_>| precompile(input.data, input.gas)),
+        )
+    }
+
+    /// Registers a [`DynPrecompile`] at the given address.
+    ///
+    /// Unlike [`Self::with_precompile`], this can be used for precompiles that need access to the
+    /// EVM state. An existing precompile at the same address is replaced.
+    pub fn with_dyn_precompile(mut self, address: Address, precompile: DynPrecompile) -> Self {
+        self.precompiles.push((address, precompile));
+        self
+    }
+
+    /// Caches the results of the precompile at the given address.
+    ///
+    /// This can be used for custom precompiles and precompiles of the base chain alike, and is
+    /// only worth it for expensive precompiles, e.g. KZG point evaluation. Precompiles that are
+    /// not pure, see [`Precompile::is_pure`](reth_evm::precompiles::Precompile::is_pure), are never
+    /// cached.
+    pub fn with_cache(mut self, address: Address) -> Self {
+        self.cached.insert(address);
+        self
+    }
+
+    /// Returns true if no precompiles are registered or cached.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty() && self.cached.is_empty()
+    }
+
+    /// Returns an [`EvmFactory`] that installs these precompiles into every EVM created by the
+    /// given factory.
+    pub fn evm_factory<F>(self, inner: F) -> PrecompilesEvmFactory<F>
+    where
+        F: EvmFactory<Precompiles = PrecompilesMap>,
+    {
+        PrecompilesEvmFactory::new(inner, self)
+    }
+}
+
+/// An [`EvmFactory`] that registers [`CustomPrecompiles`] in the EVMs created by the inner
+/// factory.
+///
+/// The precompile caches are shared by all EVMs created by this factory and its clones.
+#[derive(Debug, Clone)]
+pub struct PrecompilesEvmFactory<F: EvmFactory> {
+    /// The factory to create the EVMs with.
+    inner: F,
+    /// The precompiles to register.
+    precompiles: Arc<Vec<(Address, DynPrecompile)>>,
+    /// The cache for each of the cached precompiles.
+    caches: Arc<HashMap<Address, PrecompileCache<F::Spec>>>,
+}
+
+impl<F: EvmFactory> PrecompilesEvmFactory<F> {
+    /// Creates a new factory that registers the given precompiles in the EVMs created by `inner`.
+    pub fn new(inner: F, precompiles: CustomPrecompiles) -> Self {
+        let CustomPrecompiles { precompiles, cached } = precompiles;
+        let caches = cached.into_iter().map(|address| (address, Default::default())).collect();
+        Self { inner, precompiles: Arc::new(precompiles), caches: Arc::new(caches) }
+    }
+
+    /// Returns the inner [`EvmFactory`].
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Registers the precompiles in the given map, using the caches for the given spec.
+    fn apply(&self, precompiles: &mut PrecompilesMap, spec: F::Spec) {
+        for (address, precompile) in self.precompiles.iter() {
+            let precompile = precompile.clone();
+            precompiles.apply_precompile(address, move |_| Some(precompile));
+        }
+
+        for (address, cache) in self.caches.iter() {
+            let cache = cache.clone();
+            precompiles.map_pure_precompiles(|precompile_address, precompile| {
+                if precompile_address == address {
+                    CachedPrecompile::wrap(precompile, cache.clone(), spec, None)
+                } else {
+                    precompile
+                }
+            });
+        }
+    }
+}
+
+impl<F> EvmFactory for PrecompilesEvmFactory<F>
+where
+    F: EvmFactory<Precompiles = PrecompilesMap>,
+{
+    type Evm<DB: Database, I: Inspector<Self::Context<DB>>> = F::Evm<DB, I>;
+    type Context<DB: Database> = F::Context<DB>;
+    type Tx = F::Tx;
+    type Error<DBError: core::error::Error + Send + Sync + 'static> = F::Error<DBError>;
+    type HaltReason = F::HaltReason;
+    type Spec = F::Spec;
+    type Precompiles = PrecompilesMap;
+
+    fn create_evm<DB: Database>(
+        &self,
+        db: DB,
+        evm_env: EvmEnv<Self::Spec>,
+    ) -> Self::Evm<DB, NoOpInspector> {
+        let spec = evm_env.cfg_env.spec;
+        let mut evm = self.inner.create_evm(db, evm_env);
+        self.apply(evm.precompiles_mut(), spec);
+        evm
+    }
+
+    fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>>>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec>,
+        inspector: I,
+    ) -> Self::Evm<DB, I> {
+        let spec = input.cfg_env.spec;
+        let mut evm = self.inner.create_evm_with_inspector(db, input, inspector);
+        self.apply(evm.precompiles_mut(), spec);
+        evm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes};
+    use reth_evm::{
+        revm::{database::EmptyDB, precompile::PrecompileOutput},
+        EthEvmFactory,
+    };
+
+    #[test]
+    fn registers_precompiles() {
+        let custom = address!("0x0000000000000000000000000000000000000100");
+        let kzg = address!("0x000000000000000000000000000000000000000a");
+        let factory = CustomPrecompiles::default()
+            .with_precompile(custom, |_, _| Ok(PrecompileOutput::new(0, Bytes::new())))
+            .with_cache(kzg)
+            .evm_factory(EthEvmFactory::default());
+
+        let mut evm = factory.create_evm(EmptyDB::default(), EvmEnv::default());
+        assert!(evm.precompiles_mut().addresses().any(|address| *address == custom));
+        assert!(evm.precompiles_mut().addresses().any(|address| *address == kzg));
+    }
+}
@@ -0,0 +1,164 @@
+//! Typed lifecycle events of a launched node.
+//!
+//! Programs that embed reth as a library can subscribe to these via
+//! [`NodeHandle::node_events`](crate::NodeHandle::node_events) to e.g. drive a UI, instead of
+//! relying on the log output of the node.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{map::HashMap, BlockNumber};
+use futures::{future, stream_select, Stream, StreamExt};
+use reth_engine_primitives::ConsensusEngineEvent;
+use reth_network_api::{NetworkEventListenerProvider, PeersInfo};
+use reth_primitives_traits::NodePrimitives;
+use reth_prune::PrunerEvent;
+use reth_stages::{PipelineEvent, StageCheckpoint, StageId};
+use reth_tokio_util::EventSender;
+use std::time::Duration;
+
+/// The numbers of connected peers that emit a [`NodeEvent::PeerCountMilestone`] once reached.
+pub const PEER_COUNT_MILESTONES: &[usize] = &[1, 5, 10, 25, 50, 100];
+
+/// A lifecycle event of a launched node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeEvent {
+    /// A stage of the sync pipeline made progress.
+    StageProgress {
+        /// The stage that made progress.
+        stage_id: StageId,
+        /// The new checkpoint of the stage.
+        checkpoint: StageCheckpoint,
+        /// The block number up to which the stage is running, if known.
+        target: Option<BlockNumber>,
+        /// Whether the stage reached its target.
+        done: bool,
+    },
+    /// A new canonical head was committed.
+    CanonicalHead {
+        /// The number and hash of the new head.
+        head: BlockNumHash,
+        /// The time it took to commit the canonical chain.
+        elapsed: Duration,
+    },
+    /// The number of connected peers reached a milestone of [`PEER_COUNT_MILESTONES`].
+    ///
+    /// This is also emitted with a lower milestone if the number of connected peers drops below
+    /// the current milestone, or with `0` if the node lost all of its peers.
+    PeerCountMilestone {
+        /// The milestone that was reached.
+        milestone: usize,
+        /// The number of connected peers.
+        connected_peers: usize,
+    },
+    /// The pruner finished a run.
+    PrunerFinished {
+        /// The tip block number before pruning.
+        tip_block_number: BlockNumber,
+        /// The duration of the run.
+        elapsed: Duration,
+    },
+}
+
+/// Tracks the [`PEER_COUNT_MILESTONES`] reached by the number of connected peers.
+#[derive(Debug, Default)]
+struct PeerCountMilestones {
+    /// The last reached milestone.
+    current: usize,
+}
+
+impl PeerCountMilestones {
+    /// Updates the number of connected peers and returns the milestone if it changed.
+    fn update(&mut self, connected_peers: usize) -> Option<usize> {
+        let milestone = PEER_COUNT_MILESTONES
+            .iter()
+            .rev()
+            .find(|milestone| connected_peers >= **milestone)
+            .copied()
+            .unwrap_or_default();
+        (milestone != self.current).then(|| {
+            self.current = milestone;
+            milestone
+        })
+    }
+}
+
+/// Converts the events of the node components to [`NodeEvent`]s and sends them to the
+/// subscribers of `sender` until all component streams are exhausted.
+pub(crate) async fn forward_node_events<N, Network>(
+    sender: EventSender<NodeEvent>,
+    network: Network,
+    pipeline_events: impl Stream<Item = PipelineEvent> + Unpin,
+    engine_events: impl Stream<Item = ConsensusEngineEvent<N>> + Unpin,
+    pruner_events: impl Stream<Item = PrunerEvent> + Unpin,
+) where
+    N: NodePrimitives,
+    Network: NetworkEventListenerProvider + PeersInfo,
+{
+    let mut milestones = PeerCountMilestones::default();
+    let peer_events = network.event_listener().filter_map(move |_| {
+        let connected_peers = network.num_connected_peers();
+        future::ready(
+            milestones
+                .update(connected_peers)
+                .map(|milestone| NodeEvent::PeerCountMilestone { milestone, connected_peers }),
+        )
+    });
+
+    // the targets of the running stages, which are only included in the events before a run
+    let mut targets = HashMap::<StageId, Option<BlockNumber>>::default();
+    let pipeline_events = pipeline_events.filter_map(move |event| {
+        future::ready(match event {
+            PipelineEvent::Run { stage_id, target, .. } => {
+                targets.insert(stage_id, target);
+                None
+            }
+            PipelineEvent::Ran { stage_id, result, .. } => Some(NodeEvent::StageProgress {
+                stage_id,
+                checkpoint: result.checkpoint,
+                target: targets.get(&stage_id).copied().flatten(),
+                done: result.done,
+            }),
+            _ => None,
+        })
+    });
+
+    let engine_events = engine_events.filter_map(|event| {
+        future::ready(match event {
+            ConsensusEngineEvent::CanonicalChainCommitted(header, elapsed) => {
+                Some(NodeEvent::CanonicalHead { head: header.num_hash(), elapsed })
+            }
+            _ => None,
+        })
+    });
+
+    let pruner_events = pruner_events.filter_map(|event| {
+        future::ready(match event {
+            PrunerEvent::Finished { tip_block_number, elapsed, .. } => {
+                Some(NodeEvent::PrunerFinished { tip_block_number, elapsed })
+            }
+            PrunerEvent::Started { .. } => None,
+        })
+    });
+
+    let mut events = stream_select!(peer_events, pipeline_events, engine_events, pruner_events);
+    while let Some(event) = events.next().await {
+        sender.notify(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_count_milestones() {
+        let mut milestones = PeerCountMilestones::default();
+        assert_eq!(milestones.update(0), None);
+        assert_eq!(milestones.update(1), Some(1));
+        assert_eq!(milestones.update(4), None);
+        assert_eq!(milestones.update(12), Some(10));
+        assert_eq!(milestones.update(11), None);
+        assert_eq!(milestones.update(7), Some(5));
+        assert_eq!(milestones.update(0), Some(0));
+        assert_eq!(milestones.update(500), Some(100));
+    }
+}
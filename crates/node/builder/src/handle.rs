@@ -3,7 +3,8 @@ use std::fmt;
 use reth_node_api::FullNodeComponents;
 use reth_node_core::exit::NodeExitFuture;
 
-use crate::{node::FullNode, rpc::RethRpcAddOns};
+use crate::{events::NodeEvent, node::FullNode, rpc::RethRpcAddOns};
+use reth_tokio_util::EventStream;
 
 /// A Handle to the launched node.
 #[must_use = "Needs to await the node exit future"]
@@ -19,6 +20,13 @@ where
     Node: FullNodeComponents,
     AddOns: RethRpcAddOns<Node>,
 {
+    /// Returns a new stream of the lifecycle events of the node.
+    ///
+    /// Only events emitted after the subscription are received.
+    pub fn node_events(&self) -> EventStream<NodeEvent> {
+        self.node.node_events.new_listener()
+    }
+
     /// Waits for the node to exit, if it was configured to exit.
     pub async fn wait_for_node_exit(self) -> eyre::Result<()> {
         self.node_exit_future.await
@@ -32,7 +32,7 @@
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
-    BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
+    BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy, ShutdownCoordinator,
 };
 use alloy_consensus::BlockHeader as _;
 use alloy_eips::eip2124::Head;
@@ -50,7 +50,10 @@ use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_nippy_jar::NippyJarError;
+use reth_node_api::{
+    FullNodeTypes, NodePrimitives, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter,
+};
 use reth_node_core::{
     args::DefaultEraHost,
     dirs::{ChainPath, DataDirPath},
@@ -82,7 +85,10 @@ use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, thread::available_parallelism};
+use std::{
+    sync::{Arc, OnceLock},
+    thread::available_parallelism,
+};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
     oneshot, watch,
@@ -116,12 +122,14 @@ pub struct LaunchContext {
     pub task_executor: TaskExecutor,
     /// The data directory for the node.
     pub data_dir: ChainPath<DataDirPath>,
+    /// Coordinates the graceful shutdown of the node, created on first use.
+    shutdown: OnceLock<ShutdownCoordinator>,
 }
 
 impl LaunchContext {
     /// Create a new instance of the default node launcher.
     pub const fn new(task_executor: TaskExecutor, data_dir: ChainPath<DataDirPath>) -> Self {
-        Self { task_executor, data_dir }
+        Self { task_executor, data_dir, shutdown: OnceLock::new() }
     }
 
     /// Create launch context with attachment.
@@ -266,6 +274,11 @@ impl<T> LaunchContextWith<T> {
         &self.inner.task_executor
     }
 
+    /// Returns the coordinator of the node's graceful shutdown.
+    pub fn shutdown(&self) -> &ShutdownCoordinator {
+        self.inner.shutdown.get_or_init(ShutdownCoordinator::new)
+    }
+
     /// Attaches another value to the launch context.
     pub fn attach<A>(self, attachment: A) -> LaunchContextWith<Attached<T, A>> {
         LaunchContextWith {
@@ -472,6 +485,11 @@ where
         .with_prune_modes(self.prune_modes())
         .with_static_files_metrics();
 
+        // Verify the static files before the consistency check below reads or heals them.
+        if self.node_config().verify_static_files {
+            verify_static_files(&factory.static_file_provider())?;
+        }
+
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
 
@@ -609,7 +627,8 @@ where
                         }
                     })
                     .build(),
-            );
+            )
+            .with_instance(self.node_config().instance);
 
             MetricServer::new(config).serve().await?;
         }
@@ -759,6 +778,7 @@ where
             head,
             self.blockchain_db().clone(),
             self.task_executor().clone(),
+            self.shutdown().clone(),
             self.configs().clone(),
         );
 
@@ -916,9 +936,22 @@ where
             .unwrap_or_default()
             .block_number;
 
+        // Opt-in stages are only checked if they are enabled.
+        let stages_config = &self.toml_config().stages;
+        let optional_stages = [
+            stages_config
+                .index_sender_transactions
+                .enabled
+                .then_some(StageId::IndexSenderTransactions),
+            stages_config
+                .index_contract_deployments
+                .enabled
+                .then_some(StageId::IndexContractDeployments),
+        ];
+
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
         // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        for stage_id in StageId::ALL.iter().skip(1).chain(optional_stages.iter().flatten()) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -1174,6 +1207,35 @@ where
     head: Head,
 }
 
+/// Verifies all static files against their checksum manifests.
+///
+/// Returns an error if any static file is corrupted. Static files without a checksum manifest are
+/// skipped.
+fn verify_static_files<N: NodePrimitives>(provider: &StaticFileProvider<N>) -> eyre::Result<()> {
+    info!(target: "reth::cli", "Verifying static files");
+
+    let mut corrupted = 0;
+    for (path, result) in provider.verify_checksums()? {
+        match result {
+            Ok(()) => {}
+            Err(NippyJarError::MissingChecksums(_)) => {
+                warn!(target: "reth::cli", path = %path.display(), "Static file has no checksum manifest, skipping verification");
+            }
+            Err(err) => {
+                error!(target: "reth::cli", path = %path.display(), %err, "Static file verification failed");
+                corrupted += 1;
+            }
+        }
+    }
+
+    if corrupted > 0 {
+        eyre::bail!("{corrupted} static files failed verification, see the logs above for details")
+    }
+
+    info!(target: "reth::cli", "Verified static files");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{LaunchContext, NodeConfig};
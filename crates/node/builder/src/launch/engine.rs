@@ -2,8 +2,12 @@
 
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
+    events,
     hooks::NodeHooks,
-    rpc::{EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RpcHandle},
+    rpc::{
+        EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RethRpcServerHandles,
+        RpcHandle,
+    },
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
@@ -22,7 +26,8 @@ use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
+    BuiltPayload, ConsensusEngineHandle, EngineClientTracker, FullNodeTypes, NodeTypes,
+    NodeTypesWithDBAdapter,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -32,11 +37,11 @@ use reth_node_core::{
 use reth_node_events::node;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
-    BlockNumReader,
+    BlockNumReader, DatabaseProviderFactory, StaticFileProviderFactory,
 };
-use reth_tasks::TaskExecutor;
+use reth_tasks::{runtime::CONSENSUS_ENGINE_TASK, TaskExecutor};
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
+use reth_tracing::tracing::{debug, error, info, warn};
 use std::sync::Arc;
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -83,6 +88,12 @@ where
         target: NodeBuilderWithComponents<T, CB, AO>,
     ) -> eyre::Result<Self::Node> {
         let Self { ctx, engine_tree_config } = self;
+        let engine_tree_config = if engine_tree_config.fork_choice_history_path().is_some() {
+            engine_tree_config
+        } else {
+            let path = ctx.data_dir.fork_choice_history();
+            engine_tree_config.with_fork_choice_history_path(Some(path))
+        };
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
@@ -166,6 +177,7 @@ where
         pipeline.move_to_static_files()?;
 
         let pipeline_events = pipeline.events();
+        let node_pipeline_events = pipeline.events();
 
         let mut pruner_builder = ctx.pruner_builder();
         if let Some(exex_manager_handle) = &maybe_exex_manager_handle {
@@ -174,11 +186,13 @@ where
         }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
+        let node_pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
         let event_sender = EventSender::default();
 
-        let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone());
+        let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone())
+            .with_client_tracker(EngineClientTracker::default());
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
@@ -189,6 +203,7 @@ where
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            flush_signal: ctx.shutdown().flush_signal(),
         };
         let validator_builder = add_ons.engine_validator_builder();
 
@@ -199,7 +214,9 @@ where
             .await?;
 
         // Create the consensus engine stream with optional reorg
-        let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+        let consensus_engine_stream = ctx
+            .shutdown()
+            .track_engine_messages(UnboundedReceiverStream::from(consensus_engine_rx))
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
@@ -251,6 +268,15 @@ where
             )),
         );
 
+        let node_events = EventSender::default();
+        ctx.task_executor().spawn(Box::pin(events::forward_node_events(
+            node_events.clone(),
+            ctx.components().network().clone(),
+            node_pipeline_events,
+            event_sender.new_listener(),
+            node_pruner_events,
+        )));
+
         let RpcHandle { rpc_server_handles, rpc_registry, engine_events, beacon_engine_handle } =
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
@@ -271,7 +297,10 @@ where
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical("consensus engine", Box::pin(async move {
+        // The engine keeps running after the node's shutdown signal fired until the shutdown
+        // coordinator drained in-flight engine messages.
+        let mut engine_shutdown = ctx.shutdown().flush_signal();
+        ctx.task_executor().spawn_critical_with_shutdown_signal(CONSENSUS_ENGINE_TASK, |_| Box::pin(async move {
             if let Some(initial_target) = initial_target {
                 debug!(target: "reth::cli", %initial_target,  "start backfill sync");
                 engine_service.orchestrator_mut().start_backfill_sync(initial_target);
@@ -280,8 +309,14 @@ where
             let mut res = Ok(());
 
             // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
+            let mut shutdown_guard = None;
             loop {
                 tokio::select! {
+                    guard = &mut engine_shutdown => {
+                        debug!(target: "reth::cli", "Stopping consensus engine");
+                        shutdown_guard = Some(guard);
+                        break
+                    }
                     payload = built_payloads.select_next_some() => {
                         if let Some(executed_block) = payload.executed_block() {
                             debug!(target: "reth::cli", block=?executed_block.recovered_block().num_hash(),  "inserting built payload");
@@ -334,8 +369,56 @@ where
             }
 
             let _ = exit.send(res);
+            drop(engine_service);
+            drop(shutdown_guard);
         }));
 
+        // Once the node shuts down, stop RPC intake, drain the engine, flush in-memory state and
+        // wait for in-flight writes, in this order.
+        let stop_rpc = {
+            let RethRpcServerHandles { rpc, auth } = rpc_server_handles.clone();
+            async move {
+                let _ = rpc.clone().stop();
+                let _ = auth.clone().stop();
+                rpc.stopped().await;
+                auth.stopped().await;
+            }
+        };
+        let sync_static_files = {
+            let provider_factory = ctx.provider_factory().clone();
+            let has_receipt_pruning =
+                ctx.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
+            async move {
+                let result = tokio::task::spawn_blocking(move || {
+                    // Opening a read-write transaction waits for any in-flight write to be
+                    // committed, after which partially written static files can be healed.
+                    let provider = provider_factory.database_provider_rw()?;
+                    provider_factory
+                        .static_file_provider()
+                        .check_consistency(&provider, has_receipt_pruning)
+                })
+                .await;
+                match result {
+                    Ok(Ok(None)) => {}
+                    Ok(Ok(Some(unwind_target))) => {
+                        warn!(target: "reth::cli", %unwind_target, "Static files are ahead of the database, an unwind will be executed on the next start")
+                    }
+                    Ok(Err(err)) => {
+                        warn!(target: "reth::cli", %err, "Failed to check storage consistency")
+                    }
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to check storage consistency")
+                    }
+                }
+            }
+        };
+        ctx.shutdown().clone().spawn(
+            ctx.task_executor(),
+            ctx.node_config().shutdown,
+            stop_rpc,
+            sync_static_files,
+        );
+
         let full_node = FullNode {
             evm_config: ctx.components().evm_config().clone(),
             pool: ctx.components().pool().clone(),
@@ -351,6 +434,7 @@ where
                 engine_events,
                 beacon_engine_handle,
             },
+            node_events,
         };
         // Notify on node started
         on_node_started.on_event(FullNode::clone(&full_node))?;
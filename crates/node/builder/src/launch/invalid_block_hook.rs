@@ -54,6 +54,7 @@ where
 /// This function constructs the appropriate [`InvalidBlockHook`] based on the debug
 /// configuration in the node config. It supports:
 /// - Witness hooks for capturing block witness data
+/// - Dump hooks for writing the invalid block, its witness and touched state to disk
 /// - Healthy node verification via RPC
 ///
 /// # Arguments
@@ -80,7 +81,7 @@ where
     E: reth_evm::ConfigureEvm<Primitives = N> + Clone + 'static,
 {
     use reth_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+    use reth_invalid_block_hooks::{InvalidBlockDumpHook, InvalidBlockWitnessHook};
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -102,11 +103,16 @@ where
                     evm_config.clone(),
                     output_directory,
                     healthy_node_rpc_client.clone(),
+                )) as Box<dyn InvalidBlockHook<_>>,
+                InvalidBlockHookType::Dump => Box::new(InvalidBlockDumpHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    output_directory,
                 )),
                 InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
                     eyre::bail!("invalid block hook {hook:?} is not implemented yet")
                 }
-            } as Box<dyn InvalidBlockHook<_>>)
+            })
         })
         .collect::<Result<_, _>>()?;
 
@@ -14,6 +14,8 @@
 /// Node event hooks.
 pub mod hooks;
 
+pub mod events;
+
 /// Support for configuring the higher level node types.
 pub mod node;
 pub use node::*;
@@ -43,6 +45,10 @@ pub mod rpc;
 
 pub mod setup;
 
+/// Support for shutting down the node gracefully.
+pub mod shutdown;
+pub use shutdown::ShutdownCoordinator;
+
 /// Type aliases for traits that are often used together
 pub mod aliases;
 pub use aliases::*;
@@ -1,7 +1,10 @@
 // re-export the node api types
 pub use reth_node_api::{FullNodeTypes, NodeTypes};
 
-use crate::{components::NodeComponentsBuilder, rpc::RethRpcAddOns, NodeAdapter, NodeAddOns};
+use crate::{
+    components::NodeComponentsBuilder, events::NodeEvent, rpc::RethRpcAddOns, NodeAdapter,
+    NodeAddOns,
+};
 use reth_node_api::{EngineTypes, FullNodeComponents, PayloadTypes};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -12,6 +15,7 @@ use reth_provider::ChainSpecProvider;
 use reth_rpc_api::EngineApiClient;
 use reth_rpc_builder::{auth::AuthServerHandle, RpcServerHandle};
 use reth_tasks::TaskExecutor;
+use reth_tokio_util::{EventSender, EventStream};
 use std::{
     fmt::Debug,
     marker::PhantomData,
@@ -119,6 +123,8 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub data_dir: ChainPath<DataDirPath>,
     /// The handle to launched add-ons
     pub add_ons_handle: AddOns::Handle,
+    /// The sender of the lifecycle events of the node.
+    pub node_events: EventSender<NodeEvent>,
 }
 
 impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node, AddOns> {
@@ -133,6 +139,7 @@ impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node
             config: self.config.clone(),
             data_dir: self.data_dir.clone(),
             add_ons_handle: self.add_ons_handle.clone(),
+            node_events: self.node_events.clone(),
         }
     }
 }
@@ -147,6 +154,11 @@ where
     pub fn chain_spec(&self) -> Arc<<Node::Types as NodeTypes>::ChainSpec> {
         self.provider.chain_spec()
     }
+
+    /// Returns a new stream of the lifecycle events of the node.
+    pub fn node_events(&self) -> EventStream<NodeEvent> {
+        self.node_events.new_listener()
+    }
 }
 
 impl<Payload, Node, AddOns> FullNode<Node, AddOns>
@@ -15,25 +15,35 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineApiValidator, EngineTypes, FullNodeComponents, FullNodeTypes,
-    NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
+    NodeAddOns, NodeTypes, PayloadAttributesValidator, PayloadTypes, PayloadValidator,
+    PrimitivesTy, TreeConfig, WithAttributesValidator,
 };
 use reth_node_core::{
+    args::RpcServerArgs,
     cli::config::RethTransactionPoolConfig,
     node_config::NodeConfig,
     version::{version_metadata, CLIENT_CODE},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
 use reth_rpc::eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc_api::{
+    eth::helpers::{AddDevSigners, Call, LoadFee},
+    IntoEngineApiRpcModule, RethEngineApiServer,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
+    cors::{CorsConfig, CorsHandle},
     RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
 };
-use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
-use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi, RethEngineApi};
+use reth_rpc_eth_types::{
+    cache::cache_new_blocks_task, fee_history::fee_history_cache_backup_task, EthConfig,
+    EthStateCache,
+};
+use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, info};
+use reth_tracing::tracing::{debug, info, warn};
 use std::{
     fmt::{self, Debug},
     future::Future,
@@ -409,6 +419,64 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     engine_handle: ConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
 }
 
+/// The [`CorsHandle`]s that are reloaded on `SIGHUP`, with the files they are loaded from.
+#[cfg(unix)]
+static CORS_RELOADS: std::sync::Mutex<Vec<(std::path::PathBuf, CorsHandle)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Creates the [`CorsHandle`] of the regular RPC servers if a CORS file is configured.
+///
+/// On unix, the CORS domains are reloaded from the file whenever the node receives `SIGHUP`. The
+/// signal handler is installed once, when the first handle is created.
+fn cors_handle(args: &RpcServerArgs, executor: &TaskExecutor) -> eyre::Result<Option<CorsHandle>> {
+    let (Some(path), Some(config)) = (args.rpc_cors.clone(), args.rpc_cors_config()?) else {
+        return Ok(None)
+    };
+    let cors = CorsHandle::new(&config)?;
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut reloads = CORS_RELOADS.lock().unwrap_or_else(|err| err.into_inner());
+        if reloads.is_empty() {
+            let mut hangup = signal(SignalKind::hangup())?;
+            executor.spawn(async move {
+                while hangup.recv().await.is_some() {
+                    reload_cors_handles();
+                }
+            });
+        }
+        reloads.push((path, cors.clone()));
+    }
+    #[cfg(not(unix))]
+    let _ = (path, executor);
+
+    Ok(Some(cors))
+}
+
+/// Reloads all [`CorsHandle`]s from their files, keeping the current domains on failure.
+#[cfg(unix)]
+fn reload_cors_handles() {
+    let reloads = CORS_RELOADS.lock().unwrap_or_else(|err| err.into_inner()).clone();
+    for (path, cors) in reloads {
+        let reload = CorsConfig::load(&path)
+            .map_err(eyre::Report::from)
+            .and_then(|config| Ok(cors.reload(&config)?));
+        match reload {
+            Ok(()) => info!(target: "reth::cli", ?path, "Reloaded RPC CORS domains"),
+            Err(err) => {
+                warn!(
+                    target: "reth::cli",
+                    ?path,
+                    %err,
+                    "Failed to reload RPC CORS domains, keeping the current ones"
+                )
+            }
+        }
+    }
+}
+
 /// Node add-ons containing RPC server configuration, with customizable eth API handler.
 ///
 /// This struct can be used to provide the RPC server functionality. It is responsible for launching
@@ -768,6 +836,8 @@ where
         let server_config = config
             .rpc
             .rpc_server_config()
+            .with_api_keys(config.rpc.rpc_api_keys()?)
+            .with_cors_handle(cors_handle(&config.rpc, node.task_executor())?)
             .set_rpc_middleware(rpc_middleware)
             .with_tokio_runtime(tokio_runtime);
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
@@ -839,6 +909,8 @@ where
         let server_config = config
             .rpc
             .rpc_server_config()
+            .with_api_keys(config.rpc.rpc_api_keys()?)
+            .with_cors_handle(cors_handle(&config.rpc, node.task_executor())?)
             .set_rpc_middleware(rpc_middleware)
             .with_tokio_runtime(tokio_runtime);
 
@@ -889,7 +961,14 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            flush_signal,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -909,9 +988,25 @@ where
         );
 
         let eth_config = config.rpc.eth_config().max_batch_size(config.txpool.max_batch_size());
-        let ctx = EthApiCtx { components: &node, config: eth_config, cache };
+        let ctx = EthApiCtx { components: &node, config: eth_config, cache: cache.clone() };
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
+        let rpc_limits = eth_api.rpc_limits();
+        let fee_history_cache = eth_api.fee_history_cache().clone();
+        let fee_history_cache_path = config.datadir().fee_history_cache();
+        let provider = node.provider().clone();
+        node.task_executor().spawn_critical_with_shutdown_signal(
+            "fee history cache backup task",
+            |_| {
+                fee_history_cache_backup_task(
+                    flush_signal,
+                    fee_history_cache,
+                    fee_history_cache_path,
+                    provider,
+                )
+            },
+        );
+
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
@@ -925,6 +1020,19 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api);
 
+        // expose the payload builder stats, the backfill controls and the pool, cache and RPC
+        // limits on the auth server
+        auth_module.merge_auth_methods(
+            RethEngineApi::new(
+                node.payload_builder_handle().clone(),
+                beacon_engine_handle.clone(),
+                node.pool().clone(),
+                cache,
+                rpc_limits,
+            )
+            .into_rpc(),
+        )?;
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
@@ -1081,13 +1189,17 @@ impl<'a, N: FullNodeComponents<Types: NodeTypes<ChainSpec: EthereumHardforks>>>
             .eth_cache(self.cache)
             .task_spawner(self.components.task_executor().clone())
             .gas_cap(self.config.rpc_gas_cap.into())
+            .tx_fee_cap(self.config.rpc_tx_fee_cap)
             .max_simulate_blocks(self.config.rpc_max_simulate_blocks)
             .eth_proof_window(self.config.eth_proof_window)
+            .max_state_history(self.config.max_state_history)
+            .timeouts(self.config.rpc_timeouts)
             .fee_history_cache_config(self.config.fee_history_cache)
             .proof_permits(self.config.proof_permits)
             .gas_oracle_config(self.config.gas_oracle)
             .max_batch_size(self.config.max_batch_size)
             .pending_block_kind(self.config.pending_block_kind)
+            .sync_progress(self.config.sync_progress)
     }
 }
 
@@ -1167,6 +1279,49 @@ pub trait PayloadValidatorBuilder<Node: FullNodeComponents>: Send + Sync + Clone
         self,
         ctx: &AddOnsContext<'_, Node>,
     ) -> impl Future<Output = eyre::Result<Self::Validator>> + Send;
+
+    /// Applies the given [`PayloadAttributesValidator`] to the payload attributes received over
+    /// the engine API, on top of the checks of the built validator.
+    ///
+    /// Attributes rejected by it are answered with `-38003: Invalid payload attributes`.
+    fn with_attributes_validator<A>(
+        self,
+        attributes_validator: A,
+    ) -> AttributesValidatorBuilder<Self, A>
+    where
+        Self: Sized,
+    {
+        AttributesValidatorBuilder { builder: self, attributes_validator }
+    }
+}
+
+/// A [`PayloadValidatorBuilder`] that wraps the validator of another builder in a
+/// [`WithAttributesValidator`].
+///
+/// See [`PayloadValidatorBuilder::with_attributes_validator`].
+#[derive(Debug, Clone)]
+pub struct AttributesValidatorBuilder<B, A> {
+    /// The builder of the wrapped validator.
+    builder: B,
+    /// The additional validation of the payload attributes.
+    attributes_validator: A,
+}
+
+impl<Node, B, A> PayloadValidatorBuilder<Node> for AttributesValidatorBuilder<B, A>
+where
+    Node: FullNodeComponents,
+    B: PayloadValidatorBuilder<Node>,
+    A: PayloadAttributesValidator<
+            <<Node::Types as NodeTypes>::Payload as PayloadTypes>::PayloadAttributes,
+        > + Clone
+        + Unpin,
+{
+    type Validator = WithAttributesValidator<B::Validator, A>;
+
+    async fn build(self, ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
+        let validator = self.builder.build(ctx).await?;
+        Ok(WithAttributesValidator::new(validator, self.attributes_validator))
+    }
 }
 
 /// Builder trait for creating engine validators for the consensus engine.
@@ -49,7 +49,7 @@ where
     Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
 {
     // building network downloaders using the fetch client
-    let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
+    let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers.clone())
         .build(client.clone(), consensus.clone())
         .into_task_with(task_executor);
 
@@ -123,13 +123,16 @@ where
                 prune_modes,
                 era_import_source,
             )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(
+                ExecutionStage::new(
+                    evm_config,
+                    consensus,
+                    stage_config.execution.into(),
+                    stage_config.execution_external_clean_threshold(),
+                    exex_manager_handle,
+                )
+                .with_hashed_state_writes(stage_config.execution.write_hashed_state),
+            ),
         )
         .build(provider_factory, static_file_producer);
 
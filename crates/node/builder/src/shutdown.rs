@@ -0,0 +1,350 @@
+//! Ordered graceful shutdown of a node.
+
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_node_api::{BeaconEngineMessage, PayloadTypes};
+use reth_node_core::args::ShutdownArgs;
+use reth_tasks::{
+    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
+    TaskExecutor,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::sync::{oneshot, Notify};
+use tracing::{debug, info, warn};
+
+/// Coordinates the graceful shutdown of a node.
+///
+/// Once the node's [`GracefulShutdown`] signal fires, the coordinator runs the following stages in
+/// order, each bounded by the corresponding timeout of the [`ShutdownArgs`]:
+///
+/// 1. Stop accepting RPC requests, including the engine API.
+/// 2. Wait for the engine to answer all in-flight engine API messages, see
+///    [`ShutdownCoordinator::track_engine_messages`].
+/// 3. Fire the [flush signal](ShutdownCoordinator::flush_signal) and wait for the tasks holding it
+///    to persist their state, e.g. the transaction pool journal and the fee history cache.
+/// 4. Wait for in-flight database and static file writes to finish and heal any partially written
+///    static files.
+///
+/// A stage that does not complete within its timeout is abandoned and the next stage is started,
+/// so that the node still exits in bounded time.
+#[derive(Debug, Clone)]
+pub struct ShutdownCoordinator {
+    /// Fires the flush signal, taken once it fired.
+    flush: Arc<Mutex<Option<Signal>>>,
+    /// Receiver of the flush signal.
+    on_flush: Shutdown,
+    /// How many guards of the flush signal are still held.
+    pending_flushes: Arc<AtomicUsize>,
+    /// Notified once the last guard of the flush signal was dropped.
+    flushed: Arc<Notify>,
+    /// Tracks the engine API messages the engine hasn't answered yet.
+    engine_messages: EngineMessageTracker,
+}
+
+impl ShutdownCoordinator {
+    /// Creates a new [`ShutdownCoordinator`].
+    pub fn new() -> Self {
+        let (flush, on_flush) = signal();
+        Self {
+            flush: Arc::new(Mutex::new(Some(flush))),
+            on_flush,
+            pending_flushes: Default::default(),
+            flushed: Default::default(),
+            engine_messages: Default::default(),
+        }
+    }
+
+    /// Returns a [`GracefulShutdown`] that fires once the engine has been drained during shutdown.
+    ///
+    /// Tasks that write in-memory state to disk on shutdown should use this signal instead of the
+    /// [`TaskExecutor`]'s shutdown signal, so that the state is written after the node stopped
+    /// processing new blocks. The coordinator waits for the returned guard to be dropped.
+    ///
+    /// Note that tasks spawned via [`TaskExecutor::spawn_critical`] are cancelled as soon as the
+    /// executor's shutdown signal fires, so tasks waiting for this signal should be spawned via
+    /// [`TaskExecutor::spawn_critical_with_shutdown_signal`] instead.
+    pub fn flush_signal(&self) -> GracefulShutdown {
+        GracefulShutdown::new(
+            self.on_flush.clone(),
+            GracefulShutdownGuard::with_notify(
+                Arc::clone(&self.pending_flushes),
+                Arc::clone(&self.flushed),
+            ),
+        )
+    }
+
+    /// Wraps the stream of engine API messages that is consumed by the engine, so that the
+    /// shutdown waits for the engine to answer the new payload and forkchoice update messages it
+    /// received.
+    pub fn track_engine_messages<S>(&self, stream: S) -> TrackedEngineMessages<S> {
+        TrackedEngineMessages { stream, tracker: self.engine_messages.clone() }
+    }
+
+    /// Spawns the shutdown sequence, which runs once the [`TaskExecutor`]'s graceful shutdown
+    /// signal fires.
+    ///
+    /// The given futures implement the node specific stages: `stop_rpc` stops the RPC servers and
+    /// `sync_static_files` resolves once in-flight database and static file writes finished.
+    pub fn spawn(
+        self,
+        executor: &TaskExecutor,
+        args: ShutdownArgs,
+        stop_rpc: impl Future<Output = ()> + Send + 'static,
+        sync_static_files: impl Future<Output = ()> + Send + 'static,
+    ) {
+        executor.extend_graceful_shutdown_timeout(args.total_timeout());
+        executor.spawn_critical_with_graceful_shutdown_signal(
+            "shutdown coordinator",
+            |shutdown| async move {
+                let guard = shutdown.await;
+                info!(target: "reth::cli", "Shutting down node");
+
+                run_stage("rpc", args.rpc_timeout, stop_rpc).await;
+                let drained = self.engine_messages.clone().drained();
+                run_stage("engine", args.engine_timeout, drained).await;
+                run_stage("flush", args.flush_timeout, self.flush()).await;
+                run_stage("static files", args.static_files_timeout, sync_static_files).await;
+
+                drop(guard)
+            },
+        );
+    }
+
+    /// Fires the flush signal and waits until all guards of it were dropped.
+    async fn flush(self) {
+        if let Some(flush) = self.flush.lock().take() {
+            flush.fire();
+        }
+        loop {
+            // registered before checking the counter, so that a guard dropped in between is seen
+            let flushed = self.flushed.notified();
+            if self.pending_flushes.load(Ordering::SeqCst) == 0 {
+                return
+            }
+            flushed.await;
+        }
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks the engine API messages that the engine received but hasn't answered yet.
+///
+/// The engine is drained once it polled all pending messages and answered all new payload and
+/// forkchoice update messages it received.
+#[derive(Debug, Clone, Default)]
+struct EngineMessageTracker {
+    inner: Arc<EngineMessageTrackerInner>,
+}
+
+#[derive(Debug, Default)]
+struct EngineMessageTrackerInner {
+    /// Whether the engine found no pending message the last time it polled for one.
+    idle: AtomicBool,
+    /// Number of received messages that weren't answered yet.
+    in_flight: AtomicUsize,
+    /// Notified when the engine may have become drained.
+    notify: Notify,
+}
+
+impl EngineMessageTracker {
+    /// Returns true if the engine is drained.
+    fn is_drained(&self) -> bool {
+        self.inner.idle.load(Ordering::SeqCst) && self.inner.in_flight.load(Ordering::SeqCst) == 0
+    }
+
+    /// Waits until the engine is drained.
+    async fn drained(self) {
+        loop {
+            // registered before checking the state, so that a change in between is seen
+            let notified = self.inner.notify.notified();
+            if self.is_drained() {
+                return
+            }
+            notified.await;
+        }
+    }
+
+    /// Records whether the engine found no pending message when it last polled for one.
+    fn set_idle(&self, idle: bool) {
+        let was_idle = self.inner.idle.swap(idle, Ordering::SeqCst);
+        if idle && !was_idle && self.is_drained() {
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Replaces the response sender of new payload and forkchoice update messages, so that the
+    /// message is tracked until the engine answered it.
+    fn track<T: PayloadTypes>(&self, msg: BeaconEngineMessage<T>) -> BeaconEngineMessage<T> {
+        match msg {
+            BeaconEngineMessage::NewPayload { payload, tx } => {
+                BeaconEngineMessage::NewPayload { payload, tx: self.forward(tx) }
+            }
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+                BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    version,
+                    tx: self.forward(tx),
+                }
+            }
+            msg => msg,
+        }
+    }
+
+    /// Returns a sender that forwards the engine's response to the given sender. The message is
+    /// in flight until the response was forwarded or the engine dropped the returned sender.
+    fn forward<R: Send + 'static>(&self, tx: oneshot::Sender<R>) -> oneshot::Sender<R> {
+        let (forward_tx, forward_rx) = oneshot::channel();
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            if let Ok(response) = forward_rx.await {
+                let _ = tx.send(response);
+            }
+            if tracker.inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 && tracker.is_drained() {
+                tracker.inner.notify.notify_waiters();
+            }
+        });
+        forward_tx
+    }
+}
+
+/// A stream of engine API messages that are tracked until the engine answered them, see
+/// [`ShutdownCoordinator::track_engine_messages`].
+#[derive(Debug)]
+pub struct TrackedEngineMessages<S> {
+    stream: S,
+    tracker: EngineMessageTracker,
+}
+
+impl<S, T> Stream for TrackedEngineMessages<S>
+where
+    S: Stream<Item = BeaconEngineMessage<T>> + Unpin,
+    T: PayloadTypes,
+{
+    type Item = BeaconEngineMessage<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(msg)) => {
+                this.tracker.set_idle(false);
+                Poll::Ready(Some(this.tracker.track(msg)))
+            }
+            Poll::Ready(None) => {
+                this.tracker.set_idle(true);
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                this.tracker.set_idle(true);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Runs a single stage of the shutdown sequence, abandoning it after the given timeout.
+async fn run_stage(name: &'static str, timeout: Duration, stage: impl Future<Output = ()>) {
+    let start = Instant::now();
+    debug!(target: "reth::cli", stage = name, ?timeout, "Running shutdown stage");
+    match tokio::time::timeout(timeout, stage).await {
+        Ok(()) => {
+            debug!(target: "reth::cli", stage = name, elapsed = ?start.elapsed(), "Shutdown stage completed")
+        }
+        Err(_) => warn!(target: "reth::cli", stage = name, ?timeout, "Shutdown stage timed out"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ForkchoiceState;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_node_api::{EngineApiMessageVersion, OnForkChoiceUpdated};
+    use reth_tasks::TaskManager;
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    #[test]
+    fn runs_stages_in_order() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+
+        let coordinator = ShutdownCoordinator::new();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let flush_signal = coordinator.flush_signal();
+        let flush_order = Arc::clone(&order);
+        runtime.spawn(async move {
+            let guard = flush_signal.await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            flush_order.lock().push("flush");
+            drop(guard)
+        });
+
+        let push = |stage: &'static str| {
+            let order = Arc::clone(&order);
+            async move { order.lock().push(stage) }
+        };
+        // the engine never polls its messages, so its stage is abandoned after the timeout
+        let args = ShutdownArgs { engine_timeout: Duration::from_millis(50), ..Default::default() };
+        coordinator.spawn(&executor, args, push("rpc"), push("static files"));
+
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(5)));
+        assert_eq!(*order.lock(), ["rpc", "flush", "static files"]);
+    }
+
+    #[tokio::test]
+    async fn waits_for_engine_to_answer_messages() {
+        let coordinator = ShutdownCoordinator::new();
+        let (to_engine, from_handle) = unbounded_channel();
+        let mut messages =
+            coordinator.track_engine_messages(UnboundedReceiverStream::new(from_handle));
+        let drained = || {
+            tokio::time::timeout(
+                Duration::from_millis(50),
+                coordinator.engine_messages.clone().drained(),
+            )
+        };
+
+        let (tx, rx) = oneshot::channel();
+        to_engine
+            .send(BeaconEngineMessage::<EthEngineTypes>::ForkchoiceUpdated {
+                state: ForkchoiceState::default(),
+                payload_attrs: None,
+                version: EngineApiMessageVersion::V3,
+                tx,
+            })
+            .unwrap();
+
+        // the message is pending
+        assert!(drained().await.is_err());
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { tx, .. }) = messages.next().await else {
+            unreachable!()
+        };
+        assert!(futures::poll!(messages.next()).is_pending());
+
+        // the message was received, but not answered
+        assert!(drained().await.is_err());
+
+        tx.send(Ok(OnForkChoiceUpdated::syncing())).unwrap();
+        assert!(rx.await.unwrap().is_ok());
+        assert!(drained().await.is_ok());
+    }
+}
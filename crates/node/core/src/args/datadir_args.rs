@@ -27,6 +27,13 @@ pub struct DatadirArgs {
         verbatim_doc_comment
     )]
     pub static_files_path: Option<PathBuf>,
+
+    /// Optional subdirectory of the data dir that holds all files of the node.
+    ///
+    /// This separates the data of nodes that share a data dir, e.g. nodes that are co-hosted in
+    /// one process. Does not apply to an explicitly configured static files path.
+    #[arg(skip)]
+    pub namespace: Option<String>,
 }
 
 impl DatadirArgs {
@@ -70,6 +70,9 @@ pub struct DebugArgs {
 
     /// Determines which type of invalid block hook to install
     ///
+    /// The `dump` hook writes the invalid block, its execution witness and touched state to the
+    /// `invalid_block_hooks/dump` directory in the datadir.
+    ///
     /// Example: `witness,prestate`
     #[arg(
         long = "debug.invalid-block-hook",
@@ -291,6 +294,8 @@ pub enum InvalidBlockHookType {
     PreState,
     /// An opcode trace value enum
     Opcode,
+    /// A dump of the invalid block, its execution witness and touched state
+    Dump,
 }
 
 impl FromStr for InvalidBlockHookType {
@@ -301,6 +306,7 @@ impl FromStr for InvalidBlockHookType {
             "witness" => Self::Witness,
             "prestate" => Self::PreState,
             "opcode" => Self::Opcode,
+            "dump" => Self::Dump,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -397,5 +403,20 @@ mod tests {
         ])
         .args;
         assert_eq!(args, expected_args);
+
+        let expected_args = DebugArgs {
+            invalid_block_hook: Some(InvalidBlockSelection::from([
+                InvalidBlockHookType::Witness,
+                InvalidBlockHookType::Dump,
+            ])),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.invalid-block-hook",
+            "witness,dump",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
     }
 }
@@ -1,7 +1,10 @@
 //! clap [Args](clap::Args) for engine purposes
 
 use clap::Args;
-use reth_engine_primitives::TreeConfig;
+use reth_engine_primitives::{
+    BackfillThresholds, TreeConfig, DEFAULT_BACKFILL_HYSTERESIS, DEFAULT_BACKFILL_THRESHOLD,
+    DEFAULT_DEFERRED_STATE_ROOT_WINDOW, DEFAULT_FORK_CHOICE_HISTORY_SIZE,
+};
 
 use crate::node_config::{
     DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
@@ -20,6 +23,13 @@ pub struct EngineArgs {
     #[arg(long = "engine.memory-block-buffer-target", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
 
+    /// Configure the maximum estimated size in megabytes of the blocks kept in memory.
+    ///
+    /// Once the in-memory blocks exceed this budget, they are persisted even if the persistence
+    /// threshold is not reached yet.
+    #[arg(long = "engine.in-memory-budget", value_name = "MB")]
+    pub in_memory_budget: Option<u64>,
+
     /// Enable legacy state root
     #[arg(long = "engine.legacy-state-root", default_value = "false")]
     pub legacy_state_root_task_enabled: bool,
@@ -89,6 +99,38 @@ pub struct EngineArgs {
         default_value = "false"
     )]
     pub always_process_payload_attributes_on_canonical_head: bool,
+
+    /// Minimum distance between the local tip and the target block that triggers a backfill sync
+    /// instead of downloading the missing blocks.
+    #[arg(long = "engine.backfill-threshold", default_value_t = DEFAULT_BACKFILL_THRESHOLD)]
+    pub backfill_threshold: u64,
+
+    /// Number of blocks added to the backfill threshold after a backfill sync finished, until the
+    /// node caught up with the consensus layer.
+    #[arg(long = "engine.backfill-hysteresis", default_value_t = DEFAULT_BACKFILL_HYSTERESIS)]
+    pub backfill_hysteresis: u64,
+
+    /// Number of canonical head changes and reorgs kept in the fork choice history, which is
+    /// persisted in the datadir and served by `reth_getReorgHistory`.
+    #[arg(long = "engine.fork-choice-history-size", default_value_t = DEFAULT_FORK_CHOICE_HISTORY_SIZE)]
+    pub fork_choice_history_size: usize,
+
+    /// Accept executed payloads before their state root is verified and verify the state root in
+    /// the background. Blocks with a mismatching state root are reverted.
+    ///
+    /// WARNING: Invalid blocks are reported as VALID until the verification finished, only use
+    /// this with a trusted consensus layer.
+    #[arg(long = "engine.deferred-state-root", default_value = "false")]
+    pub deferred_state_root: bool,
+
+    /// Maximum number of blocks with a deferred state root verification in progress. Once
+    /// reached, the state root is verified before the payload is accepted.
+    #[arg(
+        long = "engine.deferred-state-root-window",
+        default_value_t = DEFAULT_DEFERRED_STATE_ROOT_WINDOW,
+        requires = "deferred_state_root"
+    )]
+    pub deferred_state_root_window: u64,
 }
 
 #[allow(deprecated)]
@@ -97,6 +139,7 @@ impl Default for EngineArgs {
         Self {
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            in_memory_budget: None,
             legacy_state_root_task_enabled: false,
             state_root_task_compare_updates: false,
             caching_and_prewarming_enabled: true,
@@ -111,6 +154,11 @@ impl Default for EngineArgs {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            backfill_threshold: DEFAULT_BACKFILL_THRESHOLD,
+            backfill_hysteresis: DEFAULT_BACKFILL_HYSTERESIS,
+            fork_choice_history_size: DEFAULT_FORK_CHOICE_HISTORY_SIZE,
+            deferred_state_root: false,
+            deferred_state_root_window: DEFAULT_DEFERRED_STATE_ROOT_WINDOW,
         }
     }
 }
@@ -121,6 +169,7 @@ impl EngineArgs {
         TreeConfig::default()
             .with_persistence_threshold(self.persistence_threshold)
             .with_memory_block_buffer_target(self.memory_block_buffer_target)
+            .with_in_memory_budget(self.in_memory_budget.map(|budget| budget * 1024 * 1024))
             .with_legacy_state_root(self.legacy_state_root_task_enabled)
             .without_caching_and_prewarming(self.caching_and_prewarming_disabled)
             .with_enable_parallel_sparse_trie(self.parallel_sparse_trie_enabled)
@@ -134,6 +183,13 @@ impl EngineArgs {
             .with_always_process_payload_attributes_on_canonical_head(
                 self.always_process_payload_attributes_on_canonical_head,
             )
+            .with_backfill_thresholds(BackfillThresholds {
+                threshold: self.backfill_threshold,
+                hysteresis: self.backfill_hysteresis,
+            })
+            .with_fork_choice_history_size(self.fork_choice_history_size)
+            .with_deferred_state_root(self.deferred_state_root)
+            .with_deferred_state_root_window(self.deferred_state_root_window)
     }
 }
 
@@ -155,4 +211,29 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_deferred_state_root_args() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.deferred-state-root",
+            "--engine.deferred-state-root-window",
+            "8",
+        ])
+        .args;
+        assert!(args.deferred_state_root);
+        assert_eq!(args.deferred_state_root_window, 8);
+
+        let config = args.tree_config();
+        assert!(config.deferred_state_root());
+        assert_eq!(config.deferred_state_root_window(), 8);
+
+        // the window can't be configured without enabling the mode
+        assert!(CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.deferred-state-root-window",
+            "8",
+        ])
+        .is_err());
+    }
 }
@@ -68,5 +68,9 @@ pub use ress_args::RessArgs;
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ShutdownArgs` for configuring the graceful shutdown sequence.
+mod shutdown;
+pub use shutdown::ShutdownArgs;
+
 mod error;
 pub mod types;
@@ -1,6 +1,7 @@
 //! clap [Args](clap::Args) for network related arguments.
 
 use std::{
+    io,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
@@ -17,6 +18,9 @@ use reth_discv5::{
 };
 use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
 use reth_network::{
+    capture::{
+        CaptureConfig, ProtocolCapture, DEFAULT_MAX_CAPTURE_FILES, DEFAULT_MAX_CAPTURE_FILE_SIZE,
+    },
     transactions::{
         config::TransactionPropagationKind,
         constants::{
@@ -25,19 +29,25 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS, DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
             },
         },
         TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
+    transport::Transport,
     HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
 use tracing::error;
 
+/// The name of the file the trusted peers are persisted to, next to the known peers file.
+const TRUSTED_PEERS_FILE_NAME: &str = "trusted-peers.json";
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -87,7 +97,7 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|publicip|stun|extip:\<IP\>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -149,6 +159,13 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Max byte size of a transaction that is broadcast in full to peers.
+    ///
+    /// Larger transactions, and blob transactions of any size, are only announced by hash and
+    /// fetched by the peers that don't have them yet.
+    #[arg(long = "max-tx-broadcast-size", value_name = "BYTES", default_value_t = DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST, verbatim_doc_comment)]
+    pub max_full_broadcast_transaction_size: usize,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
@@ -167,6 +184,41 @@ pub struct NetworkArgs {
     /// personal nodes, though providers should always opt to enable this flag.
     #[arg(long = "disable-tx-gossip")]
     pub disable_tx_gossip: bool,
+
+    /// Enable serving the `snap/1` protocol
+    ///
+    /// Serves the latest persisted state to peers that snap sync, e.g. geth nodes.
+    #[arg(long = "enable-snap-server")]
+    pub enable_snap_server: bool,
+
+    /// Enable the `poolsync/1` protocol
+    ///
+    /// Exchanges summaries of the transaction pool with newly connected peers and announces the
+    /// transactions they are missing, so that a restarted node repopulates its pool right away.
+    #[arg(long = "enable-pool-sync")]
+    pub enable_pool_sync: bool,
+
+    /// Record the `eth` messages exchanged with peers to rotating capture files in this directory
+    ///
+    /// Every message is recorded in its encoded form with its peer, direction and, for responses,
+    /// the latency since the request. The capture can be decoded and analyzed with
+    /// `reth p2p replay`.
+    #[arg(long = "p2p-capture", value_name = "DIR")]
+    pub p2p_capture: Option<PathBuf>,
+
+    /// Maximum size of a p2p capture file in bytes before it's rotated
+    #[arg(long = "p2p-capture.max-file-size", value_name = "BYTES", default_value_t = DEFAULT_MAX_CAPTURE_FILE_SIZE, requires = "p2p_capture")]
+    pub p2p_capture_max_file_size: u64,
+
+    /// Number of p2p capture files to keep, including the one that is written to
+    #[arg(long = "p2p-capture.max-files", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPTURE_FILES, requires = "p2p_capture")]
+    pub p2p_capture_max_files: usize,
+
+    /// The transport peer connections are established over.
+    ///
+    /// Nodes of one process can be connected in memory with [`Transport::Memory`], e.g. in tests.
+    #[arg(skip)]
+    pub transport: Transport,
 }
 
 impl NetworkArgs {
@@ -209,6 +261,8 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            max_full_broadcast_transaction_size: self.max_full_broadcast_transaction_size,
+            max_blob_prefill_peers: DEFAULT_MAX_COUNT_BLOB_PREFILL_PEERS,
         }
     }
 
@@ -234,6 +288,7 @@ impl NetworkArgs {
         let chain_bootnodes = self
             .resolved_bootnodes()
             .unwrap_or_else(|| chain_spec.bootnodes().unwrap_or_else(mainnet_nodes));
+        let trusted_peers_file = self.persistent_trusted_peers_file(default_peers_file.clone());
         let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
 
         // Configure peer connections
@@ -242,6 +297,13 @@ impl NetworkArgs {
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
+        let peers_config = peers_config
+            .clone()
+            .with_trusted_nodes_file(trusted_peers_file)
+            .unwrap_or_else(|err| {
+                error!(target: "reth::cli", %err, "Failed to load persisted trusted peers");
+                peers_config
+            });
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
@@ -279,6 +341,18 @@ impl NetworkArgs {
                 self.discovery.port,
             ))
             .disable_tx_gossip(self.disable_tx_gossip)
+            .transport(self.transport)
+    }
+
+    /// Starts the [`ProtocolCapture`] if `--p2p-capture` is set.
+    ///
+    /// Returns an error if the capture directory can't be created.
+    pub fn protocol_capture(&self) -> io::Result<Option<ProtocolCapture>> {
+        let Some(dir) = &self.p2p_capture else { return Ok(None) };
+        let config = CaptureConfig::new(dir)
+            .with_max_file_size(self.p2p_capture_max_file_size)
+            .with_max_files(self.p2p_capture_max_files);
+        ProtocolCapture::new(config).map(Some)
     }
 
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
@@ -286,6 +360,17 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the persistent trusted peers
+    /// file, which is stored next to the known peers file.
+    ///
+    /// Trusted peers that are added or removed at runtime via `admin_addTrustedPeer` and
+    /// `admin_removeTrustedPeer` are written to this file when they change and restored on
+    /// startup.
+    pub fn persistent_trusted_peers_file(&self, default_peers_file: PathBuf) -> Option<PathBuf> {
+        let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
+        self.persistent_peers_file(peers_file.with_file_name(TRUSTED_PEERS_FILE_NAME))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -348,9 +433,16 @@ impl Default for NetworkArgs {
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            max_full_broadcast_transaction_size: DEFAULT_MAX_SIZE_TRANSACTION_FULL_BROADCAST,
             net_if: None,
             tx_propagation_policy: TransactionPropagationKind::default(),
             disable_tx_gossip: false,
+            enable_snap_server: false,
+            enable_pool_sync: false,
+            p2p_capture: None,
+            p2p_capture_max_file_size: DEFAULT_MAX_CAPTURE_FILE_SIZE,
+            p2p_capture_max_files: DEFAULT_MAX_CAPTURE_FILES,
+            transport: Transport::Tcp,
         }
     }
 }
@@ -631,6 +723,41 @@ mod tests {
         assert!(args.disable_tx_gossip);
     }
 
+    #[test]
+    fn parse_enable_snap_server_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--enable-snap-server"]).args;
+        assert!(args.enable_snap_server);
+    }
+
+    #[test]
+    fn parse_enable_pool_sync_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--enable-pool-sync"]).args;
+        assert!(args.enable_pool_sync);
+    }
+
+    #[test]
+    fn parse_p2p_capture_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--p2p-capture",
+            "/tmp/capture",
+            "--p2p-capture.max-files",
+            "2",
+        ])
+        .args;
+        assert_eq!(args.p2p_capture, Some(PathBuf::from("/tmp/capture")));
+        assert_eq!(args.p2p_capture_max_files, 2);
+        assert_eq!(args.p2p_capture_max_file_size, DEFAULT_MAX_CAPTURE_FILE_SIZE);
+
+        // the limits require a capture directory
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--p2p-capture.max-files",
+            "2"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn network_args_default_sanity_test() {
         let default_args = NetworkArgs::default();
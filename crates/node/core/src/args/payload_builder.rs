@@ -35,6 +35,30 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum data availability size in bytes of a single transaction included in built
+    /// blocks.
+    #[arg(long = "builder.max-da-tx-size", value_name = "BYTES")]
+    pub max_da_tx_size: Option<u64>,
+
+    /// Maximum total data availability size in bytes of built blocks.
+    #[arg(long = "builder.max-da-block-size", value_name = "BYTES")]
+    pub max_da_block_size: Option<u64>,
+
+    /// Target number of blobs per built block.
+    ///
+    /// Blob transactions are no longer included once the target is reached, even if the
+    /// protocol would allow more blobs per block.
+    #[arg(long = "builder.target-blobs", value_name = "COUNT")]
+    pub target_blob_count: Option<u64>,
+
+    /// Number of blocks within which the blob transactions in the pool should be included at the
+    /// target blob count.
+    ///
+    /// If the blob demand of the pool can't be included at the target within these blocks, built
+    /// blocks include blobs up to the protocol limit.
+    #[arg(long = "builder.blob-backlog-blocks", value_name = "BLOCKS", requires = "target_blob_count")]
+    pub blob_backlog_blocks: Option<u64>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +69,10 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            max_da_tx_size: None,
+            max_da_block_size: None,
+            target_blob_count: None,
+            blob_backlog_blocks: None,
         }
     }
 }
@@ -69,6 +97,22 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn max_da_tx_size(&self) -> Option<u64> {
+        self.max_da_tx_size
+    }
+
+    fn max_da_block_size(&self) -> Option<u64> {
+        self.max_da_block_size
+    }
+
+    fn target_blob_count(&self) -> Option<u64> {
+        self.target_blob_count
+    }
+
+    fn blob_backlog_blocks(&self) -> Option<u64> {
+        self.blob_backlog_blocks
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -128,6 +172,43 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_da_limits() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.max-da-tx-size",
+            "1000",
+            "--builder.max-da-block-size",
+            "100000",
+            "--builder.target-blobs",
+            "3",
+        ])
+        .args;
+        assert_eq!(args.max_da_tx_size, Some(1000));
+        assert_eq!(args.max_da_block_size, Some(100000));
+        assert_eq!(args.target_blob_count, Some(3));
+    }
+
+    #[test]
+    fn test_args_with_blob_backlog_blocks() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.target-blobs",
+            "3",
+            "--builder.blob-backlog-blocks",
+            "4",
+        ])
+        .args;
+        assert_eq!(args.blob_backlog_blocks, Some(4));
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.blob-backlog-blocks",
+            "4",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extra_data() {
         let extra_data = default_extra_data();
@@ -127,7 +127,9 @@ impl PruningArgs {
                     // TODO: set default to pre-merge block if available
                     bodies_history: None,
                     receipts_log_filter: Default::default(),
+                    receipts_log_topic_filter: Default::default(),
                 },
+                schedule: config.schedule,
             }
         }
 
@@ -5,6 +5,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_primitives::Address;
@@ -13,6 +14,7 @@ use clap::{
     builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use humantime::parse_duration;
 use rand::Rng;
 use reth_cli_util::parse_ether_value;
 use reth_rpc_eth_types::builder::config::PendingBlockKind;
@@ -141,6 +143,22 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Path to a JSON file mapping API keys to the namespaces and methods they are allowed to call
+    /// on the regular RPC server(s), see `--http.api` and `--ws.api`.
+    ///
+    /// Keys are passed in the `X-Api-Key` header. Requests without a key are restricted to the
+    /// `public` entry of the file, or rejected if there is none.
+    #[arg(long = "rpc.api-keys", value_name = "PATH", global = true, required = false)]
+    pub rpc_api_keys: Option<PathBuf>,
+
+    /// Path to a JSON file with the allowed CORS domains of the HTTP and WS servers, of the form
+    /// `{"http": "https://*.example.com", "ws": "*"}`.
+    ///
+    /// This overrides `--http.corsdomain` and `--ws.origins`. On unix, the file is reloaded when
+    /// the node receives `SIGHUP`, which updates the allowed origins without a restart.
+    #[arg(long = "rpc.cors", value_name = "PATH", global = true, required = false)]
+    pub rpc_cors: Option<PathBuf>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -157,6 +175,16 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
 
+    /// Maximum number of calls of a batch request that are executed concurrently.
+    #[arg(long = "rpc.batch-parallelism", value_name = "COUNT", default_value_t = constants::DEFAULT_BATCH_PARALLELISM, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub rpc_batch_parallelism: usize,
+
+    /// Maximum total cost of the calls of a batch request. Calls that are executed on the blocking
+    /// task pool, like `eth_call` or tracing calls, cost 10, all other calls cost 1. Calls over
+    /// budget are rejected. (0 = no limit)
+    #[arg(long = "rpc.max-batch-cost", value_name = "COST", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_max_batch_cost: ZeroAsNoneU64,
+
     /// Maximum number of concurrent tracing requests.
     ///
     /// By default this chooses a sensible value based on the number of available cores.
@@ -178,6 +206,12 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum estimated cost of a logs query, i.e. the estimated number of blocks in the range
+    /// whose receipts must be scanned. Queries over budget are rejected with a suggested narrower
+    /// range. (0 = no limit)
+    #[arg(long = "rpc.max-log-query-cost", alias = "rpc-max-log-query-cost", value_name = "COST", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_max_log_query_cost: ZeroAsNoneU64,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -198,6 +232,26 @@ pub struct RpcServerArgs {
     )]
     pub rpc_tx_fee_cap: u128,
 
+    /// Maximum execution time of `eth_call`, `eth_callMany`, `eth_estimateGas`,
+    /// `eth_createAccessList` and `eth_simulateV1` requests, e.g. `5s`. Unlimited by default.
+    ///
+    /// Can be adjusted at runtime with `reth_setRpcLimits`.
+    #[arg(long = "rpc.eth-timeout", value_parser = parse_duration, value_name = "DURATION")]
+    pub rpc_eth_timeout: Option<Duration>,
+
+    /// Maximum execution time of `debug_trace*` requests, e.g. `30s`. Unlimited by default.
+    ///
+    /// Can be adjusted at runtime with `reth_setRpcLimits`.
+    #[arg(long = "rpc.debug-timeout", value_parser = parse_duration, value_name = "DURATION")]
+    pub rpc_debug_timeout: Option<Duration>,
+
+    /// Maximum execution time of `trace_*` requests that execute transactions, e.g. `30s`.
+    /// Unlimited by default.
+    ///
+    /// Can be adjusted at runtime with `reth_setRpcLimits`.
+    #[arg(long = "rpc.trace-timeout", value_parser = parse_duration, value_name = "DURATION")]
+    pub rpc_trace_timeout: Option<Duration>,
+
     /// Maximum number of blocks for `eth_simulateV1` call.
     #[arg(
         long = "rpc.max-simulate-blocks",
@@ -216,6 +270,14 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Maximum number of blocks below the tip at which state can be queried on full nodes whose
+    /// state history is pruned, e.g. by `eth_call` or `eth_getBalance`.
+    ///
+    /// Queries for older blocks, or for blocks whose history has already been pruned, are rejected
+    /// with the earliest block that can be queried. Unlimited by default.
+    #[arg(long = "rpc.max-state-history", value_name = "BLOCKS")]
+    pub rpc_max_state_history: Option<u64>,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
@@ -227,6 +289,13 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.pending-block", default_value = "full", value_name = "KIND")]
     pub rpc_pending_block: PendingBlockKind,
 
+    /// Includes reth specific sync progress in `eth_syncing` responses.
+    ///
+    /// The response is extended with the current pipeline stage, the number of entities the
+    /// stage processed and has to process, and an estimated completion time of the stage.
+    #[arg(long = "rpc.syncing-progress")]
+    pub rpc_syncing_progress: bool,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
@@ -359,19 +428,29 @@ impl Default for RpcServerArgs {
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             disable_auth_server: false,
             rpc_jwtsecret: None,
+            rpc_api_keys: None,
+            rpc_cors: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
+            rpc_batch_parallelism: constants::DEFAULT_BATCH_PARALLELISM,
+            rpc_max_batch_cost: ZeroAsNoneU64(None),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_log_query_cost: ZeroAsNoneU64(None),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
+            rpc_eth_timeout: None,
+            rpc_debug_timeout: None,
+            rpc_trace_timeout: None,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_max_state_history: None,
             rpc_pending_block: PendingBlockKind::Full,
+            rpc_syncing_progress: false,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_HEADER_CACHE_SIZE_BYTES_MB,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
 };
 
 /// Parameters to configure RPC state cache.
@@ -30,6 +31,27 @@ pub struct RpcStateCacheArgs {
     )]
     pub max_headers: u32,
 
+    /// Max memory of the cached blocks in megabytes.
+    #[arg(
+        long = "rpc-cache.max-blocks-size",
+        default_value_t = DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_blocks_size: usize,
+
+    /// Max memory of the cached receipts in megabytes.
+    #[arg(
+        long = "rpc-cache.max-receipts-size",
+        default_value_t = DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_receipts_size: usize,
+
+    /// Max memory of the cached headers in megabytes.
+    #[arg(
+        long = "rpc-cache.max-headers-size",
+        default_value_t = DEFAULT_HEADER_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_headers_size: usize,
+
     /// Max number of concurrent database requests.
     #[arg(
         long = "rpc-cache.max-concurrent-db-requests",
@@ -53,6 +75,9 @@ impl Default for RpcStateCacheArgs {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
+            max_blocks_size: DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB,
+            max_receipts_size: DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+            max_headers_size: DEFAULT_HEADER_CACHE_SIZE_BYTES_MB,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
@@ -0,0 +1,96 @@
+//! clap [Args](clap::Args) for graceful shutdown configuration
+
+use clap::Args;
+use humantime::parse_duration;
+use std::time::Duration;
+
+/// Default time to wait for the RPC servers to stop accepting requests.
+const DEFAULT_SHUTDOWN_RPC_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Default time to wait for the engine to process in-flight messages.
+const DEFAULT_SHUTDOWN_ENGINE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time to wait for the transaction pool and fee history cache to be written to disk.
+const DEFAULT_SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time to wait for in-flight database and static file writes to finish.
+const DEFAULT_SHUTDOWN_STATIC_FILES_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Parameters for configuring the graceful shutdown sequence of the node.
+///
+/// On shutdown the node stops accepting RPC requests, drains in-flight engine messages, flushes
+/// the transaction pool and fee history cache to disk and waits for in-flight static file writes,
+/// in that order. Each step is bounded by its own timeout.
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Shutdown")]
+pub struct ShutdownArgs {
+    /// Maximum time to wait for the RPC servers to stop accepting requests.
+    #[arg(long = "shutdown.rpc-timeout", value_parser = parse_duration, default_value = "1s", value_name = "DURATION")]
+    pub rpc_timeout: Duration,
+
+    /// Maximum time to wait for the engine to process in-flight engine API messages.
+    #[arg(long = "shutdown.engine-timeout", value_parser = parse_duration, default_value = "5s", value_name = "DURATION")]
+    pub engine_timeout: Duration,
+
+    /// Maximum time to wait for the transaction pool journal and fee history cache to be
+    /// written to disk.
+    #[arg(long = "shutdown.flush-timeout", value_parser = parse_duration, default_value = "5s", value_name = "DURATION")]
+    pub flush_timeout: Duration,
+
+    /// Maximum time to wait for in-flight database and static file writes to finish.
+    #[arg(long = "shutdown.static-files-timeout", value_parser = parse_duration, default_value = "15s", value_name = "DURATION")]
+    pub static_files_timeout: Duration,
+}
+
+impl ShutdownArgs {
+    /// Returns the maximum time the whole shutdown sequence can take.
+    pub fn total_timeout(&self) -> Duration {
+        self.rpc_timeout + self.engine_timeout + self.flush_timeout + self.static_files_timeout
+    }
+}
+
+impl Default for ShutdownArgs {
+    fn default() -> Self {
+        Self {
+            rpc_timeout: DEFAULT_SHUTDOWN_RPC_TIMEOUT,
+            engine_timeout: DEFAULT_SHUTDOWN_ENGINE_TIMEOUT,
+            flush_timeout: DEFAULT_SHUTDOWN_FLUSH_TIMEOUT,
+            static_files_timeout: DEFAULT_SHUTDOWN_STATIC_FILES_TIMEOUT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn shutdown_args_default_sanity_test() {
+        let default_args = ShutdownArgs::default();
+        let args = CommandParser::<ShutdownArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_shutdown_args() {
+        let args = CommandParser::<ShutdownArgs>::parse_from([
+            "reth",
+            "--shutdown.rpc-timeout",
+            "500ms",
+            "--shutdown.static-files-timeout",
+            "1m",
+        ])
+        .args;
+        assert_eq!(args.rpc_timeout, Duration::from_millis(500));
+        assert_eq!(args.static_files_timeout, Duration::from_secs(60));
+        assert_eq!(args.total_timeout(), Duration::from_millis(70_500));
+    }
+}
@@ -50,4 +50,12 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The sender transactions index stage within the pipeline.
+    ///
+    /// Indexes the transactions sent by each address.
+    SenderTxs,
+    /// The contract deployments index stage within the pipeline.
+    ///
+    /// Indexes the contracts deployed with each code hash.
+    ContractDeployments,
 }
@@ -103,6 +103,13 @@ pub struct TxPoolArgs {
     /// Number of additional transaction validation tasks to spawn.
     #[arg(long = "txpool.additional-validation-tasks", alias = "txpool.additional_validation_tasks", default_value_t = DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS)]
     pub additional_validation_tasks: usize,
+    /// Validate the balance of the sender against the pending view of the pool.
+    ///
+    /// Transactions whose cost exceeds the latest balance of the sender are accepted if the
+    /// balance suffices once the pending transactions of the pool are applied, e.g. transfers
+    /// from an account that is funded by a pending transaction.
+    #[arg(long = "txpool.pending-balance-check")]
+    pub pending_balance_check: bool,
 
     /// Maximum number of pending transactions from the network to buffer
     #[arg(long = "txpool.max-pending-txns", alias = "txpool.max_pending_txns", default_value_t = PENDING_TX_LISTENER_BUFFER_SIZE)]
@@ -121,6 +128,26 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
 
+    /// Maximum number of nonces a non-local transaction may be ahead of the sender's on-chain
+    /// nonce. Transactions exceeding this gap are rejected.
+    #[arg(long = "txpool.max-nonce-gap", value_name = "NONCES")]
+    pub max_nonce_gap: Option<u64>,
+
+    /// Maximum amount of time non-local transactions with a nonce gap are queued.
+    ///
+    /// The lifetime decays with the distance to the on-chain nonce: a transaction `n` nonces ahead
+    /// is evicted after `lifetime / n`.
+    #[arg(long = "txpool.nonce-gap-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub nonce_gap_lifetime: Option<Duration>,
+
+    /// Number of blocks a local transaction can remain pending before it is re-broadcast.
+    #[arg(long = "txpool.local-rebroadcast-blocks", value_name = "BLOCKS")]
+    pub local_rebroadcast_blocks: Option<u64>,
+
+    /// Maximum amount of time local transactions are kept in the pool before they are dropped.
+    #[arg(long = "txpool.local-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub local_lifetime: Option<Duration>,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH")]
     pub transactions_backup_path: Option<std::path::PathBuf>,
@@ -163,10 +190,15 @@ impl Default for TxPoolArgs {
             locals: Default::default(),
             no_local_transactions_propagation: false,
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+            pending_balance_check: false,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_nonce_gap: None,
+            nonce_gap_lifetime: None,
+            local_rebroadcast_blocks: None,
+            local_lifetime: None,
             transactions_backup_path: None,
             disable_transactions_backup: false,
             max_batch_size: 1,
@@ -204,7 +236,9 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
+                replace_blob_fee_price_bump: self.blob_transaction_price_bump,
             },
+            replacement_policy: None,
             minimal_protocol_basefee: self.minimal_protocol_basefee,
             minimum_priority_fee: self.minimum_priority_fee,
             gas_limit: self.enforced_gas_limit,
@@ -212,6 +246,10 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            max_nonce_gap: self.max_nonce_gap,
+            nonce_gap_lifetime: self.nonce_gap_lifetime,
+            local_rebroadcast_blocks: self.local_rebroadcast_blocks,
+            local_lifetime: self.local_lifetime,
         }
     }
 
@@ -270,4 +308,40 @@ mod tests {
 
         assert!(result.is_err(), "Expected an error for invalid duration");
     }
+
+    #[test]
+    fn txpool_parse_nonce_gap_limits() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.max-nonce-gap",
+            "64",
+            "--txpool.nonce-gap-lifetime",
+            "600",
+        ])
+        .args;
+        assert_eq!(args.max_nonce_gap, Some(64));
+        assert_eq!(args.nonce_gap_lifetime, Some(Duration::from_secs(600)));
+
+        let config = args.pool_config();
+        assert_eq!(config.max_nonce_gap, Some(64));
+        assert_eq!(config.nonce_gap_lifetime, Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn txpool_parse_local_rebroadcast() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.local-rebroadcast-blocks",
+            "5",
+            "--txpool.local-lifetime",
+            "3600",
+        ])
+        .args;
+        assert_eq!(args.local_rebroadcast_blocks, Some(5));
+        assert_eq!(args.local_lifetime, Some(Duration::from_secs(3600)));
+
+        let config = args.pool_config();
+        assert_eq!(config.local_rebroadcast_blocks, Some(5));
+        assert_eq!(config.local_lifetime, Some(Duration::from_secs(3600)));
+    }
 }
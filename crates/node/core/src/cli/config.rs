@@ -38,6 +38,27 @@ pub trait PayloadBuilderConfig {
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
 
+    /// Maximum data availability size of a single transaction included in built blocks.
+    fn max_da_tx_size(&self) -> Option<u64> {
+        None
+    }
+
+    /// Maximum total data availability size of built blocks.
+    fn max_da_block_size(&self) -> Option<u64> {
+        None
+    }
+
+    /// Target number of blobs per built block.
+    fn target_blob_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Number of blocks within which the blob demand of the pool should be included at the target
+    /// blob count.
+    fn blob_backlog_blocks(&self) -> Option<u64> {
+        None
+    }
+
     /// Returns the configured gas limit if set, or a chain-specific default.
     fn gas_limit_for(&self, chain: Chain) -> u64 {
         if let Some(limit) = self.gas_limit() {
@@ -184,14 +184,18 @@ pub struct MaybePlatformPath<D>(Option<PlatformPath<D>>);
 
 impl<D: XdgPath> MaybePlatformPath<D> {
     /// Returns the path if it is set, otherwise returns the default path for the given chain.
+    ///
+    /// If [`DatadirArgs::namespace`] is set, the namespace subdirectory of that path is returned
+    /// instead.
     pub fn unwrap_or_chain_default(&self, chain: Chain, datadir_args: DatadirArgs) -> ChainPath<D> {
-        ChainPath(
-            self.0
-                .clone()
-                .unwrap_or_else(|| PlatformPath::default().platform_path_from_chain(chain)),
-            chain,
-            datadir_args,
-        )
+        let mut path = self
+            .0
+            .clone()
+            .unwrap_or_else(|| PlatformPath::default().platform_path_from_chain(chain));
+        if let Some(namespace) = &datadir_args.namespace {
+            path = path.join(namespace);
+        }
+        ChainPath(path, chain, datadir_args)
     }
 
     /// Returns the default platform path for the specified [Chain].
@@ -301,6 +305,13 @@ impl<D> ChainPath<D> {
         }
     }
 
+    /// Returns the path to the database storage statistics history file for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/db-stats-history.json`
+    pub fn db_stats_history(&self) -> PathBuf {
+        self.data_dir().join("db-stats-history.json")
+    }
+
     /// Returns the path to the reth p2p secret key for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/discovery-secret`
@@ -330,6 +341,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the fee history cache backup file for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/fee-history-cache.json`
+    pub fn fee_history_cache(&self) -> PathBuf {
+        self.data_dir().join("fee-history-cache.json")
+    }
+
+    /// Returns the path to the fork choice history log of the engine for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/fork-choice-history.jsonl`
+    pub fn fork_choice_history(&self) -> PathBuf {
+        self.data_dir().join("fork-choice-history.jsonl")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -403,4 +428,19 @@ mod tests {
         let path = path.unwrap_or_chain_default(Chain::sepolia(), DatadirArgs::default());
         assert!(path.as_ref().ends_with("reth/sepolia"), "{path:?}");
     }
+
+    #[test]
+    fn test_namespaced_datadir_path() {
+        let datadir_args =
+            DatadirArgs { namespace: Some("instance-2".to_string()), ..Default::default() };
+
+        let path = MaybePlatformPath::<DataDirPath>::default();
+        let path = path.unwrap_or_chain_default(Chain::mainnet(), datadir_args.clone());
+        assert!(path.as_ref().ends_with("reth/mainnet/instance-2"), "{path:?}");
+
+        let path = MaybePlatformPath::<DataDirPath>::from_str("my/path/to/datadir").unwrap();
+        let path = path.unwrap_or_chain_default(Chain::mainnet(), datadir_args);
+        assert!(path.as_ref().ends_with("my/path/to/datadir/instance-2"), "{path:?}");
+        assert!(path.db().ends_with("my/path/to/datadir/instance-2/db"));
+    }
 }
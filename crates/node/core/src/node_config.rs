@@ -3,7 +3,7 @@
 use crate::{
     args::{
         DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        PruningArgs, RpcServerArgs, ShutdownArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -154,6 +154,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
+
+    /// All shutdown related arguments with --shutdown prefix
+    pub shutdown: ShutdownArgs,
+
+    /// Verify all static files against their checksum manifests on startup.
+    pub verify_static_files: bool,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -184,6 +190,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            shutdown: ShutdownArgs::default(),
+            verify_static_files: false,
         }
     }
 
@@ -236,6 +244,27 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the namespace of the node within the data dir, see [`DatadirArgs::namespace`]
+    pub fn with_datadir_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.datadir.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Configures the node to be co-hosted with other nodes in the same process.
+    ///
+    /// The ports of the node are derived from the given instance, see [`Self::instance`], and its
+    /// data is stored in the `instance-<instance>` namespace of the data dir. Co-hosted nodes
+    /// that are configured with the same metrics address share the metrics endpoint.
+    pub fn co_hosted(self, instance: u16) -> Self {
+        self.with_instance(instance).with_datadir_namespace(format!("instance-{instance}"))
+    }
+
+    /// Set whether to verify all static files against their checksum manifests on startup
+    pub const fn with_verify_static_files(mut self, verify_static_files: bool) -> Self {
+        self.verify_static_files = verify_static_files;
+        self
+    }
+
     /// Returns the instance value, defaulting to 1 if not set.
     pub fn get_instance(&self) -> u16 {
         self.instance.unwrap_or(1)
@@ -289,6 +318,12 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the shutdown args for the node
+    pub const fn with_shutdown(mut self, shutdown: ShutdownArgs) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -490,6 +525,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            shutdown: self.shutdown,
+            verify_static_files: self.verify_static_files,
         }
     }
 
@@ -527,6 +564,8 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            shutdown: self.shutdown,
+            verify_static_files: self.verify_static_files,
         }
     }
 }
@@ -6,11 +6,90 @@ use crate::{
 };
 use eyre::WrapErr;
 use http::{header::CONTENT_TYPE, HeaderValue, Response};
-use metrics::describe_gauge;
+use metrics::{
+    describe_gauge, Counter, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder,
+    SharedString,
+};
 use metrics_process::Collector;
+use parking_lot::{Mutex, RwLock};
 use reth_metrics::metrics::Unit;
 use reth_tasks::TaskExecutor;
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, LazyLock},
+};
+
+/// The hooks of all metric servers that are running in this process, by listen address.
+///
+/// Nodes that are co-hosted in one process share the metrics endpoint: the first node starts the
+/// server, all other nodes register their hooks with it.
+static RUNNING_SERVERS: LazyLock<Mutex<HashMap<SocketAddr, Arc<RwLock<Vec<NodeHooks>>>>>> =
+    LazyLock::new(Default::default);
+
+/// The hooks of a node that reports to a metrics endpoint.
+#[derive(Debug)]
+struct NodeHooks {
+    hooks: Hooks,
+    instance: Option<u16>,
+}
+
+impl NodeHooks {
+    /// Runs the hooks, labeling the metrics they record with the instance of the node, if any.
+    fn run(&self) {
+        let Some(instance) = self.instance else {
+            return self.hooks.iter().for_each(|hook| hook())
+        };
+
+        metrics::with_recorder(|recorder| {
+            let recorder = LabeledRecorder {
+                inner: recorder,
+                label: Label::new("instance", instance.to_string()),
+            };
+            metrics::with_local_recorder(&recorder, || self.hooks.iter().for_each(|hook| hook()))
+        })
+    }
+}
+
+/// A [`Recorder`] that adds a label to all metrics before registering them with the inner
+/// recorder.
+struct LabeledRecorder<'a> {
+    inner: &'a dyn Recorder,
+    label: Label,
+}
+
+impl LabeledRecorder<'_> {
+    fn key(&self, key: &Key) -> Key {
+        key.with_extra_labels(vec![self.label.clone()])
+    }
+}
+
+impl Recorder for LabeledRecorder<'_> {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_counter(key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_gauge(key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_histogram(key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        self.inner.register_counter(&self.key(key), metadata)
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        self.inner.register_gauge(&self.key(key), metadata)
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        self.inner.register_histogram(&self.key(key), metadata)
+    }
+}
 
 /// Configuration for the [`MetricServer`]
 #[derive(Debug)]
@@ -20,6 +99,7 @@ pub struct MetricServerConfig {
     chain_spec_info: ChainSpecInfo,
     task_executor: TaskExecutor,
     hooks: Hooks,
+    instance: Option<u16>,
 }
 
 impl MetricServerConfig {
@@ -31,7 +111,16 @@ impl MetricServerConfig {
         task_executor: TaskExecutor,
         hooks: Hooks,
     ) -> Self {
-        Self { listen_addr, hooks, task_executor, version_info, chain_spec_info }
+        Self { listen_addr, hooks, task_executor, version_info, chain_spec_info, instance: None }
+    }
+
+    /// Sets the instance of the node.
+    ///
+    /// The metrics recorded by the hooks are labeled with the instance, so that nodes that share
+    /// the metrics endpoint don't overwrite each other's metrics.
+    pub const fn with_instance(mut self, instance: Option<u16>) -> Self {
+        self.instance = instance;
+        self
     }
 }
 
@@ -48,18 +137,44 @@ impl MetricServer {
     }
 
     /// Spawns the metrics server
+    ///
+    /// If a metrics server is already running at the configured address in this process, the
+    /// hooks are registered with the running server instead.
     pub async fn serve(&self) -> eyre::Result<()> {
-        let MetricServerConfig { listen_addr, hooks, task_executor, version_info, chain_spec_info } =
-            &self.config;
+        let MetricServerConfig {
+            listen_addr,
+            hooks,
+            instance,
+            task_executor,
+            version_info,
+            chain_spec_info,
+        } = &self.config;
+        let node_hooks = NodeHooks { hooks: hooks.clone(), instance: *instance };
+
+        // Look up the running server and bind the listener under the same lock, so that nodes
+        // starting concurrently can't both try to start a server at the address.
+        let (listener, all_hooks) = {
+            let mut running = RUNNING_SERVERS.lock();
+            if let Some(all_hooks) = running.get(listen_addr) {
+                all_hooks.write().push(node_hooks);
+                chain_spec_info.register_chain_spec_metrics();
+                return Ok(())
+            }
+
+            let listener = bind(*listen_addr).wrap_err_with(|| {
+                format!("Could not start Prometheus endpoint at {listen_addr}")
+            })?;
+            let all_hooks = Arc::new(RwLock::new(vec![node_hooks]));
+            running.insert(*listen_addr, all_hooks.clone());
+            (listener, all_hooks)
+        };
 
-        let hooks = hooks.clone();
         self.start_endpoint(
             *listen_addr,
-            Arc::new(move || hooks.iter().for_each(|hook| hook())),
+            listener,
+            Arc::new(move || all_hooks.read().iter().for_each(NodeHooks::run)),
             task_executor.clone(),
-        )
-        .await
-        .wrap_err_with(|| format!("Could not start Prometheus endpoint at {listen_addr}"))?;
+        );
 
         // Describe metrics after recorder installation
         describe_db_metrics();
@@ -74,16 +189,13 @@ impl MetricServer {
         Ok(())
     }
 
-    async fn start_endpoint<F: Hook + 'static>(
+    fn start_endpoint<F: Hook + 'static>(
         &self,
         listen_addr: SocketAddr,
+        listener: tokio::net::TcpListener,
         hook: Arc<F>,
         task_executor: TaskExecutor,
-    ) -> eyre::Result<()> {
-        let listener = tokio::net::TcpListener::bind(listen_addr)
-            .await
-            .wrap_err("Could not bind to address")?;
-
+    ) {
         task_executor.spawn_with_graceful_shutdown_signal(|mut signal| {
             Box::pin(async move {
                 loop {
@@ -123,13 +235,22 @@ impl MetricServer {
                         .inspect_err(|error| tracing::debug!(%error, "failed to serve request"));
                     });
                 }
+
+                RUNNING_SERVERS.lock().remove(&listen_addr);
             })
         });
-
-        Ok(())
     }
 }
 
+/// Binds a listener to the given address without yielding, so it can be done while holding the
+/// lock on [`RUNNING_SERVERS`].
+fn bind(listen_addr: SocketAddr) -> eyre::Result<tokio::net::TcpListener> {
+    let listener =
+        std::net::TcpListener::bind(listen_addr).wrap_err("Could not bind to address")?;
+    listener.set_nonblocking(true)?;
+    Ok(tokio::net::TcpListener::from_std(listener)?)
+}
+
 fn describe_db_metrics() {
     describe_gauge!("db.table_size", Unit::Bytes, "The size of a database table (in bytes)");
     describe_gauge!("db.table_pages", "The number of database pages for a table");
@@ -256,4 +377,50 @@ mod tests {
         assert!(body.contains("reth_process_cpu_seconds_total"));
         assert!(body.contains("reth_process_start_time_seconds"));
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_shared_metrics_endpoint() {
+        let version_info = VersionInfo {
+            version: "test",
+            build_timestamp: "test",
+            cargo_features: "test",
+            git_sha: "test",
+            target_triple: "test",
+            build_profile: "test",
+        };
+
+        let tasks = TaskManager::current();
+        let executor = tasks.executor();
+
+        let listen_addr = get_random_available_addr();
+
+        // start two nodes concurrently, each reporting the same gauge
+        let nodes = [1u16, 2].map(|instance| {
+            let hooks = Hooks::builder()
+                .with_hook(move || metrics::gauge!("test.shared_endpoint").set(f64::from(instance)))
+                .build();
+            let config = MetricServerConfig::new(
+                listen_addr,
+                version_info.clone(),
+                ChainSpecInfo { name: format!("l{instance}") },
+                executor.clone(),
+                hooks,
+            )
+            .with_instance(Some(instance));
+            tokio::spawn(async move { MetricServer::new(config).serve().await })
+        });
+        for node in nodes {
+            node.await.unwrap().unwrap();
+        }
+
+        let url = format!("http://{listen_addr}");
+        let response = Client::new().get(&url).send().await.unwrap();
+        assert!(response.status().is_success());
+
+        // the gauges of both nodes are reported by the shared endpoint without overwriting each
+        // other
+        let body = response.text().await.unwrap();
+        assert!(body.contains(r#"reth_test_shared_endpoint{instance="1"} 1"#), "{body}");
+        assert!(body.contains(r#"reth_test_shared_endpoint{instance="2"} 2"#), "{body}");
+    }
 }
@@ -31,7 +31,7 @@ use reth_node_builder::{
         EngineValidatorBuilder, EthApiBuilder, Identity, PayloadValidatorBuilder, RethRpcAddOns,
         RethRpcMiddleware, RethRpcServerHandles, RpcAddOns, RpcContext, RpcHandle,
     },
-    BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder,
+    BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder, PayloadBuilderConfig,
 };
 use reth_optimism_chainspec::{OpChainSpec, OpHardfork};
 use reth_optimism_consensus::OpBeaconConsensus;
@@ -1050,6 +1050,16 @@ where
         pool: Pool,
         evm_config: Evm,
     ) -> eyre::Result<Self::PayloadBuilder> {
+        // seed the shared DA config with the limits configured on the command line, these can
+        // still be adjusted at runtime via `miner_setMaxDASize`
+        let conf = ctx.payload_builder_config();
+        if let Some(max_da_tx_size) = conf.max_da_tx_size() {
+            self.da_config.set_max_tx_size(max_da_tx_size);
+        }
+        if let Some(max_da_block_size) = conf.max_da_block_size() {
+            self.da_config.set_max_block_size(max_da_block_size);
+        }
+
         let payload_builder = reth_optimism_payload_builder::OpPayloadBuilder::with_builder_config(
             pool,
             ctx.provider().clone(),
@@ -4,6 +4,7 @@ use reth_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall},
     FromEvmError, RpcConvert,
 };
+use reth_rpc_eth_types::RpcLimitsHandle;
 
 impl<N, Rpc> EthCall for OpEthApi<N, Rpc>
 where
@@ -29,11 +30,16 @@ where
 {
     #[inline]
     fn call_gas_limit(&self) -> u64 {
-        self.inner.eth_api.gas_cap()
+        self.inner.eth_api.limits().gas_cap()
     }
 
     #[inline]
     fn max_simulate_blocks(&self) -> u64 {
         self.inner.eth_api.max_simulate_blocks()
     }
+
+    #[inline]
+    fn rpc_limits(&self) -> RpcLimitsHandle {
+        self.inner.eth_api.limits().clone()
+    }
 }
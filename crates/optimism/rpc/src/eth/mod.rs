@@ -28,7 +28,7 @@ use reth_rpc_eth_api::{
     EthApiTypes, FromEvmError, FullEthApiServer, RpcConvert, RpcConverter, RpcNodeCore,
     RpcNodeCoreExt, RpcTypes, SignableTxRequest,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle, SyncProgressTracker};
 use reth_storage_api::{ProviderHeader, ProviderTx};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
@@ -161,6 +161,11 @@ where
     fn signers(&self) -> &SignersForApi<Self> {
         self.inner.eth_api.signers()
     }
+
+    #[inline]
+    fn sync_progress(&self) -> Option<&SyncProgressTracker> {
+        self.inner.eth_api.sync_progress()
+    }
 }
 
 impl<N, Rpc> SpawnBlocking for OpEthApi<N, Rpc>
@@ -211,6 +216,9 @@ where
     N: RpcNodeCore,
     Rpc: RpcConvert<Primitives = N::Primitives>,
 {
+    fn max_state_history(&self) -> Option<u64> {
+        self.inner.eth_api.limits().max_state_history()
+    }
 }
 
 impl<N, Rpc> EthState for OpEthApi<N, Rpc>
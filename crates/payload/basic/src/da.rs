@@ -0,0 +1,215 @@
+//! Data availability limits for built payloads.
+
+/// Data availability constraints that are enforced while filling a payload with pool transactions.
+///
+/// This is intended for chains with sequencer DA constraints, where the amount of data that can be
+/// posted per block is limited independently of the block gas limit.
+///
+/// All limits are optional, `None` means no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadDaLimits {
+    /// Don't include any transaction with a DA size larger than this.
+    pub max_da_tx_size: Option<u64>,
+    /// Maximum total DA size of all transactions included in a payload.
+    pub max_da_block_size: Option<u64>,
+    /// Target number of blobs per payload.
+    ///
+    /// If set, the builder stops including blob transactions once the target is reached, even if
+    /// the protocol would allow more blobs per block.
+    pub target_blob_count: Option<u64>,
+}
+
+impl PayloadDaLimits {
+    /// Creates new [`PayloadDaLimits`] without any limits.
+    pub const fn new() -> Self {
+        Self { max_da_tx_size: None, max_da_block_size: None, target_blob_count: None }
+    }
+
+    /// Sets the maximum DA size per transaction.
+    pub const fn with_max_da_tx_size(mut self, max_da_tx_size: u64) -> Self {
+        self.max_da_tx_size = Some(max_da_tx_size);
+        self
+    }
+
+    /// Sets the maximum DA size per payload.
+    pub const fn with_max_da_block_size(mut self, max_da_block_size: u64) -> Self {
+        self.max_da_block_size = Some(max_da_block_size);
+        self
+    }
+
+    /// Sets the target number of blobs per payload.
+    pub const fn with_target_blob_count(mut self, target_blob_count: u64) -> Self {
+        self.target_blob_count = Some(target_blob_count);
+        self
+    }
+
+    /// Returns true if no limit is configured.
+    pub const fn is_empty(&self) -> bool {
+        self.max_da_tx_size.is_none() &&
+            self.max_da_block_size.is_none() &&
+            self.target_blob_count.is_none()
+    }
+
+    /// Returns the effective number of blobs allowed in a payload, given the protocol's maximum
+    /// blob count for the block.
+    pub fn blob_limit(&self, max_blob_count: u64) -> u64 {
+        self.target_blob_count.map_or(max_blob_count, |target| target.min(max_blob_count))
+    }
+
+    /// Returns a new [`DaBudget`] that tracks the DA usage of a single payload against these
+    /// limits.
+    pub const fn budget(&self, max_blob_count: u64) -> DaBudget {
+        DaBudget { limits: *self, max_blob_count, da_size_used: 0, blob_count_used: 0 }
+    }
+}
+
+/// Tracks the DA usage of a payload that is being built against the configured
+/// [`PayloadDaLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaBudget {
+    /// The configured limits.
+    limits: PayloadDaLimits,
+    /// The protocol's maximum blob count for the block.
+    max_blob_count: u64,
+    /// The cumulative DA size of all included transactions.
+    da_size_used: u64,
+    /// The cumulative number of blobs of all included transactions.
+    blob_count_used: u64,
+}
+
+impl DaBudget {
+    /// Returns the cumulative DA size of all included transactions.
+    pub const fn da_size_used(&self) -> u64 {
+        self.da_size_used
+    }
+
+    /// Returns the cumulative number of blobs of all included transactions.
+    pub const fn blob_count_used(&self) -> u64 {
+        self.blob_count_used
+    }
+
+    /// Returns the number of blobs that can be included in the payload.
+    pub fn blob_limit(&self) -> u64 {
+        self.limits.blob_limit(self.max_blob_count)
+    }
+
+    /// Returns true if no more blobs can be included in the payload.
+    pub fn is_blob_limit_reached(&self) -> bool {
+        self.blob_count_used >= self.blob_limit()
+    }
+
+    /// Checks whether a transaction with the given DA size and blob count still fits into the
+    /// payload.
+    pub fn check(&self, tx_da_size: u64, tx_blob_count: u64) -> Result<(), DaLimitExceeded> {
+        if let Some(limit) = self.limits.max_da_tx_size {
+            if tx_da_size > limit {
+                return Err(DaLimitExceeded::TxDaSize { size: tx_da_size, limit })
+            }
+        }
+
+        if let Some(limit) = self.limits.max_da_block_size {
+            let total = self.da_size_used + tx_da_size;
+            if total > limit {
+                return Err(DaLimitExceeded::BlockDaSize { total, limit })
+            }
+        }
+
+        if tx_blob_count > 0 {
+            let total = self.blob_count_used + tx_blob_count;
+            let limit = self.blob_limit();
+            if total > limit {
+                return Err(DaLimitExceeded::BlobCount { total, limit })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records an included transaction with the given DA size and blob count.
+    pub const fn record(&mut self, tx_da_size: u64, tx_blob_count: u64) {
+        self.da_size_used += tx_da_size;
+        self.blob_count_used += tx_blob_count;
+    }
+}
+
+/// Returned by [`DaBudget::check`] if a transaction would exceed the configured DA limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DaLimitExceeded {
+    /// The transaction's DA size exceeds the per-transaction limit.
+    #[error("transaction DA size {size} exceeds per-transaction limit {limit}")]
+    TxDaSize {
+        /// DA size of the transaction.
+        size: u64,
+        /// Configured per-transaction limit.
+        limit: u64,
+    },
+    /// Including the transaction would exceed the per-payload DA limit.
+    #[error("payload DA size {total} would exceed limit {limit}")]
+    BlockDaSize {
+        /// DA size of the payload including the transaction.
+        total: u64,
+        /// Configured per-payload limit.
+        limit: u64,
+    },
+    /// Including the transaction would exceed the blob limit of the payload.
+    #[error("payload blob count {total} would exceed limit {limit}")]
+    BlobCount {
+        /// Blob count of the payload including the transaction.
+        total: u64,
+        /// Effective blob limit of the payload.
+        limit: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unconstrained_budget() {
+        let limits = PayloadDaLimits::default();
+        assert!(limits.is_empty());
+
+        let mut budget = limits.budget(6);
+        assert_eq!(budget.check(u64::MAX / 2, 6), Ok(()));
+        budget.record(100, 6);
+        assert!(budget.is_blob_limit_reached());
+        assert_eq!(budget.check(1, 1), Err(DaLimitExceeded::BlobCount { total: 7, limit: 6 }));
+        // non-blob transactions are unaffected by the blob limit
+        assert_eq!(budget.check(1, 0), Ok(()));
+    }
+
+    #[test]
+    fn da_size_limits() {
+        let limits =
+            PayloadDaLimits::default().with_max_da_tx_size(100).with_max_da_block_size(250);
+        let mut budget = limits.budget(0);
+
+        assert_eq!(budget.check(101, 0), Err(DaLimitExceeded::TxDaSize { size: 101, limit: 100 }));
+        assert_eq!(budget.check(100, 0), Ok(()));
+        budget.record(100, 0);
+        budget.record(100, 0);
+        assert_eq!(
+            budget.check(100, 0),
+            Err(DaLimitExceeded::BlockDaSize { total: 300, limit: 250 })
+        );
+        assert_eq!(budget.check(50, 0), Ok(()));
+        assert_eq!(budget.da_size_used(), 200);
+    }
+
+    #[test]
+    fn target_blob_count() {
+        let limits = PayloadDaLimits::default().with_target_blob_count(3);
+        assert_eq!(limits.blob_limit(9), 3);
+        // the target never exceeds the protocol maximum
+        assert_eq!(limits.blob_limit(2), 2);
+
+        let mut budget = limits.budget(9);
+        budget.record(0, 2);
+        assert!(!budget.is_blob_limit_reached());
+        assert_eq!(budget.check(0, 2), Err(DaLimitExceeded::BlobCount { total: 4, limit: 3 }));
+        budget.record(0, 1);
+        assert!(budget.is_blob_limit_reached());
+        assert_eq!(budget.blob_count_used(), 3);
+    }
+}
@@ -14,10 +14,12 @@ use alloy_primitives::{B256, U256};
 use futures_core::ready;
 use futures_util::FutureExt;
 use reth_chain_state::CanonStateNotification;
-use reth_payload_builder::{KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator};
+use reth_payload_builder::{
+    KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator, PayloadJobStats,
+};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
-use reth_primitives_traits::{HeaderTy, NodePrimitives, SealedHeader};
+use reth_primitives_traits::{BlockBody, HeaderTy, NodePrimitives, SealedHeader};
 use reth_revm::{cached::CachedReads, cancelled::CancelOnDrop};
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -26,9 +28,12 @@ use std::{
     future::Future,
     ops::Deref,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -37,10 +42,12 @@ use tokio::{
 use tracing::{debug, trace, warn};
 
 mod better_payload_emitter;
+mod da;
 mod metrics;
 mod stack;
 
 pub use better_payload_emitter::BetterPayloadEmitter;
+pub use da::{DaBudget, DaLimitExceeded, PayloadDaLimits};
 pub use stack::PayloadBuilderStack;
 
 /// Helper to access [`NodePrimitives::BlockHeader`] from [`PayloadBuilder::BuiltPayload`].
@@ -165,6 +172,13 @@ where
 
         let cached_reads = self.maybe_pre_cached(parent_header.hash());
 
+        let stats = PayloadJobStats {
+            id: config.payload_id(),
+            parent: config.parent_header.hash(),
+            active: true,
+            ..Default::default()
+        };
+
         let mut job = BasicPayloadJob {
             config,
             executor: self.executor.clone(),
@@ -177,6 +191,9 @@ where
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
             builder: self.builder.clone(),
+            stats,
+            counters: Default::default(),
+            build_started_at: None,
         };
 
         // start the first job right away
@@ -328,6 +345,12 @@ where
     ///
     /// See [`PayloadBuilder`]
     builder: Builder,
+    /// The statistics of this job, see [`PayloadJob::stats`].
+    stats: PayloadJobStats,
+    /// Counters shared with all build attempts of this job.
+    counters: PayloadBuildCounters,
+    /// When the pending build attempt was started.
+    build_started_at: Option<Instant>,
 }
 
 impl<Tasks, Builder> BasicPayloadJob<Tasks, Builder>
@@ -347,19 +370,44 @@ where
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.payload().cloned();
         self.metrics.inc_initiated_payload_builds();
+        self.stats.iterations += 1;
+        self.build_started_at = Some(Instant::now());
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
+        let counters = self.counters.clone();
         self.executor.spawn_blocking(Box::pin(async move {
             // acquire the permit for executing the task
             let _permit = guard.acquire().await;
-            let args =
-                BuildArguments { cached_reads, config: payload_config, cancel, best_payload };
+            let args = BuildArguments {
+                cached_reads,
+                config: payload_config,
+                cancel,
+                best_payload,
+                counters,
+            };
             let result = builder.try_build(args);
             let _ = tx.send(result);
         }));
 
         self.pending_block = Some(PendingPayload { _cancel, payload: rx });
     }
+
+    /// Records the latency of the build attempt that just finished.
+    fn on_build_finished(&mut self) {
+        if let Some(started_at) = self.build_started_at.take() {
+            let latency = started_at.elapsed().as_millis() as u64;
+            self.stats.last_build_latency_ms = latency;
+            self.stats.total_build_latency_ms += latency;
+        }
+    }
+
+    /// Records that the pending build attempt, if any, is preempted.
+    const fn preempt_pending_build(&mut self) {
+        if self.pending_block.is_some() {
+            self.stats.preemptions += 1;
+            self.build_started_at = None;
+        }
+    }
 }
 
 impl<Tasks, Builder> Future for BasicPayloadJob<Tasks, Builder>
@@ -377,6 +425,7 @@ where
         // check if the deadline is reached
         if this.deadline.as_mut().poll(cx).is_ready() {
             trace!(target: "payload_builder", "payload building deadline reached");
+            this.preempt_pending_build();
             return Poll::Ready(Ok(()))
         }
 
@@ -391,7 +440,11 @@ where
 
         // poll the pending block
         if let Some(mut fut) = this.pending_block.take() {
-            match fut.poll_unpin(cx) {
+            let poll = fut.poll_unpin(cx);
+            if poll.is_ready() {
+                this.on_build_finished();
+            }
+            match poll {
                 Poll::Ready(Ok(outcome)) => match outcome {
                     BuildOutcome::Better { payload, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
@@ -465,6 +518,7 @@ where
             self.spawn_build_job();
         }
 
+        self.preempt_pending_build();
         let maybe_better = self.pending_block.take();
         let mut empty_payload = None;
 
@@ -476,6 +530,7 @@ where
                 config: self.config.clone(),
                 cancel: CancelOnDrop::default(),
                 best_payload: None,
+                counters: self.counters.clone(),
             };
 
             match self.builder.on_missing_payload(args) {
@@ -518,6 +573,18 @@ where
 
         (fut, KeepPayloadJobAlive::No)
     }
+
+    fn stats(&self) -> Option<PayloadJobStats> {
+        let best_payload = self.best_payload.payload();
+        Some(PayloadJobStats {
+            transactions_considered: self.counters.transactions_considered(),
+            transactions_included: best_payload
+                .map(|payload| payload.block().body().transaction_count() as u64)
+                .unwrap_or_default(),
+            cumulative_fees: best_payload.map(|payload| payload.fees()).unwrap_or_default(),
+            ..self.stats.clone()
+        })
+    }
 }
 
 /// Represents the current state of a payload being built.
@@ -787,17 +854,40 @@ pub struct BuildArguments<Attributes, Payload: BuiltPayload> {
     pub cancel: CancelOnDrop,
     /// The best payload achieved so far.
     pub best_payload: Option<Payload>,
+    /// Counters of the payload job, shared by all of its build attempts.
+    pub counters: PayloadBuildCounters,
 }
 
 impl<Attributes, Payload: BuiltPayload> BuildArguments<Attributes, Payload> {
     /// Create new build arguments.
-    pub const fn new(
+    pub fn new(
         cached_reads: CachedReads,
         config: PayloadConfig<Attributes, HeaderTy<Payload::Primitives>>,
         cancel: CancelOnDrop,
         best_payload: Option<Payload>,
     ) -> Self {
-        Self { cached_reads, config, cancel, best_payload }
+        Self { cached_reads, config, cancel, best_payload, counters: Default::default() }
+    }
+}
+
+/// Counters that a [`PayloadBuilder`] can update during a build attempt.
+///
+/// These are shared by all build attempts of a [`BasicPayloadJob`] and reported as part of its
+/// [`PayloadJobStats`].
+#[derive(Debug, Clone, Default)]
+pub struct PayloadBuildCounters {
+    transactions_considered: Arc<AtomicU64>,
+}
+
+impl PayloadBuildCounters {
+    /// Records that a transaction was considered for inclusion in the payload.
+    pub fn inc_transactions_considered(&self) {
+        self.transactions_considered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of transactions that were considered for inclusion so far.
+    pub fn transactions_considered(&self) -> u64 {
+        self.transactions_considered.load(Ordering::Relaxed)
     }
 }
 
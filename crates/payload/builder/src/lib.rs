@@ -114,7 +114,7 @@ pub mod test_utils;
 
 pub use alloy_rpc_types::engine::PayloadId;
 pub use reth_payload_builder_primitives::PayloadBuilderError;
-pub use reth_payload_primitives::PayloadKind;
+pub use reth_payload_primitives::{PayloadJobStats, PayloadKind};
 pub use service::{
     PayloadBuilderHandle, PayloadBuilderService, PayloadServiceCommand, PayloadStore,
 };
@@ -12,13 +12,16 @@ use alloy_rpc_types::engine::PayloadId;
 use futures_util::{future::FutureExt, Stream, StreamExt};
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder_primitives::{Events, PayloadBuilderError, PayloadEvents};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTypes};
-use reth_primitives_traits::NodePrimitives;
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuilderAttributes, PayloadJobStats, PayloadKind, PayloadTypes,
+};
+use reth_primitives_traits::{BlockBody, NodePrimitives};
 use std::{
+    collections::VecDeque,
     fmt,
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 use tokio::sync::{
@@ -177,6 +180,15 @@ impl<T: PayloadTypes> PayloadBuilderHandle<T> {
         self.to_service.send(PayloadServiceCommand::PayloadAttributes(id, tx)).ok()?;
         rx.await.ok()?
     }
+
+    /// Returns the statistics of all active payload jobs and of the most recently finished ones.
+    ///
+    /// Note: only jobs that collect statistics are included, see [`PayloadJob::stats`].
+    pub async fn stats(&self) -> Result<Vec<PayloadJobStats>, PayloadBuilderError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(PayloadServiceCommand::Stats(tx));
+        Ok(rx.await?)
+    }
 }
 
 impl<T> Clone for PayloadBuilderHandle<T>
@@ -218,10 +230,15 @@ where
     chain_events: St,
     /// Payload events handler, used to broadcast and subscribe to payload events.
     payload_events: broadcast::Sender<Events<T>>,
+    /// Statistics of the most recently finished payload jobs.
+    finished_job_stats: Arc<Mutex<VecDeque<PayloadJobStats>>>,
 }
 
 const PAYLOAD_EVENTS_BUFFER_SIZE: usize = 20;
 
+/// The number of finished payload jobs to keep the statistics of.
+const FINISHED_JOB_STATS_LIMIT: usize = 16;
+
 // === impl PayloadBuilderService ===
 
 impl<Gen, St, T> PayloadBuilderService<Gen, St, T>
@@ -249,6 +266,7 @@ where
             metrics: Default::default(),
             chain_events,
             payload_events,
+            finished_job_stats: Default::default(),
         };
 
         let handle = service.handle();
@@ -297,8 +315,10 @@ where
         let job = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id)?;
         let (fut, keep_alive) = self.payload_jobs[job].0.resolve_kind(kind);
 
-        if keep_alive == KeepPayloadJobAlive::No {
-            let (_, id) = self.payload_jobs.swap_remove(job);
+        let terminated = keep_alive == KeepPayloadJobAlive::No;
+        if terminated {
+            let (job, id) = self.payload_jobs.swap_remove(job);
+            self.on_job_finished(&job);
             debug!(target: "payload_builder", %id, "terminated resolved job");
         }
 
@@ -306,6 +326,7 @@ where
         // the future in a new future that will update the metrics.
         let resolved_metrics = self.metrics.clone();
         let payload_events = self.payload_events.clone();
+        let finished_job_stats = Arc::clone(&self.finished_job_stats);
 
         let fut = async move {
             let res = fut.await;
@@ -316,12 +337,43 @@ where
 
                 resolved_metrics
                     .set_resolved_revenue(payload.block().number(), f64::from(payload.fees()));
+
+                // The job may resolve to a payload that was still being built when the job was
+                // terminated, so the recorded statistics are updated with the resolved payload.
+                if terminated {
+                    let mut finished_job_stats = finished_job_stats.lock().unwrap();
+                    if let Some(stats) = finished_job_stats.iter_mut().rev().find(|s| s.id == id) {
+                        stats.transactions_included =
+                            payload.block().body().transaction_count() as u64;
+                        stats.cumulative_fees = payload.fees();
+                    }
+                }
             }
             res.map(|p| p.into())
         };
 
         Some(Box::pin(fut))
     }
+
+    /// Records the final statistics of a job that was resolved or terminated.
+    fn on_job_finished(&self, job: &Gen::Job) {
+        let Some(mut stats) = job.stats() else { return };
+        stats.active = false;
+        let mut finished_job_stats = self.finished_job_stats.lock().unwrap();
+        if finished_job_stats.len() == FINISHED_JOB_STATS_LIMIT {
+            finished_job_stats.pop_front();
+        }
+        finished_job_stats.push_back(stats);
+    }
+
+    /// Returns the statistics of all active jobs, followed by the most recently finished jobs.
+    fn job_stats(&self) -> Vec<PayloadJobStats> {
+        self.payload_jobs
+            .iter()
+            .filter_map(|(job, _)| job.stats())
+            .chain(self.finished_job_stats.lock().unwrap().iter().rev().cloned())
+            .collect()
+    }
 }
 
 impl<Gen, St, T> PayloadBuilderService<Gen, St, T>
@@ -379,11 +431,13 @@ where
                 // drain better payloads from the job
                 match job.poll_unpin(cx) {
                     Poll::Ready(Ok(_)) => {
+                        this.on_job_finished(&job);
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                         trace!(target: "payload_builder", %id, "payload job finished");
                     }
                     Poll::Ready(Err(err)) => {
                         warn!(target: "payload_builder",%err, ?id, "Payload builder job failed; resolving payload");
+                        this.on_job_finished(&job);
                         this.metrics.inc_failed_jobs();
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                     }
@@ -442,6 +496,9 @@ where
                         let new_rx = this.payload_events.subscribe();
                         let _ = tx.send(new_rx);
                     }
+                    PayloadServiceCommand::Stats(tx) => {
+                        let _ = tx.send(this.job_stats());
+                    }
                 }
             }
 
@@ -474,6 +531,8 @@ pub enum PayloadServiceCommand<T: PayloadTypes> {
     ),
     /// Payload service events
     Subscribe(oneshot::Sender<broadcast::Receiver<Events<T>>>),
+    /// Get the statistics of the active and recently finished payload jobs
+    Stats(oneshot::Sender<Vec<PayloadJobStats>>),
 }
 
 impl<T> fmt::Debug for PayloadServiceCommand<T>
@@ -493,6 +552,7 @@ where
             }
             Self::Resolve(f0, f1, _f2) => f.debug_tuple("Resolve").field(&f0).field(&f1).finish(),
             Self::Subscribe(f0) => f.debug_tuple("Subscribe").field(&f0).finish(),
+            Self::Stats(f0) => f.debug_tuple("Stats").field(&f0).finish(),
         }
     }
 }
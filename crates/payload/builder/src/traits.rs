@@ -2,7 +2,9 @@
 
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuilderAttributes, PayloadJobStats, PayloadKind,
+};
 use reth_primitives_traits::NodePrimitives;
 use std::future::Future;
 
@@ -72,6 +74,11 @@ pub trait PayloadJob: Future<Output = Result<(), PayloadBuilderError>> + Send +
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
         self.resolve_kind(PayloadKind::Earliest)
     }
+
+    /// Returns the statistics of the job collected so far, if the job collects any.
+    fn stats(&self) -> Option<PayloadJobStats> {
+        None
+    }
 }
 
 /// Whether the payload job should be kept alive or terminated after the payload was requested by
@@ -91,6 +91,10 @@ pub enum EngineObjectValidationError {
     /// timestamp.
     #[error("Unsupported fork")]
     UnsupportedFork,
+    /// Thrown if the `PayloadAttributes` violate a chain specific rule, for example one enforced
+    /// by a [`PayloadAttributesValidator`](crate::PayloadAttributesValidator).
+    #[error("Invalid payload attributes: {0}")]
+    InvalidPayloadAttributes(Box<dyn core::error::Error + Send + Sync>),
     /// Another type of error that is not covered by the above variants.
     #[error("Invalid params: {0}")]
     InvalidParams(#[from] Box<dyn core::error::Error + Send + Sync>),
@@ -169,6 +173,14 @@ impl EngineObjectValidationError {
     {
         Self::InvalidParams(Box::new(error))
     }
+
+    /// Creates an instance of the `InvalidPayloadAttributes` variant with the given error.
+    pub fn invalid_payload_attributes<E>(error: E) -> Self
+    where
+        E: core::error::Error + Send + Sync + 'static,
+    {
+        Self::InvalidPayloadAttributes(Box::new(error))
+    }
 }
 
 /// Thrown when validating the correctness of a payloadattributes object.
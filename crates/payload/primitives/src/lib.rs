@@ -27,12 +27,15 @@ pub use error::{
 mod traits;
 pub use traits::{
     BuildNextEnv, BuiltPayload, PayloadAttributes, PayloadAttributesBuilder,
-    PayloadBuilderAttributes,
+    PayloadAttributesValidator, PayloadBuilderAttributes,
 };
 
 mod payload;
 pub use payload::{ExecutionPayload, PayloadOrAttributes};
 
+mod stats;
+pub use stats::PayloadJobStats;
+
 /// Core trait that defines the associated types for working with execution payloads.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
     /// The format for execution payload data that can be processed and validated.
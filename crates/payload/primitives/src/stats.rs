@@ -0,0 +1,33 @@
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_engine::PayloadId;
+use serde::{Deserialize, Serialize};
+
+/// Statistics of a payload job.
+///
+/// These are collected over the lifetime of the job, across all of its build attempts, and can be
+/// used to tune the settings of the payload builder, e.g. the interval between build attempts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadJobStats {
+    /// The identifier of the payload.
+    pub id: PayloadId,
+    /// The hash of the parent block the payload is built on.
+    pub parent: B256,
+    /// Whether the job is still active, or was already resolved or terminated.
+    pub active: bool,
+    /// The number of build attempts that were started.
+    pub iterations: u64,
+    /// The number of transactions that were considered for inclusion, across all build attempts.
+    pub transactions_considered: u64,
+    /// The number of transactions included in the best payload.
+    pub transactions_included: u64,
+    /// The cumulative fees of the transactions included in the best payload.
+    pub cumulative_fees: U256,
+    /// The duration of the last completed build attempt in milliseconds.
+    pub last_build_latency_ms: u64,
+    /// The total duration of all completed build attempts in milliseconds.
+    pub total_build_latency_ms: u64,
+    /// The number of build attempts that were still in progress when the job was resolved or
+    /// terminated.
+    pub preemptions: u64,
+}
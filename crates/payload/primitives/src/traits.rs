@@ -11,7 +11,7 @@ use core::fmt;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_primitives_traits::{NodePrimitives, SealedBlock, SealedHeader};
 
-use crate::PayloadBuilderError;
+use crate::{EngineApiMessageVersion, EngineObjectValidationError, PayloadBuilderError};
 
 /// Represents a successfully built execution payload (block).
 ///
@@ -147,6 +147,23 @@ pub trait PayloadAttributesBuilder<Attributes>: Send + Sync + 'static {
     fn build(&self, timestamp: u64) -> Attributes;
 }
 
+/// Enforces custom rules on the payload attributes received over the engine API.
+///
+/// This is an extension point for chains that restrict the payload attributes further than the
+/// engine API specification, for example by only allowing certain fee recipients.
+///
+/// Returning [`EngineObjectValidationError::InvalidPayloadAttributes`] rejects the attributes with
+/// `-38003: Invalid payload attributes`.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PayloadAttributesValidator<Attributes>: Send + Sync + fmt::Debug + 'static {
+    /// Validates the payload attributes received with the given [`EngineApiMessageVersion`].
+    fn validate_payload_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &Attributes,
+    ) -> Result<(), EngineObjectValidationError>;
+}
+
 /// Trait to build the EVM environment for the next block from the given payload attributes.
 ///
 /// Accepts payload attributes from CL, parent header and additional payload builder context.
@@ -9,7 +9,7 @@ use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneModes, PruneSchedule};
 use std::time::Duration;
 use tokio::sync::watch;
 
@@ -24,6 +24,8 @@ pub struct PrunerBuilder {
     delete_limit: usize,
     /// Time a pruner job can run before timing out.
     timeout: Option<Duration>,
+    /// Time windows during which the pruner is allowed to run.
+    schedule: PruneSchedule,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
 }
@@ -37,6 +39,7 @@ impl PrunerBuilder {
         Self::default()
             .block_interval(pruner_config.block_interval)
             .segments(pruner_config.segments)
+            .schedule(pruner_config.schedule)
     }
 
     /// Sets the minimum pruning interval measured in blocks.
@@ -66,6 +69,12 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the time windows during which the pruner is allowed to run.
+    pub fn schedule(mut self, schedule: PruneSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
     /// Sets the receiver for the finished height of all `ExEx`'s.
     pub fn finished_exex_height(
         mut self,
@@ -99,6 +108,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_schedule(self.schedule)
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -122,6 +132,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_schedule(self.schedule)
     }
 }
 
@@ -132,6 +143,7 @@ impl Default for PrunerBuilder {
             segments: PruneModes::none(),
             delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             timeout: None,
+            schedule: PruneSchedule::default(),
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
         }
     }
@@ -10,6 +10,8 @@ use std::collections::HashMap;
 pub(crate) struct Metrics {
     /// Pruning duration
     pub(crate) duration_seconds: Histogram,
+    /// Unix timestamp of the next time the pruner is allowed to run according to its schedule
+    pub(crate) next_scheduled_run_timestamp: Gauge,
     #[metric(skip)]
     prune_segments: HashMap<PruneSegment, PrunerSegmentMetrics>,
 }
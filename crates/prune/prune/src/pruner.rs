@@ -9,9 +9,9 @@ use reth_exex_types::FinishedExExHeight;
 use reth_provider::{
     DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_prune_types::{PruneProgress, PruneSchedule, PrunedSegmentInfo, PrunerOutput};
 use reth_tokio_util::{EventSender, EventStream};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::watch;
 use tracing::debug;
 
@@ -41,6 +41,8 @@ pub struct Pruner<Provider, PF> {
     delete_limit: usize,
     /// Maximum time for one pruner run.
     timeout: Option<Duration>,
+    /// Time windows during which the pruner is allowed to run.
+    schedule: PruneSchedule,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
     #[doc(hidden)]
@@ -64,6 +66,7 @@ impl<Provider> Pruner<Provider, ()> {
             previous_tip_block_number: None,
             delete_limit,
             timeout,
+            schedule: PruneSchedule::default(),
             finished_exex_height,
             metrics: Metrics::default(),
             event_sender: Default::default(),
@@ -91,6 +94,7 @@ where
             previous_tip_block_number: None,
             delete_limit,
             timeout,
+            schedule: PruneSchedule::default(),
             finished_exex_height,
             metrics: Metrics::default(),
             event_sender: Default::default(),
@@ -98,6 +102,14 @@ where
     }
 }
 
+impl<Provider, S> Pruner<Provider, S> {
+    /// Sets the [`PruneSchedule`] that restricts when the pruner is allowed to run.
+    pub fn with_schedule(mut self, schedule: PruneSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
 impl<Provider, S> Pruner<Provider, S>
 where
     Provider: PruneCheckpointReader + PruneCheckpointWriter,
@@ -135,8 +147,12 @@ where
         let start = Instant::now();
 
         let mut limiter = PruneLimiter::default().set_deleted_entries_limit(self.delete_limit);
-        if let Some(timeout) = self.timeout {
-            limiter = limiter.set_time_limit(timeout);
+        let time_limit = match (self.timeout, self.schedule.run_time_limit(unix_timestamp())) {
+            (Some(timeout), Some(schedule_limit)) => Some(timeout.min(schedule_limit)),
+            (timeout, schedule_limit) => timeout.or(schedule_limit),
+        };
+        if let Some(time_limit) = time_limit {
+            limiter = limiter.set_time_limit(time_limit);
         };
 
         let (stats, deleted_entries, output) =
@@ -265,8 +281,14 @@ where
 
     /// Returns `true` if the pruning is needed at the provided tip block number.
     /// This is determined by the check against minimum pruning interval and last pruned block
-    /// number.
+    /// number, and by the configured [`PruneSchedule`].
     pub fn is_pruning_needed(&self, tip_block_number: BlockNumber) -> bool {
+        self.is_pruning_needed_at(tip_block_number, unix_timestamp())
+    }
+
+    /// Returns `true` if the pruning is needed at the provided tip block number and unix
+    /// timestamp in seconds.
+    fn is_pruning_needed_at(&self, tip_block_number: BlockNumber, timestamp: u64) -> bool {
         let Some(tip_block_number) =
             self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
         else {
@@ -276,19 +298,40 @@ where
         // Saturating subtraction is needed for the case when the chain was reverted, meaning
         // current block number might be less than the previous tip block number.
         // If that's the case, no pruning is needed as outdated data is also reverted.
-        if tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default()) >=
-            self.min_block_interval as u64
-        {
+        let blocks_since_last_run =
+            tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default());
+        if blocks_since_last_run < self.min_block_interval as u64 {
+            return false
+        }
+
+        self.metrics.next_scheduled_run_timestamp.set(self.schedule.next_run(timestamp) as f64);
+        if !self.schedule.is_open(timestamp) {
+            if self.schedule.is_emergency(blocks_since_last_run) {
+                debug!(
+                    target: "pruner",
+                    previous_tip_block_number = ?self.previous_tip_block_number,
+                    %tip_block_number,
+                    "Emergency pruning distance reached outside of pruning windows"
+                );
+                return true
+            }
+
             debug!(
                 target: "pruner",
-                previous_tip_block_number = ?self.previous_tip_block_number,
                 %tip_block_number,
-                "Minimum pruning interval reached"
+                next_run = self.schedule.next_run(timestamp),
+                "Minimum pruning interval reached, but outside of pruning windows"
             );
-            true
-        } else {
-            false
+            return false
         }
+
+        debug!(
+            target: "pruner",
+            previous_tip_block_number = ?self.previous_tip_block_number,
+            %tip_block_number,
+            "Minimum pruning interval reached"
+        );
+        true
     }
 
     /// Adjusts the tip block number to the finished `ExEx` height. This is needed to not prune more
@@ -333,11 +376,17 @@ where
     }
 }
 
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Pruner;
     use reth_exex_types::FinishedExExHeight;
     use reth_provider::test_utils::create_test_provider_factory;
+    use reth_prune_types::{PruneSchedule, PruneWindow};
 
     #[test]
     fn is_pruning_needed() {
@@ -378,4 +427,34 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn is_pruning_needed_with_schedule() {
+        let provider_factory = create_test_provider_factory();
+
+        let (_finished_exex_height_tx, finished_exex_height_rx) =
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
+
+        let mut pruner =
+            Pruner::new_with_factory(provider_factory, vec![], 5, 0, None, finished_exex_height_rx)
+                .with_schedule(PruneSchedule {
+                    windows: vec![PruneWindow::new((1, 0), (5, 0)).unwrap()],
+                    max_run_duration: None,
+                    emergency_distance: Some(100),
+                });
+        pruner.previous_tip_block_number = Some(1);
+
+        // 2024-01-01T02:00:00Z and 2024-01-01T12:00:00Z
+        let inside_window = 1_704_067_200 + 2 * 3600;
+        let outside_window = 1_704_067_200 + 12 * 3600;
+
+        // Minimum pruning interval reached inside of the window
+        assert!(pruner.is_pruning_needed_at(6, inside_window));
+
+        // Minimum pruning interval reached outside of the window
+        assert!(!pruner.is_pruning_needed_at(6, outside_window));
+
+        // Emergency distance reached outside of the window
+        assert!(pruner.is_pruning_needed_at(101, outside_window));
+    }
 }
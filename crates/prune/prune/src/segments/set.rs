@@ -11,7 +11,10 @@ use reth_provider::{
 };
 use reth_prune_types::PruneModes;
 
-use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
+use super::{
+    StaticFileAccountChangeSets, StaticFileHeaders, StaticFileReceipts,
+    StaticFileStorageChangeSets, StaticFileTransactions,
+};
 
 /// Collection of [`Segment`]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -67,6 +70,7 @@ where
             storage_history,
             bodies_history: _,
             receipts_log_filter,
+            receipts_log_topic_filter,
         } = prune_modes;
 
         Self::default()
@@ -75,7 +79,11 @@ where
             // Static file transactions
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
-            .segment(StaticFileReceipts::new(static_file_provider))
+            .segment(StaticFileReceipts::new(static_file_provider.clone()))
+            // Static file account changesets
+            .segment(StaticFileAccountChangeSets::new(static_file_provider.clone()))
+            // Static file storage changesets
+            .segment(StaticFileStorageChangeSets::new(static_file_provider))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
@@ -84,8 +92,8 @@ where
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
             .segment_opt(
-                (!receipts_log_filter.is_empty())
-                    .then(|| ReceiptsByLogs::new(receipts_log_filter.clone())),
+                (!receipts_log_filter.is_empty() || !receipts_log_topic_filter.is_empty())
+                    .then(|| ReceiptsByLogs::new(receipts_log_filter, receipts_log_topic_filter)),
             )
             // Transaction lookup
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
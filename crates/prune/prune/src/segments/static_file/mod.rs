@@ -1,7 +1,11 @@
+mod account_change_sets;
 mod headers;
 mod receipts;
+mod storage_change_sets;
 mod transactions;
 
+pub use account_change_sets::AccountChangeSets;
 pub use headers::Headers;
 pub use receipts::Receipts;
+pub use storage_change_sets::StorageChangeSets;
 pub use transactions::Transactions;
@@ -10,18 +10,23 @@ use reth_provider::{
     BlockReader, DBProvider, NodePrimitivesProvider, PruneCheckpointWriter, TransactionsProvider,
 };
 use reth_prune_types::{
-    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, ReceiptsLogPruneConfig, SegmentOutput,
-    MINIMUM_PRUNING_DISTANCE,
+    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, ReceiptsLogPruneConfig,
+    ReceiptsLogTopicPruneConfig, SegmentOutput, MINIMUM_PRUNING_DISTANCE,
 };
+use std::collections::BTreeMap;
 use tracing::{instrument, trace};
 #[derive(Debug)]
 pub struct ReceiptsByLogs {
     config: ReceiptsLogPruneConfig,
+    topic_config: ReceiptsLogTopicPruneConfig,
 }
 
 impl ReceiptsByLogs {
-    pub const fn new(config: ReceiptsLogPruneConfig) -> Self {
-        Self { config }
+    pub const fn new(
+        config: ReceiptsLogPruneConfig,
+        topic_config: ReceiptsLogTopicPruneConfig,
+    ) -> Self {
+        Self { config, topic_config }
     }
 }
 
@@ -70,11 +75,19 @@ where
         };
 
         // Figure out what receipts have already been pruned, so we can have an accurate
-        // `address_filter`
-        let address_filter = self.config.group_by_block(input.to_block, last_pruned_block)?;
+        // `log_filter`
+        let mut log_filter = BTreeMap::<_, (Vec<_>, Vec<_>)>::new();
+        for (block, addresses) in self.config.group_by_block(input.to_block, last_pruned_block)? {
+            log_filter.entry(block).or_default().0.extend(addresses);
+        }
+        for (block, topics) in
+            self.topic_config.group_by_block(input.to_block, last_pruned_block)?
+        {
+            log_filter.entry(block).or_default().1.extend(topics);
+        }
 
         // Splits all transactions in different block ranges. Each block range will have its own
-        // filter address list and will check it while going through the table
+        // filter address and topic lists and will check them while going through the table
         //
         // Example:
         // For an `address_filter` such as:
@@ -88,40 +101,51 @@ where
         //  ]
         // `filtered_addresses`: [a1, a2, a3, a4, a5]
         //
+        // Topics are split the same way, and a receipt is kept if any of its logs matches either
+        // an address or the first topic of the range.
+        //
         // The first range will delete all receipts between block0 - block8
         // The second range will delete all receipts between block9 - 19, except the ones with
         //     emitter logs from these addresses: [a1, a2].
         // The third range will delete all receipts between block20 - to_block, except the ones with
         //     emitter logs from these addresses: [a1, a2, a3, a4, a5]
         let mut block_ranges = vec![];
-        let mut blocks_iter = address_filter.iter().peekable();
+        let mut blocks_iter = log_filter.iter().peekable();
         let mut filtered_addresses = vec![];
+        let mut filtered_topics = vec![];
 
-        while let Some((start_block, addresses)) = blocks_iter.next() {
+        while let Some((start_block, (addresses, topics))) = blocks_iter.next() {
             filtered_addresses.extend_from_slice(addresses);
+            filtered_topics.extend_from_slice(topics);
 
             // This will clear all receipts before the first  appearance of a contract log or since
             // the block after the last pruned one.
             if block_ranges.is_empty() {
                 let init = last_pruned_block.map(|b| b + 1).unwrap_or_default();
                 if init < *start_block {
-                    block_ranges.push((init, *start_block - 1, 0));
+                    block_ranges.push((init, *start_block - 1, 0, 0));
                 }
             }
 
             let end_block =
                 blocks_iter.peek().map(|(next_block, _)| *next_block - 1).unwrap_or(to_block);
 
-            // Addresses in lower block ranges, are still included in the inclusion list for future
-            // ranges.
-            block_ranges.push((*start_block, end_block, filtered_addresses.len()));
+            // Addresses and topics in lower block ranges, are still included in the inclusion list
+            // for future ranges.
+            block_ranges.push((
+                *start_block,
+                end_block,
+                filtered_addresses.len(),
+                filtered_topics.len(),
+            ));
         }
 
         trace!(
             target: "pruner",
             ?block_ranges,
             ?filtered_addresses,
-            "Calculated block ranges and filtered addresses",
+            ?filtered_topics,
+            "Calculated block ranges and filtered addresses and topics",
         );
 
         let mut limiter = input.limiter;
@@ -129,7 +153,7 @@ where
         let mut done = true;
         let mut pruned = 0;
         let mut last_pruned_transaction = None;
-        for (start_block, end_block, num_addresses) in block_ranges {
+        for (start_block, end_block, num_addresses, num_topics) in block_ranges {
             let block_range = start_block..=end_block;
 
             // Calculate the transaction range from this block range
@@ -155,9 +179,12 @@ where
                 tx_range,
                 &mut limiter,
                 |(tx_num, receipt)| {
-                    let skip = num_addresses > 0 &&
+                    let skip = (num_addresses > 0 || num_topics > 0) &&
                         receipt.logs().iter().any(|log| {
-                            filtered_addresses[..num_addresses].contains(&&log.address)
+                            filtered_addresses[..num_addresses].contains(&&log.address) ||
+                                log.topics().first().is_some_and(|topic| {
+                                    filtered_topics[..num_topics].contains(&topic)
+                                })
                         });
 
                     if skip {
@@ -196,19 +223,25 @@ where
             from_tx_number = last_pruned_transaction + 1;
         }
 
-        // If there are contracts using `PruneMode::Distance(_)` there will be receipts before
-        // `to_block` that become eligible to be pruned in future runs. Therefore, our checkpoint is
-        // not actually `to_block`, but the `lowest_block_with_distance` from any contract.
+        // If there are contracts or topics using `PruneMode::Distance(_)` there will be receipts
+        // before `to_block` that become eligible to be pruned in future runs. Therefore, our
+        // checkpoint is not actually `to_block`, but the `lowest_block_with_distance` from any
+        // contract or topic.
         // This ensures that in future pruner runs we can prune all these receipts between the
         // previous `lowest_block_with_distance` and the new one using
         // `get_next_tx_num_range_from_checkpoint`.
         //
         // Only applies if we were able to prune everything intended for this run, otherwise the
         // checkpoint is the `last_pruned_block`.
-        let prune_mode_block = self
-            .config
-            .lowest_block_with_distance(input.to_block, initial_last_pruned_block)?
-            .unwrap_or(to_block);
+        let prune_mode_block = [
+            self.config.lowest_block_with_distance(input.to_block, initial_last_pruned_block)?,
+            self.topic_config
+                .lowest_block_with_distance(input.to_block, initial_last_pruned_block)?,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(to_block);
 
         provider.save_prune_checkpoint(
             PruneSegment::ContractLogs,
@@ -228,12 +261,14 @@ where
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, ReceiptsByLogs, Segment};
-    use alloy_primitives::B256;
+    use alloy_primitives::{b256, Address, Bytes, Log, B256};
     use assert_matches::assert_matches;
     use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
     use reth_primitives_traits::InMemorySize;
     use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader, TransactionsProvider};
-    use reth_prune_types::{PruneMode, PruneSegment, ReceiptsLogPruneConfig};
+    use reth_prune_types::{
+        PruneMode, PruneSegment, ReceiptsLogPruneConfig, ReceiptsLogTopicPruneConfig,
+    };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
         self, random_block_range, random_eoa_account, random_log, random_receipt, BlockRangeParams,
@@ -304,7 +339,7 @@ mod tests {
 
             let limiter = PruneLimiter::default().set_deleted_entries_limit(10);
 
-            let result = ReceiptsByLogs::new(receipts_log_filter).prune(
+            let result = ReceiptsByLogs::new(receipts_log_filter, Default::default()).prune(
                 &provider,
                 PruneInput {
                     previous_checkpoint: db
@@ -359,4 +394,91 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn prune_receipts_by_log_topics() {
+        reth_tracing::init_test_tracing();
+
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let tip = 20000;
+        let blocks = [
+            random_block_range(
+                &mut rng,
+                0..=100,
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..5, ..Default::default() },
+            ),
+            random_block_range(
+                &mut rng,
+                (100 + 1)..=tip,
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+            ),
+        ]
+        .concat();
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // `Transfer(address,address,uint256)`
+        let transfer_topic =
+            b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for (txi, transaction) in block.body().transactions.iter().enumerate() {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(1), None);
+                if txi == 0 {
+                    receipt.logs.push(Log::new_unchecked(
+                        Address::random(),
+                        vec![transfer_topic, B256::random()],
+                        Bytes::new(),
+                    ));
+                }
+                receipts.push((receipts.len() as u64, receipt));
+            }
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let prune_before_block = 20;
+        let topic_filter = ReceiptsLogTopicPruneConfig(BTreeMap::from([(
+            transfer_topic,
+            PruneMode::Before(prune_before_block),
+        )]));
+        let segment = ReceiptsByLogs::new(Default::default(), topic_filter);
+
+        loop {
+            let provider = db.factory.database_provider_rw().unwrap();
+            let output = segment
+                .prune(
+                    &provider,
+                    PruneInput {
+                        previous_checkpoint: provider
+                            .get_prune_checkpoint(PruneSegment::ContractLogs)
+                            .unwrap(),
+                        to_block: tip,
+                        limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+                    },
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+
+            if output.progress.is_finished() {
+                break
+            }
+        }
+
+        let provider = db.factory.provider().unwrap();
+        let mut cursor = provider.tx_ref().cursor_read::<tables::Receipts>().unwrap();
+        let mut retained = 0;
+        for receipt in cursor.walk(None).unwrap() {
+            let (tx_num, receipt) = receipt.unwrap();
+            let block = provider.transaction_block(tx_num).unwrap().unwrap();
+            let has_topic =
+                receipt.logs.iter().any(|log| log.topics().first() == Some(&transfer_topic));
+
+            // Only the receipts with the topic from `prune_before_block` onwards are retained
+            assert!(has_topic && block >= prune_before_block);
+            retained += 1;
+        }
+        assert_eq!(retained, (prune_before_block..=100).count());
+    }
 }
@@ -9,8 +9,9 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::DBProvider;
+use reth_provider::{DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -33,7 +34,7 @@ impl StorageHistory {
 
 impl<Provider> Segment<Provider> for StorageHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::StorageHistory
@@ -80,9 +81,34 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_storages = FxHashMap::default();
+
+        // Changesets that were moved to static files are not in the database anymore, so the
+        // history indices to prune are collected from the static files instead.
+        let mut pruned_static_changesets = 0;
+        let mut db_range = range;
+        let static_file_provider = provider.static_file_provider();
+        if let Some(highest_static_block) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| *db_range.start() <= *highest)
+        {
+            let static_range_end = range_end.min(highest_static_block);
+            for change in static_file_provider
+                .storage_change_sets_walker(*db_range.start()..static_range_end + 1)
+            {
+                let (BlockNumberAddress((block_number, address)), entry) = change?;
+                highest_deleted_storages.insert((address, entry.key), block_number);
+                pruned_static_changesets += 1;
+            }
+            static_file_provider.delete_segment_below_block(
+                StaticFileSegment::StorageChangeSets,
+                static_range_end + 1,
+            )?;
+            db_range = static_range_end + 1..=range_end;
+        }
+
         let (pruned_changesets, done) =
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
-                BlockNumberAddress::range(range),
+                BlockNumberAddress::range(db_range),
                 &mut limiter,
                 |_| false,
                 |(BlockNumberAddress((block_number, address)), entry)| {
@@ -122,7 +148,7 @@ where
 
         Ok(SegmentOutput {
             progress,
-            pruned: pruned_changesets + outcomes.deleted,
+            pruned: pruned_static_changesets + pruned_changesets + outcomes.deleted,
             checkpoint: Some(SegmentOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
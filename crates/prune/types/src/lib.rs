@@ -15,11 +15,12 @@ mod checkpoint;
 mod event;
 mod mode;
 mod pruner;
+mod schedule;
 mod segment;
 mod target;
 
 use alloc::{collections::BTreeMap, vec::Vec};
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, B256};
 use core::ops::Deref;
 
 pub use checkpoint::PruneCheckpoint;
@@ -29,6 +30,7 @@ pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
 };
+pub use schedule::{ParsePruneWindowError, PruneSchedule, PruneWindow};
 pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError};
 pub use target::{PruneModes, UnwindTargetPrunedError, MINIMUM_PRUNING_DISTANCE};
 
@@ -63,27 +65,7 @@ impl ReceiptsLogPruneConfig {
         tip: BlockNumber,
         pruned_block: Option<BlockNumber>,
     ) -> Result<BTreeMap<BlockNumber, Vec<&Address>>, PruneSegmentError> {
-        let mut map = BTreeMap::new();
-        let base_block = pruned_block.unwrap_or_default() + 1;
-
-        for (address, mode) in &self.0 {
-            // Getting `None`, means that there is nothing to prune yet, so we need it to include in
-            // the BTreeMap (block = 0), otherwise it will be excluded.
-            // Reminder that this BTreeMap works as an inclusion list that excludes (prunes) all
-            // other receipts.
-            //
-            // Reminder, that we increment because the [`BlockNumber`] key of the new map should be
-            // viewed as `PruneMode::Before(block)`
-            let block = base_block.max(
-                mode.prune_target_block(tip, PruneSegment::ContractLogs, PrunePurpose::User)?
-                    .map(|(block, _)| block)
-                    .unwrap_or_default() +
-                    1,
-            );
-
-            map.entry(block).or_insert_with(Vec::new).push(address)
-        }
-        Ok(map)
+        group_by_block(&self.0, tip, pruned_block)
     }
 
     /// Returns the lowest block where we start filtering logs which use `PruneMode::Distance(_)`.
@@ -92,20 +74,19 @@ impl ReceiptsLogPruneConfig {
         tip: BlockNumber,
         pruned_block: Option<BlockNumber>,
     ) -> Result<Option<BlockNumber>, PruneSegmentError> {
-        let pruned_block = pruned_block.unwrap_or_default();
-        let mut lowest = None;
-
-        for mode in self.values() {
-            if mode.is_distance() {
-                if let Some((block, _)) =
-                    mode.prune_target_block(tip, PruneSegment::ContractLogs, PrunePurpose::User)?
-                {
-                    lowest = Some(lowest.unwrap_or(u64::MAX).min(block));
-                }
-            }
-        }
+        lowest_block_with_distance(&self.0, tip, pruned_block)
+    }
 
-        Ok(lowest.map(|lowest| lowest.max(pruned_block)))
+    /// Returns the block from which on the receipts with logs emitted by any of the `addresses`
+    /// are retained, given the `tip` block number.
+    ///
+    /// Returns `None` if `addresses` is empty or if any of them is not in the configuration.
+    pub fn retained_from<'a>(
+        &self,
+        tip: BlockNumber,
+        addresses: impl IntoIterator<Item = &'a Address>,
+    ) -> Result<Option<BlockNumber>, PruneSegmentError> {
+        retained_from(&self.0, tip, addresses)
     }
 }
 
@@ -117,6 +98,137 @@ impl Deref for ReceiptsLogPruneConfig {
     }
 }
 
+/// Configuration for pruning receipts not associated with logs that have one of the specified
+/// first topics, i.e. event signatures.
+///
+/// E.g. retaining the ERC-20 `Transfer(address,address,uint256)` topic keeps the receipts of all
+/// token transfers, regardless of the emitting contract.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct ReceiptsLogTopicPruneConfig(pub BTreeMap<B256, PruneMode>);
+
+impl ReceiptsLogTopicPruneConfig {
+    /// Checks if the configuration is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Given the `tip` block number, consolidates the structure so it can easily be queried for
+    /// filtering across a range of blocks.
+    ///
+    /// See [`ReceiptsLogPruneConfig::group_by_block`].
+    pub fn group_by_block(
+        &self,
+        tip: BlockNumber,
+        pruned_block: Option<BlockNumber>,
+    ) -> Result<BTreeMap<BlockNumber, Vec<&B256>>, PruneSegmentError> {
+        group_by_block(&self.0, tip, pruned_block)
+    }
+
+    /// Returns the lowest block where we start filtering logs which use `PruneMode::Distance(_)`.
+    pub fn lowest_block_with_distance(
+        &self,
+        tip: BlockNumber,
+        pruned_block: Option<BlockNumber>,
+    ) -> Result<Option<BlockNumber>, PruneSegmentError> {
+        lowest_block_with_distance(&self.0, tip, pruned_block)
+    }
+
+    /// Returns the block from which on the receipts with logs that have any of the `topics` as
+    /// their first topic are retained, given the `tip` block number.
+    ///
+    /// Returns `None` if `topics` is empty or if any of them is not in the configuration.
+    pub fn retained_from<'a>(
+        &self,
+        tip: BlockNumber,
+        topics: impl IntoIterator<Item = &'a B256>,
+    ) -> Result<Option<BlockNumber>, PruneSegmentError> {
+        retained_from(&self.0, tip, topics)
+    }
+}
+
+impl Deref for ReceiptsLogTopicPruneConfig {
+    type Target = BTreeMap<B256, PruneMode>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Groups the keys of a receipts log filter by the block from which on their receipts are
+/// retained.
+fn group_by_block<K>(
+    filter: &BTreeMap<K, PruneMode>,
+    tip: BlockNumber,
+    pruned_block: Option<BlockNumber>,
+) -> Result<BTreeMap<BlockNumber, Vec<&K>>, PruneSegmentError> {
+    let mut map = BTreeMap::new();
+    let base_block = pruned_block.unwrap_or_default() + 1;
+
+    for (key, mode) in filter {
+        // Getting `None`, means that there is nothing to prune yet, so we need it to include in
+        // the BTreeMap (block = 0), otherwise it will be excluded.
+        // Reminder that this BTreeMap works as an inclusion list that excludes (prunes) all
+        // other receipts.
+        //
+        // Reminder, that we increment because the [`BlockNumber`] key of the new map should be
+        // viewed as `PruneMode::Before(block)`
+        let block = base_block.max(
+            mode.prune_target_block(tip, PruneSegment::ContractLogs, PrunePurpose::User)?
+                .map(|(block, _)| block)
+                .unwrap_or_default() +
+                1,
+        );
+
+        map.entry(block).or_insert_with(Vec::new).push(key)
+    }
+    Ok(map)
+}
+
+/// Returns the lowest block where a receipts log filter starts filtering logs which use
+/// `PruneMode::Distance(_)`.
+fn lowest_block_with_distance<K>(
+    filter: &BTreeMap<K, PruneMode>,
+    tip: BlockNumber,
+    pruned_block: Option<BlockNumber>,
+) -> Result<Option<BlockNumber>, PruneSegmentError> {
+    let pruned_block = pruned_block.unwrap_or_default();
+    let mut lowest = None;
+
+    for mode in filter.values() {
+        if mode.is_distance() {
+            if let Some((block, _)) =
+                mode.prune_target_block(tip, PruneSegment::ContractLogs, PrunePurpose::User)?
+            {
+                lowest = Some(lowest.unwrap_or(u64::MAX).min(block));
+            }
+        }
+    }
+
+    Ok(lowest.map(|lowest| lowest.max(pruned_block)))
+}
+
+/// Returns the block from which on the receipts of all `keys` are retained by a receipts log
+/// filter.
+fn retained_from<'a, K: Ord + 'a>(
+    filter: &BTreeMap<K, PruneMode>,
+    tip: BlockNumber,
+    keys: impl IntoIterator<Item = &'a K>,
+) -> Result<Option<BlockNumber>, PruneSegmentError> {
+    let mut retained_from: Option<BlockNumber> = None;
+
+    for key in keys {
+        let Some(mode) = filter.get(key) else { return Ok(None) };
+        let block = mode
+            .prune_target_block(tip, PruneSegment::ContractLogs, PrunePurpose::User)?
+            .map(|(block, _)| block + 1)
+            .unwrap_or_default();
+        retained_from = Some(retained_from.unwrap_or_default().max(block));
+    }
+
+    Ok(retained_from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,4 +440,46 @@ mod tests {
         // - Finally the lowest block is 100000
         assert_eq!(config.lowest_block_with_distance(tip, pruned_block).unwrap(), Some(100000));
     }
+
+    #[test]
+    fn test_topic_group_by_block() {
+        let topic1 = B256::new([1; 32]);
+        let topic2 = B256::new([2; 32]);
+        let config = ReceiptsLogTopicPruneConfig(BTreeMap::from([
+            (topic1, PruneMode::Before(600)),
+            (topic2, PruneMode::Distance(100000)),
+        ]));
+        let tip = 100700;
+        let pruned_block = Some(400);
+
+        let result = config.group_by_block(tip, pruned_block).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[&600], vec![&topic1], "Topic1 should be grouped under block 600");
+        assert_eq!(result[&701], vec![&topic2], "Topic2 should be grouped under block 700");
+
+        assert_eq!(config.lowest_block_with_distance(tip, pruned_block).unwrap(), Some(700));
+    }
+
+    #[test]
+    fn test_retained_from() {
+        let address1 = Address::new([1; 20]);
+        let address2 = Address::new([2; 20]);
+        let config = ReceiptsLogPruneConfig(BTreeMap::from([
+            (address1, PruneMode::Before(600)),
+            (address2, PruneMode::Distance(100000)),
+        ]));
+        let tip = 100700;
+
+        assert_eq!(config.retained_from(tip, []).unwrap(), None);
+        assert_eq!(config.retained_from(tip, [&address1]).unwrap(), Some(600));
+        assert_eq!(config.retained_from(tip, [&address2]).unwrap(), Some(701));
+        assert_eq!(config.retained_from(tip, [&address1, &address2]).unwrap(), Some(701));
+        // the receipts of an address outside of the configuration are not retained
+        assert_eq!(config.retained_from(tip, [&address1, &Address::new([3; 20])]).unwrap(), None);
+
+        let topic = B256::new([1; 32]);
+        let config = ReceiptsLogTopicPruneConfig(BTreeMap::from([(topic, PruneMode::Before(600))]));
+        assert_eq!(config.retained_from(tip, [&topic]).unwrap(), Some(600));
+        assert_eq!(config.retained_from(tip, [&B256::ZERO]).unwrap(), None);
+    }
 }
@@ -0,0 +1,273 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr, time::Duration};
+
+/// Number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A daily time window in UTC during which the pruner is allowed to run, e.g. `01:00-05:30`.
+///
+/// The start of the window is inclusive and the end is exclusive. If the end is before the start,
+/// the window wraps around midnight, and if both are equal the window spans the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneWindow {
+    /// Start of the window, in seconds since midnight UTC.
+    start: u32,
+    /// End of the window, in seconds since midnight UTC.
+    end: u32,
+}
+
+impl PruneWindow {
+    /// Creates a new window from the given start and end times, as `(hour, minute)` tuples in UTC.
+    ///
+    /// Returns `None` if any of the times is not a valid time of the day.
+    pub const fn new(start: (u8, u8), end: (u8, u8)) -> Option<Self> {
+        if start.0 > 23 || start.1 > 59 || end.0 > 23 || end.1 > 59 {
+            return None
+        }
+        Some(Self {
+            start: start.0 as u32 * 3600 + start.1 as u32 * 60,
+            end: end.0 as u32 * 3600 + end.1 as u32 * 60,
+        })
+    }
+
+    /// Returns `true` if the given unix timestamp in seconds falls into this window.
+    pub const fn contains(&self, timestamp: u64) -> bool {
+        let time = (timestamp % SECONDS_PER_DAY) as u32;
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else if self.start > self.end {
+            time >= self.start || time < self.end
+        } else {
+            true
+        }
+    }
+
+    /// Returns the number of seconds from the given unix timestamp until the window opens.
+    ///
+    /// Returns zero if the timestamp falls into this window.
+    pub const fn secs_until_open(&self, timestamp: u64) -> u64 {
+        if self.contains(timestamp) {
+            return 0
+        }
+        let time = timestamp % SECONDS_PER_DAY;
+        (self.start as u64 + SECONDS_PER_DAY - time) % SECONDS_PER_DAY
+    }
+
+    /// Returns the number of seconds from the given unix timestamp until the window closes.
+    ///
+    /// Returns `None` if the timestamp doesn't fall into this window or the window spans the whole
+    /// day.
+    pub const fn secs_until_close(&self, timestamp: u64) -> Option<u64> {
+        if self.start == self.end || !self.contains(timestamp) {
+            return None
+        }
+        let time = timestamp % SECONDS_PER_DAY;
+        Some((self.end as u64 + SECONDS_PER_DAY - time) % SECONDS_PER_DAY)
+    }
+}
+
+impl fmt::Display for PruneWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start / 3600,
+            self.start % 3600 / 60,
+            self.end / 3600,
+            self.end % 3600 / 60
+        )
+    }
+}
+
+/// Error returned when parsing a [`PruneWindow`] fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid prune window {0:?}, expected format HH:MM-HH:MM")]
+pub struct ParsePruneWindowError(String);
+
+impl FromStr for PruneWindow {
+    type Err = ParsePruneWindowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParsePruneWindowError(s.into());
+        let parse_time = |time: &str| -> Option<(u8, u8)> {
+            let (hour, minute) = time.trim().split_once(':')?;
+            Some((hour.parse().ok()?, minute.parse().ok()?))
+        };
+
+        let (start, end) = s.split_once('-').ok_or_else(err)?;
+        let start = parse_time(start).ok_or_else(err)?;
+        let end = parse_time(end).ok_or_else(err)?;
+        Self::new(start, end).ok_or_else(err)
+    }
+}
+
+#[cfg(any(test, feature = "serde"))]
+impl serde::Serialize for PruneWindow {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(any(test, feature = "serde"))]
+impl<'de> serde::Deserialize<'de> for PruneWindow {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Scheduling configuration of the pruner.
+///
+/// By default the pruner is allowed to run at any time. If any [`PruneWindow`]s are configured,
+/// the pruner only runs while one of them is open, unless the chain has advanced by more than
+/// [`PruneSchedule::emergency_distance`] blocks since the last run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(default))]
+pub struct PruneSchedule {
+    /// Daily windows in UTC during which the pruner is allowed to run.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Vec::is_empty"))]
+    pub windows: Vec<PruneWindow>,
+    /// Maximum duration of a single pruner run.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(with = "humantime_serde", skip_serializing_if = "Option::is_none")
+    )]
+    pub max_run_duration: Option<Duration>,
+    /// Number of blocks since the last pruner run after which the pruner runs even outside of the
+    /// configured windows.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
+    pub emergency_distance: Option<u64>,
+}
+
+impl PruneSchedule {
+    /// Returns `true` if no pruning windows are configured, meaning the pruner can run at any
+    /// time.
+    pub const fn is_unrestricted(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Returns `true` if the pruner is allowed to run at the given unix timestamp in seconds.
+    pub fn is_open(&self, timestamp: u64) -> bool {
+        self.is_unrestricted() || self.windows.iter().any(|window| window.contains(timestamp))
+    }
+
+    /// Returns `true` if the chain advanced far enough since the last pruner run for the pruner to
+    /// ignore the configured windows.
+    pub fn is_emergency(&self, blocks_since_last_run: u64) -> bool {
+        self.emergency_distance.is_some_and(|distance| blocks_since_last_run >= distance)
+    }
+
+    /// Returns the unix timestamp in seconds at which the pruner is next allowed to run, starting
+    /// from the given unix timestamp.
+    pub fn next_run(&self, timestamp: u64) -> u64 {
+        timestamp +
+            self.windows.iter().map(|window| window.secs_until_open(timestamp)).min().unwrap_or(0)
+    }
+
+    /// Returns the maximum duration of a pruner run started at the given unix timestamp in
+    /// seconds, taking into account both [`PruneSchedule::max_run_duration`] and the time left
+    /// until the current window closes.
+    pub fn run_time_limit(&self, timestamp: u64) -> Option<Duration> {
+        let window_limit = self
+            .windows
+            .iter()
+            .filter(|window| window.contains(timestamp))
+            .map(|window| window.secs_until_close(timestamp))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|secs| secs.into_iter().max())
+            .map(Duration::from_secs);
+
+        match (self.max_run_duration, window_limit) {
+            (Some(max), Some(window)) => Some(max.min(window)),
+            (max, window) => max.or(window),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01T00:00:00Z
+    const MIDNIGHT: u64 = 1_704_067_200;
+
+    fn at(hour: u64, minute: u64) -> u64 {
+        MIDNIGHT + hour * 3600 + minute * 60
+    }
+
+    #[test]
+    fn parse_window() {
+        let window: PruneWindow = "01:00-05:30".parse().unwrap();
+        assert_eq!(window, PruneWindow::new((1, 0), (5, 30)).unwrap());
+        assert_eq!(window.to_string(), "01:00-05:30");
+
+        assert!("1:00".parse::<PruneWindow>().is_err());
+        assert!("24:00-01:00".parse::<PruneWindow>().is_err());
+        assert!("01:60-02:00".parse::<PruneWindow>().is_err());
+    }
+
+    #[test]
+    fn window_contains() {
+        let window = PruneWindow::new((1, 0), (5, 0)).unwrap();
+        assert!(!window.contains(at(0, 59)));
+        assert!(window.contains(at(1, 0)));
+        assert!(window.contains(at(4, 59)));
+        assert!(!window.contains(at(5, 0)));
+        assert_eq!(window.secs_until_open(at(0, 30)), 30 * 60);
+        assert_eq!(window.secs_until_open(at(6, 0)), 19 * 3600);
+        assert_eq!(window.secs_until_close(at(4, 0)), Some(3600));
+
+        // wraps around midnight
+        let window = PruneWindow::new((22, 0), (2, 0)).unwrap();
+        assert!(window.contains(at(23, 0)));
+        assert!(window.contains(at(1, 0)));
+        assert!(!window.contains(at(12, 0)));
+        assert_eq!(window.secs_until_close(at(23, 0)), Some(3 * 3600));
+    }
+
+    #[test]
+    fn schedule() {
+        let schedule = PruneSchedule {
+            windows: vec![
+                PruneWindow::new((1, 0), (5, 0)).unwrap(),
+                PruneWindow::new((13, 0), (14, 0)).unwrap(),
+            ],
+            max_run_duration: Some(Duration::from_secs(2 * 3600)),
+            emergency_distance: Some(1000),
+        };
+
+        assert!(schedule.is_open(at(2, 0)));
+        assert!(!schedule.is_open(at(6, 0)));
+        assert_eq!(schedule.next_run(at(2, 0)), at(2, 0));
+        assert_eq!(schedule.next_run(at(6, 0)), at(13, 0));
+        assert_eq!(schedule.next_run(at(15, 0)), at(25, 0));
+
+        assert_eq!(schedule.run_time_limit(at(2, 0)), Some(Duration::from_secs(2 * 3600)));
+        assert_eq!(schedule.run_time_limit(at(13, 30)), Some(Duration::from_secs(30 * 60)));
+
+        assert!(!schedule.is_emergency(999));
+        assert!(schedule.is_emergency(1000));
+
+        let schedule = PruneSchedule::default();
+        assert!(schedule.is_open(at(6, 0)));
+        assert_eq!(schedule.next_run(at(6, 0)), at(6, 0));
+        assert_eq!(schedule.run_time_limit(at(6, 0)), None);
+        assert!(!schedule.is_emergency(u64::MAX));
+    }
+
+    #[test]
+    fn schedule_serde_roundtrip() {
+        let schedule = PruneSchedule {
+            windows: vec![PruneWindow::new((22, 0), (2, 0)).unwrap()],
+            max_run_duration: Some(Duration::from_secs(600)),
+            emergency_distance: Some(10_000),
+        };
+        let s = toml::to_string(&schedule).unwrap();
+        assert_eq!(
+            s,
+            "windows = [\"22:00-02:00\"]\nmax_run_duration = \"10m\"\nemergency_distance = 10000\n"
+        );
+        assert_eq!(toml::from_str::<PruneSchedule>(&s).unwrap(), schedule);
+    }
+}
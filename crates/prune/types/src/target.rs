@@ -1,8 +1,8 @@
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{Address, BlockNumber, B256};
 use derive_more::Display;
 use thiserror::Error;
 
-use crate::{PruneMode, ReceiptsLogPruneConfig};
+use crate::{PruneMode, PruneSegmentError, ReceiptsLogPruneConfig, ReceiptsLogTopicPruneConfig};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -90,6 +90,14 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Receipts pruning configuration by retaining only those receipts that contain logs with the
+    /// specified first topics, i.e. event signatures, discarding others. A receipt is retained if
+    /// it matches either this or `receipts_log_filter`. This setting is overridden by `receipts`.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(skip_serializing_if = "ReceiptsLogTopicPruneConfig::is_empty")
+    )]
+    pub receipts_log_topic_filter: ReceiptsLogTopicPruneConfig,
 }
 
 impl PruneModes {
@@ -108,12 +116,37 @@ impl PruneModes {
             storage_history: Some(PruneMode::Full),
             bodies_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            receipts_log_topic_filter: Default::default(),
         }
     }
 
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
-        self.receipts.is_some() || !self.receipts_log_filter.is_empty()
+        self.receipts.is_some() || self.has_receipts_log_filter()
+    }
+
+    /// Returns whether receipts are filtered by the addresses or the topics of their logs.
+    pub fn has_receipts_log_filter(&self) -> bool {
+        !self.receipts_log_filter.is_empty() || !self.receipts_log_topic_filter.is_empty()
+    }
+
+    /// Returns the block from which on the receipts with logs emitted by any of the `addresses`,
+    /// or with any of the `topics` as their first topic, are retained by the receipts log filters,
+    /// given the `tip` block number.
+    ///
+    /// Returns `None` if neither all `addresses` nor all `topics` are retained.
+    pub fn receipts_log_filter_retained_from<'a>(
+        &self,
+        tip: BlockNumber,
+        addresses: impl IntoIterator<Item = &'a Address>,
+        topics: impl IntoIterator<Item = &'a B256>,
+    ) -> Result<Option<BlockNumber>, PruneSegmentError> {
+        let by_address = self.receipts_log_filter.retained_from(tip, addresses)?;
+        let by_topic = self.receipts_log_topic_filter.retained_from(tip, topics)?;
+        Ok(match (by_address, by_topic) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
     }
 
     /// Returns true if all prune modes are set to [`None`].
@@ -195,9 +228,67 @@ fn deserialize_opt_prune_mode_with_min_blocks<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::collections::BTreeMap;
     use assert_matches::assert_matches;
     use serde::Deserialize;
 
+    #[test]
+    fn test_receipts_log_filter_retained_from() {
+        let address = Address::new([1; 20]);
+        let topic = B256::new([1; 32]);
+        let modes = PruneModes {
+            receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
+                address,
+                PruneMode::Before(800),
+            )])),
+            receipts_log_topic_filter: ReceiptsLogTopicPruneConfig(BTreeMap::from([(
+                topic,
+                PruneMode::Before(500),
+            )])),
+            ..Default::default()
+        };
+        assert!(modes.has_receipts_log_filter());
+
+        let tip = 100000;
+        assert_eq!(modes.receipts_log_filter_retained_from(tip, [], []).unwrap(), None);
+        assert_eq!(
+            modes.receipts_log_filter_retained_from(tip, [&address], []).unwrap(),
+            Some(800)
+        );
+        assert_eq!(modes.receipts_log_filter_retained_from(tip, [], [&topic]).unwrap(), Some(500));
+        assert_eq!(
+            modes.receipts_log_filter_retained_from(tip, [&address], [&topic]).unwrap(),
+            Some(500)
+        );
+        assert_eq!(
+            modes
+                .receipts_log_filter_retained_from(tip, [&address], [&topic, &B256::ZERO])
+                .unwrap(),
+            Some(800)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_receipts_log_topic_filter() {
+        let modes: PruneModes = toml::from_str(
+            r#"
+[receipts_log_topic_filter]
+"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef" = { distance = 100000 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            modes.receipts_log_topic_filter.0,
+            BTreeMap::from([(
+                alloy_primitives::b256!(
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+                ),
+                PruneMode::Distance(100000)
+            )])
+        );
+        assert!(modes.receipts_log_filter.is_empty());
+    }
+
     #[test]
     fn test_deserialize_opt_prune_mode_with_min_blocks() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -5,10 +5,30 @@ use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{Block, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+    BlockTraceResult, CallConfig, CallFrame, GethDebugTracingCallOptions, GethDebugTracingOptions,
+    GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+
+/// A call frame of a transaction trace streamed by `debug_subscribeTraceTransaction`.
+///
+/// Frames are streamed in the order the calls were entered, so the parent of a frame is always
+/// streamed before the frame itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamedCallFrame {
+    /// The index of the frame in the trace.
+    pub index: usize,
+    /// The index of the parent frame, `None` for the top-level call.
+    pub parent: Option<usize>,
+    /// The total number of frames in the trace.
+    pub total: usize,
+    /// The call frame, without any nested calls except for selfdestructs.
+    #[serde(flatten)]
+    pub frame: CallFrame,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -87,6 +107,9 @@ pub trait DebugApi<TxReq: RpcObject> {
     /// exact same manner as it was executed on the network. It will replay any transaction that
     /// may have been executed prior to this one before it will finally attempt to execute the
     /// transaction that corresponds to the given hash.
+    ///
+    /// In addition to the geth built-in tracers, the `opcodeStats` tracer returns the number of
+    /// executions and the combined gas usage per opcode, without recording the individual steps.
     #[method(name = "traceTransaction")]
     async fn debug_trace_transaction(
         &self,
@@ -94,6 +117,21 @@ pub trait DebugApi<TxReq: RpcObject> {
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTrace>;
 
+    /// Streaming variant of `debug_traceTransaction` with the `callTracer`.
+    ///
+    /// Instead of returning the nested call trace at once, every call frame is sent as a separate
+    /// notification, which avoids building huge responses for transactions with many calls.
+    #[subscription(
+        name = "subscribeTraceTransaction",
+        unsubscribe = "unsubscribeTraceTransaction",
+        item = StreamedCallFrame
+    )]
+    async fn debug_subscribe_trace_transaction(
+        &self,
+        tx_hash: B256,
+        config: Option<CallConfig>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
     /// block execution using the final state of parent block as the base.
     ///
@@ -0,0 +1,102 @@
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, TxHash, B256, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks returned by `explorer_getLatestBlocks`.
+pub const EXPLORER_MAX_LATEST_BLOCKS: u64 = 100;
+
+/// The fields of a block that are shown in block lists.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSummary {
+    /// Block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Block hash.
+    pub hash: B256,
+    /// Block timestamp.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// Beneficiary of the block.
+    pub miner: Address,
+    /// Gas used by the transactions of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Base fee per gas, if the block is post London.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub base_fee_per_gas: Option<u64>,
+    /// Number of transactions in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_count: u64,
+}
+
+/// The fields of a transaction that are shown in transaction lists.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummary {
+    /// Transaction hash.
+    pub hash: TxHash,
+    /// Sender of the transaction.
+    pub from: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// Transferred value.
+    pub value: U256,
+    /// Nonce of the sender.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Transaction type.
+    #[serde(rename = "type", with = "alloy_serde::quantity")]
+    pub tx_type: u8,
+    /// Gas used by the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Whether the transaction succeeded.
+    pub status: bool,
+}
+
+/// The state of an address that is shown on its overview page.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSummary {
+    /// Balance of the address.
+    pub balance: U256,
+    /// Nonce of the address.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Whether code is deployed at the address.
+    pub is_contract: bool,
+}
+
+/// Explorer API namespace for the queries small block explorers issue the most.
+///
+/// Every method is answered with a single request and only returns the fields explorers show in
+/// their lists, instead of requiring one request per block or transaction.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "explorer"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "explorer"))]
+pub trait ExplorerApi {
+    /// Returns the summaries of the latest `count` blocks, newest first.
+    ///
+    /// At most [`EXPLORER_MAX_LATEST_BLOCKS`] blocks are returned.
+    #[method(name = "getLatestBlocks")]
+    async fn get_latest_blocks(&self, count: u64) -> RpcResult<Vec<BlockSummary>>;
+
+    /// Returns the summaries of all transactions in a block, or `None` if the block wasn't found.
+    #[method(name = "getBlockTransactions")]
+    async fn get_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionSummary>>>;
+
+    /// Returns the summary of an address at the given block, defaults to the latest block.
+    #[method(name = "getAddressSummary")]
+    async fn get_address_summary(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<AddressSummary>;
+}
@@ -18,6 +18,7 @@ mod admin;
 mod anvil;
 mod debug;
 mod engine;
+mod explorer;
 mod hardhat;
 mod mev;
 mod miner;
@@ -30,6 +31,11 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::StreamedCallFrame;
+pub use explorer::{AddressSummary, BlockSummary, TransactionSummary, EXPLORER_MAX_LATEST_BLOCKS};
+pub use reth::{AccountChange, ContractCreator, TRANSACTIONS_BY_SENDER_PAGE_SIZE};
+pub use txpool::{DEFAULT_TXPOOL_STATS_PERCENTILES, MAX_TXPOOL_STATS_PERCENTILES};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -39,11 +45,12 @@ pub mod servers {
         admin::AdminApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        explorer::ExplorerApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethEngineApiServer, RethFeesApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -68,12 +75,13 @@ pub mod clients {
         anvil::AnvilApiClient,
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
+        explorer::ExplorerApiClient,
         hardhat::HardhatApiClient,
         mev::{MevFullApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethEngineApiClient, RethFeesApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
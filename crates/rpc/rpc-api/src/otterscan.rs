@@ -69,23 +69,31 @@ pub trait Otterscan<T: RpcObject, H: RpcObject> {
         page_size: usize,
     ) -> RpcResult<OtsBlockTransactions<T, H>>;
 
-    /// Gets paginated inbound/outbound transaction calls for a certain address.
+    /// Gets paginated inbound/outbound transactions of a certain address in the blocks before the
+    /// given block, or in all blocks if the block number is 0, from the most recent to the oldest.
+    ///
+    /// A page contains at least `page_size` transactions if available, and always all matching
+    /// transactions of its oldest block, so that the next page can start at that block.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
         address: Address,
         block_number: LenientBlockNumberOrTag,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
-    /// Gets paginated inbound/outbound transaction calls for a certain address.
+    /// Gets paginated inbound/outbound transactions of a certain address in the blocks after the
+    /// given block, from the most recent to the oldest.
+    ///
+    /// A page contains at least `page_size` transactions if available, and always all matching
+    /// transactions of its most recent block, so that the next page can start at that block.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
         address: Address,
         block_number: LenientBlockNumberOrTag,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
     /// Gets the transaction hash for a certain sender address, given its nonce.
     #[method(name = "getTransactionBySenderAndNonce")]
@@ -1,11 +1,49 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, TxHash, B256, U256, U64};
+use alloy_rpc_types_beacon::requests::ExecutionRequestsV4;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_engine_primitives::{BackfillThresholds, EngineClientInfo, ForkchoiceHistoryEntry};
+use reth_payload_primitives::PayloadJobStats;
+use reth_primitives_traits::Account;
+use reth_rpc_eth_types::{EthStateCacheLimits, EthStateCacheStats, RpcLimits};
+use reth_transaction_pool::SubPoolLimits;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
 use reth_chain_state as _;
 
+/// The number of transactions returned per page by `reth_getTransactionsBySender`.
+pub const TRANSACTIONS_BY_SENDER_PAGE_SIZE: usize = 100;
+
+/// The state of an account before and after a block.
+///
+/// `None` means the account did not exist before the block or was destroyed by it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountChange {
+    /// The account before the block was executed.
+    pub before: Option<Account>,
+    /// The account after the block was executed.
+    pub after: Option<Account>,
+}
+
+/// The deployment of a contract, as returned by `reth_getContractCreator`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreator {
+    /// The hash of the code the contract was deployed with.
+    pub code_hash: B256,
+    /// The number of the block the contract was deployed in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The hash of the block the contract was deployed in.
+    pub block_hash: B256,
+    /// The hash of the transaction the contract was deployed in.
+    pub transaction_hash: TxHash,
+    /// The sender of the transaction the contract was deployed in.
+    pub creator: Address,
+}
+
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
@@ -17,6 +55,53 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the nonce, balance and bytecode hash changes of all accounts modified in a block
+    #[method(name = "getAccountChangesInBlock")]
+    async fn reth_get_account_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<HashMap<Address, AccountChange>>;
+
+    /// Returns the [EIP-7685] requests of a block, decoded into deposit ([EIP-6110]), withdrawal
+    /// ([EIP-7002]) and consolidation ([EIP-7251]) requests.
+    ///
+    /// The requests are stored when the block is executed. Blocks before Prague have no requests,
+    /// and the requests of blocks that were executed by an older version of the node are not
+    /// available.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    /// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
+    /// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
+    /// [EIP-7251]: https://eips.ethereum.org/EIPS/eip-7251
+    #[method(name = "getExecutionRequests")]
+    async fn reth_get_execution_requests(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<ExecutionRequestsV4>;
+
+    /// Returns the hashes of the transactions sent by an address, oldest first.
+    ///
+    /// Transactions are returned in pages of [`TRANSACTIONS_BY_SENDER_PAGE_SIZE`], starting at page
+    /// `0`. Only transactions of blocks persisted to disk are included, and the method requires
+    /// the sender transactions index to be enabled.
+    #[method(name = "getTransactionsBySender")]
+    async fn reth_get_transactions_by_sender(
+        &self,
+        address: Address,
+        page: u64,
+    ) -> RpcResult<Vec<TxHash>>;
+
+    /// Returns the block and transaction the contract currently deployed at an address was
+    /// deployed in.
+    ///
+    /// Returns `null` if there is no contract at the address, or if it was deployed in the
+    /// genesis. The method requires the contract deployments index to be enabled.
+    #[method(name = "getContractCreator")]
+    async fn reth_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> RpcResult<Option<ContractCreator>>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -25,3 +110,104 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for reth-specific fee methods.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethFeesApi {
+    /// Returns a suggestion for the max fee per blob gas of blob transactions.
+    ///
+    /// The suggestion is the highest blob base fee projected for the given number of upcoming
+    /// blocks (default: 5, max: 64), assuming that the blob transactions currently in the pool are
+    /// included as fast as the blob capacity of the blocks allows.
+    #[method(name = "maxFeePerBlobGas")]
+    async fn reth_max_fee_per_blob_gas(&self, blocks: Option<U64>) -> RpcResult<U256>;
+}
+
+/// Reth API namespace for reth-specific methods that are only served on the authenticated engine
+/// API server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethEngineApi {
+    /// Returns the statistics of the active and most recently finished payload jobs, active jobs
+    /// first.
+    #[method(name = "payloadStats")]
+    async fn reth_payload_stats(&self) -> RpcResult<Vec<PayloadJobStats>>;
+
+    /// Starts a backfill sync to the given block.
+    ///
+    /// Returns `false` if a backfill sync is already running or about to start.
+    #[method(name = "triggerBackfill")]
+    async fn reth_trigger_backfill(&self, target: B256) -> RpcResult<bool>;
+
+    /// Returns the thresholds that control when the engine switches from live sync to a backfill
+    /// sync.
+    #[method(name = "backfillThresholds")]
+    async fn reth_backfill_thresholds(&self) -> RpcResult<BackfillThresholds>;
+
+    /// Updates the thresholds that control when the engine switches from live sync to a backfill
+    /// sync and returns the previous ones.
+    #[method(name = "setBackfillThresholds")]
+    async fn reth_set_backfill_thresholds(
+        &self,
+        thresholds: BackfillThresholds,
+    ) -> RpcResult<BackfillThresholds>;
+
+    /// Returns the size limits of the sub-pools of the transaction pool.
+    #[method(name = "poolLimits")]
+    async fn reth_pool_limits(&self) -> RpcResult<SubPoolLimits>;
+
+    /// Updates the size limits of the sub-pools of the transaction pool and returns the previous
+    /// ones.
+    ///
+    /// Transactions that exceed the new limits are evicted right away.
+    #[method(name = "setPoolLimits")]
+    async fn reth_set_pool_limits(&self, limits: SubPoolLimits) -> RpcResult<SubPoolLimits>;
+
+    /// Returns the usage statistics and size limits of the tiers of the `eth` RPC state cache.
+    #[method(name = "ethCacheStats")]
+    async fn reth_eth_cache_stats(&self) -> RpcResult<EthStateCacheStats>;
+
+    /// Updates the size limits of the tiers of the `eth` RPC state cache and returns the previous
+    /// ones.
+    ///
+    /// Entries that exceed the new limits are evicted right away.
+    #[method(name = "setEthCacheLimits")]
+    async fn reth_set_eth_cache_limits(
+        &self,
+        limits: EthStateCacheLimits,
+    ) -> RpcResult<EthStateCacheLimits>;
+
+    /// Returns the gas cap, tx fee cap, per-namespace execution timeouts and state history depth of
+    /// the RPC.
+    #[method(name = "rpcLimits")]
+    async fn reth_rpc_limits(&self) -> RpcResult<RpcLimits>;
+
+    /// Updates the gas cap, tx fee cap, per-namespace execution timeouts and state history depth of
+    /// the RPC and returns the previous ones.
+    ///
+    /// Requests that are already running are not affected. A timed out request stops executing
+    /// before its next transaction. The tx fee cap can only be lowered below the one the node was
+    /// started with, which is still enforced by the transaction pool.
+    #[method(name = "setRpcLimits")]
+    async fn reth_set_rpc_limits(&self, limits: RpcLimits) -> RpcResult<RpcLimits>;
+
+    /// Returns the engine API usage of the consensus client: the capabilities it announced in
+    /// `engine_exchangeCapabilities`, its client version and the versions of the engine API
+    /// methods it called.
+    ///
+    /// This helps to debug mismatches between the consensus and execution client after a fork.
+    #[method(name = "engineClientInfo")]
+    async fn reth_engine_client_info(&self) -> RpcResult<EngineClientInfo>;
+
+    /// Returns the most recent changes of the canonical head, most recent first, including the
+    /// depth of the reorg that caused them, if any.
+    ///
+    /// Returns all recorded changes if no limit is given. The number of recorded changes is bounded
+    /// by the fork choice history size of the engine.
+    #[method(name = "getReorgHistory")]
+    async fn reth_get_reorg_history(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ForkchoiceHistoryEntry>>;
+}
@@ -73,12 +73,10 @@ pub trait TraceApi<TxReq> {
     #[method(name = "filter")]
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
 
-    /// Returns transaction trace at given index.
+    /// Returns transaction trace at given position.
     ///
-    /// `indices` represent the index positions of the traces.
-    ///
-    /// Note: This expects a list of indices but only one is supported since this function returns a
-    /// single [`LocalizedTransactionTrace`].
+    /// `indices` represent the trace address of the trace, i.e. the path to the trace in the call
+    /// tree of the transaction: `[]` is the top level call and `[0]` is its first subcall.
     #[method(name = "get")]
     async fn trace_get(
         &self,
@@ -2,6 +2,13 @@ use alloy_json_rpc::RpcObject;
 use alloy_primitives::Address;
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::PoolStats;
+
+/// The fee percentiles returned by `txpool_stats` if none are requested.
+pub const DEFAULT_TXPOOL_STATS_PERCENTILES: [f64; 5] = [10.0, 25.0, 50.0, 75.0, 90.0];
+
+/// The maximum number of fee percentiles that can be requested from `txpool_stats`.
+pub const MAX_TXPOOL_STATS_PERCENTILES: usize = 100;
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -14,6 +21,13 @@ pub trait TxPoolApi<T: RpcObject> {
     #[method(name = "status")]
     async fn txpool_status(&self) -> RpcResult<TxpoolStatus>;
 
+    /// Returns the fee distribution, accumulated gas and blob counts of each sub-pool.
+    ///
+    /// The fees are returned at the given percentiles, in `0.0..=100.0`, or at
+    /// [`DEFAULT_TXPOOL_STATS_PERCENTILES`] if none are given.
+    #[method(name = "stats")]
+    async fn txpool_stats(&self, percentiles: Option<Vec<f64>>) -> RpcResult<PoolStats>;
+
     /// Returns a summary of all the transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
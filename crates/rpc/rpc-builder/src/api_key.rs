@@ -0,0 +1,373 @@
+//! API key authentication for the RPC server.
+//!
+//! Operators can map static API keys to the RPC namespaces and methods they are allowed to call,
+//! e.g. to expose `debug_` to internal tooling while keeping the same port public for `eth_`.
+//!
+//! This consists of two layers:
+//! - [`ApiKeyAuthLayer`]: an HTTP middleware that resolves the permissions of the request from the
+//!   [`API_KEY_HEADER`] and rejects requests with unknown keys.
+//! - [`ApiKeyPermissionLayer`]: an RPC middleware that rejects calls to methods that are not
+//!   permitted for the resolved permissions.
+
+use http::{HeaderMap, Response, StatusCode};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, ResponseFuture},
+    server::{middleware::rpc::RpcServiceT, HttpBody, HttpRequest, HttpResponse, MethodResponse},
+    types::{ErrorObject, ErrorObjectOwned, Request},
+};
+use reth_fs_util::FsPathError;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    path::Path,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The HTTP header that carries the API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The error code returned for calls to methods that are not permitted.
+pub const METHOD_NOT_PERMITTED_CODE: i32 = -32001;
+
+/// The namespaces and methods a caller is allowed to call.
+///
+/// Deserialized from a list of entries, where each entry is either:
+/// - `*`: all methods are allowed
+/// - a namespace, e.g. `eth`: all methods of the namespace are allowed
+/// - a method, e.g. `debug_traceTransaction`: only this method is allowed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "Vec<String>")]
+pub struct RpcPermissions {
+    /// Whether all methods are allowed.
+    all: bool,
+    /// Namespaces of which all methods are allowed.
+    namespaces: HashSet<String>,
+    /// Individually allowed methods.
+    methods: HashSet<String>,
+}
+
+impl RpcPermissions {
+    /// Returns permissions that allow all methods.
+    pub fn allow_all() -> Self {
+        Self { all: true, ..Default::default() }
+    }
+
+    /// Returns true if the given method is allowed.
+    pub fn allows(&self, method: &str) -> bool {
+        self.all ||
+            self.methods.contains(method) ||
+            method
+                .split_once('_')
+                .is_some_and(|(namespace, _)| self.namespaces.contains(namespace))
+    }
+}
+
+impl<T: Into<String>> FromIterator<T> for RpcPermissions {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut permissions = Self::default();
+        for entry in iter {
+            let entry = entry.into();
+            if entry == "*" {
+                permissions.all = true;
+            } else if entry.contains('_') {
+                permissions.methods.insert(entry);
+            } else {
+                permissions.namespaces.insert(entry);
+            }
+        }
+        permissions
+    }
+}
+
+impl From<Vec<String>> for RpcPermissions {
+    fn from(entries: Vec<String>) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+/// Maps API keys to the [`RpcPermissions`] of their holders.
+///
+/// Loaded from a JSON file of the form:
+///
+/// ```json
+/// {
+///   "public": ["eth", "net", "web3"],
+///   "keys": {
+///     "internal-tooling-key": ["*"],
+///     "tracing-key": ["eth", "debug_traceTransaction"]
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Permissions of requests without an API key.
+    ///
+    /// If not set, requests without an API key are rejected.
+    #[serde(default)]
+    pub public: Option<RpcPermissions>,
+    /// Permissions of each API key.
+    #[serde(default)]
+    pub keys: HashMap<String, RpcPermissions>,
+}
+
+impl ApiKeyConfig {
+    /// Loads the config from the JSON file at the given path.
+    pub fn load(path: &Path) -> Result<Self, FsPathError> {
+        reth_fs_util::read_json_file(path)
+    }
+
+    /// Sets the permissions of requests without an API key.
+    pub fn with_public(mut self, permissions: RpcPermissions) -> Self {
+        self.public = Some(permissions);
+        self
+    }
+
+    /// Adds an API key with the given permissions.
+    pub fn with_key(mut self, key: impl Into<String>, permissions: RpcPermissions) -> Self {
+        self.keys.insert(key.into(), permissions);
+        self
+    }
+}
+
+/// HTTP middleware that resolves the [`RpcPermissions`] of a request from its
+/// [`API_KEY_HEADER`].
+///
+/// The resolved permissions are inserted into the request extensions, from where they are picked
+/// up by the [`ApiKeyPermissionLayer`]. Requests with an unknown API key, or without an API key if
+/// no public permissions are configured, are rejected with `401 Unauthorized`.
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuthLayer {
+    inner: Arc<ApiKeyAuthLayerInner>,
+}
+
+impl ApiKeyAuthLayer {
+    /// Creates a new layer from the given config.
+    pub fn new(config: ApiKeyConfig) -> Self {
+        Self {
+            inner: Arc::new(ApiKeyAuthLayerInner {
+                public: config.public.map(Arc::new),
+                keys: config.keys.into_iter().map(|(key, perms)| (key, Arc::new(perms))).collect(),
+            }),
+        }
+    }
+
+    /// Returns the permissions of a request with the given headers.
+    fn permissions(&self, headers: &HeaderMap) -> Result<Arc<RpcPermissions>, HttpResponse> {
+        match headers.get(API_KEY_HEADER) {
+            Some(key) => key
+                .to_str()
+                .ok()
+                .and_then(|key| self.inner.keys.get(key))
+                .cloned()
+                .ok_or_else(|| unauthorized("invalid API key")),
+            None => self.inner.public.clone().ok_or_else(|| unauthorized("missing API key")),
+        }
+    }
+}
+
+impl<S> Layer<S> for ApiKeyAuthLayer {
+    type Service = ApiKeyAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyAuthService { layer: self.clone(), inner }
+    }
+}
+
+/// The resolved permissions of the [`ApiKeyAuthLayer`].
+#[derive(Debug)]
+struct ApiKeyAuthLayerInner {
+    /// Permissions of requests without an API key.
+    public: Option<Arc<RpcPermissions>>,
+    /// Permissions of each API key.
+    keys: HashMap<String, Arc<RpcPermissions>>,
+}
+
+/// The HTTP service of the [`ApiKeyAuthLayer`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuthService<S> {
+    layer: ApiKeyAuthLayer,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for ApiKeyAuthService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, Result<HttpResponse, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        match self.layer.permissions(req.headers()) {
+            Ok(permissions) => {
+                req.extensions_mut().insert(permissions);
+                ResponseFuture::future(self.inner.call(req))
+            }
+            Err(response) => ResponseFuture::ready(Ok(response)),
+        }
+    }
+}
+
+/// Builds a `401 Unauthorized` response with the given message.
+fn unauthorized(message: &'static str) -> HttpResponse {
+    Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .body(HttpBody::from(message))
+        .expect("This should never happen")
+}
+
+/// RPC middleware that rejects calls to methods that are not allowed by the [`RpcPermissions`]
+/// resolved by the [`ApiKeyAuthLayer`].
+///
+/// Requests without resolved permissions, e.g. requests over IPC, are not restricted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiKeyPermissionLayer;
+
+impl<S> Layer<S> for ApiKeyPermissionLayer {
+    type Service = ApiKeyPermissionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyPermissionService { inner }
+    }
+}
+
+/// The RPC service of the [`ApiKeyPermissionLayer`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyPermissionService<S> {
+    inner: S,
+}
+
+impl<S> RpcServiceT for ApiKeyPermissionService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        if is_permitted(&req) {
+            ResponseFuture::future(self.inner.call(req))
+        } else {
+            let err = method_not_permitted(req.method_name());
+            ResponseFuture::ready(MethodResponse::error(req.id, err))
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // the inner service doesn't go through `call` for batch entries, so they're checked here
+        for entry in requests.iter_mut() {
+            let Ok(BatchEntry::Call(req)) = entry else { continue };
+            if !is_permitted(req) {
+                let err =
+                    BatchEntryErr::new(req.id.clone(), method_not_permitted(req.method_name()));
+                *entry = Err(err);
+            }
+        }
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        let permitted = n
+            .extensions()
+            .get::<Arc<RpcPermissions>>()
+            .is_none_or(|permissions| permissions.allows(n.method_name()));
+        if permitted {
+            ResponseFuture::future(self.inner.notification(n))
+        } else {
+            ResponseFuture::ready(MethodResponse::notification())
+        }
+    }
+}
+
+/// Returns true if the request is allowed by its resolved [`RpcPermissions`], if any.
+fn is_permitted(req: &Request<'_>) -> bool {
+    req.extensions()
+        .get::<Arc<RpcPermissions>>()
+        .is_none_or(|permissions| permissions.allows(req.method_name()))
+}
+
+/// Returns the error for a call to a method that is not permitted.
+fn method_not_permitted(method: &str) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        METHOD_NOT_PERMITTED_CODE,
+        format!("method {method} is not permitted"),
+        None::<()>,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn parse_permissions() {
+        let config: ApiKeyConfig = serde_json::from_str(
+            r#"{
+                "public": ["eth", "net"],
+                "keys": {
+                    "internal": ["*"],
+                    "tracing": ["eth", "debug_traceTransaction"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let public = config.public.as_ref().unwrap();
+        assert!(public.allows("eth_call"));
+        assert!(public.allows("net_version"));
+        assert!(!public.allows("debug_traceTransaction"));
+        assert!(!public.allows("ethx_call"));
+
+        assert!(config.keys["internal"].allows("admin_addPeer"));
+
+        let tracing = &config.keys["tracing"];
+        assert!(tracing.allows("eth_blockNumber"));
+        assert!(tracing.allows("debug_traceTransaction"));
+        assert!(!tracing.allows("debug_traceBlockByNumber"));
+    }
+
+    #[test]
+    fn resolve_api_key() {
+        let layer = ApiKeyAuthLayer::new(
+            ApiKeyConfig::default()
+                .with_public(RpcPermissions::from(vec!["eth".to_string()]))
+                .with_key("secret", RpcPermissions::allow_all()),
+        );
+
+        let mut headers = HeaderMap::new();
+        assert!(!layer.permissions(&headers).unwrap().allows("debug_traceTransaction"));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("secret"));
+        assert!(layer.permissions(&headers).unwrap().allows("debug_traceTransaction"));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("unknown"));
+        let response = layer.permissions(&headers).unwrap_err();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let layer = ApiKeyAuthLayer::new(ApiKeyConfig::default());
+        let response = layer.permissions(&HeaderMap::new()).unwrap_err();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
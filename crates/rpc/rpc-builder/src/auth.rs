@@ -341,6 +341,16 @@ impl AuthServerHandle {
         handle.stop()
     }
 
+    /// Waits until the server has stopped.
+    ///
+    /// This resolves once [`AuthServerHandle::stop`] has been called on this handle or any of its
+    /// clones and the server finished processing in-flight requests.
+    pub async fn stopped(self) {
+        if let Some(handle) = self.handle {
+            handle.stopped().await
+        }
+    }
+
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
         format!("http://{}", self.local_addr)
@@ -0,0 +1,234 @@
+//! Concurrent execution of JSON-RPC batch requests.
+//!
+//! By default the calls of a batch request are executed one after another. The [`BatchLayer`]
+//! executes up to [`BatchConfig::parallelism`] calls of a batch concurrently, while the responses
+//! are still returned in the order of the requests.
+//!
+//! Every call of a batch has a cost, see [`method_cost`]. Calls that would exceed the
+//! [`BatchConfig::max_cost`] of the batch are rejected without being executed, so that a single
+//! huge batch can't occupy the blocking task pool. There is no limit by default.
+//!
+//! The layer is the innermost RPC middleware: custom middleware set with
+//! [`RpcServerConfig::set_rpc_middleware`](crate::RpcServerConfig::set_rpc_middleware) receives
+//! batch requests through [`RpcServiceT::batch`] and forwards them to this layer.
+
+use futures::{stream, StreamExt};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, ErrorObjectOwned, Request},
+    BatchResponseBuilder, MethodResponse,
+};
+use reth_rpc_server_types::constants::DEFAULT_BATCH_PARALLELISM;
+use std::future::Future;
+
+/// The error code returned for calls that exceed the maximum cost of a batch.
+pub const BATCH_COST_EXCEEDED_CODE: i32 = -32005;
+
+/// The cost of a call that is executed on the blocking task pool.
+pub const BLOCKING_CALL_COST: u64 = 10;
+
+/// The cost of any other call.
+pub const CALL_COST: u64 = 1;
+
+/// Returns the cost of a call to the given method in a batch request.
+///
+/// Tracing calls and calls that execute transactions or scan the database run on the blocking
+/// task pool and are more expensive than other calls.
+pub fn method_cost(method: &str) -> u64 {
+    if method.starts_with("debug_") ||
+        method.starts_with("trace_") ||
+        matches!(
+            method,
+            "eth_call" |
+                "eth_callMany" |
+                "eth_estimateGas" |
+                "eth_createAccessList" |
+                "eth_simulateV1" |
+                "eth_getProof" |
+                "eth_getLogs"
+        )
+    {
+        BLOCKING_CALL_COST
+    } else {
+        CALL_COST
+    }
+}
+
+/// Configuration of the [`BatchLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    /// Maximum number of calls of a batch that are executed concurrently.
+    pub parallelism: usize,
+    /// Maximum total cost of the calls of a batch, if any.
+    pub max_cost: Option<u64>,
+    /// Maximum size of the batch response in bytes.
+    pub max_response_size: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            parallelism: DEFAULT_BATCH_PARALLELISM,
+            max_cost: None,
+            // the default of the jsonrpsee server
+            max_response_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Sets the maximum number of calls of a batch that are executed concurrently.
+    pub const fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Sets the maximum total cost of the calls of a batch.
+    pub const fn with_max_cost(mut self, max_cost: Option<u64>) -> Self {
+        self.max_cost = max_cost;
+        self
+    }
+
+    /// Sets the maximum size of the batch response in bytes.
+    pub const fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+}
+
+/// RPC middleware that executes the calls of batch requests concurrently.
+///
+/// See the [module docs](self) for details.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchLayer {
+    config: BatchConfig,
+}
+
+impl BatchLayer {
+    /// Creates a new layer with the given config.
+    pub const fn new(config: BatchConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<S> tower::Layer<S> for BatchLayer {
+    type Service = BatchService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BatchService { inner, config: self.config }
+    }
+}
+
+/// The RPC service of the [`BatchLayer`].
+#[derive(Debug, Clone)]
+pub struct BatchService<S> {
+    inner: S,
+    config: BatchConfig,
+}
+
+impl<S> RpcServiceT for BatchService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        self.inner.call(req)
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        if let Some(max_cost) = self.config.max_cost {
+            let mut cost = 0u64;
+            for entry in requests.iter_mut() {
+                let Ok(BatchEntry::Call(req)) = entry else { continue };
+                cost = cost.saturating_add(method_cost(req.method_name()));
+                if cost > max_cost {
+                    *entry = Err(BatchEntryErr::new(req.id.clone(), batch_cost_exceeded(max_cost)));
+                }
+            }
+        }
+
+        let service = self.inner.clone();
+        let BatchConfig { parallelism, max_response_size, .. } = self.config;
+        async move {
+            let service = &service;
+            let mut responses = stream::iter(requests)
+                .map(|entry| async move {
+                    match entry {
+                        Ok(BatchEntry::Call(req)) => Some(service.call(req).await),
+                        Ok(BatchEntry::Notification(n)) => {
+                            service.notification(n).await;
+                            None
+                        }
+                        Err(err) => {
+                            let (err, id) = err.into_parts();
+                            Some(MethodResponse::error(id, err))
+                        }
+                    }
+                })
+                // responses are yielded in the order of the requests
+                .buffered(parallelism.max(1));
+
+            let mut batch = BatchResponseBuilder::new_with_limit(max_response_size);
+            let mut got_notification = false;
+            while let Some(response) = responses.next().await {
+                let Some(response) = response else {
+                    got_notification = true;
+                    continue
+                };
+                if let Err(too_large) = batch.append(response) {
+                    return too_large
+                }
+            }
+
+            if batch.is_empty() && got_notification {
+                MethodResponse::notification()
+            } else {
+                MethodResponse::from_batch(batch.finish())
+            }
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Returns the error for a call that exceeds the maximum cost of its batch.
+fn batch_cost_exceeded(max_cost: u64) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        BATCH_COST_EXCEEDED_CODE,
+        format!("batch exceeds the maximum cost of {max_cost}"),
+        None::<()>,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_costs() {
+        assert_eq!(method_cost("eth_blockNumber"), CALL_COST);
+        assert_eq!(method_cost("eth_getBalance"), CALL_COST);
+        assert_eq!(method_cost("eth_call"), BLOCKING_CALL_COST);
+        assert_eq!(method_cost("debug_traceTransaction"), BLOCKING_CALL_COST);
+        assert_eq!(method_cost("trace_block"), BLOCKING_CALL_COST);
+    }
+}
@@ -1,7 +1,8 @@
 use jsonrpsee::server::ServerConfigBuilder;
+use reth_fs_util::FsPathError;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::ValidationApiConfig;
-use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
+use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig, RpcTimeouts};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{net::SocketAddr, path::PathBuf};
@@ -9,8 +10,8 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    api_key::ApiKeyConfig, auth::AuthServerConfig, batch::BatchConfig, cors::CorsConfig,
+    error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -79,6 +80,14 @@ pub trait RethRpcServerConfig {
     ///
     /// Note: this is not used for the auth server (engine API).
     fn rpc_secret_key(&self) -> Option<JwtSecret>;
+
+    /// Loads the configured API keys and their permissions for the regular rpc servers, if any.
+    fn rpc_api_keys(&self) -> Result<Option<ApiKeyConfig>, FsPathError>;
+
+    /// Loads the configured CORS domains of the http and ws servers, if any.
+    ///
+    /// If set, these take precedence over the CORS domains of the [`RpcServerConfig`].
+    fn rpc_cors_config(&self) -> Result<Option<CorsConfig>, FsPathError>;
 }
 
 impl RethRpcServerConfig for RpcServerArgs {
@@ -97,13 +106,22 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_log_query_cost(self.rpc_max_log_query_cost.unwrap_or_max())
             .eth_proof_window(self.rpc_eth_proof_window)
+            .max_state_history(self.rpc_max_state_history)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .rpc_tx_fee_cap(self.rpc_tx_fee_cap)
+            .rpc_timeouts(RpcTimeouts {
+                eth: self.rpc_eth_timeout.map(|timeout| timeout.as_millis() as u64),
+                debug: self.rpc_debug_timeout.map(|timeout| timeout.as_millis() as u64),
+                trace: self.rpc_trace_timeout.map(|timeout| timeout.as_millis() as u64),
+            })
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
             .pending_block_kind(self.rpc_pending_block)
+            .sync_progress(self.rpc_syncing_progress)
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -118,6 +136,9 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_headers: self.rpc_state_cache.max_headers,
+            max_blocks_bytes: self.rpc_state_cache.max_blocks_size * 1024 * 1024,
+            max_receipts_bytes: self.rpc_state_cache.max_receipts_size * 1024 * 1024,
+            max_headers_bytes: self.rpc_state_cache.max_headers_size * 1024 * 1024,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
         }
     }
@@ -179,7 +200,13 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config =
+            RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key()).with_batch_config(
+                BatchConfig::default()
+                    .with_parallelism(self.rpc_batch_parallelism)
+                    .with_max_cost(self.rpc_max_batch_cost.0)
+                    .with_max_response_size(self.rpc_max_response_size_bytes() as usize),
+            );
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -236,6 +263,14 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn rpc_secret_key(&self) -> Option<JwtSecret> {
         self.rpc_jwtsecret
     }
+
+    fn rpc_api_keys(&self) -> Result<Option<ApiKeyConfig>, FsPathError> {
+        self.rpc_api_keys.as_deref().map(ApiKeyConfig::load).transpose()
+    }
+
+    fn rpc_cors_config(&self) -> Result<Option<CorsConfig>, FsPathError> {
+        self.rpc_cors.as_deref().map(CorsConfig::load).transpose()
+    }
 }
 
 #[cfg(test)]
@@ -370,6 +405,7 @@ mod tests {
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(u64::MAX));
         assert_eq!(config.max_logs_per_response, Some(usize::MAX));
+        assert_eq!(config.max_log_query_cost, Some(u64::MAX));
     }
 
     #[test]
@@ -380,11 +416,14 @@ mod tests {
             "100",
             "--rpc-max-logs-per-response",
             "200",
+            "--rpc-max-log-query-cost",
+            "300",
         ])
         .args;
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
+        assert_eq!(config.max_log_query_cost, Some(300));
     }
 }
@@ -1,4 +1,15 @@
-use http::{HeaderValue, Method};
+//! CORS policies of the HTTP and WS servers.
+//!
+//! The policies are shared with the [`CorsLayer`] of the servers through a [`CorsHandle`], which
+//! allows replacing the allowed origins at runtime, e.g. when the config file is reloaded.
+
+use http::{header, request::Parts, HeaderValue, Method};
+use reth_fs_util::FsPathError;
+use serde::Deserialize;
+use std::{
+    path::Path,
+    sync::{Arc, RwLock},
+};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 /// Error thrown when parsing cors domains went wrong
@@ -17,37 +28,267 @@ pub enum CorsDomainError {
         /// The input string containing the incorrectly used wildcard
         input: String,
     },
+
+    /// Indicates that a wildcard was used in a domain other than as its leftmost label
+    #[error("{domain} is an invalid wildcard domain, expected e.g. `https://*.example.com`")]
+    InvalidWildcard {
+        /// The domain that contains the invalid wildcard
+        domain: String,
+    },
+}
+
+/// An allowed origin of a [`CorsPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginPattern {
+    /// Matches the origin exactly, e.g. `https://example.com`.
+    Exact(HeaderValue),
+    /// Matches all subdomains of a domain, e.g. `https://*.example.com`.
+    Subdomains {
+        /// The required scheme, any scheme if `None`.
+        scheme: Option<String>,
+        /// The lowercase suffix the host must end with, including the leading `.`.
+        suffix: String,
+    },
 }
 
-/// Creates a [`CorsLayer`] from the given domains
-pub(crate) fn create_cors_layer(http_cors_domains: &str) -> Result<CorsLayer, CorsDomainError> {
-    let cors = match http_cors_domains.trim() {
-        "*" => CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST])
-            .allow_origin(Any)
-            .allow_headers(Any),
-        _ => {
-            let iter = http_cors_domains.split(',');
-            if iter.clone().any(|o| o == "*") {
-                return Err(CorsDomainError::WildCardNotAllowed {
-                    input: http_cors_domains.to_string(),
-                })
+impl OriginPattern {
+    /// Parses a single domain of a comma separated list.
+    fn parse(domain: &str) -> Result<Self, CorsDomainError> {
+        if !domain.contains('*') {
+            return domain
+                .parse::<HeaderValue>()
+                .map(Self::Exact)
+                .map_err(|_| CorsDomainError::InvalidHeader { domain: domain.to_string() })
+        }
+
+        let (scheme, host) = match domain.split_once("://") {
+            Some((scheme, host)) => (Some(scheme.to_ascii_lowercase()), host),
+            None => (None, domain),
+        };
+        match host.strip_prefix('*') {
+            Some(suffix)
+                if suffix.len() > 1 && suffix.starts_with('.') && !suffix.contains('*') =>
+            {
+                Ok(Self::Subdomains { scheme, suffix: suffix.to_ascii_lowercase() })
             }
+            _ => Err(CorsDomainError::InvalidWildcard { domain: domain.to_string() }),
+        }
+    }
 
-            let origins = iter
-                .map(|domain| {
-                    domain
-                        .parse::<HeaderValue>()
-                        .map_err(|_| CorsDomainError::InvalidHeader { domain: domain.to_string() })
-                })
-                .collect::<Result<Vec<HeaderValue>, _>>()?;
-
-            let origin = AllowOrigin::list(origins);
-            CorsLayer::new()
-                .allow_methods([Method::GET, Method::POST])
-                .allow_origin(origin)
-                .allow_headers(Any)
+    /// Returns true if the origin matches the pattern.
+    fn matches(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Exact(allowed) => allowed.as_bytes().eq_ignore_ascii_case(origin.as_bytes()),
+            Self::Subdomains { scheme, suffix } => {
+                let Ok(origin) = origin.to_str() else { return false };
+                let origin = origin.to_ascii_lowercase();
+                let host = match (scheme, origin.split_once("://")) {
+                    (Some(scheme), Some((origin_scheme, host))) if scheme == origin_scheme => host,
+                    (None, Some((_, host))) => host,
+                    _ => return false,
+                };
+                host.len() > suffix.len() && host.ends_with(suffix.as_str())
+            }
         }
-    };
-    Ok(cors)
+    }
+}
+
+/// The origins a server accepts cross-origin requests from.
+///
+/// Parsed from either `*`, which allows all origins, or a comma separated list of origins. Origins
+/// in the list may use a wildcard as their leftmost label to allow all subdomains of a domain,
+/// e.g. `https://*.example.com` allows `https://app.example.com` but not `https://example.com`.
+/// If the scheme is omitted, e.g. `*.example.com`, the subdomains are allowed with any scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsPolicy {
+    /// Whether all origins are allowed.
+    any: bool,
+    /// The allowed origins.
+    origins: Vec<OriginPattern>,
+}
+
+impl CorsPolicy {
+    /// Parses the policy from the given domains.
+    pub fn parse(domains: &str) -> Result<Self, CorsDomainError> {
+        if domains.trim() == "*" {
+            return Ok(Self { any: true, origins: Vec::new() })
+        }
+
+        let iter = domains.split(',').map(str::trim);
+        if iter.clone().any(|o| o == "*") {
+            return Err(CorsDomainError::WildCardNotAllowed { input: domains.to_string() })
+        }
+
+        let origins = iter.map(OriginPattern::parse).collect::<Result<_, _>>()?;
+        Ok(Self { any: false, origins })
+    }
+
+    /// Returns true if requests from the given origin are allowed.
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        self.any || self.origins.iter().any(|pattern| pattern.matches(origin))
+    }
+}
+
+/// The CORS domains of the HTTP and WS servers.
+///
+/// Loaded from a JSON file of the form:
+///
+/// ```json
+/// {
+///   "http": "https://*.example.com,http://localhost:3000",
+///   "ws": "*"
+/// }
+/// ```
+///
+/// See [`CorsPolicy`] for the format of the domains. Cross-origin requests to a server without
+/// domains are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct CorsConfig {
+    /// Allowed CORS domains of the HTTP server.
+    #[serde(default)]
+    pub http: Option<String>,
+    /// Allowed CORS domains of the WS server.
+    #[serde(default)]
+    pub ws: Option<String>,
+}
+
+impl CorsConfig {
+    /// Loads the config from the JSON file at the given path.
+    pub fn load(path: &Path) -> Result<Self, FsPathError> {
+        reth_fs_util::read_json_file(path)
+    }
+}
+
+/// The parsed [`CorsPolicy`] per transport.
+#[derive(Debug, Default)]
+struct TransportPolicies {
+    http: Option<CorsPolicy>,
+    ws: Option<CorsPolicy>,
+}
+
+impl TransportPolicies {
+    fn parse(config: &CorsConfig) -> Result<Self, CorsDomainError> {
+        Ok(Self {
+            http: config.http.as_deref().map(CorsPolicy::parse).transpose()?,
+            ws: config.ws.as_deref().map(CorsPolicy::parse).transpose()?,
+        })
+    }
+}
+
+/// A shared handle to the CORS policies of the HTTP and WS servers.
+///
+/// The policies can be replaced at runtime with [`CorsHandle::reload`], which takes effect for all
+/// subsequent requests of the servers the handle was passed to.
+#[derive(Debug, Clone, Default)]
+pub struct CorsHandle {
+    policies: Arc<RwLock<TransportPolicies>>,
+}
+
+impl CorsHandle {
+    /// Creates a new handle with the policies of the given config.
+    pub fn new(config: &CorsConfig) -> Result<Self, CorsDomainError> {
+        Ok(Self { policies: Arc::new(RwLock::new(TransportPolicies::parse(config)?)) })
+    }
+
+    /// Replaces the policies with the ones of the given config.
+    ///
+    /// The current policies are kept if the config is invalid.
+    pub fn reload(&self, config: &CorsConfig) -> Result<(), CorsDomainError> {
+        let policies = TransportPolicies::parse(config)?;
+        *self.policies.write().unwrap_or_else(|err| err.into_inner()) = policies;
+        Ok(())
+    }
+
+    /// Returns true if requests from the given origin are allowed, for websocket requests if `ws`
+    /// is set and for plain HTTP requests otherwise.
+    pub fn allows(&self, origin: &HeaderValue, ws: bool) -> bool {
+        let policies = self.policies.read().unwrap_or_else(|err| err.into_inner());
+        let policy = if ws { &policies.ws } else { &policies.http };
+        policy.as_ref().is_some_and(|policy| policy.allows(origin))
+    }
+}
+
+/// Returns true if the request is a websocket upgrade request.
+fn is_websocket_upgrade(parts: &Parts) -> bool {
+    parts
+        .headers
+        .get(header::UPGRADE)
+        .is_some_and(|upgrade| upgrade.as_bytes().eq_ignore_ascii_case(b"websocket"))
+}
+
+/// Creates a [`CorsLayer`] that checks origins against the policies of the given handle.
+///
+/// The WS policy applies to websocket upgrade requests and the HTTP policy to all other requests,
+/// so that both can be served on the same port.
+pub(crate) fn create_cors_layer(cors: CorsHandle) -> CorsLayer {
+    CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST])
+        .allow_origin(AllowOrigin::predicate(move |origin, parts| {
+            cors.allows(origin, is_websocket_upgrade(parts))
+        }))
+        .allow_headers(Any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allows(domains: &str, origin: &'static str) -> bool {
+        CorsPolicy::parse(domains).unwrap().allows(&HeaderValue::from_static(origin))
+    }
+
+    #[test]
+    fn parse_domains() {
+        assert!(CorsPolicy::parse("*").is_ok());
+        assert!(CorsPolicy::parse("https://a.com, https://*.b.com").is_ok());
+        assert!(matches!(
+            CorsPolicy::parse("https://a.com,*"),
+            Err(CorsDomainError::WildCardNotAllowed { .. })
+        ));
+        for invalid in ["https://a.*.com", "https://*", "https://*.", "https://*a.com", "**.a.com"]
+        {
+            assert!(
+                matches!(CorsPolicy::parse(invalid), Err(CorsDomainError::InvalidWildcard { .. })),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn match_origins() {
+        assert!(allows("*", "https://anything.io"));
+        assert!(allows("https://a.com,https://b.com", "https://b.com"));
+        assert!(!allows("https://a.com", "https://b.a.com"));
+
+        assert!(allows("https://*.example.com", "https://app.example.com"));
+        assert!(allows("https://*.example.com", "https://a.b.Example.com"));
+        assert!(!allows("https://*.example.com", "https://example.com"));
+        assert!(!allows("https://*.example.com", "http://app.example.com"));
+        assert!(!allows("https://*.example.com", "https://app.example.com.evil.io"));
+        assert!(!allows("https://*.example.com", "https://appexample.com"));
+
+        assert!(allows("*.example.com", "http://app.example.com"));
+        assert!(allows("*.example.com:8080", "https://app.example.com:8080"));
+        assert!(!allows("*.example.com", "https://app.example.com:8080"));
+    }
+
+    #[test]
+    fn reload_per_transport() {
+        let origin = HeaderValue::from_static("https://app.example.com");
+        let cors = CorsHandle::new(&CorsConfig {
+            http: Some("https://*.example.com".to_string()),
+            ws: None,
+        })
+        .unwrap();
+        assert!(cors.allows(&origin, false));
+        assert!(!cors.allows(&origin, true));
+
+        cors.reload(&CorsConfig { http: None, ws: Some("*".to_string()) }).unwrap();
+        assert!(!cors.allows(&origin, false));
+        assert!(cors.allows(&origin, true));
+
+        // invalid configs keep the current policies
+        assert!(cors.reload(&CorsConfig { http: Some("*.*".to_string()), ws: None }).is_err());
+        assert!(cors.allows(&origin, true));
+    }
 }
@@ -114,6 +114,7 @@ impl std::fmt::Display for ConflictingModules {
 #[derive(Debug, thiserror::Error)]
 pub enum WsHttpSamePortError {
     /// Ws and http server configured on same port but with different cors domains.
+    #[deprecated(note = "use a `CorsHandle` for different CORS domains on the same port")]
     #[error(
         "CORS domains for HTTP and WS are different, but they are on the same port: \
          HTTP: {http_cors_domains:?}, WS: {ws_cors_domains:?}"
@@ -23,6 +23,7 @@ use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequest
 use alloy_network::Ethereum;
 use alloy_provider::{fillers::RecommendedFillers, Provider, ProviderBuilder};
 use core::marker::PhantomData;
+pub use cors::{CorsConfig, CorsDomainError, CorsHandle};
 use error::{ConflictingModules, RpcError, ServerKind};
 use http::{header::AUTHORIZATION, HeaderMap};
 use jsonrpsee::{
@@ -36,8 +37,9 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, ExplorerApi, MinerApi,
+    NetApi, OtterscanApi, RPCApi, RethApi, RethFees, TraceApi, TxPoolApi, ValidationApiConfig,
+    Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -52,8 +54,8 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    AccountReader, BlockReader, ChangeSetReader, ContractDeploymentsProvider, FullRpcProvider,
+    ProviderBlock, RequestsProvider, SenderTransactionsProvider, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -64,9 +66,6 @@ use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tower_http::cors::CorsLayer;
-
-pub use cors::CorsDomainError;
 
 // re-export for convenience
 pub use jsonrpsee::server::ServerBuilder;
@@ -77,9 +76,17 @@ pub use reth_ipc::server::{
 pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 pub use tower::layer::util::{Identity, Stack};
 
+/// API key authentication utilities.
+pub mod api_key;
+use api_key::{ApiKeyAuthLayer, ApiKeyConfig, ApiKeyPermissionLayer};
+
 /// Auth server utilities.
 pub mod auth;
 
+/// Concurrent execution of batch requests.
+pub mod batch;
+use batch::{BatchConfig, BatchLayer};
+
 /// RPC server utilities.
 pub mod config;
 
@@ -87,7 +94,7 @@ pub mod config;
 pub mod middleware;
 
 /// Cors utilities.
-mod cors;
+pub mod cors;
 
 /// Rpc error utilities.
 pub mod error;
@@ -309,7 +316,10 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -660,6 +670,9 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider
         + CanonStateSubscriptions,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
@@ -676,9 +689,12 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn register_eth(&mut self) -> &mut Self {
+    pub fn register_eth(&mut self) -> &mut Self
+    where
+        EthApi: FullEthApiServer,
+    {
         let eth_api = self.eth_api().clone();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        self.modules.insert(RethRpcModule::Eth, eth_api.into_eth_rpc_module().into());
         self
     }
 
@@ -748,9 +764,13 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
-        let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        EthApi: FullEthApiServer,
+    {
+        let mut rethapi = self.reth_api().into_rpc();
+        rethapi.merge(RethFees::new(self.eth_api().clone()).into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, rethapi.into());
         self
     }
 
@@ -759,9 +779,19 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&self) -> OtterscanApi<EthApi> {
+    pub fn otterscan_api(&self) -> OtterscanApi<EthApi, Provider> {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(eth_api, self.provider.clone())
+    }
+
+    /// Instantiates `ExplorerApi`
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn explorer_api(&self) -> ExplorerApi<EthApi> {
+        let eth_api = self.eth_api().clone();
+        ExplorerApi::new(eth_api)
     }
 }
 
@@ -775,7 +805,10 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -825,8 +858,8 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), self.executor.clone())
+    pub fn reth_api(&self) -> RethApi<Provider, EvmConfig> {
+        RethApi::new(self.provider.clone(), self.evm_config.clone(), self.executor.clone())
     }
 }
 
@@ -837,7 +870,10 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -932,7 +968,7 @@ where
                         }
                         RethRpcModule::Eth => {
                             // merge all eth handlers
-                            let mut module = eth_api.clone().into_rpc();
+                            let mut module = eth_api.clone().into_eth_rpc_module();
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
                             module
@@ -972,12 +1008,23 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(eth_api.clone(), self.provider.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Reth => {
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                self.evm_config.clone(),
+                                self.executor.clone(),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(RethFees::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
+                        }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
                         // TODO: can we get rid of this here?
@@ -988,6 +1035,9 @@ where
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Explorer => {
+                            ExplorerApi::new(eth_api.clone()).into_rpc().into()
+                        }
                     })
                     .clone()
             })
@@ -1020,6 +1070,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
     ws_cors_domains: Option<String>,
+    /// Reloadable CORS policies for http and ws, takes precedence over the CORS domains
+    cors: Option<CorsHandle>,
     /// Address where to bind the ws server to
     ws_addr: Option<SocketAddr>,
     /// Configs for JSON-RPC IPC server
@@ -1028,6 +1080,10 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// API keys and their permissions for http and ws
+    api_keys: Option<ApiKeyConfig>,
+    /// Execution of batch requests for http and ws
+    batch_config: BatchConfig,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1044,10 +1100,13 @@ impl Default for RpcServerConfig<Identity> {
             http_disable_compression: false,
             ws_server_config: None,
             ws_cors_domains: None,
+            cors: None,
             ws_addr: None,
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            api_keys: None,
+            batch_config: BatchConfig::default(),
             rpc_middleware: Default::default(),
         }
     }
@@ -1108,10 +1167,13 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_disable_compression: self.http_disable_compression,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
+            cors: self.cors,
             ws_addr: self.ws_addr,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            api_keys: self.api_keys,
+            batch_config: self.batch_config,
             rpc_middleware,
         }
     }
@@ -1127,6 +1189,15 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure a shared [`CorsHandle`] for http and ws, which allows reloading the CORS policies
+    /// after the servers are started.
+    ///
+    /// If set, this takes precedence over the CORS domains of http and ws.
+    pub fn with_cors_handle(mut self, cors: Option<CorsHandle>) -> Self {
+        self.cors = cors;
+        self
+    }
+
     /// Configure whether HTTP responses should be compressed
     pub const fn with_http_disable_compression(mut self, http_disable_compression: bool) -> Self {
         self.http_disable_compression = http_disable_compression;
@@ -1191,6 +1262,23 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the API keys and the namespaces and methods they are allowed to call over http
+    /// and ws.
+    ///
+    /// See [`ApiKeyConfig`] for details.
+    pub fn with_api_keys(mut self, api_keys: Option<ApiKeyConfig>) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Configures how batch requests are executed over http and ws.
+    ///
+    /// See [`BatchConfig`] for details.
+    pub const fn with_batch_config(mut self, batch_config: BatchConfig) -> Self {
+        self.batch_config = batch_config;
+        self
+    }
+
     /// Configures a custom tokio runtime for the rpc server.
     pub fn with_tokio_runtime(mut self, tokio_runtime: Option<tokio::runtime::Handle>) -> Self {
         let Some(tokio_runtime) = tokio_runtime else { return self };
@@ -1232,9 +1320,24 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self.ipc_endpoint.clone()
     }
 
-    /// Creates the [`CorsLayer`] if any
-    fn maybe_cors_layer(cors: Option<String>) -> Result<Option<CorsLayer>, CorsDomainError> {
-        cors.as_deref().map(cors::create_cors_layer).transpose()
+    /// Returns the [`CorsHandle`] shared by the http and ws servers, if any CORS domains are
+    /// configured.
+    ///
+    /// If both are on the same port, a transport without CORS domains uses the domains of the
+    /// other one.
+    fn cors_handle(&self, same_port: bool) -> Result<Option<CorsHandle>, CorsDomainError> {
+        if let Some(cors) = &self.cors {
+            return Ok(Some(cors.clone()))
+        }
+
+        let (mut http, mut ws) = (self.http_cors_domains.clone(), self.ws_cors_domains.clone());
+        if same_port {
+            (http, ws) = (http.clone().or_else(|| ws.clone()), ws.or(http));
+        }
+        if http.is_none() && ws.is_none() {
+            return Ok(None)
+        }
+        CorsHandle::new(&CorsConfig { http, ws }).map(Some)
     }
 
     /// Creates the [`AuthLayer`] if any
@@ -1242,6 +1345,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`ApiKeyAuthLayer`] if any
+    fn maybe_api_key_layer(api_keys: Option<ApiKeyConfig>) -> Option<ApiKeyAuthLayer> {
+        api_keys.map(ApiKeyAuthLayer::new)
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
@@ -1291,20 +1399,20 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             self.http_server_config.is_some() &&
             self.ws_server_config.is_some()
         {
-            let cors = match (self.ws_cors_domains.as_ref(), self.http_cors_domains.as_ref()) {
-                (Some(ws_cors), Some(http_cors)) => {
-                    if ws_cors.trim() != http_cors.trim() {
-                        return Err(WsHttpSamePortError::ConflictingCorsDomains {
-                            http_cors_domains: Some(http_cors.clone()),
-                            ws_cors_domains: Some(ws_cors.clone()),
-                        }
-                        .into());
-                    }
-                    Some(ws_cors)
+            // different domains on the same port require a shared cors handle
+            if let (None, Some(ws_cors), Some(http_cors)) =
+                (&self.cors, &self.ws_cors_domains, &self.http_cors_domains)
+            {
+                if ws_cors.trim() != http_cors.trim() {
+                    #[allow(deprecated)]
+                    let err = WsHttpSamePortError::ConflictingCorsDomains {
+                        http_cors_domains: Some(http_cors.clone()),
+                        ws_cors_domains: Some(ws_cors.clone()),
+                    };
+                    return Err(err.into())
                 }
-                (a, b) => a.or(b),
             }
-            .cloned();
+            let cors = self.cors_handle(true)?;
 
             // we merge this into one server using the http setup
             modules.config.ensure_ws_http_identical()?;
@@ -1313,8 +1421,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 let server = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
+                            .option_layer(cors.map(cors::create_cors_layer))
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(Self::maybe_api_key_layer(self.api_keys.clone()))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
@@ -1329,7 +1438,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
-                            .layer(self.rpc_middleware.clone()),
+                            .option_layer(self.api_keys.as_ref().map(|_| ApiKeyPermissionLayer))
+                            .layer(self.rpc_middleware.clone())
+                            .layer(BatchLayer::new(self.batch_config)),
                     )
                     .set_config(config.build())
                     .build(http_socket_addr)
@@ -1357,6 +1468,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             }
         }
 
+        let cors = self.cors_handle(false)?;
         let mut ws_local_addr = None;
         let mut ws_server = None;
         let mut http_local_addr = None;
@@ -1367,13 +1479,16 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(cors.clone().map(cors::create_cors_layer))
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_api_key_layer(self.api_keys.clone())),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
-                        .layer(self.rpc_middleware.clone()),
+                        .option_layer(self.api_keys.as_ref().map(|_| ApiKeyPermissionLayer))
+                        .layer(self.rpc_middleware.clone())
+                        .layer(BatchLayer::new(self.batch_config)),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1392,8 +1507,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
+                        .option_layer(cors.map(cors::create_cors_layer))
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_api_key_layer(self.api_keys.clone()))
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
@@ -1401,7 +1517,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
-                        .layer(self.rpc_middleware.clone()),
+                        .option_layer(self.api_keys.as_ref().map(|_| ApiKeyPermissionLayer))
+                        .layer(self.rpc_middleware.clone())
+                        .layer(BatchLayer::new(self.batch_config)),
                 )
                 .build(http_socket_addr)
                 .await
@@ -2038,6 +2156,16 @@ impl RpcServerHandle {
         Ok(())
     }
 
+    /// Waits until all servers have stopped.
+    ///
+    /// This resolves once [`RpcServerHandle::stop`] has been called on this handle or any of its
+    /// clones and all servers finished processing in-flight requests.
+    pub async fn stopped(self) {
+        for handle in [self.http, self.ws, self.ipc].into_iter().flatten() {
+            handle.stopped().await
+        }
+    }
+
     /// Returns the endpoint of the launched IPC server, if any
     pub fn ipc_endpoint(&self) -> Option<String> {
         self.ipc_endpoint.clone()
@@ -2244,6 +2372,7 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "explorer" => RethRpcModule::Explorer,
             );
     }
 
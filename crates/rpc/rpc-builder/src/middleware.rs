@@ -1,10 +1,13 @@
+use crate::batch::BatchService;
 use jsonrpsee::server::middleware::rpc::RpcService;
 use tower::Layer;
 
 /// A Helper alias trait for the RPC middleware supported by the server.
+///
+/// The middleware wraps the [`BatchService`], which executes the calls of batch requests.
 pub trait RethRpcMiddleware:
     Layer<
-        RpcService,
+        BatchService<RpcService>,
         Service: jsonrpsee::server::middleware::rpc::RpcServiceT<
             MethodResponse = jsonrpsee::MethodResponse,
             BatchResponse = jsonrpsee::MethodResponse,
@@ -21,7 +24,7 @@ pub trait RethRpcMiddleware:
 
 impl<T> RethRpcMiddleware for T where
     T: Layer<
-            RpcService,
+            BatchService<RpcService>,
             Service: jsonrpsee::server::middleware::rpc::RpcServiceT<
                 MethodResponse = jsonrpsee::MethodResponse,
                 BatchResponse = jsonrpsee::MethodResponse,
@@ -22,8 +22,8 @@ use reth_ethereum_primitives::Receipt;
 use reth_network_peers::NodeRecord;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient, OtterscanClient,
-    TraceApiClient, Web3ApiClient,
+    AddressSummary, DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, ExplorerApiClient,
+    NetApiClient, OtterscanClient, TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -311,6 +311,7 @@ where
         call_request.clone(),
         Some(block_number.into()),
         None,
+        None,
     )
     .await
     .unwrap_err();
@@ -328,6 +329,7 @@ where
         Some(block_number.into()),
         None,
         None,
+        None,
     )
     .await
     .unwrap_err();
@@ -484,6 +486,24 @@ where
     Web3ApiClient::sha3(client, Bytes::default()).await.unwrap();
 }
 
+async fn test_basic_explorer_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let address = Address::default();
+
+    // the test provider doesn't have any blocks
+    assert!(ExplorerApiClient::get_latest_blocks(client, 10).await.unwrap().is_empty());
+    assert_eq!(
+        ExplorerApiClient::get_block_transactions(client, BlockId::number(1)).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        ExplorerApiClient::get_address_summary(client, address, None).await.unwrap(),
+        AddressSummary::default()
+    );
+}
+
 async fn test_basic_otterscan_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -541,28 +561,24 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    // the sender transactions index is not enabled
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    // the sender transactions index is not enabled
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -773,6 +789,15 @@ async fn test_call_otterscan_functions_http_and_ws() {
     test_basic_otterscan_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_explorer_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Explorer]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_explorer_calls(&client).await;
+}
+
 // <https://github.com/paradigmxyz/reth/issues/5830>
 #[tokio::test(flavor = "multi_thread")]
 async fn test_eth_logs_args() {
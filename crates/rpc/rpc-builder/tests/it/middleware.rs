@@ -1,11 +1,22 @@
 use crate::utils::{test_address, test_rpc_builder};
 use alloy_rpc_types_eth::{Block, Header, Receipt, Transaction, TransactionRequest};
+use http::{HeaderMap, HeaderName, HeaderValue};
 use jsonrpsee::{
-    core::middleware::{Batch, Notification},
+    core::{
+        client::{ClientT, Error as ClientError},
+        middleware::{Batch, Notification},
+        params::BatchRequestBuilder,
+    },
+    http_client::HttpClientBuilder,
+    rpc_params,
     server::middleware::rpc::RpcServiceT,
     types::Request,
 };
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_builder::{
+    api_key::{ApiKeyConfig, RpcPermissions, API_KEY_HEADER, METHOD_NOT_PERMITTED_CODE},
+    batch::{BatchConfig, BATCH_COST_EXCEEDED_CODE},
+    RpcServerConfig, TransportRpcModuleConfig,
+};
 use reth_rpc_eth_api::EthApiClient;
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{
@@ -20,13 +31,18 @@ use tower::Layer;
 #[derive(Clone, Default)]
 struct MyMiddlewareLayer {
     count: Arc<AtomicUsize>,
+    batches: Arc<AtomicUsize>,
 }
 
 impl<S> Layer<S> for MyMiddlewareLayer {
     type Service = MyMiddlewareService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        MyMiddlewareService { service: inner, count: self.count.clone() }
+        MyMiddlewareService {
+            service: inner,
+            count: self.count.clone(),
+            batches: self.batches.clone(),
+        }
     }
 }
 
@@ -34,6 +50,7 @@ impl<S> Layer<S> for MyMiddlewareLayer {
 struct MyMiddlewareService<S> {
     service: S,
     count: Arc<AtomicUsize>,
+    batches: Arc<AtomicUsize>,
 }
 
 impl<S> RpcServiceT for MyMiddlewareService<S>
@@ -57,6 +74,7 @@ where
     }
 
     fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.batches.fetch_add(1, Ordering::Relaxed);
         self.service.batch(req)
     }
 
@@ -93,3 +111,134 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_middleware_batch() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules =
+        builder.build(TransportRpcModuleConfig::set_http(RpcModuleSelection::All), eth_api);
+
+    let mylayer = MyMiddlewareLayer::default();
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .set_rpc_middleware(mylayer.clone())
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    // the calls of the batch are executed concurrently, but answered in order
+    let inputs = (0..8u8).map(|i| format!("0x{i:02x}")).collect::<Vec<_>>();
+    let mut batch = BatchRequestBuilder::new();
+    for input in &inputs {
+        batch.insert("web3_sha3", rpc_params![input]).unwrap();
+    }
+    let response = client.batch_request::<String>(batch).await.unwrap();
+    assert_eq!(response.num_successful_calls(), inputs.len());
+
+    // the batch went through the middleware
+    assert_eq!(mylayer.batches.load(Ordering::Relaxed), 1);
+
+    for (input, hash) in inputs.iter().zip(response) {
+        let expected = client.request::<String, _>("web3_sha3", rpc_params![input]).await.unwrap();
+        assert_eq!(hash.unwrap(), expected);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_max_cost() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules =
+        builder.build(TransportRpcModuleConfig::set_http(RpcModuleSelection::All), eth_api);
+
+    // without a limit, any batch is executed
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+    let mut batch = BatchRequestBuilder::new();
+    for _ in 0..20 {
+        batch.insert("web3_clientVersion", rpc_params![]).unwrap();
+    }
+    let response = client.batch_request::<String>(batch).await.unwrap();
+    assert_eq!(response.num_successful_calls(), 20);
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_batch_config(BatchConfig::default().with_max_cost(Some(2)))
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    // the calls over budget are rejected
+    let mut batch = BatchRequestBuilder::new();
+    for _ in 0..3 {
+        batch.insert("web3_clientVersion", rpc_params![]).unwrap();
+    }
+    let response = client.batch_request::<String>(batch).await.unwrap();
+    assert_eq!(response.num_successful_calls(), 2);
+    let err = response.into_iter().nth(2).unwrap().unwrap_err();
+    assert_eq!(err.code(), BATCH_COST_EXCEEDED_CODE);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_api_key_permissions() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules =
+        builder.build(TransportRpcModuleConfig::set_http(RpcModuleSelection::All), eth_api);
+
+    let api_keys = ApiKeyConfig::default()
+        .with_public(RpcPermissions::from(vec!["web3".to_string()]))
+        .with_key("secret", RpcPermissions::allow_all());
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_api_keys(Some(api_keys))
+        .start(&modules)
+        .await
+        .unwrap();
+    let url = handle.http_url().unwrap();
+
+    // without a key, only the public namespaces can be called
+    let client = HttpClientBuilder::default().build(&url).unwrap();
+    client.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap();
+    let err = client.request::<String, _>("eth_protocolVersion", rpc_params![]).await.unwrap_err();
+    assert!(
+        matches!(err, ClientError::Call(ref err) if err.code() == METHOD_NOT_PERMITTED_CODE),
+        "{err:?}"
+    );
+
+    let mut batch = BatchRequestBuilder::new();
+    batch.insert("web3_clientVersion", rpc_params![]).unwrap();
+    batch.insert("eth_protocolVersion", rpc_params![]).unwrap();
+    let response = client.batch_request::<String>(batch).await.unwrap();
+    assert_eq!(response.num_successful_calls(), 1);
+    assert_eq!(response.num_failed_calls(), 1);
+
+    // with a key, all methods can be called
+    let client = HttpClientBuilder::default()
+        .set_headers(HeaderMap::from_iter([(
+            HeaderName::from_static(API_KEY_HEADER),
+            HeaderValue::from_static("secret"),
+        )]))
+        .build(&url)
+        .unwrap();
+    client.request::<String, _>("eth_protocolVersion", rpc_params![]).await.unwrap();
+
+    // unknown keys are rejected
+    let client = HttpClientBuilder::default()
+        .set_headers(HeaderMap::from_iter([(
+            HeaderName::from_static(API_KEY_HEADER),
+            HeaderValue::from_static("unknown"),
+        )]))
+        .build(&url)
+        .unwrap();
+    let err = client.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap_err();
+    assert!(matches!(err, ClientError::Transport(_)), "{err:?}");
+}
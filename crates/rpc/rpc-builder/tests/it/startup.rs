@@ -4,7 +4,7 @@ use std::io;
 
 use reth_rpc_builder::{
     error::{RpcError, ServerKind, WsHttpSamePortError},
-    RpcServerConfig, TransportRpcModuleConfig,
+    CorsConfig, CorsHandle, RpcServerConfig, TransportRpcModuleConfig,
 };
 use reth_rpc_server_types::RethRpcModule;
 
@@ -101,6 +101,7 @@ async fn test_launch_same_port_same_cors() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
+#[allow(deprecated)]
 async fn test_launch_same_port_different_cors() {
     let builder = test_rpc_builder();
     let eth_api = builder.bootstrap_eth_api();
@@ -124,3 +125,28 @@ async fn test_launch_same_port_different_cors() {
         RpcError::WsHttpSamePortError(WsHttpSamePortError::ConflictingCorsDomains { .. })
     ));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_launch_same_port_different_cors_handle() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
+            .with_http(vec![RethRpcModule::Eth]),
+        eth_api,
+    );
+    let cors = CorsHandle::new(&CorsConfig {
+        http: Some("https://*.example.com".to_string()),
+        ws: Some("*".to_string()),
+    })
+    .unwrap();
+    let addr = test_address();
+    let res = RpcServerConfig::ws(Default::default())
+        .with_ws_address(addr)
+        .with_http(Default::default())
+        .with_cors_handle(Some(cors))
+        .with_http_address(addr)
+        .start(&server)
+        .await;
+    assert!(res.is_ok());
+}
@@ -0,0 +1,149 @@
+//! Benchmarks serializing full blocks into their RPC representation, comparing the intermediate
+//! RPC block with the [`RpcBlockSerializer`].
+
+#![allow(missing_docs)]
+
+use alloy_consensus::transaction::Recovered;
+use alloy_rpc_types_eth::{BlockTransactionsKind, Header, Transaction, TransactionInfo};
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_ethereum_primitives::{Block, TransactionSigned};
+use reth_primitives_traits::RecoveredBlock;
+use reth_rpc_convert::{transaction::FromConsensusHeader, RpcBlockSerializer};
+use reth_testing_utils::generators::{self, random_block, BlockParams};
+use std::{convert::Infallible, hint::black_box};
+
+mod tracking_allocator {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[derive(Debug)]
+    pub(crate) struct TrackingAllocator {
+        allocations: AtomicUsize,
+        total_allocated: AtomicUsize,
+        inner: System,
+    }
+
+    impl TrackingAllocator {
+        pub(crate) const fn new() -> Self {
+            Self {
+                allocations: AtomicUsize::new(0),
+                total_allocated: AtomicUsize::new(0),
+                inner: System,
+            }
+        }
+
+        pub(crate) fn reset(&self) {
+            self.allocations.store(0, Ordering::SeqCst);
+            self.total_allocated.store(0, Ordering::SeqCst);
+        }
+
+        pub(crate) fn allocations(&self) -> usize {
+            self.allocations.load(Ordering::SeqCst)
+        }
+
+        pub(crate) fn total_allocated(&self) -> usize {
+            self.total_allocated.load(Ordering::SeqCst)
+        }
+    }
+
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ret = self.inner.alloc(layout);
+            if !ret.is_null() {
+                self.allocations.fetch_add(1, Ordering::SeqCst);
+                self.total_allocated.fetch_add(layout.size(), Ordering::SeqCst);
+            }
+            ret
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.inner.dealloc(ptr, layout)
+        }
+    }
+}
+
+use tracking_allocator::TrackingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator::new();
+
+const TX_COUNT: u8 = 200;
+
+fn tx_resp_builder(
+    tx: Recovered<TransactionSigned>,
+    tx_info: TransactionInfo,
+) -> Result<Transaction<TransactionSigned>, Infallible> {
+    Ok(Transaction::from_transaction(tx, tx_info))
+}
+
+/// Serializes the block by converting it into an RPC block first.
+fn serialize_rpc_block(block: &RecoveredBlock<Block>, buf: &mut Vec<u8>) {
+    let rpc_block = block
+        .clone_into_rpc_block(BlockTransactionsKind::Full, tx_resp_builder, |header, size| {
+            Ok(Header::from_consensus_header(header, size))
+        })
+        .unwrap();
+    serde_json::to_writer(buf, &rpc_block).unwrap();
+}
+
+/// Serializes the block with the [`RpcBlockSerializer`].
+fn serialize_streaming(block: &RecoveredBlock<Block>, buf: &mut Vec<u8>) {
+    let header = Header::from_consensus_header(block.clone_sealed_header(), block.rlp_length());
+    let serializer =
+        RpcBlockSerializer::new(block, BlockTransactionsKind::Full, header, tx_resp_builder);
+    serde_json::to_writer(buf, &serializer).unwrap();
+}
+
+/// Returns the number of allocations and allocated bytes of a single serialization.
+fn measure_allocations(f: impl FnOnce()) -> (usize, usize) {
+    ALLOCATOR.reset();
+    f();
+    (ALLOCATOR.allocations(), ALLOCATOR.total_allocated())
+}
+
+fn block_serializer(c: &mut Criterion) {
+    let mut rng = generators::rng();
+    let block = random_block(
+        &mut rng,
+        1,
+        BlockParams { tx_count: Some(TX_COUNT), withdrawals_count: Some(16), ..Default::default() },
+    )
+    .try_recover()
+    .unwrap();
+
+    // the output buffer is reused, so its allocations don't count towards the conversion
+    let mut buf = Vec::new();
+    serialize_rpc_block(&block, &mut buf);
+    let capacity = buf.len();
+
+    for (name, serialize) in [
+        ("rpc block", serialize_rpc_block as fn(&RecoveredBlock<Block>, &mut Vec<u8>)),
+        ("streaming", serialize_streaming),
+    ] {
+        let mut buf = Vec::with_capacity(capacity);
+        let (allocations, bytes) = measure_allocations(|| serialize(&block, &mut buf));
+        println!("{name}: {allocations} allocations, {bytes} bytes allocated");
+    }
+
+    let mut group = c.benchmark_group("Serialize Full Block");
+    group.bench_function("rpc block", |b| {
+        let mut buf = Vec::with_capacity(capacity);
+        b.iter(|| {
+            buf.clear();
+            serialize_rpc_block(black_box(&block), &mut buf);
+        })
+    });
+    group.bench_function("streaming", |b| {
+        let mut buf = Vec::with_capacity(capacity);
+        b.iter(|| {
+            buf.clear();
+            serialize_streaming(black_box(&block), &mut buf);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, block_serializer);
+criterion_main!(benches);
@@ -1,7 +1,13 @@
-//! Conversion traits for block responses to primitive block types.
+//! Conversion traits for block responses to primitive block types and serialization of blocks
+//! into their RPC representation.
 
+use alloy_consensus::{transaction::Recovered, BlockHeader, Sealable};
 use alloy_network::Network;
-use std::convert::Infallible;
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::{BlockTransactionsKind, TransactionInfo};
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SignedTransaction};
+use serde::{ser::Error as _, Serialize, Serializer};
+use std::{convert::Infallible, fmt::Display, marker::PhantomData};
 
 /// Trait for converting network block responses to primitive block types.
 pub trait TryFromBlockResponse<N: Network> {
@@ -29,12 +35,158 @@ where
     }
 }
 
+/// Serializes a [`RecoveredBlock`] into the JSON representation of the RPC block, without building
+/// the intermediate [`RpcBlock`](crate::RpcBlock).
+///
+/// Converting a block with [`RecoveredBlock::clone_into_rpc_block`] clones the entire block and
+/// collects all converted transactions before anything is written. This serializer borrows the
+/// block instead and converts every transaction right before it is written to the serializer, so
+/// only a single RPC transaction object is alive at a time.
+///
+/// The output is identical to the serialized [`RpcBlock`](crate::RpcBlock) with the same header
+/// and transaction response types.
+pub struct RpcBlockSerializer<'a, B: Block, H, F, T, E> {
+    block: &'a RecoveredBlock<B>,
+    kind: BlockTransactionsKind,
+    header: H,
+    tx_resp_builder: F,
+    _phantom: PhantomData<fn() -> (T, E)>,
+}
+
+impl<'a, B, H, F, T, E> RpcBlockSerializer<'a, B, H, F, T, E>
+where
+    B: Block,
+    F: Fn(Recovered<<B::Body as BlockBody>::Transaction>, TransactionInfo) -> Result<T, E>,
+{
+    /// Creates a new serializer for the block with the already converted RPC `header`.
+    ///
+    /// The `tx_resp_builder` closure transforms each transaction into the desired response type,
+    /// it's only invoked for [`BlockTransactionsKind::Full`].
+    pub const fn new(
+        block: &'a RecoveredBlock<B>,
+        kind: BlockTransactionsKind,
+        header: H,
+        tx_resp_builder: F,
+    ) -> Self {
+        Self { block, kind, header, tx_resp_builder, _phantom: PhantomData }
+    }
+}
+
+impl<B, H, F, T, E> std::fmt::Debug for RpcBlockSerializer<'_, B, H, F, T, E>
+where
+    B: Block,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcBlockSerializer")
+            .field("hash", &self.block.hash())
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, H, F, T, E> Serialize for RpcBlockSerializer<'_, B, H, F, T, E>
+where
+    B: Block,
+    H: Serialize,
+    F: Fn(Recovered<<B::Body as BlockBody>::Transaction>, TransactionInfo) -> Result<T, E>,
+    T: Serialize,
+    E: Display,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Mirrors the layout of [`alloy_rpc_types_eth::Block`].
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct BlockRef<'a, H, T, W> {
+            #[serde(flatten)]
+            header: &'a H,
+            uncles: Vec<B256>,
+            transactions: T,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            withdrawals: Option<&'a W>,
+        }
+
+        let body = self.block.body();
+        let uncles = body.ommers().unwrap_or(&[]).iter().map(|h| h.hash_slow()).collect();
+        let withdrawals = body.withdrawals();
+
+        match self.kind {
+            BlockTransactionsKind::Hashes => BlockRef {
+                header: &self.header,
+                uncles,
+                transactions: TransactionHashes(self.block),
+                withdrawals,
+            }
+            .serialize(serializer),
+            BlockTransactionsKind::Full => BlockRef {
+                header: &self.header,
+                uncles,
+                transactions: FullTransactions {
+                    block: self.block,
+                    tx_resp_builder: &self.tx_resp_builder,
+                },
+                withdrawals,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+/// Serializes the hashes of the block transactions.
+struct TransactionHashes<'a, B: Block>(&'a RecoveredBlock<B>);
+
+impl<B: Block> Serialize for TransactionHashes<'_, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.body().transactions_iter().map(|tx| tx.tx_hash()))
+    }
+}
+
+/// Serializes the block transactions, converting them one by one.
+struct FullTransactions<'a, B: Block, F> {
+    block: &'a RecoveredBlock<B>,
+    tx_resp_builder: &'a F,
+}
+
+impl<B, F, T, E> Serialize for FullTransactions<'_, B, F>
+where
+    B: Block,
+    F: Fn(Recovered<<B::Body as BlockBody>::Transaction>, TransactionInfo) -> Result<T, E>,
+    T: Serialize,
+    E: Display,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let header = self.block.header();
+        let block_hash = Some(self.block.hash());
+        let block_number = Some(header.number());
+        let base_fee = header.base_fee_per_gas();
+
+        let mut seq = serializer.serialize_seq(Some(self.block.body().transaction_count()))?;
+        for (idx, (sender, tx)) in self.block.transactions_with_sender().enumerate() {
+            let tx_info = TransactionInfo {
+                hash: Some(*tx.tx_hash()),
+                block_hash,
+                block_number,
+                base_fee,
+                index: Some(idx as u64),
+            };
+            let tx = (self.tx_resp_builder)(Recovered::new_unchecked(tx.clone(), *sender), tx_info)
+                .map_err(S::Error::custom)?;
+            seq.serialize_element(&tx)?;
+        }
+        seq.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction::FromConsensusHeader;
     use alloy_consensus::{Block, TxEnvelope};
     use alloy_network::Ethereum;
-    use alloy_rpc_types_eth::BlockTransactions;
+    use alloy_rpc_types_eth::{BlockTransactions, Header, Transaction};
+    use reth_ethereum_primitives::TransactionSigned;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
 
     #[test]
     fn test_try_from_block_response() {
@@ -44,4 +196,47 @@ mod tests {
             <Block<TxEnvelope> as TryFromBlockResponse<Ethereum>>::from_block_response(rpc_block);
         assert!(result.is_ok());
     }
+
+    fn assert_serializes_like_rpc_block(kind: BlockTransactionsKind) {
+        let mut rng = generators::rng();
+        let block = random_block(
+            &mut rng,
+            1,
+            BlockParams {
+                tx_count: Some(5),
+                ommers_count: Some(2),
+                withdrawals_count: Some(3),
+                ..Default::default()
+            },
+        )
+        .try_recover()
+        .unwrap();
+
+        let tx_resp_builder = |tx: Recovered<TransactionSigned>, tx_info| {
+            Ok::<_, Infallible>(Transaction::from_transaction(tx, tx_info))
+        };
+        let rpc_block = block
+            .clone_into_rpc_block(kind, tx_resp_builder, |header, size| {
+                Ok(Header::from_consensus_header(header, size))
+            })
+            .unwrap();
+
+        let header = Header::from_consensus_header(block.clone_sealed_header(), block.rlp_length());
+        let serializer = RpcBlockSerializer::new(&block, kind, header, tx_resp_builder);
+
+        assert_eq!(
+            serde_json::to_string(&serializer).unwrap(),
+            serde_json::to_string(&rpc_block).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serialize_block_with_hashes() {
+        assert_serializes_like_rpc_block(BlockTransactionsKind::Hashes);
+    }
+
+    #[test]
+    fn test_serialize_block_with_full_transactions() {
+        assert_serializes_like_rpc_block(BlockTransactionsKind::Full);
+    }
 }
@@ -16,13 +16,13 @@ pub mod receipt;
 mod rpc;
 pub mod transaction;
 
-pub use block::TryFromBlockResponse;
+pub use block::{RpcBlockSerializer, TryFromBlockResponse};
 pub use fees::{CallFees, CallFeesError};
 pub use receipt::TryFromReceiptResponse;
 pub use rpc::*;
 pub use transaction::{
-    EthTxEnvError, IntoRpcTx, RpcConvert, RpcConverter, TransactionConversionError,
-    TryFromTransactionResponse, TryIntoSimTx, TxInfoMapper,
+    EthTxEnvError, IntoRpcTx, RpcConvert, RpcConverter, RpcResponseEnricher,
+    TransactionConversionError, TryFromTransactionResponse, TryIntoSimTx, TxInfoMapper,
 };
 
 #[cfg(feature = "op")]
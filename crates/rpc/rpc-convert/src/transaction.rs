@@ -363,6 +363,50 @@ impl<T> TxInfoMapper<T> for () {
     }
 }
 
+/// Enriches converted RPC responses with chain specific fields.
+///
+/// The enricher runs after the regular conversion of [`RpcConverter`] and can modify the
+/// transaction and receipt responses in place, e.g. to populate L1 fee fields of OP-stack variants
+/// or custom deposit metadata, without re-implementing the whole conversion layer.
+///
+/// Both hooks do nothing by default. The `()` implementation leaves all responses unchanged and is
+/// used as default for [`RpcConverter`]. A custom enricher can be applied using
+/// [`RpcConverter::with_enricher`].
+pub trait RpcResponseEnricher<N: NodePrimitives, Network: RpcTypes>:
+    Clone + Debug + Unpin + Send + Sync + 'static
+{
+    /// An associated error that can occur during enrichment.
+    type Err;
+
+    /// Enriches the converted transaction response.
+    ///
+    /// The `tx_info` is the block context of the transaction, it is the default
+    /// [`TransactionInfo`] for pending transactions.
+    fn enrich_transaction(
+        &self,
+        tx: &mut RpcTransaction<Network>,
+        tx_info: &TransactionInfo,
+    ) -> Result<(), Self::Err> {
+        let _ = (tx, tx_info);
+        Ok(())
+    }
+
+    /// Enriches the converted receipt response of the transaction `tx`.
+    fn enrich_receipt(
+        &self,
+        tx: Recovered<&TxTy<N>>,
+        meta: &TransactionMeta,
+        receipt: &mut RpcReceipt<Network>,
+    ) -> Result<(), Self::Err> {
+        let _ = (tx, meta, receipt);
+        Ok(())
+    }
+}
+
+impl<N: NodePrimitives, Network: RpcTypes> RpcResponseEnricher<N, Network> for () {
+    type Err = Infallible;
+}
+
 impl TryIntoSimTx<EthereumTxEnvelope<TxEip4844>> for TransactionRequest {
     fn try_into_sim_tx(self) -> Result<EthereumTxEnvelope<TxEip4844>, ValueError<Self>> {
         Self::build_typed_simulate_transaction(self)
@@ -504,8 +548,20 @@ pub struct TransactionConversionError(String);
 ///   implemented for a dedicated struct that is assigned to `Map`. If [`FromConsensusTx::TxInfo`]
 ///   is [`TransactionInfo`] then `()` can be used as `Map` which trivially passes over the input
 ///   object.
+///
+/// Chain specific fields can be added to the converted responses with a [`RpcResponseEnricher`]
+/// assigned to `Enrich`, see [`RpcConverter::with_enricher`].
 #[derive(Debug)]
-pub struct RpcConverter<Network, Evm, Receipt, Header = (), Map = (), SimTx = (), RpcTx = ()> {
+pub struct RpcConverter<
+    Network,
+    Evm,
+    Receipt,
+    Header = (),
+    Map = (),
+    SimTx = (),
+    RpcTx = (),
+    Enrich = (),
+> {
     network: PhantomData<Network>,
     evm: PhantomData<Evm>,
     receipt_converter: Receipt,
@@ -513,6 +569,7 @@ pub struct RpcConverter<Network, Evm, Receipt, Header = (), Map = (), SimTx = ()
     mapper: Map,
     sim_tx_converter: SimTx,
     rpc_tx_converter: RpcTx,
+    enricher: Enrich,
 }
 
 impl<Network, Evm, Receipt> RpcConverter<Network, Evm, Receipt> {
@@ -526,15 +583,18 @@ impl<Network, Evm, Receipt> RpcConverter<Network, Evm, Receipt> {
             mapper: (),
             sim_tx_converter: (),
             rpc_tx_converter: (),
+            enricher: (),
         }
     }
 }
 
-impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
-    RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
+impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich>
+    RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich>
 {
     /// Converts the network type
-    pub fn with_network<N>(self) -> RpcConverter<N, Evm, Receipt, Header, Map, SimTx, RpcTx> {
+    pub fn with_network<N>(
+        self,
+    ) -> RpcConverter<N, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich> {
         let Self {
             receipt_converter,
             header_converter,
@@ -542,6 +602,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
             ..
         } = self;
         RpcConverter {
@@ -552,6 +613,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         }
     }
 
@@ -559,7 +621,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
     pub fn with_header_converter<HeaderNew>(
         self,
         header_converter: HeaderNew,
-    ) -> RpcConverter<Network, Evm, Receipt, HeaderNew, Map, SimTx, RpcTx> {
+    ) -> RpcConverter<Network, Evm, Receipt, HeaderNew, Map, SimTx, RpcTx, Enrich> {
         let Self {
             receipt_converter,
             header_converter: _,
@@ -568,6 +630,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         } = self;
         RpcConverter {
             receipt_converter,
@@ -577,6 +640,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         }
     }
 
@@ -584,7 +648,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
     pub fn with_mapper<MapNew>(
         self,
         mapper: MapNew,
-    ) -> RpcConverter<Network, Evm, Receipt, Header, MapNew, SimTx, RpcTx> {
+    ) -> RpcConverter<Network, Evm, Receipt, Header, MapNew, SimTx, RpcTx, Enrich> {
         let Self {
             receipt_converter,
             header_converter,
@@ -593,6 +657,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         } = self;
         RpcConverter {
             receipt_converter,
@@ -602,6 +667,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         }
     }
 
@@ -609,7 +675,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
     pub fn with_sim_tx_converter<SimTxNew>(
         self,
         sim_tx_converter: SimTxNew,
-    ) -> RpcConverter<Network, Evm, Receipt, Header, Map, SimTxNew, RpcTx> {
+    ) -> RpcConverter<Network, Evm, Receipt, Header, Map, SimTxNew, RpcTx, Enrich> {
         let Self {
             receipt_converter,
             header_converter,
@@ -617,6 +683,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             network,
             evm,
             rpc_tx_converter,
+            enricher,
             ..
         } = self;
         RpcConverter {
@@ -627,6 +694,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         }
     }
 
@@ -634,7 +702,35 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
     pub fn with_rpc_tx_converter<RpcTxNew>(
         self,
         rpc_tx_converter: RpcTxNew,
-    ) -> RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTxNew> {
+    ) -> RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTxNew, Enrich> {
+        let Self {
+            receipt_converter,
+            header_converter,
+            mapper,
+            network,
+            evm,
+            sim_tx_converter,
+            enricher,
+            ..
+        } = self;
+        RpcConverter {
+            receipt_converter,
+            header_converter,
+            mapper,
+            network,
+            evm,
+            sim_tx_converter,
+            rpc_tx_converter,
+            enricher,
+        }
+    }
+
+    /// Configures the [`RpcResponseEnricher`] that adds chain specific fields to converted
+    /// transaction and receipt responses.
+    pub fn with_enricher<EnrichNew>(
+        self,
+        enricher: EnrichNew,
+    ) -> RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, EnrichNew> {
         let Self {
             receipt_converter,
             header_converter,
@@ -642,6 +738,7 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             network,
             evm,
             sim_tx_converter,
+            rpc_tx_converter,
             ..
         } = self;
         RpcConverter {
@@ -652,18 +749,20 @@ impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
             evm,
             sim_tx_converter,
             rpc_tx_converter,
+            enricher,
         }
     }
 }
 
-impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx> Default
-    for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
+impl<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich> Default
+    for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich>
 where
     Receipt: Default,
     Header: Default,
     Map: Default,
     SimTx: Default,
     RpcTx: Default,
+    Enrich: Default,
 {
     fn default() -> Self {
         Self {
@@ -674,12 +773,21 @@ where
             mapper: Default::default(),
             sim_tx_converter: Default::default(),
             rpc_tx_converter: Default::default(),
+            enricher: Default::default(),
         }
     }
 }
 
-impl<Network, Evm, Receipt: Clone, Header: Clone, Map: Clone, SimTx: Clone, RpcTx: Clone> Clone
-    for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
+impl<
+        Network,
+        Evm,
+        Receipt: Clone,
+        Header: Clone,
+        Map: Clone,
+        SimTx: Clone,
+        RpcTx: Clone,
+        Enrich: Clone,
+    > Clone for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich>
 {
     fn clone(&self) -> Self {
         Self {
@@ -690,12 +798,13 @@ impl<Network, Evm, Receipt: Clone, Header: Clone, Map: Clone, SimTx: Clone, RpcT
             mapper: self.mapper.clone(),
             sim_tx_converter: self.sim_tx_converter.clone(),
             rpc_tx_converter: self.rpc_tx_converter.clone(),
+            enricher: self.enricher.clone(),
         }
     }
 }
 
-impl<N, Network, Evm, Receipt, Header, Map, SimTx, RpcTx> RpcConvert
-    for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx>
+impl<N, Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich> RpcConvert
+    for RpcConverter<Network, Evm, Receipt, Header, Map, SimTx, RpcTx, Enrich>
 where
     N: NodePrimitives,
     Network: RpcTypes + Send + Sync + Unpin + Clone + Debug,
@@ -709,6 +818,7 @@ where
                        + From<<RpcTxReq<Network> as TryIntoTxEnv<TxEnvFor<Evm>>>::Err>
                        + From<<Map as TxInfoMapper<TxTy<N>>>::Err>
                        + From<RpcTx::Err>
+                       + From<Enrich::Err>
                        + Error
                        + Unpin
                        + Sync
@@ -724,6 +834,7 @@ where
     SimTx: SimTxConverter<RpcTxReq<Network>, TxTy<N>>,
     RpcTx:
         RpcTxConverter<TxTy<N>, Network::TransactionResponse, <Map as TxInfoMapper<TxTy<N>>>::Out>,
+    Enrich: RpcResponseEnricher<N, Network>,
 {
     type Primitives = N;
     type Network = Network;
@@ -736,9 +847,12 @@ where
         tx_info: TransactionInfo,
     ) -> Result<Network::TransactionResponse, Self::Error> {
         let (tx, signer) = tx.into_parts();
-        let tx_info = self.mapper.try_map(&tx, tx_info)?;
+        let mapped_tx_info = self.mapper.try_map(&tx, tx_info)?;
+
+        let mut rpc_tx = self.rpc_tx_converter.convert_rpc_tx(tx, signer, mapped_tx_info)?;
+        self.enricher.enrich_transaction(&mut rpc_tx, &tx_info)?;
 
-        Ok(self.rpc_tx_converter.convert_rpc_tx(tx, signer, tx_info)?)
+        Ok(rpc_tx)
     }
 
     fn build_simulate_v1_transaction(
@@ -764,7 +878,14 @@ where
         &self,
         receipts: Vec<ConvertReceiptInput<'_, Self::Primitives>>,
     ) -> Result<Vec<RpcReceipt<Self::Network>>, Self::Error> {
-        self.receipt_converter.convert_receipts(receipts)
+        let txs = receipts.iter().map(|input| (input.tx, input.meta)).collect::<Vec<_>>();
+        let mut rpc_receipts = self.receipt_converter.convert_receipts(receipts)?;
+
+        for ((tx, meta), receipt) in txs.into_iter().zip(&mut rpc_receipts) {
+            self.enricher.enrich_receipt(tx, &meta, receipt)?;
+        }
+
+        Ok(rpc_receipts)
     }
 
     fn convert_header(
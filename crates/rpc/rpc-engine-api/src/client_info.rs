@@ -0,0 +1,94 @@
+use reth_chainspec::EthereumHardforks;
+use reth_payload_primitives::EngineApiMessageVersion;
+
+/// The engine API methods that are versioned by hardfork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VersionedMethod {
+    /// `engine_newPayloadVx`
+    NewPayload,
+    /// `engine_forkchoiceUpdatedVx`
+    ForkchoiceUpdated,
+    /// `engine_getPayloadVx`
+    GetPayload,
+}
+
+impl VersionedMethod {
+    /// Returns the name of the given version of the method.
+    pub(crate) fn name(self, version: EngineApiMessageVersion) -> String {
+        let method = match self {
+            Self::NewPayload => "engine_newPayload",
+            Self::ForkchoiceUpdated => "engine_forkchoiceUpdated",
+            Self::GetPayload => "engine_getPayload",
+        };
+        format!("{method}V{}", version as u8)
+    }
+
+    /// Returns the version of the method the consensus client is expected to use for a payload
+    /// with the given timestamp.
+    ///
+    /// Lower versions are deprecated at that timestamp.
+    pub(crate) fn expected_version<ChainSpec: EthereumHardforks>(
+        self,
+        chain_spec: &ChainSpec,
+        timestamp: u64,
+    ) -> EngineApiMessageVersion {
+        let version = if chain_spec.is_osaka_active_at_timestamp(timestamp) {
+            EngineApiMessageVersion::V5
+        } else if chain_spec.is_prague_active_at_timestamp(timestamp) {
+            EngineApiMessageVersion::V4
+        } else if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            EngineApiMessageVersion::V3
+        } else if chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+            EngineApiMessageVersion::V2
+        } else {
+            EngineApiMessageVersion::V1
+        };
+
+        // not every fork introduced a new version of every method
+        let latest = match self {
+            Self::NewPayload => EngineApiMessageVersion::V4,
+            Self::ForkchoiceUpdated => EngineApiMessageVersion::V3,
+            Self::GetPayload => EngineApiMessageVersion::V5,
+        };
+        version.min(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+
+    #[test]
+    fn expected_versions() {
+        // after cancun
+        let timestamp = 1_710_338_135;
+        assert_eq!(
+            VersionedMethod::NewPayload.expected_version(&*MAINNET, timestamp),
+            EngineApiMessageVersion::V3
+        );
+        assert_eq!(
+            VersionedMethod::ForkchoiceUpdated.expected_version(&*MAINNET, timestamp),
+            EngineApiMessageVersion::V3
+        );
+
+        // after prague
+        let timestamp = 1_746_612_311;
+        assert_eq!(
+            VersionedMethod::NewPayload.expected_version(&*MAINNET, timestamp),
+            EngineApiMessageVersion::V4
+        );
+        assert_eq!(
+            VersionedMethod::ForkchoiceUpdated.expected_version(&*MAINNET, timestamp),
+            EngineApiMessageVersion::V3
+        );
+        assert_eq!(
+            VersionedMethod::GetPayload.expected_version(&*MAINNET, timestamp),
+            EngineApiMessageVersion::V4
+        );
+        assert_eq!(
+            VersionedMethod::GetPayload.name(EngineApiMessageVersion::V4),
+            "engine_getPayloadV4"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    capabilities::EngineCapabilities, metrics::EngineApiMetrics, EngineApiError, EngineApiResult,
+    capabilities::EngineCapabilities, client_info::VersionedMethod, metrics::EngineApiMetrics,
+    EngineApiError, EngineApiResult,
 };
 use alloy_eips::{
     eip1898::BlockHashOrNumber,
@@ -21,7 +22,7 @@ use reth_chainspec::EthereumHardforks;
 use reth_engine_primitives::{ConsensusEngineHandle, EngineApiValidator, EngineTypes};
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayload,
+    validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayload, PayloadAttributes,
     PayloadBuilderAttributes, PayloadOrAttributes, PayloadTypes,
 };
 use reth_primitives_traits::{Block, BlockBody};
@@ -29,7 +30,10 @@ use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::oneshot;
 use tracing::{debug, trace, warn};
 
@@ -113,11 +117,49 @@ where
     /// Fetches the client version.
     pub fn get_client_version_v1(
         &self,
-        _client: ClientVersionV1,
+        client: ClientVersionV1,
     ) -> EngineApiResult<Vec<ClientVersionV1>> {
+        if let Some(tracker) = self.inner.beacon_consensus.client_tracker() {
+            tracker.on_client_version(client);
+        }
         Ok(vec![self.inner.client.clone()])
     }
 
+    /// Records the capabilities announced by the consensus client and returns the supported
+    /// capabilities.
+    pub fn exchange_capabilities(&self, capabilities: Vec<String>) -> Vec<String> {
+        let supported = self.capabilities().list();
+        if let Some(tracker) = self.inner.beacon_consensus.client_tracker() {
+            tracker.on_exchange_capabilities(capabilities, &supported);
+        }
+        supported
+    }
+
+    /// Records the call of a versioned method by the consensus client.
+    ///
+    /// Warns once per method if the version is deprecated by the fork that is active at the given
+    /// timestamp.
+    fn on_versioned_call(
+        &self,
+        method: VersionedMethod,
+        version: EngineApiMessageVersion,
+        timestamp: Option<u64>,
+    ) {
+        let expected =
+            timestamp.map(|timestamp| method.expected_version(&*self.inner.chain_spec, timestamp));
+        let deprecated = expected.is_some_and(|expected| version < expected);
+        let name = method.name(version);
+        let Some(tracker) = self.inner.beacon_consensus.client_tracker() else { return };
+        if tracker.on_call(&name, deprecated) {
+            warn!(
+                target: "rpc::engine",
+                method = %name,
+                expected = ?expected.map(|expected| method.name(expected)),
+                "Consensus client uses a deprecated engine API method, make sure it supports the active fork"
+            );
+        }
+    }
+
     /// Fetches the attributes for the payload with the given id.
     async fn get_payload_attributes(
         &self,
@@ -137,6 +179,12 @@ where
         &self,
         payload: PayloadT::ExecutionData,
     ) -> EngineApiResult<PayloadStatus> {
+        self.on_versioned_call(
+            VersionedMethod::NewPayload,
+            EngineApiMessageVersion::V1,
+            Some(payload.timestamp()),
+        );
+
         let payload_or_attrs = PayloadOrAttributes::<
             '_,
             PayloadT::ExecutionData,
@@ -175,6 +223,12 @@ where
         &self,
         payload: PayloadT::ExecutionData,
     ) -> EngineApiResult<PayloadStatus> {
+        self.on_versioned_call(
+            VersionedMethod::NewPayload,
+            EngineApiMessageVersion::V2,
+            Some(payload.timestamp()),
+        );
+
         let payload_or_attrs = PayloadOrAttributes::<
             '_,
             PayloadT::ExecutionData,
@@ -211,6 +265,12 @@ where
         &self,
         payload: PayloadT::ExecutionData,
     ) -> EngineApiResult<PayloadStatus> {
+        self.on_versioned_call(
+            VersionedMethod::NewPayload,
+            EngineApiMessageVersion::V3,
+            Some(payload.timestamp()),
+        );
+
         let payload_or_attrs = PayloadOrAttributes::<
             '_,
             PayloadT::ExecutionData,
@@ -248,6 +308,12 @@ where
         &self,
         payload: PayloadT::ExecutionData,
     ) -> EngineApiResult<PayloadStatus> {
+        self.on_versioned_call(
+            VersionedMethod::NewPayload,
+            EngineApiMessageVersion::V4,
+            Some(payload.timestamp()),
+        );
+
         let payload_or_attrs = PayloadOrAttributes::<
             '_,
             PayloadT::ExecutionData,
@@ -399,6 +465,8 @@ where
     where
         EngineT::BuiltPayload: TryInto<R>,
     {
+        self.on_versioned_call(VersionedMethod::GetPayload, version, Some(unix_timestamp()));
+
         // First we fetch the payload attributes to check the timestamp
         let attributes = self.get_payload_attributes(payload_id).await?;
 
@@ -425,6 +493,11 @@ where
         &self,
         payload_id: PayloadId,
     ) -> EngineApiResult<EngineT::ExecutionPayloadEnvelopeV1> {
+        self.on_versioned_call(
+            VersionedMethod::GetPayload,
+            EngineApiMessageVersion::V1,
+            Some(unix_timestamp()),
+        );
         self.get_built_payload(payload_id).await?.try_into().map_err(|_| {
             warn!(version = ?EngineApiMessageVersion::V1, "could not transform built payload");
             EngineApiError::UnknownPayload
@@ -716,6 +789,11 @@ where
         payload_attrs: Option<EngineT::PayloadAttributes>,
     ) -> EngineApiResult<ForkchoiceUpdated> {
         self.inner.record_elapsed_time_on_fcu();
+        self.on_versioned_call(
+            VersionedMethod::ForkchoiceUpdated,
+            version,
+            payload_attrs.as_ref().map(|attrs| attrs.timestamp()),
+        );
 
         if let Some(ref attrs) = payload_attrs {
             let attr_validation_res =
@@ -1091,8 +1169,9 @@ where
 
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
-        Ok(self.capabilities().list())
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+        trace!(target: "rpc::engine", "Serving engine_exchangeCapabilities");
+        Ok(Self::exchange_capabilities(self, capabilities))
     }
 
     async fn get_blobs_v1(
@@ -1143,6 +1222,11 @@ where
     }
 }
 
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 /// The container type for the engine API internals.
 struct EngineApiInner<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec> {
     /// The provider to interact with the chain.
@@ -1193,14 +1277,21 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_rpc_types_engine::{ClientCode, ClientVersionV1};
+    use crate::error::INVALID_PAYLOAD_ATTRIBUTES;
+    use alloy_primitives::Address;
+    use alloy_rpc_types_engine::{
+        ClientCode, ClientVersionV1, PayloadAttributes as EthPayloadAttributes,
+    };
     use assert_matches::assert_matches;
-    use reth_chainspec::{ChainSpec, MAINNET};
-    use reth_engine_primitives::BeaconEngineMessage;
+    use reth_chainspec::{ChainSpec, EthereumHardfork, Hardforks, MAINNET};
+    use reth_engine_primitives::{
+        BeaconEngineMessage, EngineClientTracker, OnForkChoiceUpdated, WithAttributesValidator,
+    };
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_ethereum_primitives::Block;
     use reth_node_ethereum::EthereumEngineValidator;
     use reth_payload_builder::test_utils::spawn_test_payload_service;
+    use reth_payload_primitives::{EngineObjectValidationError, PayloadAttributesValidator};
     use reth_provider::test_utils::MockEthProvider;
     use reth_tasks::TokioTaskExecutor;
     use reth_transaction_pool::noop::NoopTransactionPool;
@@ -1216,6 +1307,18 @@ mod tests {
             ChainSpec,
         >,
     ) {
+        setup_engine_api_with_validator(EthereumEngineValidator::new(MAINNET.clone()))
+    }
+
+    fn setup_engine_api_with_validator<Validator>(
+        validator: Validator,
+    ) -> (
+        EngineApiTestHandle,
+        EngineApi<Arc<MockEthProvider>, EthEngineTypes, NoopTransactionPool, Validator, ChainSpec>,
+    )
+    where
+        Validator: EngineApiValidator<EthEngineTypes>,
+    {
         let client = ClientVersionV1 {
             code: ClientCode::RH,
             name: "Reth".to_string(),
@@ -1231,13 +1334,14 @@ mod tests {
         let api = EngineApi::new(
             provider.clone(),
             chain_spec.clone(),
-            ConsensusEngineHandle::new(to_engine),
+            ConsensusEngineHandle::new(to_engine)
+                .with_client_tracker(EngineClientTracker::default()),
             payload_store.into(),
             NoopTransactionPool::default(),
             task_executor,
             client,
             EngineCapabilities::default(),
-            EthereumEngineValidator::new(chain_spec.clone()),
+            validator,
             false,
         );
         let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
@@ -1257,6 +1361,25 @@ mod tests {
         assert_eq!(res.unwrap(), vec![client]);
     }
 
+    #[tokio::test]
+    async fn tracks_consensus_client_capabilities() {
+        let (_, api) = setup_engine_api();
+        let supported = api.exchange_capabilities(vec![
+            "engine_newPayloadV4".to_string(),
+            "engine_newPayloadV9".to_string(),
+        ]);
+        assert_eq!(supported.len(), api.capabilities().list().len());
+
+        let info = api.inner.beacon_consensus.client_tracker().unwrap().info();
+        assert_eq!(
+            info.capabilities,
+            Some(vec!["engine_newPayloadV4".to_string(), "engine_newPayloadV9".to_string()])
+        );
+        assert_eq!(info.unsupported_capabilities, vec!["engine_newPayloadV9".to_string()]);
+        assert!(info.missing_capabilities.contains(&"engine_forkchoiceUpdatedV3".to_string()));
+        assert!(!info.missing_capabilities.contains(&"engine_newPayloadV4".to_string()));
+    }
+
     struct EngineApiTestHandle {
         #[allow(dead_code)]
         chain_spec: Arc<ChainSpec>,
@@ -1280,6 +1403,62 @@ mod tests {
         assert_matches!(handle.from_api.recv().await, Some(BeaconEngineMessage::NewPayload { .. }));
     }
 
+    /// Rejects payload attributes with the given fee recipient.
+    #[derive(Debug, Clone)]
+    struct DenyFeeRecipient(Address);
+
+    impl PayloadAttributesValidator<EthPayloadAttributes> for DenyFeeRecipient {
+        fn validate_payload_attributes(
+            &self,
+            _version: EngineApiMessageVersion,
+            attributes: &EthPayloadAttributes,
+        ) -> Result<(), EngineObjectValidationError> {
+            if attributes.suggested_fee_recipient == self.0 {
+                return Err(EngineObjectValidationError::invalid_payload_attributes(
+                    std::io::Error::other("fee recipient not allowed"),
+                ))
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn fork_choice_updated_rejects_attributes_of_attributes_validator() {
+        let denied = Address::with_last_byte(1);
+        let (mut handle, api) = setup_engine_api_with_validator(WithAttributesValidator::new(
+            EthereumEngineValidator::new(MAINNET.clone()),
+            DenyFeeRecipient(denied),
+        ));
+
+        // the forkchoice update is still applied, without the attributes
+        tokio::spawn(async move {
+            let Some(BeaconEngineMessage::ForkchoiceUpdated { payload_attrs, tx, .. }) =
+                handle.from_api.recv().await
+            else {
+                panic!("expected forkchoice update")
+            };
+            assert!(payload_attrs.is_none());
+            let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+        });
+
+        let shanghai = MAINNET.fork(EthereumHardfork::Shanghai).as_timestamp().unwrap();
+        let attributes = EthPayloadAttributes {
+            timestamp: shanghai + 12,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: denied,
+            withdrawals: Some(vec![]),
+            parent_beacon_block_root: None,
+        };
+        let err = EngineApiServer::fork_choice_updated_v2(
+            &api,
+            ForkchoiceState::default(),
+            Some(attributes),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), INVALID_PAYLOAD_ATTRIBUTES);
+    }
+
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
@@ -19,14 +19,21 @@ pub use capabilities::EngineCapabilities;
 /// Engine API error.
 mod error;
 
+/// Tracking of the engine API usage of the consensus client.
+mod client_info;
+
 /// Engine API metrics.
 mod metrics;
 
+/// The `reth` API implementation of the engine API server.
+mod reth;
+
 pub use engine_api::{EngineApi, EngineApiSender};
 pub use error::*;
+pub use reth::RethEngineApi;
 
 // re-export server trait for convenience
-pub use reth_rpc_api::EngineApiServer;
+pub use reth_rpc_api::{EngineApiServer, RethEngineApiServer};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,131 @@
+use crate::EngineApiError;
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use reth_engine_primitives::{
+    BackfillThresholds, ConsensusEngineHandle, EngineClientInfo, EngineUnavailable,
+    ForkchoiceHistoryEntry,
+};
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{PayloadJobStats, PayloadTypes};
+use reth_primitives_traits::NodePrimitives;
+use reth_rpc_api::RethEngineApiServer;
+use reth_rpc_eth_types::{
+    EthApiError, EthStateCache, EthStateCacheLimits, EthStateCacheStats, RpcLimits, RpcLimitsHandle,
+};
+use reth_transaction_pool::{SubPoolLimits, TransactionPool};
+
+/// `reth` API implementation for the authenticated engine API server.
+///
+/// This exposes insights into the payload builder that are only relevant to the consensus client
+/// and operators of block builders and the engine API usage of the consensus client, and allows
+/// operators to control the sync of the engine, the size limits of the transaction pool and the
+/// `eth` RPC state cache and the limits of the RPC.
+#[derive(Debug)]
+pub struct RethEngineApi<T: PayloadTypes, Pool, N: NodePrimitives> {
+    /// Handle to the payload builder service.
+    payload_builder: PayloadBuilderHandle<T>,
+    /// Handle to the consensus engine.
+    beacon_consensus: ConsensusEngineHandle<T>,
+    /// The transaction pool.
+    pool: Pool,
+    /// The state cache of the `eth` RPC.
+    eth_cache: EthStateCache<N>,
+    /// The gas cap, tx fee cap, execution timeouts and state history depth of the RPC.
+    rpc_limits: RpcLimitsHandle,
+}
+
+impl<T: PayloadTypes, Pool, N: NodePrimitives> RethEngineApi<T, Pool, N> {
+    /// Creates a new instance of the [`RethEngineApi`].
+    pub const fn new(
+        payload_builder: PayloadBuilderHandle<T>,
+        beacon_consensus: ConsensusEngineHandle<T>,
+        pool: Pool,
+        eth_cache: EthStateCache<N>,
+        rpc_limits: RpcLimitsHandle,
+    ) -> Self {
+        Self { payload_builder, beacon_consensus, pool, eth_cache, rpc_limits }
+    }
+}
+
+#[async_trait]
+impl<T, Pool, N> RethEngineApiServer for RethEngineApi<T, Pool, N>
+where
+    T: PayloadTypes,
+    Pool: TransactionPool + 'static,
+    N: NodePrimitives,
+{
+    /// Handler for `reth_payloadStats`
+    async fn reth_payload_stats(&self) -> RpcResult<Vec<PayloadJobStats>> {
+        Ok(self.payload_builder.stats().await.map_err(EngineApiError::from)?)
+    }
+
+    /// Handler for `reth_triggerBackfill`
+    async fn reth_trigger_backfill(&self, target: B256) -> RpcResult<bool> {
+        Ok(self.beacon_consensus.trigger_backfill(target).await.map_err(unavailable)?)
+    }
+
+    /// Handler for `reth_backfillThresholds`
+    async fn reth_backfill_thresholds(&self) -> RpcResult<BackfillThresholds> {
+        Ok(self.beacon_consensus.backfill_thresholds().await.map_err(unavailable)?)
+    }
+
+    /// Handler for `reth_setBackfillThresholds`
+    async fn reth_set_backfill_thresholds(
+        &self,
+        thresholds: BackfillThresholds,
+    ) -> RpcResult<BackfillThresholds> {
+        Ok(self.beacon_consensus.set_backfill_thresholds(thresholds).await.map_err(unavailable)?)
+    }
+
+    /// Handler for `reth_poolLimits`
+    async fn reth_pool_limits(&self) -> RpcResult<SubPoolLimits> {
+        Ok(self.pool.subpool_limits())
+    }
+
+    /// Handler for `reth_setPoolLimits`
+    async fn reth_set_pool_limits(&self, limits: SubPoolLimits) -> RpcResult<SubPoolLimits> {
+        Ok(self.pool.set_subpool_limits(limits))
+    }
+
+    /// Handler for `reth_ethCacheStats`
+    async fn reth_eth_cache_stats(&self) -> RpcResult<EthStateCacheStats> {
+        Ok(self.eth_cache.stats().await.map_err(EthApiError::from)?)
+    }
+
+    /// Handler for `reth_setEthCacheLimits`
+    async fn reth_set_eth_cache_limits(
+        &self,
+        limits: EthStateCacheLimits,
+    ) -> RpcResult<EthStateCacheLimits> {
+        Ok(self.eth_cache.set_limits(limits).await.map_err(EthApiError::from)?)
+    }
+
+    /// Handler for `reth_rpcLimits`
+    async fn reth_rpc_limits(&self) -> RpcResult<RpcLimits> {
+        Ok(self.rpc_limits.get())
+    }
+
+    /// Handler for `reth_setRpcLimits`
+    async fn reth_set_rpc_limits(&self, limits: RpcLimits) -> RpcResult<RpcLimits> {
+        Ok(self.rpc_limits.set(limits)?)
+    }
+
+    /// Handler for `reth_engineClientInfo`
+    async fn reth_engine_client_info(&self) -> RpcResult<EngineClientInfo> {
+        Ok(self.beacon_consensus.client_tracker().map(|tracker| tracker.info()).unwrap_or_default())
+    }
+
+    /// Handler for `reth_getReorgHistory`
+    async fn reth_get_reorg_history(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ForkchoiceHistoryEntry>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        Ok(self.beacon_consensus.fork_choice_history(limit).await.map_err(unavailable)?)
+    }
+}
+
+fn unavailable(err: EngineUnavailable) -> EngineApiError {
+    EngineApiError::Internal(Box::new(err))
+}
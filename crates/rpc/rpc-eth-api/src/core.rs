@@ -1,9 +1,12 @@
 //! Implementation of the [`jsonrpsee`] generated [`EthApiServer`] trait. Handles RPC requests for
 //! the `eth_` namespace.
 use crate::{
-    helpers::{EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi},
+    helpers::{
+        Call, EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi,
+    },
     RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
+use alloy_consensus::BlockHeader;
 use alloy_dyn_abi::TypedData;
 use alloy_eips::{eip2930::AccessListResult, BlockId, BlockNumberOrTag};
 use alloy_json_rpc::RpcObject;
@@ -12,12 +15,18 @@ use alloy_rpc_types_eth::{
     simulate::{SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
     BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Index,
-    StateContext, SyncStatus, Work,
+    StateContext, Work,
 };
 use alloy_serde::JsonStorageKey;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, RpcModule};
+use jsonrpsee_types::ErrorObjectOwned;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_rpc_convert::RpcTxReq;
+use reth_rpc_eth_types::{
+    limits::with_timeout, BlockQueryOptions, BlockWithMetadata, EthSyncStatus,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_storage_api::RequestsProvider;
 use tracing::trace;
 
 /// Helper trait, unifies functionality that must be supported to implement all RPC methods for
@@ -32,6 +41,41 @@ pub trait FullEthApiServer:
     > + FullEthApi
     + Clone
 {
+    /// Converts the API into the `eth_` namespace [`RpcModule`].
+    ///
+    /// Unlike [`EthApiServer::into_rpc`], the `eth_getBlockByHash` and `eth_getBlockByNumber`
+    /// handlers write the block straight into the response, see [`EthBlocks::rpc_block_json`].
+    fn into_eth_rpc_module(self) -> RpcModule<Self> {
+        let mut module = self.into_rpc();
+
+        module.remove_method("eth_getBlockByHash");
+        module
+            .register_async_method("eth_getBlockByHash", |params, eth_api, _| async move {
+                let mut params = params.sequence();
+                let hash: B256 = params.next()?;
+                let full: bool = params.next()?;
+                trace!(target: "rpc::eth", ?hash, ?full, "Serving eth_getBlockByHash");
+                EthBlocks::rpc_block_json(&*eth_api, hash.into(), full)
+                    .await
+                    .map_err(Into::<ErrorObjectOwned>::into)
+            })
+            .expect("method was removed");
+
+        module.remove_method("eth_getBlockByNumber");
+        module
+            .register_async_method("eth_getBlockByNumber", |params, eth_api, _| async move {
+                let mut params = params.sequence();
+                let number: BlockNumberOrTag = params.next()?;
+                let full: bool = params.next()?;
+                trace!(target: "rpc::eth", ?number, ?full, "Serving eth_getBlockByNumber");
+                EthBlocks::rpc_block_json(&*eth_api, number.into(), full)
+                    .await
+                    .map_err(Into::<ErrorObjectOwned>::into)
+            })
+            .expect("method was removed");
+
+        module
+    }
 }
 
 impl<T> FullEthApiServer for T where
@@ -56,7 +100,7 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
 
     /// Returns an object with data about the sync status or false.
     #[method(name = "syncing")]
-    fn syncing(&self) -> RpcResult<SyncStatus>;
+    fn syncing(&self) -> RpcResult<EthSyncStatus>;
 
     /// Returns the client coinbase address.
     #[method(name = "coinbase")]
@@ -82,6 +126,18 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     #[method(name = "getBlockByNumber")]
     async fn block_by_number(&self, number: BlockNumberOrTag, full: bool) -> RpcResult<Option<B>>;
 
+    /// Returns information about a block by number.
+    ///
+    /// In contrast to `eth_getBlockByNumber`, the options control which sub objects are included
+    /// in the response, e.g. to exclude withdrawals or include the execution requests and the
+    /// active blob schedule.
+    #[method(name = "getBlockByNumberWithOptions")]
+    async fn block_by_number_with_options(
+        &self,
+        number: BlockNumberOrTag,
+        options: Option<BlockQueryOptions>,
+    ) -> RpcResult<Option<BlockWithMetadata<B>>>;
+
     /// Returns the number of transactions in a block from a block matching the given block hash.
     #[method(name = "getBlockTransactionCountByHash")]
     async fn block_transaction_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>>;
@@ -219,6 +275,9 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     ) -> RpcResult<Vec<SimulatedBlock<B>>>;
 
     /// Executes a new message call immediately without creating a transaction on the block chain.
+    ///
+    /// If `disable_base_fee_check` is set, the fee cap of the request is not required to cover the
+    /// base fee of the block.
     #[method(name = "call")]
     async fn call(
         &self,
@@ -226,6 +285,7 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        disable_base_fee_check: Option<bool>,
     ) -> RpcResult<Bytes>;
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
@@ -252,12 +312,16 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     /// when the transaction is actually mined. Adding an accessList to your transaction does
     /// not necessary result in lower gas usage compared to a transaction without an access
     /// list.
+    ///
+    /// Transactions carrying an [EIP7702](https://eips.ethereum.org/EIPS/eip-7702) authorization
+    /// list are supported, the authorities are not included in the generated access list.
     #[method(name = "createAccessList")]
     async fn create_access_list(
         &self,
         request: TxReq,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<AccessListResult>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
@@ -400,7 +464,7 @@ where
     }
 
     /// Handler for: `eth_syncing`
-    fn syncing(&self) -> RpcResult<SyncStatus> {
+    fn syncing(&self) -> RpcResult<EthSyncStatus> {
         trace!(target: "rpc::eth", "Serving eth_syncing");
         EthApiSpec::sync_status(self).to_rpc_result()
     }
@@ -450,6 +514,39 @@ where
         Ok(EthBlocks::rpc_block(self, number.into(), full).await?)
     }
 
+    /// Handler for: `eth_getBlockByNumberWithOptions`
+    async fn block_by_number_with_options(
+        &self,
+        number: BlockNumberOrTag,
+        options: Option<BlockQueryOptions>,
+    ) -> RpcResult<Option<BlockWithMetadata<RpcBlock<T::NetworkTypes>>>> {
+        trace!(target: "rpc::eth", ?number, ?options, "Serving eth_getBlockByNumberWithOptions");
+        let options = options.unwrap_or_default();
+        let Some(mut block) =
+            EthBlocks::rpc_block(self, number.into(), options.full_transactions).await?
+        else {
+            return Ok(None)
+        };
+
+        if !options.withdrawals {
+            block.withdrawals = None;
+        }
+
+        let requests = if options.requests {
+            self.provider().requests_by_block(block.header.number()).to_rpc_result()?
+        } else {
+            None
+        };
+
+        let blob_schedule = if options.blob_schedule {
+            self.provider().chain_spec().blob_params_at_timestamp(block.header.timestamp())
+        } else {
+            None
+        };
+
+        Ok(Some(BlockWithMetadata { block, requests, blob_schedule }))
+    }
+
     /// Handler for: `eth_getBlockTransactionCountByHash`
     async fn block_transaction_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getBlockTransactionCountByHash");
@@ -469,11 +566,7 @@ where
     async fn block_uncles_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getUncleCountByBlockHash");
 
-        if let Some(block) = self.block_by_hash(hash, false).await? {
-            Ok(Some(U256::from(block.uncles.len())))
-        } else {
-            Ok(None)
-        }
+        Ok(EthBlocks::ommers_count(self, hash.into()).await?.map(U256::from))
     }
 
     /// Handler for: `eth_getUncleCountByBlockNumber`
@@ -483,11 +576,7 @@ where
     ) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?number, "Serving eth_getUncleCountByBlockNumber");
 
-        if let Some(block) = self.block_by_number(number, false).await? {
-            Ok(Some(U256::from(block.uncles.len())))
-        } else {
-            Ok(None)
-        }
+        Ok(EthBlocks::ommers_count(self, number.into()).await?.map(U256::from))
     }
 
     /// Handler for: `eth_getBlockReceipts`
@@ -660,8 +749,9 @@ where
         block_number: Option<BlockId>,
     ) -> RpcResult<Vec<SimulatedBlock<RpcBlock<T::NetworkTypes>>>> {
         trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
-        let _permit = self.tracing_task_guard().clone().acquire_owned().await;
-        Ok(EthCall::simulate_v1(self, payload, block_number).await?)
+        let permit = self.tracing_task_guard().clone().acquire_owned().await.ok();
+        let call = EthCall::simulate_v1(self, payload, block_number);
+        Ok(with_timeout(self.rpc_limits().eth_timeout(), permit, call).await?)
     }
 
     /// Handler for: `eth_call`
@@ -671,15 +761,17 @@ where
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        disable_base_fee_check: Option<bool>,
     ) -> RpcResult<Bytes> {
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
-        Ok(EthCall::call(
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, ?disable_base_fee_check, "Serving eth_call");
+        let call = EthCall::call(
             self,
             request,
             block_number,
             EvmOverrides::new(state_overrides, block_overrides),
-        )
-        .await?)
+            disable_base_fee_check.unwrap_or_default(),
+        );
+        Ok(with_timeout(self.rpc_limits().eth_timeout(), None, call).await?)
     }
 
     /// Handler for: `eth_callMany`
@@ -690,7 +782,8 @@ where
         state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<Vec<EthCallResponse>>> {
         trace!(target: "rpc::eth", ?bundles, ?state_context, ?state_override, "Serving eth_callMany");
-        Ok(EthCall::call_many(self, bundles, state_context, state_override).await?)
+        let call = EthCall::call_many(self, bundles, state_context, state_override);
+        Ok(with_timeout(self.rpc_limits().eth_timeout(), None, call).await?)
     }
 
     /// Handler for: `eth_createAccessList`
@@ -699,9 +792,16 @@ where
         request: RpcTxReq<T::NetworkTypes>,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<AccessListResult> {
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, "Serving eth_createAccessList");
-        Ok(EthCall::create_access_list_at(self, request, block_number, state_override).await?)
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, ?block_overrides, "Serving eth_createAccessList");
+        let call = EthCall::create_access_list_at(
+            self,
+            request,
+            block_number,
+            EvmOverrides::new(state_override, block_overrides),
+        );
+        Ok(with_timeout(self.rpc_limits().eth_timeout(), None, call).await?)
     }
 
     /// Handler for: `eth_estimateGas`
@@ -712,13 +812,13 @@ where
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
-        Ok(EthCall::estimate_gas_at(
+        let call = EthCall::estimate_gas_at(
             self,
             request,
             block_number.unwrap_or_default(),
             state_override,
-        )
-        .await?)
+        );
+        Ok(with_timeout(self.rpc_limits().eth_timeout(), None, call).await?)
     }
 
     /// Handler for: `eth_gasPrice`
@@ -48,6 +48,9 @@ pub struct QueryLimits {
     pub max_blocks_per_filter: Option<u64>,
     /// Maximum number of logs that can be returned in a response
     pub max_logs_per_response: Option<usize>,
+    /// Maximum estimated cost of a query, i.e. the estimated number of blocks in the range whose
+    /// bloom filter matches and whose receipts must be scanned
+    pub max_log_query_cost: Option<u64>,
 }
 
 impl QueryLimits {
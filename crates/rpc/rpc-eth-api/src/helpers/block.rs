@@ -5,19 +5,27 @@ use crate::{
     node::RpcNodeCoreExt, EthApiTypes, FromEthApiError, FullEthApiTypes, RpcBlock, RpcNodeCore,
     RpcReceipt,
 };
-use alloy_consensus::TxReceipt;
+use alloy_consensus::{constants::EMPTY_OMMER_ROOT_HASH, TxReceipt};
 use alloy_eips::BlockId;
 use alloy_rlp::Encodable;
 use alloy_rpc_types_eth::{Block, BlockTransactions, Index};
 use futures::Future;
+use jsonrpsee::core::{to_json_raw_value, JsonRawValue};
+use reth_errors::RethError;
 use reth_node_api::BlockBody;
 use reth_primitives_traits::{
     AlloyBlockHeader, RecoveredBlock, SealedHeader, SignedTransaction, TransactionMeta,
 };
-use reth_rpc_convert::{transaction::ConvertReceiptInput, RpcConvert, RpcHeader};
-use reth_storage_api::{BlockIdReader, BlockReader, ProviderHeader, ProviderReceipt, ProviderTx};
+use reth_rpc_convert::{
+    transaction::ConvertReceiptInput, RpcBlockSerializer, RpcConvert, RpcHeader,
+};
+use reth_rpc_eth_types::{utils::ensure_receipts_not_pruned, EthApiError};
+use reth_storage_api::{
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, ProviderHeader,
+    ProviderReceipt, ProviderTx,
+};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, cell::RefCell, sync::Arc};
 
 /// Result type of the fetched block receipts.
 pub type BlockReceiptsResult<N, E> = Result<Option<Vec<RpcReceipt<N>>>, E>;
@@ -70,6 +78,44 @@ pub trait EthBlocks:
         }
     }
 
+    /// Returns the rpc block object for the given block id, serialized to JSON.
+    ///
+    /// Unlike [`Self::rpc_block`], this neither clones the block nor collects the converted
+    /// transactions, every transaction is converted right before it's written, see
+    /// [`RpcBlockSerializer`].
+    fn rpc_block_json(
+        &self,
+        block_id: BlockId,
+        full: bool,
+    ) -> impl Future<Output = Result<Option<Box<JsonRawValue>>, Self::Error>> + Send
+    where
+        Self: FullEthApiTypes,
+    {
+        async move {
+            let Some(block) = self.recovered_block(block_id).await? else { return Ok(None) };
+
+            let header = self
+                .tx_resp_builder()
+                .convert_header(block.clone_sealed_header(), block.rlp_length())?;
+            // keeps the error of a failed transaction conversion, the serializer only reports it
+            // as a message
+            let conversion_error = RefCell::new(None);
+            let serializer = RpcBlockSerializer::new(&block, full.into(), header, |tx, tx_info| {
+                self.tx_resp_builder().fill(tx, tx_info).map_err(|err| {
+                    let msg = err.to_string();
+                    conversion_error.replace(Some(err));
+                    msg
+                })
+            });
+            match to_json_raw_value(&serializer) {
+                Ok(json) => Ok(Some(json)),
+                Err(err) => Err(conversion_error.take().unwrap_or_else(|| {
+                    Self::Error::from_eth_err(EthApiError::Internal(RethError::msg(err)))
+                })),
+            }
+        }
+    }
+
     /// Returns the number transactions in the given block.
     ///
     /// Returns `None` if the block does not exist
@@ -87,21 +133,60 @@ pub trait EthBlocks:
                     .map(|block| block.body().transaction_count()));
             }
 
-            let block_hash = match self
-                .provider()
-                .block_hash_for_id(block_id)
-                .map_err(Self::Error::from_eth_err)?
-            {
-                Some(block_hash) => block_hash,
-                None => return Ok(None),
+            // The count is served from the body indices, so the block body doesn't have to be
+            // loaded and decoded.
+            let Some(number) =
+                self.provider().block_number_for_id(block_id).map_err(Self::Error::from_eth_err)?
+            else {
+                return Ok(None)
             };
 
+            // The body indices are keyed by number, so they only apply to a block hash if it's
+            // canonical. A non-canonical block is loaded by its hash instead.
+            if let BlockId::Hash(hash) = block_id {
+                let canonical_hash =
+                    self.provider().block_hash(number).map_err(Self::Error::from_eth_err)?;
+                if canonical_hash != Some(hash.block_hash) {
+                    return Ok(self
+                        .cache()
+                        .get_recovered_block(hash.block_hash)
+                        .await
+                        .map_err(Self::Error::from_eth_err)?
+                        .map(|block| block.body().transaction_count()))
+                }
+            }
+
             Ok(self
-                .cache()
-                .get_recovered_block(block_hash)
-                .await
+                .provider()
+                .block_body_indices(number)
                 .map_err(Self::Error::from_eth_err)?
-                .map(|b| b.body().transaction_count()))
+                .map(|indices| indices.tx_count as usize))
+        }
+    }
+
+    /// Returns the number of uncles in the given block.
+    ///
+    /// Returns `None` if the block does not exist
+    fn ommers_count(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Option<usize>, Self::Error>> + Send {
+        async move {
+            if !block_id.is_pending() {
+                let Some(header) =
+                    self.provider().header_by_id(block_id).map_err(Self::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                if header.ommers_hash() == EMPTY_OMMER_ROOT_HASH {
+                    return Ok(Some(0))
+                }
+            }
+
+            Ok(self
+                .recovered_block(block_id)
+                .await?
+                .map(|block| block.body().ommers().map_or(0, |ommers| ommers.len())))
         }
     }
 
@@ -201,6 +286,14 @@ pub trait EthBlocks:
                 {
                     return Ok(Some((block, receipts)));
                 }
+
+                // the receipts might be missing because they've been pruned
+                if let Some(block_number) =
+                    self.provider().block_number(block_hash).map_err(Self::Error::from_eth_err)?
+                {
+                    ensure_receipts_not_pruned(self.provider(), block_number)
+                        .map_err(Self::Error::from_eth_err)?;
+                }
             }
 
             Ok(None)
@@ -217,6 +310,19 @@ pub trait EthBlocks:
     ) -> impl Future<Output = Result<Option<Vec<ProviderHeader<Self::Provider>>>, Self::Error>> + Send
     {
         async move {
+            if !block_id.is_pending() {
+                // Blocks without uncles, which includes all post-merge blocks, are served from the
+                // header alone.
+                let Some(header) =
+                    self.provider().header_by_id(block_id).map_err(Self::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                if header.ommers_hash() == EMPTY_OMMER_ROOT_HASH {
+                    return Ok(Some(Vec::new()))
+                }
+            }
+
             if let Some(block) = self.recovered_block(block_id).await? {
                 Ok(block.body().ommers().map(|o| o.to_vec()))
             } else {
@@ -242,9 +348,7 @@ pub trait EthBlocks:
                     .map_err(Self::Error::from_eth_err)?
                     .and_then(|block| block.body().ommers().map(|o| o.to_vec()))
             } else {
-                self.recovered_block(block_id)
-                    .await?
-                    .map(|block| block.body().ommers().map(|o| o.to_vec()).unwrap_or_default())
+                self.ommers(block_id).await?
             }
             .unwrap_or_default();
 
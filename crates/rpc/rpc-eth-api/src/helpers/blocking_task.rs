@@ -2,7 +2,7 @@
 //! are executed on the `tokio` runtime.
 
 use futures::Future;
-use reth_rpc_eth_types::EthApiError;
+use reth_rpc_eth_types::{EthApiError, RequestScope};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
@@ -43,6 +43,8 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
 
     /// Executes the future on a new blocking task.
     ///
+    /// The task runs in the [`RequestScope`] of the request that spawned it, if any.
+    ///
     /// Note: This is expected for futures that are dominated by blocking IO operations, for tracing
     /// or CPU bound operations in general use [`spawn_tracing`](Self::spawn_tracing).
     fn spawn_blocking_io<F, R>(&self, f: F) -> impl Future<Output = Result<R, Self::Error>> + Send
@@ -52,8 +54,9 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
     {
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
+        let scope = RequestScope::current().unwrap_or_default();
         self.io_task_spawner().spawn_blocking(Box::pin(async move {
-            let res = f(this);
+            let res = scope.run(|| f(this));
             let _ = tx.send(res);
         }));
 
@@ -62,6 +65,8 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
 
     /// Executes a blocking task on the tracing pool.
     ///
+    /// The task runs in the [`RequestScope`] of the request that spawned it, if any.
+    ///
     /// Note: This is expected for futures that are predominantly CPU bound, as it uses `rayon`
     /// under the hood, for blocking IO futures use [`spawn_blocking`](Self::spawn_blocking_io). See
     /// <https://ryhl.io/blog/async-what-is-blocking/>.
@@ -71,7 +76,8 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
         R: Send + 'static,
     {
         let this = self.clone();
-        let fut = self.tracing_task_pool().spawn(move || f(this));
+        let scope = RequestScope::current().unwrap_or_default();
+        let fut = self.tracing_task_pool().spawn(move || scope.run(|| f(this)));
         async move { fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)? }
     }
 }
@@ -8,7 +8,7 @@ use crate::{
     helpers::estimate::EstimateCall, FromEvmError, FullEthApiTypes, RpcBlock, RpcNodeCore,
 };
 use alloy_consensus::BlockHeader;
-use alloy_eips::eip2930::AccessListResult;
+use alloy_eips::eip2930::{AccessList, AccessListResult};
 use alloy_evm::{
     call::caller_gas_allowance,
     overrides::{apply_block_overrides, apply_state_overrides, OverrideBlockHashes},
@@ -21,10 +21,11 @@ use alloy_rpc_types_eth::{
     BlockId, Bundle, EthCallResponse, StateContext, TransactionInfo,
 };
 use futures::Future;
+use reth_chainspec::ChainSpecProvider;
 use reth_errors::{ProviderError, RethError};
 use reth_evm::{
-    ConfigureEvm, Evm, EvmEnv, EvmEnvFor, HaltReasonFor, InspectorFor, SpecFor, TransactionEnv,
-    TxEnvFor,
+    apply_simulated_block_system_calls, ConfigureEvm, Evm, EvmEnv, EvmEnvFor, HaltReasonFor,
+    InspectorFor, SpecFor, TransactionEnv, TxEnvFor,
 };
 use reth_node_api::BlockBody;
 use reth_primitives_traits::{Recovered, SignedTransaction};
@@ -34,12 +35,13 @@ use reth_revm::{
 };
 use reth_rpc_convert::{RpcConvert, RpcTxReq};
 use reth_rpc_eth_types::{
-    cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    cache::db::{prefetch_access_list, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     error::{api::FromEvmHalt, ensure_success, FromEthApiError},
+    limits::ensure_within_deadline,
     simulate::{self, EthSimulateError},
-    EthApiError, RevertError, StateCacheDb,
+    EthApiError, RevertError, RpcLimits, RpcLimitsHandle, StateCacheDb,
 };
-use reth_storage_api::{BlockIdReader, ProviderTx};
+use reth_storage_api::{BlockIdReader, BlockNumReader, ProviderTx};
 use revm::{
     context_interface::{
         result::{ExecutionResult, ResultAndState},
@@ -212,15 +214,24 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
     }
 
     /// Executes the call request (`eth_call`) and returns the output
+    ///
+    /// See [`Call::prepare_call_env`] for `disable_base_fee_check`.
     fn call(
         &self,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
+        disable_base_fee_check: bool,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
         async move {
-            let res =
-                self.transact_call_at(request, block_number.unwrap_or_default(), overrides).await?;
+            let res = self
+                .transact_call_at(
+                    request,
+                    block_number.unwrap_or_default(),
+                    overrides,
+                    disable_base_fee_check,
+                )
+                .await?;
 
             ensure_success(res.result)
         }
@@ -297,6 +308,17 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
                     }
                 }
 
+                // load the state of all access lists of the bundles at once
+                let access_list = AccessList(
+                    bundles
+                        .iter()
+                        .flat_map(|bundle| &bundle.transactions)
+                        .filter_map(|tx| tx.as_ref().access_list())
+                        .flat_map(|access_list| access_list.iter().cloned())
+                        .collect(),
+                );
+                prefetch_access_list(&mut db, &access_list).map_err(Self::Error::from_eth_err)?;
+
                 // transact all bundles
                 for bundle in bundles {
                     let Bundle { transactions, block_override } = bundle;
@@ -316,7 +338,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
                             EvmOverrides::new(state_override.take(), block_overrides.clone());
 
                         let (current_evm_env, prepared_tx) =
-                            this.prepare_call_env(evm_env.clone(), tx, &mut db, overrides)?;
+                            this.prepare_call_env(evm_env.clone(), tx, &mut db, overrides, false)?;
                         let res = this.transact(&mut db, current_evm_env, prepared_tx)?;
 
                         match ensure_success::<_, Self::Error>(res.result) {
@@ -348,11 +370,14 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
 
     /// Creates [`AccessListResult`] for the [`RpcTxReq`] at the given
     /// [`BlockId`], or latest block.
+    ///
+    /// The request may carry an EIP-7702 authorization list, in which case the authorities are
+    /// excluded from the resulting access list since they are loaded anyway.
     fn create_access_list_at(
         &self,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         block_number: Option<BlockId>,
-        state_override: Option<StateOverride>,
+        overrides: EvmOverrides,
     ) -> impl Future<Output = Result<AccessListResult, Self::Error>> + Send
     where
         Self: Trace,
@@ -362,7 +387,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             let (evm_env, at) = self.evm_env_at(block_id).await?;
 
             self.spawn_blocking_io(move |this| {
-                this.create_access_list_with(evm_env, at, request, state_override)
+                this.create_access_list_with(evm_env, at, request, overrides)
             })
             .await
         }
@@ -375,15 +400,21 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         mut evm_env: EvmEnvFor<Self::Evm>,
         at: BlockId,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
-        state_override: Option<StateOverride>,
+        overrides: EvmOverrides,
     ) -> Result<AccessListResult, Self::Error>
     where
         Self: Trace,
     {
         let state = self.state_at_block_id(at)?;
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
+        if let Some(access_list) = request.as_ref().access_list() {
+            prefetch_access_list(&mut db, access_list).map_err(Self::Error::from_eth_err)?;
+        }
 
-        if let Some(state_overrides) = state_override {
+        if let Some(block_overrides) = overrides.block {
+            apply_block_overrides(*block_overrides, &mut db, &mut evm_env.block_env);
+        }
+        if let Some(state_overrides) = overrides.state {
             apply_state_overrides(state_overrides, &mut db).map_err(Self::Error::from_eth_err)?;
         }
 
@@ -466,6 +497,19 @@ pub trait Call:
     /// Returns the maximum number of blocks accepted for `eth_simulateV1`.
     fn max_simulate_blocks(&self) -> u64;
 
+    /// Returns the gas cap, tx fee cap, execution timeouts and state history depth of the RPC,
+    /// which can be adjusted while the node is running.
+    ///
+    /// Defaults to limits that can't be adjusted, with the [`Call::call_gas_limit`] as gas cap, no
+    /// timeouts, and the tx fee cap left to the transaction pool.
+    fn rpc_limits(&self) -> RpcLimitsHandle {
+        RpcLimitsHandle::new(RpcLimits {
+            gas_cap: self.call_gas_limit(),
+            tx_fee_cap: 0,
+            ..Default::default()
+        })
+    }
+
     /// Executes the closure with the state that corresponds to the given [`BlockId`].
     fn with_state_at_block<F, R>(&self, at: BlockId, f: F) -> Result<R, Self::Error>
     where
@@ -486,6 +530,7 @@ pub trait Call:
     where
         DB: Database<Error = ProviderError> + fmt::Debug,
     {
+        ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
         let mut evm = self.evm_config().evm_with_env(db, evm_env);
         let res = evm.transact(tx_env).map_err(Self::Error::from_evm_err)?;
 
@@ -505,6 +550,7 @@ pub trait Call:
         DB: Database<Error = ProviderError> + fmt::Debug,
         I: InspectorFor<Self::Evm, DB>,
     {
+        ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
         let mut evm = self.evm_config().evm_with_env_and_inspector(db, evm_env, inspector);
         let res = evm.transact(tx_env).map_err(Self::Error::from_evm_err)?;
 
@@ -512,19 +558,26 @@ pub trait Call:
     }
 
     /// Executes the call request at the given [`BlockId`].
+    ///
+    /// See [`Call::prepare_call_env`] for `disable_base_fee_check`.
     fn transact_call_at(
         &self,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         at: BlockId,
         overrides: EvmOverrides,
+        disable_base_fee_check: bool,
     ) -> impl Future<Output = Result<ResultAndState<HaltReasonFor<Self::Evm>>, Self::Error>> + Send
     where
         Self: LoadPendingBlock,
     {
         let this = self.clone();
-        self.spawn_with_call_at(request, at, overrides, move |db, evm_env, tx_env| {
-            this.transact(db, evm_env, tx_env)
-        })
+        self.spawn_with_call_at(
+            request,
+            at,
+            overrides,
+            disable_base_fee_check,
+            move |db, evm_env, tx_env| this.transact(db, evm_env, tx_env),
+        )
     }
 
     /// Executes the closure with the state that corresponds to the given [`BlockId`] on a new task
@@ -551,6 +604,8 @@ pub trait Call:
     ///
     /// This is primarily used by `eth_call`.
     ///
+    /// See [`Call::prepare_call_env`] for `disable_base_fee_check`.
+    ///
     /// # Blocking behaviour
     ///
     /// This assumes executing the call is relatively more expensive on IO than CPU because it
@@ -563,6 +618,7 @@ pub trait Call:
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         at: BlockId,
         overrides: EvmOverrides,
+        disable_base_fee_check: bool,
         f: F,
     ) -> impl Future<Output = Result<R, Self::Error>> + Send
     where
@@ -583,9 +639,37 @@ pub trait Call:
                 let state = this.state_at_block_id(at)?;
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
+                if let Some(access_list) = request.as_ref().access_list() {
+                    prefetch_access_list(&mut db, access_list)
+                        .map_err(Self::Error::from_eth_err)?;
+                }
 
-                let (evm_env, tx_env) =
-                    this.prepare_call_env(evm_env, request, &mut db, overrides)?;
+                let (evm_env, tx_env) = this.prepare_call_env(
+                    evm_env,
+                    request,
+                    &mut db,
+                    overrides,
+                    disable_base_fee_check,
+                )?;
+
+                // if the call is simulated in a block after the block of the state, e.g. in the
+                // pending block or with an overridden block number, the state lacks the system
+                // calls of the simulated block
+                if let BlockId::Hash(hash) = at {
+                    let state_block_number = this
+                        .provider()
+                        .block_number(hash.block_hash)
+                        .map_err(Self::Error::from_eth_err)?;
+                    if state_block_number
+                        .is_some_and(|number| evm_env.block_env.number > U256::from(number))
+                    {
+                        this.apply_simulated_block_system_calls(
+                            &mut db,
+                            &evm_env,
+                            hash.block_hash,
+                        )?;
+                    }
+                }
 
                 f(StateCacheDbRefMutWrapper(&mut db), evm_env, tx_env)
             })
@@ -593,6 +677,34 @@ pub trait Call:
         }
     }
 
+    /// Applies the EIP-2935 and EIP-4788 system calls of a block that is simulated on top of the
+    /// block with the given `parent_hash`, see [`apply_simulated_block_system_calls`].
+    ///
+    /// The beacon root of the simulated block is not known yet, so the zero hash is stored in the
+    /// beacon roots contract, same as for a locally built pending block.
+    fn apply_simulated_block_system_calls<DB>(
+        &self,
+        db: &mut DB,
+        evm_env: &EvmEnvFor<Self::Evm>,
+        parent_hash: B256,
+    ) -> Result<(), Self::Error>
+    where
+        DB: Database<Error = ProviderError> + DatabaseCommit + fmt::Debug,
+    {
+        let mut evm = self.evm_config().evm_with_env(db, evm_env.clone());
+        apply_simulated_block_system_calls(
+            self.provider().chain_spec(),
+            &mut evm,
+            parent_hash,
+            Some(B256::ZERO),
+        )
+        .map_err(|err| {
+            Self::Error::from_eth_err(EthApiError::EvmCustom(format!(
+                "failed to apply system calls {err}"
+            )))
+        })
+    }
+
     /// Retrieves the transaction if it exists and executes it.
     ///
     /// Before the transaction is executed, all previous transaction in the block are applied to the
@@ -675,6 +787,7 @@ pub trait Call:
                 break
             }
 
+            ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
             let tx_env = self.evm_config().tx_env(tx);
             evm.transact_commit(tx_env).map_err(Self::Error::from_evm_err)?;
             index += 1;
@@ -716,6 +829,9 @@ pub trait Call:
     ///  - `nonce` is set to `None`
     ///
     /// In addition, this changes the block's gas limit to the configured [`Self::call_gas_limit`].
+    ///
+    /// If `disable_base_fee_check` is set or the block overrides set the `baseFee`, the fee cap of
+    /// the request is not required to cover the base fee of the block.
     #[expect(clippy::type_complexity)]
     fn prepare_call_env<DB>(
         &self,
@@ -723,6 +839,7 @@ pub trait Call:
         mut request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         db: &mut DB,
         overrides: EvmOverrides,
+        disable_base_fee_check: bool,
     ) -> Result<(EvmEnvFor<Self::Evm>, TxEnvFor<Self::Evm>), Self::Error>
     where
         DB: Database + DatabaseCommit + OverrideBlockHashes,
@@ -751,6 +868,11 @@ pub trait Call:
         // set nonce to None so that the correct nonce is chosen by the EVM
         request.as_mut().take_nonce();
 
+        // A base fee override simulates the call under different fee market conditions, so the fee
+        // cap of the request is not validated against the overridden base fee either.
+        let disable_base_fee_check = disable_base_fee_check ||
+            overrides.block.as_ref().is_some_and(|overrides| overrides.base_fee.is_some());
+
         if let Some(block_overrides) = overrides.block {
             apply_block_overrides(*block_overrides, db, &mut evm_env.block_env);
         }
@@ -760,7 +882,20 @@ pub trait Call:
         }
 
         let request_gas = request.as_ref().gas_limit();
-        let mut tx_env = self.create_txn_env(&evm_env, request, &mut *db)?;
+        let max_fee_per_gas = request.as_ref().max_fee_per_gas();
+        let mut tx_env = match max_fee_per_gas {
+            Some(max_fee_per_gas)
+                if disable_base_fee_check &&
+                    max_fee_per_gas < evm_env.block_env.basefee as u128 =>
+            {
+                // validate the fees against a base fee the fee cap can pay, the effective gas
+                // price of the call is then the fee cap
+                let mut fee_env = evm_env.clone();
+                fee_env.block_env.basefee = max_fee_per_gas as u64;
+                self.create_txn_env(&fee_env, request, &mut *db)?
+            }
+            _ => self.create_txn_env(&evm_env, request, &mut *db)?,
+        };
 
         // lower the basefee to 0 to avoid breaking EVM invariants (basefee < gasprice): <https://github.com/ethereum/go-ethereum/blob/355228b011ef9a85ebc0f21e7196f892038d49f0/internal/ethapi/api.go#L700-L704>
         if tx_env.gas_price() == 0 {
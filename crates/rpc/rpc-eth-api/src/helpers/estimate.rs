@@ -13,7 +13,9 @@ use reth_evm::{ConfigureEvm, Database, Evm, EvmEnvFor, EvmFor, TransactionEnv, T
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_convert::{RpcConvert, RpcTxReq};
 use reth_rpc_eth_types::{
+    cache::db::prefetch_access_list,
     error::{api::FromEvmHalt, FromEvmError},
+    limits::ensure_within_deadline,
     EthApiError, RevertError, RpcInvalidTransactionError,
 };
 use reth_rpc_server_types::constants::gas_oracle::{CALL_STIPEND_GAS, ESTIMATE_GAS_ERROR_RATIO};
@@ -76,6 +78,9 @@ pub trait EstimateCall: Call {
 
         // Configure the evm env
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
+        if let Some(access_list) = request.as_ref().access_list() {
+            prefetch_access_list(&mut db, access_list).map_err(Self::Error::from_eth_err)?;
+        }
 
         // Apply any state overrides if specified.
         if let Some(state_override) = state_override {
@@ -234,6 +239,8 @@ pub trait EstimateCall: Call {
                 break
             };
 
+            ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
+
             let mut mid_tx_env = tx_env.clone();
             mid_tx_env.set_gas_limit(mid_gas_limit);
 
@@ -14,6 +14,7 @@ use reth_rpc_eth_types::{
     EthApiError, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
 };
 use reth_storage_api::{BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderHeader};
+use reth_transaction_pool::{BlobFeeProjection, TransactionPool, MAX_BLOB_FEE_PROJECTION_BLOCKS};
 use tracing::debug;
 
 /// Fee related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
@@ -41,6 +42,24 @@ pub trait EthFees:
         LoadFee::blob_base_fee(self)
     }
 
+    /// Returns a suggestion for the max fee per blob gas of blob transactions, so that they remain
+    /// includable over the given number of upcoming blocks.
+    fn suggested_max_fee_per_blob_gas(
+        &self,
+        blocks: u64,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+    {
+        async move {
+            LoadFee::blob_fee_projection(self, blocks)
+                .await?
+                .suggested_max_fee_per_blob_gas()
+                .ok_or(EthApiError::ExcessBlobGasNotSet.into())
+                .map(U256::from)
+        }
+    }
+
     /// Returns a suggestion for the priority fee (the tip)
     fn suggested_priority_fee(&self) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
@@ -372,6 +391,38 @@ where
         }
     }
 
+    /// Projects the blob base fee of the given number of upcoming blocks, based on the blob demand
+    /// in the pool.
+    ///
+    /// The number of blocks is capped at [`MAX_BLOB_FEE_PROJECTION_BLOCKS`].
+    fn blob_fee_projection(
+        &self,
+        blocks: u64,
+    ) -> impl Future<Output = Result<BlobFeeProjection, Self::Error>> + Send {
+        async move {
+            let header = self
+                .provider()
+                .latest_header()
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+            let (Some(excess_blob_gas), Some(blob_params)) = (
+                header.excess_blob_gas(),
+                self.provider().chain_spec().blob_params_at_timestamp(header.timestamp()),
+            ) else {
+                return Err(EthApiError::ExcessBlobGasNotSet.into())
+            };
+
+            Ok(BlobFeeProjection::new(
+                blob_params,
+                excess_blob_gas,
+                header.blob_gas_used().unwrap_or_default(),
+                self.pool().blob_gas_demand(),
+                blob_params.max_blob_gas_per_block(),
+                blocks.min(MAX_BLOB_FEE_PROJECTION_BLOCKS),
+            ))
+        }
+    }
+
     /// Returns a suggestion for the priority fee (the tip)
     fn suggested_priority_fee(&self) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
@@ -1,12 +1,13 @@
 //! Loads chain metadata.
 
 use alloy_primitives::{Address, U256, U64};
-use alloy_rpc_types_eth::{Stage, SyncInfo, SyncStatus};
+use alloy_rpc_types_eth::{Stage, SyncInfo};
 use futures::Future;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthereumHardforks};
 use reth_errors::{RethError, RethResult};
 use reth_network_api::NetworkInfo;
 use reth_rpc_convert::{RpcTxReq, RpcTypes};
+use reth_rpc_eth_types::{EthSyncInfo, EthSyncStatus, SyncProgressTracker};
 use reth_storage_api::{BlockNumReader, StageCheckpointReader, TransactionsProvider};
 
 use crate::{helpers::EthSigner, RpcNodeCore};
@@ -58,36 +59,50 @@ pub trait EthApiSpec:
         self.signers().read().iter().flat_map(|s| s.accounts()).collect()
     }
 
+    /// Returns the tracker of the reth specific sync progress, if it should be included in the
+    /// [`EthSyncStatus`].
+    fn sync_progress(&self) -> Option<&SyncProgressTracker> {
+        None
+    }
+
     /// Returns `true` if the network is undergoing sync.
     fn is_syncing(&self) -> bool {
         self.network().is_syncing()
     }
 
-    /// Returns the [`SyncStatus`] of the network
-    fn sync_status(&self) -> RethResult<SyncStatus> {
+    /// Returns the [`EthSyncStatus`] of the network.
+    ///
+    /// The reth specific sync progress is only included if a
+    /// [`sync_progress`](Self::sync_progress) tracker is configured.
+    fn sync_status(&self) -> RethResult<EthSyncStatus> {
         let status = if self.is_syncing() {
             let current_block = U256::from(
                 self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
             );
 
-            let stages = self
-                .provider()
-                .get_all_checkpoints()
-                .unwrap_or_default()
+            let checkpoints = self.provider().get_all_checkpoints().unwrap_or_default();
+            let progress = self
+                .sync_progress()
+                .map(|tracker| tracker.progress(&checkpoints))
+                .unwrap_or_default();
+            let stages = checkpoints
                 .into_iter()
                 .map(|(name, checkpoint)| Stage { name, block: checkpoint.block_number })
                 .collect();
 
-            SyncStatus::Info(Box::new(SyncInfo {
-                starting_block: self.starting_block(),
-                current_block,
-                highest_block: current_block,
-                warp_chunks_amount: None,
-                warp_chunks_processed: None,
-                stages: Some(stages),
+            EthSyncStatus::Info(Box::new(EthSyncInfo {
+                info: SyncInfo {
+                    starting_block: self.starting_block(),
+                    current_block,
+                    highest_block: current_block,
+                    warp_chunks_amount: None,
+                    warp_chunks_processed: None,
+                    stages: Some(stages),
+                },
+                progress,
             }))
         } else {
-            SyncStatus::None
+            EthSyncStatus::None
         };
         Ok(status)
     }
@@ -10,7 +10,10 @@ use alloy_serde::JsonStorageKey;
 use futures::Future;
 use reth_errors::RethError;
 use reth_evm::{ConfigureEvm, EvmEnvFor};
-use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionError};
+use reth_rpc_eth_types::{
+    utils::ensure_state_history_not_pruned, EthApiError, PendingBlockEnv,
+    RpcInvalidTransactionError,
+};
 use reth_storage_api::{
     BlockIdReader, BlockNumReader, StateProvider, StateProviderBox, StateProviderFactory,
 };
@@ -192,8 +195,34 @@ pub trait EthState: LoadState + SpawnBlocking {
 ///
 /// Behaviour shared by several `eth_` RPC methods, not exclusive to `eth_` state RPC methods.
 pub trait LoadState: EthApiTypes + RpcNodeCoreExt {
+    /// Returns the maximum number of blocks below the tip at which state can be queried on nodes
+    /// whose state history is pruned, `None` if unlimited.
+    fn max_state_history(&self) -> Option<u64> {
+        None
+    }
+
+    /// Ensures that the state at the given [`BlockId`] is available if the state history of the
+    /// node has been pruned.
+    ///
+    /// Otherwise, returns [`EthApiError::PrunedHistoryUnavailable`] with the earliest block whose
+    /// state can be queried, derived from the history prune checkpoints and
+    /// [`LoadState::max_state_history`]. Unknown blocks are ignored, they fail when the state is
+    /// loaded.
+    fn ensure_state_history(&self, at: BlockId) -> Result<(), Self::Error> {
+        let Some(max_state_history) = self.max_state_history() else { return Ok(()) };
+        let Some(block_number) =
+            self.provider().block_number_for_id(at).map_err(Self::Error::from_eth_err)?
+        else {
+            return Ok(())
+        };
+
+        ensure_state_history_not_pruned(self.provider(), block_number, max_state_history)
+            .map_err(Self::Error::from_eth_err)
+    }
+
     /// Returns the state at the given block number
     fn state_at_hash(&self, block_hash: B256) -> Result<StateProviderBox, Self::Error> {
+        self.ensure_state_history(block_hash.into())?;
         self.provider().history_by_block_hash(block_hash).map_err(Self::Error::from_eth_err)
     }
 
@@ -202,6 +231,7 @@ pub trait LoadState: EthApiTypes + RpcNodeCoreExt {
     /// Note: if not [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this
     /// will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
     fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
+        self.ensure_state_history(at)?;
         self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
     }
 
@@ -1,7 +1,7 @@
 //! Loads a pending block from database. Helper trait for `eth_` call and trace RPC methods.
 
 use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction};
-use crate::FromEvmError;
+use crate::{FromEthApiError, FromEvmError};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use alloy_rpc_types_eth::{BlockId, TransactionInfo};
@@ -9,17 +9,19 @@ use futures::Future;
 use reth_chainspec::ChainSpecProvider;
 use reth_errors::ProviderError;
 use reth_evm::{
-    evm::EvmFactoryExt, system_calls::SystemCaller, tracing::TracingCtx, ConfigureEvm, Database,
-    Evm, EvmEnvFor, EvmFor, HaltReasonFor, InspectorFor, TxEnvFor,
+    evm::EvmFactoryExt, execute::BlockExecutionOutput, system_calls::SystemCaller,
+    tracing::TracingCtx, ConfigureEvm, Database, Evm, EvmEnvFor, EvmFor, HaltReasonFor,
+    InspectorFor, ReexecuteBlock, TxEnvFor,
 };
 use reth_primitives_traits::{BlockBody, Recovered, RecoveredBlock, SignedTransaction};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    limits::ensure_within_deadline,
     EthApiError,
 };
-use reth_storage_api::{ProviderBlock, ProviderTx};
-use revm::{context_interface::result::ResultAndState, DatabaseCommit};
+use reth_storage_api::{ProviderBlock, ProviderReceipt, ProviderTx, StateProviderBox};
+use revm::{context_interface::result::ResultAndState, database::State, DatabaseCommit};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use std::sync::Arc;
 
@@ -38,6 +40,7 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
         DB: Database<Error = ProviderError>,
         I: InspectorFor<Self::Evm, DB>,
     {
+        ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
         let mut evm = self.evm_config().evm_with_env_and_inspector(db, evm_env, inspector);
         evm.transact(tx_env).map_err(Self::Error::from_evm_err)
     }
@@ -334,6 +337,7 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
                         };
                         idx += 1;
 
+                        ensure_within_deadline().map_err(Self::Error::from_eth_err)?;
                         f(tx_info, ctx)
                     })
                     .collect::<Result<_, _>>()?;
@@ -422,6 +426,46 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
         self.trace_block_until_with_inspector(block_id, block, None, insp_setup, f)
     }
 
+    /// Re-executes the block on top of the state of its parent block with the given inspector.
+    ///
+    /// Unlike [`Self::trace_block_inspector`], this executes the block as a whole, exactly like
+    /// regular block execution does, so the inspector also observes the pre and post execution
+    /// system calls. See [`ReexecuteBlock`].
+    ///
+    /// Returns the receipts and state changes of the block together with the inspector, or `None`
+    /// if the block does not exist.
+    #[expect(clippy::type_complexity)]
+    fn reexecute_block_with_inspector<I>(
+        &self,
+        block_id: BlockId,
+        mut inspector: I,
+    ) -> impl Future<
+        Output = Result<
+            Option<(BlockExecutionOutput<ProviderReceipt<Self::Provider>>, I)>,
+            Self::Error,
+        >,
+    > + Send
+    where
+        Self: LoadBlock,
+        I: for<'a> InspectorFor<Self::Evm, &'a mut State<StateProviderDatabase<StateProviderBox>>>
+            + Send
+            + 'static,
+    {
+        async move {
+            let Some(block) = self.recovered_block(block_id).await? else { return Ok(None) };
+
+            self.spawn_tracing(move |this| {
+                let state = this.state_at_block_id(block.parent_hash().into())?;
+                let output = this
+                    .evm_config()
+                    .reexecute_block(StateProviderDatabase::new(state), &block, &mut inspector)
+                    .map_err(|err| Self::Error::from_eth_err(EthApiError::from(err)))?;
+                Ok(Some((output, inspector)))
+            })
+            .await
+        }
+    }
+
     /// Applies chain-specific state transitions required before executing a block.
     ///
     /// Note: This should only be called when tracing an entire block vs individual transactions.
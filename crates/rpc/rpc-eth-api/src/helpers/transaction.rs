@@ -1,7 +1,9 @@
 //! Database access for `eth_` transaction RPC methods. Loads transaction and receipt data w.r.t.
 //! network.
 
-use super::{EthApiSpec, EthSigner, LoadBlock, LoadReceipt, LoadState, SpawnBlocking};
+use super::{
+    Call, EthApiSpec, EthSigner, LoadBlock, LoadPendingBlock, LoadReceipt, LoadState, SpawnBlocking,
+};
 use crate::{
     helpers::{estimate::EstimateCall, spec::SignersForRpc},
     FromEthApiError, FullEthApiTypes, IntoEthApiError, RpcNodeCore, RpcNodeCoreExt, RpcReceipt,
@@ -14,16 +16,22 @@ use alloy_consensus::{
 use alloy_dyn_abi::TypedData;
 use alloy_eips::{eip2718::Encodable2718, BlockId};
 use alloy_network::TransactionBuilder;
-use alloy_primitives::{Address, Bytes, TxHash, B256};
-use alloy_rpc_types_eth::{BlockNumberOrTag, TransactionInfo};
+use alloy_primitives::{Address, Bytes, StorageKey, TxHash, B256, U256};
+use alloy_rpc_types_eth::{
+    erc4337::{AccountStorage, TransactionConditional},
+    BlockNumberOrTag, TransactionInfo,
+};
 use futures::{Future, StreamExt};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::BlockBody;
 use reth_primitives_traits::{RecoveredBlock, SignedTransaction};
 use reth_rpc_convert::{transaction::RpcConvert, RpcTxReq};
 use reth_rpc_eth_types::{
-    utils::binary_search, EthApiError, EthApiError::TransactionConfirmationTimeout, SignError,
-    TransactionSource,
+    error::TransactionConditionalError,
+    utils::{binary_search, ensure_receipts_not_pruned},
+    EthApiError,
+    EthApiError::TransactionConfirmationTimeout,
+    SignError, TransactionSource,
 };
 use reth_storage_api::{
     BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx, ReceiptProvider,
@@ -34,6 +42,10 @@ use reth_transaction_pool::{
 };
 use std::sync::Arc;
 
+/// Maximum number of state lookups required to check the preconditions of a conditional
+/// transaction, see [`TransactionConditional::cost`].
+pub const MAX_TRANSACTION_CONDITIONAL_COST: u64 = 5000;
+
 /// Transaction related functions for the [`EthApiServer`](crate::EthApiServer) trait in
 /// the `eth_` namespace.
 ///
@@ -111,6 +123,102 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         }
     }
 
+    /// Decodes and recovers the transaction and submits it to the pool, if the preconditions of
+    /// the given [`TransactionConditional`] are met by the pending block.
+    ///
+    /// Returns the hash of the transaction.
+    fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        condition: TransactionConditional,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock + LoadState,
+    {
+        async move {
+            self.validate_transaction_conditional(condition).await?;
+            self.send_raw_transaction(tx).await
+        }
+    }
+
+    /// Checks the preconditions of the [`TransactionConditional`] against the environment and the
+    /// state of the pending block.
+    ///
+    /// Returns [`TransactionConditionalError`] if they're not met.
+    fn validate_transaction_conditional(
+        &self,
+        condition: TransactionConditional,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send
+    where
+        Self: LoadPendingBlock + LoadState,
+    {
+        async move {
+            let cost = condition.cost();
+            if cost > MAX_TRANSACTION_CONDITIONAL_COST {
+                return Err(Self::Error::from_eth_err(TransactionConditionalError::CostExceeded {
+                    cost,
+                    max: MAX_TRANSACTION_CONDITIONAL_COST,
+                }))
+            }
+
+            self.spawn_blocking_io(move |this| {
+                let block_env = this.pending_block_env_and_cfg()?.evm_env.block_env;
+
+                let number = block_env.number.saturating_to();
+                if !condition.matches_block_number(number) {
+                    return Err(Self::Error::from_eth_err(
+                        TransactionConditionalError::BlockNumberOutOfRange(number),
+                    ))
+                }
+                let timestamp = block_env.timestamp.saturating_to();
+                if !condition.matches_timestamp(timestamp) {
+                    return Err(Self::Error::from_eth_err(
+                        TransactionConditionalError::TimestampOutOfRange(timestamp),
+                    ))
+                }
+
+                if condition.known_accounts.is_empty() {
+                    return Ok(())
+                }
+
+                let state = this.state_at_block_id(BlockId::pending())?;
+                for (address, storage) in &condition.known_accounts {
+                    match storage {
+                        AccountStorage::Slots(slots) => {
+                            for (slot, expected) in slots {
+                                let value = state
+                                    .storage(*address, StorageKey::from(*slot))
+                                    .map_err(Self::Error::from_eth_err)?
+                                    .unwrap_or_default();
+                                if value != U256::from_be_bytes(expected.0) {
+                                    return Err(Self::Error::from_eth_err(
+                                        TransactionConditionalError::StorageValueMismatch {
+                                            address: *address,
+                                            slot: *slot,
+                                        },
+                                    ))
+                                }
+                            }
+                        }
+                        AccountStorage::RootHash(expected) => {
+                            let root = state
+                                .storage_root(*address, Default::default())
+                                .map_err(Self::Error::from_eth_err)?;
+                            if root != *expected {
+                                return Err(Self::Error::from_eth_err(
+                                    TransactionConditionalError::StorageRootMismatch(*address),
+                                ))
+                            }
+                        }
+                    }
+                }
+
+                Ok(())
+            })
+            .await
+        }
+    }
+
     /// Returns the transaction by hash.
     ///
     /// Checks the pool and state.
@@ -237,7 +345,12 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
 
             let receipt = match provider.receipt_by_hash(hash).map_err(Self::Error::from_eth_err)? {
                 Some(recpt) => recpt,
-                None => return Ok(None),
+                None => {
+                    // the receipt might be missing because it's been pruned
+                    ensure_receipts_not_pruned(provider, meta.block_number)
+                        .map_err(Self::Error::from_eth_err)?;
+                    return Ok(None)
+                }
             };
 
             Ok(Some((tx, meta, receipt)))
@@ -411,6 +524,9 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
             request.as_mut().set_gas_limit(gas_limit.to());
 
             let transaction = self.sign_request(&from, request).await?.with_signer(from);
+            self.rpc_limits()
+                .ensure_tx_fee(transaction.max_fee_per_gas(), transaction.gas_limit())
+                .map_err(Self::Error::from_eth_err)?;
 
             let pool_transaction =
                 <<Self as RpcNodeCore>::Pool as TransactionPool>::Transaction::try_from_consensus(
@@ -0,0 +1,68 @@
+//! Types for block queries with configurable response contents.
+
+use alloy_eips::{eip7685::Requests, eip7840::BlobParams};
+use serde::{Deserialize, Serialize};
+
+/// Options for `eth_getBlockByNumberWithOptions` that control which, potentially expensive, sub
+/// objects are included in the block response.
+///
+/// Indexers that only need headers and transaction hashes can use this to reduce response sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BlockQueryOptions {
+    /// Whether to include full transaction objects instead of transaction hashes.
+    pub full_transactions: bool,
+    /// Whether to include the withdrawals of the block.
+    pub withdrawals: bool,
+    /// Whether to include the execution requests of the block, the preimage of its
+    /// `requestsHash`.
+    pub requests: bool,
+    /// Whether to include the blob schedule parameters that are active for the block.
+    pub blob_schedule: bool,
+}
+
+impl Default for BlockQueryOptions {
+    fn default() -> Self {
+        Self { full_transactions: false, withdrawals: true, requests: false, blob_schedule: false }
+    }
+}
+
+/// A block response with additional block metadata requested via [`BlockQueryOptions`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithMetadata<B> {
+    /// The block.
+    #[serde(flatten)]
+    pub block: B,
+    /// The execution requests of the block, if requested and stored for the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests: Option<Requests>,
+    /// The blob schedule parameters active for the block, if requested and the block is
+    /// post-Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_schedule: Option<BlobParams>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_partial_options() {
+        let options: BlockQueryOptions =
+            serde_json::from_str(r#"{"withdrawals":false,"requests":true,"blobSchedule":true}"#)
+                .unwrap();
+        assert_eq!(
+            options,
+            BlockQueryOptions {
+                full_transactions: false,
+                withdrawals: false,
+                requests: true,
+                blob_schedule: true
+            }
+        );
+
+        let options: BlockQueryOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options, BlockQueryOptions::default());
+    }
+}
@@ -3,12 +3,13 @@
 use std::time::Duration;
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RpcTimeouts,
+    RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
     DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS,
+    DEFAULT_PROOF_PERMITS, DEFAULT_TX_FEE_CAP_WEI,
 };
 use serde::{Deserialize, Serialize};
 
@@ -64,6 +65,9 @@ pub struct EthConfig {
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of blocks into the past for generating state proofs.
     pub eth_proof_window: u64,
+    /// The maximum number of blocks into the past at which state can be queried, `None` if
+    /// unlimited.
+    pub max_state_history: Option<u64>,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
     /// Maximum number of blocks for `trace_filter` requests.
@@ -72,10 +76,19 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum estimated cost of an `eth_getLogs` query, see
+    /// [`EthFilterConfig::max_log_query_cost`].
+    pub max_log_query_cost: u64,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Maximum fee in wei of transactions submitted via the RPC, `0` disables the cap.
+    ///
+    /// Defaults to [`DEFAULT_TX_FEE_CAP_WEI`]
+    pub rpc_tx_fee_cap: u128,
+    /// Maximum execution time of EVM backed requests per namespace.
+    pub rpc_timeouts: RpcTimeouts,
     /// Max number of blocks for `eth_simulateV1`.
     pub rpc_max_simulate_blocks: u64,
     ///
@@ -89,6 +102,8 @@ pub struct EthConfig {
     pub max_batch_size: usize,
     /// Controls how pending blocks are built when requested via RPC methods
     pub pending_block_kind: PendingBlockKind,
+    /// Whether to include the reth specific sync progress in `eth_syncing` responses.
+    pub sync_progress: bool,
 }
 
 impl EthConfig {
@@ -97,6 +112,7 @@ impl EthConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_log_query_cost(self.max_log_query_cost)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
 }
@@ -107,17 +123,22 @@ impl Default for EthConfig {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_state_history: None,
             max_tracing_requests: default_max_tracing_requests(),
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_log_query_cost: u64::MAX,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_tx_fee_cap: DEFAULT_TX_FEE_CAP_WEI,
+            rpc_timeouts: RpcTimeouts::default(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
             max_batch_size: 1,
             pending_block_kind: PendingBlockKind::Full,
+            sync_progress: false,
         }
     }
 }
@@ -159,12 +180,30 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum estimated cost of an `eth_getLogs` query
+    pub const fn max_log_query_cost(mut self, max_cost: u64) -> Self {
+        self.max_log_query_cost = max_cost;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
 
+    /// Configures the maximum fee in wei of transactions submitted via the RPC
+    pub const fn rpc_tx_fee_cap(mut self, rpc_tx_fee_cap: u128) -> Self {
+        self.rpc_tx_fee_cap = rpc_tx_fee_cap;
+        self
+    }
+
+    /// Configures the maximum execution time of EVM backed requests per namespace
+    pub const fn rpc_timeouts(mut self, rpc_timeouts: RpcTimeouts) -> Self {
+        self.rpc_timeouts = rpc_timeouts;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_max_simulate_blocks(mut self, max_blocks: u64) -> Self {
         self.rpc_max_simulate_blocks = max_blocks;
@@ -177,6 +216,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of blocks into the past at which state can be queried.
+    pub const fn max_state_history(mut self, max_state_history: Option<u64>) -> Self {
+        self.max_state_history = max_state_history;
+        self
+    }
+
     /// Configures the number of getproof requests
     pub const fn proof_permits(mut self, permits: usize) -> Self {
         self.proof_permits = permits;
@@ -194,6 +239,12 @@ impl EthConfig {
         self.pending_block_kind = pending_block_kind;
         self
     }
+
+    /// Configures whether to include the reth specific sync progress in `eth_syncing` responses
+    pub const fn sync_progress(mut self, sync_progress: bool) -> Self {
+        self.sync_progress = sync_progress;
+        self
+    }
 }
 
 /// Config for the filter
@@ -207,6 +258,15 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_logs_per_response: Option<usize>,
+    /// Maximum estimated cost of an `eth_getLogs` query.
+    ///
+    /// The cost is the estimated number of blocks in the queried range whose bloom filter matches
+    /// the filter, i.e. the number of blocks whose receipts must be scanned. It's estimated from a
+    /// sample of the headers in the range before scanning it, queries over budget are rejected
+    /// with a suggested narrower range.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_log_query_cost: Option<u64>,
     /// How long a filter remains valid after the last poll.
     ///
     /// A filter is considered stale if it has not been polled for longer than this duration and
@@ -228,6 +288,12 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum estimated cost of an `eth_getLogs` query.
+    pub const fn max_log_query_cost(mut self, cost: u64) -> Self {
+        self.max_log_query_cost = Some(cost);
+        self
+    }
+
     /// Sets how long a filter remains valid after the last poll before it will be removed.
     pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
         self.stale_filter_ttl = duration;
@@ -240,6 +306,7 @@ impl Default for EthFilterConfig {
         Self {
             max_blocks_per_filter: None,
             max_logs_per_response: None,
+            max_log_query_cost: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
         }
@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_HEADER_CACHE_SIZE_BYTES_MB,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -23,19 +24,72 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_headers: u32,
+    /// Max memory of the cached blocks in bytes.
+    ///
+    /// Default is 500MB.
+    pub max_blocks_bytes: usize,
+    /// Max memory of the cached receipts in bytes.
+    ///
+    /// Default is 500MB.
+    pub max_receipts_bytes: usize,
+    /// Max memory of the cached headers in bytes.
+    ///
+    /// Default is 1MB.
+    pub max_headers_bytes: usize,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
 }
 
+impl EthStateCacheConfig {
+    /// Returns the size limits of the cache tiers.
+    pub const fn limits(&self) -> EthStateCacheLimits {
+        EthStateCacheLimits {
+            blocks: CacheLimits { max_len: self.max_blocks, max_bytes: self.max_blocks_bytes },
+            receipts: CacheLimits {
+                max_len: self.max_receipts,
+                max_bytes: self.max_receipts_bytes,
+            },
+            headers: CacheLimits { max_len: self.max_headers, max_bytes: self.max_headers_bytes },
+        }
+    }
+}
+
 impl Default for EthStateCacheConfig {
     fn default() -> Self {
         Self {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
+            max_blocks_bytes: DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            max_receipts_bytes: DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            max_headers_bytes: DEFAULT_HEADER_CACHE_SIZE_BYTES_MB * 1024 * 1024,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
 }
+
+/// Size limits of a single tier of the [`EthStateCache`](super::EthStateCache).
+///
+/// The least recently used entries are evicted once either of the limits is exceeded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheLimits {
+    /// Max number of entries.
+    pub max_len: u32,
+    /// Max memory of the entries in bytes.
+    pub max_bytes: usize,
+}
+
+/// Size limits of the tiers of the [`EthStateCache`](super::EthStateCache).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStateCacheLimits {
+    /// Limits of the block cache.
+    pub blocks: CacheLimits,
+    /// Limits of the receipts cache.
+    pub receipts: CacheLimits,
+    /// Limits of the header cache.
+    pub headers: CacheLimits,
+}
@@ -2,13 +2,14 @@
 //! <https://github.com/rust-lang/rust/issues/100013> in default implementation of
 //! `reth_rpc_eth_api::helpers::Call`.
 
+use alloy_eips::eip2930::AccessList;
 use alloy_primitives::{Address, B256, U256};
 use reth_errors::ProviderResult;
 use reth_revm::{database::StateProviderDatabase, DatabaseRef};
 use reth_storage_api::{BytecodeReader, HashedPostStateProvider, StateProvider};
 use reth_trie::{HashedStorage, MultiProofTargets};
 use revm::{
-    database::{BundleState, CacheDB},
+    database::{AccountState, BundleState, CacheDB, DbAccount},
     primitives::HashMap,
     state::{AccountInfo, Bytecode},
     Database, DatabaseCommit,
@@ -17,6 +18,53 @@ use revm::{
 /// Helper alias type for the state's [`CacheDB`]
 pub type StateCacheDb<'a> = CacheDB<StateProviderDatabase<StateProviderTraitObjWrapper<'a>>>;
 
+/// Loads the accounts and storage slots of the given [`AccessList`] into the cache of the
+/// [`CacheDB`].
+///
+/// This reads all entries that are not cached yet with [`StateProvider::basic_accounts`] and
+/// [`StateProvider::storage_batch`], instead of loading them one by one during execution.
+pub fn prefetch_access_list<S: StateProvider>(
+    db: &mut CacheDB<StateProviderDatabase<S>>,
+    access_list: &AccessList,
+) -> ProviderResult<()> {
+    let mut addresses = access_list
+        .iter()
+        .map(|item| item.address)
+        .filter(|address| !db.cache.accounts.contains_key(address))
+        .collect::<Vec<_>>();
+    addresses.sort_unstable();
+    addresses.dedup();
+    let accounts = db.db.basic_accounts(&addresses)?;
+    for (address, account) in addresses.into_iter().zip(accounts) {
+        let account = account
+            .map(|account| DbAccount { info: account.into(), ..Default::default() })
+            .unwrap_or_else(DbAccount::new_not_existing);
+        db.cache.accounts.insert(address, account);
+    }
+
+    let mut slots = access_list
+        .iter()
+        .flat_map(|item| item.storage_keys.iter().map(|key| (item.address, *key)))
+        .filter(|(address, key)| {
+            // storage of non existing or cleared accounts is not read from the database
+            db.cache.accounts.get(address).is_some_and(|account| {
+                matches!(account.account_state, AccountState::None | AccountState::Touched) &&
+                    !account.storage.contains_key(&(*key).into())
+            })
+        })
+        .collect::<Vec<_>>();
+    slots.sort_unstable();
+    slots.dedup();
+    let values = db.db.storage_batch(&slots)?;
+    for ((address, key), value) in slots.into_iter().zip(values) {
+        if let Some(account) = db.cache.accounts.get_mut(&address) {
+            account.storage.insert(key.into(), value.unwrap_or_default());
+        }
+    }
+
+    Ok(())
+}
+
 /// Hack to get around 'higher-ranked lifetime error', see
 /// <https://github.com/rust-lang/rust/issues/100013>
 #[expect(missing_debug_implementations)]
@@ -105,6 +153,15 @@ impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
     ) -> reth_errors::ProviderResult<Vec<alloy_primitives::Bytes>> {
         self.0.witness(input, target)
     }
+
+    fn witness_into(
+        &self,
+        input: reth_trie::TrieInput,
+        target: reth_trie::HashedPostState,
+        witness: &mut dyn reth_trie::WitnessSink,
+    ) -> ProviderResult<()> {
+        self.0.witness_into(input, target, witness)
+    }
 }
 
 impl reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'_> {
@@ -155,6 +212,20 @@ impl StateProvider for StateProviderTraitObjWrapper<'_> {
         self.0.storage(account, storage_key)
     }
 
+    fn basic_accounts(
+        &self,
+        addresses: &[Address],
+    ) -> reth_errors::ProviderResult<Vec<Option<reth_primitives_traits::Account>>> {
+        self.0.basic_accounts(addresses)
+    }
+
+    fn storage_batch(
+        &self,
+        slots: &[(Address, alloy_primitives::StorageKey)],
+    ) -> reth_errors::ProviderResult<Vec<Option<alloy_primitives::StorageValue>>> {
+        self.0.storage_batch(slots)
+    }
+
     fn account_code(
         &self,
         addr: &Address,
@@ -234,3 +305,39 @@ impl DatabaseCommit for StateCacheDbRefMutWrapper<'_, '_> {
         self.0.commit(changes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::AccessListItem;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn prefetched_access_list_is_served_from_cache() {
+        let address = Address::with_last_byte(1);
+        let listed = B256::with_last_byte(1);
+        let unlisted = B256::with_last_byte(2);
+
+        let provider = MockEthProvider::default();
+        let account = ExtendedAccount::new(1, U256::from(10))
+            .extend_storage([(listed, U256::from(1)), (unlisted, U256::from(2))]);
+        provider.add_account(address, account);
+
+        let mut db = CacheDB::new(StateProviderDatabase::new(provider.clone()));
+        let access_list = AccessList(vec![AccessListItem { address, storage_keys: vec![listed] }]);
+        prefetch_access_list(&mut db, &access_list).unwrap();
+
+        let account = &db.cache.accounts[&address];
+        assert_eq!(account.info.nonce, 1);
+        assert_eq!(account.storage.get(&listed.into()), Some(&U256::from(1)));
+        assert!(!account.storage.contains_key(&unlisted.into()));
+
+        // later changes to the state provider are not observed for prefetched slots
+        let account = ExtendedAccount::new(2, U256::from(20))
+            .extend_storage([(listed, U256::from(3)), (unlisted, U256::from(4))]);
+        provider.add_account(address, account);
+
+        assert_eq!(db.storage(address, listed.into()).unwrap(), U256::from(1));
+        assert_eq!(db.storage(address, unlisted.into()).unwrap(), U256::from(4));
+    }
+}
@@ -1,7 +1,9 @@
 //! Tracks state of RPC cache.
 
+use super::config::CacheLimits;
 use metrics::Counter;
 use reth_metrics::{metrics::Gauge, Metrics};
+use serde::{Deserialize, Serialize};
 
 #[derive(Metrics)]
 #[metrics(scope = "rpc.eth_cache")]
@@ -14,6 +16,51 @@ pub(crate) struct CacheMetrics {
     pub(crate) hits_total: Counter,
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
+    /// The ratio of lookups that were served from the cache.
+    pub(crate) hit_rate: Gauge,
+    /// The number of entities evicted to stay within the limits of the cache.
+    pub(crate) evictions_total: Counter,
     /// The memory usage of the cache.
     pub(crate) memory_usage: Gauge,
+    /// The max memory usage of the cache.
+    pub(crate) memory_limit: Gauge,
+}
+
+/// Usage statistics of a single tier of the [`EthStateCache`](super::EthStateCache).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    /// The number of cached entries.
+    pub len: usize,
+    /// The tracked memory of the cached entries in bytes.
+    pub memory_usage: usize,
+    /// The number of lookups that were served from the cache.
+    pub hits: u64,
+    /// The number of lookups that missed the cache.
+    pub misses: u64,
+    /// The limits of the cache.
+    pub limits: CacheLimits,
+}
+
+impl CacheStats {
+    /// Returns the ratio of lookups that were served from the cache, or 0 if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+/// Usage statistics of the tiers of the [`EthStateCache`](super::EthStateCache).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStateCacheStats {
+    /// Statistics of the block cache.
+    pub blocks: CacheStats,
+    /// Statistics of the receipts cache.
+    pub receipts: CacheStats,
+    /// Statistics of the header cache.
+    pub headers: CacheStats,
 }
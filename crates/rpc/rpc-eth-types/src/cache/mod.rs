@@ -1,6 +1,6 @@
 //! Async caching support for eth RPC
 
-use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use super::{EthStateCacheConfig, EthStateCacheLimits, EthStateCacheStats, MultiConsumerLruCache};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
@@ -50,6 +50,12 @@ type HeaderResponseSender<H> = oneshot::Sender<ProviderResult<H>>;
 /// The type that can send the response with a chain of cached blocks
 type CachedParentBlocksResponseSender<B> = oneshot::Sender<Vec<Arc<RecoveredBlock<B>>>>;
 
+/// The type that can send the response with the usage statistics of the cache
+type StatsResponseSender = oneshot::Sender<EthStateCacheStats>;
+
+/// The type that can send the response with the previous limits of the cache
+type LimitsResponseSender = oneshot::Sender<EthStateCacheLimits>;
+
 type BlockLruCache<B, L> = MultiConsumerLruCache<
     B256,
     Arc<RecoveredBlock<B>>,
@@ -85,9 +91,7 @@ impl<N: NodePrimitives> EthStateCache<N> {
     fn create<Provider, Tasks>(
         provider: Provider,
         action_task_spawner: Tasks,
-        max_blocks: u32,
-        max_receipts: u32,
-        max_headers: u32,
+        limits: EthStateCacheLimits,
         max_concurrent_db_operations: usize,
     ) -> (Self, EthStateCacheService<Provider, Tasks>)
     where
@@ -96,9 +100,9 @@ impl<N: NodePrimitives> EthStateCache<N> {
         let (to_service, rx) = unbounded_channel();
         let service = EthStateCacheService {
             provider,
-            full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
-            receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
-            headers_cache: HeaderLruCache::new(max_headers, "headers"),
+            full_block_cache: BlockLruCache::new(limits.blocks, "blocks"),
+            receipts_cache: ReceiptsLruCache::new(limits.receipts, "receipts"),
+            headers_cache: HeaderLruCache::new(limits.headers, "headers"),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
@@ -122,7 +126,7 @@ impl<N: NodePrimitives> EthStateCache<N> {
     /// Creates a new async LRU backed cache service task and spawns it to a new task via the given
     /// spawner.
     ///
-    /// The cache is memory limited by the given max bytes values.
+    /// Every tier of the cache is limited by the configured number of entries and max bytes.
     pub fn spawn_with<Provider, Tasks>(
         provider: Provider,
         config: EthStateCacheConfig,
@@ -132,19 +136,11 @@ impl<N: NodePrimitives> EthStateCache<N> {
         Provider: BlockReader<Block = N::Block, Receipt = N::Receipt> + Clone + Unpin + 'static,
         Tasks: TaskSpawner + Clone + 'static,
     {
-        let EthStateCacheConfig {
-            max_blocks,
-            max_receipts,
-            max_headers,
-            max_concurrent_db_requests,
-        } = config;
         let (this, service) = Self::create(
             provider,
             executor.clone(),
-            max_blocks,
-            max_receipts,
-            max_headers,
-            max_concurrent_db_requests,
+            config.limits(),
+            config.max_concurrent_db_requests,
         );
         executor.spawn_critical("eth state cache", Box::pin(service));
         this
@@ -265,6 +261,25 @@ impl<N: NodePrimitives> EthStateCache<N> {
             Some(blocks)
         }
     }
+
+    /// Returns the usage statistics and limits of the cache tiers.
+    pub async fn stats(&self) -> ProviderResult<EthStateCacheStats> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetStats { response_tx });
+        Ok(rx.await.map_err(|_| CacheServiceUnavailable)?)
+    }
+
+    /// Updates the size limits of the cache tiers and returns the previous ones.
+    ///
+    /// The least recently used entries that exceed the new limits are evicted right away.
+    pub async fn set_limits(
+        &self,
+        limits: EthStateCacheLimits,
+    ) -> ProviderResult<EthStateCacheLimits> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::SetLimits { limits, response_tx });
+        Ok(rx.await.map_err(|_| CacheServiceUnavailable)?)
+    }
 }
 /// Thrown when the cache service task dropped.
 #[derive(Debug, thiserror::Error)]
@@ -423,6 +438,22 @@ where
         self.headers_cache.shrink_to(min_capacity);
     }
 
+    fn stats(&self) -> EthStateCacheStats {
+        EthStateCacheStats {
+            blocks: self.full_block_cache.stats(),
+            receipts: self.receipts_cache.stats(),
+            headers: self.headers_cache.stats(),
+        }
+    }
+
+    fn set_limits(&mut self, limits: EthStateCacheLimits) -> EthStateCacheLimits {
+        EthStateCacheLimits {
+            blocks: self.full_block_cache.set_limits(limits.blocks),
+            receipts: self.receipts_cache.set_limits(limits.receipts),
+            headers: self.headers_cache.set_limits(limits.headers),
+        }
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
@@ -624,6 +655,12 @@ where
 
                             let _ = response_tx.send(blocks);
                         }
+                        CacheAction::GetStats { response_tx } => {
+                            let _ = response_tx.send(this.stats());
+                        }
+                        CacheAction::SetLimits { limits, response_tx } => {
+                            let _ = response_tx.send(this.set_limits(limits));
+                        }
                     };
                     this.update_cached_metrics();
                 }
@@ -677,6 +714,13 @@ enum CacheAction<B: Block, R> {
         max_blocks: usize,
         response_tx: CachedParentBlocksResponseSender<B>,
     },
+    GetStats {
+        response_tx: StatsResponseSender,
+    },
+    SetLimits {
+        limits: EthStateCacheLimits,
+        response_tx: LimitsResponseSender,
+    },
 }
 
 struct BlockReceipts<R> {
@@ -1,7 +1,10 @@
 //! Metered cache, which also provides storage for senders in order to queue queries that result in
 //! a cache miss.
 
-use super::metrics::CacheMetrics;
+use super::{
+    config::CacheLimits,
+    metrics::{CacheMetrics, CacheStats},
+};
 use reth_primitives_traits::InMemorySize;
 use schnellru::{ByLength, Limiter, LruMap};
 use std::{
@@ -11,6 +14,8 @@ use std::{
 };
 
 /// A multi-consumer LRU cache.
+///
+/// Besides the limiter of the LRU map, the cache is bounded by the tracked memory of its entries.
 pub struct MultiConsumerLruCache<K, V, L, S>
 where
     K: Hash + Eq,
@@ -24,6 +29,12 @@ where
     metrics: CacheMetrics,
     // Tracked heap usage
     memory_usage: usize,
+    /// The limits of the cache.
+    limits: CacheLimits,
+    /// The number of lookups that were served from the cache.
+    hits: u64,
+    /// The number of lookups that missed the cache.
+    misses: u64,
 }
 
 impl<K, V, L, S> Debug for MultiConsumerLruCache<K, V, L, S>
@@ -37,6 +48,7 @@ where
             .field("cache_memory_usage", &self.cache.memory_usage())
             .field("queued_length", &self.queued.len())
             .field("memory_usage", &self.memory_usage)
+            .field("limits", &self.limits)
             .finish()
     }
 }
@@ -81,8 +93,10 @@ where
     pub fn get(&mut self, key: &K) -> Option<&mut V> {
         let entry = self.cache.get(key);
         if entry.is_some() {
+            self.hits += 1;
             self.metrics.hits_total.increment(1);
         } else {
+            self.misses += 1;
             self.metrics.misses_total.increment(1);
         }
         entry
@@ -90,7 +104,10 @@ where
 
     /// Inserts a new element into the map.
     ///
-    /// Can fail if the element is rejected by the limiter or if we fail to grow an empty map.
+    /// The oldest elements are evicted until the new element fits into the limits of the cache.
+    ///
+    /// Can fail if the element alone exceeds the memory limit, if the element is rejected by the
+    /// limiter or if we fail to grow an empty map.
     ///
     /// See [`Schnellru::insert`](LruMap::insert) for more info.
     pub fn insert<'a>(&mut self, key: L::KeyToInsert<'a>, value: V) -> bool
@@ -99,11 +116,15 @@ where
         V: InMemorySize,
     {
         let size = value.size();
+        if size > self.limits.max_bytes {
+            return false
+        }
 
-        if self.cache.limiter().is_over_the_limit(self.cache.len() + 1) {
-            if let Some((_, evicted)) = self.cache.pop_oldest() {
-                // update tracked memory with the evicted value
-                self.memory_usage = self.memory_usage.saturating_sub(evicted.size());
+        while self.cache.limiter().is_over_the_limit(self.cache.len() + 1) ||
+            self.memory_usage.saturating_add(size) > self.limits.max_bytes
+        {
+            if !self.evict_oldest() {
+                break
             }
         }
 
@@ -115,6 +136,31 @@ where
         }
     }
 
+    /// Evicts the least recently used element.
+    ///
+    /// Returns `false` if the cache is empty.
+    fn evict_oldest(&mut self) -> bool
+    where
+        V: InMemorySize,
+    {
+        let Some((_, evicted)) = self.cache.pop_oldest() else { return false };
+        // update tracked memory with the evicted value
+        self.memory_usage = self.memory_usage.saturating_sub(evicted.size());
+        self.metrics.evictions_total.increment(1);
+        true
+    }
+
+    /// Returns the usage statistics of the cache.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            len: self.cache.len(),
+            memory_usage: self.memory_usage,
+            hits: self.hits,
+            misses: self.misses,
+            limits: self.limits,
+        }
+    }
+
     /// Shrinks the capacity of the queue with a lower limit.
     #[inline]
     pub fn shrink_to(&mut self, min_capacity: usize) {
@@ -126,6 +172,8 @@ where
     pub fn update_cached_metrics(&self) {
         self.metrics.cached_count.set(self.cache.len() as f64);
         self.metrics.memory_usage.set(self.memory_usage as f64);
+        self.metrics.memory_limit.set(self.limits.max_bytes as f64);
+        self.metrics.hit_rate.set(self.stats().hit_rate());
     }
 }
 
@@ -133,13 +181,85 @@ impl<K, V, S> MultiConsumerLruCache<K, V, ByLength, S>
 where
     K: Hash + Eq,
 {
-    /// Creates a new empty map with a given `max_len` and metric label.
-    pub fn new(max_len: u32, cache_id: &str) -> Self {
+    /// Creates a new empty map with the given limits and metric label.
+    pub fn new(limits: CacheLimits, cache_id: &str) -> Self {
         Self {
-            cache: LruMap::new(ByLength::new(max_len)),
+            cache: LruMap::new(ByLength::new(limits.max_len)),
             queued: Default::default(),
             metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
             memory_usage: 0,
+            limits,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Updates the limits of the cache and returns the previous ones.
+    ///
+    /// The oldest elements are evicted until the cache is within the new limits.
+    pub fn set_limits(&mut self, limits: CacheLimits) -> CacheLimits
+    where
+        K: Debug,
+        V: InMemorySize,
+    {
+        *self.cache.limiter_mut() = ByLength::new(limits.max_len);
+        let previous = std::mem::replace(&mut self.limits, limits);
+
+        while Limiter::<K, V>::is_over_the_limit(self.cache.limiter(), self.cache.len()) ||
+            self.memory_usage > limits.max_bytes
+        {
+            if !self.evict_oldest() {
+                break
+            }
         }
+
+        previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestCache = MultiConsumerLruCache<u64, Vec<u64>, ByLength, ()>;
+
+    #[test]
+    fn evicts_oldest_over_memory_limit() {
+        // every entry of two words takes 16 bytes
+        let mut cache = TestCache::new(CacheLimits { max_len: 10, max_bytes: 40 }, "test");
+        assert!(cache.insert(1, vec![0; 2]));
+        assert!(cache.insert(2, vec![0; 2]));
+        assert!(cache.insert(3, vec![0; 2]));
+
+        let stats = cache.stats();
+        assert_eq!(stats.len, 2);
+        assert_eq!(stats.memory_usage, 32);
+        assert!(cache.get(&1).is_none());
+        assert!(cache.get(&3).is_some());
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().misses, 1);
+
+        // entries that alone exceed the memory limit are rejected
+        assert!(!cache.insert(4, vec![0; 6]));
+        assert_eq!(cache.stats().len, 2);
+    }
+
+    #[test]
+    fn set_limits_evicts() {
+        let limits = CacheLimits { max_len: 10, max_bytes: 1024 };
+        let mut cache = TestCache::new(limits, "test");
+        for key in 0..5 {
+            assert!(cache.insert(key, vec![0; 2]));
+        }
+
+        let previous = cache.set_limits(CacheLimits { max_len: 3, max_bytes: 1024 });
+        assert_eq!(previous, limits);
+        assert_eq!(cache.stats().len, 3);
+        assert!(cache.get(&1).is_none());
+
+        cache.set_limits(CacheLimits { max_len: 3, max_bytes: 16 });
+        assert_eq!(cache.stats().len, 1);
+        assert_eq!(cache.stats().memory_usage, 16);
+        assert!(cache.get(&4).is_some());
     }
 }
@@ -4,7 +4,7 @@ pub mod api;
 use crate::error::api::FromEvmHalt;
 use alloy_eips::BlockId;
 use alloy_evm::{call::CallError, overrides::StateOverrideError};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256, U64};
 use alloy_rpc_types_eth::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
 use alloy_sol_types::{ContractError, RevertReason};
 pub use api::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
@@ -68,9 +68,26 @@ pub enum EthApiError {
     /// This error is intended for use as a standard response when historical data is
     /// requested that has been pruned according to the node's data retention policy.
     ///
+    /// The earliest block whose data is still available is returned in the `data` field of the
+    /// RPC error.
+    ///
     /// See also <https://eips.ethereum.org/EIPS/eip-4444>
-    #[error("pruned history unavailable")]
-    PrunedHistoryUnavailable,
+    #[error("pruned history unavailable, earliest available block: {earliest_block}")]
+    PrunedHistoryUnavailable {
+        /// The earliest block whose data is still available.
+        earliest_block: BlockNumber,
+    },
+    /// Thrown when logs matching a filter may have been pruned by the receipts log filters
+    ///
+    /// Before the earliest block, only the receipts with logs of the retained addresses or topics
+    /// are available. The earliest block is returned in the `data` field of the RPC error.
+    #[error(
+        "logs matching the filter have been pruned, earliest available block: {earliest_block}"
+    )]
+    PrunedLogsUnavailable {
+        /// The earliest block from which on all logs matching the filter are available.
+        earliest_block: BlockNumber,
+    },
     /// Receipts not found for block hash/number/tag
     #[error("receipts not found")]
     ReceiptsNotFound(BlockId),
@@ -283,7 +300,14 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 internal_rpc_err(err.to_string())
             }
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
-            EthApiError::PrunedHistoryUnavailable => rpc_error_with_code(4444, error.to_string()),
+            EthApiError::PrunedHistoryUnavailable { earliest_block } |
+            EthApiError::PrunedLogsUnavailable { earliest_block } => {
+                jsonrpsee_types::error::ErrorObject::owned(
+                    4444,
+                    error.to_string(),
+                    Some(U64::from(earliest_block)),
+                )
+            }
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
             EthApiError::BatchTxRecvError(err) => internal_rpc_err(err.to_string()),
@@ -878,6 +902,9 @@ pub enum RpcPoolError {
     /// When the max initcode size is exceeded
     #[error("max initcode size exceeded")]
     ExceedsMaxInitCodeSize,
+    /// When the transaction nonce is too far ahead of the sender's nonce
+    #[error("nonce too high, exceeds max nonce gap {0}")]
+    ExceedsMaxNonceGap(u64),
     /// Errors related to invalid transactions
     #[error(transparent)]
     Invalid(#[from] RpcInvalidTransactionError),
@@ -896,6 +923,10 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the preconditions of a conditional transaction are not met, see
+    /// `eth_sendRawTransactionConditional`.
+    #[error("transaction conditional rejected: {0}")]
+    ConditionalRejected(#[from] TransactionConditionalError),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -905,7 +936,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::ConditionalRejected(_) => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             RpcPoolError::AlreadyKnown |
@@ -918,6 +949,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
             RpcPoolError::NegativeValue |
             RpcPoolError::OversizedData |
             RpcPoolError::ExceedsMaxInitCodeSize |
+            RpcPoolError::ExceedsMaxNonceGap(_) |
             RpcPoolError::PoolTransactionError(_) |
             RpcPoolError::Eip4844(_) |
             RpcPoolError::Eip7702(_) |
@@ -929,6 +961,36 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     }
 }
 
+/// Errors related to the preconditions of a conditional transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// When the number of state lookups required to check the preconditions is too high
+    #[error("conditional cost {cost} exceeds maximum {max}")]
+    CostExceeded {
+        /// The cost of the conditional.
+        cost: u64,
+        /// The maximum allowed cost.
+        max: u64,
+    },
+    /// When the pending block number is outside of the allowed range
+    #[error("block number {0} out of range")]
+    BlockNumberOutOfRange(u64),
+    /// When the pending block timestamp is outside of the allowed range
+    #[error("timestamp {0} out of range")]
+    TimestampOutOfRange(u64),
+    /// When the value of a known storage slot doesn't match the pending state
+    #[error("storage value mismatch for slot {slot} of account {address}")]
+    StorageValueMismatch {
+        /// The account of the storage slot.
+        address: Address,
+        /// The storage slot.
+        slot: U256,
+    },
+    /// When the known storage root of an account doesn't match the pending state
+    #[error("storage root mismatch for account {0}")]
+    StorageRootMismatch(Address),
+}
+
 impl From<PoolError> for RpcPoolError {
     fn from(err: PoolError) -> Self {
         match err.kind {
@@ -937,6 +999,9 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::SpammerExceededCapacity(_) | PoolErrorKind::DiscardedOnInsert => {
                 Self::TxPoolOverflow
             }
+            PoolErrorKind::ExceededMaxNonceGap(max_nonce_gap) => {
+                Self::ExceedsMaxNonceGap(max_nonce_gap)
+            }
             PoolErrorKind::InvalidTransaction(err) => err.into(),
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
@@ -986,6 +1051,12 @@ impl From<PoolError> for EthApiError {
     }
 }
 
+impl From<TransactionConditionalError> for EthApiError {
+    fn from(err: TransactionConditionalError) -> Self {
+        Self::PoolError(RpcPoolError::from(err))
+    }
+}
+
 /// Errors returned from a sign request.
 #[derive(Debug, thiserror::Error)]
 pub enum SignError {
@@ -1066,6 +1137,23 @@ mod tests {
         assert_eq!(err.message(), "block not found: finalized");
     }
 
+    #[test]
+    fn pruned_history_unavailable_error() {
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::PrunedHistoryUnavailable { earliest_block: 100 }.into();
+        assert_eq!(err.code(), 4444);
+        assert_eq!(err.message(), "pruned history unavailable, earliest available block: 100");
+        assert_eq!(err.data().unwrap().get(), "\"0x64\"");
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::PrunedLogsUnavailable { earliest_block: 100 }.into();
+        assert_eq!(err.code(), 4444);
+        assert_eq!(
+            err.message(),
+            "logs matching the filter have been pruned, earliest available block: 100"
+        );
+    }
+
     #[test]
     fn revert_err_display() {
         let revert = Revert::from("test_revert_reason");
@@ -3,10 +3,11 @@
 use std::{
     collections::{BTreeMap, VecDeque},
     fmt::Debug,
+    path::Path,
     sync::{atomic::Ordering::SeqCst, Arc},
 };
 
-use alloy_consensus::{BlockHeader, Header, Transaction, TxReceipt};
+use alloy_consensus::{BlockHeader, Header, Sealable, Transaction, TxReceipt};
 use alloy_eips::eip7840::BlobParams;
 use alloy_rpc_types_eth::TxGasAndReward;
 use futures::{
@@ -18,9 +19,10 @@ use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_primitives_traits::{Block, BlockBody, NodePrimitives, SealedBlock};
 use reth_rpc_server_types::constants::gas_oracle::MAX_HEADER_HISTORY;
-use reth_storage_api::BlockReaderIdExt;
-use serde::{Deserialize, Serialize};
-use tracing::trace;
+use reth_storage_api::{BlockHashReader, BlockReaderIdExt};
+use reth_tasks::shutdown::GracefulShutdown;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{debug, info, trace, warn};
 
 use crate::utils::checked_blob_gas_used_ratio;
 
@@ -102,6 +104,31 @@ where
             entries.insert(block.number(), fee_history_entry);
         }
 
+        self.enforce_bounds(&mut entries);
+    }
+
+    /// Inserts previously computed entries into the cache, e.g. entries restored from disk.
+    ///
+    /// Entries for blocks that are already cached are not replaced.
+    pub async fn extend_entries<I>(&self, new_entries: I)
+    where
+        I: IntoIterator<Item = FeeHistoryEntry<H>>,
+    {
+        let mut entries = self.inner.entries.write().await;
+        for entry in new_entries {
+            entries.entry(entry.header.number()).or_insert(entry);
+        }
+
+        self.enforce_bounds(&mut entries);
+    }
+
+    /// Returns all cached entries, ordered by block number.
+    pub async fn entries(&self) -> Vec<FeeHistoryEntry<H>> {
+        self.inner.entries.read().await.values().cloned().collect()
+    }
+
+    /// Drops the oldest entries that exceed the configured capacity and updates the bounds.
+    fn enforce_bounds(&self, entries: &mut BTreeMap<u64, FeeHistoryEntry<H>>) {
         // enforce bounds by popping the oldest entries
         while entries.len() > self.inner.config.max_blocks as usize {
             entries.pop_first();
@@ -126,6 +153,87 @@ where
         self.inner.lower_bound.store(lower_bound, SeqCst);
     }
 
+    /// Writes all cached entries to the given file.
+    pub async fn save_to_file(&self, path: &Path) -> Result<(), reth_fs_util::FsPathError>
+    where
+        H: Serialize,
+    {
+        let backup =
+            FeeHistoryCacheBackup { resolution: self.resolution(), entries: self.entries().await };
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::write_json_file(path, &backup)
+    }
+
+    /// Restores the entries previously written with [`Self::save_to_file`] from the given file.
+    ///
+    /// Only the newest contiguous run of entries whose block hashes match the canonical hashes
+    /// of the provider is restored. The newest entries that can't be verified, e.g. of blocks that
+    /// were reorged out while the node was offline, are skipped, and entries before a gap are
+    /// discarded. If the cache already holds entries of new blocks, restored entries are only
+    /// kept if they extend the cached range without a gap. Entries that were computed with a
+    /// different percentile resolution are discarded as well. Returns the number of restored
+    /// entries.
+    pub async fn load_from_file<Provider>(
+        &self,
+        path: &Path,
+        provider: &Provider,
+    ) -> Result<usize, reth_fs_util::FsPathError>
+    where
+        H: Sealable + DeserializeOwned,
+        Provider: BlockHashReader,
+    {
+        if !path.exists() {
+            return Ok(0)
+        }
+
+        let backup: FeeHistoryCacheBackup<H> = reth_fs_util::read_json_file(path)?;
+        if backup.resolution != self.resolution() {
+            debug!(target: "rpc::fee", path=?path, resolution=backup.resolution, "Discarding fee history cache backup with different resolution");
+            return Ok(0)
+        }
+
+        let mut restored = Vec::new();
+        for entry in backup.entries.into_iter().rev() {
+            let number = entry.header.number();
+            if restored
+                .last()
+                .is_some_and(|last: &FeeHistoryEntry<H>| last.header.number() != number + 1)
+            {
+                // gap in the backup
+                break
+            }
+            // entries that can't be verified are discarded
+            if provider.block_hash(number).ok().flatten() != Some(entry.header.hash_slow()) {
+                if restored.is_empty() {
+                    // newest entries may be of blocks that are no longer canonical
+                    continue
+                }
+                break
+            }
+            restored.push(entry);
+        }
+
+        let mut entries = self.inner.entries.write().await;
+        if let Some(&first_live) = entries.keys().next() {
+            // restored entries must end right before the oldest block that was cached since
+            // startup, otherwise there would be a gap in the cached range
+            restored.retain(|entry| entry.header.number() < first_live);
+            if restored.first().is_some_and(|entry| entry.header.number() + 1 != first_live) {
+                restored.clear();
+            }
+        }
+
+        let count = restored.len();
+        for entry in restored {
+            entries.insert(entry.header.number(), entry);
+        }
+        self.enforce_bounds(&mut entries);
+
+        Ok(count)
+    }
+
     /// Get `UpperBound` value for `FeeHistoryCache`
     pub fn upper_bound(&self) -> u64 {
         self.inner.upper_bound.load(SeqCst)
@@ -141,7 +249,7 @@ where
     /// This function retrieves fee history entries from the cache for the specified range.
     /// If the requested range (`start_block` to `end_block`) is within the cache bounds,
     /// it returns the corresponding entries.
-    /// Otherwise it returns None.
+    /// Otherwise, or if any block of the range is missing in the cache, it returns None.
     pub async fn get_history(
         &self,
         start_block: u64,
@@ -160,7 +268,8 @@ where
                 .map(|(_, fee_entry)| fee_entry.clone())
                 .collect::<Vec<_>>();
 
-            if result.is_empty() {
+            if result.len() as u64 != end_block - start_block + 1 {
+                // gaps in the cache, the caller has to fall back to the database
                 return None
             }
 
@@ -214,6 +323,49 @@ struct FeeHistoryCacheInner<H> {
     entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry<H>>>,
 }
 
+/// On-disk representation of the [`FeeHistoryCache`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeHistoryCacheBackup<H> {
+    /// The percentile resolution the rewards of the entries were approximated with.
+    resolution: u64,
+    /// The cached entries, ordered by block number.
+    entries: Vec<FeeHistoryEntry<H>>,
+}
+
+/// Restores the [`FeeHistoryCache`] from the given file and writes the cached entries back to it
+/// on shutdown.
+///
+/// This makes `eth_feeHistory` fully available right after a restart instead of only after the
+/// node has processed enough new blocks. Restored entries are validated against the canonical
+/// block hashes of the provider, see [`FeeHistoryCache::load_from_file`].
+pub async fn fee_history_cache_backup_task<H, Provider>(
+    shutdown: GracefulShutdown,
+    fee_history_cache: FeeHistoryCache<H>,
+    path: impl AsRef<Path>,
+    provider: Provider,
+) where
+    H: BlockHeader + Sealable + Clone + Serialize + DeserializeOwned,
+    Provider: BlockHashReader,
+{
+    let path = path.as_ref();
+    match fee_history_cache.load_from_file(path, &provider).await {
+        Ok(restored) => {
+            debug!(target: "rpc::fee", ?path, restored, "Restored fee history cache entries")
+        }
+        Err(err) => warn!(target: "rpc::fee", %err, "Failed to restore fee history cache"),
+    }
+
+    let graceful_guard = shutdown.await;
+
+    match fee_history_cache.save_to_file(path).await {
+        Ok(()) => info!(target: "rpc::fee", ?path, "Wrote fee history cache to file"),
+        Err(err) => warn!(target: "rpc::fee", %err, "Failed to write fee history cache to file"),
+    }
+
+    drop(graceful_guard)
+}
+
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
 pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
@@ -340,7 +492,8 @@ where
 }
 
 /// A cached entry for a block's fee history.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FeeHistoryEntry<H = Header> {
     /// The full block header.
     pub header: H,
@@ -412,3 +565,131 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{BlockNumber, B256};
+    use reth_storage_api::errors::provider::ProviderResult;
+
+    /// Canonical block hashes by number.
+    #[derive(Default)]
+    struct CanonicalHashes(BTreeMap<BlockNumber, B256>);
+
+    impl BlockHashReader for CanonicalHashes {
+        fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok(self.0.get(&number).copied())
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            start: BlockNumber,
+            end: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            Ok(self.0.range(start..end).map(|(_, hash)| *hash).collect())
+        }
+    }
+
+    fn entry(number: BlockNumber) -> FeeHistoryEntry {
+        FeeHistoryEntry {
+            header: Header { number, gas_limit: 30_000_000, ..Default::default() },
+            gas_used_ratio: 0.5,
+            base_fee_per_blob_gas: None,
+            blob_gas_used_ratio: 0.0,
+            rewards: vec![1, 2, 3],
+            blob_params: None,
+        }
+    }
+
+    fn canonical(entries: &[FeeHistoryEntry]) -> CanonicalHashes {
+        CanonicalHashes(
+            entries.iter().map(|entry| (entry.header.number, entry.header.hash_slow())).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn backup_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fee-history-cache.json");
+
+        let entries = (1..=5).map(entry).collect::<Vec<_>>();
+        let cache = FeeHistoryCache::<Header>::new(Default::default());
+        cache.extend_entries(entries.clone()).await;
+        cache.save_to_file(&path).await.unwrap();
+
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        assert_eq!(restored.load_from_file(&path, &canonical(&entries)).await.unwrap(), 5);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (1, 5));
+
+        let restored = restored.entries().await;
+        assert_eq!(restored.len(), entries.len());
+        for (restored, entry) in restored.iter().zip(&entries) {
+            assert_eq!(restored.header, entry.header);
+            assert_eq!(restored.rewards, entry.rewards);
+        }
+    }
+
+    #[tokio::test]
+    async fn backup_drops_gaps_and_reorged_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fee-history-cache.json");
+
+        // block 3 is missing from the backup
+        let entries = [1, 2, 4, 5, 6].map(entry);
+        let cache = FeeHistoryCache::<Header>::new(Default::default());
+        cache.extend_entries(entries.clone()).await;
+        cache.save_to_file(&path).await.unwrap();
+
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        assert_eq!(restored.load_from_file(&path, &canonical(&entries)).await.unwrap(), 3);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (4, 6));
+
+        // block 6 was reorged out while the node was offline
+        let mut reorged = canonical(&entries);
+        reorged.0.insert(6, B256::random());
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        assert_eq!(restored.load_from_file(&path, &reorged).await.unwrap(), 2);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (4, 5));
+
+        // block 5 was reorged out, block 6 verifies but is cut off by it
+        let mut reorged = canonical(&entries);
+        reorged.0.insert(5, B256::random());
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        assert_eq!(restored.load_from_file(&path, &reorged).await.unwrap(), 1);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (6, 6));
+    }
+
+    #[tokio::test]
+    async fn backup_must_extend_live_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fee-history-cache.json");
+
+        let entries = (1..=10).map(entry).collect::<Vec<_>>();
+        let cache = FeeHistoryCache::<Header>::new(Default::default());
+        cache.extend_entries(entries[..5].to_vec()).await;
+        cache.save_to_file(&path).await.unwrap();
+
+        // block 6 was cached since startup, backup ends right before it
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        restored.extend_entries([entries[5].clone()]).await;
+        assert_eq!(restored.load_from_file(&path, &canonical(&entries)).await.unwrap(), 5);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (1, 6));
+        assert_eq!(restored.get_history(1, 6).await.unwrap().len(), 6);
+
+        // blocks 6 and 7 are missing between the backup and the live entries
+        let restored = FeeHistoryCache::<Header>::new(Default::default());
+        restored.extend_entries([entries[7].clone()]).await;
+        assert_eq!(restored.load_from_file(&path, &canonical(&entries)).await.unwrap(), 0);
+        assert_eq!((restored.lower_bound(), restored.upper_bound()), (8, 8));
+    }
+
+    #[tokio::test]
+    async fn get_history_requires_all_blocks() {
+        let cache = FeeHistoryCache::<Header>::new(Default::default());
+        cache.extend_entries([1, 2, 4, 5].map(entry)).await;
+
+        assert_eq!(cache.get_history(1, 2).await.unwrap().len(), 2);
+        assert!(cache.get_history(1, 5).await.is_none());
+        assert!(cache.get_history(3, 3).await.is_none());
+    }
+}
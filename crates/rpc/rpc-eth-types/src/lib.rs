@@ -8,22 +8,29 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod block;
 pub mod builder;
 pub mod cache;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
 pub mod id_provider;
+pub mod limits;
 pub mod logs_utils;
 pub mod pending_block;
 pub mod receipt;
 pub mod simulate;
+pub mod sync;
 pub mod transaction;
 pub mod utils;
 
+pub use block::{BlockQueryOptions, BlockWithMetadata};
 pub use builder::config::{EthConfig, EthFilterConfig};
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
+    config::{CacheLimits, EthStateCacheConfig, EthStateCacheLimits},
+    db::StateCacheDb,
+    metrics::{CacheStats, EthStateCacheStats},
+    multi_consumer::MultiConsumerLruCache,
     EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
@@ -32,5 +39,7 @@ pub use gas_oracle::{
     GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
 };
 pub use id_provider::EthSubscriptionIdProvider;
+pub use limits::{RequestScope, RpcLimits, RpcLimitsHandle, RpcTimeouts};
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use sync::{EthSyncInfo, EthSyncStatus, SyncProgress, SyncProgressTracker};
 pub use transaction::TransactionSource;
@@ -0,0 +1,358 @@
+//! Limits of the RPC that can be adjusted at runtime.
+
+use crate::{error::RpcPoolError, EthApiError};
+use parking_lot::RwLock;
+use reth_rpc_server_types::constants::{gas_oracle::RPC_DEFAULT_GAS_CAP, DEFAULT_TX_FEE_CAP_WEI};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::OwnedSemaphorePermit;
+
+tokio::task_local! {
+    /// Scope of the request that is served by the current task.
+    static REQUEST_SCOPE: RequestScope;
+}
+
+std::thread_local! {
+    /// Deadline and timeout of the request whose blocking work runs on the current thread.
+    static DEADLINE: Cell<Option<(Instant, Duration)>> = const { Cell::new(None) };
+}
+
+/// Limits that are applied to `eth`, `debug` and `trace` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLimits {
+    /// Maximum gas limit for `eth_call` and call tracing RPC methods.
+    pub gas_cap: u64,
+    /// Maximum fee in wei of transactions submitted via `eth_sendRawTransaction` and
+    /// `eth_sendTransaction`, `0` disables the cap.
+    ///
+    /// Submitted transactions are still validated by the transaction pool against the cap the node
+    /// was started with, so this can only lower the effective cap.
+    pub tx_fee_cap: u128,
+    /// Maximum execution time of EVM backed requests per namespace.
+    #[serde(default)]
+    pub timeouts: RpcTimeouts,
+    /// Maximum number of blocks below the tip at which state can be queried on nodes whose state
+    /// history is pruned, `None` if unlimited.
+    ///
+    /// This rejects such queries up front with the earliest block that can be queried, instead of
+    /// failing deep in the call stack.
+    #[serde(default)]
+    pub max_state_history: Option<u64>,
+}
+
+impl Default for RpcLimits {
+    fn default() -> Self {
+        Self {
+            gas_cap: RPC_DEFAULT_GAS_CAP,
+            tx_fee_cap: DEFAULT_TX_FEE_CAP_WEI,
+            timeouts: RpcTimeouts::default(),
+            max_state_history: None,
+        }
+    }
+}
+
+impl RpcLimits {
+    /// Checks that the limits are usable.
+    ///
+    /// A gas cap of `0` would reject every call and a timeout of `0` would abort every request, use
+    /// `None` to disable a timeout instead.
+    pub fn validate(&self) -> Result<(), EthApiError> {
+        if self.gas_cap == 0 {
+            return Err(EthApiError::InvalidParams("gas cap must not be zero".to_string()))
+        }
+        let RpcTimeouts { eth, debug, trace } = self.timeouts;
+        for (namespace, timeout) in [("eth", eth), ("debug", debug), ("trace", trace)] {
+            if timeout == Some(0) {
+                return Err(EthApiError::InvalidParams(format!(
+                    "{namespace} timeout must not be zero"
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum execution time in milliseconds of EVM backed requests per namespace, `None` if
+/// requests of the namespace never time out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcTimeouts {
+    /// Timeout of `eth_call`, `eth_callMany`, `eth_estimateGas`, `eth_createAccessList` and
+    /// `eth_simulateV1`.
+    pub eth: Option<u64>,
+    /// Timeout of the `debug_trace*` methods.
+    pub debug: Option<u64>,
+    /// Timeout of the `trace_*` methods that execute transactions.
+    pub trace: Option<u64>,
+}
+
+/// Shared handle to the [`RpcLimits`] that allows updating them while the node is running.
+#[derive(Debug, Clone, Default)]
+pub struct RpcLimitsHandle {
+    inner: Arc<RwLock<RpcLimits>>,
+}
+
+impl RpcLimitsHandle {
+    /// Creates a new handle with the given initial limits.
+    pub fn new(limits: RpcLimits) -> Self {
+        Self { inner: Arc::new(RwLock::new(limits)) }
+    }
+
+    /// Returns the current limits.
+    pub fn get(&self) -> RpcLimits {
+        *self.inner.read()
+    }
+
+    /// Replaces the limits and returns the previous ones.
+    ///
+    /// Requests that are already running keep the limits they started with.
+    pub fn set(&self, limits: RpcLimits) -> Result<RpcLimits, EthApiError> {
+        limits.validate()?;
+        Ok(std::mem::replace(&mut *self.inner.write(), limits))
+    }
+
+    /// Returns the current gas cap.
+    pub fn gas_cap(&self) -> u64 {
+        self.inner.read().gas_cap
+    }
+
+    /// Returns the current tx fee cap in wei, `0` if disabled.
+    pub fn tx_fee_cap(&self) -> u128 {
+        self.inner.read().tx_fee_cap
+    }
+
+    /// Returns the timeout of the `eth` namespace.
+    pub fn eth_timeout(&self) -> Option<Duration> {
+        self.inner.read().timeouts.eth.map(Duration::from_millis)
+    }
+
+    /// Returns the timeout of the `debug` namespace.
+    pub fn debug_timeout(&self) -> Option<Duration> {
+        self.inner.read().timeouts.debug.map(Duration::from_millis)
+    }
+
+    /// Returns the timeout of the `trace` namespace.
+    pub fn trace_timeout(&self) -> Option<Duration> {
+        self.inner.read().timeouts.trace.map(Duration::from_millis)
+    }
+
+    /// Returns the maximum number of blocks below the tip at which state can be queried, `None` if
+    /// unlimited.
+    pub fn max_state_history(&self) -> Option<u64> {
+        self.inner.read().max_state_history
+    }
+
+    /// Ensures that the max possible fee of a transaction, `max_fee_per_gas * gas_limit`, doesn't
+    /// exceed the current tx fee cap.
+    pub fn ensure_tx_fee(&self, max_fee_per_gas: u128, gas_limit: u64) -> Result<(), EthApiError> {
+        let tx_fee_cap_wei = self.tx_fee_cap();
+        if tx_fee_cap_wei == 0 {
+            return Ok(())
+        }
+        let max_tx_fee_wei = max_fee_per_gas.saturating_mul(gas_limit as u128);
+        if max_tx_fee_wei > tx_fee_cap_wei {
+            return Err(EthApiError::PoolError(RpcPoolError::ExceedsFeeCap {
+                max_tx_fee_wei,
+                tx_fee_cap_wei,
+            }))
+        }
+        Ok(())
+    }
+}
+
+/// Deadline and tracing permit of an RPC request, which are carried over to the blocking tasks that
+/// execute it.
+#[derive(Debug, Clone, Default)]
+pub struct RequestScope {
+    /// Instant at which the request times out, and the timeout it was configured with.
+    deadline: Option<(Instant, Duration)>,
+    /// Permit of the tracing semaphore acquired for the request.
+    permit: Option<Arc<OwnedSemaphorePermit>>,
+}
+
+impl RequestScope {
+    /// Returns the scope of the request that is served by the current task, see [`with_timeout`].
+    pub fn current() -> Option<Self> {
+        REQUEST_SCOPE.try_with(Clone::clone).ok()
+    }
+
+    /// Runs blocking work of the request on the current thread.
+    ///
+    /// The deadline of the request is enforced by [`ensure_within_deadline`] while the work runs,
+    /// and the permit is held until it returns, even if the request itself already timed out.
+    pub fn run<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = DeadlineGuard(DEADLINE.replace(self.deadline));
+        f()
+    }
+}
+
+/// Restores the deadline of the current thread on drop.
+struct DeadlineGuard(Option<(Instant, Duration)>);
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.set(self.0);
+    }
+}
+
+/// Fails with [`EthApiError::ExecutionTimedOut`] if the blocking work that runs on the current
+/// thread belongs to a request that timed out.
+///
+/// This is checked before every transaction that is executed for a request, so timed out requests
+/// stop executing instead of running to completion in the background.
+pub fn ensure_within_deadline() -> Result<(), EthApiError> {
+    match DEADLINE.get() {
+        Some((deadline, timeout)) if Instant::now() >= deadline => {
+            Err(EthApiError::ExecutionTimedOut(timeout))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Serves the request future in a [`RequestScope`] with the given timeout and tracing permit,
+/// failing with [`EthApiError::ExecutionTimedOut`] if it doesn't complete within the timeout.
+///
+/// Blocking tasks spawned for the request inherit the scope: they stop executing transactions once
+/// the timeout elapsed and hold the permit until they return, so the permit is only released when
+/// all work of the request ended.
+pub async fn with_timeout<F, T, E>(
+    timeout: Option<Duration>,
+    permit: Option<OwnedSemaphorePermit>,
+    fut: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<EthApiError>,
+{
+    let scope = RequestScope {
+        deadline: timeout.map(|timeout| (Instant::now() + timeout, timeout)),
+        permit: permit.map(Arc::new),
+    };
+    let fut = REQUEST_SCOPE.scope(scope, fut);
+    let Some(timeout) = timeout else { return fut.await };
+    tokio::time::timeout(timeout, fut)
+        .await
+        .unwrap_or_else(|_| Err(EthApiError::ExecutionTimedOut(timeout).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_limits() {
+        let handle = RpcLimitsHandle::default();
+
+        let limits = RpcLimits { gas_cap: 0, ..Default::default() };
+        assert!(handle.set(limits).is_err());
+
+        let limits = RpcLimits {
+            timeouts: RpcTimeouts { trace: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        assert!(handle.set(limits).is_err());
+        assert_eq!(handle.get(), RpcLimits::default());
+
+        let limits = RpcLimits {
+            gas_cap: 1_000_000,
+            tx_fee_cap: 0,
+            timeouts: RpcTimeouts { eth: Some(500), ..Default::default() },
+            max_state_history: Some(128),
+        };
+        assert_eq!(handle.set(limits).unwrap(), RpcLimits::default());
+        assert_eq!(handle.gas_cap(), 1_000_000);
+        assert_eq!(handle.eth_timeout(), Some(Duration::from_millis(500)));
+        assert_eq!(handle.debug_timeout(), None);
+        assert_eq!(handle.max_state_history(), Some(128));
+    }
+
+    #[test]
+    fn tx_fee_cap() {
+        let handle = RpcLimitsHandle::new(RpcLimits { tx_fee_cap: 100, ..Default::default() });
+        assert!(handle.ensure_tx_fee(10, 10).is_ok());
+        assert!(matches!(
+            handle.ensure_tx_fee(10, 11),
+            Err(EthApiError::PoolError(RpcPoolError::ExceedsFeeCap {
+                max_tx_fee_wei: 110,
+                tx_fee_cap_wei: 100
+            }))
+        ));
+
+        handle.set(RpcLimits { tx_fee_cap: 0, ..Default::default() }).unwrap();
+        assert!(handle.ensure_tx_fee(u128::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn serde_limits() {
+        let limits: RpcLimits =
+            serde_json::from_str(r#"{"gasCap":50000000,"txFeeCap":0,"timeouts":{"debug":2000}}"#)
+                .unwrap();
+        assert_eq!(
+            limits,
+            RpcLimits {
+                gas_cap: 50_000_000,
+                tx_fee_cap: 0,
+                timeouts: RpcTimeouts { eth: None, debug: Some(2000), trace: None },
+                max_state_history: None,
+            }
+        );
+
+        let limits: RpcLimits =
+            serde_json::from_str(r#"{"gasCap":1,"txFeeCap":0,"maxStateHistory":10064}"#).unwrap();
+        assert_eq!(limits.max_state_history, Some(10064));
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let res: Result<(), EthApiError> = with_timeout(
+            Some(Duration::from_millis(10)),
+            None,
+            futures::future::pending::<Result<(), EthApiError>>(),
+        )
+        .await;
+        assert!(matches!(res, Err(EthApiError::ExecutionTimedOut(_))));
+
+        let res: Result<u64, EthApiError> = with_timeout(None, None, async { Ok(1) }).await;
+        assert_eq!(res.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn blocking_work_stops_at_deadline_and_keeps_permit() {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(1));
+        let permit = semaphore.clone().acquire_owned().await.unwrap();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+
+        let res: Result<(), EthApiError> =
+            with_timeout(Some(Duration::from_millis(10)), Some(permit), async move {
+                let scope = RequestScope::current().expect("in request scope");
+                tokio::task::spawn_blocking(move || {
+                    let res = scope.run(|| -> Result<(), EthApiError> {
+                        // executes transactions until the deadline is reached
+                        loop {
+                            ensure_within_deadline()?;
+                            std::thread::sleep(Duration::from_millis(1));
+                        }
+                    });
+                    let _ = done_tx.send(res);
+                });
+                futures::future::pending::<Result<(), EthApiError>>().await
+            })
+            .await;
+        assert!(matches!(res, Err(EthApiError::ExecutionTimedOut(_))));
+
+        let res: Result<(), EthApiError> = done_rx.recv().unwrap();
+        assert!(matches!(res, Err(EthApiError::ExecutionTimedOut(_))));
+        // the permit is released once the blocking work returned
+        let _permit = tokio::time::timeout(Duration::from_secs(5), semaphore.acquire())
+            .await
+            .expect("permit released");
+
+        // work outside of a request scope is never timed out
+        assert!(ensure_within_deadline().is_ok());
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
         api::{FromEthApiError, FromEvmHalt},
         ToRpcError,
     },
+    limits::ensure_within_deadline,
     EthApiError, RevertError,
 };
 use alloy_consensus::{BlockHeader, Transaction as _};
@@ -85,6 +86,8 @@ where
 
     let mut results = Vec::with_capacity(calls.len());
     for call in calls {
+        ensure_within_deadline()?;
+
         // Resolve transaction, populate missing fields and enforce calls
         // correctness.
         let tx = resolve_transaction(
@@ -0,0 +1,292 @@
+//! Reth specific extension of the `eth_syncing` response.
+
+use alloy_rpc_types_eth::{SyncInfo, SyncStatus};
+use parking_lot::Mutex;
+use reth_stages_types::{StageCheckpoint, StageId};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The window over which the throughput of the current stage is measured.
+pub const SYNC_THROUGHPUT_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// The minimum interval between two throughput samples.
+const SYNC_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Response of `eth_syncing`.
+///
+/// This is the same as [`SyncStatus`], but can additionally carry the reth specific
+/// [`SyncProgress`] of the pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EthSyncStatus {
+    /// Info when syncing
+    Info(Box<EthSyncInfo>),
+    /// Not syncing
+    None,
+}
+
+impl From<SyncStatus> for EthSyncStatus {
+    fn from(status: SyncStatus) -> Self {
+        match status {
+            SyncStatus::Info(info) => {
+                Self::Info(Box::new(EthSyncInfo { info: *info, progress: SyncProgress::default() }))
+            }
+            SyncStatus::None => Self::None,
+        }
+    }
+}
+
+impl From<EthSyncStatus> for SyncStatus {
+    fn from(status: EthSyncStatus) -> Self {
+        match status {
+            EthSyncStatus::Info(info) => Self::Info(Box::new(info.info)),
+            EthSyncStatus::None => Self::None,
+        }
+    }
+}
+
+impl Serialize for EthSyncStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Info(info) => info.serialize(serializer),
+            Self::None => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EthSyncStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Syncing {
+            None(bool),
+            IsSyncing(Box<EthSyncInfo>),
+        }
+
+        match Syncing::deserialize(deserializer)? {
+            Syncing::None(false) => Ok(Self::None),
+            Syncing::None(true) => Err(serde::de::Error::custom(
+                "eth_syncing returned `true` that is undefined value.",
+            )),
+            Syncing::IsSyncing(info) => Ok(Self::Info(info)),
+        }
+    }
+}
+
+/// Syncing info of the `eth_syncing` response, see [`EthSyncStatus`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthSyncInfo {
+    /// The standard syncing info.
+    #[serde(flatten)]
+    pub info: SyncInfo,
+    /// The reth specific sync progress, only populated if enabled.
+    #[serde(flatten)]
+    pub progress: SyncProgress,
+}
+
+/// Reth specific progress of the pipeline, derived from the persisted stage checkpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    /// The stage the pipeline is currently running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_stage: Option<String>,
+    /// The number of entities the current stage has processed.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_processed: Option<u64>,
+    /// The total number of entities the current stage has to process.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_total: Option<u64>,
+    /// The estimated unix timestamp in seconds at which the current stage completes.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub estimated_completion: Option<u64>,
+}
+
+/// A throughput sample of the current stage.
+#[derive(Debug, Clone, Copy)]
+struct ProgressSample {
+    stage: StageId,
+    at: Instant,
+    processed: u64,
+}
+
+/// Computes the [`SyncProgress`] of the pipeline.
+///
+/// The estimated completion time is based on the throughput of the current stage within the last
+/// [`SYNC_THROUGHPUT_WINDOW`]. The throughput is sampled whenever the progress is requested, so
+/// the estimate becomes available from the second request onwards.
+#[derive(Debug, Default)]
+pub struct SyncProgressTracker {
+    samples: Mutex<VecDeque<ProgressSample>>,
+}
+
+impl SyncProgressTracker {
+    /// Returns the [`SyncProgress`] for the given stage checkpoints.
+    pub fn progress(&self, checkpoints: &[(String, StageCheckpoint)]) -> SyncProgress {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.progress_at(checkpoints, Instant::now(), timestamp)
+    }
+
+    fn progress_at(
+        &self,
+        checkpoints: &[(String, StageCheckpoint)],
+        now: Instant,
+        timestamp: u64,
+    ) -> SyncProgress {
+        let Some((stage, checkpoint)) = current_stage(checkpoints) else {
+            self.samples.lock().clear();
+            return SyncProgress::default()
+        };
+
+        let mut progress =
+            SyncProgress { current_stage: Some(stage.to_string()), ..Default::default() };
+        let Some(entities) = checkpoint.entities() else { return progress };
+        progress.entities_processed = Some(entities.processed);
+        progress.entities_total = Some(entities.total);
+
+        let mut samples = self.samples.lock();
+        if samples.back().is_some_and(|sample| sample.stage != stage) {
+            samples.clear();
+        }
+        if samples.back().is_none_or(|sample| now - sample.at >= SYNC_SAMPLE_INTERVAL) {
+            samples.push_back(ProgressSample { stage, at: now, processed: entities.processed });
+        }
+        while samples.front().is_some_and(|sample| now - sample.at > SYNC_THROUGHPUT_WINDOW) {
+            samples.pop_front();
+        }
+
+        if let (Some(first), Some(last)) = (samples.front(), samples.back()) {
+            let elapsed = (last.at - first.at).as_secs_f64();
+            let processed = last.processed.saturating_sub(first.processed);
+            if elapsed > 0.0 && processed > 0 {
+                let remaining = entities.total.saturating_sub(entities.processed);
+                let secs = (remaining as f64 / (processed as f64 / elapsed)).ceil() as u64;
+                progress.estimated_completion = Some(timestamp + secs);
+            }
+        }
+
+        progress
+    }
+}
+
+/// Returns the stage the pipeline is currently running, together with its checkpoint.
+///
+/// This is the first stage in pipeline order that is behind the most advanced stage, or if all
+/// stages are at the same block, the first stage that has not processed all of its entities.
+fn current_stage(checkpoints: &[(String, StageCheckpoint)]) -> Option<(StageId, StageCheckpoint)> {
+    let stages = StageId::ALL
+        .into_iter()
+        .filter_map(|id| {
+            let (_, checkpoint) = checkpoints.iter().find(|(name, _)| name == id.as_str())?;
+            Some((id, *checkpoint))
+        })
+        .collect::<Vec<_>>();
+
+    let tip = stages.iter().map(|(_, checkpoint)| checkpoint.block_number).max()?;
+    stages
+        .iter()
+        .find(|(_, checkpoint)| checkpoint.block_number < tip)
+        .or_else(|| {
+            stages.iter().find(|(_, checkpoint)| {
+                checkpoint.entities().is_some_and(|entities| entities.processed < entities.total)
+            })
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages_types::EntitiesCheckpoint;
+
+    fn checkpoint(
+        id: StageId,
+        block: u64,
+        entities: Option<(u64, u64)>,
+    ) -> (String, StageCheckpoint) {
+        let mut checkpoint = StageCheckpoint::new(block);
+        if let Some((processed, total)) = entities {
+            checkpoint =
+                checkpoint.with_entities_stage_checkpoint(EntitiesCheckpoint { processed, total });
+        }
+        (id.to_string(), checkpoint)
+    }
+
+    #[test]
+    fn sync_progress() {
+        let tracker = SyncProgressTracker::default();
+        let start = Instant::now();
+        let checkpoints = |processed| {
+            vec![
+                checkpoint(StageId::Headers, 100, None),
+                checkpoint(StageId::Bodies, 100, None),
+                checkpoint(StageId::SenderRecovery, 50, Some((processed, 1000))),
+                checkpoint(StageId::Execution, 50, None),
+            ]
+        };
+
+        let progress = tracker.progress_at(&checkpoints(100), start, 1_000);
+        assert_eq!(
+            progress,
+            SyncProgress {
+                current_stage: Some("SenderRecovery".to_string()),
+                entities_processed: Some(100),
+                entities_total: Some(1000),
+                estimated_completion: None,
+            }
+        );
+
+        // 100 entities per 10 seconds, 800 remaining
+        let progress =
+            tracker.progress_at(&checkpoints(200), start + Duration::from_secs(10), 1_010);
+        assert_eq!(progress.estimated_completion, Some(1_090));
+
+        // samples of a different stage are discarded
+        let checkpoints = [
+            checkpoint(StageId::Headers, 100, None),
+            checkpoint(StageId::Bodies, 100, None),
+            checkpoint(StageId::SenderRecovery, 100, None),
+            checkpoint(StageId::Execution, 50, Some((0, 10))),
+        ];
+        let progress = tracker.progress_at(&checkpoints, start + Duration::from_secs(20), 1_020);
+        assert_eq!(progress.current_stage.as_deref(), Some("Execution"));
+        assert_eq!(progress.estimated_completion, None);
+    }
+
+    #[test]
+    fn current_stage_with_incomplete_entities() {
+        let checkpoints = [
+            checkpoint(StageId::Headers, 100, None),
+            checkpoint(StageId::Bodies, 100, Some((10, 10))),
+            checkpoint(StageId::Execution, 100, Some((5, 10))),
+        ];
+        assert_eq!(current_stage(&checkpoints).map(|(id, _)| id), Some(StageId::Execution));
+
+        let checkpoints = [checkpoint(StageId::Headers, 100, None)];
+        assert_eq!(current_stage(&checkpoints), None);
+    }
+
+    #[test]
+    fn serde_sync_status() {
+        let status = EthSyncStatus::Info(Box::new(EthSyncInfo {
+            info: SyncInfo { current_block: alloy_primitives::U256::from(1), ..Default::default() },
+            progress: SyncProgress {
+                current_stage: Some("Execution".to_string()),
+                entities_processed: Some(1),
+                entities_total: Some(2),
+                estimated_completion: Some(3),
+            },
+        }));
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["currentStage"], "Execution");
+        assert_eq!(json["entitiesTotal"], "0x2");
+        assert_eq!(serde_json::from_value::<EthSyncStatus>(json.clone()).unwrap(), status);
+        // compatible with the standard response
+        assert!(matches!(serde_json::from_value(json).unwrap(), SyncStatus::Info(_)));
+
+        let status = EthSyncStatus::from(SyncStatus::None);
+        assert_eq!(serde_json::to_string(&status).unwrap(), "false");
+    }
+}
@@ -1,7 +1,12 @@
 //! Commonly used code snippets
 
 use super::{EthApiError, EthResult};
+use alloy_primitives::BlockNumber;
+use alloy_rpc_types_eth::Filter;
+use reth_errors::ProviderError;
 use reth_primitives_traits::{Recovered, SignedTransaction};
+use reth_prune_types::PruneSegment;
+use reth_storage_api::{BlockNumReader, PruneCheckpointReader};
 use std::future::Future;
 
 /// Recovers a [`SignedTransaction`] from an enveloped encoded byte stream.
@@ -22,6 +27,85 @@ pub fn recover_raw_transaction<T: SignedTransaction>(mut data: &[u8]) -> EthResu
         .or(Err(EthApiError::InvalidTransactionSignature))
 }
 
+/// Returns [`EthApiError::PrunedHistoryUnavailable`] if the receipts of the given block have been
+/// pruned.
+///
+/// This should be used to distinguish pruned receipts from missing ones, e.g. if the receipts of a
+/// block that exists can't be found.
+pub fn ensure_receipts_not_pruned(
+    provider: &impl PruneCheckpointReader,
+    block_number: BlockNumber,
+) -> EthResult<()> {
+    let pruned_block = provider
+        .get_prune_checkpoint(PruneSegment::Receipts)?
+        .and_then(|checkpoint| checkpoint.block_number);
+    match pruned_block {
+        Some(pruned_block) if block_number <= pruned_block => {
+            Err(EthApiError::PrunedHistoryUnavailable { earliest_block: pruned_block + 1 })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns [`EthApiError::PrunedHistoryUnavailable`] if the state of the given block can't be
+/// queried on a node whose state history has been pruned.
+///
+/// The earliest block that can be queried is the first block above the account and storage history
+/// prune checkpoints, but at most `max_state_history` blocks below the tip. Nodes that keep the
+/// full state history are not limited.
+pub fn ensure_state_history_not_pruned(
+    provider: &(impl PruneCheckpointReader + BlockNumReader),
+    block_number: BlockNumber,
+    max_state_history: u64,
+) -> EthResult<()> {
+    let mut pruned_block = None;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        let checkpoint = provider.get_prune_checkpoint(segment)?;
+        pruned_block = pruned_block.max(checkpoint.and_then(|checkpoint| checkpoint.block_number));
+    }
+    let Some(pruned_block) = pruned_block else { return Ok(()) };
+
+    let earliest_block =
+        (pruned_block + 1).max(provider.best_block_number()?.saturating_sub(max_state_history));
+    if block_number < earliest_block {
+        return Err(EthApiError::PrunedHistoryUnavailable { earliest_block })
+    }
+    Ok(())
+}
+
+/// Returns [`EthApiError::PrunedLogsUnavailable`] if logs matching the `filter` from the given
+/// block on may have been pruned by the receipts log filters.
+///
+/// Receipts with logs emitted by the retained addresses, or with the retained first topics, are
+/// kept, so this only fails if the filter isn't restricted to either of them.
+pub fn ensure_logs_not_pruned(
+    provider: &(impl PruneCheckpointReader + BlockNumReader),
+    filter: &Filter,
+    from_block: BlockNumber,
+) -> EthResult<()> {
+    let pruned_block = provider
+        .get_prune_checkpoint(PruneSegment::ContractLogs)?
+        .and_then(|checkpoint| checkpoint.block_number);
+    let Some(pruned_block) = pruned_block.filter(|pruned_block| from_block <= *pruned_block) else {
+        return Ok(())
+    };
+
+    let retained_from = provider
+        .prune_modes()?
+        .receipts_log_filter_retained_from(
+            provider.best_block_number()?,
+            filter.address.iter(),
+            filter.topics[0].iter(),
+        )
+        .map_err(ProviderError::from)?;
+    match retained_from {
+        Some(retained_from) if from_block >= retained_from => Ok(()),
+        retained_from => Err(EthApiError::PrunedLogsUnavailable {
+            earliest_block: retained_from.unwrap_or(u64::MAX).min(pruned_block + 1),
+        }),
+    }
+}
+
 /// Performs a binary search within a given block range to find the desired block number.
 ///
 /// The binary search is performed by calling the provided asynchronous `check` closure on the
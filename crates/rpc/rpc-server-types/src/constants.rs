@@ -32,6 +32,9 @@ pub fn default_max_tracing_requests() -> usize {
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
+/// The default number of calls of a batch request that are executed concurrently.
+pub const DEFAULT_BATCH_PARALLELISM: usize = 4;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -104,7 +107,6 @@ pub mod gas_oracle {
 
 /// Cache specific constants
 pub mod cache {
-    // TODO: memory based limiter is currently disabled pending <https://github.com/paradigmxyz/reth/issues/3503>
     /// Default cache size for the block cache: 500MB
     ///
     /// With an average block size of ~100kb this should be able to cache ~5000 blocks.
@@ -113,8 +115,12 @@ pub mod cache {
     /// Default cache size for the receipts cache: 500MB
     pub const DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB: usize = 500;
 
-    /// Default cache size for the env cache: 1MB
-    pub const DEFAULT_ENV_CACHE_SIZE_BYTES_MB: usize = 1;
+    /// Default cache size for the header cache: 1MB
+    pub const DEFAULT_HEADER_CACHE_SIZE_BYTES_MB: usize = 1;
+
+    /// Default cache size for the header cache: 1MB
+    #[deprecated(note = "use `DEFAULT_HEADER_CACHE_SIZE_BYTES_MB` instead")]
+    pub const DEFAULT_ENV_CACHE_SIZE_BYTES_MB: usize = DEFAULT_HEADER_CACHE_SIZE_BYTES_MB;
 
     /// Default cache size for the block cache: 5000 blocks.
     pub const DEFAULT_BLOCK_CACHE_MAX_LEN: u32 = 5000;
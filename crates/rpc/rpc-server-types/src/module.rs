@@ -273,6 +273,8 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `explorer_` module
+    Explorer,
 }
 
 // === impl RethRpcModule ===
@@ -324,6 +326,7 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "explorer" => Self::Explorer,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
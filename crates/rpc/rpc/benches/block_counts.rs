@@ -0,0 +1,115 @@
+//! Benchmarks for the `eth_` endpoints that count the transactions and uncles of a block.
+
+#![allow(missing_docs)]
+
+use alloy_eips::BlockNumberOrTag;
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_chainspec::ChainSpecProvider;
+use reth_db_api::models::StoredBlockBodyIndices;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_network_api::noop::NoopNetwork;
+use reth_provider::test_utils::MockEthProvider;
+use reth_rpc::EthApiBuilder;
+use reth_rpc_eth_api::EthApiServer;
+use reth_testing_utils::generators::{self, random_block, BlockParams};
+use reth_transaction_pool::test_utils::testing_pool;
+use std::hint::black_box;
+
+const BLOCK_COUNT: u64 = 64;
+const TX_COUNT: u8 = 200;
+
+/// Creates a provider with full blocks and their body indices, the first half of the blocks
+/// without uncles.
+fn create_provider() -> MockEthProvider {
+    let mut rng = generators::rng();
+    let provider = MockEthProvider::default();
+
+    let mut first_tx_num = 0;
+    for number in 0..BLOCK_COUNT {
+        let ommers_count = if number < BLOCK_COUNT / 2 { 0 } else { 2 };
+        let block = random_block(
+            &mut rng,
+            number,
+            BlockParams {
+                tx_count: Some(TX_COUNT),
+                ommers_count: Some(ommers_count),
+                ..Default::default()
+            },
+        );
+        provider.add_block_body_indices(
+            number,
+            StoredBlockBodyIndices { first_tx_num, tx_count: TX_COUNT as u64 },
+        );
+        first_tx_num += TX_COUNT as u64;
+        provider.add_block(block.hash(), block.into_block());
+    }
+
+    provider
+}
+
+fn block_counts(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let provider = create_provider();
+    let eth_api = runtime.block_on(async {
+        EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build()
+    });
+
+    let mut group = c.benchmark_group("Block Counts");
+
+    group.bench_function("eth_getBlockTransactionCountByNumber", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for number in 0..BLOCK_COUNT {
+                    black_box(
+                        eth_api
+                            .block_transaction_count_by_number(BlockNumberOrTag::Number(number))
+                            .await
+                            .unwrap(),
+                    );
+                }
+            })
+        })
+    });
+
+    group.bench_function("eth_getUncleCountByBlockNumber", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for number in 0..BLOCK_COUNT {
+                    black_box(
+                        eth_api
+                            .block_uncles_count_by_number(BlockNumberOrTag::Number(number))
+                            .await
+                            .unwrap(),
+                    );
+                }
+            })
+        })
+    });
+
+    // Baseline that loads the full blocks.
+    group.bench_function("eth_getBlockByNumber", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for number in 0..BLOCK_COUNT {
+                    black_box(
+                        eth_api
+                            .block_by_number(BlockNumberOrTag::Number(number), false)
+                            .await
+                            .unwrap(),
+                    );
+                }
+            })
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, block_counts);
+criterion_main!(benches);
@@ -1,20 +1,26 @@
+use crate::trace::{OpcodeStatsFrame, OPCODE_STATS_TRACER};
 use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{uint, Address, Bytes, B256};
+use alloy_primitives::{uint, Address, Bytes, Log, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
     state::EvmOverrides, Block as RpcBlock, BlockError, Bundle, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
-    call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
+    call::FlatCallFrame, BlockTraceResult, CallConfig, FourByteFrame, GethDebugBuiltInTracerType,
     GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage,
+};
+use rayon::prelude::*;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_errors::RethError;
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
     Block as _, BlockBody, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
@@ -24,27 +30,50 @@ use reth_revm::{
     db::{CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{DebugApiServer, StreamedCallFrame};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
-    helpers::{EthTransactions, TraceExt},
+    helpers::{Call, EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
+use reth_rpc_eth_types::{
+    cache::db::StateProviderTraitObjWrapper, limits::with_timeout, EthApiError, StateCacheDb,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_storage_api::{
     BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
     StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_trie::witness::WitnessFileWriter;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
-use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
-use revm_inspectors::tracing::{
-    FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
+use revm::{
+    context_interface::Transaction,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    state::EvmState,
+    DatabaseCommit, Inspector,
 };
-use std::sync::Arc;
+use revm_inspectors::{
+    opcode::OpcodeGasInspector,
+    tracing::{
+        types::CallTraceNode, FourByteInspector, MuxInspector, TracingInspector,
+        TracingInspectorConfig, TransactionContext,
+    },
+};
+use std::{ops::Range, sync::Arc, time::Duration};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// Minimum number of transactions traced in a row by one thread when tracing a block in parallel.
+///
+/// Each chunk requires a copy of the intermediate state, so tiny chunks aren't worth it.
+const MIN_PARALLEL_TRACE_CHUNK_SIZE: usize = 8;
+
+/// Maximum number of call frames streamed by `debug_subscribeTraceTransaction`.
+///
+/// The call trace is recorded in memory before it is streamed, so this bounds the memory used by a
+/// single subscription.
+const MAX_STREAMED_CALL_FRAMES: usize = 100_000;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -85,7 +114,22 @@ where
         self.inner.blocking_task_guard.clone().acquire_owned().await
     }
 
+    /// Returns the currently configured timeout of `debug_trace*` requests.
+    fn trace_timeout(&self) -> Option<Duration> {
+        self.eth_api().rpc_limits().debug_timeout()
+    }
+
     /// Trace the entire block asynchronously
+    ///
+    /// The transactions are traced in parallel: the block is split into chunks of consecutive
+    /// transactions that are traced on separate threads of the tracing pool. The intermediate state
+    /// at the start of each chunk is obtained by executing the preceding transactions without
+    /// tracing, which is cheap compared to tracing them. The traces of the chunks are then
+    /// assembled in order.
+    ///
+    /// Every chunk beyond the first occupies an additional tracing permit, so the parallelism is
+    /// bounded by the permits that are currently available and the block is traced serially if
+    /// there are none.
     async fn trace_block(
         &self,
         block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
@@ -96,46 +140,112 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
-                let mut results = Vec::with_capacity(block.body().transactions().len());
-                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let state = state.0;
+                let mut db =
+                    CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(state)));
 
                 this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
 
-                let mut transactions = block.transactions_recovered().enumerate().peekable();
-                let mut inspector = None;
-                while let Some((index, tx)) = transactions.next() {
-                    let tx_hash = *tx.tx_hash();
+                let tx_count = block.body().transactions().len();
+                let max_chunks = tx_count
+                    .div_ceil(MIN_PARALLEL_TRACE_CHUNK_SIZE)
+                    .min(rayon::current_num_threads());
+                // held until all chunks are traced
+                let extra_permits = this
+                    .inner
+                    .blocking_task_guard
+                    .try_acquire_up_to_owned(max_chunks.saturating_sub(1) as u32);
+                let num_chunks = 1 + extra_permits.as_ref().map_or(0, |p| p.num_permits());
+                let chunk_size = tx_count.div_ceil(num_chunks).max(MIN_PARALLEL_TRACE_CHUNK_SIZE);
+                if chunk_size >= tx_count {
+                    return this.trace_transactions(&block, &evm_env, &opts, db, 0..tx_count)
+                }
 
+                // cache the intermediate state at the start of each chunk
+                let mut chunk_states = vec![db.cache.clone()];
+                let last_chunk_start = tx_count - (tx_count - 1) % chunk_size - 1;
+                for (index, tx) in block.transactions_recovered().enumerate().take(last_chunk_start)
+                {
                     let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let res = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                    db.commit(res.state);
 
-                    let (result, state_changes) = this.trace_transaction(
-                        &opts,
-                        evm_env.clone(),
-                        tx_env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash: Some(block.hash()),
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                        &mut inspector,
-                    )?;
-
-                    inspector = inspector.map(|insp| insp.fused());
-
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
+                    if (index + 1) % chunk_size == 0 {
+                        chunk_states.push(db.cache.clone());
                     }
                 }
 
-                Ok(results)
+                let chunks = chunk_states
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(chunk, cache)| {
+                        let db = CacheDB {
+                            cache,
+                            db: StateProviderDatabase::new(StateProviderTraitObjWrapper(state)),
+                        };
+                        let start = chunk * chunk_size;
+                        let end = (start + chunk_size).min(tx_count);
+                        this.trace_transactions(&block, &evm_env, &opts, db, start..end)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                drop(extra_permits);
+
+                Ok(chunks.into_iter().flatten().collect())
             })
             .await
     }
 
+    /// Traces the transactions of the block in the given index range, one after another, on top
+    /// of the given state at the start of the range.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn trace_transactions(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Eth::Provider>>,
+        evm_env: &EvmEnvFor<Eth::Evm>,
+        opts: &GethDebugTracingOptions,
+        mut db: StateCacheDb<'_>,
+        range: Range<usize>,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let mut results = Vec::with_capacity(range.len());
+        let mut transactions = block
+            .transactions_recovered()
+            .enumerate()
+            .skip(range.start)
+            .take(range.len())
+            .peekable();
+        let mut inspector = None;
+        while let Some((index, tx)) = transactions.next() {
+            let tx_hash = *tx.tx_hash();
+
+            let tx_env = self.eth_api().evm_config().tx_env(tx);
+
+            let (result, state_changes) = self.trace_transaction(
+                opts,
+                evm_env.clone(),
+                tx_env,
+                &mut db,
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &mut inspector,
+            )?;
+
+            inspector = inspector.map(|insp| insp.fused());
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+            if transactions.peek().is_some() {
+                // need to apply the state changes of this transaction before executing the
+                // next transaction
+                db.commit(state_changes)
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Replays the given block and returns the trace of each transaction.
     ///
     /// This expects a rlp encoded block
@@ -257,6 +367,66 @@ where
             .await
     }
 
+    /// Traces the transaction with the call tracer and returns the recorded call trace nodes and
+    /// the gas used by the transaction.
+    ///
+    /// Unlike [`Self::debug_trace_transaction`] this doesn't build the nested call frames, so they
+    /// can be converted one at a time by the caller.
+    ///
+    /// At most `max_nodes` calls are recorded, so that transactions with a huge number of calls
+    /// can't exhaust the memory of the node. If the transaction makes more calls, this fails,
+    /// unless only the top call is requested.
+    pub async fn debug_trace_transaction_call_nodes(
+        &self,
+        tx_hash: B256,
+        call_config: CallConfig,
+        max_nodes: usize,
+    ) -> Result<(Vec<CallTraceNode>, u64), Eth::Error> {
+        let (transaction, block) = match self.eth_api().transaction_and_block(tx_hash).await? {
+            None => return Err(EthApiError::TransactionNotFound.into()),
+            Some(res) => res,
+        };
+        let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+
+        let only_top_call = call_config.only_top_call.unwrap_or_default();
+        let max_nodes = if only_top_call { 1 } else { max_nodes };
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let tx = transaction.into_recovered();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                this.eth_api().replay_transactions_until(
+                    &mut db,
+                    evm_env.clone(),
+                    block.transactions_recovered(),
+                    *tx.tx_hash(),
+                )?;
+
+                let tx_env = this.eth_api().evm_config().tx_env(&tx);
+                let mut inspector = CallLimitInspector::new(
+                    TracingInspector::new(TracingInspectorConfig::from_geth_call_config(
+                        &call_config,
+                    )),
+                    max_nodes,
+                );
+                let res = this.eth_api().inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+                if inspector.exceeded && !only_top_call {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "call trace exceeds the limit of {max_nodes} call frames"
+                    ))
+                    .into())
+                }
+
+                Ok((inspector.inner.into_traces().into_nodes(), res.result.gas_used()))
+            })
+            .await
+    }
+
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
     /// block execution using the final state of parent block as the base.
     ///
@@ -272,209 +442,21 @@ where
         let GethDebugTracingCallOptions { tracing_options, state_overrides, block_overrides } =
             opts;
         let overrides = EvmOverrides::new(state_overrides, block_overrides.map(Box::new));
-        let GethDebugTracingOptions { config, tracer, tracer_config, .. } = tracing_options;
 
+        // The overrides are applied to the database and the evm env before any tracer is set up,
+        // so all tracers observe the same state and block.
         let this = self.clone();
-        if let Some(tracer) = tracer {
-            #[allow(unreachable_patterns)]
-            return match tracer {
-                GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
-                    GethDebugBuiltInTracerType::FourByteTracer => {
-                        let mut inspector = FourByteInspector::default();
-                        let inspector = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                                this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
-                                Ok(inspector)
-                            })
-                            .await?;
-                        Ok(FourByteFrame::from(&inspector).into())
-                    }
-                    GethDebugBuiltInTracerType::CallTracer => {
-                        let call_config = tracer_config
-                            .into_call_config()
-                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
-
-                        let mut inspector = TracingInspector::new(
-                            TracingInspectorConfig::from_geth_call_config(&call_config),
-                        );
-
-                        let frame = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                                let gas_limit = tx_env.gas_limit();
-                                let res =
-                                    this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
-                                let frame = inspector
-                                    .with_transaction_gas_limit(gas_limit)
-                                    .into_geth_builder()
-                                    .geth_call_traces(call_config, res.result.gas_used());
-                                Ok(frame.into())
-                            })
-                            .await?;
-                        Ok(frame)
-                    }
-                    GethDebugBuiltInTracerType::PreStateTracer => {
-                        let prestate_config = tracer_config
-                            .into_pre_state_config()
-                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
-                        let mut inspector = TracingInspector::new(
-                            TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
-                        );
-
-                        let frame = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                                // wrapper is hack to get around 'higher-ranked lifetime error',
-                                // see <https://github.com/rust-lang/rust/issues/100013>
-                                let db = db.0;
-
-                                let gas_limit = tx_env.gas_limit();
-                                let res = this.eth_api().inspect(
-                                    &mut *db,
-                                    evm_env,
-                                    tx_env,
-                                    &mut inspector,
-                                )?;
-                                let frame = inspector
-                                    .with_transaction_gas_limit(gas_limit)
-                                    .into_geth_builder()
-                                    .geth_prestate_traces(&res, &prestate_config, db)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                                Ok(frame)
-                            })
-                            .await?;
-                        Ok(frame.into())
-                    }
-                    GethDebugBuiltInTracerType::NoopTracer => Ok(NoopFrame::default().into()),
-                    GethDebugBuiltInTracerType::MuxTracer => {
-                        let mux_config = tracer_config
-                            .into_mux_config()
-                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
-
-                        let mut inspector = MuxInspector::try_from_config(mux_config)
-                            .map_err(Eth::Error::from_eth_err)?;
-
-                        let frame = self
-                            .inner
-                            .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                                // wrapper is hack to get around 'higher-ranked lifetime error', see
-                                // <https://github.com/rust-lang/rust/issues/100013>
-                                let db = db.0;
-
-                                let tx_info = TransactionInfo {
-                                    block_number: Some(evm_env.block_env.number.saturating_to()),
-                                    base_fee: Some(evm_env.block_env.basefee),
-                                    hash: None,
-                                    block_hash: None,
-                                    index: None,
-                                };
-
-                                let res = this.eth_api().inspect(
-                                    &mut *db,
-                                    evm_env,
-                                    tx_env,
-                                    &mut inspector,
-                                )?;
-                                let frame = inspector
-                                    .try_into_mux_frame(&res, db, tx_info)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                                Ok(frame.into())
-                            })
-                            .await?;
-                        Ok(frame)
-                    }
-                    GethDebugBuiltInTracerType::FlatCallTracer => {
-                        let flat_call_config = tracer_config
-                            .into_flat_call_config()
-                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
-
-                        let mut inspector = TracingInspector::new(
-                            TracingInspectorConfig::from_flat_call_config(&flat_call_config),
-                        );
-
-                        let frame: FlatCallFrame = self
-                            .inner
-                            .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                                let gas_limit = tx_env.gas_limit();
-                                this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
-                                let tx_info = TransactionInfo::default();
-                                let frame: FlatCallFrame = inspector
-                                    .with_transaction_gas_limit(gas_limit)
-                                    .into_parity_builder()
-                                    .into_localized_transaction_traces(tx_info);
-                                Ok(frame)
-                            })
-                            .await?;
-
-                        Ok(frame.into())
-                    }
-                },
-                #[cfg(not(feature = "js-tracer"))]
-                GethDebugTracerType::JsTracer(_) => {
-                    Err(EthApiError::Unsupported("JS Tracer is not enabled").into())
-                }
-                #[cfg(feature = "js-tracer")]
-                GethDebugTracerType::JsTracer(code) => {
-                    let config = tracer_config.into_json();
-
-                    let (_, at) = self.eth_api().evm_env_at(at).await?;
-
-                    let res = self
-                        .eth_api()
-                        .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                            // wrapper is hack to get around 'higher-ranked lifetime error', see
-                            // <https://github.com/rust-lang/rust/issues/100013>
-                            let db = db.0;
-
-                            let mut inspector =
-                                revm_inspectors::tracing::js::JsInspector::new(code, config)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                            let res = this.eth_api().inspect(
-                                &mut *db,
-                                evm_env.clone(),
-                                tx_env.clone(),
-                                &mut inspector,
-                            )?;
-                            inspector
-                                .json_result(res, &tx_env, &evm_env.block_env, db)
-                                .map_err(Eth::Error::from_eth_err)
-                        })
-                        .await?;
-
-                    Ok(GethTrace::JS(res))
-                }
-                _ => {
-                    // Note: this match is non-exhaustive in case we need to add support for
-                    // additional tracers
-                    Err(EthApiError::Unsupported("unsupported tracer").into())
-                }
-            }
-        }
-
-        // default structlog tracer
-        let inspector_config = TracingInspectorConfig::from_geth_config(&config);
-
-        let mut inspector = TracingInspector::new(inspector_config);
-
-        let (res, tx_gas_limit, inspector) = self
-            .eth_api()
-            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                let gas_limit = tx_env.gas_limit();
-                let res = this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
-                Ok((res, gas_limit, inspector))
+        self.eth_api()
+            .spawn_with_call_at(call, at, overrides, false, move |db, evm_env, tx_env| {
+                // wrapper is hack to get around 'higher-ranked lifetime error', see
+                // <https://github.com/rust-lang/rust/issues/100013>
+                let db = db.0;
+
+                let (trace, _) =
+                    this.trace_transaction(&tracing_options, evm_env, tx_env, db, None, &mut None)?;
+                Ok(trace)
             })
-            .await?;
-        let gas_used = res.result.gas_used();
-        let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector
-            .with_transaction_gas_limit(tx_gas_limit)
-            .into_geth_builder()
-            .geth_traces(gas_used, return_value, config);
-
-        Ok(frame.into())
+            .await
     }
 
     /// The `debug_traceCallMany` method lets you run an `eth_callMany` within the context of the
@@ -560,6 +542,7 @@ where
                             tx,
                             &mut db,
                             overrides,
+                            false,
                         )?;
 
                         let (trace, state) = this.trace_transaction(
@@ -650,9 +633,19 @@ where
                 let ExecutionWitnessRecord { hashed_state, codes, keys, lowest_block_number } =
                     witness_record;
 
-                let state = state_provider
-                    .witness(Default::default(), hashed_state)
+                // the witness is spilled to a temporary file while it's gathered, and only read
+                // back into memory for the response
+                let mut witness = WitnessFileWriter::new()
+                    .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+                state_provider
+                    .witness_into(Default::default(), hashed_state, &mut witness)
                     .map_err(EthApiError::from)?;
+                let witness =
+                    witness.finish().map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+                let state = witness
+                    .nodes()
+                    .and_then(|nodes| nodes.map(|node| Ok(node?.1)).collect())
+                    .map_err(|err: std::io::Error| EthApiError::Internal(RethError::other(err)))?;
                 Ok((
                     ExecutionWitness { state, codes, keys, ..Default::default() },
                     lowest_block_number,
@@ -740,8 +733,18 @@ where
 
         if let Some(tracer) = tracer {
             #[allow(unreachable_patterns)]
-            return match tracer {
-                GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
+            return match DebugTracer::new(tracer) {
+                DebugTracer::OpcodeStats => {
+                    let mut inspector = OpcodeGasInspector::default();
+                    let res = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
+                    let frame = OpcodeStatsFrame::new(&inspector, res.result.gas_used());
+                    // `GethTrace` has no variant for tracers that geth doesn't know, so the frame
+                    // is returned as plain JSON
+                    let frame = serde_json::to_value(frame)
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+                    Ok((GethTrace::JS(frame), res.state))
+                }
+                DebugTracer::Geth(GethDebugTracerType::BuiltInTracer(tracer)) => match tracer {
                     GethDebugBuiltInTracerType::FourByteTracer => {
                         let mut inspector = FourByteInspector::default();
                         let res = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
@@ -833,11 +836,11 @@ where
                     }
                 },
                 #[cfg(not(feature = "js-tracer"))]
-                GethDebugTracerType::JsTracer(_) => {
+                DebugTracer::Geth(GethDebugTracerType::JsTracer(_)) => {
                     Err(EthApiError::Unsupported("JS Tracer is not enabled").into())
                 }
                 #[cfg(feature = "js-tracer")]
-                GethDebugTracerType::JsTracer(code) => {
+                DebugTracer::Geth(GethDebugTracerType::JsTracer(code)) => {
                     let config = tracer_config.clone().into_json();
                     let mut inspector =
                         revm_inspectors::tracing::js::JsInspector::with_transaction_context(
@@ -995,10 +998,9 @@ where
         rlp_block: Bytes,
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_raw_block(self, rlp_block, opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_raw_block(self, rlp_block, opts.unwrap_or_default());
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
     }
 
     /// Handler for `debug_traceBlockByHash`
@@ -1007,10 +1009,9 @@ where
         block: B256,
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_block(self, block.into(), opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_block(self, block.into(), opts.unwrap_or_default());
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
     }
 
     /// Handler for `debug_traceBlockByNumber`
@@ -1019,10 +1020,9 @@ where
         block: BlockNumberOrTag,
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_block(self, block.into(), opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_block(self, block.into(), opts.unwrap_or_default());
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
     }
 
     /// Handler for `debug_traceTransaction`
@@ -1031,10 +1031,64 @@ where
         tx_hash: B256,
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTrace> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_transaction(self, tx_hash, opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_transaction(self, tx_hash, opts.unwrap_or_default());
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
+    }
+
+    /// Handler for `debug_subscribeTraceTransaction`
+    async fn debug_subscribe_trace_transaction(
+        &self,
+        pending: PendingSubscriptionSink,
+        tx_hash: B256,
+        config: Option<CallConfig>,
+    ) -> SubscriptionResult {
+        let config = config.unwrap_or_default();
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            self.debug_trace_transaction_call_nodes(tx_hash, config, MAX_STREAMED_CALL_FRAMES).await
+        };
+        let (nodes, gas_used) = match res {
+            Ok(res) => res,
+            Err(err) => {
+                pending.reject(err.into()).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let include_logs = config.with_log.unwrap_or_default();
+        let total = if config.only_top_call.unwrap_or_default() { 1 } else { nodes.len() };
+
+        // whether the call or any of its parents failed, logs are only included for successful
+        // calls
+        let mut failed = Vec::with_capacity(total);
+        for (index, node) in nodes.iter().take(total).enumerate() {
+            let call_or_parent_failed =
+                !node.trace.success || node.parent.is_some_and(|parent| failed[parent]);
+            failed.push(call_or_parent_failed);
+
+            let mut frame = node.geth_empty_call_frame(
+                include_logs && (node.parent.is_none() || !call_or_parent_failed),
+            );
+            if node.parent.is_none() {
+                frame.gas_used = U256::from(gas_used);
+            }
+            // selfdestructs are not recorded as individual call traces but are derived from the
+            // call trace
+            if let Some(selfdestruct) = node.geth_selfdestruct_call_trace() {
+                frame.calls.push(selfdestruct);
+            }
+
+            let item = StreamedCallFrame { index, parent: node.parent, total, frame };
+            let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)?;
+            if sink.send(msg).await.is_err() {
+                // connection dropped
+                break
+            }
+        }
+
+        Ok(())
     }
 
     /// Handler for `debug_traceCall`
@@ -1044,10 +1098,9 @@ where
         block_id: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<GethTrace> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_call(self, request, block_id, opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_call(self, request, block_id, opts.unwrap_or_default());
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
     }
 
     async fn debug_trace_call_many(
@@ -1056,8 +1109,9 @@ where
         state_context: Option<StateContext>,
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<Vec<Vec<GethTrace>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_call_many(self, bundles, state_context, opts).await.map_err(Into::into)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::debug_trace_call_many(self, bundles, state_context, opts);
+        with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)
     }
 
     /// Handler for `debug_executionWitness`
@@ -1328,3 +1382,329 @@ struct DebugApiInner<Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+/// Forwards to the wrapped [`TracingInspector`] until it recorded `max_calls` calls and ignores
+/// all further calls, so that the memory used by the recorded trace is bounded.
+#[derive(Debug)]
+struct CallLimitInspector {
+    inner: TracingInspector,
+    /// Maximum number of calls to record.
+    max_calls: usize,
+    /// Number of recorded calls.
+    calls: usize,
+    /// Depth of the ignored calls that are currently executed, `0` if the current call is
+    /// recorded.
+    ignored_depth: usize,
+    /// Whether more than `max_calls` calls were made.
+    exceeded: bool,
+}
+
+impl CallLimitInspector {
+    const fn new(inner: TracingInspector, max_calls: usize) -> Self {
+        Self { inner, max_calls, calls: 0, ignored_depth: 0, exceeded: false }
+    }
+
+    /// Returns true if the call that is entered should be recorded.
+    const fn enter(&mut self) -> bool {
+        if self.ignored_depth == 0 && self.calls < self.max_calls {
+            self.calls += 1;
+            return true
+        }
+        self.exceeded = true;
+        self.ignored_depth += 1;
+        false
+    }
+
+    /// Returns true if the call that is exited was recorded.
+    const fn exit(&mut self) -> bool {
+        if self.ignored_depth == 0 {
+            return true
+        }
+        self.ignored_depth -= 1;
+        false
+    }
+}
+
+impl<CTX> Inspector<CTX> for CallLimitInspector
+where
+    TracingInspector: Inspector<CTX>,
+{
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.ignored_depth == 0 {
+            self.inner.initialize_interp(interp, context)
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.ignored_depth == 0 {
+            self.inner.step(interp, context)
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.ignored_depth == 0 {
+            self.inner.step_end(interp, context)
+        }
+    }
+
+    fn log(&mut self, interp: &mut Interpreter, context: &mut CTX, log: Log) {
+        if self.ignored_depth == 0 {
+            self.inner.log(interp, context, log)
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if self.enter() {
+            return self.inner.call(context, inputs)
+        }
+        None
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if self.exit() {
+            self.inner.call_end(context, inputs, outcome)
+        }
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        if self.enter() {
+            return self.inner.create(context, inputs)
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if self.exit() {
+            self.inner.create_end(context, inputs, outcome)
+        }
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if self.ignored_depth == 0 {
+            self.inner.selfdestruct(contract, target, value)
+        }
+    }
+}
+
+/// The tracer selected by the `tracer` option of the `debug_trace*` endpoints.
+#[derive(Debug)]
+enum DebugTracer<'a> {
+    /// A built-in or JS tracer of geth.
+    Geth(&'a GethDebugTracerType),
+    /// The built-in [`OPCODE_STATS_TRACER`].
+    OpcodeStats,
+}
+
+impl<'a> DebugTracer<'a> {
+    /// Resolves the tracer of the options.
+    ///
+    /// Names of built-in tracers that geth doesn't know are deserialized as JS code, so they are
+    /// resolved before they can be executed as such.
+    fn new(tracer: &'a GethDebugTracerType) -> Self {
+        match tracer {
+            GethDebugTracerType::JsTracer(name) if name == OPCODE_STATS_TRACER => Self::OpcodeStats,
+            tracer => Self::Geth(tracer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth::helpers::types::EthRpcConverter, EthApi};
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{hex, TxKind};
+    use reth_chainspec::ChainSpec;
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
+    use reth_testing_utils::generators::{self, Rng};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestDebugApi = DebugApi<
+        EthApi<
+            RpcNodeCoreAdapter<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>,
+            EthRpcConverter<ChainSpec>,
+        >,
+    >;
+
+    fn debug_api(provider: MockEthProvider, max_tracing_requests: usize) -> TestDebugApi {
+        let eth_api = EthApi::builder(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        DebugApi::new(eth_api, BlockingTaskGuard::new(max_tracing_requests))
+    }
+
+    /// Traces the given raw block with a tracing guard of the given size.
+    async fn trace_raw_block(
+        provider: MockEthProvider,
+        max_tracing_requests: usize,
+        rlp_block: Bytes,
+    ) -> Vec<TraceResult> {
+        let api = debug_api(provider, max_tracing_requests);
+        let opts = GethDebugTracingOptions::default().with_tracer(
+            GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer),
+        );
+
+        DebugApiServer::debug_trace_block(&api, rlp_block, Some(opts)).await.unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn parallel_block_trace_matches_serial() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let recipient: Address = rng.random();
+
+        // all transactions credit the same recipient, so the prestate of each transaction
+        // depends on all the preceding ones
+        let transactions = generators::generate_keys(&mut rng, 8 * MIN_PARALLEL_TRACE_CHUNK_SIZE)
+            .into_iter()
+            .map(|key_pair| {
+                generators::sign_tx_with_key_pair(
+                    key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        gas_limit: 21_000,
+                        to: TxKind::Call(recipient),
+                        value: U256::from(1),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        provider.extend_accounts(
+            transactions.iter().map(|tx| {
+                (tx.recover_signer().unwrap(), ExtendedAccount::new(0, U256::from(1_000)))
+            }),
+        );
+
+        let block = Block {
+            header: Header { number: 1, gas_limit: 30_000_000, ..Default::default() },
+            body: BlockBody { transactions, ..Default::default() },
+        };
+        let rlp_block = Bytes::from(alloy_rlp::encode(&block));
+
+        // the caller holds the only permit, so the block is traced serially
+        let serial = trace_raw_block(provider.clone(), 1, rlp_block.clone()).await;
+        let parallel = trace_raw_block(provider, 64, rlp_block).await;
+
+        assert_eq!(serial.len(), block.body.transactions.len());
+        assert_eq!(
+            serde_json::to_value(&serial).unwrap(),
+            serde_json::to_value(&parallel).unwrap()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_trace_nodes_are_limited() {
+        let provider = MockEthProvider::default();
+        let contract = Address::with_last_byte(0xaa);
+
+        // calls 0xbb three times:
+        // PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0xbb GAS CALL POP
+        let call = hex!("6000600060006000600060bb5af150");
+        let code = [call, call, call].concat();
+        provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.into()));
+
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let tx = generators::sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                gas_limit: 1_000_000,
+                to: TxKind::Call(contract),
+                ..Default::default()
+            }),
+        );
+        let tx_hash = *tx.tx_hash();
+        provider.add_account(tx.recover_signer().unwrap(), ExtendedAccount::new(0, U256::ZERO));
+
+        let block = Block {
+            header: Header { number: 1, gas_limit: 30_000_000, ..Default::default() },
+            body: BlockBody { transactions: vec![tx], ..Default::default() },
+        };
+        provider.add_block(block.header.hash_slow(), block);
+
+        let api = debug_api(provider, 1);
+        let (nodes, _) = api
+            .debug_trace_transaction_call_nodes(tx_hash, CallConfig::default(), 10)
+            .await
+            .unwrap();
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(nodes.iter().filter(|node| node.parent == Some(0)).count(), 3);
+
+        let err = api.debug_trace_transaction_call_nodes(tx_hash, CallConfig::default(), 2).await;
+        assert!(err.is_err());
+
+        // only the top call is recorded
+        let (nodes, _) = api
+            .debug_trace_transaction_call_nodes(tx_hash, CallConfig::default().only_top_call(), 2)
+            .await
+            .unwrap();
+        assert_eq!(nodes.len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn opcode_stats_tracer() {
+        let provider = MockEthProvider::default();
+        let contract = Address::with_last_byte(0xaa);
+
+        // calls 0xbb twice:
+        // PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0xbb GAS CALL POP
+        let call = hex!("6000600060006000600060bb5af150");
+        let code = [call, call].concat();
+        provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.into()));
+
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let tx = generators::sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                gas_limit: 1_000_000,
+                to: TxKind::Call(contract),
+                ..Default::default()
+            }),
+        );
+        let tx_hash = *tx.tx_hash();
+        provider.add_account(tx.recover_signer().unwrap(), ExtendedAccount::new(0, U256::ZERO));
+
+        let block = Block {
+            header: Header { number: 1, gas_limit: 30_000_000, ..Default::default() },
+            body: BlockBody { transactions: vec![tx], ..Default::default() },
+        };
+        provider.add_block(block.header.hash_slow(), block);
+
+        let tracer = GethDebugTracerType::JsTracer(OPCODE_STATS_TRACER.to_string());
+        assert!(matches!(DebugTracer::new(&tracer), DebugTracer::OpcodeStats));
+
+        let api = debug_api(provider, 1);
+        let opts = GethDebugTracingOptions::default().with_tracer(tracer);
+        let GethTrace::JS(frame) = api.debug_trace_transaction(tx_hash, opts).await.unwrap() else {
+            panic!("expected opcode stats frame")
+        };
+
+        let count = |opcode: &str| {
+            frame["opcodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|stats| stats["opcode"] == opcode)
+                .map(|stats| stats["count"].as_u64().unwrap())
+        };
+        assert_eq!(count("PUSH1"), Some(12));
+        assert_eq!(count("CALL"), Some(2));
+        assert_eq!(count("POP"), Some(2));
+        assert_eq!(count("SSTORE"), None);
+        assert!(frame["gasUsed"].as_u64().unwrap() > 21_000);
+    }
+}
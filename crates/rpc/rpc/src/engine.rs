@@ -56,7 +56,7 @@ where
     fn syncing(&self) -> Result<SyncStatus> {
         let span = engine_span!();
         let _enter = span.enter();
-        self.eth.syncing()
+        self.eth.syncing().map(Into::into)
     }
 
     /// Handler for: `eth_chainId`
@@ -12,10 +12,11 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{
     builder::config::PendingBlockKind, fee_history::fee_history_cache_new_blocks_task,
     receipt::EthReceiptConverter, EthStateCache, EthStateCacheConfig, FeeHistoryCache,
-    FeeHistoryCacheConfig, GasCap, GasPriceOracle, GasPriceOracleConfig,
+    FeeHistoryCacheConfig, GasCap, GasPriceOracle, GasPriceOracleConfig, RpcLimits, RpcTimeouts,
 };
 use reth_rpc_server_types::constants::{
     DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_TX_FEE_CAP_WEI,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use std::sync::Arc;
@@ -29,8 +30,11 @@ pub struct EthApiBuilder<N: RpcNodeCore, Rpc, NextEnv = ()> {
     components: N,
     rpc_converter: Rpc,
     gas_cap: GasCap,
+    tx_fee_cap: u128,
     max_simulate_blocks: u64,
     eth_proof_window: u64,
+    max_state_history: Option<u64>,
+    timeouts: RpcTimeouts,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
     eth_state_cache_config: EthStateCacheConfig,
@@ -42,6 +46,7 @@ pub struct EthApiBuilder<N: RpcNodeCore, Rpc, NextEnv = ()> {
     next_env: NextEnv,
     max_batch_size: usize,
     pending_block_kind: PendingBlockKind,
+    sync_progress: bool,
 }
 
 impl<Provider, Pool, Network, EvmConfig, ChainSpec>
@@ -69,8 +74,11 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             components,
             rpc_converter,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -82,13 +90,17 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         } = self;
         EthApiBuilder {
             components,
             rpc_converter: f(rpc_converter),
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -100,6 +112,7 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         }
     }
 }
@@ -118,8 +131,11 @@ where
             eth_cache: None,
             gas_oracle: None,
             gas_cap: GasCap::default(),
+            tx_fee_cap: DEFAULT_TX_FEE_CAP_WEI,
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_state_history: None,
+            timeouts: RpcTimeouts::default(),
             blocking_task_pool: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
@@ -129,6 +145,7 @@ where
             next_env: Default::default(),
             max_batch_size: 1,
             pending_block_kind: PendingBlockKind::Full,
+            sync_progress: false,
         }
     }
 }
@@ -152,8 +169,11 @@ where
             components,
             rpc_converter: _,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -165,13 +185,17 @@ where
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         } = self;
         EthApiBuilder {
             components,
             rpc_converter,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -183,6 +207,7 @@ where
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         }
     }
 
@@ -195,8 +220,11 @@ where
             components,
             rpc_converter,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -208,13 +236,17 @@ where
             next_env: _,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         } = self;
         EthApiBuilder {
             components,
             rpc_converter,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -226,6 +258,7 @@ where
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         }
     }
 
@@ -264,6 +297,12 @@ where
         self
     }
 
+    /// Sets the maximum fee in wei of transactions submitted via the RPC, `0` disables the cap.
+    pub const fn tx_fee_cap(mut self, tx_fee_cap: u128) -> Self {
+        self.tx_fee_cap = tx_fee_cap;
+        self
+    }
+
     /// Sets the maximum number of blocks for `eth_simulateV1`.
     pub const fn max_simulate_blocks(mut self, max_simulate_blocks: u64) -> Self {
         self.max_simulate_blocks = max_simulate_blocks;
@@ -276,6 +315,19 @@ where
         self
     }
 
+    /// Sets the maximum number of blocks into the past at which state can be queried, `None` if
+    /// unlimited.
+    pub const fn max_state_history(mut self, max_state_history: Option<u64>) -> Self {
+        self.max_state_history = max_state_history;
+        self
+    }
+
+    /// Sets the maximum execution time of EVM backed requests per namespace.
+    pub const fn timeouts(mut self, timeouts: RpcTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     /// Sets the blocking task pool.
     pub fn blocking_task_pool(mut self, blocking_task_pool: BlockingTaskPool) -> Self {
         self.blocking_task_pool = Some(blocking_task_pool);
@@ -309,6 +361,12 @@ where
         self
     }
 
+    /// Sets whether to include the reth specific sync progress in `eth_syncing` responses
+    pub const fn sync_progress(mut self, sync_progress: bool) -> Self {
+        self.sync_progress = sync_progress;
+        self
+    }
+
     /// Builds the [`EthApiInner`] instance.
     ///
     /// If not configured, this will spawn the cache backend: [`EthStateCache::spawn`].
@@ -330,8 +388,11 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            tx_fee_cap,
             max_simulate_blocks,
             eth_proof_window,
+            max_state_history,
+            timeouts,
             blocking_task_pool,
             fee_history_cache_config,
             proof_permits,
@@ -339,6 +400,7 @@ where
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         } = self;
 
         let provider = components.provider().clone();
@@ -364,7 +426,7 @@ where
             components,
             eth_cache,
             gas_oracle,
-            gas_cap,
+            RpcLimits { gas_cap: gas_cap.into(), tx_fee_cap, timeouts, max_state_history },
             max_simulate_blocks,
             eth_proof_window,
             blocking_task_pool.unwrap_or_else(|| {
@@ -377,6 +439,7 @@ where
             next_env,
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         )
     }
 
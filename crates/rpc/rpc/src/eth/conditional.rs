@@ -0,0 +1,47 @@
+//! `eth_sendRawTransactionConditional` implementation.
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::{
+    helpers::{EthTransactions, LoadPendingBlock, LoadState},
+    L2EthApiExtServer,
+};
+
+/// `Eth` API for submitting conditional transactions, as used by account abstraction bundlers.
+///
+/// The preconditions are checked against the pending block before the transaction is submitted
+/// to the pool.
+#[derive(Debug, Clone)]
+pub struct EthTransactionConditional<Eth> {
+    /// The `Eth` API to submit the transactions with.
+    eth_api: Eth,
+}
+
+impl<Eth> EthTransactionConditional<Eth> {
+    /// Creates a new `EthTransactionConditional` instance.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> L2EthApiExtServer for EthTransactionConditional<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + LoadState + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        EthTransactions::send_raw_transaction_conditional(&self.eth_api, bytes, condition)
+            .await
+            .map_err(Into::into)
+    }
+}
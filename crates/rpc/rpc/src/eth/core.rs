@@ -21,7 +21,8 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     builder::config::PendingBlockKind, receipt::EthReceiptConverter, EthApiError, EthStateCache,
-    FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock,
+    FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock, RpcLimits, RpcLimitsHandle,
+    SyncProgressTracker,
 };
 use reth_storage_api::{noop::NoopProvider, BlockReaderIdExt, ProviderHeader};
 use reth_tasks::{
@@ -152,12 +153,13 @@ where
         rpc_converter: Rpc,
         max_batch_size: usize,
         pending_block_kind: PendingBlockKind,
+        sync_progress: bool,
     ) -> Self {
         let inner = EthApiInner::new(
             components,
             eth_cache,
             gas_oracle,
-            gas_cap,
+            RpcLimits { gas_cap: gas_cap.into().into(), ..Default::default() },
             max_simulate_blocks,
             eth_proof_window,
             blocking_task_pool,
@@ -168,6 +170,7 @@ where
             (),
             max_batch_size,
             pending_block_kind,
+            sync_progress,
         );
 
         Self { inner: Arc::new(inner) }
@@ -269,8 +272,10 @@ pub struct EthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
     eth_cache: EthStateCache<N::Primitives>,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<N::Provider>,
-    /// Maximum gas limit for `eth_call` and call tracing RPC methods.
+    /// Maximum gas limit for `eth_call` and call tracing RPC methods the API was configured with.
     gas_cap: u64,
+    /// Gas cap, tx fee cap and execution timeouts that can be adjusted at runtime.
+    limits: RpcLimitsHandle,
     /// Maximum number of blocks for `eth_simulateV1`.
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
@@ -304,6 +309,9 @@ pub struct EthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
 
     /// Configuration for pending block construction.
     pending_block_kind: PendingBlockKind,
+
+    /// Tracker of the sync progress included in `eth_syncing` responses, if enabled.
+    sync_progress: Option<SyncProgressTracker>,
 }
 
 impl<N, Rpc> EthApiInner<N, Rpc>
@@ -317,7 +325,7 @@ where
         components: N,
         eth_cache: EthStateCache<N::Primitives>,
         gas_oracle: GasPriceOracle<N::Provider>,
-        gas_cap: impl Into<GasCap>,
+        limits: RpcLimits,
         max_simulate_blocks: u64,
         eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
@@ -328,6 +336,7 @@ where
         next_env: impl PendingEnvBuilder<N::Evm>,
         max_batch_size: usize,
         pending_block_kind: PendingBlockKind,
+        sync_progress: bool,
     ) -> Self {
         let signers = parking_lot::RwLock::new(Default::default());
         // get the block number of the latest block
@@ -353,7 +362,8 @@ where
             signers,
             eth_cache,
             gas_oracle,
-            gas_cap: gas_cap.into().into(),
+            gas_cap: limits.gas_cap,
+            limits: RpcLimitsHandle::new(limits),
             max_simulate_blocks,
             eth_proof_window,
             starting_block,
@@ -367,6 +377,7 @@ where
             next_env_builder: Box::new(next_env),
             tx_batch_sender,
             pending_block_kind,
+            sync_progress: sync_progress.then(SyncProgressTracker::default),
         }
     }
 }
@@ -431,12 +442,20 @@ where
         self.components.pool()
     }
 
-    /// Returns the gas cap.
+    /// Returns the gas cap the API was configured with.
+    ///
+    /// See [`Self::limits`] for the gas cap that is currently applied.
     #[inline]
     pub const fn gas_cap(&self) -> u64 {
         self.gas_cap
     }
 
+    /// Returns a handle to the limits that can be adjusted at runtime.
+    #[inline]
+    pub const fn limits(&self) -> &RpcLimitsHandle {
+        &self.limits
+    }
+
     /// Returns the `max_simulate_blocks`.
     #[inline]
     pub const fn max_simulate_blocks(&self) -> u64 {
@@ -526,27 +545,36 @@ where
     pub const fn pending_block_kind(&self) -> PendingBlockKind {
         self.pending_block_kind
     }
+
+    /// Returns the tracker of the sync progress included in `eth_syncing` responses, if enabled.
+    #[inline]
+    pub const fn sync_progress(&self) -> Option<&SyncProgressTracker> {
+        self.sync_progress.as_ref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{eth::helpers::types::EthRpcConverter, EthApi, EthApiBuilder};
     use alloy_consensus::{Block, BlockBody, Header};
-    use alloy_eips::BlockNumberOrTag;
-    use alloy_primitives::{Signature, B256, U64};
+    use alloy_eips::{eip7685::Requests, BlockNumberOrTag};
+    use alloy_primitives::{Bytes, Signature, B256, U256, U64};
     use alloy_rpc_types::FeeHistory;
     use jsonrpsee_types::error::INVALID_PARAMS_CODE;
     use rand::Rng;
     use reth_chain_state::CanonStateSubscriptions;
     use reth_chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec};
+    use reth_db_api::models::StoredBlockBodyIndices;
     use reth_ethereum_primitives::TransactionSigned;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
-        StageCheckpointReader,
+        PruneCheckpointReader, RequestsProvider, StageCheckpointReader,
     };
-    use reth_rpc_eth_api::{node::RpcNodeCoreAdapter, EthApiServer};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+    use reth_rpc_eth_api::{helpers::EthBlocks, node::RpcNodeCoreAdapter, EthApiServer};
+    use reth_rpc_eth_types::BlockQueryOptions;
     use reth_storage_api::{BlockReader, BlockReaderIdExt, StateProviderFactory};
     use reth_testing_utils::generators;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
@@ -567,6 +595,8 @@ mod tests {
             + StateProviderFactory
             + CanonStateSubscriptions<Primitives = reth_ethereum_primitives::EthPrimitives>
             + StageCheckpointReader
+            + PruneCheckpointReader
+            + RequestsProvider
             + Unpin
             + Clone
             + 'static,
@@ -827,4 +857,141 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    #[tokio::test]
+    async fn test_block_receipts_pruned() {
+        let mock_provider = MockEthProvider::default();
+        mock_provider.add_prune_checkpoint(
+            PruneSegment::Receipts,
+            PruneCheckpoint {
+                block_number: Some(5),
+                tx_number: None,
+                prune_mode: PruneMode::Before(6),
+            },
+        );
+        let (eth_api, _, _) = prepare_eth_api(10, None, 10, mock_provider);
+
+        let error_object = <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_receipts(
+            &eth_api,
+            BlockNumberOrTag::Number(3).into(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error_object.code(), 4444);
+        assert_eq!(error_object.data().unwrap().get(), "\"0x6\"");
+
+        // receipts that are missing but not pruned
+        let receipts = <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_receipts(
+            &eth_api,
+            BlockNumberOrTag::Number(8).into(),
+        )
+        .await
+        .unwrap();
+        assert!(receipts.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_counts_from_indices() {
+        let mock_provider = MockEthProvider::default();
+        let mut rng = generators::rng();
+        let block = generators::random_block(
+            &mut rng,
+            1,
+            generators::BlockParams {
+                tx_count: Some(3),
+                ommers_count: Some(2),
+                ..Default::default()
+            },
+        );
+        let hash = block.hash();
+        mock_provider.add_block(hash, block.into_block());
+        // the count is served from the body indices, not the block body
+        mock_provider
+            .add_block_body_indices(1, StoredBlockBodyIndices { first_tx_num: 10, tx_count: 5 });
+        let eth_api = build_test_eth_api(mock_provider);
+
+        let tx_count =
+            <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_transaction_count_by_hash(
+                &eth_api, hash,
+            )
+            .await
+            .unwrap();
+        assert_eq!(tx_count, Some(U256::from(5)));
+
+        let uncles_count =
+            <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_uncles_count_by_number(
+                &eth_api,
+                BlockNumberOrTag::Number(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(uncles_count, Some(U256::from(2)));
+
+        let missing =
+            <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_transaction_count_by_number(
+                &eth_api,
+                BlockNumberOrTag::Number(2),
+            )
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_block_json_matches_rpc_block() {
+        let mock_provider = MockEthProvider::default();
+        let mut rng = generators::rng();
+        let block = generators::random_block(
+            &mut rng,
+            1,
+            generators::BlockParams { tx_count: Some(3), ..Default::default() },
+        );
+        let hash = block.hash();
+        mock_provider.add_block(hash, block.into_block());
+        let eth_api = build_test_eth_api(mock_provider);
+
+        for full in [false, true] {
+            let expected = EthBlocks::rpc_block(&eth_api, hash.into(), full).await.unwrap();
+            let raw = EthBlocks::rpc_block_json(&eth_api, hash.into(), full).await.unwrap();
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(raw.unwrap().get()).unwrap(),
+                serde_json::to_value(expected.unwrap()).unwrap()
+            );
+        }
+
+        let missing = EthBlocks::rpc_block_json(&eth_api, B256::ZERO.into(), true).await.unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_with_options_requests() {
+        let mock_provider = MockEthProvider::default();
+        let mut rng = generators::rng();
+        let block = generators::random_block(&mut rng, 1, Default::default());
+        mock_provider.add_block(block.hash(), block.into_block());
+        let requests = Requests::new(vec![Bytes::from_static(&[0x00, 0xaa])]);
+        mock_provider.add_requests(1, requests.clone());
+        let eth_api = build_test_eth_api(mock_provider);
+
+        // requests are excluded by default
+        let block = <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_by_number_with_options(
+            &eth_api,
+            BlockNumberOrTag::Number(1),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(block.requests.is_none());
+
+        let block = <EthApi<_, _> as EthApiServer<_, _, _, _, _>>::block_by_number_with_options(
+            &eth_api,
+            BlockNumberOrTag::Number(1),
+            Some(BlockQueryOptions { requests: true, ..Default::default() }),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(block.requests, Some(requests));
+    }
 }
@@ -7,10 +7,12 @@ use alloy_rpc_types_eth::{
     PendingTransactionFilterKind,
 };
 use async_trait::async_trait;
-use futures::future::TryFutureExt;
+use futures::{future::TryFutureExt, Stream, StreamExt};
 use itertools::Itertools;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
+use reth_chain_state::{CanonStateNotification, CanonStateSubscriptions};
 use reth_errors::ProviderError;
+use reth_execution_types::Chain;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
 use reth_rpc_eth_api::{
     EngineEthFilter, EthApiTypes, EthFilterApiServer, FullEthApiTypes, QueryLimits, RpcConvert,
@@ -18,6 +20,7 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
+    utils::{ensure_logs_not_pruned, ensure_receipts_not_pruned},
     EthApiError, EthFilterConfig, EthStateCache, EthSubscriptionIdProvider,
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
@@ -33,7 +36,10 @@ use std::{
     future::Future,
     iter::{Peekable, StepBy},
     ops::RangeInclusive,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tokio::{
@@ -78,6 +84,9 @@ const PARALLEL_PROCESSING_THRESHOLD: u64 = 1000;
 /// Default concurrency for parallel processing
 const DEFAULT_PARALLEL_CONCURRENCY: usize = 4;
 
+/// The maximum number of headers sampled to estimate the bloom hit rate of a logs query
+const LOG_QUERY_COST_SAMPLE_SIZE: u64 = 64;
+
 /// `Eth` filter RPC implementation.
 ///
 /// This type handles `eth_` rpc requests related to filters (`eth_getLogs`).
@@ -127,8 +136,12 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            max_log_query_cost,
+            stale_filter_ttl,
+        } = config;
         let inner = EthFilterInner {
             eth_api,
             active_filters: ActiveFilters::new(),
@@ -136,7 +149,12 @@ where
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
-            query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            watching_reorgs: AtomicBool::new(false),
+            query_limits: QueryLimits {
+                max_blocks_per_filter,
+                max_logs_per_response,
+                max_log_query_cost,
+            },
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -201,6 +219,37 @@ where
         self.inner.eth_api.pool()
     }
 
+    /// Spawns the task that tracks reorgs for the installed filters, if it is not running yet.
+    fn ensure_reorg_watcher(&self) {
+        if self.inner.watching_reorgs.swap(true, Ordering::Relaxed) {
+            return
+        }
+
+        // subscribe before the filter is installed, so that no reorg is missed
+        let canon_state_stream = self.provider().canonical_state_stream();
+        let this = self.clone();
+        self.inner.task_spawner.spawn_critical(
+            "eth-filters_reorgs",
+            Box::pin(async move {
+                this.watch_reorgs(canon_state_stream).await;
+            }),
+        );
+    }
+
+    /// Future that handles the reverted chain of every reorg of the canonical chain, see
+    /// [`ActiveFilters::on_reverted_chain`].
+    async fn watch_reorgs<St>(&self, mut canon_state_stream: St)
+    where
+        St: Stream<Item = CanonStateNotification<Eth::Primitives>> + Unpin,
+    {
+        while let Some(notification) = canon_state_stream.next().await {
+            if let Some(reverted) = notification.reverted() {
+                trace!(target: "rpc::eth::filter", fork_block = reverted.first().header().number(), "handling reorg");
+                self.active_filters().on_reverted_chain(&reverted).await;
+            }
+        }
+    }
+
     /// Returns all the filter changes for the given id, if any
     pub async fn filter_changes(
         &self,
@@ -211,13 +260,17 @@ where
 
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let (start_block, kind, removed_logs) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
             let filter = filters.get_mut(&id).ok_or(EthFilterError::FilterNotFound(id))?;
+            let removed_logs = std::mem::take(&mut filter.removed_logs);
 
             if filter.block > best_number {
                 // no new blocks since the last poll
-                return Ok(FilterChanges::Empty)
+                if removed_logs.is_empty() {
+                    return Ok(FilterChanges::Empty)
+                }
+                return Ok(FilterChanges::Logs(removed_logs))
             }
 
             // update filter
@@ -227,7 +280,7 @@ where
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, filter.kind.clone(), removed_logs)
         };
 
         match kind {
@@ -272,7 +325,9 @@ where
                         self.inner.query_limits,
                     )
                     .await?;
-                Ok(FilterChanges::Logs(logs))
+                // logs of reorged blocks are removed before the logs of the new canonical blocks
+                // are delivered
+                Ok(FilterChanges::Logs(removed_logs.into_iter().chain(logs).collect()))
             }
         }
     }
@@ -316,6 +371,7 @@ where
     /// Handler for `eth_newFilter`
     async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
         trace!(target: "rpc::eth", "Serving eth_newFilter");
+        self.ensure_reorg_watcher();
         self.inner
             .install_filter(FilterKind::<RpcTransaction<Eth::NetworkTypes>>::Log(Box::new(filter)))
             .await
@@ -324,6 +380,7 @@ where
     /// Handler for `eth_newBlockFilter`
     async fn new_block_filter(&self) -> RpcResult<FilterId> {
         trace!(target: "rpc::eth", "Serving eth_newBlockFilter");
+        self.ensure_reorg_watcher();
         self.inner.install_filter(FilterKind::<RpcTransaction<Eth::NetworkTypes>>::Block).await
     }
 
@@ -424,6 +481,8 @@ struct EthFilterInner<Eth: EthApiTypes> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Whether the task that tracks reorgs for the installed filters is running.
+    watching_reorgs: AtomicBool,
 }
 
 impl<Eth> EthFilterInner<Eth>
@@ -459,13 +518,18 @@ where
 
                 let block_num_hash = BlockNumHash::new(header.number(), block_hash);
 
+                // the logs of the block are incomplete if the receipts that match the filter may
+                // have been pruned
+                ensure_logs_not_pruned(self.provider(), &filter, block_num_hash.number)?;
+
                 // we also need to ensure that the receipts are available and return an error if
-                // not, in case the block hash been reorged
-                let (receipts, maybe_block) = self
-                    .eth_cache()
-                    .get_receipts_and_maybe_block(block_num_hash.hash)
-                    .await?
-                    .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+                // not, in case the block hash been reorged or the receipts have been pruned
+                let Some((receipts, maybe_block)) =
+                    self.eth_cache().get_receipts_and_maybe_block(block_num_hash.hash).await?
+                else {
+                    ensure_receipts_not_pruned(self.provider(), block_num_hash.number)?;
+                    return Err(EthApiError::HeaderNotFound(block_hash.into()).into())
+                };
 
                 let mut all_logs = Vec::new();
                 append_matching_block_logs(
@@ -523,6 +587,7 @@ where
                 block: last_poll_block_number,
                 last_poll_timestamp: Instant::now(),
                 kind,
+                removed_logs: Vec::new(),
             },
         );
         Ok(id)
@@ -572,6 +637,9 @@ where
     ///
     /// Returns an error if:
     ///  - underlying database error
+    ///  - receipts of the range have been pruned
+    ///  - receipts matching the filter may have been pruned by the receipts log filters
+    ///  - estimated cost of the query exceeds configured limit
     async fn get_logs_in_block_range_inner(
         self: Arc<Self>,
         filter: &Filter,
@@ -579,6 +647,15 @@ where
         to_block: u64,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
+        // the receipts of the range must not have been pruned, the logs would be incomplete
+        // otherwise
+        ensure_receipts_not_pruned(self.provider(), from_block)?;
+        ensure_logs_not_pruned(self.provider(), filter, from_block)?;
+
+        if let Some(max_cost) = limits.max_log_query_cost {
+            self.check_log_query_cost(filter, from_block, to_block, max_cost)?;
+        }
+
         let mut all_logs = Vec::new();
         let mut matching_headers = Vec::new();
 
@@ -666,6 +743,64 @@ where
 
         Ok(all_logs)
     }
+
+    /// Estimates the cost of querying the logs in the given _inclusive_ range and returns an error
+    /// with a suggested narrower range if it exceeds `max_cost`.
+    ///
+    /// The cost is the number of blocks whose receipts must be scanned, i.e. the number of blocks
+    /// in the range times the rate of headers whose bloom matches the filter. The rate is
+    /// estimated from up to [`LOG_QUERY_COST_SAMPLE_SIZE`] evenly spaced headers of the range.
+    fn check_log_query_cost(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        max_cost: u64,
+    ) -> Result<(), EthFilterError> {
+        // the cost can't exceed the number of blocks, and all logs of a single block are always
+        // returned
+        let block_count = to_block - from_block + 1;
+        if block_count <= max_cost || from_block == to_block {
+            return Ok(())
+        }
+
+        let step = block_count.div_ceil(LOG_QUERY_COST_SAMPLE_SIZE);
+        let (mut sampled, mut matches) = (0u64, 0u64);
+        for number in (from_block..=to_block).step_by(step as usize) {
+            let Some(header) = self.provider().header_by_number(number)? else { continue };
+            sampled += 1;
+            if filter.matches_bloom(header.logs_bloom()) {
+                matches += 1;
+            }
+        }
+        if sampled == 0 {
+            return Ok(())
+        }
+
+        let estimated_cost = (block_count as u128 * matches as u128).div_ceil(sampled as u128);
+        if estimated_cost <= max_cost as u128 {
+            return Ok(())
+        }
+
+        // suggest a range starting at the same block with an estimated cost within budget
+        let suggested_count = (block_count as u128 * max_cost as u128 / estimated_cost).max(1);
+        let suggested_to_block = from_block + suggested_count as u64 - 1;
+        debug!(
+            target: "rpc::eth::filter",
+            estimated_cost,
+            max_cost,
+            from_block,
+            to_block,
+            suggested_to_block,
+            "Query exceeded max log query cost"
+        );
+        Err(EthFilterError::QueryExceedsMaxCost {
+            max_cost,
+            estimated_cost: estimated_cost as u64,
+            from_block,
+            to_block: suggested_to_block,
+        })
+    }
 }
 
 /// All active filters
@@ -679,6 +814,39 @@ impl<T> ActiveFilters<T> {
     pub fn new() -> Self {
         Self { inner: Arc::new(Mutex::new(HashMap::default())) }
     }
+
+    /// Handles the reverted chain of a reorg.
+    ///
+    /// The matching logs of reverted blocks that were already delivered to a log filter are queued
+    /// with `removed: true`. Log and block filters are rewound to the first reverted block, so that
+    /// the blocks of the new canonical chain are delivered with the next poll.
+    async fn on_reverted_chain<N: NodePrimitives>(&self, reverted: &Chain<N>) {
+        let fork_block = reverted.first().header().number();
+        let reverted_receipts = reverted.receipts_with_attachment();
+
+        let mut filters = self.inner.lock().await;
+        for filter in filters.values_mut() {
+            match &filter.kind {
+                FilterKind::Log(log_filter) => {
+                    for block_receipts in reverted_receipts
+                        .iter()
+                        .filter(|receipts| receipts.block.number < filter.block)
+                    {
+                        filter.removed_logs.extend(logs_utils::matching_block_logs_with_tx_hashes(
+                            log_filter,
+                            block_receipts.block,
+                            block_receipts.timestamp,
+                            block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                            true,
+                        ));
+                    }
+                }
+                FilterKind::Block => {}
+                FilterKind::PendingTransaction(_) => continue,
+            }
+            filter.block = filter.block.min(fork_block);
+        }
+    }
 }
 
 /// An installed filter
@@ -690,6 +858,9 @@ struct ActiveFilter<T> {
     last_poll_timestamp: Instant,
     /// What kind of filter it is.
     kind: FilterKind<T>,
+    /// Logs of reorged blocks that were delivered before and have to be removed with the next
+    /// poll.
+    removed_logs: Vec<Log>,
 }
 
 /// A receiver for pending transactions that returns all new transactions since the last poll.
@@ -848,6 +1019,20 @@ pub enum EthFilterError {
         /// End block of the suggested retry range (last successfully processed block)
         to_block: u64,
     },
+    /// Estimated cost of the query is too high.
+    #[error(
+        "query exceeds max cost {max_cost} with an estimated cost of {estimated_cost}, retry with the range {from_block}-{to_block}"
+    )]
+    QueryExceedsMaxCost {
+        /// Maximum estimated cost allowed per query
+        max_cost: u64,
+        /// Estimated cost of the query
+        estimated_cost: u64,
+        /// Start block of the suggested retry range
+        from_block: u64,
+        /// End block of the suggested retry range
+        to_block: u64,
+    },
     /// Error serving request in `eth_` namespace.
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
@@ -869,7 +1054,8 @@ impl From<EthFilterError> for jsonrpsee::types::error::ErrorObject<'static> {
             EthFilterError::EthAPIError(err) => err.into(),
             err @ (EthFilterError::InvalidBlockRangeParams |
             EthFilterError::QueryExceedsMaxBlocks(_) |
-            EthFilterError::QueryExceedsMaxResults { .. }) => {
+            EthFilterError::QueryExceedsMaxResults { .. } |
+            EthFilterError::QueryExceedsMaxCost { .. }) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
         }
@@ -1160,13 +1346,16 @@ mod tests {
     use super::*;
     use crate::{eth::EthApi, EthApiBuilder};
     use alloy_network::Ethereum;
-    use alloy_primitives::FixedBytes;
+    use alloy_primitives::{FixedBytes, B256};
     use rand::Rng;
     use reth_chainspec::{ChainSpec, ChainSpecProvider};
     use reth_ethereum_primitives::TxType;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_provider::test_utils::MockEthProvider;
+    use reth_prune_types::{
+        PruneCheckpoint, PruneMode, PruneModes, PruneSegment, ReceiptsLogTopicPruneConfig,
+    };
     use reth_rpc_convert::RpcConverter;
     use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
     use reth_rpc_eth_types::receipt::EthReceiptConverter;
@@ -1737,4 +1926,229 @@ mod tests {
         assert_eq!(logs[0].block_hash, Some(expected_hashes[0])); // block 100
         assert_eq!(logs[1].block_hash, Some(expected_hashes[2])); // block 102
     }
+
+    #[tokio::test]
+    async fn test_log_query_cost_rejects_with_suggested_range() {
+        let provider = MockEthProvider::default();
+
+        // the first half of the blocks 100..=199 matches the bloom filter
+        for number in 100u64..200 {
+            let header = alloy_consensus::Header {
+                number,
+                logs_bloom: if number < 150 {
+                    alloy_primitives::Bloom::from([u8::MAX; 256])
+                } else {
+                    alloy_primitives::Bloom::default()
+                },
+                ..Default::default()
+            };
+            provider.add_header(header.hash_slow(), header);
+        }
+
+        let eth_api = build_test_eth_api(provider);
+        let eth_filter = EthFilter::new(
+            eth_api,
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        let filter = Filter::new().address(alloy_primitives::Address::ZERO);
+
+        // within budget
+        eth_filter.inner.check_log_query_cost(&filter, 100, 199, 50).unwrap();
+        // a single block is always within budget
+        eth_filter.inner.check_log_query_cost(&filter, 100, 100, 1).unwrap();
+
+        let err = eth_filter.inner.check_log_query_cost(&filter, 100, 199, 10).unwrap_err();
+        assert!(matches!(
+            err,
+            EthFilterError::QueryExceedsMaxCost {
+                max_cost: 10,
+                estimated_cost: 50,
+                from_block: 100,
+                to_block: 119,
+            }
+        ));
+
+        let limits = QueryLimits { max_log_query_cost: Some(10), ..Default::default() };
+        let err = eth_filter
+            .inner
+            .clone()
+            .get_logs_in_block_range(filter, 100, 199, limits)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthFilterError::QueryExceedsMaxCost { .. }));
+    }
+
+    /// Returns a chain of blocks with one transaction each, whose receipt emits a log of the
+    /// given address.
+    fn chain_with_logs(
+        parent_hash: alloy_primitives::B256,
+        blocks: RangeInclusive<u64>,
+        address: alloy_primitives::Address,
+    ) -> Vec<(
+        alloy_primitives::B256,
+        reth_ethereum_primitives::Block,
+        Vec<reth_ethereum_primitives::Receipt>,
+    )> {
+        use alloy_consensus::TxLegacy;
+        use reth_ethereum_primitives::TransactionSigned;
+
+        let mut parent_hash = parent_hash;
+        blocks
+            .map(|number| {
+                let tx = TransactionSigned::new_unhashed(
+                    TxLegacy { nonce: number, ..Default::default() }.into(),
+                    alloy_primitives::Signature::test_signature(),
+                );
+                let header = alloy_consensus::Header { number, parent_hash, ..Default::default() };
+                let hash = header.hash_slow();
+                parent_hash = hash;
+
+                let receipt = reth_ethereum_primitives::Receipt {
+                    tx_type: TxType::Legacy,
+                    cumulative_gas_used: 21_000,
+                    logs: vec![alloy_primitives::Log {
+                        address,
+                        data: alloy_primitives::LogData::new_unchecked(
+                            vec![],
+                            alloy_primitives::Bytes::new(),
+                        ),
+                    }],
+                    success: true,
+                };
+                let block = reth_ethereum_primitives::Block {
+                    header,
+                    body: reth_ethereum_primitives::BlockBody {
+                        transactions: vec![tx],
+                        ..Default::default()
+                    },
+                };
+                (hash, block, vec![receipt])
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_reorg_removes_delivered_logs() {
+        let provider = MockEthProvider::default();
+        let old_chain =
+            chain_with_logs(Default::default(), 0..=3, alloy_primitives::Address::repeat_byte(1));
+        provider.reorg(old_chain.clone());
+
+        let eth_api = build_test_eth_api(provider.clone());
+        let eth_filter = EthFilter::new(
+            eth_api,
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        // install the filters without spawning the reorg watcher, the reorg is handled below
+        let log_filter = eth_filter
+            .inner
+            .install_filter(FilterKind::Log(Box::new(Filter::default())))
+            .await
+            .unwrap();
+        let block_filter = eth_filter.inner.install_filter(FilterKind::Block).await.unwrap();
+
+        // all blocks were delivered to the log filter, the block filter was never polled
+        eth_filter.active_filters().inner.lock().await.get_mut(&log_filter).unwrap().block = 4;
+
+        // reorg the two blocks at the tip
+        let notifications = provider.canonical_state_stream().take(1);
+        let new_chain =
+            chain_with_logs(old_chain[1].0, 2..=3, alloy_primitives::Address::repeat_byte(2));
+        provider.reorg(new_chain);
+        eth_filter.watch_reorgs(notifications).await;
+
+        let filters = eth_filter.active_filters().inner.lock().await;
+        let removed_logs = &filters.get(&log_filter).unwrap().removed_logs;
+        assert_eq!(removed_logs.len(), 2);
+        for (log, (hash, block, _)) in removed_logs.iter().zip(&old_chain[2..]) {
+            assert!(log.removed);
+            assert_eq!(log.block_hash, Some(*hash));
+            assert_eq!(log.block_number, Some(block.header.number));
+        }
+
+        // both filters are rewound to the fork block to deliver the new canonical blocks
+        assert_eq!(filters.get(&log_filter).unwrap().block, 2);
+        assert_eq!(filters.get(&block_filter).unwrap().block, 2);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_of_undelivered_blocks_removes_nothing() {
+        let provider = MockEthProvider::default();
+        let old_chain =
+            chain_with_logs(Default::default(), 0..=3, alloy_primitives::Address::repeat_byte(1));
+        provider.reorg(old_chain.clone());
+
+        let eth_api = build_test_eth_api(provider.clone());
+        let eth_filter = EthFilter::new(
+            eth_api,
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        let log_filter = eth_filter
+            .inner
+            .install_filter(FilterKind::Log(Box::new(Filter::default())))
+            .await
+            .unwrap();
+        eth_filter.active_filters().inner.lock().await.get_mut(&log_filter).unwrap().block = 2;
+
+        let notifications = provider.canonical_state_stream().take(1);
+        let new_chain =
+            chain_with_logs(old_chain[1].0, 2..=3, alloy_primitives::Address::repeat_byte(2));
+        provider.reorg(new_chain);
+        eth_filter.watch_reorgs(notifications).await;
+
+        let filters = eth_filter.active_filters().inner.lock().await;
+        let filter = filters.get(&log_filter).unwrap();
+        assert!(filter.removed_logs.is_empty());
+        assert_eq!(filter.block, 2);
+    }
+
+    #[tokio::test]
+    async fn test_logs_pruned_by_topic_filter() {
+        let retained_topic = B256::with_last_byte(1);
+        let prune_modes = PruneModes {
+            receipts_log_topic_filter: ReceiptsLogTopicPruneConfig(
+                [(retained_topic, PruneMode::Before(5_000))].into(),
+            ),
+            ..PruneModes::none()
+        };
+        let provider = MockEthProvider::default().with_prune_modes(prune_modes);
+        let tip = alloy_consensus::Header { number: 20_000, ..Default::default() };
+        provider.add_header(FixedBytes::random(), tip);
+        provider.add_prune_checkpoint(
+            PruneSegment::ContractLogs,
+            PruneCheckpoint {
+                block_number: Some(8_000),
+                tx_number: None,
+                prune_mode: PruneMode::Before(8_001),
+            },
+        );
+
+        let eth_filter = super::EthFilter::new(
+            build_test_eth_api(provider),
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        let logs = |from: u64, topic: B256| {
+            EthFilterApiServer::logs(
+                &eth_filter,
+                Filter::new().from_block(from).to_block(from + 10).event_signature(topic),
+            )
+        };
+        let assert_pruned = |result: RpcResult<Vec<Log>>, earliest_block: &str| {
+            let err = result.unwrap_err();
+            assert_eq!(err.code(), 4444);
+            assert_eq!(err.data().unwrap().get(), format!("\"{earliest_block}\""));
+        };
+
+        // receipts with the retained topic are kept from block 5000 on
+        assert!(logs(5_000, retained_topic).await.unwrap().is_empty());
+        assert_pruned(logs(4_000, retained_topic).await, "0x1388");
+
+        // all other receipts are only available after the pruned block 8000
+        assert_pruned(logs(5_000, B256::ZERO).await, "0x1f41");
+        assert!(logs(8_001, B256::ZERO).await.unwrap().is_empty());
+    }
 }
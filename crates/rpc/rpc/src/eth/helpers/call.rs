@@ -7,7 +7,7 @@ use reth_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall},
     FromEvmError, RpcNodeCore,
 };
-use reth_rpc_eth_types::EthApiError;
+use reth_rpc_eth_types::{EthApiError, RpcLimitsHandle};
 
 impl<N, Rpc> EthCall for EthApi<N, Rpc>
 where
@@ -25,13 +25,18 @@ where
 {
     #[inline]
     fn call_gas_limit(&self) -> u64 {
-        self.inner.gas_cap()
+        self.inner.limits().gas_cap()
     }
 
     #[inline]
     fn max_simulate_blocks(&self) -> u64 {
         self.inner.max_simulate_blocks()
     }
+
+    #[inline]
+    fn rpc_limits(&self) -> RpcLimitsHandle {
+        self.inner.limits().clone()
+    }
 }
 
 impl<N, Rpc> EstimateCall for EthApi<N, Rpc>
@@ -41,3 +46,85 @@ where
     Rpc: RpcConvert<Primitives = N::Primitives, Error = EthApiError, TxEnv = TxEnvFor<N::Evm>>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::helpers::types::EthRpcConverter;
+    use alloy_primitives::{Address, U256};
+    use alloy_rpc_types_eth::{state::EvmOverrides, BlockOverrides, TransactionRequest};
+    use reth_chainspec::ChainSpec;
+    use reth_evm::EvmEnv;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::{test_utils::NoopProvider, StateProviderFactory};
+    use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+    use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
+    use reth_rpc_eth_types::error::RpcInvalidTransactionError;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    fn noop_eth_api() -> EthApi<
+        RpcNodeCoreAdapter<NoopProvider, TestPool, NoopNetwork, EthEvmConfig>,
+        EthRpcConverter<ChainSpec>,
+    > {
+        EthApi::builder(
+            NoopProvider::default(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::mainnet(),
+        )
+        .build()
+    }
+
+    #[tokio::test]
+    async fn disable_base_fee_check() {
+        let eth_api = noop_eth_api();
+        let mut db = CacheDB::new(StateProviderDatabase::new(eth_api.provider().latest().unwrap()));
+        let request = TransactionRequest {
+            from: Some(Address::random()),
+            to: Some(Address::random().into()),
+            gas: Some(21_000),
+            max_fee_per_gas: Some(10),
+            max_priority_fee_per_gas: Some(1),
+            ..Default::default()
+        };
+
+        // the fee cap is checked against the base fee of the block
+        let mut evm_env = EvmEnv::default();
+        evm_env.block_env.basefee = 100;
+        let err = eth_api
+            .prepare_call_env(
+                evm_env.clone(),
+                request.clone(),
+                &mut db,
+                EvmOverrides::default(),
+                false,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::InvalidTransaction(RpcInvalidTransactionError::FeeCapTooLow)
+        ));
+
+        // unless the check is disabled
+        let (evm_env, tx_env) = eth_api
+            .prepare_call_env(evm_env, request.clone(), &mut db, EvmOverrides::default(), true)
+            .unwrap();
+        assert_eq!(evm_env.block_env.basefee, 100);
+        assert_eq!(tx_env.gas_price, 10);
+
+        // but not against an overridden base fee
+        let overrides = EvmOverrides::new(
+            None,
+            Some(Box::new(BlockOverrides {
+                base_fee: Some(U256::from(100)),
+                ..Default::default()
+            })),
+        );
+        let (evm_env, tx_env) = eth_api
+            .prepare_call_env(EvmEnv::default(), request, &mut db, overrides, false)
+            .unwrap();
+        assert_eq!(evm_env.block_env.basefee, 100);
+        assert_eq!(tx_env.gas_price, 10);
+    }
+}
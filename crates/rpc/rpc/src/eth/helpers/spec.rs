@@ -4,6 +4,7 @@ use reth_rpc_eth_api::{
     helpers::{spec::SignersForApi, EthApiSpec},
     RpcNodeCore,
 };
+use reth_rpc_eth_types::SyncProgressTracker;
 use reth_storage_api::ProviderTx;
 
 use crate::EthApi;
@@ -23,4 +24,8 @@ where
     fn signers(&self) -> &SignersForApi<Self> {
         self.inner.signers()
     }
+
+    fn sync_progress(&self) -> Option<&SyncProgressTracker> {
+        self.inner.sync_progress()
+    }
 }
@@ -23,6 +23,9 @@ where
     N: RpcNodeCore,
     Rpc: RpcConvert<Primitives = N::Primitives>,
 {
+    fn max_state_history(&self) -> Option<u64> {
+        self.inner.limits().max_state_history()
+    }
 }
 
 #[cfg(test)]
@@ -30,7 +33,9 @@ mod tests {
     use crate::eth::helpers::types::EthRpcConverter;
 
     use super::*;
-    use alloy_primitives::{Address, StorageKey, StorageValue, U256};
+    use alloy_consensus::Header;
+    use alloy_eips::BlockNumberOrTag;
+    use alloy_primitives::{Address, StorageKey, StorageValue, B256, U256};
     use reth_chainspec::ChainSpec;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
@@ -38,7 +43,9 @@ mod tests {
         test_utils::{ExtendedAccount, MockEthProvider, NoopProvider},
         ChainSpecProvider,
     };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
     use reth_rpc_eth_api::{helpers::EthState, node::RpcNodeCoreAdapter};
+    use reth_rpc_eth_types::EthApiError;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
     use std::collections::HashMap;
 
@@ -97,4 +104,60 @@ mod tests {
         let account = eth_api.get_account(address, Default::default()).await.unwrap();
         assert!(account.is_none());
     }
+
+    #[test]
+    fn test_ensure_state_history() {
+        let mock_provider = MockEthProvider::default();
+        for number in 0..=10 {
+            mock_provider.add_header(B256::random(), Header { number, ..Default::default() });
+        }
+        let eth_api = |max_state_history| {
+            EthApi::builder(
+                mock_provider.clone(),
+                testing_pool(),
+                NoopNetwork::default(),
+                EthEvmConfig::new(mock_provider.chain_spec()),
+            )
+            .max_state_history(max_state_history)
+            .build()
+        };
+        let ensure_state_history = |eth_api: &EthApi<_, _>, number| {
+            eth_api.ensure_state_history(BlockNumberOrTag::Number(number).into())
+        };
+
+        // the full state history is available
+        assert!(ensure_state_history(&eth_api(Some(4)), 0).is_ok());
+
+        for (segment, block_number) in
+            [(PruneSegment::AccountHistory, 3), (PruneSegment::StorageHistory, 4)]
+        {
+            mock_provider.add_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(block_number),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(block_number + 1),
+                },
+            );
+        }
+
+        // the guard is disabled
+        assert!(ensure_state_history(&eth_api(None), 0).is_ok());
+
+        // limited by the max state history
+        let limited = eth_api(Some(4));
+        assert!(ensure_state_history(&limited, 6).is_ok());
+        assert!(matches!(
+            ensure_state_history(&limited, 5),
+            Err(EthApiError::PrunedHistoryUnavailable { earliest_block: 6 })
+        ));
+
+        // limited by the pruned history
+        let unlimited = eth_api(Some(u64::MAX));
+        assert!(ensure_state_history(&unlimited, 5).is_ok());
+        assert!(matches!(
+            ensure_state_history(&unlimited, 4),
+            Err(EthApiError::PrunedHistoryUnavailable { earliest_block: 5 })
+        ));
+    }
 }
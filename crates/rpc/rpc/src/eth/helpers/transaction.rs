@@ -1,6 +1,7 @@
 //! Contains RPC handler implementations specific to transactions
 
 use crate::EthApi;
+use alloy_consensus::Transaction as _;
 use alloy_primitives::{Bytes, B256};
 use reth_rpc_convert::RpcConvert;
 use reth_rpc_eth_api::{
@@ -26,6 +27,7 @@ where
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
         let recovered = recover_raw_transaction(&tx)?;
+        self.inner.limits().ensure_tx_fee(recovered.max_fee_per_gas(), recovered.gas_limit())?;
 
         // broadcast raw transaction to subscribers if there is any.
         self.broadcast_raw_transaction(tx);
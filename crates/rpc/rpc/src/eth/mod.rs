@@ -2,6 +2,7 @@
 
 pub mod builder;
 pub mod bundle;
+pub mod conditional;
 pub mod core;
 pub mod filter;
 pub mod helpers;
@@ -11,6 +12,7 @@ pub mod sim_bundle;
 /// Implementation of `eth` namespace API.
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
+pub use conditional::EthTransactionConditional;
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
@@ -2,6 +2,8 @@
 
 use std::sync::Arc;
 
+use alloy_consensus::{BlockHeader, Transaction, TxReceipt};
+use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::{TxHash, U256};
 use alloy_rpc_types_eth::{
     pubsub::{Params, PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
@@ -12,8 +14,9 @@ use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_chain_state::CanonStateSubscriptions;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_network_api::NetworkInfo;
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{Block, BlockBody, NodePrimitives, RecoveredBlock};
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
@@ -22,7 +25,7 @@ use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, PoolConsensusTx, TransactionPool};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
@@ -58,7 +61,9 @@ impl<Eth> EthPubSub<Eth> {
 impl<N: NodePrimitives, Eth> EthPubSub<Eth>
 where
     Eth: RpcNodeCore<
-            Provider: BlockNumReader + CanonStateSubscriptions<Primitives = N>,
+            Provider: BlockNumReader
+                          + CanonStateSubscriptions<Primitives = N>
+                          + ChainSpecProvider<ChainSpec: EthChainSpec>,
             Pool: TransactionPool,
             Network: NetworkInfo,
         > + EthApiTypes<
@@ -89,6 +94,13 @@ where
         self.inner.new_headers_stream()
     }
 
+    /// Returns a stream that yields all new RPC blocks together with their [`HeaderExtras`].
+    pub fn new_headers_with_extras_stream(
+        &self,
+    ) -> impl Stream<Item = HeaderWithExtras<N::BlockHeader>> {
+        self.inner.new_headers_with_extras_stream()
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     pub fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
         self.inner.log_stream(filter)
@@ -102,9 +114,14 @@ where
         params: Option<Params>,
     ) -> Result<(), ErrorObject<'static>> {
         match kind {
-            SubscriptionKind::NewHeads => {
-                pipe_from_stream(accepted_sink, self.new_headers_stream()).await
-            }
+            SubscriptionKind::NewHeads => match params {
+                Some(Params::Bool(true)) => {
+                    // headers with extras requested
+                    pipe_from_stream(accepted_sink, self.new_headers_with_extras_stream()).await
+                }
+                Some(Params::Logs(_)) => Err(invalid_params_rpc_err("Invalid params for newHeads")),
+                _ => pipe_from_stream(accepted_sink, self.new_headers_stream()).await,
+            },
             SubscriptionKind::Logs => {
                 // if no params are provided, used default filter params
                 let filter = match params {
@@ -207,7 +224,9 @@ where
 impl<Eth> EthPubSubApiServer<RpcTransaction<Eth::NetworkTypes>> for EthPubSub<Eth>
 where
     Eth: RpcNodeCore<
-            Provider: BlockNumReader + CanonStateSubscriptions,
+            Provider: BlockNumReader
+                          + CanonStateSubscriptions
+                          + ChainSpecProvider<ChainSpec: EthChainSpec>,
             Pool: TransactionPool,
             Network: NetworkInfo,
         > + EthApiTypes<
@@ -233,6 +252,83 @@ where
     }
 }
 
+/// A `newHeads` notification that carries [`HeaderExtras`] in addition to the header.
+///
+/// Sent for `eth_subscribe("newHeads", true)` subscriptions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderWithExtras<H> {
+    /// The header of the new block.
+    #[serde(flatten)]
+    pub header: Header<H>,
+    /// Additional data of the new block computed by reth.
+    pub extras: HeaderExtras,
+}
+
+/// Additional data of a new block that would otherwise require fetching the full block.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderExtras {
+    /// The fees burnt by the block, which are the base fee and the blob base fee paid for all gas
+    /// and blob gas used.
+    pub burnt_fees: U256,
+    /// The priority fees paid by all transactions of the block to the beneficiary.
+    pub priority_fees: U256,
+    /// The blob gas used by the block in percent of the maximum blob gas per block, if blobs are
+    /// active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_utilization: Option<f64>,
+    /// The number of transactions in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_count: u64,
+}
+
+impl HeaderExtras {
+    /// Computes the extras of the given block from its receipts.
+    ///
+    /// `blob_params` are the blob parameters active at the block, if any.
+    pub fn new<B, R>(
+        block: &RecoveredBlock<B>,
+        receipts: &[R],
+        blob_params: Option<BlobParams>,
+    ) -> Self
+    where
+        B: Block,
+        R: TxReceipt,
+    {
+        let header = block.header();
+        let base_fee = header.base_fee_per_gas().unwrap_or_default();
+
+        let mut burnt_fees = U256::from(base_fee) * U256::from(header.gas_used());
+        if let Some((blob_fee, blob_gas_used)) =
+            blob_params.and_then(|params| header.blob_fee(params)).zip(header.blob_gas_used())
+        {
+            burnt_fees += U256::from(blob_fee) * U256::from(blob_gas_used);
+        }
+
+        let mut priority_fees = U256::ZERO;
+        let mut cumulative_gas_used = 0;
+        for (tx, receipt) in block.body().transactions_iter().zip(receipts) {
+            let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used();
+
+            let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            priority_fees += U256::from(tip) * U256::from(gas_used);
+        }
+
+        let blob_gas_utilization =
+            blob_params.zip(header.blob_gas_used()).map(|(params, blob_gas_used)| {
+                blob_gas_used as f64 / params.max_blob_gas_per_block() as f64 * 100.0
+            });
+
+        Self {
+            burnt_fees,
+            priority_fees,
+            blob_gas_utilization,
+            transaction_count: block.body().transaction_count() as u64,
+        }
+    }
+}
+
 /// Helper to convert a serde error into an [`ErrorObject`]
 #[derive(Debug, thiserror::Error)]
 #[error("Failed to serialize subscription item: {0}")]
@@ -348,7 +444,10 @@ where
 
 impl<N: NodePrimitives, Eth> EthPubSubInner<Eth>
 where
-    Eth: RpcNodeCore<Provider: CanonStateSubscriptions<Primitives = N>>,
+    Eth: RpcNodeCore<
+        Provider: CanonStateSubscriptions<Primitives = N>
+                      + ChainSpecProvider<ChainSpec: EthChainSpec>,
+    >,
 {
     /// Returns a stream that yields all new RPC blocks.
     fn new_headers_stream(&self) -> impl Stream<Item = Header<N::BlockHeader>> {
@@ -368,6 +467,32 @@ where
         })
     }
 
+    /// Returns a stream that yields all new RPC blocks together with their [`HeaderExtras`].
+    fn new_headers_with_extras_stream(
+        &self,
+    ) -> impl Stream<Item = HeaderWithExtras<N::BlockHeader>> {
+        let chain_spec = self.eth_api.provider().chain_spec();
+        self.eth_api.provider().canonical_state_stream().flat_map(move |new_chain| {
+            let headers = new_chain
+                .committed()
+                .blocks_and_receipts()
+                .map(|(block, receipts)| {
+                    let blob_params =
+                        chain_spec.blob_params_at_timestamp(block.header().timestamp());
+                    HeaderWithExtras {
+                        header: Header::from_consensus(
+                            block.clone_sealed_header().into(),
+                            None,
+                            Some(U256::from(block.rlp_length())),
+                        ),
+                        extras: HeaderExtras::new(block, receipts, blob_params),
+                    }
+                })
+                .collect::<Vec<_>>();
+            futures::stream::iter(headers)
+        })
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.eth_api.provider().subscribe_to_canonical_state())
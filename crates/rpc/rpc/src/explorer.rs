@@ -0,0 +1,152 @@
+use alloy_consensus::{BlockHeader, Transaction, TxReceipt};
+use alloy_eips::{BlockId, Typed2718};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_primitives_traits::SignedTransaction;
+use reth_rpc_api::{
+    AddressSummary, BlockSummary, ExplorerApiServer, TransactionSummary, EXPLORER_MAX_LATEST_BLOCKS,
+};
+use reth_rpc_eth_api::{
+    helpers::{EthBlocks, LoadReceipt, LoadState, SpawnBlocking},
+    FromEthApiError, RpcNodeCore,
+};
+use reth_storage_api::{BlockBodyIndicesProvider, BlockNumReader, HeaderProvider};
+
+/// `explorer` API implementation.
+///
+/// This type answers the common block explorer queries with only the fields explorers show.
+#[derive(Debug)]
+pub struct ExplorerApi<Eth> {
+    eth: Eth,
+}
+
+impl<Eth> ExplorerApi<Eth> {
+    /// Creates a new instance of the [`ExplorerApi`].
+    pub const fn new(eth: Eth) -> Self {
+        Self { eth }
+    }
+}
+
+impl<Eth> ExplorerApi<Eth>
+where
+    Eth: EthBlocks + LoadReceipt + LoadState + 'static,
+{
+    /// Returns the summaries of the latest `count` blocks, newest first.
+    async fn latest_blocks(&self, count: u64) -> Result<Vec<BlockSummary>, Eth::Error> {
+        let count = count.min(EXPLORER_MAX_LATEST_BLOCKS);
+        if count == 0 {
+            return Ok(Vec::new())
+        }
+
+        self.eth
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let best = provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+                let range = best.saturating_sub(count - 1)..=best;
+                let headers = provider
+                    .sealed_headers_range(range.clone())
+                    .map_err(Eth::Error::from_eth_err)?;
+                let body_indices =
+                    provider.block_body_indices_range(range).map_err(Eth::Error::from_eth_err)?;
+
+                Ok(headers
+                    .into_iter()
+                    .zip(body_indices)
+                    .rev()
+                    .map(|(header, body_indices)| BlockSummary {
+                        number: header.number(),
+                        hash: header.hash(),
+                        timestamp: header.timestamp(),
+                        miner: header.beneficiary(),
+                        gas_used: header.gas_used(),
+                        gas_limit: header.gas_limit(),
+                        base_fee_per_gas: header.base_fee_per_gas(),
+                        transaction_count: body_indices.tx_count,
+                    })
+                    .collect())
+            })
+            .await
+    }
+
+    /// Returns the summaries of all transactions in a block.
+    async fn block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<Vec<TransactionSummary>>, Eth::Error> {
+        let Some((block, receipts)) = self.eth.load_block_and_receipts(block_id).await? else {
+            return Ok(None)
+        };
+
+        let mut cumulative_gas_used = 0;
+        let transactions = block
+            .transactions_with_sender()
+            .zip(receipts.iter())
+            .map(|((sender, tx), receipt)| {
+                let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+                cumulative_gas_used = receipt.cumulative_gas_used();
+                TransactionSummary {
+                    hash: *tx.tx_hash(),
+                    from: *sender,
+                    to: tx.to(),
+                    value: tx.value(),
+                    nonce: tx.nonce(),
+                    tx_type: tx.ty(),
+                    gas_used,
+                    status: receipt.status(),
+                }
+            })
+            .collect();
+
+        Ok(Some(transactions))
+    }
+
+    /// Returns the summary of an address at the given block.
+    async fn address_summary(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> Result<AddressSummary, Eth::Error> {
+        self.eth
+            .spawn_blocking_io(move |this| {
+                let state = this.state_at_block_id_or_latest(block_id)?;
+                let account = state.basic_account(&address).map_err(Eth::Error::from_eth_err)?;
+                Ok(account
+                    .map(|account| AddressSummary {
+                        balance: account.balance,
+                        nonce: account.nonce,
+                        is_contract: account.has_bytecode(),
+                    })
+                    .unwrap_or_default())
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<Eth> ExplorerApiServer for ExplorerApi<Eth>
+where
+    Eth: EthBlocks + LoadReceipt + LoadState + 'static,
+{
+    /// Handler for `explorer_getLatestBlocks`
+    async fn get_latest_blocks(&self, count: u64) -> RpcResult<Vec<BlockSummary>> {
+        self.latest_blocks(count).await.map_err(Into::into)
+    }
+
+    /// Handler for `explorer_getBlockTransactions`
+    async fn get_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionSummary>>> {
+        self.block_transactions(block_id).await.map_err(Into::into)
+    }
+
+    /// Handler for `explorer_getAddressSummary`
+    async fn get_address_summary(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<AddressSummary> {
+        self.address_summary(address, block_id).await.map_err(Into::into)
+    }
+}
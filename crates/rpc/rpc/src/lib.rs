@@ -36,6 +36,7 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+mod explorer;
 mod miner;
 mod net;
 mod otterscan;
@@ -49,14 +50,18 @@ mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub,
+    EthTransactionConditional,
+};
+pub use explorer::ExplorerApi;
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethFees};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+pub use trace::{OpcodeStatsFrame, TraceApi, OPCODE_STATS_TRACER};
 pub use txpool::TxPoolApi;
 pub use validation::{ValidationApi, ValidationApiConfig};
 pub use web3::Web3Api;
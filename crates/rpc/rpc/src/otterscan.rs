@@ -1,7 +1,7 @@
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -12,14 +12,18 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_primitives_traits::SignedTransaction;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
-    helpers::{EthTransactions, TraceExt},
+    helpers::{EthTransactions, SpawnBlocking, TraceExt},
     FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
-use reth_rpc_eth_types::{utils::binary_search, EthApiError};
+use reth_rpc_eth_types::{utils::binary_search, EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::{
+    BlockBodyIndicesProvider, BlockReaderIdExt, SenderTransactionsProvider, TransactionsProvider,
+};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
@@ -30,18 +34,20 @@ const API_LEVEL: u64 = 8;
 
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Eth, Provider> {
     eth: Eth,
+    /// Serves the transactions of an address from the sender transactions index.
+    provider: Provider,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Eth, Provider> OtterscanApi<Eth, Provider> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(eth: Eth, provider: Provider) -> Self {
+        Self { eth, provider }
     }
 }
 
-impl<Eth> OtterscanApi<Eth>
+impl<Eth, Provider> OtterscanApi<Eth, Provider>
 where
     Eth: FullEthApiTypes,
 {
@@ -63,9 +69,75 @@ where
     }
 }
 
+impl<Eth, Provider> OtterscanApi<Eth, Provider>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + 'static,
+    Provider: BlockReaderIdExt + SenderTransactionsProvider + Clone + 'static,
+{
+    /// Returns a page of the transactions sent or received by `address` in the blocks before or
+    /// after the given block, looked up in the transactions index.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+        direction: SearchDirection,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let block_number = block_number.into_inner();
+        let block_number = self
+            .provider
+            .convert_block_number(block_number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+
+        let provider = self.provider.clone();
+        let (page, has_more) = self
+            .eth
+            .spawn_blocking_io(move |_| {
+                Ok(search_transactions_page(
+                    &provider,
+                    address,
+                    block_number,
+                    page_size,
+                    direction,
+                )?)
+            })
+            .await
+            .map_err(Into::into)?;
+
+        let mut txs = Vec::with_capacity(page.len());
+        let mut receipts = Vec::with_capacity(page.len());
+        for (hash, timestamp) in page {
+            let tx = EthApiServer::transaction_by_hash(&self.eth, hash);
+            let receipt = EthApiServer::transaction_receipt(&self.eth, hash);
+            let (tx, receipt) = futures::try_join!(tx, receipt)?;
+            let tx = tx.ok_or(EthApiError::TransactionNotFound)?;
+            let receipt = receipt.ok_or(EthApiError::TransactionNotFound)?;
+
+            receipts.push(ots_receipt(receipt, tx.ty(), Some(timestamp)));
+            txs.push(tx);
+        }
+
+        // Block 0 is the start of the search in the respective direction.
+        let (first_page, last_page) = match direction {
+            SearchDirection::Before => (block_number == 0, !has_more),
+            SearchDirection::After => (!has_more, block_number == 0),
+        };
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+}
+
 #[async_trait]
-impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
-    for OtterscanApi<Eth>
+impl<Eth, Provider> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
+    for OtterscanApi<Eth, Provider>
 where
     Eth: EthApiServer<
             RpcTxReq<Eth::NetworkTypes>,
@@ -76,6 +148,7 @@ where
         > + EthTransactions
         + TraceExt
         + 'static,
+    Provider: BlockReaderIdExt + SenderTransactionsProvider + Clone + 'static,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
     async fn get_header_by_number(
@@ -247,32 +320,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,21 +332,21 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(address, block_number, page_size, SearchDirection::Before).await
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(address, block_number, page_size, SearchDirection::After).await
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -382,3 +430,220 @@ where
         Ok(found)
     }
 }
+
+/// The blocks an address transactions search covers, relative to the given block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchDirection {
+    /// The blocks before the given block, or all blocks for block 0.
+    Before,
+    /// The blocks after the given block.
+    After,
+}
+
+/// Looks up a page of the transactions sent or received by `address` in the transactions index.
+///
+/// Returns the hashes and block timestamps of the transactions, from the most recent to the oldest
+/// one, and whether there are more transactions beyond the page.
+fn search_transactions_page<Provider>(
+    provider: &Provider,
+    address: Address,
+    block_number: BlockNumber,
+    page_size: usize,
+    direction: SearchDirection,
+) -> EthResult<(Vec<(TxHash, u64)>, bool)>
+where
+    Provider: BlockReaderIdExt + SenderTransactionsProvider,
+{
+    let (ids, has_more) =
+        address_transactions_page(provider, address, block_number, page_size, direction)?;
+
+    let mut page = Vec::with_capacity(ids.len());
+    let mut block_timestamp = None;
+    for id in ids {
+        let block = provider.transaction_block(id)?.ok_or(EthApiError::TransactionNotFound)?;
+        let timestamp = match block_timestamp {
+            Some((number, timestamp)) if number == block => timestamp,
+            _ => {
+                let timestamp = provider
+                    .header_by_number(block)?
+                    .ok_or(EthApiError::HeaderNotFound(block.into()))?
+                    .timestamp();
+                block_timestamp = Some((block, timestamp));
+                timestamp
+            }
+        };
+
+        let tx = provider.transaction_by_id(id)?.ok_or(EthApiError::TransactionNotFound)?;
+        page.push((*tx.tx_hash(), timestamp));
+    }
+
+    Ok((page, has_more))
+}
+
+/// Looks up the ids of a page of the transactions sent or received by `address` in the
+/// transactions index.
+///
+/// Returns the ids from the most recent to the oldest transaction, and whether there are more
+/// transactions beyond the page. The page is filled up to `page_size` transactions and then
+/// completed with the remaining transactions of its last block.
+fn address_transactions_page<Provider>(
+    provider: &Provider,
+    address: Address,
+    block_number: BlockNumber,
+    page_size: usize,
+    direction: SearchDirection,
+) -> EthResult<(Vec<TxNumber>, bool)>
+where
+    Provider: TransactionsProvider + BlockBodyIndicesProvider + SenderTransactionsProvider,
+{
+    let transaction_ids = |range, limit| {
+        provider
+            .address_transaction_ids(address, range, limit, direction == SearchDirection::Before)?
+            .ok_or(EthApiError::Unsupported("sender transactions index is not enabled"))
+    };
+
+    // Transaction ids are assigned in block order, so the transactions of the searched blocks are
+    // a contiguous range of ids.
+    let indices = provider.block_body_indices(block_number)?;
+    let range = match direction {
+        SearchDirection::Before => {
+            0..indices
+                .filter(|_| block_number != 0)
+                .map_or(TxNumber::MAX, |indices| indices.first_tx_num())
+        }
+        SearchDirection::After => {
+            indices.map_or(TxNumber::MAX, |indices| indices.next_tx_num())..TxNumber::MAX
+        }
+    };
+
+    let mut ids = transaction_ids(range.clone(), page_size)?;
+    let mut has_more = false;
+    if let Some(&last) = ids.last().filter(|_| ids.len() >= page_size) {
+        let block = provider.transaction_block(last)?.ok_or(EthApiError::TransactionNotFound)?;
+        let indices =
+            provider.block_body_indices(block)?.ok_or(EthApiError::HeaderNotFound(block.into()))?;
+
+        // Complete the page with the remaining transactions of its last block, and look ahead for
+        // a transaction beyond it.
+        let (rest, beyond) = match direction {
+            SearchDirection::Before => {
+                (indices.first_tx_num()..last, range.start..indices.first_tx_num())
+            }
+            SearchDirection::After => {
+                (last + 1..indices.next_tx_num(), indices.next_tx_num()..range.end)
+            }
+        };
+        ids.extend(transaction_ids(rest, usize::MAX)?);
+        has_more = !transaction_ids(beyond, 1)?.is_empty();
+    }
+
+    // Pages are always ordered from the most recent to the oldest transaction.
+    if direction == SearchDirection::After {
+        ids.reverse();
+    }
+
+    Ok((ids, has_more))
+}
+
+/// Converts a receipt into the [`OtsTransactionReceipt`] without logs and bloom.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_db_api::{models::StoredBlockBodyIndices, tables, transaction::DbTxMut};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DBProvider, HistoryWriter, StageCheckpointWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+
+    const ALICE: Address = address!("0x0000000000000000000000000000000000000001");
+    const BOB: Address = address!("0x0000000000000000000000000000000000000002");
+
+    #[test]
+    fn address_transactions_pages() {
+        let factory = create_test_provider_factory();
+
+        // Blocks `0..=4` with three transactions each. Alice sends the first two transactions of
+        // every block, the first one to Bob, and Bob sends the last one to Alice.
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..=4u64 {
+            let tx = provider_rw.tx_ref();
+            tx.put::<tables::BlockBodyIndices>(
+                block,
+                StoredBlockBodyIndices { first_tx_num: block * 3, tx_count: 3 },
+            )
+            .unwrap();
+            tx.put::<tables::TransactionBlocks>(block * 3 + 2, block).unwrap();
+        }
+        provider_rw
+            .insert_sender_transactions_index([
+                (ALICE, (0..5).flat_map(|block| [block * 3, block * 3 + 1]).collect::<Vec<_>>()),
+                (BOB, (0..5).map(|block| block * 3 + 2).collect()),
+            ])
+            .unwrap();
+        provider_rw
+            .insert_recipient_transactions_index([
+                (ALICE, (0..5).map(|block| block * 3 + 2).collect::<Vec<_>>()),
+                (BOB, (0..5).map(|block| block * 3).collect()),
+            ])
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let page = |block_number, page_size, direction| {
+            address_transactions_page(&provider, BOB, block_number, page_size, direction)
+        };
+
+        // the index is not enabled
+        assert!(page(0, 3, SearchDirection::Before).is_err());
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexSenderTransactions, StageCheckpoint::new(4))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        let provider = factory.provider().unwrap();
+        let page = |block_number, page_size, direction| {
+            address_transactions_page(&provider, BOB, block_number, page_size, direction).unwrap()
+        };
+
+        // the last block of a page is always complete
+        assert_eq!(page(0, 3, SearchDirection::Before), (vec![14, 12, 11, 9], true));
+        assert_eq!(page(3, 3, SearchDirection::Before), (vec![8, 6, 5, 3], true));
+        assert_eq!(page(1, 10, SearchDirection::Before), (vec![2, 0], false));
+        assert_eq!(page(1, 1, SearchDirection::After), (vec![8, 6], true));
+        assert_eq!(page(3, 1, SearchDirection::After), (vec![14, 12], false));
+        assert_eq!(page(4, 1, SearchDirection::After), (vec![], false));
+    }
+}
@@ -1,46 +1,80 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader, Transaction};
+use alloy_eips::{
+    eip7685::{Requests, EMPTY_REQUESTS_HASH},
+    BlockId,
+};
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::{Address, TxHash, B256, U256, U64};
+use alloy_rpc_types_beacon::requests::ExecutionRequestsV4;
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
+use parking_lot::Mutex;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
-use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_errors::{ProviderError, RethError, RethResult};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SignedTransaction};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::{
+    AccountChange, ContractCreator, RethApiServer, RethFeesApiServer,
+    TRANSACTIONS_BY_SENDER_PAGE_SIZE,
+};
+use reth_rpc_eth_api::helpers::{EthFees, LoadBlock};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{
+    BlockReaderIdExt, ChangeSetReader, ContractDeploymentsProvider, RequestsProvider,
+    SenderTransactionsProvider, StateProviderFactory, TransactionVariant,
+};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::DEFAULT_BLOB_FEE_PROJECTION_BLOCKS;
+use revm::state::EvmState;
 use tokio::sync::oneshot;
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, EvmConfig> {
+    inner: Arc<RethApiInner<Provider, EvmConfig>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, EvmConfig> RethApi<Provider, EvmConfig> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// The evm config used to re-execute blocks.
+    pub fn evm_config(&self) -> &EvmConfig {
+        &self.inner.evm_config
+    }
+
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        evm_config: EvmConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, evm_config, task_spawner });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, EvmConfig> RethApi<Provider, EvmConfig>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider
+        + 'static,
+    EvmConfig: ConfigureEvm<Primitives: NodePrimitives<Block = Provider::Block>> + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,16 +122,207 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns a map of addresses to the account state before and after a particular block, for
+    /// all accounts changed in the block.
+    pub async fn account_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<HashMap<Address, AccountChange>> {
+        self.on_blocking_task(|this| async move { this.try_account_changes_in_block(block_id) })
+            .await
+    }
+
+    fn try_account_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<HashMap<Address, AccountChange>> {
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+
+        let state = self.provider().state_by_block_id(block_id)?;
+        let accounts_before = self.provider().account_block_changeset(block_number)?;
+        let hash_map = accounts_before.into_iter().try_fold(
+            HashMap::default(),
+            |mut hash_map, account_before| -> RethResult<_> {
+                let after = state.basic_account(&account_before.address)?;
+                if after != account_before.info {
+                    hash_map.insert(
+                        account_before.address,
+                        AccountChange { before: account_before.info, after },
+                    );
+                }
+                Ok(hash_map)
+            },
+        )?;
+        Ok(hash_map)
+    }
+
+    /// Returns the decoded execution requests of a particular block.
+    pub async fn execution_requests(&self, block_id: BlockId) -> EthResult<ExecutionRequestsV4> {
+        self.on_blocking_task(|this| async move { this.try_execution_requests(block_id) }).await
+    }
+
+    fn try_execution_requests(&self, block_id: BlockId) -> EthResult<ExecutionRequestsV4> {
+        let header = self
+            .provider()
+            .sealed_header_by_id(block_id)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        // requests were introduced with Prague
+        let Some(requests_hash) = header.requests_hash() else {
+            return Ok(ExecutionRequestsV4::default())
+        };
+
+        let requests = match self.provider().requests_by_block(header.number())? {
+            Some(requests) => requests,
+            // only blocks with requests have stored requests
+            None if requests_hash == EMPTY_REQUESTS_HASH => Requests::default(),
+            None => {
+                return Err(EthApiError::Unsupported(
+                    "execution requests of the block are not available",
+                ))
+            }
+        };
+
+        ExecutionRequestsV4::try_from(&requests)
+            .map_err(|err| EthApiError::Internal(RethError::msg(err)))
+    }
+
+    /// Returns the hashes of the transactions sent by the given address on the given page.
+    pub async fn transactions_by_sender(
+        &self,
+        sender: Address,
+        page: u64,
+    ) -> EthResult<Vec<TxHash>> {
+        self.on_blocking_task(|this| async move { this.try_transactions_by_sender(sender, page) })
+            .await
+    }
+
+    fn try_transactions_by_sender(&self, sender: Address, page: u64) -> EthResult<Vec<TxHash>> {
+        let offset = usize::try_from(page)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(TRANSACTIONS_BY_SENDER_PAGE_SIZE);
+        let Some(ids) = self.provider().sender_transaction_ids(
+            sender,
+            offset,
+            TRANSACTIONS_BY_SENDER_PAGE_SIZE,
+        )?
+        else {
+            return Err(EthApiError::Unsupported("sender transactions index is not enabled"))
+        };
+
+        ids.into_iter()
+            .map(|id| {
+                let tx = self
+                    .provider()
+                    .transaction_by_id(id)?
+                    .ok_or(ProviderError::TransactionNotFound(id.into()))?;
+                Ok(*tx.tx_hash())
+            })
+            .collect()
+    }
+
+    /// Returns the deployment of the contract currently deployed at the given address.
+    pub async fn contract_creator(&self, address: Address) -> EthResult<Option<ContractCreator>> {
+        self.on_blocking_task(|this| async move { this.try_contract_creator(address) }).await
+    }
+
+    fn try_contract_creator(&self, address: Address) -> EthResult<Option<ContractCreator>> {
+        if !self.provider().contract_deployments_indexed()? {
+            return Err(EthApiError::Unsupported("contract deployments index is not enabled"))
+        }
+
+        let Some(code_hash) = self
+            .provider()
+            .latest()?
+            .basic_account(&address)?
+            .and_then(|account| account.bytecode_hash)
+            .filter(|hash| *hash != KECCAK_EMPTY)
+        else {
+            return Ok(None)
+        };
+
+        let Some(deployment) = self.provider().contract_deployment(address, code_hash)? else {
+            return Ok(None)
+        };
+        let block_id = BlockId::from(deployment.block_number);
+        let block = self
+            .provider()
+            .block_with_senders_by_id(block_id, TransactionVariant::WithHash)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        // contracts deployed by a transaction directly can be found without re-executing the block
+        let index = match block
+            .transactions_with_sender()
+            .position(|(sender, tx)| tx.kind().is_create() && sender.create(tx.nonce()) == address)
+        {
+            Some(index) => Some(index),
+            None => self.deployment_transaction_index(&block, address, code_hash)?,
+        };
+        let (creator, tx) = index
+            .and_then(|index| block.transactions_with_sender().nth(index))
+            .ok_or_else(|| {
+                EthApiError::Internal(RethError::msg(format!(
+                    "deployment of {address} not found in block {}",
+                    deployment.block_number
+                )))
+            })?;
+
+        Ok(Some(ContractCreator {
+            code_hash,
+            block_number: deployment.block_number,
+            block_hash: block.hash(),
+            transaction_hash: *tx.tx_hash(),
+            creator: *creator,
+        }))
+    }
+
+    /// Re-executes the block and returns the index of the last transaction that created the
+    /// contract at the given address with the given code hash.
+    fn deployment_transaction_index(
+        &self,
+        block: &RecoveredBlock<Provider::Block>,
+        address: Address,
+        code_hash: B256,
+    ) -> EthResult<Option<usize>> {
+        let deployed_in = Arc::new(Mutex::new(None));
+        let hook = {
+            let deployed_in = deployed_in.clone();
+            move |source: StateChangeSource, state: &EvmState| {
+                if let StateChangeSource::Transaction(index) = source {
+                    if state.get(&address).is_some_and(|account| {
+                        account.is_created() && account.info.code_hash == code_hash
+                    }) {
+                        *deployed_in.lock() = Some(index);
+                    }
+                }
+            }
+        };
+
+        let state = self.provider().history_by_block_hash(block.header().parent_hash())?;
+        self.evm_config()
+            .executor(StateProviderDatabase::new(state))
+            .execute_with_state_hook(block, hook)
+            .map_err(|err| EthApiError::Internal(err.into()))?;
+
+        Ok(deployed_in.lock().take())
+    }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, EvmConfig> RethApiServer for RethApi<Provider, EvmConfig>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
         + StateProviderFactory
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider
         + CanonStateSubscriptions
         + 'static,
+    EvmConfig: ConfigureEvm<Primitives: NodePrimitives<Block = Provider::Block>> + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -107,6 +332,39 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getAccountChangesInBlock`
+    async fn reth_get_account_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<HashMap<Address, AccountChange>> {
+        Ok(Self::account_changes_in_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getExecutionRequests`
+    async fn reth_get_execution_requests(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<ExecutionRequestsV4> {
+        Ok(Self::execution_requests(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getTransactionsBySender`
+    async fn reth_get_transactions_by_sender(
+        &self,
+        address: Address,
+        page: u64,
+    ) -> RpcResult<Vec<TxHash>> {
+        Ok(Self::transactions_by_sender(self, address, page).await?)
+    }
+
+    /// Handler for `reth_getContractCreator`
+    async fn reth_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> RpcResult<Option<ContractCreator>> {
+        Ok(Self::contract_creator(self, address).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -122,6 +380,31 @@ where
     }
 }
 
+/// `reth` API implementation of the fee methods, backed by the `eth` API.
+#[derive(Debug, Clone)]
+pub struct RethFees<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethFees<Eth> {
+    /// Creates a new instance with the given `eth` API.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+#[async_trait]
+impl<Eth> RethFeesApiServer for RethFees<Eth>
+where
+    Eth: EthFees + LoadBlock + 'static,
+{
+    /// Handler for `reth_maxFeePerBlobGas`
+    async fn reth_max_fee_per_blob_gas(&self, blocks: Option<U64>) -> RpcResult<U256> {
+        let blocks = blocks.map_or(DEFAULT_BLOB_FEE_PROJECTION_BLOCKS, |blocks| blocks.to());
+        self.eth_api.suggested_max_fee_per_blob_gas(blocks).await.map_err(Into::into)
+    }
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<N: NodePrimitives>(
     sink: SubscriptionSink,
@@ -152,21 +435,65 @@ async fn pipe_from_stream<N: NodePrimitives>(
     }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, EvmConfig> std::fmt::Debug for RethApi<Provider, EvmConfig> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, EvmConfig> Clone for RethApi<Provider, EvmConfig> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, EvmConfig> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The evm config used to re-execute blocks.
+    evm_config: EvmConfig,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::Bytes;
+    use reth_ethereum_primitives::Block;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+
+    #[tokio::test]
+    async fn execution_requests_are_read_from_storage() {
+        let provider = MockEthProvider::default();
+        // a deposit request is its type followed by a 192 byte SSZ encoded deposit
+        let mut deposit = vec![0x00];
+        deposit.extend([0xaa; 192]);
+        let requests = Requests::new(vec![Bytes::from(deposit)]);
+
+        // block 1 is pre-Prague, block 2 has requests, block 3 has none and block 4 has requests
+        // that are not stored
+        let requests_hashes =
+            [None, Some(requests.requests_hash()), Some(EMPTY_REQUESTS_HASH), Some(B256::ZERO)];
+        for (number, requests_hash) in (1..).zip(requests_hashes) {
+            let header = Header { number, requests_hash, ..Default::default() };
+            provider.add_block(
+                B256::with_last_byte(number as u8),
+                Block { header, body: Default::default() },
+            );
+        }
+        provider.add_requests(2, requests);
+
+        let api =
+            RethApi::new(provider, EthEvmConfig::mainnet(), Box::new(TokioTaskExecutor::default()));
+        assert_eq!(api.execution_requests(1.into()).await.unwrap(), ExecutionRequestsV4::default());
+        let stored = api.execution_requests(2.into()).await.unwrap();
+        assert_eq!(stored.deposits.len(), 1);
+        assert!(stored.withdrawals.is_empty() && stored.consolidations.is_empty());
+        assert_eq!(api.execution_requests(3.into()).await.unwrap(), ExecutionRequestsV4::default());
+        assert!(matches!(api.execution_requests(4.into()).await, Err(EthApiError::Unsupported(_))));
+    }
+}
@@ -8,7 +8,7 @@ use alloy_rpc_types_eth::{
 };
 use alloy_rpc_types_trace::{
     filter::TraceFilter,
-    opcode::{BlockOpcodeGas, TransactionOpcodeGas},
+    opcode::{BlockOpcodeGas, OpcodeGas, TransactionOpcodeGas},
     parity::*,
     tracerequest::TraceCallRequest,
 };
@@ -24,16 +24,19 @@ use reth_rpc_eth_api::{
     helpers::{Call, LoadPendingBlock, LoadTransaction, Trace, TraceExt},
     FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
+use reth_rpc_eth_types::{
+    error::EthApiError, limits::with_timeout, utils::recover_raw_transaction, EthConfig,
+};
 use reth_storage_api::{BlockNumReader, BlockReader};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
-use revm::DatabaseCommit;
+use revm::{context_interface::result::ResultAndState, DatabaseCommit};
 use revm_inspectors::{
     opcode::OpcodeGasInspector,
-    tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
+    tracing::{TracingInspector, TracingInspectorConfig},
 };
-use std::sync::Arc;
+use serde::Serialize;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -84,6 +87,11 @@ where
     // bound
     Eth: Trace + Call + LoadPendingBlock + LoadTransaction + 'static,
 {
+    /// Returns the currently configured timeout of `trace_*` requests.
+    fn trace_timeout(&self) -> Option<Duration> {
+        self.eth_api().rpc_limits().trace_timeout()
+    }
+
     /// Executes the given call and returns a number of possible traces for it.
     pub async fn trace_call(
         &self,
@@ -96,18 +104,24 @@ where
         let mut inspector = TracingInspector::new(config);
         let this = self.clone();
         self.eth_api()
-            .spawn_with_call_at(trace_request.call, at, overrides, move |db, evm_env, tx_env| {
-                // wrapper is hack to get around 'higher-ranked lifetime error', see
-                // <https://github.com/rust-lang/rust/issues/100013>
-                let db = db.0;
-
-                let res = this.eth_api().inspect(&mut *db, evm_env, tx_env, &mut inspector)?;
-                let trace_res = inspector
-                    .into_parity_builder()
-                    .into_trace_results_with_state(&res, &trace_request.trace_types, &db)
-                    .map_err(Eth::Error::from_eth_err)?;
-                Ok(trace_res)
-            })
+            .spawn_with_call_at(
+                trace_request.call,
+                at,
+                overrides,
+                false,
+                move |db, evm_env, tx_env| {
+                    // wrapper is hack to get around 'higher-ranked lifetime error', see
+                    // <https://github.com/rust-lang/rust/issues/100013>
+                    let db = db.0;
+
+                    let res = this.eth_api().inspect(&mut *db, evm_env, tx_env, &mut inspector)?;
+                    let trace_res = inspector
+                        .into_parity_builder()
+                        .into_trace_results_with_state(&res, &trace_request.trace_types, &db)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    Ok(trace_res)
+                },
+            )
             .await
     }
 
@@ -163,6 +177,7 @@ where
                         call,
                         &mut db,
                         Default::default(),
+                        false,
                     )?;
                     let config = TracingInspectorConfig::from_parity_config(&trace_types);
                     let mut inspector = TracingInspector::new(config);
@@ -209,22 +224,22 @@ where
             .ok_or(EthApiError::TransactionNotFound)?
     }
 
-    /// Returns transaction trace objects at the given index
+    /// Returns the transaction trace object at the given trace address.
     ///
-    /// Note: For compatibility reasons this only supports 1 single index, since this method is
-    /// supposed to return a single trace. See also: <https://github.com/ledgerwatch/erigon/blob/862faf054b8a0fa15962a9c73839b619886101eb/turbo/jsonrpc/trace_filtering.go#L114-L133>
+    /// The `indices` are the path to the trace in the call tree of the transaction, i.e. the
+    /// `traceAddress` of the trace: `[]` is the top level call, `[0]` is its first subcall and
+    /// `[0, 1]` is the second subcall of the first subcall. This matches the `OpenEthereum`
+    /// implementation.
     ///
-    /// This returns `None` if `indices` is empty
+    /// This returns `None` if the transaction or the trace does not exist.
     pub async fn trace_get(
         &self,
         hash: B256,
         indices: Vec<usize>,
     ) -> Result<Option<LocalizedTransactionTrace>, Eth::Error> {
-        if indices.len() != 1 {
-            // The OG impl failed if it gets more than a single index
-            return Ok(None)
-        }
-        self.trace_get_index(hash, indices[0]).await
+        Ok(self.trace_transaction(hash).await?.and_then(|traces| {
+            traces.into_iter().find(|trace| trace.trace.trace_address == indices)
+        }))
     }
 
     /// Returns transaction trace object at the given index.
@@ -510,17 +525,20 @@ where
                 None,
                 TracingInspectorConfig::from_parity_config(&trace_types),
                 move |tx_info, mut ctx| {
-                    let mut full_trace = ctx
-                        .take_inspector()
-                        .into_parity_builder()
-                        .into_trace_results(&ctx.result, &trace_types);
-
-                    // If statediffs were requested, populate them with the account balance and
-                    // nonce from pre-state
-                    if let Some(ref mut state_diff) = full_trace.state_diff {
-                        populate_state_diff(state_diff, &ctx.db, ctx.state.iter())
-                            .map_err(Eth::Error::from_eth_err)?;
-                    }
+                    let builder = ctx.take_inspector().into_parity_builder();
+
+                    // The state diff and the bytecodes of the vm trace are populated from the
+                    // state before the transaction
+                    let full_trace = if trace_types.contains(&TraceType::StateDiff) ||
+                        trace_types.contains(&TraceType::VmTrace)
+                    {
+                        let res = ResultAndState::new(ctx.result, ctx.state.clone());
+                        builder
+                            .into_trace_results_with_state(&res, &trace_types, &ctx.db)
+                            .map_err(Eth::Error::from_eth_err)?
+                    } else {
+                        builder.into_trace_results(&ctx.result, &trace_types)
+                    };
 
                     let trace = TraceResultsWithTransactionHash {
                         transaction_hash: tx_info.hash.expect("tx hash is set"),
@@ -584,10 +602,11 @@ where
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
+        let permit = self.acquire_trace_permit().await.ok();
         let request =
             TraceCallRequest { call, trace_types, block_id, state_overrides, block_overrides };
-        Ok(Self::trace_call(self, request).await.map_err(Into::into)?)
+        let trace = Self::trace_call(self, request);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_callMany`
@@ -596,8 +615,9 @@ where
         calls: Vec<(RpcTxReq<Eth::NetworkTypes>, HashSet<TraceType>)>,
         block_id: Option<BlockId>,
     ) -> RpcResult<Vec<TraceResults>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_call_many(self, calls, block_id).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_call_many(self, calls, block_id);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_rawTransaction`
@@ -607,10 +627,9 @@ where
         trace_types: HashSet<TraceType>,
         block_id: Option<BlockId>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_raw_transaction(self, data, trace_types, block_id)
-            .await
-            .map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_raw_transaction(self, data, trace_types, block_id);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_replayBlockTransactions`
@@ -619,10 +638,9 @@ where
         block_id: BlockId,
         trace_types: HashSet<TraceType>,
     ) -> RpcResult<Option<Vec<TraceResultsWithTransactionHash>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::replay_block_transactions(self, block_id, trace_types)
-            .await
-            .map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::replay_block_transactions(self, block_id, trace_types);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_replayTransaction`
@@ -631,8 +649,9 @@ where
         transaction: B256,
         trace_types: HashSet<TraceType>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::replay_transaction(self, transaction, trace_types).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::replay_transaction(self, transaction, trace_types);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_block`
@@ -640,8 +659,9 @@ where
         &self,
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_block(self, block_id).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_block(self, block_id);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_filter`
@@ -654,17 +674,15 @@ where
         Ok(Self::trace_filter(self, filter).await.map_err(Into::into)?)
     }
 
-    /// Returns transaction trace at given index.
     /// Handler for `trace_get`
     async fn trace_get(
         &self,
         hash: B256,
         indices: Vec<Index>,
     ) -> RpcResult<Option<LocalizedTransactionTrace>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_get(self, hash, indices.into_iter().map(Into::into).collect())
-            .await
-            .map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_get(self, hash, indices.into_iter().map(Into::into).collect());
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_transaction`
@@ -672,8 +690,9 @@ where
         &self,
         hash: B256,
     ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_transaction(self, hash).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_transaction(self, hash);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_transactionOpcodeGas`
@@ -681,14 +700,16 @@ where
         &self,
         tx_hash: B256,
     ) -> RpcResult<Option<TransactionOpcodeGas>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_transaction_opcode_gas(self, tx_hash).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_transaction_opcode_gas(self, tx_hash);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_blockOpcodeGas`
     async fn trace_block_opcode_gas(&self, block_id: BlockId) -> RpcResult<Option<BlockOpcodeGas>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_block_opcode_gas(self, block_id).await.map_err(Into::into)?)
+        let permit = self.acquire_trace_permit().await.ok();
+        let trace = Self::trace_block_opcode_gas(self, block_id);
+        Ok(with_timeout(self.trace_timeout(), permit, trace).await.map_err(Into::into)?)
     }
 }
 
@@ -712,6 +733,33 @@ struct TraceApiInner<Eth> {
     eth_config: EthConfig,
 }
 
+/// Name of the `debug_trace*` tracer that aggregates the number of executions and the gas usage
+/// per opcode, without recording the individual steps like the default struct logger.
+pub const OPCODE_STATS_TRACER: &str = "opcodeStats";
+
+/// The result of the [`OPCODE_STATS_TRACER`] for a single transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeStatsFrame {
+    /// Total gas used by the transaction.
+    pub gas_used: u64,
+    /// The executed opcodes with their count and combined gas usage, highest gas usage first.
+    ///
+    /// The gas usage of call and create opcodes excludes the gas consumed by the sub-call.
+    pub opcodes: Vec<OpcodeGas>,
+}
+
+impl OpcodeStatsFrame {
+    /// Creates the frame from the stats collected by the inspector.
+    pub fn new(inspector: &OpcodeGasInspector, gas_used: u64) -> Self {
+        let mut opcodes = inspector.opcode_gas_iter().collect::<Vec<_>>();
+        opcodes.sort_unstable_by(|a, b| {
+            b.gas_used.cmp(&a.gas_used).then_with(|| a.opcode.cmp(&b.opcode))
+        });
+        Self { gas_used, opcodes }
+    }
+}
+
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
 /// beneficiary.
 fn reward_trace<H: BlockHeader>(header: &H, reward: RewardAction) -> LocalizedTransactionTrace {
@@ -9,11 +9,14 @@ use alloy_rpc_types_txpool::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{
+    TxPoolApiServer, DEFAULT_TXPOOL_STATS_PERCENTILES, MAX_TXPOOL_STATS_PERCENTILES,
+};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, PoolConsensusTx, PoolStats, PoolTransaction, TransactionPool,
 };
 use tracing::trace;
 
@@ -92,6 +95,26 @@ where
         Ok(TxpoolStatus { pending: pending as u64, queued: queued as u64 })
     }
 
+    /// Returns the fee distribution, accumulated gas and blob counts of each sub-pool.
+    ///
+    /// Handler for `txpool_stats`
+    async fn txpool_stats(&self, percentiles: Option<Vec<f64>>) -> RpcResult<PoolStats> {
+        trace!(target: "rpc::eth", ?percentiles, "Serving txpool_stats");
+        let percentiles = percentiles.unwrap_or_else(|| DEFAULT_TXPOOL_STATS_PERCENTILES.to_vec());
+        if percentiles.len() > MAX_TXPOOL_STATS_PERCENTILES {
+            return Err(invalid_params_rpc_err(format!(
+                "too many percentiles, at most {MAX_TXPOOL_STATS_PERCENTILES} are allowed"
+            )))
+        }
+        if let Some(percentile) = percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+            return Err(invalid_params_rpc_err(format!(
+                "invalid percentile {percentile}, must be in 0..=100"
+            )))
+        }
+
+        Ok(self.pool.pool_stats(&percentiles))
+    }
+
     /// Returns a summary of all the transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
@@ -154,3 +177,55 @@ impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::helpers::types::EthRpcConverter;
+    use reth_chainspec::MAINNET;
+    use reth_rpc_eth_types::receipt::EthReceiptConverter;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    #[tokio::test]
+    async fn txpool_stats() {
+        let pool = testing_pool();
+        let api = TxPoolApi::new(
+            pool.clone(),
+            EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone())),
+        );
+
+        let tx = MockTransaction::eip1559()
+            .with_gas_limit(21_000)
+            .with_priority_fee(2_000_000_000)
+            .with_max_fee(100_000_000_000);
+        pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+
+        let stats = api.txpool_stats(Some(vec![50.0])).await.unwrap();
+        let pending = serde_json::to_value(&stats).unwrap()["pending"].clone();
+        assert_eq!(
+            pending,
+            serde_json::json!({
+                "transactions": "0x1",
+                "gas": "0x5208",
+                "blobTransactions": "0x0",
+                "blobs": "0x0",
+                "maxFeePercentiles": ["0x174876e800"],
+                "priorityFeePercentiles": ["0x77359400"],
+            })
+        );
+        assert_eq!(stats.queued, PoolStats::empty(1).queued);
+
+        // the default percentiles are used if none are requested
+        let stats = api.txpool_stats(None).await.unwrap();
+        assert_eq!(stats.pending.max_fee_percentiles.len(), DEFAULT_TXPOOL_STATS_PERCENTILES.len());
+
+        assert!(api.txpool_stats(Some(vec![101.0])).await.is_err());
+        assert!(api
+            .txpool_stats(Some(vec![50.0; MAX_TXPOOL_STATS_PERCENTILES + 1]))
+            .await
+            .is_err());
+    }
+}
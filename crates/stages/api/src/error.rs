@@ -118,6 +118,18 @@ pub enum StageError {
     Fatal(Box<dyn core::error::Error + Send + Sync>),
 }
 
+/// Class of a non-fatal [`StageError`] that the [Pipeline][crate::Pipeline] retries, see
+/// [`RetryPolicy`][crate::RetryPolicy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StageErrorClass {
+    /// [`StageError::Recoverable`] errors.
+    Recoverable,
+    /// Errors of the pruner or its configuration.
+    Pruning,
+    /// All other non-fatal errors.
+    Other,
+}
+
 impl StageError {
     /// If the error is fatal the pipeline will stop.
     pub const fn is_fatal(&self) -> bool {
@@ -134,6 +146,25 @@ impl StageError {
                 Self::Fatal(_)
         )
     }
+
+    /// Returns the class of the error if the pipeline retries the stage on it.
+    ///
+    /// Returns `None` for fatal errors and for errors that make the pipeline unwind.
+    pub const fn retry_class(&self) -> Option<StageErrorClass> {
+        if self.is_fatal() {
+            return None
+        }
+        match self {
+            Self::Recoverable(_) => Some(StageErrorClass::Recoverable),
+            Self::PruningConfiguration(_) | Self::Pruner(_) | Self::MissingPruneCheckpoint(_) => {
+                Some(StageErrorClass::Pruning)
+            }
+            Self::DetachedHead { .. } | Self::Block { .. } | Self::MissingStaticFileData { .. } => {
+                None
+            }
+            _ => Some(StageErrorClass::Other),
+        }
+    }
 }
 
 impl From<std::io::Error> for StageError {
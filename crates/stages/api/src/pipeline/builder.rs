@@ -1,4 +1,6 @@
-use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageId, StageSet};
+use crate::{
+    pipeline::BoxedStage, MetricEventsSender, Pipeline, RetryPolicy, Stage, StageId, StageSet,
+};
 use alloy_primitives::{BlockNumber, B256};
 use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory};
 use reth_static_file::StaticFileProducer;
@@ -15,6 +17,8 @@ pub struct PipelineBuilder<Provider> {
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
     fail_on_unwind: bool,
+    /// How stages are retried on non-fatal errors.
+    retry_policy: RetryPolicy,
 }
 
 impl<Provider> PipelineBuilder<Provider> {
@@ -69,6 +73,12 @@ impl<Provider> PipelineBuilder<Provider> {
         self
     }
 
+    /// Set how stages are retried on non-fatal errors.
+    pub const fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build<N>(
         self,
@@ -79,7 +89,7 @@ impl<Provider> PipelineBuilder<Provider> {
         N: ProviderNodeTypes,
         ProviderFactory<N>: DatabaseProviderFactory<ProviderRW = Provider>,
     {
-        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind, retry_policy } = self;
         Pipeline {
             provider_factory,
             stages,
@@ -92,6 +102,8 @@ impl<Provider> PipelineBuilder<Provider> {
             fail_on_unwind,
             last_detached_head_unwind_target: None,
             detached_head_attempts: 0,
+            retry_policy,
+            retry_attempts: 0,
         }
     }
 }
@@ -104,6 +116,7 @@ impl<Provider> Default for PipelineBuilder<Provider> {
             tip_tx: None,
             metrics_tx: None,
             fail_on_unwind: false,
+            retry_policy: RetryPolicy::default(),
         }
     }
 }
@@ -114,6 +127,7 @@ impl<Provider> std::fmt::Debug for PipelineBuilder<Provider> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("fail_on_unwind", &self.fail_on_unwind)
+            .field("retry_policy", &self.retry_policy)
             .finish()
     }
 }
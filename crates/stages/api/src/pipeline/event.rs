@@ -1,9 +1,12 @@
 use crate::{
     stage::{ExecOutput, UnwindInput, UnwindOutput},
-    StageCheckpoint, StageId,
+    StageCheckpoint, StageErrorClass, StageId,
 };
 use alloy_primitives::BlockNumber;
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
 
 /// An event emitted by a [Pipeline][crate::Pipeline].
 ///
@@ -64,6 +67,17 @@ pub enum PipelineEvent {
         /// The stage that encountered an error.
         stage_id: StageId,
     },
+    /// Emitted when a stage is about to be retried after a non-fatal error.
+    Retrying {
+        /// The stage that is retried.
+        stage_id: StageId,
+        /// The class of the error the stage encountered.
+        class: StageErrorClass,
+        /// The number of consecutive retries of the stage, starting at `1`.
+        attempt: u32,
+        /// The delay before the stage is retried.
+        delay: Duration,
+    },
     /// Emitted when a stage was skipped due to it's run conditions not being met:
     ///
     /// - The stage might have progressed beyond the point of our target block
@@ -23,17 +23,31 @@ use tracing::*;
 
 mod builder;
 mod progress;
+mod retry;
 mod set;
 
 use crate::{
     BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender, PipelineError, Stage,
-    StageError, StageExt, UnwindInput,
+    StageError, StageErrorClass, StageExt, UnwindInput, UnwindPlan,
 };
 pub use builder::*;
 use progress::*;
 use reth_errors::RethResult;
+pub use retry::*;
 pub use set::*;
 
+/// The planned unwind of a single stage, see [`Pipeline::plan_unwind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageUnwindPlan {
+    /// The ID of the stage.
+    pub stage_id: StageId,
+    /// The current checkpoint of the stage.
+    pub checkpoint: StageCheckpoint,
+    /// The changes the unwind would make, or `None` if the stage would be skipped because its
+    /// checkpoint is already below the unwind target.
+    pub plan: Option<UnwindPlan>,
+}
+
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
 
@@ -92,6 +106,10 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Number of consecutive unwind attempts due to [`StageError::DetachedHead`] for the current
     /// fork.
     detached_head_attempts: u64,
+    /// How stages are retried on non-fatal errors.
+    retry_policy: RetryPolicy,
+    /// Number of consecutive retries of the current stage.
+    retry_attempts: u32,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -408,6 +426,40 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         Ok(())
     }
 
+    /// Plans an unwind of the stages to the target block (exclusive), without writing anything.
+    ///
+    /// Returns the changes each stage would make, in the order the stages would be unwound.
+    pub fn plan_unwind(&self, to: BlockNumber) -> Result<Vec<StageUnwindPlan>, PipelineError> {
+        let provider = self.provider_factory.provider()?;
+        let latest_block = provider.last_block_number()?;
+        provider.prune_modes_ref().ensure_unwind_target_unpruned(latest_block, to)?;
+
+        // The stages expect a read-write provider. The transaction is never committed, so it is
+        // aborted when the provider is dropped.
+        let provider_rw = self.provider_factory.database_provider_rw()?;
+
+        let mut plans = Vec::with_capacity(self.stages.len());
+        for stage in self.stages.iter().rev() {
+            let stage_id = stage.id();
+            let checkpoint = provider_rw.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+
+            let plan = if checkpoint.block_number > to {
+                let input = UnwindInput { checkpoint, unwind_to: to, bad_block: None };
+                Some(
+                    stage
+                        .plan_unwind(&provider_rw, input)
+                        .map_err(|err| PipelineError::Stage(StageError::Fatal(Box::new(err))))?,
+                )
+            } else {
+                None
+            };
+
+            plans.push(StageUnwindPlan { stage_id, checkpoint, plan });
+        }
+
+        Ok(plans)
+    }
+
     async fn execute_stage_to_completion(
         &mut self,
         previous_stage: Option<BlockNumber>,
@@ -418,6 +470,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let stage_id = self.stage(stage_index).id();
         let mut made_progress = false;
         let target = self.max_block.or(previous_stage);
+        self.retry_attempts = 0;
 
         loop {
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
@@ -455,7 +508,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
             if let Err(err) = self.stage(stage_index).execute_ready(exec_input).await {
                 self.event_sender.notify(PipelineEvent::Error { stage_id });
-                match self.on_stage_error(stage_id, prev_checkpoint, err)? {
+                match self.on_stage_error(stage_id, prev_checkpoint, err).await? {
                     Some(ctrl) => return Ok(ctrl),
                     None => continue,
                 };
@@ -476,6 +529,8 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
             match self.stage(stage_index).execute(&provider_rw, exec_input) {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
+                    self.retry_attempts = 0;
+
                     // Update stage checkpoint.
                     provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
 
@@ -518,7 +573,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                     drop(provider_rw);
                     self.event_sender.notify(PipelineEvent::Error { stage_id });
 
-                    if let Some(ctrl) = self.on_stage_error(stage_id, prev_checkpoint, err)? {
+                    if let Some(ctrl) = self.on_stage_error(stage_id, prev_checkpoint, err).await? {
                         return Ok(ctrl)
                     }
                 }
@@ -526,7 +581,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         }
     }
 
-    fn on_stage_error(
+    async fn on_stage_error(
         &mut self,
         stage_id: StageId,
         prev_checkpoint: Option<StageCheckpoint>,
@@ -622,12 +677,33 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
             Err(err.into())
         } else {
             // On other errors we assume they are recoverable if we discard the
-            // transaction and run the stage again.
+            // transaction and run the stage again after a backoff.
+            let class = err.retry_class().unwrap_or(StageErrorClass::Other);
+            self.retry_attempts += 1;
+            let attempt = self.retry_attempts;
+            let Some(delay) = self.retry_policy.backoff(class).delay(attempt) else {
+                error!(
+                    target: "sync::pipeline",
+                    stage = %stage_id,
+                    ?class,
+                    retries = attempt - 1,
+                    "Stage exceeded the maximum number of retries: {err}"
+                );
+                return Err(err.into())
+            };
+
             warn!(
                 target: "sync::pipeline",
                 stage = %stage_id,
+                ?class,
+                attempt,
+                ?delay,
                 "Stage encountered a non-fatal error: {err}. Retrying..."
             );
+            self.event_sender.notify(PipelineEvent::Retrying { stage_id, class, attempt, delay });
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
             Ok(None)
         }
     }
@@ -763,6 +839,48 @@ mod tests {
         assert_eq!(post_unwind_commit_counter_b.load(Ordering::Relaxed), 0);
     }
 
+    /// Plans an unwind without modifying the stage checkpoints.
+    #[test]
+    fn plan_unwind_pipeline() {
+        let provider_factory = create_test_provider_factory();
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), StageCheckpoint::new(10)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("B"), StageCheckpoint::new(3)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .add_stage(TestStage::new(StageId::Other("B")))
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+
+        // Stages are planned in reverse order, stages below the target are skipped
+        assert_eq!(
+            pipeline.plan_unwind(5).unwrap(),
+            vec![
+                StageUnwindPlan {
+                    stage_id: StageId::Other("B"),
+                    checkpoint: StageCheckpoint::new(3),
+                    plan: None,
+                },
+                StageUnwindPlan {
+                    stage_id: StageId::Other("A"),
+                    checkpoint: StageCheckpoint::new(10),
+                    plan: Some(UnwindPlan::default()),
+                },
+            ]
+        );
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Other("A")).unwrap(),
+            Some(StageCheckpoint::new(10))
+        );
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
@@ -1170,4 +1288,49 @@ mod tests {
             )))
         );
     }
+
+    /// Checks that the pipeline retries according to the retry policy and stops once the maximum
+    /// number of retries is exceeded.
+    #[tokio::test]
+    async fn pipeline_retry_policy() {
+        let provider_factory = create_test_provider_factory();
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("Retried"))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error)))),
+            )
+            .with_max_block(10)
+            .with_retry_policy(RetryPolicy::immediate().with_backoff(
+                StageErrorClass::Recoverable,
+                Backoff::immediate().with_max_attempts(2),
+            ))
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+
+        let result = pipeline.run().await;
+        assert_matches!(result, Err(PipelineError::Stage(StageError::Recoverable(_))));
+
+        drop(pipeline);
+        let retries = events
+            .filter_map(|event| match event {
+                PipelineEvent::Retrying { class, attempt, delay, .. } => {
+                    Some((class, attempt, delay))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            retries,
+            vec![
+                (StageErrorClass::Recoverable, 1, Duration::ZERO),
+                (StageErrorClass::Recoverable, 2, Duration::ZERO),
+            ]
+        );
+    }
 }
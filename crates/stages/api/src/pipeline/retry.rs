@@ -0,0 +1,116 @@
+use crate::StageErrorClass;
+use std::time::Duration;
+
+/// Exponential backoff between retries of a stage that failed with a non-fatal error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Upper bound of the delay, which is doubled with every consecutive attempt.
+    pub max_delay: Duration,
+    /// Maximum number of consecutive retries before the error is treated as fatal, unlimited if
+    /// `None`.
+    pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+    /// Creates a new [`Backoff`] with unlimited attempts.
+    pub const fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self { initial_delay, max_delay, max_attempts: None }
+    }
+
+    /// Retries right away, without any delay, an unlimited number of times.
+    pub const fn immediate() -> Self {
+        Self::new(Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Sets the maximum number of consecutive retries.
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Returns the delay before the given retry attempt, starting at `1`, or `None` if the
+    /// maximum number of attempts is exceeded.
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max_attempts| attempt > max_attempts) {
+            return None
+        }
+
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        Some(self.initial_delay.saturating_mul(factor).min(self.max_delay))
+    }
+}
+
+/// How the [`Pipeline`](crate::Pipeline) retries stages that failed with a non-fatal error, per
+/// [`StageErrorClass`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Backoff for [`StageErrorClass::Recoverable`] errors.
+    pub recoverable: Backoff,
+    /// Backoff for [`StageErrorClass::Pruning`] errors.
+    pub pruning: Backoff,
+    /// Backoff for [`StageErrorClass::Other`] errors.
+    pub other: Backoff,
+}
+
+impl RetryPolicy {
+    /// Retries all errors right away, without any delay, an unlimited number of times.
+    pub const fn immediate() -> Self {
+        Self {
+            recoverable: Backoff::immediate(),
+            pruning: Backoff::immediate(),
+            other: Backoff::immediate(),
+        }
+    }
+
+    /// Returns the backoff for the given class of errors.
+    pub const fn backoff(&self, class: StageErrorClass) -> &Backoff {
+        match class {
+            StageErrorClass::Recoverable => &self.recoverable,
+            StageErrorClass::Pruning => &self.pruning,
+            StageErrorClass::Other => &self.other,
+        }
+    }
+
+    /// Sets the backoff for the given class of errors.
+    pub const fn with_backoff(mut self, class: StageErrorClass, backoff: Backoff) -> Self {
+        match class {
+            StageErrorClass::Recoverable => self.recoverable = backoff,
+            StageErrorClass::Pruning => self.pruning = backoff,
+            StageErrorClass::Other => self.other = backoff,
+        }
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            recoverable: Backoff::new(Duration::from_millis(100), Duration::from_secs(30)),
+            pruning: Backoff::new(Duration::from_secs(1), Duration::from_secs(60)),
+            other: Backoff::new(Duration::from_secs(1), Duration::from_secs(60)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_delay() {
+        let backoff =
+            Backoff::new(Duration::from_millis(100), Duration::from_secs(1)).with_max_attempts(6);
+
+        assert_eq!(backoff.delay(1), Some(Duration::from_millis(100)));
+        assert_eq!(backoff.delay(2), Some(Duration::from_millis(200)));
+        assert_eq!(backoff.delay(4), Some(Duration::from_millis(800)));
+        assert_eq!(backoff.delay(5), Some(Duration::from_secs(1)));
+        assert_eq!(backoff.delay(6), Some(Duration::from_secs(1)));
+        assert_eq!(backoff.delay(7), None);
+
+        let unlimited = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(unlimited.delay(u32::MAX), Some(Duration::from_secs(1)));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{error::StageError, StageCheckpoint, StageId};
 use alloy_primitives::{BlockNumber, TxNumber};
 use reth_provider::{BlockReader, ProviderError};
+use reth_static_file_types::StaticFileSegment;
 use std::{
     cmp::{max, min},
     future::{poll_fn, Future},
@@ -183,6 +184,53 @@ pub struct UnwindOutput {
     pub checkpoint: StageCheckpoint,
 }
 
+/// The changes that unwinding a stage would make, see [`Stage::plan_unwind`].
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct UnwindPlan {
+    /// The database tables that would be modified.
+    pub tables: Vec<TableUnwindPlan>,
+    /// The static file segments that would be pruned.
+    pub static_file_segments: Vec<StaticFileUnwindPlan>,
+}
+
+impl UnwindPlan {
+    /// Adds a database table with the number of entries that would be removed or modified.
+    pub fn with_table(mut self, table: &'static str, entries: Option<u64>) -> Self {
+        self.tables.push(TableUnwindPlan { table, entries });
+        self
+    }
+
+    /// Adds a static file segment with the number of rows that would be pruned.
+    pub fn with_static_file_segment(mut self, segment: StaticFileSegment, rows: u64) -> Self {
+        self.static_file_segments.push(StaticFileUnwindPlan { segment, rows });
+        self
+    }
+
+    /// Returns `true` if the unwind would not modify any table or static file segment.
+    pub const fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.static_file_segments.is_empty()
+    }
+}
+
+/// The changes an unwind would make to a single database table.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TableUnwindPlan {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The number of entries that would be removed or modified, if it can be determined without
+    /// performing the unwind.
+    pub entries: Option<u64>,
+}
+
+/// The changes an unwind would make to a single static file segment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StaticFileUnwindPlan {
+    /// The static file segment.
+    pub segment: StaticFileSegment,
+    /// The number of rows that would be pruned.
+    pub rows: u64,
+}
+
 /// A stage is a segmented part of the syncing process of the node.
 ///
 /// Each stage takes care of a well-defined task, such as downloading headers or executing
@@ -254,6 +302,20 @@ pub trait Stage<Provider>: Send + Sync {
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError>;
 
+    /// Returns the changes that [`Self::unwind`] would make for the given input, without writing
+    /// anything.
+    ///
+    /// The whole range of the input is planned at once, regardless of any commit thresholds of the
+    /// stage. Stages that don't modify any data on unwind, or that can't report their changes,
+    /// return an empty plan.
+    fn plan_unwind(
+        &self,
+        _provider: &Provider,
+        _input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        Ok(UnwindPlan::default())
+    }
+
     /// Post unwind commit hook.
     ///
     /// This is called after the stage has been unwound and the data has been committed by the
@@ -39,7 +39,8 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
+        HeaderStage, IndexAccountHistoryStage, IndexContractDeploymentsStage,
+        IndexSenderTransactionsStage, IndexStorageHistoryStage, MerkleStage,
         PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
         TransactionLookupStage,
     },
@@ -70,6 +71,7 @@ use tokio::sync::watch;
 /// - [`BodyStage`]
 /// - [`SenderRecoveryStage`]
 /// - [`ExecutionStage`]
+/// - [`IndexSenderTransactionsStage`] (if enabled)
 /// - [`PruneSenderRecoveryStage`] (execute)
 /// - [`MerkleStage`] (unwind)
 /// - [`AccountHashingStage`]
@@ -78,6 +80,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexContractDeploymentsStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -286,6 +289,7 @@ where
 /// A combination of (in order)
 ///
 /// - [`ExecutionStages`]
+/// - [`IndexSenderTransactionsStage`] (if enabled)
 /// - [`PruneSenderRecoveryStage`]
 /// - [`HashingStages`]
 /// - [`HistoryIndexingStages`]
@@ -319,6 +323,7 @@ impl<E, Provider> StageSet<Provider> for OfflineStages<E>
 where
     E: ConfigureEvm,
     ExecutionStages<E>: StageSet<Provider>,
+    IndexSenderTransactionsStage: Stage<Provider>,
     PruneSenderRecoveryStage: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
@@ -327,6 +332,14 @@ where
     fn builder(self) -> StageSetBuilder<Provider> {
         ExecutionStages::new(self.evm_config, self.consensus, self.stages_config.clone())
             .builder()
+            // If the sender transactions index is enabled, add its stage. It has to run before
+            // the senders are pruned.
+            .add_stage_opt(self.stages_config.index_sender_transactions.enabled.then(|| {
+                IndexSenderTransactionsStage::new(
+                    self.stages_config.index_sender_transactions,
+                    self.stages_config.etl.clone(),
+                )
+            }))
             // If sender recovery prune mode is set, add the prune sender recovery stage.
             .add_stage_opt(self.prune_modes.sender_recovery.map(|prune_mode| {
                 PruneSenderRecoveryStage::new(prune_mode, self.stages_config.prune.commit_threshold)
@@ -433,6 +446,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexContractDeploymentsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -451,5 +465,10 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            // If the contract deployments index is enabled, add its stage. It has to run after
+            // the account history is indexed.
+            .add_stage_opt(self.stages_config.index_contract_deployments.enabled.then(|| {
+                IndexContractDeploymentsStage::new(self.stages_config.index_contract_deployments)
+            }))
     }
 }
@@ -1,7 +1,8 @@
-use super::missing_static_data_error;
+use super::{count_entries_in_range, missing_static_data_error};
 use futures_util::TryStreamExt;
 use reth_db_api::{
     cursor::DbCursorRO,
+    table::Table,
     tables,
     transaction::{DbTx, DbTxMut},
 };
@@ -12,7 +13,7 @@ use reth_provider::{
 };
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
-    UnwindInput, UnwindOutput,
+    UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderResult;
@@ -237,6 +238,50 @@ where
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx = provider.tx_ref();
+        let range = (input.unwind_to + 1)..;
+
+        // First transaction to be removed
+        let unwind_tx_from = provider
+            .block_body_indices(input.unwind_to)?
+            .map(|indices| indices.next_tx_num())
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(input.unwind_to))?;
+
+        let static_file_transactions = provider
+            .static_file_provider()
+            .get_highest_static_file_tx(StaticFileSegment::Transactions)
+            .map(|static_tx| (static_tx + 1).saturating_sub(unwind_tx_from))
+            .unwrap_or_default();
+
+        Ok(UnwindPlan::default()
+            .with_table(
+                tables::BlockBodyIndices::NAME,
+                Some(count_entries_in_range::<tables::BlockBodyIndices>(tx, range.clone())?),
+            )
+            .with_table(
+                <tables::BlockOmmers>::NAME,
+                Some(count_entries_in_range::<tables::BlockOmmers>(tx, range.clone())?),
+            )
+            .with_table(
+                tables::BlockWithdrawals::NAME,
+                Some(count_entries_in_range::<tables::BlockWithdrawals>(tx, range)?),
+            )
+            .with_table(
+                tables::TransactionBlocks::NAME,
+                Some(count_entries_in_range::<tables::TransactionBlocks>(tx, unwind_tx_from..)?),
+            )
+            .with_table(
+                <tables::Transactions>::NAME,
+                Some(count_entries_in_range::<tables::Transactions>(tx, unwind_tx_from..)?),
+            )
+            .with_static_file_segment(StaticFileSegment::Transactions, static_file_transactions))
+    }
 }
 
 // TODO(alexey): ideally, we want to measure Bodies stage progress in bytes, but it's hard to know
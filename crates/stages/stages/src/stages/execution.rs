@@ -1,3 +1,6 @@
+use super::{
+    changed_accounts_in_range, changed_storages_in_range, count_entries_in_range, unwind_tx_range,
+};
 use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
@@ -5,23 +8,26 @@ use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::{static_file::HeaderMask, tables};
+use reth_db_api::table::Table;
 use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
 use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
+    BlockHashReader, BlockReader, ChangeSetReader, DBProvider, ExecutionOutcome, HeaderProvider,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StateWriter, StaticFileProviderFactory, StatsReader,
+    StorageChangeSetReader, StorageLocation, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
-    UnwindInput, UnwindOutput,
+    UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_static_file_types::StaticFileSegment;
+use reth_trie::KeccakKeyHasher;
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
@@ -56,6 +62,12 @@ use super::missing_static_data_error;
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
 ///
+/// If hashed state writes are enabled and the hashing stages are caught up with this stage, the
+/// hashed state of the executed range is written to [`tables::HashedAccounts`] and
+/// [`tables::HashedStorages`] as well, and the checkpoints of [`super::AccountHashingStage`] and
+/// [`super::StorageHashingStage`] are advanced together with the checkpoint of this stage. This
+/// spares the hashing stages from re-reading the plain state of the range.
+///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
@@ -88,6 +100,9 @@ where
     post_unwind_commit_input: Option<Chain<E::Primitives>>,
     /// Handle to communicate with `ExEx` manager.
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
+    /// Whether to write the hashed state of the executed blocks, see
+    /// [`ExecutionStage::with_hashed_state_writes`].
+    write_hashed_state: bool,
     /// Executor metrics.
     metrics: ExecutorMetrics,
 }
@@ -112,10 +127,22 @@ where
             post_execute_commit_input: None,
             post_unwind_commit_input: None,
             exex_manager_handle,
+            write_hashed_state: false,
             metrics: ExecutorMetrics::default(),
         }
     }
 
+    /// Configures whether the hashed state of the executed blocks is written to the hashed state
+    /// tables right away.
+    ///
+    /// The hashed state is only written if [`super::AccountHashingStage`] and
+    /// [`super::StorageHashingStage`] are at the same block as this stage, in which case their
+    /// checkpoints are advanced with the checkpoint of this stage.
+    pub const fn with_hashed_state_writes(mut self, write_hashed_state: bool) -> Self {
+        self.write_hashed_state = write_hashed_state;
+        self
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD`].
@@ -146,6 +173,29 @@ where
             external_clean_threshold,
             ExExManagerHandle::empty(),
         )
+        .with_hashed_state_writes(config.write_hashed_state)
+    }
+
+    /// Returns whether the hashed state of the blocks after `checkpoint` should be written by this
+    /// stage, which is the case if hashed state writes are enabled and both hashing stages are at
+    /// `checkpoint`.
+    fn should_write_hashed_state(
+        &self,
+        provider: &impl StageCheckpointReader,
+        checkpoint: BlockNumber,
+    ) -> Result<bool, StageError> {
+        if !self.write_hashed_state {
+            return Ok(false)
+        }
+
+        for stage_id in [StageId::AccountHashing, StageId::StorageHashing] {
+            let hashing_checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            if hashing_checkpoint.block_number != checkpoint {
+                return Ok(false)
+            }
+        }
+
+        Ok(true)
     }
 
     /// Returns whether we can perform pruning of [`tables::AccountChangeSets`] and
@@ -260,6 +310,10 @@ where
             Primitives: NodePrimitives<BlockHeader: reth_db_api::table::Value>,
         > + StatsReader
         + BlockHashReader
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + ChangeSetReader
+        + StorageChangeSetReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>,
 {
     /// Return the id of the stage
@@ -379,11 +433,15 @@ where
             }
 
             // Check if we should commit now
+            let state_size = executor.state_size_hint();
             if self.thresholds.is_end_of_batch(
                 block_number - start_block,
                 executor.size_hint() as u64,
                 cumulative_gas,
                 batch_start.elapsed(),
+            ) || self.thresholds.is_resource_limit_reached(
+                state_size.changeset_entries as u64,
+                state_size.state_changes as u64,
             ) {
                 break
             }
@@ -454,6 +512,16 @@ where
         // write output
         provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
 
+        if self.should_write_hashed_state(provider, input.checkpoint().block_number)? {
+            // The hashing stages are caught up with this stage, so hash the changed state right
+            // away instead of letting them re-read the plain state of the executed range.
+            provider
+                .write_hashed_state(&state.hash_state_slow::<KeccakKeyHasher>().into_sorted())?;
+            for stage_id in [StageId::AccountHashing, StageId::StorageHashing] {
+                provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(stage_progress))?;
+            }
+        }
+
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -546,6 +614,37 @@ where
         Ok(UnwindOutput { checkpoint })
     }
 
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx = provider.tx_ref();
+        let range = input.unwind_block_range();
+        if range.is_empty() {
+            return Ok(UnwindPlan::default())
+        }
+
+        let (account_changesets, accounts) = changed_accounts_in_range(provider, range.clone())?;
+        let (storage_changesets, slots) = changed_storages_in_range(provider, range)?;
+
+        let tx_range = unwind_tx_range(provider, input.unwind_to, input.checkpoint.block_number)?;
+        let receipts = count_entries_in_range::<tables::Receipts>(tx, tx_range.clone())?;
+        let static_file_receipts = provider
+            .static_file_provider()
+            .get_highest_static_file_tx(StaticFileSegment::Receipts)
+            .map(|static_tx| (static_tx + 1).saturating_sub(tx_range.start))
+            .unwrap_or_default();
+
+        Ok(UnwindPlan::default()
+            .with_table(tables::PlainAccountState::NAME, Some(accounts.len() as u64))
+            .with_table(tables::PlainStorageState::NAME, Some(slots.len() as u64))
+            .with_table(tables::AccountChangeSets::NAME, Some(account_changesets))
+            .with_table(tables::StorageChangeSets::NAME, Some(storage_changesets))
+            .with_table(<tables::Receipts>::NAME, Some(receipts))
+            .with_static_file_segment(StaticFileSegment::Receipts, static_file_receipts))
+    }
+
     fn post_unwind_commit(&mut self) -> Result<(), StageError> {
         let Some(chain) = self.post_unwind_commit_input.take() else { return Ok(()) };
 
@@ -696,6 +795,8 @@ mod tests {
             ExecutionStageThresholds {
                 max_blocks: Some(100),
                 max_changes: None,
+                max_changeset_bytes: None,
+                max_trie_updates: None,
                 max_cumulative_gas: None,
                 max_duration: None,
             },
@@ -1094,6 +1195,85 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn execution_writes_hashed_state() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap();
+        provider.insert_historical_block(genesis.try_recover().unwrap()).unwrap();
+        provider.insert_historical_block(block.clone().try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let static_file_provider = provider.static_file_provider();
+            let mut receipts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+        provider.commit().unwrap();
+
+        // variables
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        // pre state
+        let provider = factory.provider_rw().unwrap();
+
+        let db_tx = provider.tx_ref();
+        let acc1 = address!("0x1000000000000000000000000000000000000000");
+        let acc1_info = Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) };
+        let acc2 = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let acc2_info = Account { nonce: 0, balance, bytecode_hash: None };
+
+        db_tx.put::<tables::PlainAccountState>(acc1, acc1_info).unwrap();
+        db_tx.put::<tables::PlainAccountState>(acc2, acc2_info).unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        // Hashing stages lag behind, so the hashed state is left to them
+        let provider = factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::AccountHashing, StageCheckpoint::new(1)).unwrap();
+        let mut execution_stage = stage().with_hashed_state_writes(true);
+        execution_stage.execute(&provider, input).unwrap();
+        assert!(provider.tx_ref().entries::<tables::HashedAccounts>().unwrap().is_zero());
+        assert_eq!(provider.get_stage_checkpoint(StageId::StorageHashing).unwrap(), None);
+        drop(provider);
+
+        // Hashing stages are caught up, so the hashed state is written with the plain state
+        let provider = factory.database_provider_rw().unwrap();
+        let mut execution_stage = stage().with_hashed_state_writes(true);
+        execution_stage.execute(&provider, input).unwrap();
+
+        let acc2_info = Account {
+            balance: U256::from(0x3635c9adc5de996b46u128),
+            nonce: 0x01,
+            bytecode_hash: None,
+        };
+        assert_eq!(
+            provider.tx_ref().get::<tables::HashedAccounts>(keccak256(acc2)).unwrap(),
+            Some(acc2_info)
+        );
+        assert_eq!(
+            provider.tx_ref().get::<tables::HashedStorages>(keccak256(acc1)).unwrap(),
+            Some(StorageEntry { key: keccak256(B256::with_last_byte(1)), value: U256::from(2) })
+        );
+        for stage_id in [StageId::AccountHashing, StageId::StorageHashing] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(1))
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_selfdestruct() {
         let test_db = TestStageDB::default();
@@ -1,18 +1,20 @@
+use super::changed_accounts_in_range;
 use alloy_primitives::{keccak256, B256};
 use itertools::Itertools;
 use reth_config::config::{EtlConfig, HashingConfig};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
+    table::Table,
     tables,
     transaction::{DbTx, DbTxMut},
     RawKey, RawTable, RawValue,
 };
 use reth_etl::Collector;
 use reth_primitives_traits::Account;
-use reth_provider::{AccountExtReader, DBProvider, HashingWriter, StatsReader};
+use reth_provider::{AccountExtReader, ChangeSetReader, DBProvider, HashingWriter, StatsReader};
 use reth_stages_api::{
     AccountHashingCheckpoint, EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint,
-    StageError, StageId, UnwindInput, UnwindOutput,
+    StageError, StageId, UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_storage_errors::provider::ProviderResult;
 use std::{
@@ -30,6 +32,10 @@ const WORKER_CHUNK_SIZE: usize = 100;
 
 /// Account hashing stage hashes plain account.
 /// This is preparation before generating intermediate hashes and calculating Merkle tree root.
+///
+/// If the [`ExecutionStage`](super::ExecutionStage) writes hashed state, it hashes the account
+/// of the blocks it executes itself and advances the checkpoint of this stage, which then only
+/// has to catch up on ranges that were executed while it was lagging behind.
 #[derive(Clone, Debug)]
 pub struct AccountHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
@@ -134,7 +140,8 @@ impl Default for AccountHashingStage {
 
 impl<Provider> Stage<Provider> for AccountHashingStage
 where
-    Provider: DBProvider<Tx: DbTxMut> + HashingWriter + AccountExtReader + StatsReader,
+    Provider:
+        DBProvider<Tx: DbTxMut> + HashingWriter + AccountExtReader + ChangeSetReader + StatsReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -250,6 +257,17 @@ where
                 .with_account_hashing_stage_checkpoint(stage_checkpoint),
         })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let (_, accounts) = changed_accounts_in_range(provider, input.unwind_block_range())?;
+
+        Ok(UnwindPlan::default()
+            .with_table(tables::HashedAccounts::NAME, Some(accounts.len() as u64)))
+    }
 }
 
 /// Flushes channels hashes to ETL collector.
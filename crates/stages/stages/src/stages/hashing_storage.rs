@@ -1,19 +1,22 @@
+use super::changed_storages_in_range;
 use alloy_primitives::{bytes::BufMut, keccak256, B256};
 use itertools::Itertools;
 use reth_config::config::{EtlConfig, HashingConfig};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRW},
     models::{BlockNumberAddress, CompactU256},
-    table::Decompress,
+    table::{Decompress, Table},
     tables,
     transaction::{DbTx, DbTxMut},
 };
 use reth_etl::Collector;
 use reth_primitives_traits::StorageEntry;
-use reth_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
+use reth_provider::{
+    DBProvider, HashingWriter, StatsReader, StorageChangeSetReader, StorageReader,
+};
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
-    StorageHashingCheckpoint, UnwindInput, UnwindOutput,
+    StorageHashingCheckpoint, UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_storage_errors::provider::ProviderResult;
 use std::{
@@ -30,6 +33,10 @@ const WORKER_CHUNK_SIZE: usize = 100;
 
 /// Storage hashing stage hashes plain storage.
 /// This is preparation before generating intermediate hashes and calculating Merkle tree root.
+///
+/// If the [`ExecutionStage`](super::ExecutionStage) writes hashed state, it hashes the storage
+/// of the blocks it executes itself and advances the checkpoint of this stage, which then only
+/// has to catch up on ranges that were executed while it was lagging behind.
 #[derive(Debug)]
 pub struct StorageHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
@@ -64,7 +71,11 @@ impl Default for StorageHashingStage {
 
 impl<Provider> Stage<Provider> for StorageHashingStage
 where
-    Provider: DBProvider<Tx: DbTxMut> + StorageReader + HashingWriter + StatsReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + StorageReader
+        + StorageChangeSetReader
+        + HashingWriter
+        + StatsReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -181,6 +192,16 @@ where
                 .with_storage_hashing_stage_checkpoint(stage_checkpoint),
         })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let (_, slots) = changed_storages_in_range(provider, input.unwind_block_range())?;
+
+        Ok(UnwindPlan::default().with_table(tables::HashedStorages::NAME, Some(slots.len() as u64)))
+    }
 }
 
 /// Flushes channels hashes to ETL collector.
@@ -1,10 +1,11 @@
+use super::count_entries_in_range;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{BlockHash, BlockNumber, Bytes, B256};
 use futures_util::StreamExt;
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    table::Value,
+    table::{Table, Value},
     tables,
     transaction::{DbTx, DbTxMut},
     DbTxUnwindExt, RawKey, RawTable, RawValue,
@@ -21,7 +22,7 @@ use reth_provider::{
 };
 use reth_stages_api::{
     CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput, HeadersCheckpoint, Stage,
-    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderError;
@@ -392,6 +393,41 @@ where
 
         Ok(UnwindOutput { checkpoint })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx = provider.tx_ref();
+        let range = (input.unwind_to + 1)..;
+
+        let canonical_headers =
+            count_entries_in_range::<tables::CanonicalHeaders>(tx, range.clone())?;
+        let headers = count_entries_in_range::<tables::Headers>(tx, range.clone())?;
+        let terminal_difficulties =
+            count_entries_in_range::<tables::HeaderTerminalDifficulties>(tx, range)?;
+
+        let highest_static_file_block = provider
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+        let static_file_headers = highest_static_file_block.saturating_sub(input.unwind_to);
+
+        // `HeaderNumbers` contains the hashes of headers both in the database and in static files
+        let highest_block = tx
+            .cursor_read::<tables::CanonicalHeaders>()?
+            .last()?
+            .map_or(highest_static_file_block, |(number, _)| number.max(highest_static_file_block));
+        let header_numbers = highest_block.saturating_sub(input.unwind_to);
+
+        Ok(UnwindPlan::default()
+            .with_table(tables::CanonicalHeaders::NAME, Some(canonical_headers))
+            .with_table(tables::HeaderNumbers::NAME, Some(header_numbers))
+            .with_table(tables::HeaderTerminalDifficulties::NAME, Some(terminal_difficulties))
+            .with_table(<tables::Headers>::NAME, Some(headers))
+            .with_static_file_segment(StaticFileSegment::Headers, static_file_headers))
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,22 @@
-use super::{collect_history_indices, load_history_indices};
+use super::{
+    changed_accounts_in_range, collect_account_history_indices, count_entries_in_range,
+    load_history_indices,
+};
 use alloy_primitives::Address;
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
-use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_db_api::{
+    models::ShardedKey,
+    table::{Decode, Table},
+    tables,
+    transaction::DbTxMut,
+};
+use reth_provider::{
+    ChangeSetReader, DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
     ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    UnwindPlan,
 };
 use std::fmt::Debug;
 use tracing::info;
@@ -43,8 +54,11 @@ impl Default for IndexAccountHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexAccountHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + HistoryWriter + PruneCheckpointReader + PruneCheckpointWriter,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -102,14 +116,7 @@ where
         }
 
         info!(target: "sync::stages::index_account_history::exec", ?first_sync, "Collecting indices");
-        let collector =
-            collect_history_indices::<_, tables::AccountChangeSets, tables::AccountsHistory, _>(
-                provider,
-                range.clone(),
-                ShardedKey::new,
-                |(index, value)| (index, value.address),
-                &self.etl_config,
-            )?;
+        let collector = collect_account_history_indices(provider, range.clone(), &self.etl_config)?;
 
         info!(target: "sync::stages::index_account_history::exec", "Loading indices into database");
         load_history_indices::<_, tables::AccountsHistory, _>(
@@ -138,6 +145,26 @@ where
         // from HistoryIndex higher than that number.
         Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let range = input.unwind_block_range();
+        let (_, accounts) = changed_accounts_in_range(provider, range.clone())?;
+
+        // All shards that contain blocks of the unwound range are rewritten
+        let mut shards = 0;
+        for address in accounts {
+            shards += count_entries_in_range::<tables::AccountsHistory>(
+                provider.tx_ref(),
+                ShardedKey::new(address, *range.start())..=ShardedKey::last(address),
+            )?;
+        }
+
+        Ok(UnwindPlan::default().with_table(tables::AccountsHistory::NAME, Some(shards)))
+    }
 }
 
 #[cfg(test)]
@@ -153,12 +180,15 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, sharded_key::NUM_OF_INDICES_IN_SHARD, AccountBeforeTx,
-            StoredBlockBodyIndices,
+            StaticFileAccountChangeSet, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
     };
-    use reth_provider::{providers::StaticFileWriter, DatabaseProviderFactory};
+    use reth_provider::{
+        providers::StaticFileWriter, DatabaseProviderFactory, StaticFileProviderFactory,
+    };
+    use reth_static_file_types::StaticFileSegment;
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_contract_account_range,
         BlockRangeParams,
@@ -260,6 +290,76 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_over_migrated_range() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup: the changesets of blocks 0..=2 were moved to static files
+        {
+            let static_file_provider = db.factory.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=2 {
+                writer
+                    .append_account_change_set(
+                        block,
+                        &StaticFileAccountChangeSet { changes: vec![acc()] },
+                    )
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        db.commit(|tx| {
+            for block in 3..=5 {
+                tx.put::<tables::AccountChangeSets>(block, acc())?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify: rebuilding from genesis indexes the migrated blocks as well
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+    }
+
+    #[tokio::test]
+    async fn plan_unwind_counts_affected_shards() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+        run(&db, MAX_BLOCK, None);
+        assert_eq!(db.table::<tables::AccountsHistory>().unwrap().len(), 2);
+
+        let plan = |unwind_to| {
+            let input = UnwindInput {
+                checkpoint: StageCheckpoint::new(MAX_BLOCK),
+                unwind_to,
+                ..Default::default()
+            };
+            let provider = db.factory.database_provider_rw().unwrap();
+            IndexAccountHistoryStage::default().plan_unwind(&provider, input).unwrap()
+        };
+
+        // only the last shard contains the unwound block
+        assert_eq!(
+            plan(MAX_BLOCK - 1),
+            UnwindPlan::default().with_table(tables::AccountsHistory::NAME, Some(1))
+        );
+        // both shards contain unwound blocks
+        assert_eq!(
+            plan(0),
+            UnwindPlan::default().with_table(tables::AccountsHistory::NAME, Some(2))
+        );
+        // planning doesn't modify the table
+        assert_eq!(db.table::<tables::AccountsHistory>().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
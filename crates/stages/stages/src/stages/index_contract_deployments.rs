@@ -0,0 +1,170 @@
+use reth_config::config::IndexContractDeploymentsConfig;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::{DBProvider, HistoryWriter};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::info;
+
+/// Stage indexing the contracts deployed with each code hash, using the account changesets and
+/// the account history built by
+/// [`IndexAccountHistoryStage`][crate::stages::IndexAccountHistoryStage].
+/// See [`tables::ContractDeployments`] for the layout of the index.
+///
+/// Contracts of the genesis allocation are not indexed, since they have no changesets.
+///
+/// The index is opt-in, see [`IndexContractDeploymentsConfig::enabled`]. Once the stage has run,
+/// the index is also kept up to date for blocks that are persisted outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexContractDeploymentsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexContractDeploymentsStage {
+    /// Create new instance of [`IndexContractDeploymentsStage`].
+    pub const fn new(config: IndexContractDeploymentsConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+}
+
+impl Default for IndexContractDeploymentsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexContractDeploymentsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + HistoryWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexContractDeployments
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table, there might be leftovers of a dropped index.
+        if first_sync {
+            provider.tx_ref().clear::<tables::ContractDeployments>()?;
+        }
+
+        info!(target: "sync::stages::index_contract_deployments::exec", ?first_sync, ?range, "Indexing contract deployments");
+        let deployments = provider.insert_contract_deployments_index(range.clone())?;
+        info!(target: "sync::stages::index_contract_deployments::exec", deployments, "Indexed contract deployments");
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_contract_deployments_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, b256, Address, B256, U256};
+    use reth_db_api::{
+        models::{AccountBeforeTx, ContractDeployment, ShardedKey},
+        BlockNumberList,
+    };
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        ContractDeploymentsProvider, DatabaseProviderFactory, StageCheckpointWriter,
+    };
+
+    const CONTRACT: Address = address!("0x0000000000000000000000000000000000000001");
+    const EOA: Address = address!("0x0000000000000000000000000000000000000002");
+    const CODE_HASH: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+
+    /// Sets up blocks `1..=4`. The contract is deployed in block 3, the EOA is created in block 1
+    /// and changes in every block after.
+    fn setup(db: &TestStageDB) {
+        db.commit(|tx| {
+            let eoa = |nonce| Account { nonce, balance: U256::from(1), bytecode_hash: None };
+            tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: EOA, info: None })?;
+            for block in 2..=4u64 {
+                tx.put::<tables::AccountChangeSets>(
+                    block,
+                    AccountBeforeTx { address: EOA, info: Some(eoa(block - 2)) },
+                )?;
+            }
+            tx.put::<tables::AccountChangeSets>(
+                3,
+                AccountBeforeTx { address: CONTRACT, info: None },
+            )?;
+            tx.put::<tables::PlainAccountState>(EOA, eoa(3))?;
+            tx.put::<tables::PlainAccountState>(
+                CONTRACT,
+                Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(CODE_HASH) },
+            )?;
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::last(EOA),
+                BlockNumberList::new([1, 2, 3, 4]).unwrap(),
+            )?;
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::last(CONTRACT),
+                BlockNumberList::new([3]).unwrap(),
+            )?;
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        let mut stage = IndexContractDeploymentsStage { commit_threshold: 2 };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(4), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(2), done: false });
+
+        let input = ExecInput { target: Some(4), checkpoint: Some(out.checkpoint) };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(4), done: true });
+        provider.save_stage_checkpoint(StageId::IndexContractDeployments, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        let provider = db.factory.database_provider_ro().unwrap();
+        assert_eq!(
+            provider.contract_deployment(CONTRACT, CODE_HASH).unwrap(),
+            Some(ContractDeployment { address: CONTRACT, block_number: 3 })
+        );
+        assert_eq!(db.table::<tables::ContractDeployments>().unwrap().len(), 1);
+        drop(provider);
+
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(4), unwind_to: 2, ..Default::default() };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(2) });
+        provider.commit().unwrap();
+
+        assert!(db.table::<tables::ContractDeployments>().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+use super::{
+    collect_history_indices, count_entries_in_range, load_history_indices, unwind_tx_range,
+};
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, TxNumber};
+use reth_config::config::{EtlConfig, IndexSenderTransactionsConfig};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::ShardedKey,
+    table::{Decode, Table},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{BlockReader, DBProvider, HistoryWriter, ProviderError};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    UnwindPlan,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+use tracing::info;
+
+/// Number of transactions that are read at once to collect their recipients.
+const RECIPIENTS_BATCH_SIZE: u64 = 100_000;
+
+/// Stage indexing the transactions sent by each address, using the senders recovered in
+/// [`SenderRecoveryStage`][crate::stages::SenderRecoveryStage], and the transactions sent to each
+/// address. For more information on index sharding take a look at [`tables::SenderTransactions`].
+///
+/// The index is opt-in, see [`IndexSenderTransactionsConfig::enabled`]. Once the stage has run,
+/// the index is also kept up to date for blocks that are persisted outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexSenderTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexSenderTransactionsStage {
+    /// Create new instance of [`IndexSenderTransactionsStage`].
+    pub const fn new(config: IndexSenderTransactionsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexSenderTransactionsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexSenderTransactionsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader + HistoryWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexSenderTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table since it's faster to rebuild it from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::SenderTransactions>()?;
+            provider.tx_ref().clear::<tables::RecipientTransactions>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        let body_indices = |block| {
+            provider
+                .block_body_indices(block)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block))
+        };
+        let tx_range =
+            body_indices(*range.start())?.first_tx_num()..body_indices(*range.end())?.next_tx_num();
+
+        info!(target: "sync::stages::index_sender_transactions::exec", ?first_sync, ?tx_range, "Collecting indices");
+        let collector = collect_history_indices::<
+            _,
+            tables::TransactionSenders,
+            tables::SenderTransactions,
+            _,
+        >(
+            provider,
+            tx_range.clone(),
+            ShardedKey::new,
+            |(tx_number, sender)| (tx_number, sender),
+            &self.etl_config,
+        )?;
+
+        info!(target: "sync::stages::index_sender_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::SenderTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        info!(target: "sync::stages::index_sender_transactions::exec", "Collecting recipient indices");
+        let collector = collect_recipient_indices(provider, tx_range, &self.etl_config)?;
+
+        info!(target: "sync::stages::index_sender_transactions::exec", "Loading recipient indices into database");
+        load_history_indices::<_, tables::RecipientTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let tx_range = unwind_tx_range(provider, unwind_progress, *range.end())?;
+        provider.unwind_sender_transactions_index_range(tx_range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx_range = unwind_tx_range(provider, input.unwind_to, input.checkpoint.block_number)?;
+
+        // The first unwound transaction of each sender and recipient
+        let mut first_sent = BTreeMap::<Address, TxNumber>::new();
+        for entry in provider
+            .tx_ref()
+            .cursor_read::<tables::TransactionSenders>()?
+            .walk_range(tx_range.clone())?
+        {
+            let (tx_number, sender) = entry?;
+            first_sent.entry(sender).or_insert(tx_number);
+        }
+        let mut first_received = BTreeMap::<Address, TxNumber>::new();
+        for (tx_number, transaction) in
+            tx_range.clone().zip(provider.transactions_by_tx_range(tx_range)?)
+        {
+            if let Some(recipient) = transaction.to() {
+                first_received.entry(recipient).or_insert(tx_number);
+            }
+        }
+
+        Ok(UnwindPlan::default()
+            .with_table(
+                tables::SenderTransactions::NAME,
+                Some(count_unwound_shards::<_, tables::SenderTransactions>(provider, first_sent)?),
+            )
+            .with_table(
+                tables::RecipientTransactions::NAME,
+                Some(count_unwound_shards::<_, tables::RecipientTransactions>(
+                    provider,
+                    first_received,
+                )?),
+            ))
+    }
+}
+
+/// Collects the transactions sent to each address in the given range into a [`Collector`], the
+/// same way [`collect_history_indices`] collects the senders.
+fn collect_recipient_indices<Provider>(
+    provider: &Provider,
+    tx_range: Range<TxNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<ShardedKey<Address>, BlockNumberList>, StageError>
+where
+    Provider: BlockReader,
+{
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut cache = HashMap::<Address, Vec<TxNumber>>::new();
+
+    let mut start = tx_range.start;
+    while start < tx_range.end {
+        let end = tx_range.end.min(start.saturating_add(RECIPIENTS_BATCH_SIZE));
+        for (tx_number, transaction) in
+            (start..end).zip(provider.transactions_by_tx_range(start..end)?)
+        {
+            if let Some(recipient) = transaction.to() {
+                cache.entry(recipient).or_default().push(tx_number);
+            }
+        }
+
+        for (recipient, indices) in cache.drain() {
+            let last = *indices.last().expect("qed");
+            collector.insert(
+                ShardedKey::new(recipient, last),
+                BlockNumberList::new_pre_sorted(indices),
+            )?;
+        }
+        start = end;
+    }
+
+    Ok(collector)
+}
+
+/// Returns the number of shards of an index of transactions by address that contain transactions
+/// of the unwound range, all of which are rewritten.
+fn count_unwound_shards<Provider, T>(
+    provider: &Provider,
+    first_transactions: BTreeMap<Address, TxNumber>,
+) -> Result<u64, StageError>
+where
+    Provider: DBProvider,
+    T: Table<Key = ShardedKey<Address>>,
+{
+    let mut shards = 0;
+    for (address, tx_number) in first_transactions {
+        shards += count_entries_in_range::<T>(
+            provider.tx_ref(),
+            ShardedKey::new(address, tx_number)..=ShardedKey::last(address),
+        )?;
+    }
+    Ok(shards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{address, Signature, TxKind};
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_provider::{
+        DatabaseProviderFactory, SenderTransactionsProvider, StageCheckpointWriter,
+    };
+
+    const ALICE: Address = address!("0x0000000000000000000000000000000000000001");
+    const BOB: Address = address!("0x0000000000000000000000000000000000000002");
+
+    /// Sets up blocks `0..=4` with three transactions each. Alice sends the first two transactions
+    /// of every block, a transfer to Bob and a contract creation, and Bob sends the last one to
+    /// Alice.
+    fn setup(db: &TestStageDB) {
+        let transaction = |to| {
+            TxLegacy { to, ..Default::default() }.into_signed(Signature::test_signature()).into()
+        };
+        db.commit(|tx| {
+            for block in 0..=4u64 {
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block * 3, tx_count: 3 },
+                )?;
+                tx.put::<tables::TransactionSenders>(block * 3, ALICE)?;
+                tx.put::<tables::TransactionSenders>(block * 3 + 1, ALICE)?;
+                tx.put::<tables::TransactionSenders>(block * 3 + 2, BOB)?;
+                tx.put::<tables::Transactions>(block * 3, transaction(TxKind::Call(BOB)))?;
+                tx.put::<tables::Transactions>(block * 3 + 1, transaction(TxKind::Create))?;
+                tx.put::<tables::Transactions>(block * 3 + 2, transaction(TxKind::Call(ALICE)))?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    fn index<T>(db: &TestStageDB) -> BTreeMap<Address, Vec<TxNumber>>
+    where
+        T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    {
+        db.table::<T>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| (key.key, list.iter().collect()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        let mut stage = IndexSenderTransactionsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(4), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(4), done: true });
+        provider.save_stage_checkpoint(StageId::IndexSenderTransactions, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(
+            index::<tables::SenderTransactions>(&db),
+            BTreeMap::from([
+                (ALICE, vec![0, 1, 3, 4, 6, 7, 9, 10, 12, 13]),
+                (BOB, vec![2, 5, 8, 11, 14])
+            ])
+        );
+        assert_eq!(
+            index::<tables::RecipientTransactions>(&db),
+            BTreeMap::from([(ALICE, vec![2, 5, 8, 11, 14]), (BOB, vec![0, 3, 6, 9, 12])])
+        );
+
+        let provider = db.factory.database_provider_ro().unwrap();
+        assert_eq!(provider.sender_transaction_ids(ALICE, 3, 4).unwrap(), Some(vec![4, 6, 7, 9]));
+        assert_eq!(provider.sender_transaction_ids(BOB, 4, 10).unwrap(), Some(vec![14]));
+        assert_eq!(provider.sender_transaction_ids(BOB, 5, 10).unwrap(), Some(vec![]));
+        assert_eq!(
+            provider.address_transaction_ids(BOB, 4..12, 4, false).unwrap(),
+            Some(vec![5, 6, 8, 9])
+        );
+        assert_eq!(
+            provider.address_transaction_ids(BOB, 4..12, 4, true).unwrap(),
+            Some(vec![11, 9, 8, 6])
+        );
+        assert_eq!(
+            provider.address_transaction_ids(ALICE, 0..15, 100, true).unwrap().map(|ids| ids.len()),
+            Some(15)
+        );
+        drop(provider);
+
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(4), unwind_to: 2, ..Default::default() };
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(
+            stage.plan_unwind(&provider, input).unwrap(),
+            UnwindPlan::default()
+                .with_table(tables::SenderTransactions::NAME, Some(2))
+                .with_table(tables::RecipientTransactions::NAME, Some(2))
+        );
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(2) });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            index::<tables::SenderTransactions>(&db),
+            BTreeMap::from([(ALICE, vec![0, 1, 3, 4, 6, 7]), (BOB, vec![2, 5, 8])])
+        );
+        assert_eq!(
+            index::<tables::RecipientTransactions>(&db),
+            BTreeMap::from([(ALICE, vec![2, 5, 8]), (BOB, vec![0, 3, 6])])
+        );
+    }
+
+    #[tokio::test]
+    async fn index_disabled() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        let provider = db.factory.database_provider_ro().unwrap();
+        assert_eq!(provider.sender_transaction_ids(ALICE, 0, 10).unwrap(), None);
+        assert_eq!(provider.address_transaction_ids(ALICE, 0..15, 10, false).unwrap(), None);
+    }
+}
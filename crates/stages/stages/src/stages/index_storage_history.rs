@@ -1,15 +1,22 @@
-use super::{collect_history_indices, load_history_indices};
+use super::{
+    changed_storages_in_range, collect_storage_history_indices, count_entries_in_range,
+    load_history_indices,
+};
 use crate::{StageCheckpoint, StageId};
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
 use reth_db_api::{
     models::{storage_sharded_key::StorageShardedKey, AddressStorageKey, BlockNumberAddress},
-    table::Decode,
+    table::{Decode, Table},
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter, StorageChangeSetReader,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
-use reth_stages_api::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput, UnwindPlan,
+};
 use std::fmt::Debug;
 use tracing::info;
 
@@ -46,8 +53,11 @@ impl Default for IndexStorageHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexStorageHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + PruneCheckpointWriter + HistoryWriter + PruneCheckpointReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + PruneCheckpointWriter
+        + HistoryWriter
+        + PruneCheckpointReader
+        + StorageChangeSetReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -105,16 +115,7 @@ where
         }
 
         info!(target: "sync::stages::index_storage_history::exec", ?first_sync, "Collecting indices");
-        let collector =
-            collect_history_indices::<_, tables::StorageChangeSets, tables::StoragesHistory, _>(
-                provider,
-                BlockNumberAddress::range(range.clone()),
-                |AddressStorageKey((address, storage_key)), highest_block_number| {
-                    StorageShardedKey::new(address, storage_key, highest_block_number)
-                },
-                |(key, value)| (key.block_number(), AddressStorageKey((key.address(), value.key))),
-                &self.etl_config,
-            )?;
+        let collector = collect_storage_history_indices(provider, range.clone(), &self.etl_config)?;
 
         info!(target: "sync::stages::index_storage_history::exec", "Loading indices into database");
         load_history_indices::<_, tables::StoragesHistory, _>(
@@ -144,6 +145,27 @@ where
 
         Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let range = input.unwind_block_range();
+        let (_, slots) = changed_storages_in_range(provider, range.clone())?;
+
+        // All shards that contain blocks of the unwound range are rewritten
+        let mut shards = 0;
+        for (address, storage_key) in slots {
+            shards += count_entries_in_range::<tables::StoragesHistory>(
+                provider.tx_ref(),
+                StorageShardedKey::new(address, storage_key, *range.start())..=
+                    StorageShardedKey::last(address, storage_key),
+            )?;
+        }
+
+        Ok(UnwindPlan::default().with_table(tables::StoragesHistory::NAME, Some(shards)))
+    }
 }
 
 #[cfg(test)]
@@ -159,13 +181,16 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, storage_sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey,
-            StoredBlockBodyIndices,
+            StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
     };
     use reth_primitives_traits::StorageEntry;
-    use reth_provider::{providers::StaticFileWriter, DatabaseProviderFactory};
+    use reth_provider::{
+        providers::StaticFileWriter, DatabaseProviderFactory, StaticFileProviderFactory,
+    };
+    use reth_static_file_types::StaticFileSegment;
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_contract_account_range,
         BlockRangeParams,
@@ -280,6 +305,44 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_over_migrated_range() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup: the changesets of blocks 0..=2 were moved to static files
+        {
+            let static_file_provider = db.factory.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=2 {
+                let StorageEntry { key, value } = storage(STORAGE_KEY);
+                let changes = vec![StorageBeforeTx { address: ADDRESS, key, value }];
+                writer
+                    .append_storage_change_set(block, &StaticFileStorageChangeSet { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        db.commit(|tx| {
+            for block in 3..=5 {
+                tx.put::<tables::StorageChangeSets>(
+                    block_number_address(block),
+                    storage(STORAGE_KEY),
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify: rebuilding from genesis indexes the migrated blocks as well
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
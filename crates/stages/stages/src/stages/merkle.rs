@@ -3,19 +3,25 @@ use alloy_primitives::{BlockNumber, Sealable, B256};
 use reth_codecs::Compact;
 use reth_consensus::ConsensusError;
 use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    table::Table,
     tables,
     transaction::{DbTx, DbTxMut},
 };
 use reth_primitives_traits::{GotExpected, SealedHeader};
 use reth_provider::{
-    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
-    StatsReader, TrieWriter,
+    ChangeSetReader, DBProvider, HeaderProvider, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StatsReader, StorageChangeSetReader, TrieWriter,
 };
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, StorageRootMerkleCheckpoint, UnwindInput, UnwindOutput,
+    UnwindPlan,
+};
+use reth_trie::{
+    BranchNodeCompact, IntermediateStateRootState, Nibbles, StateRoot, StateRootProgress,
+    StoredNibbles, StoredSubNode,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
 use reth_trie_db::DatabaseStateRoot;
 use std::fmt::Debug;
 use tracing::*;
@@ -158,6 +164,8 @@ where
         + TrieWriter
         + StatsReader
         + HeaderProvider
+        + ChangeSetReader
+        + StorageChangeSetReader
         + StageCheckpointReader
         + StageCheckpointWriter,
 {
@@ -201,7 +209,7 @@ where
             (target_block_root, input.checkpoint().entities_stage_checkpoint().unwrap_or_default())
         } else if to_block - from_block > threshold || from_block == 1 {
             // if there are more blocks than threshold it is faster to rebuild the trie
-            let mut entities_checkpoint = if let Some(checkpoint) =
+            let (entities_checkpoint, trie_block) = if let Some(checkpoint) =
                 checkpoint.as_ref().filter(|c| c.target_block == to_block)
             {
                 debug!(
@@ -209,35 +217,59 @@ where
                     current = ?current_block_number,
                     target = ?to_block,
                     last_account_key = ?checkpoint.last_account_key,
+                    trie_block = ?checkpoint.trie_block,
                     "Continuing inner merkle checkpoint"
                 );
 
-                input.checkpoint().entities_stage_checkpoint()
+                (input.checkpoint().entities_stage_checkpoint(), checkpoint.trie_block)
             } else {
-                debug!(
-                    target: "sync::stages::merkle::exec",
-                    current = ?current_block_number,
-                    target = ?to_block,
-                    previous_checkpoint = ?checkpoint,
-                    "Rebuilding trie"
-                );
-                // Reset the checkpoint and clear trie tables
+                let trie_block = match checkpoint.as_ref() {
+                    Some(previous) => reuse_trie_nodes(provider, previous, to_block)?,
+                    None => None,
+                };
+                if let Some(trie_block) = trie_block {
+                    debug!(
+                        target: "sync::stages::merkle::exec",
+                        current = ?current_block_number,
+                        target = ?to_block,
+                        previous_checkpoint = ?checkpoint,
+                        trie_block,
+                        "Reusing trie nodes of interrupted merkle checkpoint"
+                    );
+                } else {
+                    debug!(
+                        target: "sync::stages::merkle::exec",
+                        current = ?current_block_number,
+                        target = ?to_block,
+                        previous_checkpoint = ?checkpoint,
+                        "Rebuilding trie"
+                    );
+                    // Clear trie tables
+                    provider.tx_ref().clear::<tables::AccountsTrie>()?;
+                    provider.tx_ref().clear::<tables::StoragesTrie>()?;
+                }
+                // Reset the checkpoint
                 checkpoint = None;
                 self.save_execution_checkpoint(provider, None)?;
-                provider.tx_ref().clear::<tables::AccountsTrie>()?;
-                provider.tx_ref().clear::<tables::StoragesTrie>()?;
 
-                None
-            }
-            .unwrap_or(EntitiesCheckpoint {
+                (None, trie_block)
+            };
+            let mut entities_checkpoint = entities_checkpoint.unwrap_or(EntitiesCheckpoint {
                 processed: 0,
                 total: (provider.count_entries::<tables::HashedAccounts>()? +
                     provider.count_entries::<tables::HashedStorages>()?)
                     as u64,
             });
 
-            let tx = provider.tx_ref();
-            let progress = StateRoot::from_tx(tx)
+            let state_root = match trie_block {
+                // Only the paths changed since the reused trie nodes were computed are walked.
+                Some(trie_block) => {
+                    StateRoot::incremental_root_calculator(provider, trie_block + 1..=to_block)
+                        .map_err(|e| StageError::Fatal(Box::new(e)))?
+                }
+                None => StateRoot::from_tx(provider.tx_ref()),
+            };
+            let progress = state_root
                 .with_intermediate_state(checkpoint.map(IntermediateStateRootState::from))
                 .root_with_progress()
                 .map_err(|e| {
@@ -277,6 +309,7 @@ where
                                 storage_state.account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
                             ));
                     }
+                    checkpoint.trie_block = trie_block;
                     self.save_execution_checkpoint(provider, Some(checkpoint))?;
 
                     entities_checkpoint.processed += hashed_entries_walked as u64;
@@ -311,7 +344,7 @@ where
                     "Processing chunk"
                 );
                 let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), chunk_range)
+                StateRoot::incremental_root_with_updates(provider, chunk_range)
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
@@ -360,6 +393,16 @@ where
     ) -> Result<UnwindOutput, StageError> {
         let tx = provider.tx_ref();
         let range = input.unwind_block_range();
+
+        // Trie nodes of an interrupted computation for an unwound block are only valid on the
+        // unwound chain, so they must not be reused by the next execution.
+        if self
+            .get_execution_checkpoint(provider)?
+            .is_some_and(|checkpoint| checkpoint.target_block > input.unwind_to)
+        {
+            self.save_execution_checkpoint(provider, None)?;
+        }
+
         if matches!(self, Self::Execution { .. }) {
             info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
             return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
@@ -388,7 +431,7 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
+            let (block_root, updates) = StateRoot::incremental_root_with_updates(provider, range)
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -406,6 +449,108 @@ where
 
         Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        if matches!(self, Self::Execution { .. }) || input.unwind_block_range().is_empty() {
+            return Ok(UnwindPlan::default())
+        }
+
+        // Unwinding to genesis clears the trie tables, otherwise the changed trie nodes are only
+        // known after recomputing the state root.
+        let (account_nodes, storage_nodes) = if input.unwind_to == 0 {
+            let tx = provider.tx_ref();
+            (
+                Some(tx.entries::<tables::AccountsTrie>()? as u64),
+                Some(tx.entries::<tables::StoragesTrie>()? as u64),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(UnwindPlan::default()
+            .with_table(tables::AccountsTrie::NAME, account_nodes)
+            .with_table(tables::StoragesTrie::NAME, storage_nodes))
+    }
+}
+
+/// Prepares the trie tables of an interrupted rebuild for reuse when computing the root for a
+/// later target block.
+///
+/// Trie nodes of the subtrees completed before the interruption stay valid for the block they were
+/// computed for, so only the paths changed after that block need to be recomputed. Nodes under the
+/// unfinished subtree are removed, since their ancestors were only kept in the hash builder state.
+///
+/// Returns the block the remaining trie nodes are valid for, or `None` if the nodes can't be
+/// reused and the trie has to be rebuilt from scratch.
+fn reuse_trie_nodes<Provider>(
+    provider: &Provider,
+    previous: &MerkleCheckpoint,
+    to_block: BlockNumber,
+) -> Result<Option<BlockNumber>, StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    // Changed paths are loaded from the changesets, which might not be written if history
+    // pruning is configured.
+    let prune_modes = provider.prune_modes_ref();
+    if previous.target_block > to_block ||
+        prune_modes.account_history.is_some() ||
+        prune_modes.storage_history.is_some()
+    {
+        return Ok(None)
+    }
+
+    let tx = provider.tx_ref();
+
+    let nibble = previous.last_account_key[0] >> 4;
+    let mut account_trie = tx.cursor_write::<tables::AccountsTrie>()?;
+    let mut entry = account_trie.seek(StoredNibbles(Nibbles::from_nibbles_unchecked([nibble])))?;
+    while let Some((key, _)) = entry {
+        if key.0.first() != Some(nibble) {
+            break
+        }
+        account_trie.delete_current()?;
+        entry = account_trie.next()?;
+    }
+
+    // The root node still marks the removed subtree as stored and hashed. Clearing the bits makes
+    // the walker recompute the subtree from the hashed accounts.
+    let root_key = StoredNibbles(Nibbles::default());
+    if let Some((_, root)) = account_trie.seek_exact(root_key.clone())? {
+        if root.tree_mask.is_bit_set(nibble) || root.hash_mask.is_bit_set(nibble) {
+            let mut tree_mask = root.tree_mask;
+            tree_mask.unset_bit(nibble);
+            let mut hash_mask = root.hash_mask;
+            hash_mask.unset_bit(nibble);
+            // Hashes are stored in the order of the set bits of the hash mask.
+            let removed_hash = root
+                .hash_mask
+                .is_bit_set(nibble)
+                .then(|| (root.hash_mask.get() & ((1 << nibble) - 1)).count_ones() as usize);
+            let hashes = root
+                .hashes
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| Some(*index) != removed_hash)
+                .map(|(_, hash)| *hash)
+                .collect();
+            let root = BranchNodeCompact::new(root.state_mask, tree_mask, hash_mask, hashes, None);
+            account_trie.upsert(root_key, &root)?;
+        }
+    }
+
+    if previous.storage_root_checkpoint.is_some() {
+        let mut storage_trie = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+        if storage_trie.seek_exact(previous.last_account_key)?.is_some() {
+            storage_trie.delete_current_duplicates()?;
+        }
+    }
+
+    Ok(Some(previous.trie_block.unwrap_or(previous.target_block)))
 }
 
 /// Check that the computed state root matches the root in the expected header.
@@ -446,7 +591,11 @@ mod tests {
         self, random_block, random_block_range, random_changeset_range,
         random_contract_account_range, BlockParams, BlockRangeParams,
     };
-    use reth_trie::test_utils::{state_root, state_root_prehashed};
+    use reth_trie::{
+        hash_builder::HashBuilderState,
+        test_utils::{state_root, state_root_prehashed},
+        TrieMask,
+    };
     use std::collections::BTreeMap;
 
     stage_test_suite_ext!(MerkleTestRunner, merkle);
@@ -575,15 +724,10 @@ mod tests {
         let header = provider.header_by_number(previous_stage).unwrap().unwrap();
         let expected_root = header.state_root;
 
-        let actual_root = runner
-            .db
-            .query(|tx| {
-                Ok(StateRoot::incremental_root_with_updates(
-                    tx,
-                    stage_progress + 1..=previous_stage,
-                ))
-            })
-            .unwrap();
+        let actual_root = StateRoot::incremental_root_with_updates(
+            &provider,
+            stage_progress + 1..=previous_stage,
+        );
 
         assert_eq!(
             actual_root.unwrap().0,
@@ -592,6 +736,71 @@ mod tests {
         );
     }
 
+    /// Trie nodes of an interrupted rebuild for a block that is unwound must not be reused when
+    /// executing the new chain.
+    #[tokio::test]
+    async fn unwind_discards_interrupted_rebuild() {
+        let (previous_stage, stage_progress) = (500, 0);
+        let interrupted_target = 490;
+
+        let mut runner = MerkleTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        // A rebuild for a block of the unwound chain was interrupted after persisting trie nodes
+        // that are only valid on that chain.
+        let provider = runner.db.factory.database_provider_rw().unwrap();
+        let mut stage = MerkleStage::default_execution();
+        stage
+            .save_execution_checkpoint(
+                &provider,
+                Some(MerkleCheckpoint::new(
+                    interrupted_target,
+                    B256::repeat_byte(0x80),
+                    Vec::new(),
+                    HashBuilderState::default(),
+                )),
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountsTrie>(
+                StoredNibbles(Nibbles::from_nibbles_unchecked([0x1])),
+                BranchNodeCompact::new(
+                    TrieMask::new(0b11),
+                    TrieMask::default(),
+                    TrieMask::new(0b11),
+                    vec![B256::repeat_byte(0x11), B256::repeat_byte(0x22)],
+                    None,
+                ),
+            )
+            .unwrap();
+
+        stage
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(interrupted_target),
+                    unwind_to: interrupted_target - 10,
+                    bad_block: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(stage.get_execution_checkpoint(&provider).unwrap(), None);
+        provider.commit().unwrap();
+
+        // The trie is rebuilt, and the state root is validated within the stage.
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(
+            result,
+            Ok(ExecOutput { checkpoint: StageCheckpoint { block_number, .. }, done: true })
+                if block_number == previous_stage
+        );
+    }
+
     struct MerkleTestRunner {
         db: TestStageDB,
         clean_threshold: u64,
@@ -14,6 +14,10 @@ mod headers;
 mod index_account_history;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index transactions of senders
+mod index_sender_transactions;
+/// Index deployments of contracts
+mod index_contract_deployments;
 /// Stage for computing state root.
 mod merkle;
 mod prune;
@@ -21,6 +25,8 @@ mod prune;
 mod sender_recovery;
 /// The transaction lookup stage
 mod tx_lookup;
+/// Unwinding of single tables
+mod unwind_table;
 
 pub use bodies::*;
 pub use era::*;
@@ -30,11 +36,14 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_contract_deployments::*;
+pub use index_sender_transactions::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use sender_recovery::*;
 pub use tx_lookup::*;
+pub use unwind_table::*;
 
 mod era;
 mod utils;
@@ -163,6 +172,8 @@ mod tests {
                 ExecutionStageThresholds {
                     max_blocks: Some(100),
                     max_changes: None,
+                    max_changeset_bytes: None,
+                    max_trie_updates: None,
                     max_cumulative_gas: None,
                     max_duration: None,
                 },
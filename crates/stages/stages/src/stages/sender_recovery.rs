@@ -1,13 +1,14 @@
+use super::{count_entries_in_range, unwind_transaction_senders, unwind_tx_range};
 use alloy_primitives::{Address, TxNumber};
 use reth_config::config::SenderRecoveryConfig;
 use reth_consensus::ConsensusError;
 use reth_db::static_file::TransactionMask;
 use reth_db_api::{
     cursor::DbCursorRW,
-    table::Value,
+    table::{Table, Value},
     tables,
     transaction::{DbTx, DbTxMut},
-    DbTxUnwindExt, RawValue,
+    RawValue,
 };
 use reth_primitives_traits::{GotExpected, NodePrimitives, SignedTransaction};
 use reth_provider::{
@@ -17,7 +18,7 @@ use reth_provider::{
 use reth_prune_types::PruneSegment;
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError,
-    StageId, UnwindInput, UnwindOutput,
+    StageId, UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_static_file_types::StaticFileSegment;
 use std::{fmt::Debug, ops::Range, sync::mpsc};
@@ -127,18 +128,25 @@ where
     ) -> Result<UnwindOutput, StageError> {
         let (_, unwind_to, _) = input.unwind_block_range_with_threshold(self.commit_threshold);
 
-        // Lookup latest tx id that we should unwind to
-        let latest_tx_id = provider
-            .block_body_indices(unwind_to)?
-            .ok_or(ProviderError::BlockBodyIndicesNotFound(unwind_to))?
-            .last_tx_num();
-        provider.tx_ref().unwind_table_by_num::<tables::TransactionSenders>(latest_tx_id)?;
+        unwind_transaction_senders(provider, unwind_to)?;
 
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(unwind_to)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx_range = unwind_tx_range(provider, input.unwind_to, input.checkpoint.block_number)?;
+        let senders =
+            count_entries_in_range::<tables::TransactionSenders>(provider.tx_ref(), tx_range)?;
+
+        Ok(UnwindPlan::default().with_table(tables::TransactionSenders::NAME, Some(senders)))
+    }
 }
 
 fn recover_range<Provider, CURSOR>(
@@ -1,10 +1,10 @@
-use alloy_eips::eip2718::Encodable2718;
+use super::{unwind_transaction_hash_numbers, unwind_tx_range};
 use alloy_primitives::{TxHash, TxNumber};
 use num_traits::Zero;
 use reth_config::config::{EtlConfig, TransactionLookupConfig};
 use reth_db_api::{
-    cursor::{DbCursorRO, DbCursorRW},
-    table::Value,
+    cursor::DbCursorRW,
+    table::{Table, Value},
     tables,
     transaction::DbTxMut,
     RawKey, RawValue,
@@ -13,12 +13,12 @@ use reth_etl::Collector;
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_provider::{
     BlockReader, DBProvider, PruneCheckpointReader, PruneCheckpointWriter,
-    StaticFileProviderFactory, StatsReader, TransactionsProvider, TransactionsProviderExt,
+    StaticFileProviderFactory, StatsReader, TransactionsProviderExt,
 };
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
-    UnwindInput, UnwindOutput,
+    UnwindInput, UnwindOutput, UnwindPlan,
 };
 use reth_storage_errors::provider::ProviderError;
 use tracing::*;
@@ -193,39 +193,33 @@ where
         provider: &Provider,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
-        let tx = provider.tx_ref();
         let (range, unwind_to, _) = input.unwind_block_range_with_threshold(self.chunk_size);
 
-        // Cursor to unwind tx hash to number
-        let mut tx_hash_number_cursor = tx.cursor_write::<tables::TransactionHashNumbers>()?;
-        let static_file_provider = provider.static_file_provider();
-        let rev_walker = provider
-            .block_body_indices_range(range.clone())?
-            .into_iter()
-            .zip(range.collect::<Vec<_>>())
-            .rev();
-
-        for (body, number) in rev_walker {
-            if number <= unwind_to {
-                break;
-            }
-
-            // Delete all transactions that belong to this block
-            for tx_id in body.tx_num_range() {
-                // First delete the transaction and hash to id mapping
-                if let Some(transaction) = static_file_provider.transaction_by_id(tx_id)? {
-                    if tx_hash_number_cursor.seek_exact(transaction.trie_hash())?.is_some() {
-                        tx_hash_number_cursor.delete_current()?;
-                    }
-                }
-            }
-        }
+        unwind_transaction_hash_numbers(provider, range)?;
 
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(unwind_to)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn plan_unwind(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindPlan, StageError> {
+        let tx_range = unwind_tx_range(provider, input.unwind_to, input.checkpoint.block_number)?;
+
+        // Hashes of pruned transactions are not in the table anymore
+        let first_unpruned_tx = provider
+            .get_prune_checkpoint(PruneSegment::TransactionLookup)?
+            .and_then(|checkpoint| checkpoint.tx_number)
+            .map(|tx_number| tx_number + 1)
+            .unwrap_or_default();
+        let hashes = tx_range.end.saturating_sub(tx_range.start.max(first_unpruned_tx));
+
+        Ok(UnwindPlan::default().with_table(tables::TransactionHashNumbers::NAME, Some(hashes)))
+    }
 }
 
 fn stage_checkpoint<Provider>(provider: &Provider) -> Result<EntitiesCheckpoint, StageError>
@@ -260,12 +254,12 @@ mod tests {
     };
     use alloy_primitives::{BlockNumber, B256};
     use assert_matches::assert_matches;
-    use reth_db_api::transaction::DbTx;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
     use reth_ethereum_primitives::Block;
     use reth_primitives_traits::SealedBlock;
     use reth_provider::{
         providers::StaticFileWriter, BlockBodyIndicesProvider, DatabaseProviderFactory,
-        StaticFileProviderFactory,
+        StaticFileProviderFactory, TransactionsProvider,
     };
     use reth_stages_api::StageUnitCheckpoint;
     use reth_testing_utils::generators::{
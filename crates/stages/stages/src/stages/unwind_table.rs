@@ -0,0 +1,205 @@
+use super::unwind_tx_range;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::BlockNumberAddress,
+    table::Value,
+    tables,
+    transaction::DbTxMut,
+    DbTxUnwindExt, Tables,
+};
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_provider::{
+    BlockReader, DBProvider, HashingWriter, HistoryWriter, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use reth_stages_api::{StageError, StageId};
+use reth_storage_errors::provider::ProviderError;
+use std::ops::RangeInclusive;
+
+/// A table that can be unwound on its own, without unwinding the stages it is derived from.
+///
+/// Every table is populated by exactly one stage from data that is left untouched when the table is
+/// unwound. After unwinding the table, the checkpoint of [`UnwindTable::stage_id`] has to be set to
+/// the block the table was unwound to, so that the next pipeline run rebuilds the unwound entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnwindTable {
+    /// [`tables::AccountsHistory`], populated by [`StageId::IndexAccountHistory`].
+    AccountsHistory,
+    /// [`tables::StoragesHistory`], populated by [`StageId::IndexStorageHistory`].
+    StoragesHistory,
+    /// [`tables::HashedAccounts`], populated by [`StageId::AccountHashing`].
+    HashedAccounts,
+    /// [`tables::HashedStorages`], populated by [`StageId::StorageHashing`].
+    HashedStorages,
+    /// [`tables::TransactionSenders`], populated by [`StageId::SenderRecovery`].
+    TransactionSenders,
+    /// [`tables::TransactionHashNumbers`], populated by [`StageId::TransactionLookup`].
+    TransactionHashNumbers,
+    /// [`tables::SenderTransactions`], populated by [`StageId::IndexSenderTransactions`].
+    ///
+    /// [`tables::RecipientTransactions`] is populated by the same stage and unwound with it.
+    SenderTransactions,
+    /// [`tables::ContractDeployments`], populated by [`StageId::IndexContractDeployments`].
+    ContractDeployments,
+}
+
+impl UnwindTable {
+    /// Returns the id of the stage that populates the table.
+    pub const fn stage_id(&self) -> StageId {
+        match self {
+            Self::AccountsHistory => StageId::IndexAccountHistory,
+            Self::StoragesHistory => StageId::IndexStorageHistory,
+            Self::HashedAccounts => StageId::AccountHashing,
+            Self::HashedStorages => StageId::StorageHashing,
+            Self::TransactionSenders => StageId::SenderRecovery,
+            Self::TransactionHashNumbers => StageId::TransactionLookup,
+            Self::SenderTransactions => StageId::IndexSenderTransactions,
+            Self::ContractDeployments => StageId::IndexContractDeployments,
+        }
+    }
+
+    /// Returns the table.
+    pub const fn table(&self) -> Tables {
+        match self {
+            Self::AccountsHistory => Tables::AccountsHistory,
+            Self::StoragesHistory => Tables::StoragesHistory,
+            Self::HashedAccounts => Tables::HashedAccounts,
+            Self::HashedStorages => Tables::HashedStorages,
+            Self::TransactionSenders => Tables::TransactionSenders,
+            Self::TransactionHashNumbers => Tables::TransactionHashNumbers,
+            Self::SenderTransactions => Tables::SenderTransactions,
+            Self::ContractDeployments => Tables::ContractDeployments,
+        }
+    }
+
+    /// Removes the entries of the blocks in `range` from the table.
+    ///
+    /// The range is expected to start right after the block the table is unwound to and to end at
+    /// the checkpoint of [`UnwindTable::stage_id`].
+    pub fn unwind<Provider>(
+        &self,
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>
+            + BlockReader
+            + HashingWriter
+            + HistoryWriter
+            + StaticFileProviderFactory<
+                Primitives: NodePrimitives<SignedTx: Value + SignedTransaction>,
+            >,
+    {
+        let unwind_to = range.start().saturating_sub(1);
+        match self {
+            Self::AccountsHistory => {
+                provider.unwind_account_history_indices_range(range)?;
+            }
+            Self::StoragesHistory => {
+                provider.unwind_storage_history_indices_range(BlockNumberAddress::range(range))?;
+            }
+            Self::HashedAccounts => {
+                provider.unwind_account_hashing_range(range)?;
+            }
+            Self::HashedStorages => {
+                provider.unwind_storage_hashing_range(BlockNumberAddress::range(range))?;
+            }
+            Self::TransactionSenders => unwind_transaction_senders(provider, unwind_to)?,
+            Self::TransactionHashNumbers => unwind_transaction_hash_numbers(provider, range)?,
+            Self::SenderTransactions => {
+                let tx_range = unwind_tx_range(provider, unwind_to, *range.end())?;
+                provider.unwind_sender_transactions_index_range(tx_range)?;
+            }
+            Self::ContractDeployments => {
+                provider.unwind_contract_deployments_index_range(range)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<Tables> for UnwindTable {
+    type Error = Tables;
+
+    fn try_from(table: Tables) -> Result<Self, Self::Error> {
+        Ok(match table {
+            Tables::AccountsHistory => Self::AccountsHistory,
+            Tables::StoragesHistory => Self::StoragesHistory,
+            Tables::HashedAccounts => Self::HashedAccounts,
+            Tables::HashedStorages => Self::HashedStorages,
+            Tables::TransactionSenders => Self::TransactionSenders,
+            Tables::TransactionHashNumbers => Self::TransactionHashNumbers,
+            Tables::SenderTransactions => Self::SenderTransactions,
+            Tables::ContractDeployments => Self::ContractDeployments,
+            table => return Err(table),
+        })
+    }
+}
+
+/// Removes the [`tables::TransactionSenders`] entries of all transactions after block
+/// `unwind_to`.
+pub(crate) fn unwind_transaction_senders<Provider>(
+    provider: &Provider,
+    unwind_to: BlockNumber,
+) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader,
+{
+    // Lookup latest tx id that we should unwind to
+    let latest_tx_id = provider
+        .block_body_indices(unwind_to)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(unwind_to))?
+        .last_tx_num();
+    provider.tx_ref().unwind_table_by_num::<tables::TransactionSenders>(latest_tx_id)?;
+    Ok(())
+}
+
+/// Removes the [`tables::TransactionHashNumbers`] entries of all transactions of the blocks in
+/// `range`.
+pub(crate) fn unwind_transaction_hash_numbers<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value + SignedTransaction>>,
+{
+    // Cursor to unwind tx hash to number
+    let mut tx_hash_number_cursor =
+        provider.tx_ref().cursor_write::<tables::TransactionHashNumbers>()?;
+    let static_file_provider = provider.static_file_provider();
+    for body in provider.block_body_indices_range(range)?.into_iter().rev() {
+        // Delete all transactions that belong to this block
+        for tx_id in body.tx_num_range() {
+            // First delete the transaction and hash to id mapping
+            if let Some(transaction) = static_file_provider.transaction_by_id(tx_id)? {
+                if tx_hash_number_cursor.seek_exact(transaction.trie_hash())?.is_some() {
+                    tx_hash_number_cursor.delete_current()?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unwind_table_roundtrip() {
+        for table in Tables::ALL {
+            if let Ok(unwind_table) = UnwindTable::try_from(*table) {
+                assert_eq!(unwind_table.table(), *table);
+            }
+        }
+        assert_eq!(
+            UnwindTable::try_from(Tables::PlainAccountState),
+            Err(Tables::PlainAccountState)
+        );
+    }
+}
@@ -1,29 +1,150 @@
 //! Utils for `stages`.
-use alloy_primitives::{BlockNumber, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
+use itertools::Either;
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
+    models::{
+        sharded_key::NUM_OF_INDICES_IN_SHARD, storage_sharded_key::StorageShardedKey,
+        AddressStorageKey, BlockNumberAddress, ShardedKey,
+    },
     table::{Decompress, Table},
+    tables,
     transaction::{DbTx, DbTxMut},
-    BlockNumberList, DatabaseError,
+    BlockNumberList, DatabaseError, RawKey, RawTable,
 };
 use reth_etl::Collector;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
-    StaticFileProviderFactory,
+    providers::StaticFileProvider, BlockReader, ChangeSetReader, DBProvider, ProviderError,
+    StaticFileProviderFactory, StorageChangeSetReader,
 };
 use reth_stages_api::StageError;
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::{Range, RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
 const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
+/// Number of blocks to read changesets for at once when collecting history indices.
+const CHANGESETS_BATCH_BLOCKS: u64 = 1_000;
+
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
 ///
+/// Changesets are read from the `CS` table only, see [`collect_account_history_indices`] and
+/// [`collect_storage_history_indices`] for changesets that may have been moved to static files.
+pub(crate) fn collect_history_indices<Provider, CS, H, P>(
+    provider: &Provider,
+    range: impl RangeBounds<CS::Key>,
+    sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
+    partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
+    etl_config: &EtlConfig,
+) -> Result<Collector<H::Key, H::Value>, StageError>
+where
+    Provider: DBProvider,
+    CS: Table,
+    H: Table<Value = BlockNumberList>,
+    P: Copy + Eq + Hash,
+{
+    let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
+
+    // observability
+    let total_changesets = provider.tx_ref().entries::<CS>()?;
+    let interval = (total_changesets / 1000).max(1);
+
+    let walker = changeset_cursor.walk_range(range)?;
+    let entries = walker.enumerate().map(|(idx, entry)| -> Result<_, StageError> {
+        if idx > 0 && idx.is_multiple_of(interval) && total_changesets > 1000 {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
+        }
+        Ok(partial_key_factory(entry?))
+    });
+
+    collect_indices::<H, P>(entries, sharded_key_factory, etl_config)
+}
+
+/// Collects the account history indices for the account changesets of the block range.
+///
+/// Changesets are read through the provider, so the ones that were moved to static files are
+/// included.
+pub(crate) fn collect_account_history_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<ShardedKey<Address>, BlockNumberList>, StageError>
+where
+    Provider: ChangeSetReader,
+{
+    let entries = changeset_batches(range, |batch| {
+        Ok(provider
+            .account_changesets_range(batch)?
+            .into_iter()
+            .map(|(block_number, account_before)| (block_number, account_before.address)))
+    });
+
+    collect_indices::<tables::AccountsHistory, _>(entries, ShardedKey::new, etl_config)
+}
+
+/// Collects the storage history indices for the storage changesets of the block range.
+///
+/// Changesets are read through the provider, so the ones that were moved to static files are
+/// included.
+pub(crate) fn collect_storage_history_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<StorageShardedKey, BlockNumberList>, StageError>
+where
+    Provider: StorageChangeSetReader,
+{
+    let entries = changeset_batches(range, |batch| {
+        Ok(provider.storage_changesets_range(BlockNumberAddress::range(batch))?.into_iter().map(
+            |(key, storage_before)| {
+                (key.block_number(), AddressStorageKey((key.address(), storage_before.key)))
+            },
+        ))
+    });
+
+    collect_indices::<tables::StoragesHistory, _>(
+        entries,
+        |AddressStorageKey((address, storage_key)), highest_block_number| {
+            StorageShardedKey::new(address, storage_key, highest_block_number)
+        },
+        etl_config,
+    )
+}
+
+/// Reads the changesets of the block range in batches of [`CHANGESETS_BATCH_BLOCKS`] blocks, so
+/// only one batch is held in memory at a time.
+fn changeset_batches<I>(
+    range: RangeInclusive<BlockNumber>,
+    read_batch: impl Fn(RangeInclusive<BlockNumber>) -> Result<I, ProviderError>,
+) -> impl Iterator<Item = Result<I::Item, StageError>>
+where
+    I: IntoIterator,
+{
+    let (start, end) = range.into_inner();
+    let total_blocks = end.saturating_sub(start) + 1;
+    (start..=end).step_by(CHANGESETS_BATCH_BLOCKS as usize).flat_map(move |batch_start| {
+        let batch_end = batch_start.saturating_add(CHANGESETS_BATCH_BLOCKS - 1).min(end);
+        if total_blocks > CHANGESETS_BATCH_BLOCKS {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", ((batch_start - start) as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+        match read_batch(batch_start..=batch_end) {
+            Ok(entries) => Either::Left(entries.into_iter().map(Ok)),
+            Err(err) => Either::Right(std::iter::once(Err(StageError::from(err)))),
+        }
+    })
+}
+
+/// Collects the history (`H`) indices of the given `(block number, partial key)` entries, ordered
+/// by block number, and stores them in a [`Collector`].
+///
 /// ## Process
 /// The function utilizes a `HashMap` cache with a structure of `PartialKey` (`P`) (Address or
 /// Address.StorageKey) to `BlockNumberList`. When the cache exceeds its capacity, its contents are
@@ -38,21 +159,15 @@ const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 ///
 /// As a result, the `Collector` will contain entries such as `(Address1.3, [1,2,3])` and
 /// `(Address1.300, [100,300])`. The entries may be stored across one or more files.
-pub(crate) fn collect_history_indices<Provider, CS, H, P>(
-    provider: &Provider,
-    range: impl RangeBounds<CS::Key>,
+fn collect_indices<H, P>(
+    entries: impl IntoIterator<Item = Result<(u64, P), StageError>>,
     sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
-    partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
     etl_config: &EtlConfig,
 ) -> Result<Collector<H::Key, H::Value>, StageError>
 where
-    Provider: DBProvider,
-    CS: Table,
     H: Table<Value = BlockNumberList>,
     P: Copy + Eq + Hash,
 {
-    let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
-
     let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
     let mut cache: HashMap<P, Vec<u64>> = HashMap::default();
 
@@ -67,20 +182,12 @@ where
         Ok::<(), StageError>(())
     };
 
-    // observability
-    let total_changesets = provider.tx_ref().entries::<CS>()?;
-    let interval = (total_changesets / 1000).max(1);
-
     let mut flush_counter = 0;
     let mut current_block_number = u64::MAX;
-    for (idx, entry) in changeset_cursor.walk_range(range)?.enumerate() {
-        let (block_number, key) = partial_key_factory(entry?);
+    for entry in entries {
+        let (block_number, key) = entry?;
         cache.entry(key).or_default().push(block_number);
 
-        if idx > 0 && idx.is_multiple_of(interval) && total_changesets > 1000 {
-            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
-        }
-
         // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
         if current_block_number != block_number {
             current_block_number = block_number;
@@ -283,3 +390,74 @@ where
         segment,
     })
 }
+
+/// Returns the number of entries of the table `T` in the given key range.
+///
+/// Values are not decoded, so this works for tables that are generic over the stored type.
+pub(crate) fn count_entries_in_range<T: Table>(
+    tx: &impl DbTx,
+    range: impl RangeBounds<T::Key>,
+) -> Result<u64, DatabaseError> {
+    let range = (
+        range.start_bound().cloned().map(RawKey::new),
+        range.end_bound().cloned().map(RawKey::new),
+    );
+    tx.cursor_read::<RawTable<T>>()?
+        .walk_range(range)?
+        .try_fold(0, |entries, entry| entry.map(|_| entries + 1))
+}
+
+/// Returns the range of transactions in the blocks that are removed by unwinding to
+/// `unwind_to`, up to and including the `tip` block.
+pub(crate) fn unwind_tx_range<Provider: BlockReader>(
+    provider: &Provider,
+    unwind_to: BlockNumber,
+    tip: BlockNumber,
+) -> Result<Range<TxNumber>, ProviderError> {
+    let next_tx_num = |block| {
+        provider
+            .block_body_indices(block)?
+            .map(|indices| indices.next_tx_num())
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block))
+    };
+    let start = next_tx_num(unwind_to)?;
+    Ok(start..next_tx_num(tip)?.max(start))
+}
+
+/// Returns the number of account changesets in the block range and the distinct accounts they
+/// change.
+///
+/// Changesets are read through the provider, so the ones that were moved to static files are
+/// included.
+pub(crate) fn changed_accounts_in_range(
+    provider: &impl ChangeSetReader,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<(u64, HashSet<Address>), ProviderError> {
+    let mut changesets = 0;
+    let mut accounts = HashSet::default();
+    for (_, account_before) in provider.account_changesets_range(range)? {
+        changesets += 1;
+        accounts.insert(account_before.address);
+    }
+    Ok((changesets, accounts))
+}
+
+/// Returns the number of storage changesets in the block range and the distinct storage slots they
+/// change.
+///
+/// Changesets are read through the provider, so the ones that were moved to static files are
+/// included.
+pub(crate) fn changed_storages_in_range(
+    provider: &impl StorageChangeSetReader,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<(u64, HashSet<(Address, B256)>), ProviderError> {
+    let mut changesets = 0;
+    let mut slots = HashSet::default();
+    for (BlockNumberAddress((_, address)), storage_before) in
+        provider.storage_changesets_range(BlockNumberAddress::range(range))?
+    {
+        changesets += 1;
+        slots.insert((address, storage_before.key));
+    }
+    Ok((changesets, slots))
+}
@@ -17,6 +17,12 @@ pub struct MerkleCheckpoint {
     pub state: HashBuilderState,
     /// Optional storage root checkpoint for the last processed account.
     pub storage_root_checkpoint: Option<StorageRootMerkleCheckpoint>,
+    /// The block that the trie nodes persisted before the computation started are valid for.
+    ///
+    /// Set if the computation reuses the trie nodes of an interrupted computation for an earlier
+    /// target, in which case only the paths changed after this block are recomputed. `None` if
+    /// the trie is built from scratch.
+    pub trie_block: Option<BlockNumber>,
 }
 
 impl MerkleCheckpoint {
@@ -27,7 +33,14 @@ impl MerkleCheckpoint {
         walker_stack: Vec<StoredSubNode>,
         state: HashBuilderState,
     ) -> Self {
-        Self { target_block, last_account_key, walker_stack, state, storage_root_checkpoint: None }
+        Self {
+            target_block,
+            last_account_key,
+            walker_stack,
+            state,
+            storage_root_checkpoint: None,
+            trie_block: None,
+        }
     }
 }
 
@@ -68,6 +81,19 @@ impl reth_codecs::Compact for MerkleCheckpoint {
             }
         }
 
+        // Encode the optional trie block, older checkpoints end before it
+        match self.trie_block {
+            Some(trie_block) => {
+                buf.put_u8(1);
+                buf.put_u64(trie_block);
+                len += 9;
+            }
+            None => {
+                buf.put_u8(0);
+                len += 1;
+            }
+        }
+
         len
     }
 
@@ -89,7 +115,7 @@ impl reth_codecs::Compact for MerkleCheckpoint {
         let (state, mut buf) = HashBuilderState::from_compact(buf, 0);
 
         // Decode the storage root checkpoint if it exists
-        let (storage_root_checkpoint, buf) = if buf.is_empty() {
+        let (storage_root_checkpoint, mut buf) = if buf.is_empty() {
             (None, buf)
         } else {
             match buf.get_u8() {
@@ -101,7 +127,27 @@ impl reth_codecs::Compact for MerkleCheckpoint {
             }
         };
 
-        (Self { target_block, last_account_key, walker_stack, state, storage_root_checkpoint }, buf)
+        // Decode the trie block if it exists
+        let trie_block = if buf.is_empty() {
+            None
+        } else {
+            match buf.get_u8() {
+                1 => Some(buf.get_u64()),
+                _ => None,
+            }
+        };
+
+        (
+            Self {
+                target_block,
+                last_account_key,
+                walker_stack,
+                state,
+                storage_root_checkpoint,
+                trie_block,
+            },
+            buf,
+        )
     }
 }
 
@@ -567,6 +613,7 @@ mod tests {
             }],
             state: HashBuilderState::default(),
             storage_root_checkpoint: None,
+            trie_block: None,
         };
 
         let mut buf = Vec::new();
@@ -628,6 +675,7 @@ mod tests {
             }],
             state: HashBuilderState::default(),
             storage_root_checkpoint: Some(storage_checkpoint),
+            trie_block: Some(rng.random()),
         };
 
         let mut buf = Vec::new();
@@ -635,4 +683,24 @@ mod tests {
         let (decoded, _) = MerkleCheckpoint::from_compact(&buf, encoded);
         assert_eq!(decoded, checkpoint);
     }
+
+    #[test]
+    fn merkle_checkpoint_without_trie_block_decodes() {
+        let mut rng = rand::rng();
+        let checkpoint = MerkleCheckpoint::new(
+            rng.random(),
+            rng.random(),
+            Vec::new(),
+            HashBuilderState::default(),
+        );
+
+        // Checkpoints saved before the trie block was introduced end after the storage root
+        // checkpoint.
+        let mut buf = Vec::new();
+        checkpoint.to_compact(&mut buf);
+        assert_eq!(buf.pop(), Some(0));
+        let (decoded, rest) = MerkleCheckpoint::from_compact(&buf, buf.len());
+        assert_eq!(decoded, checkpoint);
+        assert!(rest.is_empty());
+    }
 }
@@ -1,18 +1,31 @@
 use core::time::Duration;
 
+/// Estimated size in bytes of a single changeset entry, used to translate the number of tracked
+/// changeset entries into [`ExecutionStageThresholds::max_changeset_bytes`].
+///
+/// This is the size of a storage changeset entry: 8 byte block number, 20 byte address, 32 byte
+/// storage key and 32 byte storage value. Account changeset entries are usually smaller.
+pub const CHANGESET_ENTRY_SIZE_ESTIMATE: u64 = 8 + 20 + 32 + 32;
+
 /// The thresholds at which the execution stage writes state changes to the database.
 ///
-/// If either of the thresholds (`max_blocks` and `max_changes`) are hit, then the execution stage
-/// commits all pending changes to the database.
+/// If any of the thresholds are hit, then the execution stage commits all pending changes to the
+/// database.
 ///
-/// A third threshold, `max_changesets`, can be set to periodically write changesets to the
-/// current database transaction, which frees up memory.
+/// The resource thresholds (`max_changes`, `max_changeset_bytes` and `max_trie_updates`) bound the
+/// amount of memory held by the execution stage between commits and can be tuned independently.
 #[derive(Debug, Clone)]
 pub struct ExecutionStageThresholds {
     /// The maximum number of blocks to execute before the execution stage commits.
     pub max_blocks: Option<u64>,
     /// The maximum number of state changes to keep in memory before the execution stage commits.
     pub max_changes: Option<u64>,
+    /// The maximum estimated size in bytes of changesets to keep in memory before the execution
+    /// stage commits.
+    pub max_changeset_bytes: Option<u64>,
+    /// The maximum number of changed accounts and storage slots (resulting in trie updates) to
+    /// keep in memory before the execution stage commits.
+    pub max_trie_updates: Option<u64>,
     /// The maximum cumulative amount of gas to process before the execution stage commits.
     pub max_cumulative_gas: Option<u64>,
     /// The maximum spent on blocks processing before the execution stage commits.
@@ -24,6 +37,8 @@ impl Default for ExecutionStageThresholds {
         Self {
             max_blocks: Some(500_000),
             max_changes: Some(5_000_000),
+            max_changeset_bytes: None,
+            max_trie_updates: None,
             // 50k full blocks of 30M gas
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
@@ -47,4 +62,35 @@ impl ExecutionStageThresholds {
             cumulative_gas_used >= self.max_cumulative_gas.unwrap_or(u64::MAX) ||
             elapsed >= self.max_duration.unwrap_or(Duration::MAX)
     }
+
+    /// Check if the per-resource memory thresholds have been hit.
+    ///
+    /// `changeset_entries` is the number of tracked account and storage changeset entries and
+    /// `trie_updates` the number of changed accounts and storage slots.
+    #[inline]
+    pub fn is_resource_limit_reached(&self, changeset_entries: u64, trie_updates: u64) -> bool {
+        self.max_changeset_bytes.is_some_and(|max| {
+            changeset_entries.saturating_mul(CHANGESET_ENTRY_SIZE_ESTIMATE) >= max
+        }) || self.max_trie_updates.is_some_and(|max| trie_updates >= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_limits() {
+        let thresholds = ExecutionStageThresholds::default();
+        assert!(!thresholds.is_resource_limit_reached(u64::MAX, u64::MAX));
+
+        let thresholds = ExecutionStageThresholds {
+            max_changeset_bytes: Some(10 * CHANGESET_ENTRY_SIZE_ESTIMATE),
+            max_trie_updates: Some(100),
+            ..Default::default()
+        };
+        assert!(!thresholds.is_resource_limit_reached(9, 99));
+        assert!(thresholds.is_resource_limit_reached(10, 0));
+        assert!(thresholds.is_resource_limit_reached(0, 100));
+    }
 }
@@ -21,6 +21,14 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing the transactions sent by each address.
+    ///
+    /// The stage is opt-in and therefore not part of [`StageId::ALL`].
+    IndexSenderTransactions,
+    /// Optional stage indexing the contracts deployed with each code hash.
+    ///
+    /// The stage is opt-in and therefore not part of [`StageId::ALL`].
+    IndexContractDeployments,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -78,6 +86,8 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexSenderTransactions => "IndexSenderTransactions",
+            Self::IndexContractDeployments => "IndexContractDeployments",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -124,6 +134,8 @@ mod tests {
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
+        assert_eq!(StageId::IndexSenderTransactions.to_string(), "IndexSenderTransactions");
+        assert_eq!(StageId::IndexContractDeployments.to_string(), "IndexContractDeployments");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
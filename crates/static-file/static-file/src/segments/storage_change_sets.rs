@@ -0,0 +1,58 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{BlockNumberAddress, StaticFileStorageChangeSet, StorageBeforeTx},
+    tables,
+    transaction::DbTx,
+};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::StorageChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct StorageChangeSets;
+
+impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for StorageChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::StorageChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::StorageChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+        let mut changesets_walker = changesets_cursor
+            .walk_range(BlockNumberAddress::range(block_range.clone()))?
+            .peekable();
+
+        for block in block_range {
+            // Every block has a row, even if it didn't change any storage slots.
+            let mut change_set = StaticFileStorageChangeSet::default();
+            while let Some((BlockNumberAddress((_, address)), entry)) = changesets_walker
+                .next_if(|entry| {
+                    entry.as_ref().map_or(true, |(key, _)| key.block_number() == block)
+                })
+                .transpose()?
+            {
+                change_set.changes.push(StorageBeforeTx {
+                    address,
+                    key: entry.key,
+                    value: entry.value,
+                });
+            }
+
+            static_file_writer.append_storage_change_set(block, &change_set)?;
+        }
+
+        Ok(())
+    }
+}
@@ -111,6 +111,11 @@ where
     /// and a read-only database transaction from [`DatabaseProviderFactory`]. All segments are run
     /// in parallel.
     ///
+    /// Every segment is finalized on its own as soon as its data is copied, by committing its
+    /// static file writer, which also advances the highest static file block of the segment. A
+    /// failing segment therefore doesn't hold back the other segments, and the first error is
+    /// returned once all segments are done.
+    ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
     pub fn run(&self, targets: StaticFileTargets) -> StaticFileProducerResult {
@@ -140,28 +145,36 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_change_sets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_change_sets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
-        segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
-            let start = Instant::now();
+        let results = segments
+            .par_iter()
+            .map(|(segment, block_range)| -> ProviderResult<()> {
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
+                let start = Instant::now();
 
-            // Create a new database transaction on every segment to prevent long-lived read-only
-            // transactions
-            let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
-            segment.copy_to_static_files(provider,  block_range.clone())?;
+                // Create a new database transaction on every segment to prevent long-lived
+                // read-only transactions
+                let provider =
+                    self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
+                segment.copy_to_static_files(provider, block_range.clone())?;
 
-            let elapsed = start.elapsed(); // TODO(alexey): track in metrics
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
+                // Finalize the segment independently of the others. The writers of the segments
+                // are locked separately, so they can be committed concurrently.
+                self.provider.static_file_provider().latest_writer(segment.segment())?.commit()?;
 
-            Ok(())
-        })?;
+                let elapsed = start.elapsed(); // TODO(alexey): track in metrics
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
 
-        self.provider.static_file_provider().commit()?;
-        for (segment, block_range) in segments {
-            self.provider
-                .static_file_provider()
-                .update_index(segment.segment(), Some(*block_range.end()))?;
-        }
+                Ok(())
+            })
+            .collect::<Vec<_>>();
+        results.into_iter().collect::<ProviderResult<()>>()?;
 
         let elapsed = start.elapsed(); // TODO(alexey): track in metrics
         debug!(target: "static_file", ?targets, ?elapsed, "StaticFileProducer finished");
@@ -175,18 +188,26 @@ where
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
+    /// Changesets are only copied up to the [`StageId::Finish`] checkpoint, because the hashing
+    /// and history index stages that run after the execution still read them from the database.
+    ///
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints =
+            [StageId::Headers, StageId::Execution, StageId::Bodies, StageId::Finish]
+                .into_iter()
+                .map(|stage| {
+                    provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            account_change_sets: stages_checkpoints[3],
+            storage_change_sets: stages_checkpoints[3],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -208,9 +229,7 @@ where
                 self.get_static_file_target(highest_static_files.headers, finalized_block_number)
             }),
             // StaticFile receipts only if they're not pruned according to the user configuration
-            receipts: if self.prune_modes.receipts.is_none() &&
-                self.prune_modes.receipts_log_filter.is_empty()
-            {
+            receipts: if !self.prune_modes.has_receipts_pruning() {
                 finalized_block_numbers.receipts.and_then(|finalized_block_number| {
                     self.get_static_file_target(
                         highest_static_files.receipts,
@@ -226,6 +245,24 @@ where
                     finalized_block_number,
                 )
             }),
+            // Changesets are moved to static files only after they were migrated with
+            // `reth db migrate-change-sets`, which creates the first static files of the segments
+            account_change_sets: highest_static_files.account_change_sets.and(
+                finalized_block_numbers.account_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_change_sets,
+                        finalized_block_number,
+                    )
+                }),
+            ),
+            storage_change_sets: highest_static_files.storage_change_sets.and(
+                finalized_block_numbers.storage_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_change_sets,
+                        finalized_block_number,
+                    )
+                }),
+            ),
         };
 
         trace!(
@@ -324,6 +361,7 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -331,13 +369,19 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                ..Default::default()
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -345,6 +389,7 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -352,13 +397,19 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                ..Default::default()
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -366,6 +417,7 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -373,16 +425,52 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                ..Default::default()
+            }
+        );
+        assert_matches!(
+            static_file_producer.run(targets),
+            Err(ProviderError::BlockBodyIndicesNotFound(4))
+        );
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
             }
         );
+    }
+
+    #[test]
+    fn run_finalizes_segments_independently() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+
+        // Block 4 doesn't exist, so the transactions segment fails while the headers segment
+        // succeeds.
+        let targets = StaticFileTargets {
+            headers: Some(0..=3),
+            receipts: None,
+            transactions: Some(0..=4),
+            ..Default::default()
+        };
         assert_matches!(
             static_file_producer.run(targets),
             Err(ProviderError::BlockBodyIndicesNotFound(4))
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: None,
+                transactions: None,
+                ..Default::default()
+            }
         );
     }
 
@@ -410,6 +498,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -73,6 +73,8 @@ pub mod database_metrics;
 
 pub mod mock;
 
+pub mod stats;
+
 /// Table traits
 pub mod table;
 
@@ -25,8 +25,9 @@ pub use accounts::*;
 pub use blocks::*;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
-    AccountBeforeTx, ClientVersion, StaticFileBlockWithdrawals, StoredBlockBodyIndices,
-    StoredBlockWithdrawals,
+    AccountBeforeTx, ClientVersion, ContractDeployment, StaticFileAccountChangeSet,
+    StaticFileBlockWithdrawals, StaticFileStorageChangeSet, StorageBeforeTx,
+    StoredBlockBodyIndices, StoredBlockRequests, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
 
@@ -225,9 +226,13 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers<H>,
     StoredBlockWithdrawals,
+    StoredBlockRequests,
     StaticFileBlockWithdrawals,
+    StaticFileAccountChangeSet,
+    StaticFileStorageChangeSet,
     Bytecode,
     AccountBeforeTx,
+    ContractDeployment,
     TransactionSigned,
     CompactU256,
     StageCheckpoint,
@@ -347,6 +352,7 @@ mod tests {
         assert_eq!(StageCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageUnitCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
+        assert_eq!(StoredBlockRequests::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
 
@@ -367,6 +373,7 @@ mod tests {
         validate_bitflag_backwards_compat!(StageCheckpoint, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(StageUnitCheckpoint, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockBodyIndices, UnusedBits::Zero);
+        validate_bitflag_backwards_compat!(StoredBlockRequests, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockWithdrawals, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StorageHashingCheckpoint, UnusedBits::NotZero);
     }
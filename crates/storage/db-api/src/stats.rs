@@ -0,0 +1,172 @@
+//! Table-level storage statistics and growth tracking.
+
+use serde::{Deserialize, Serialize};
+
+/// Number of seconds in a day, used to normalize growth rates.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Storage statistics of a single database table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStats {
+    /// The name of the table.
+    pub name: String,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The number of branch pages.
+    pub branch_pages: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of overflow pages.
+    pub overflow_pages: usize,
+    /// The size of a single page in bytes.
+    pub page_size: usize,
+}
+
+impl TableStats {
+    /// Returns the total number of pages used by the table.
+    pub const fn total_pages(&self) -> usize {
+        self.branch_pages + self.leaf_pages + self.overflow_pages
+    }
+
+    /// Returns the total size of the table in bytes.
+    pub const fn size(&self) -> usize {
+        self.total_pages() * self.page_size
+    }
+}
+
+/// A snapshot of the storage statistics of all database tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageStats {
+    /// Unix timestamp in seconds at which the statistics were collected.
+    pub timestamp: u64,
+    /// The size of a single page in bytes.
+    pub page_size: usize,
+    /// The number of pages on the freelist.
+    pub freelist_pages: usize,
+    /// Statistics of all tables.
+    pub tables: Vec<TableStats>,
+}
+
+impl StorageStats {
+    /// Returns the statistics of the table with the given name.
+    pub fn table(&self, name: &str) -> Option<&TableStats> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+
+    /// Returns the total size of all tables in bytes.
+    pub fn tables_size(&self) -> usize {
+        self.tables.iter().map(TableStats::size).sum()
+    }
+
+    /// Returns the size of the freelist in bytes.
+    pub const fn freelist_size(&self) -> usize {
+        self.freelist_pages * self.page_size
+    }
+}
+
+/// Growth rate of a table, normalized to a day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableGrowth {
+    /// Change in the number of entries per day.
+    pub entries_per_day: f64,
+    /// Change in size in bytes per day.
+    pub bytes_per_day: f64,
+}
+
+/// A bounded history of [`StorageStats`] snapshots, used to compute growth rates over time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageStatsHistory {
+    /// The recorded snapshots, ordered by timestamp.
+    pub samples: Vec<StorageStats>,
+}
+
+impl StorageStatsHistory {
+    /// The minimum number of seconds between two recorded samples. A sample that is taken earlier
+    /// replaces the latest one.
+    pub const SAMPLE_INTERVAL: u64 = 60 * 60;
+
+    /// The number of seconds samples are retained for.
+    pub const RETENTION: u64 = 30 * 24 * 60 * 60;
+
+    /// Records a new snapshot and drops samples that are outside of the retention window.
+    pub fn record(&mut self, stats: StorageStats) {
+        if let Some(last) = self.samples.last() {
+            if stats.timestamp < last.timestamp {
+                return
+            }
+            if stats.timestamp - last.timestamp < Self::SAMPLE_INTERVAL && self.samples.len() > 1 {
+                self.samples.pop();
+            }
+        }
+
+        let cutoff = stats.timestamp.saturating_sub(Self::RETENTION);
+        self.samples.push(stats);
+        self.samples.retain(|sample| sample.timestamp >= cutoff);
+    }
+
+    /// Returns the daily growth rate of the given table, computed between the oldest and the latest
+    /// sample.
+    ///
+    /// Returns `None` if there are not enough samples or the table is missing from either of them.
+    pub fn daily_growth(&self, table: &str) -> Option<TableGrowth> {
+        let (first, last) = (self.samples.first()?, self.samples.last()?);
+        let elapsed = last.timestamp.checked_sub(first.timestamp).filter(|elapsed| *elapsed > 0)?;
+        let (before, after) = (first.table(table)?, last.table(table)?);
+
+        let days = elapsed as f64 / SECONDS_PER_DAY;
+        Some(TableGrowth {
+            entries_per_day: (after.entries as f64 - before.entries as f64) / days,
+            bytes_per_day: (after.size() as f64 - before.size() as f64) / days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(timestamp: u64, entries: usize, leaf_pages: usize) -> StorageStats {
+        StorageStats {
+            timestamp,
+            page_size: 4096,
+            freelist_pages: 0,
+            tables: vec![TableStats {
+                name: "Headers".to_string(),
+                entries,
+                branch_pages: 0,
+                leaf_pages,
+                overflow_pages: 0,
+                page_size: 4096,
+            }],
+        }
+    }
+
+    #[test]
+    fn daily_growth() {
+        let mut history = StorageStatsHistory::default();
+        history.record(stats(0, 100, 10));
+        assert_eq!(history.daily_growth("Headers"), None);
+
+        // two days later
+        history.record(stats(2 * 86_400, 300, 30));
+        let growth = history.daily_growth("Headers").unwrap();
+        assert_eq!(growth.entries_per_day, 100.0);
+        assert_eq!(growth.bytes_per_day, 10.0 * 4096.0);
+        assert_eq!(history.daily_growth("Bodies"), None);
+    }
+
+    #[test]
+    fn record_replaces_recent_and_prunes_old_samples() {
+        let mut history = StorageStatsHistory::default();
+        history.record(stats(0, 1, 1));
+        history.record(stats(10, 2, 1));
+        history.record(stats(20, 3, 1));
+        assert_eq!(history.samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![0, 20]);
+
+        history.record(stats(StorageStatsHistory::RETENTION + 10, 4, 1));
+        assert_eq!(
+            history.samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![20, StorageStatsHistory::RETENTION + 10]
+        );
+    }
+}
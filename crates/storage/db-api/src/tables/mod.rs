@@ -21,8 +21,8 @@ use crate::{
         accounts::BlockNumberAddress,
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
-        AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
-        StoredBlockBodyIndices, StoredBlockWithdrawals,
+        AccountBeforeTx, ClientVersion, CompactU256, ContractDeployment, IntegerList, ShardedKey,
+        StoredBlockBodyIndices, StoredBlockRequests, StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table, TableInfo},
 };
@@ -344,6 +344,16 @@ tables! {
         type Value = StoredBlockWithdrawals;
     }
 
+    /// Stores the [EIP-7685] requests produced by executing the block.
+    ///
+    /// Only blocks with at least one request have an entry.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    table BlockRequests {
+        type Key = BlockNumber;
+        type Value = StoredBlockRequests;
+    }
+
     /// Canonical only Stores the transaction body for canonical transactions.
     table Transactions<T = TransactionSigned> {
         type Key = TxNumber;
@@ -486,6 +496,36 @@ tables! {
         type SubKey = StoredNibblesSubKey;
     }
 
+    /// Stores the numbers of the transactions sent by each address.
+    ///
+    /// Shards are keyed the same way as [`AccountsHistory`], with the highest transaction number of
+    /// the shard instead of a block number. The table is only populated if the
+    /// `IndexSenderTransactions` stage is enabled.
+    table SenderTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the numbers of the transactions sent to each address.
+    ///
+    /// Contract creations are not included. Shards are keyed the same way as
+    /// [`SenderTransactions`]. The table is only populated if the `IndexSenderTransactions` stage
+    /// is enabled.
+    table RecipientTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the contracts deployed with each code hash and the block they were deployed in.
+    ///
+    /// A contract is deployed in a block if the account had no code before the block and has code
+    /// after it. The table is only populated if the `IndexContractDeployments` stage is enabled.
+    table ContractDeployments {
+        type Key = B256;
+        type Value = ContractDeployment;
+        type SubKey = Address;
+    }
+
     /// Stores the transaction sender for each canonical transaction.
     /// It is needed to speed up execution stage and allows fetching signer without doing
     /// transaction signed recovery
@@ -518,6 +558,20 @@ tables! {
         type Value = ClientVersion;
     }
 
+    /// Stores the schema migrations that have been applied to the database by schema version,
+    /// with the unix timestamp in seconds at which they completed.
+    table SchemaMigrations {
+        type Key = u64;
+        type Value = u64;
+    }
+
+    /// Stores arbitrary data to keep track of the progress of an interrupted schema migration by
+    /// schema version.
+    table SchemaMigrationProgresses {
+        type Key = u64;
+        type Value = Vec<u8>;
+    }
+
     /// Stores generic chain state info, like the last finalized block.
     table ChainState {
         type Key = ChainStateKey;
@@ -0,0 +1,332 @@
+//! Reth state dump export utility functions.
+
+use crate::init::{GenesisAccountWithAddress, StateRoot};
+use alloy_consensus::BlockHeader;
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
+use reth_primitives_traits::Account;
+use reth_provider::{
+    BlockNumReader, ChangeSetReader, DBProvider, HeaderProvider, ProviderError,
+    PruneCheckpointReader, StorageChangeSetReader,
+};
+use reth_prune_types::PruneSegment;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    io::{BufRead, Write},
+    ops::Bound,
+};
+use tracing::info;
+
+/// Number of exported accounts after which to log progress.
+const LOG_INTERVAL_ACCOUNTS: usize = 100_000;
+
+/// Progress of a partially written state dump file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateDumpProgress {
+    /// Length in bytes of the complete lines in the file. Everything after it is a partially
+    /// written line.
+    pub len: u64,
+    /// The state root of the state dump, if the first line is complete.
+    pub root: Option<B256>,
+    /// The address of the last complete account in the state dump.
+    pub last_address: Option<Address>,
+}
+
+/// Reads the progress of a partially written state dump file, so that the export can be resumed
+/// with [`export_state_dump`].
+pub fn read_state_dump_progress(mut reader: impl BufRead) -> eyre::Result<StateDumpProgress> {
+    let mut progress = StateDumpProgress::default();
+    let mut line = String::new();
+    let mut last_line = String::new();
+
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line)?;
+        if n == 0 || !line.ends_with('\n') {
+            break
+        }
+        progress.len += n as u64;
+
+        if progress.root.is_none() {
+            progress.root = Some(serde_json::from_str::<StateRoot>(&line)?.root);
+        } else {
+            std::mem::swap(&mut line, &mut last_line);
+        }
+    }
+
+    if !last_line.is_empty() {
+        let account: GenesisAccountWithAddress = serde_json::from_str(&last_line)?;
+        progress.last_address = Some(account.address);
+    }
+
+    Ok(progress)
+}
+
+/// Writes the state at the given block to a state dump, in the format read by
+/// [`init_from_state_dump`](crate::init::init_from_state_dump).
+///
+/// The first line is the state root of the block, every following line is an account with its
+/// code and storage, ordered by address. The state is read from the plain state tables, with the
+/// changes of all blocks after the given block reverted using the changesets.
+///
+/// If `resume_after` is set, the state root line is skipped and only the accounts with a higher
+/// address are written, which appends to a state dump that was interrupted after that account.
+///
+/// Returns the number of written accounts.
+pub fn export_state_dump<Provider>(
+    provider: &Provider,
+    block: BlockNumber,
+    mut writer: impl Write,
+    resume_after: Option<Address>,
+) -> eyre::Result<usize>
+where
+    Provider: DBProvider
+        + BlockNumReader
+        + HeaderProvider
+        + PruneCheckpointReader
+        + ChangeSetReader
+        + StorageChangeSetReader,
+{
+    let tip = provider.last_block_number()?;
+    if block > tip {
+        return Err(eyre::eyre!("Block {block} is above the latest block {tip}"))
+    }
+
+    // the changesets of all blocks after the exported block are needed to revert the state
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            if pruned > block {
+                return Err(ProviderError::StateAtBlockPruned(block).into())
+            }
+        }
+    }
+
+    let state_root = provider
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+        .state_root();
+
+    let (account_reverts, mut storage_reverts) = collect_reverts(provider, block + 1..=tip)?;
+
+    if resume_after.is_none() {
+        serde_json::to_writer(&mut writer, &StateRoot { root: state_root })?;
+        writer.write_all(b"\n")?;
+    }
+
+    let tx = provider.tx_ref();
+    let mut accounts_cursor = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut bytecodes_cursor = tx.cursor_read::<tables::Bytecodes>()?;
+
+    let start = resume_after.map_or(Bound::Unbounded, Bound::Excluded);
+    let mut plain_accounts = accounts_cursor.walk_range((start, Bound::Unbounded))?;
+    let mut reverted_accounts = account_reverts.range((start, Bound::Unbounded));
+
+    // merge the plain state with the reverted accounts, both are ordered by address
+    let mut plain = plain_accounts.next().transpose()?;
+    let mut reverted = reverted_accounts.next();
+    let mut exported = 0;
+    loop {
+        let (address, account) = match (plain, reverted) {
+            (None, None) => break,
+            (Some((plain_address, _)), Some((&address, &account))) if address <= plain_address => {
+                if address == plain_address {
+                    plain = plain_accounts.next().transpose()?;
+                }
+                reverted = reverted_accounts.next();
+                (address, account)
+            }
+            (None, Some((&address, &account))) => {
+                reverted = reverted_accounts.next();
+                (address, account)
+            }
+            (Some((address, account)), _) => {
+                plain = plain_accounts.next().transpose()?;
+                (address, Some(account))
+            }
+        };
+
+        // the account did not exist at the exported block
+        let Some(account) = account else { continue };
+
+        let mut storage = BTreeMap::new();
+        for entry in storage_cursor.walk_dup(Some(address), None)? {
+            let (_, entry) = entry?;
+            storage.insert(entry.key, entry.value);
+        }
+        for (key, value) in storage_reverts.remove(&address).unwrap_or_default() {
+            if value.is_zero() {
+                storage.remove(&key);
+            } else {
+                storage.insert(key, value);
+            }
+        }
+
+        let code = match account.bytecode_hash {
+            Some(code_hash) => Some(
+                bytecodes_cursor
+                    .seek_exact(code_hash)?
+                    .ok_or_else(|| {
+                        eyre::eyre!("Bytecode {code_hash} of account {address} not found")
+                    })?
+                    .1
+                    .original_bytes(),
+            ),
+            None => None,
+        };
+
+        let genesis_account = GenesisAccount {
+            nonce: Some(account.nonce),
+            balance: account.balance,
+            code,
+            storage: (!storage.is_empty()).then(|| {
+                storage.into_iter().map(|(key, value)| (key, B256::from(value))).collect()
+            }),
+            private_key: None,
+        };
+        serde_json::to_writer(
+            &mut writer,
+            &GenesisAccountWithAddress { genesis_account, address },
+        )?;
+        writer.write_all(b"\n")?;
+
+        exported += 1;
+        if exported.is_multiple_of(LOG_INTERVAL_ACCOUNTS) {
+            info!(target: "reth::cli", exported, %address, "Exported accounts");
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(exported)
+}
+
+/// Account states and storage slots as of before the given block range, as recorded in the
+/// changesets of the first block in the range that changed them.
+type StateReverts = (BTreeMap<Address, Option<Account>>, BTreeMap<Address, BTreeMap<B256, U256>>);
+
+/// Collects the account states and storage values from before the changes of the blocks in the
+/// given range.
+///
+/// The changesets are read one block at a time through the provider, so that only the reverts
+/// themselves are held in memory and changesets that were moved to static files are included.
+fn collect_reverts<Provider>(
+    provider: &Provider,
+    range: std::ops::RangeInclusive<BlockNumber>,
+) -> eyre::Result<StateReverts>
+where
+    Provider: ChangeSetReader + StorageChangeSetReader,
+{
+    // blocks are walked in ascending order, so the first entry of an account or slot holds the
+    // value at the start of the range
+    let mut accounts = BTreeMap::new();
+    let mut storages = BTreeMap::<Address, BTreeMap<B256, U256>>::new();
+    for block in range {
+        for before in provider.account_block_changeset(block)? {
+            accounts.entry(before.address).or_insert(before.info);
+        }
+
+        for (key, before) in provider.storage_changeset(block)? {
+            if let Entry::Vacant(slot) =
+                storages.entry(key.address()).or_default().entry(before.key)
+            {
+                slot.insert(before.value);
+            }
+        }
+    }
+
+    Ok((accounts, storages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use alloy_genesis::Genesis;
+    use reth_chainspec::{Chain, ChainSpec, EthChainSpec};
+    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+    use std::sync::Arc;
+
+    fn genesis_alloc() -> BTreeMap<Address, GenesisAccount> {
+        BTreeMap::from([
+            (
+                Address::with_last_byte(1),
+                GenesisAccount { nonce: Some(1), balance: U256::from(1), ..Default::default() },
+            ),
+            (
+                Address::with_last_byte(2),
+                GenesisAccount {
+                    nonce: Some(0),
+                    code: Some(vec![0x60, 0x00].into()),
+                    storage: Some(BTreeMap::from([(B256::with_last_byte(1), B256::random())])),
+                    ..Default::default()
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn export_genesis_state() {
+        let chain_spec = Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc: genesis_alloc(), ..Default::default() },
+            ..Default::default()
+        });
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        let provider = factory.provider().unwrap();
+
+        let mut dump = Vec::new();
+        assert_eq!(export_state_dump(&provider, 0, &mut dump, None).unwrap(), 2);
+
+        let progress = read_state_dump_progress(dump.as_slice()).unwrap();
+        assert_eq!(progress.len, dump.len() as u64);
+        assert_eq!(progress.root, Some(chain_spec.genesis_header().state_root));
+        assert_eq!(progress.last_address, Some(Address::with_last_byte(2)));
+
+        let accounts = dump
+            .split(|byte| *byte == b'\n')
+            .skip(1)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let account: GenesisAccountWithAddress = serde_json::from_slice(line).unwrap();
+                (account.address, account.genesis_account)
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(accounts, genesis_alloc());
+
+        // resuming after the first account appends only the second one
+        let mut resumed = dump[..dump.iter().position(|byte| *byte == b'\n').unwrap() + 1].to_vec();
+        let first = serde_json::to_vec(&GenesisAccountWithAddress {
+            genesis_account: genesis_alloc()[&Address::with_last_byte(1)].clone(),
+            address: Address::with_last_byte(1),
+        })
+        .unwrap();
+        resumed.extend_from_slice(&first);
+        resumed.push(b'\n');
+        export_state_dump(&provider, 0, &mut resumed, Some(Address::with_last_byte(1))).unwrap();
+        assert_eq!(resumed, dump);
+    }
+
+    #[test]
+    fn read_partial_state_dump() {
+        let root = B256::random();
+        let dump = format!("{{\"root\":\"{root}\"}}\n{{\"balance\":\"0x1\",\"add");
+        let progress = read_state_dump_progress(dump.as_bytes()).unwrap();
+        assert_eq!(
+            progress,
+            StateDumpProgress {
+                len: dump.find('\n').unwrap() as u64 + 1,
+                root: Some(root),
+                last_address: None
+            }
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use alloy_eips::eip4895::Withdrawals;
-use alloy_primitives::TxNumber;
+use alloy_primitives::{Bytes, TxNumber};
 use core::ops::Range;
 
 /// Total number of transactions.
@@ -77,6 +77,21 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Withdrawals,
 }
 
+/// The storage representation of the [EIP-7685] requests produced by executing a block.
+///
+/// Each request is the request type byte followed by the request data.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StoredBlockRequests {
+    /// The encoded block requests.
+    pub requests: Vec<Bytes>,
+}
+
 /// A storage representation of block withdrawals that is static file friendly. An inner `None`
 /// represents a pre-merge block.
 #[derive(Debug, Default, Eq, PartialEq, Clone)]
@@ -0,0 +1,269 @@
+use crate::AccountBeforeTx;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+
+/// Storage slot as it is saved in the storage changesets static file segment.
+///
+/// Unlike the database, which keys the storage changesets by block number and address, the static
+/// file segment keeps all changes of a block in a single row, so the address is part of the value.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Storage value before the transaction.
+    pub value: U256,
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        buf.put_slice(self.key.as_slice());
+        self.value.to_compact(buf) + 52
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        use bytes::Buf;
+        let address = Address::from_slice(&buf[..20]);
+        let key = B256::from_slice(&buf[20..52]);
+        buf.advance(52);
+
+        let (value, buf) = U256::from_compact(buf, len - 52);
+        (Self { address, key, value }, buf)
+    }
+}
+
+/// All account changes of a block, as stored in a row of the account changesets static file
+/// segment.
+///
+/// The row is encoded so that a single change can be looked up without decoding the whole row, see
+/// [`StaticFileAccountChangeSet::find`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct StaticFileAccountChangeSet {
+    /// The account changes of the block, ordered by address.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl StaticFileAccountChangeSet {
+    /// Looks up the change of the account in an encoded row by binary search, decoding only the
+    /// matching entry.
+    pub fn find(row: &[u8], address: &Address) -> Option<AccountBeforeTx> {
+        row::find(row, address.as_slice())
+    }
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for StaticFileAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        row::encode(&self.changes, buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = row::decode(buf, len);
+        (Self { changes }, buf)
+    }
+}
+
+/// All storage changes of a block, as stored in a row of the storage changesets static file
+/// segment.
+///
+/// The row is encoded so that a single change can be looked up without decoding the whole row, see
+/// [`StaticFileStorageChangeSet::find`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct StaticFileStorageChangeSet {
+    /// The storage changes of the block, ordered by address and key.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl StaticFileStorageChangeSet {
+    /// Looks up the change of the storage slot in an encoded row by binary search, decoding only
+    /// the matching entry.
+    pub fn find(row: &[u8], address: &Address, key: &B256) -> Option<StorageBeforeTx> {
+        let mut search_key = [0u8; 52];
+        search_key[..20].copy_from_slice(address.as_slice());
+        search_key[20..].copy_from_slice(key.as_slice());
+        row::find(row, &search_key)
+    }
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for StaticFileStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        row::encode(&self.changes, buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = row::decode(buf, len);
+        (Self { changes }, buf)
+    }
+}
+
+/// Encoding of a changeset row.
+///
+/// The row starts with the number of entries and the end offset of every entry, followed by the
+/// compact encoded entries. Entries are sorted and their encoding starts with their sort key
+/// (address, or address and storage key), so an entry can be found by binary search over the
+/// offsets, comparing the raw key bytes.
+#[cfg(any(test, feature = "reth-codec"))]
+mod row {
+    use alloc::vec::Vec;
+    use bytes::Buf;
+    use reth_codecs::Compact;
+
+    /// Size of the entry count and of each entry offset.
+    const WORD: usize = 4;
+
+    pub(super) fn encode<T, B>(entries: &[T], buf: &mut B) -> usize
+    where
+        T: Compact,
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut data = Vec::new();
+        let mut offsets = Vec::with_capacity(entries.len());
+        for entry in entries {
+            entry.to_compact(&mut data);
+            offsets.push(data.len() as u32);
+        }
+
+        buf.put_u32(entries.len() as u32);
+        for offset in &offsets {
+            buf.put_u32(*offset);
+        }
+        buf.put_slice(&data);
+
+        WORD + WORD * offsets.len() + data.len()
+    }
+
+    pub(super) fn decode<T: Compact>(mut buf: &[u8], len: usize) -> (Vec<T>, &[u8]) {
+        if len == 0 {
+            return (Vec::new(), buf)
+        }
+
+        let count = buf.get_u32() as usize;
+        let offsets = (0..count).map(|_| buf.get_u32() as usize).collect::<Vec<_>>();
+
+        let mut entries = Vec::with_capacity(count);
+        let mut start = 0;
+        for end in offsets {
+            let (entry, _) = T::from_compact(&buf[start..end], end - start);
+            entries.push(entry);
+            start = end;
+        }
+        buf.advance(start);
+
+        (entries, buf)
+    }
+
+    pub(super) fn find<T: Compact>(row: &[u8], key: &[u8]) -> Option<T> {
+        if row.len() < WORD {
+            return None
+        }
+
+        let count = u32::from_be_bytes(row[..WORD].try_into().ok()?) as usize;
+        let data = &row[WORD + WORD * count..];
+        let end_offset = |index: usize| {
+            let at = WORD + WORD * index;
+            u32::from_be_bytes(row[at..at + WORD].try_into().expect("word size")) as usize
+        };
+        let entry = |index: usize| {
+            let start = if index == 0 { 0 } else { end_offset(index - 1) };
+            &data[start..end_offset(index)]
+        };
+
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let encoded = entry(mid);
+            match encoded[..key.len()].cmp(key) {
+                core::cmp::Ordering::Less => low = mid + 1,
+                core::cmp::Ordering::Greater => high = mid,
+                core::cmp::Ordering::Equal => {
+                    return Some(T::from_compact(encoded, encoded.len()).0)
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_codecs::Compact;
+    use reth_primitives_traits::Account;
+
+    #[test]
+    fn find_account_change() {
+        let changes = (1u8..=9)
+            .map(|byte| AccountBeforeTx {
+                address: Address::with_last_byte(byte * 2),
+                info: (byte % 2 == 0).then(|| Account { nonce: byte as u64, ..Default::default() }),
+            })
+            .collect::<Vec<_>>();
+        let mut row = Vec::new();
+        StaticFileAccountChangeSet { changes: changes.clone() }.to_compact(&mut row);
+
+        for change in &changes {
+            assert_eq!(
+                StaticFileAccountChangeSet::find(&row, &change.address),
+                Some(change.clone())
+            );
+        }
+        assert_eq!(StaticFileAccountChangeSet::find(&row, &Address::with_last_byte(3)), None);
+        assert_eq!(StaticFileAccountChangeSet::find(&row, &Address::ZERO), None);
+        assert_eq!(StaticFileAccountChangeSet::find(&row, &Address::repeat_byte(0xff)), None);
+
+        let mut empty = Vec::new();
+        StaticFileAccountChangeSet::default().to_compact(&mut empty);
+        assert_eq!(StaticFileAccountChangeSet::find(&empty, &Address::ZERO), None);
+    }
+
+    #[test]
+    fn find_storage_change() {
+        let address = Address::with_last_byte(1);
+        let changes = [(address, 1u8), (address, 4), (Address::with_last_byte(2), 2)]
+            .into_iter()
+            .map(|(address, key)| StorageBeforeTx {
+                address,
+                key: B256::with_last_byte(key),
+                value: U256::from(key),
+            })
+            .collect::<Vec<_>>();
+        let mut row = Vec::new();
+        StaticFileStorageChangeSet { changes: changes.clone() }.to_compact(&mut row);
+
+        for change in &changes {
+            assert_eq!(
+                StaticFileStorageChangeSet::find(&row, &change.address, &change.key),
+                Some(*change)
+            );
+        }
+        assert_eq!(
+            StaticFileStorageChangeSet::find(&row, &address, &B256::with_last_byte(2)),
+            None
+        );
+    }
+}
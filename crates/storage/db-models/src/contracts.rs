@@ -0,0 +1,37 @@
+use alloy_primitives::{Address, BlockNumber};
+
+/// Deployment of a contract as it is saved in the database, keyed by the hash of its code.
+///
+/// [`Address`] is the subkey.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct ContractDeployment {
+    /// Address of the contract. Acts as `DupSort::SubKey`.
+    pub address: Address,
+    /// Number of the block the contract was deployed in.
+    pub block_number: BlockNumber,
+}
+
+// NOTE: The subkey is encoded in full, so that values can be fetched with `seek_by_key_subkey`.
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for ContractDeployment {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        reth_codecs::Compact::to_compact(&self.block_number, buf) + 20
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        use bytes::Buf;
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        let (block_number, buf) =
+            <BlockNumber as reth_codecs::Compact>::from_compact(buf, len - 20);
+        (Self { address, block_number }, buf)
+    }
+}
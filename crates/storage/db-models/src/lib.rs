@@ -15,10 +15,20 @@ extern crate alloc;
 pub mod accounts;
 pub use accounts::AccountBeforeTx;
 
+/// Changesets
+pub mod changesets;
+pub use changesets::{StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx};
+
 /// Blocks
 pub mod blocks;
-pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockWithdrawals};
+pub use blocks::{
+    StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockRequests, StoredBlockWithdrawals,
+};
 
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Contracts
+pub mod contracts;
+pub use contracts::ContractDeployment;
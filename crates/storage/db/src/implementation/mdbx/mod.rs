@@ -7,13 +7,13 @@ use crate::{
     utils::default_page_size,
     DatabaseError, TableSet,
 };
-use eyre::Context;
 use metrics::{gauge, Label};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
     database_metrics::DatabaseMetrics,
     models::ClientVersion,
+    stats::{StorageStats, TableStats},
     transaction::{DbTx, DbTxMut},
 };
 use reth_libmdbx::{
@@ -227,54 +227,27 @@ impl DatabaseMetrics for DatabaseEnv {
     fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
         let mut metrics = Vec::new();
 
-        let _ = self
-            .view(|tx| {
-                for table in Tables::ALL.iter().map(Tables::name) {
-                    let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                    let stats = tx
-                        .inner
-                        .db_stat(&table_db)
-                        .wrap_err(format!("Could not find table: {table}"))?;
-
-                    let page_size = stats.page_size() as usize;
-                    let leaf_pages = stats.leaf_pages();
-                    let branch_pages = stats.branch_pages();
-                    let overflow_pages = stats.overflow_pages();
-                    let num_pages = leaf_pages + branch_pages + overflow_pages;
-                    let table_size = page_size * num_pages;
-                    let entries = stats.entries();
-
-                    metrics.push((
-                        "db.table_size",
-                        table_size as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        leaf_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "leaf")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        branch_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "branch")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        overflow_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "overflow")],
-                    ));
-                    metrics.push((
-                        "db.table_entries",
-                        entries as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                }
-
-                Ok::<(), eyre::Report>(())
-            })
-            .map_err(|error| error!(%error, "Failed to read db table stats"));
+        if let Ok(stats) =
+            self.storage_stats().map_err(|error| error!(%error, "Failed to read db table stats"))
+        {
+            for table in stats.tables {
+                let labels = |kind: Option<&'static str>| {
+                    let mut labels = vec![Label::new("table", table.name.clone())];
+                    labels.extend(kind.map(|kind| Label::new("type", kind)));
+                    labels
+                };
+
+                metrics.push(("db.table_size", table.size() as f64, labels(None)));
+                metrics.push(("db.table_pages", table.leaf_pages as f64, labels(Some("leaf"))));
+                metrics.push(("db.table_pages", table.branch_pages as f64, labels(Some("branch"))));
+                metrics.push((
+                    "db.table_pages",
+                    table.overflow_pages as f64,
+                    labels(Some("overflow")),
+                ));
+                metrics.push(("db.table_entries", table.entries as f64, labels(None)));
+            }
+        }
 
         if let Ok(freelist) =
             self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist"))
@@ -480,6 +453,36 @@ impl DatabaseEnv {
         Ok(())
     }
 
+    /// Collects the storage statistics of all tables defined in [`Tables`] and the freelist.
+    pub fn storage_stats(&self) -> Result<StorageStats, DatabaseError> {
+        let tx = self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
+
+        let mut tables = Vec::with_capacity(Tables::ALL.len());
+        for table in Tables::ALL.iter().map(Tables::name) {
+            let table_db = tx.open_db(Some(table)).map_err(|e| DatabaseError::Open(e.into()))?;
+            let stats = tx.db_stat(&table_db).map_err(|e| DatabaseError::Stats(e.into()))?;
+
+            tables.push(TableStats {
+                name: table.to_string(),
+                entries: stats.entries(),
+                branch_pages: stats.branch_pages(),
+                leaf_pages: stats.leaf_pages(),
+                overflow_pages: stats.overflow_pages(),
+                page_size: stats.page_size() as usize,
+            });
+        }
+
+        let freelist_pages = self.freelist().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let page_size = self.stat().map_err(|e| DatabaseError::Stats(e.into()))?.page_size();
+
+        Ok(StorageStats {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            page_size: page_size as usize,
+            freelist_pages,
+            tables,
+        })
+    }
+
     /// Records version that accesses the database with write privileges.
     pub fn record_client_version(&self, version: ClientVersion) -> Result<(), DatabaseError> {
         if version.is_empty() {
@@ -584,6 +587,27 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_storage_stats() {
+        let env = create_test_db(DatabaseEnvKind::RW);
+
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Headers>(1, Header::default()).expect(ERROR_PUT);
+        tx.put::<Headers>(2, Header::default()).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let stats = env.storage_stats().unwrap();
+        assert_eq!(stats.tables.len(), Tables::ALL.len());
+
+        let headers = stats.table(Tables::Headers.name()).unwrap();
+        assert_eq!(headers.entries, 2);
+        assert!(headers.size() > 0);
+        assert_eq!(
+            stats.table(Tables::BlockBodyIndices.name()).map(|table| table.entries),
+            Some(0)
+        );
+    }
+
     #[test]
     fn db_dup_cursor_delete_first() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -1,6 +1,10 @@
 //! Helper functions for initializing and opening a database.
 
-use crate::{is_database_empty, TableSet, Tables};
+use crate::{
+    is_database_empty,
+    migration::{Migration, Migrator},
+    TableSet, Tables,
+};
 use eyre::Context;
 use std::path::Path;
 
@@ -29,9 +33,26 @@ pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Resu
 }
 
 /// Opens up an existing database or creates a new one at the specified path. Creates tables defined
-/// in [`Tables`] if necessary. Read/Write mode.
+/// in [`Tables`] if necessary and applies all pending [`migrations`]. Read/Write mode.
 pub fn init_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result<DatabaseEnv> {
-    init_db_for::<P, Tables>(path, args)
+    let is_new = is_database_empty(path.as_ref());
+    let db = init_db_for::<P, Tables>(path, args)?;
+
+    let migrator = Migrator::new(migrations())?;
+    if is_new {
+        migrator.mark_applied(&db)?;
+    } else {
+        migrator.run(&db)?;
+    }
+
+    Ok(db)
+}
+
+/// Returns the schema migrations of the database, ordered by schema version.
+///
+/// New migrations have to be appended with the next schema version.
+pub fn migrations() -> Vec<Box<dyn Migration<DatabaseEnv>>> {
+    Vec::new()
 }
 
 /// Opens up an existing database or creates a new one at the specified path. Creates tables defined
@@ -0,0 +1,297 @@
+//! Database schema migrations.
+//!
+//! Changes to the data model that can be applied to an existing database, e.g. re-encoding the
+//! values of a table or populating a new index, are implemented as a [`Migration`]. Migrations are
+//! applied in order of their schema version by the [`Migrator`] when the database is opened. The
+//! applied migrations are recorded in [`tables::SchemaMigrations`], so the schema version of a
+//! database is the version of the last applied migration.
+//!
+//! Large migrations are split into steps. Each step is committed together with its progress in
+//! [`tables::SchemaMigrationProgresses`], so an interrupted migration resumes from the last
+//! committed step.
+
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::DatabaseError;
+use reth_tracing::tracing::info;
+use std::{
+    fmt::Debug,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The outcome of a single [`Migration::step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// The migration is not finished yet, the next step is called with the given progress.
+    Progress(Vec<u8>),
+    /// The migration is finished.
+    Done,
+}
+
+/// A change to the data model of the database.
+pub trait Migration<DB: Database>: Debug + Send + Sync {
+    /// The schema version of the database after this migration was applied.
+    ///
+    /// Versions start at `1` and have to be consecutive.
+    fn version(&self) -> u64;
+
+    /// A short description of the migration.
+    fn description(&self) -> &'static str;
+
+    /// Executes the next step of the migration.
+    ///
+    /// `progress` is the progress returned by the previous step, or `None` for the first step. The
+    /// transaction is committed after each step, so steps should be limited in size.
+    fn step(
+        &self,
+        tx: &DB::TXMut,
+        progress: Option<Vec<u8>>,
+    ) -> Result<MigrationStep, DatabaseError>;
+}
+
+/// Error returned by the [`Migrator`].
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// The migrations are not ordered by consecutive schema versions.
+    #[error("expected migration for schema version {expected}, found {version}")]
+    UnorderedMigration {
+        /// The expected schema version.
+        expected: u64,
+        /// The schema version of the migration.
+        version: u64,
+    },
+    /// The database was migrated by a newer client.
+    #[error(
+        "database schema version (v{version}) is newer than the latest supported schema version (v{latest})"
+    )]
+    UnsupportedVersion {
+        /// The schema version of the database.
+        version: u64,
+        /// The latest schema version known to this client.
+        latest: u64,
+    },
+    /// Database error.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+/// Applies [`Migration`]s to a database.
+#[derive(Debug)]
+pub struct Migrator<DB: Database> {
+    /// The migrations, ordered by schema version.
+    migrations: Vec<Box<dyn Migration<DB>>>,
+}
+
+impl<DB: Database> Default for Migrator<DB> {
+    fn default() -> Self {
+        Self { migrations: Vec::new() }
+    }
+}
+
+impl<DB: Database> Migrator<DB> {
+    /// Creates a new [`Migrator`] with the given migrations, ordered by schema version.
+    pub fn new(migrations: Vec<Box<dyn Migration<DB>>>) -> Result<Self, MigrationError> {
+        for (expected, migration) in (1..).zip(&migrations) {
+            if migration.version() != expected {
+                return Err(MigrationError::UnorderedMigration {
+                    expected,
+                    version: migration.version(),
+                })
+            }
+        }
+        Ok(Self { migrations })
+    }
+
+    /// Returns the latest schema version, i.e. the version of the last migration.
+    pub fn latest_version(&self) -> u64 {
+        self.migrations.len() as u64
+    }
+
+    /// Returns the schema version of the database.
+    pub fn schema_version(&self, db: &DB) -> Result<u64, MigrationError> {
+        let tx = db.tx()?;
+        let version =
+            tx.cursor_read::<tables::SchemaMigrations>()?.last()?.map(|(version, _)| version);
+        tx.commit()?;
+        Ok(version.unwrap_or_default())
+    }
+
+    /// Marks all migrations as applied without executing them.
+    ///
+    /// This should be used for newly created databases, which already have the latest schema.
+    pub fn mark_applied(&self, db: &DB) -> Result<(), MigrationError> {
+        let version = self.schema_version(db)?;
+        let tx = db.tx_mut()?;
+        for migration in self.pending(version)? {
+            tx.put::<tables::SchemaMigrations>(migration.version(), timestamp())?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Applies all migrations the database is missing and returns the new schema version.
+    ///
+    /// A migration that was interrupted before is resumed from its last committed step.
+    pub fn run(&self, db: &DB) -> Result<u64, MigrationError> {
+        let version = self.schema_version(db)?;
+        for migration in self.pending(version)? {
+            let version = migration.version();
+            info!(target: "reth::db", version, description = migration.description(), "Applying database migration");
+
+            let mut progress =
+                db.view(|tx| tx.get::<tables::SchemaMigrationProgresses>(version))??;
+            if progress.is_some() {
+                info!(target: "reth::db", version, "Resuming interrupted database migration");
+            }
+
+            loop {
+                let tx = db.tx_mut()?;
+                match migration.step(&tx, progress.take())? {
+                    MigrationStep::Progress(next) => {
+                        tx.put::<tables::SchemaMigrationProgresses>(version, next.clone())?;
+                        tx.commit()?;
+                        progress = Some(next);
+                    }
+                    MigrationStep::Done => {
+                        tx.delete::<tables::SchemaMigrationProgresses>(version, None)?;
+                        tx.put::<tables::SchemaMigrations>(version, timestamp())?;
+                        tx.commit()?;
+                        break
+                    }
+                }
+            }
+
+            info!(target: "reth::db", version, "Applied database migration");
+        }
+
+        Ok(self.latest_version())
+    }
+
+    /// Returns the migrations that have not been applied to a database with the given schema
+    /// version.
+    fn pending(&self, version: u64) -> Result<&[Box<dyn Migration<DB>>], MigrationError> {
+        self.migrations.get(version as usize..).ok_or_else(|| MigrationError::UnsupportedVersion {
+            version,
+            latest: self.latest_version(),
+        })
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(all(test, feature = "mdbx"))]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_rw_db, DatabaseEnv};
+    use reth_db_api::database::Database;
+    use std::sync::Arc;
+
+    type TestDb = Arc<crate::test_utils::TempDatabase<DatabaseEnv>>;
+
+    /// Writes the canonical hashes of blocks `0..5`, two blocks per step, and fails once at the
+    /// configured block.
+    #[derive(Debug)]
+    struct BackfillHashes {
+        version: u64,
+        fail_at: Option<u64>,
+    }
+
+    impl Migration<TestDb> for BackfillHashes {
+        fn version(&self) -> u64 {
+            self.version
+        }
+
+        fn description(&self) -> &'static str {
+            "backfill canonical hashes"
+        }
+
+        fn step(
+            &self,
+            tx: &<TestDb as Database>::TXMut,
+            progress: Option<Vec<u8>>,
+        ) -> Result<MigrationStep, DatabaseError> {
+            let start = progress.map(|progress| progress[0] as u64).unwrap_or_default();
+            for block in start..(start + 2).min(5) {
+                if self.fail_at == Some(block) {
+                    return Err(DatabaseError::Other("interrupted".to_string()))
+                }
+                tx.put::<tables::CanonicalHeaders>(block, Default::default())?;
+            }
+
+            let next = start + 2;
+            if next >= 5 {
+                return Ok(MigrationStep::Done)
+            }
+            Ok(MigrationStep::Progress(vec![next as u8]))
+        }
+    }
+
+    fn migration(version: u64, fail_at: Option<u64>) -> Box<dyn Migration<TestDb>> {
+        Box::new(BackfillHashes { version, fail_at })
+    }
+
+    fn hashes(db: &TestDb) -> usize {
+        db.view(|tx| tx.entries::<tables::CanonicalHeaders>()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn resumes_interrupted_migration() {
+        let db = create_test_rw_db();
+
+        let migrator = Migrator::new(vec![migration(1, Some(3))]).unwrap();
+        assert!(migrator.run(&db).is_err());
+        assert_eq!(migrator.schema_version(&db).unwrap(), 0);
+        // the first step was committed
+        assert_eq!(hashes(&db), 2);
+        assert_eq!(
+            db.view(|tx| tx.get::<tables::SchemaMigrationProgresses>(1)).unwrap().unwrap(),
+            Some(vec![2])
+        );
+
+        let migrator = Migrator::new(vec![migration(1, None)]).unwrap();
+        assert_eq!(migrator.run(&db).unwrap(), 1);
+        assert_eq!(migrator.schema_version(&db).unwrap(), 1);
+        assert_eq!(hashes(&db), 5);
+        assert_eq!(
+            db.view(|tx| tx.get::<tables::SchemaMigrationProgresses>(1)).unwrap().unwrap(),
+            None
+        );
+
+        // applied migrations are not executed again
+        let migrator = Migrator::new(vec![migration(1, Some(0)), migration(2, None)]).unwrap();
+        assert_eq!(migrator.run(&db).unwrap(), 2);
+        assert_eq!(migrator.schema_version(&db).unwrap(), 2);
+    }
+
+    #[test]
+    fn mark_applied() {
+        let db = create_test_rw_db();
+
+        let migrator = Migrator::new(vec![migration(1, None), migration(2, None)]).unwrap();
+        migrator.mark_applied(&db).unwrap();
+        assert_eq!(migrator.schema_version(&db).unwrap(), 2);
+        assert_eq!(hashes(&db), 0);
+
+        // the database is newer than the client
+        let migrator = Migrator::new(vec![migration(1, None)]).unwrap();
+        assert!(matches!(
+            migrator.run(&db),
+            Err(MigrationError::UnsupportedVersion { version: 2, latest: 1 })
+        ));
+    }
+
+    #[test]
+    fn unordered_migrations() {
+        assert!(matches!(
+            Migrator::new(vec![migration(1, None), migration(3, None)]),
+            Err(MigrationError::UnorderedMigration { expected: 2, version: 3 })
+        ));
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
     HeaderTerminalDifficulties,
 };
 use alloy_primitives::BlockHash;
-use reth_db_api::table::Table;
+use reth_db_api::{
+    models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
+    table::Table,
+};
 
 // HEADER MASKS
 add_static_file_mask! {
@@ -42,3 +45,13 @@ add_static_file_mask! {
     #[doc = "Mask for selecting a single transaction from Transactions static file segment"]
     TransactionMask<T>, T, 0b1
 }
+
+// CHANGESET MASKS
+add_static_file_mask! {
+    #[doc = "Mask for selecting the account changes of a block from AccountChangeSets static file segment"]
+    AccountChangeSetMask, StaticFileAccountChangeSet, 0b1
+}
+add_static_file_mask! {
+    #[doc = "Mask for selecting the storage changes of a block from StorageChangeSets static file segment"]
+    StorageChangeSetMask, StaticFileStorageChangeSet, 0b1
+}
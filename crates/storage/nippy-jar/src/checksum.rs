@@ -0,0 +1,165 @@
+use crate::NippyJarError;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+/// Size of a checksummed chunk of a file in bytes.
+///
+/// Files are checksummed in chunks, so that appending to a file only requires hashing its last
+/// chunk again.
+const CHECKSUM_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Checksum manifest of the data and offsets files of a [`NippyJar`](crate::NippyJar).
+///
+/// It's kept up to date by the [`NippyJarWriter`](crate::NippyJarWriter) on every commit and can
+/// be used to detect silent disk corruption with
+/// [`NippyJar::verify_checksums`](crate::NippyJar::verify_checksums).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NippyJarChecksums {
+    /// Checksums of the data file.
+    data: FileChecksums,
+    /// Checksums of the offsets file.
+    offsets: FileChecksums,
+}
+
+impl NippyJarChecksums {
+    /// Updates the checksums to the current contents of the data and offsets files.
+    ///
+    /// Only the chunks after the length of the files at the previous update are hashed, since the
+    /// files are only ever appended to or truncated.
+    pub(crate) fn update(&mut self, data: &Path, offsets: &Path) -> Result<(), NippyJarError> {
+        self.data.update(data)?;
+        self.offsets.update(offsets)?;
+        Ok(())
+    }
+
+    /// Discards the checksums of any bytes past the current length of the data and offsets files,
+    /// so they're hashed again on the next update.
+    ///
+    /// This is necessary if the files might have been truncated since the last update.
+    pub(crate) fn truncate(&mut self, data: &Path, offsets: &Path) -> Result<(), NippyJarError> {
+        self.data.truncate(reth_fs_util::metadata(data)?.len());
+        self.offsets.truncate(reth_fs_util::metadata(offsets)?.len());
+        Ok(())
+    }
+
+    /// Verifies the contents of the data and offsets files against the checksums.
+    pub(crate) fn verify(&self, data: &Path, offsets: &Path) -> Result<(), NippyJarError> {
+        self.data.verify(data)?;
+        self.offsets.verify(offsets)
+    }
+}
+
+/// Checksums of the chunks of a file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct FileChecksums {
+    /// Length of the file in bytes.
+    len: u64,
+    /// CRC32 checksum of each chunk of the file, the last chunk can be partial.
+    chunks: Vec<u32>,
+}
+
+impl FileChecksums {
+    fn update(&mut self, path: &Path) -> Result<(), NippyJarError> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        // Hash the last known chunk again, since it might have been partial
+        let first_chunk = self.len.min(len) / CHECKSUM_CHUNK_SIZE;
+        self.chunks.truncate(first_chunk as usize);
+        file.seek(SeekFrom::Start(first_chunk * CHECKSUM_CHUNK_SIZE))?;
+
+        let mut buf = Vec::with_capacity(CHECKSUM_CHUNK_SIZE as usize);
+        for _ in first_chunk..len.div_ceil(CHECKSUM_CHUNK_SIZE) {
+            buf.clear();
+            (&mut file).take(CHECKSUM_CHUNK_SIZE).read_to_end(&mut buf)?;
+            self.chunks.push(crc32fast::hash(&buf));
+        }
+        self.len = len;
+
+        Ok(())
+    }
+
+    fn truncate(&mut self, len: u64) {
+        if len < self.len {
+            self.len = len;
+            self.chunks.truncate(len.div_ceil(CHECKSUM_CHUNK_SIZE) as usize);
+        }
+    }
+
+    /// Verifies the first `self.len` bytes of the file.
+    ///
+    /// Any bytes past it are ignored, since they might belong to an append that wasn't committed
+    /// yet.
+    fn verify(&self, path: &Path) -> Result<(), NippyJarError> {
+        let mut file = File::open(path)?;
+
+        let mut buf = Vec::with_capacity(CHECKSUM_CHUNK_SIZE as usize);
+        for (index, checksum) in self.chunks.iter().enumerate() {
+            let offset = index as u64 * CHECKSUM_CHUNK_SIZE;
+            buf.clear();
+            (&mut file).take(CHECKSUM_CHUNK_SIZE.min(self.len - offset)).read_to_end(&mut buf)?;
+            if crc32fast::hash(&buf) != *checksum {
+                return Err(NippyJarError::ChecksumMismatch { path: path.to_path_buf(), offset })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::OpenOptions, io::Write};
+
+    #[test]
+    fn update_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        let mut file = File::create(&path).unwrap();
+
+        let mut checksums = FileChecksums::default();
+        let chunk = vec![1u8; CHECKSUM_CHUNK_SIZE as usize];
+
+        // Appends one and a half chunks
+        file.write_all(&chunk).unwrap();
+        file.write_all(&chunk[..chunk.len() / 2]).unwrap();
+        checksums.update(&path).unwrap();
+        assert_eq!(checksums.chunks.len(), 2);
+        checksums.verify(&path).unwrap();
+
+        // Completes the partial chunk
+        file.write_all(&chunk[..chunk.len() / 2]).unwrap();
+        checksums.update(&path).unwrap();
+        let mut expected = FileChecksums::default();
+        expected.update(&path).unwrap();
+        assert_eq!(checksums, expected);
+
+        // Uncommitted appends are ignored
+        file.write_all(&chunk).unwrap();
+        checksums.verify(&path).unwrap();
+
+        // Truncates into the first chunk and appends again
+        file.set_len(10).unwrap();
+        file.seek(SeekFrom::End(0)).unwrap();
+        checksums.truncate(10);
+        file.write_all(&[2; 10]).unwrap();
+        checksums.update(&path).unwrap();
+        let mut expected = FileChecksums::default();
+        expected.update(&path).unwrap();
+        assert_eq!(checksums, expected);
+
+        // Corrupts a byte
+        let mut file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.seek(SeekFrom::Start(5)).unwrap();
+        file.write_all(&[0]).unwrap();
+        assert!(matches!(
+            checksums.verify(&path),
+            Err(NippyJarError::ChecksumMismatch { offset: 0, .. })
+        ));
+    }
+}
@@ -92,4 +92,17 @@ pub enum NippyJarError {
     /// A specified file is missing.
     #[error("Missing file: {}", .0.display())]
     MissingFile(PathBuf),
+
+    /// The checksum manifest of the jar is missing.
+    #[error("Missing checksum manifest: {}", .0.display())]
+    MissingChecksums(PathBuf),
+
+    /// The contents of a file don't match its checksum.
+    #[error("checksum mismatch in {} at byte offset {offset}", path.display())]
+    ChecksumMismatch {
+        /// The path of the corrupted file.
+        path: PathBuf,
+        /// The offset of the corrupted chunk of the file.
+        offset: u64,
+    },
 }
@@ -51,6 +51,9 @@ pub use writer::NippyJarWriter;
 mod consistency;
 pub use consistency::NippyJarChecker;
 
+mod checksum;
+pub use checksum::NippyJarChecksums;
+
 /// The version number of the Nippy Jar format.
 const NIPPY_JAR_VERSION: usize = 1;
 /// The file extension used for index files.
@@ -59,6 +62,8 @@ const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
+/// The file extension used for checksum manifest files.
+pub const CHECKSUMS_FILE_EXTENSION: &str = "sum";
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -232,13 +237,46 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.path.with_extension(CONFIG_FILE_EXTENSION)
     }
 
+    /// Returns the path for the checksum manifest file
+    pub fn checksums_path(&self) -> PathBuf {
+        self.path.with_extension(CHECKSUMS_FILE_EXTENSION)
+    }
+
+    /// Loads the checksum manifest of the data and offsets files.
+    ///
+    /// Returns [`None`] if the jar was created without a checksum manifest.
+    pub fn load_checksums(&self) -> Result<Option<NippyJarChecksums>, NippyJarError> {
+        let path = self.checksums_path();
+        if !path.exists() {
+            return Ok(None)
+        }
+        let file = File::open(&path).map_err(|err| reth_fs_util::FsPathError::open(err, path))?;
+        Ok(Some(bincode::deserialize_from(file)?))
+    }
+
+    /// Verifies the data and offsets files against the checksum manifest, to detect silent disk
+    /// corruption.
+    ///
+    /// Returns [`NippyJarError::MissingChecksums`] if the jar was created without a checksum
+    /// manifest.
+    pub fn verify_checksums(&self) -> Result<(), NippyJarError> {
+        let checksums = self
+            .load_checksums()?
+            .ok_or_else(|| NippyJarError::MissingChecksums(self.checksums_path()))?;
+        checksums.verify(self.data_path(), &self.offsets_path())
+    }
+
     /// Deletes from disk this [`NippyJar`] alongside every satellite file.
     pub fn delete(self) -> Result<(), NippyJarError> {
         // TODO(joshie): ensure consistency on unexpected shutdown
 
-        for path in
-            [self.data_path().into(), self.index_path(), self.offsets_path(), self.config_path()]
-        {
+        for path in [
+            self.data_path().into(),
+            self.index_path(),
+            self.offsets_path(),
+            self.config_path(),
+            self.checksums_path(),
+        ] {
             if path.exists() {
                 debug!(target: "nippy-jar", ?path, "Removing file.");
                 reth_fs_util::remove_file(path)?;
@@ -259,6 +297,13 @@ impl<H: NippyJarHeader> NippyJar<H> {
             bincode::serialize_into(file, &self)
         })?)
     }
+
+    /// Writes the checksum manifest to file.
+    fn freeze_checksums(&self, checksums: &NippyJarChecksums) -> Result<(), NippyJarError> {
+        Ok(reth_fs_util::atomic_write_file(&self.checksums_path(), |file| {
+            bincode::serialize_into(file, checksums)
+        })?)
+    }
 }
 
 #[cfg(test)]
@@ -423,7 +468,10 @@ mod tests {
     use super::*;
     use compression::Compression;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
-    use std::{fs::OpenOptions, io::Read};
+    use std::{
+        fs::OpenOptions,
+        io::{Read, Write},
+    };
 
     type ColumnResults<T> = Vec<ColumnResult<T>>;
     type ColumnValues = Vec<Vec<u8>>;
@@ -784,6 +832,7 @@ mod tests {
 
         // Should be able to append new rows
         append_two_rows(num_columns, file_path.path(), &col1, &col2);
+        NippyJar::load_without_header(file_path.path()).unwrap().verify_checksums().unwrap();
 
         // Simulate an unexpected shutdown before there's a chance to commit, and see that it
         // unwinds successfully
@@ -791,6 +840,16 @@ mod tests {
 
         // Simulate an unexpected shutdown during commit, and see that it unwinds successfully
         test_append_consistency_partial_commit(file_path.path(), &col1, &col2);
+        NippyJar::load_without_header(file_path.path()).unwrap().verify_checksums().unwrap();
+
+        // Corrupt the data file
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut data_file = OpenOptions::new().write(true).open(nippy.data_path()).unwrap();
+        data_file.write_all(&[0xff; 4]).unwrap();
+        assert!(matches!(
+            nippy.verify_checksums(),
+            Err(NippyJarError::ChecksumMismatch { path, offset: 0 }) if path == nippy.data_path()
+        ));
     }
 
     #[test]
@@ -812,6 +871,7 @@ mod tests {
 
             let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
             assert_eq!(nippy.rows, expected_rows);
+            nippy.verify_checksums().unwrap();
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarChecksums,
+    NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
@@ -43,6 +43,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     column: usize,
     /// Whether the writer has changed data that needs to be committed.
     dirty: bool,
+    /// Checksum manifest of the data and offsets files, updated on every commit.
+    checksums: NippyJarChecksums,
 }
 
 impl<H: NippyJarHeader> NippyJarWriter<H> {
@@ -53,11 +55,14 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         let (data_file, offsets_file, is_created) =
             Self::create_or_open_files(jar.data_path(), &jar.offsets_path())?;
 
-        let (jar, data_file, offsets_file) = if is_created {
+        let (jar, data_file, offsets_file, checksums) = if is_created {
+            let checksums = NippyJarChecksums::default();
+
             // Makes sure we don't have dangling data and offset files when we just created the file
+            jar.freeze_checksums(&checksums)?;
             jar.freeze_config()?;
 
-            (jar, BufWriter::new(data_file), BufWriter::new(offsets_file))
+            (jar, BufWriter::new(data_file), BufWriter::new(offsets_file), checksums)
         } else {
             // If we are opening a previously created jar, we need to check its consistency, and
             // make changes if necessary.
@@ -66,8 +71,13 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
 
             let NippyJarChecker { jar, data_file, offsets_file } = checker;
 
+            // Jars created before the checksum manifest was introduced are fully hashed on the
+            // next commit. The files might have been truncated by the consistency check above.
+            let mut checksums = jar.load_checksums()?.unwrap_or_default();
+            checksums.truncate(jar.data_path(), &jar.offsets_path())?;
+
             // Calling ensure_consistency, will fill data_file and offsets_file
-            (jar, data_file.expect("qed"), offsets_file.expect("qed"))
+            (jar, data_file.expect("qed"), offsets_file.expect("qed"), checksums)
         };
 
         let mut writer = Self {
@@ -79,6 +89,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
             dirty: false,
+            checksums,
         };
 
         if !is_created {
@@ -324,6 +335,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         if self.jar.rows == 0 {
             self.jar.max_row_size = 0;
         }
+        self.commit_checksums()?;
         self.jar.freeze_config()?;
 
         Ok(())
@@ -346,6 +358,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.data_file.get_ref().sync_all()?;
 
         self.commit_offsets()?;
+        self.commit_checksums()?;
 
         // Flushes `max_row_size` and total `rows` to disk.
         self.jar.freeze_config()?;
@@ -363,6 +376,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.data_file.flush()?;
 
         self.commit_offsets_without_sync_all()?;
+        self.commit_checksums()?;
 
         // Flushes `max_row_size` and total `rows` to disk.
         self.jar.freeze_config()?;
@@ -371,6 +385,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         Ok(())
     }
 
+    /// Updates the checksum manifest to the flushed data and offsets files and writes it to disk.
+    fn commit_checksums(&mut self) -> Result<(), NippyJarError> {
+        self.checksums.update(self.jar.data_path(), &self.jar.offsets_path())?;
+        self.jar.freeze_checksums(&self.checksums)
+    }
+
     /// Flushes offsets to disk.
     pub(crate) fn commit_offsets(&mut self) -> Result<(), NippyJarError> {
         self.commit_offsets_inner()?;
@@ -1,6 +1,9 @@
 #![allow(unused)]
 use crate::{
-    providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
+    providers::{
+        unknown_block_hashes::UnknownBlockHashes, ConsistentProvider, ProviderNodeTypes,
+        StaticFileProvider,
+    },
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
     ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
@@ -9,9 +12,10 @@ use crate::{
     StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
     StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
-use alloy_consensus::{transaction::TransactionMeta, Header};
+use alloy_consensus::{constants::KECCAK_EMPTY, transaction::TransactionMeta, Header};
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
+    eip7685::Requests,
     BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
 };
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
@@ -22,7 +26,7 @@ use reth_chain_state::{
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db_api::{
-    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, BlockNumberAddress, ContractDeployment, StoredBlockBodyIndices},
     transaction::DbTx,
     Database,
 };
@@ -33,16 +37,17 @@ use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{
     Account, BlockBody, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader, StorageEntry,
 };
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StorageChangeSetReader,
+    BlockBodyIndicesProvider, ContractDeploymentsProvider, DBProvider, NodePrimitivesProvider,
+    RequestsProvider, SenderTransactionsProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
 use std::{
-    ops::{Add, RangeBounds, RangeInclusive, Sub},
+    ops::{Add, Range, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
 };
@@ -60,6 +65,8 @@ pub struct BlockchainProvider<N: NodeTypesWithDB> {
     /// Tracks the chain info wrt forkchoice updates and in memory canonical
     /// state.
     pub(crate) canonical_in_memory_state: CanonicalInMemoryState<N::Primitives>,
+    /// Block hashes that were recently found to be unknown.
+    unknown_block_hashes: UnknownBlockHashes,
 }
 
 impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
@@ -67,6 +74,7 @@ impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
         Self {
             database: self.database.clone(),
             canonical_in_memory_state: self.canonical_in_memory_state.clone(),
+            unknown_block_hashes: self.unknown_block_hashes.clone(),
         }
     }
 }
@@ -118,6 +126,7 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
                 finalized_header,
                 safe_header,
             ),
+            unknown_block_hashes: UnknownBlockHashes::default(),
         })
     }
 
@@ -134,6 +143,57 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
         ConsistentProvider::new(self.database.clone(), self.canonical_in_memory_state())
     }
 
+    /// Looks up a block by its hash with `f`, unless the hash was recently found to be unknown.
+    ///
+    /// If the lookup returns `None`, the hash is remembered as unknown until the head of the
+    /// canonical chain changes.
+    fn get_by_block_hash<T>(
+        &self,
+        hash: B256,
+        f: impl FnOnce(ConsistentProvider<N>) -> ProviderResult<Option<T>>,
+    ) -> ProviderResult<Option<T>> {
+        // blocks are inserted into the in-memory state before the canonical head is updated, and
+        // known blocks are usually in memory, so the cache is only checked after a miss
+        if self.canonical_in_memory_state.state_by_hash(hash).is_some() {
+            return f(self.consistent_provider()?)
+        }
+
+        let head = self.canonical_in_memory_state.chain_info().best_hash;
+        if self.unknown_block_hashes.contains(&hash, &head) {
+            return Ok(None)
+        }
+
+        let result = f(self.consistent_provider()?)?;
+        if result.is_none() {
+            self.unknown_block_hashes.insert(hash, head);
+        }
+        Ok(result)
+    }
+
+    /// Looks up a block by its number or hash with `f`, see [`Self::get_by_block_hash`].
+    fn get_by_block_hash_or_number<T>(
+        &self,
+        id: BlockHashOrNumber,
+        f: impl FnOnce(ConsistentProvider<N>) -> ProviderResult<Option<T>>,
+    ) -> ProviderResult<Option<T>> {
+        match id {
+            BlockHashOrNumber::Hash(hash) => self.get_by_block_hash(hash, f),
+            BlockHashOrNumber::Number(_) => f(self.consistent_provider()?),
+        }
+    }
+
+    /// Looks up a block by its id with `f`, see [`Self::get_by_block_hash`].
+    fn get_by_block_id<T>(
+        &self,
+        id: BlockId,
+        f: impl FnOnce(ConsistentProvider<N>) -> ProviderResult<Option<T>>,
+    ) -> ProviderResult<Option<T>> {
+        match id {
+            BlockId::Hash(hash) => self.get_by_block_hash(hash.block_hash, f),
+            BlockId::Number(_) => f(self.consistent_provider()?),
+        }
+    }
+
     /// This uses a given [`BlockState`] to initialize a state provider for that block.
     fn block_state_provider(
         &self,
@@ -183,7 +243,7 @@ impl<N: ProviderNodeTypes> HeaderProvider for BlockchainProvider<N> {
     type Header = HeaderTy<N>;
 
     fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Self::Header>> {
-        self.consistent_provider()?.header(block_hash)
+        self.get_by_block_hash(*block_hash, |provider| provider.header(block_hash))
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Self::Header>> {
@@ -260,7 +320,7 @@ impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider<N> {
     }
 
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
-        self.consistent_provider()?.block_number(hash)
+        self.get_by_block_hash(hash, |provider| provider.block_number(hash))
     }
 }
 
@@ -290,7 +350,7 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
     }
 
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Self::Block>> {
-        self.consistent_provider()?.block(id)
+        self.get_by_block_hash_or_number(id, |provider| provider.block(id))
     }
 
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
@@ -314,7 +374,9 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
         id: BlockHashOrNumber,
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        self.consistent_provider()?.recovered_block(id, transaction_kind)
+        self.get_by_block_hash_or_number(id, |provider| {
+            provider.recovered_block(id, transaction_kind)
+        })
     }
 
     fn sealed_block_with_senders(
@@ -322,7 +384,9 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
         id: BlockHashOrNumber,
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        self.consistent_provider()?.sealed_block_with_senders(id, transaction_kind)
+        self.get_by_block_hash_or_number(id, |provider| {
+            provider.sealed_block_with_senders(id, transaction_kind)
+        })
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
@@ -490,6 +554,10 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.consistent_provider()?.get_prune_checkpoints()
     }
+
+    fn prune_modes(&self) -> ProviderResult<PruneModes> {
+        self.database.prune_modes()
+    }
 }
 
 impl<N: NodeTypesWithDB> ChainSpecProvider for BlockchainProvider<N> {
@@ -639,7 +707,7 @@ where
     Self: ReceiptProviderIdExt,
 {
     fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Self::Block>> {
-        self.consistent_provider()?.block_by_id(id)
+        self.get_by_block_id(id, |provider| provider.block_by_id(id))
     }
 
     fn header_by_number_or_tag(
@@ -660,11 +728,11 @@ where
         &self,
         id: BlockId,
     ) -> ProviderResult<Option<SealedHeader<Self::Header>>> {
-        self.consistent_provider()?.sealed_header_by_id(id)
+        self.get_by_block_id(id, |provider| provider.sealed_header_by_id(id))
     }
 
     fn header_by_id(&self, id: BlockId) -> ProviderResult<Option<Self::Header>> {
-        self.consistent_provider()?.header_by_id(id)
+        self.get_by_block_id(id, |provider| provider.header_by_id(id))
     }
 }
 
@@ -706,6 +774,69 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> SenderTransactionsProvider for BlockchainProvider<N> {
+    /// Only transactions of blocks that have been persisted to the database are indexed.
+    fn sender_transaction_ids(
+        &self,
+        sender: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.database.provider()?.sender_transaction_ids(sender, offset, limit)
+    }
+
+    fn address_transaction_ids(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.database.provider()?.address_transaction_ids(address, range, limit, reverse)
+    }
+}
+
+impl<N: ProviderNodeTypes> RequestsProvider for BlockchainProvider<N> {
+    fn requests_by_block(&self, block: BlockNumber) -> ProviderResult<Option<Requests>> {
+        if let Some(block_state) = self.canonical_in_memory_state.state_by_number(block) {
+            let outcome = block_state.block_ref().execution_outcome();
+            return Ok(outcome
+                .block_number_to_index(block)
+                .and_then(|idx| outcome.requests.get(idx))
+                .filter(|requests| !requests.is_empty())
+                .cloned())
+        }
+
+        self.database.provider()?.requests_by_block(block)
+    }
+}
+
+impl<N: ProviderNodeTypes> ContractDeploymentsProvider for BlockchainProvider<N> {
+    fn contract_deployments_indexed(&self) -> ProviderResult<bool> {
+        self.database.provider()?.contract_deployments_indexed()
+    }
+
+    /// Blocks that haven't been persisted yet are not indexed, so their state is checked first.
+    fn contract_deployment(
+        &self,
+        address: Address,
+        code_hash: B256,
+    ) -> ProviderResult<Option<ContractDeployment>> {
+        for block_state in self.canonical_in_memory_state.canonical_chain() {
+            let bundle = &block_state.block_ref().execution_outcome().bundle;
+            let Some(account) = bundle.account(&address) else { continue };
+
+            let had_code =
+                account.original_info.as_ref().is_some_and(|info| info.code_hash != KECCAK_EMPTY);
+            if !had_code && account.info.as_ref().is_some_and(|info| info.code_hash == code_hash) {
+                return Ok(Some(ContractDeployment { address, block_number: block_state.number() }))
+            }
+        }
+
+        self.database.provider()?.contract_deployment(address, code_hash)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -1085,6 +1216,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_unknown_block_hash_becomes_known() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=6,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        let (database_blocks, new_blocks) = blocks.split_at(5);
+
+        let provider_rw = factory.provider_rw()?;
+        for block in database_blocks {
+            provider_rw.insert_historical_block(
+                block.clone().try_recover().expect("failed to seal block with senders"),
+            )?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory.clone())?;
+        let (db_block, in_memory_block) = (&new_blocks[0], &new_blocks[1]);
+
+        // the unknown hash is remembered until the head moves, even if the block is written to
+        // the database in the meantime
+        assert_eq!(provider.header(&db_block.hash())?, None);
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.insert_historical_block(
+            db_block.clone().try_recover().expect("failed to seal block with senders"),
+        )?;
+        provider_rw.commit()?;
+        assert_eq!(provider.header(&db_block.hash())?, None);
+
+        provider.set_canonical_head(db_block.clone_sealed_header());
+        assert_eq!(provider.header(&db_block.hash())?, Some(db_block.header().clone()));
+        assert_eq!(provider.block_number(db_block.hash())?, Some(db_block.number));
+
+        // blocks inserted into the in-memory state are found right away
+        assert_eq!(provider.block(in_memory_block.hash().into())?, None);
+        let senders = in_memory_block.senders().expect("failed to recover senders");
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit {
+            new: vec![ExecutedBlockWithTrieUpdates::new(
+                Arc::new(RecoveredBlock::new_sealed(in_memory_block.clone(), senders)),
+                Default::default(),
+                Default::default(),
+                ExecutedTrieUpdates::empty(),
+            )],
+        });
+        assert_eq!(
+            provider.block(in_memory_block.hash().into())?,
+            Some(in_memory_block.clone().into_block())
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_reader_block() -> eyre::Result<()> {
         // Initialize random number generator and provider factory
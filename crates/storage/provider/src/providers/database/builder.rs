@@ -42,6 +42,10 @@ impl<N> ProviderFactoryBuilder<N> {
 
     /// Opens the database with the given chainspec and [`ReadOnlyConfig`].
     ///
+    /// The database is opened in cooperative mode, so that it can be shared with other processes,
+    /// including a running node. Neither the database nor the static files can be written to
+    /// through the returned [`ProviderFactory`].
+    ///
     /// # Open a monitored instance
     ///
     /// This is recommended when the new read-only instance is used with an active node.
@@ -107,6 +111,9 @@ impl<N> ProviderFactoryBuilder<N> {
     {
         let ReadOnlyConfig { db_dir, db_args, static_files_dir, watch_static_files } =
             config.into();
+        // never open the environment in exclusive mode, which would lock out the node and any other
+        // process that uses the database
+        let db_args = db_args.with_exclusive(Some(false));
         Ok(self
             .db(Arc::new(open_db_read_only(db_dir, db_args)?))
             .chainspec(chainspec)
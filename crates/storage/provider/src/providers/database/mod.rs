@@ -13,7 +13,7 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
 use core::fmt;
 use reth_chainspec::ChainInfo;
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{database::Database, models::StoredBlockBodyIndices, stats::StorageStats};
 use reth_errors::{RethError, RethResult};
 use reth_node_types::{
     BlockTy, HeaderTy, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy, TxTy,
@@ -23,7 +23,8 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, NodePrimitivesProvider, StorageStatsProvider,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -71,6 +72,18 @@ impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>
     pub fn builder() -> ProviderFactoryBuilder<N> {
         ProviderFactoryBuilder::default()
     }
+
+    /// Opens the datadir in read-only mode, see [`ProviderFactoryBuilder::open_read_only`].
+    ///
+    /// This is intended for separate processes, e.g. analytics jobs, that read the data of a
+    /// running node: no database locks are taken and [`ProviderFactory::provider_rw`] as well as
+    /// any static file writer return an error.
+    pub fn read_only(
+        chain_spec: Arc<N::ChainSpec>,
+        config: impl Into<ReadOnlyConfig>,
+    ) -> eyre::Result<Self> {
+        Self::builder().open_read_only(chain_spec, config)
+    }
 }
 
 impl<N: NodeTypesWithDB> ProviderFactory<N> {
@@ -132,6 +145,12 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
     }
 }
 
+impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> StorageStatsProvider for ProviderFactory<N> {
+    fn storage_stats(&self) -> ProviderResult<StorageStats> {
+        Ok(self.db.storage_stats()?)
+    }
+}
+
 impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// Returns a provider with a created `DbTx` inside, which allows fetching data from the
     /// database using different types of providers. Example: [`HeaderProvider`]
@@ -578,6 +597,10 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.provider()?.get_prune_checkpoints()
     }
+
+    fn prune_modes(&self) -> ProviderResult<PruneModes> {
+        Ok(self.prune_modes.clone())
+    }
 }
 
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
@@ -682,6 +705,42 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn provider_factory_read_only() {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().build());
+        let datadir = tempfile::TempDir::new().expect(ERROR_TEMPDIR);
+        let factory = ProviderFactory::<MockNodeTypesWithDB<DatabaseEnv>>::new_with_database_path(
+            datadir.path().join("db"),
+            chain_spec.clone(),
+            DatabaseArguments::new(Default::default()),
+            StaticFileProvider::read_write(datadir.path().join("static_files")).unwrap(),
+        )
+        .unwrap();
+
+        let block = TEST_BLOCK.clone();
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database)
+            .unwrap();
+        provider.commit().unwrap();
+
+        // the read-only factory can be opened while the read-write factory is still alive
+        let read_only = ProviderFactory::<MockNodeTypesWithDB<DatabaseEnv>>::read_only(
+            chain_spec,
+            datadir.path(),
+        )
+        .unwrap();
+        assert_eq!(
+            read_only.provider().unwrap().block_hash(block.header().number).unwrap(),
+            Some(block.hash())
+        );
+        assert!(read_only.provider_rw().is_err());
+        assert_matches!(
+            read_only.static_file_provider().latest_writer(StaticFileSegment::Headers),
+            Err(ProviderError::ReadOnlyStaticFileAccess)
+        );
+    }
+
     #[test]
     fn insert_block_with_prune_modes() {
         let factory = create_test_provider_factory();
@@ -19,10 +19,11 @@ use crate::{
     TransactionsProviderExt, TrieWriter,
 };
 use alloy_consensus::{
+    constants::KECCAK_EMPTY,
     transaction::{SignerRecoverable, TransactionMeta},
     BlockHeader, Header, TxReceipt,
 };
-use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
+use alloy_eips::{eip2718::Encodable2718, eip7685::Requests, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
@@ -36,7 +37,7 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        ContractDeployment, ShardedKey, StoredBlockBodyIndices, StoredBlockRequests,
     },
     table::Table,
     tables,
@@ -55,16 +56,18 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, ContractDeploymentsProvider,
+    HashedStateRangeProvider, NodePrimitivesProvider, RequestsProvider, SenderTransactionsProvider,
+    StateProofProvider, StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostStateSorted, MultiProof, MultiProofTargets, Nibbles, StateRoot, StorageRoot,
+    StoredNibbles, StoredNibblesSubKey, TrieAccount, TrieInput, EMPTY_ROOT_HASH,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot, DatabaseStorageTrieCursor};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -72,7 +75,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
 };
 use tracing::{debug, trace};
@@ -209,6 +212,51 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
         self.prune_modes = prune_modes;
     }
+
+    /// Returns the contracts deployed in the given block range, with the hashes of their code.
+    ///
+    /// A contract is deployed in a block if the account had no code before the block, and has
+    /// code after it. Only accounts that didn't exist or had a zero nonce before the block are
+    /// considered, as a contract can't be deployed at any other address. The state after the block
+    /// is read from the next change of the account, or from the plain state if the account didn't
+    /// change since, which requires the account history index to be up to date.
+    fn contract_deployments_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(B256, ContractDeployment)>> {
+        let code_hash = |account: Option<Account>| {
+            account.and_then(|account| account.bytecode_hash).filter(|hash| *hash != KECCAK_EMPTY)
+        };
+
+        let mut history = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut deployments = Vec::new();
+        for (block_number, before) in self.account_changesets_range(range)? {
+            if before
+                .info
+                .is_some_and(|account| account.nonce != 0 || code_hash(Some(account)).is_some())
+            {
+                continue
+            }
+
+            let address = before.address;
+            let next_change = history
+                .seek(ShardedKey::new(address, block_number + 1))?
+                .filter(|(key, _)| key.key == address)
+                .and_then(|(_, list)| list.iter().find(|block| *block > block_number));
+            let after = match next_change {
+                Some(next_block) => {
+                    self.get_account_before_block(next_block, address)?.and_then(|entry| entry.info)
+                }
+                None => self.tx.get::<tables::PlainAccountState>(address)?,
+            };
+
+            if let Some(code_hash) = code_hash(after) {
+                deployments.push((code_hash, ContractDeployment { address, block_number }));
+            }
+        }
+
+        Ok(deployments)
+    }
 }
 
 impl<TX, N: NodeTypes> NodePrimitivesProvider for DatabaseProvider<TX, N> {
@@ -252,6 +300,22 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Unwinds the contract deployments index above the given block, if it's enabled.
+    ///
+    /// Must be called before the account history indices are unwound.
+    fn unwind_contract_deployments_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexContractDeployments)? {
+            if checkpoint.block_number > block {
+                self.unwind_contract_deployments_index_range(block + 1..=checkpoint.block_number)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexContractDeployments,
+                    StageCheckpoint::new(block),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -260,11 +324,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<()> {
-        let changed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range.clone())?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_accounts = self.account_changesets_range(range.clone())?;
 
         // Unwind account hashes. Add changed accounts to account prefix set.
         let hashed_addresses = self.unwind_account_hashing(changed_accounts.iter())?;
@@ -281,11 +341,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         self.unwind_account_history_indices(changed_accounts.iter())?;
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let changed_storages = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_storages = self.storage_changesets_range(storage_range)?;
 
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
@@ -511,6 +567,61 @@ where
     Ok(Vec::new())
 }
 
+/// Returns at most `limit` ids of the transactions of `address` in the given range from an index of
+/// transactions by address, like [`tables::SenderTransactions`].
+///
+/// The ids are read in ascending order from the start of the range, or in descending order from
+/// its end if `reverse` is set. Only the shards that cover the returned ids are read.
+fn indexed_transaction_ids<T, C>(
+    cursor: &mut C,
+    address: Address,
+    range: Range<TxNumber>,
+    limit: usize,
+    reverse: bool,
+) -> ProviderResult<Vec<TxNumber>>
+where
+    T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    let mut ids = Vec::new();
+    if range.is_empty() || limit == 0 {
+        return Ok(ids)
+    }
+
+    // Shards are keyed by their highest id, so this is the first shard that can contain the
+    // transactions at the respective end of the range.
+    let start = if reverse { range.end - 1 } else { range.start };
+    let mut item = cursor.seek(ShardedKey::new(address, start))?;
+    while let Some((sharded_key, list)) = item {
+        if sharded_key.key != address {
+            break
+        }
+
+        let shard = list.iter().collect::<Vec<_>>();
+        if reverse {
+            ids.extend(
+                shard.iter().rev().copied().filter(|id| range.contains(id)).take(limit - ids.len()),
+            );
+            // The previous shards only contain transactions before the range.
+            if ids.len() == limit || shard.first().is_some_and(|id| *id < range.start) {
+                break
+            }
+            item = cursor.prev()?;
+        } else {
+            ids.extend(
+                shard.iter().copied().filter(|id| range.contains(id)).take(limit - ids.len()),
+            );
+            // The next shards only contain transactions after the range.
+            if ids.len() == limit || shard.last().is_some_and(|id| *id >= range.end) {
+                break
+            }
+            item = cursor.next()?;
+        }
+    }
+
+    Ok(ids)
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -805,6 +916,62 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(self.tx.commit()?)
     }
 
+    /// Takes the account and storage changesets of the blocks above `block`, ordered by block
+    /// number.
+    ///
+    /// The changesets are removed from both the database and the static files. The static files
+    /// are truncated right away, so that the changesets of the removed blocks are not served by
+    /// the static files for the rest of this transaction.
+    fn take_change_sets_above(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<(
+        Vec<(BlockNumber, AccountBeforeTx)>,
+        Vec<(BlockNumberAddress, StorageEntry)>,
+    )> {
+        let highest_account_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets);
+        let highest_storage_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets);
+
+        let mut account_changeset =
+            self.static_file_provider.account_change_sets_range(block + 1..)?;
+        let mut storage_changeset =
+            self.static_file_provider.storage_change_sets_range(block + 1..)?;
+
+        for (segment, highest_block) in [
+            (StaticFileSegment::AccountChangeSets, highest_account_block),
+            (StaticFileSegment::StorageChangeSets, highest_storage_block),
+        ] {
+            if let Some(highest_block) = highest_block.filter(|highest| *highest > block) {
+                let mut writer = self.static_file_provider.latest_writer(segment)?;
+                writer.prune_change_sets(highest_block - block)?;
+                writer.commit()?;
+            }
+        }
+
+        // Changesets that were already moved to static files might still be in the database, if
+        // they haven't been pruned yet.
+        account_changeset.extend(
+            self.take::<tables::AccountChangeSets>(block + 1..)?.into_iter().filter(
+                |(number, _)| highest_account_block.is_none_or(|highest| *number > highest),
+            ),
+        );
+        storage_changeset.extend(
+            self.take::<tables::StorageChangeSets>(
+                BlockNumberAddress((block + 1, Address::ZERO))..,
+            )?
+            .into_iter()
+            .filter(|(key, _)| {
+                highest_storage_block.is_none_or(|highest| key.block_number() > highest)
+            }),
+        );
+
+        Ok((account_changeset, storage_changeset))
+    }
+
     /// Load shard and remove it. If list is empty, last shard was full or
     /// there are no shards at all.
     fn take_shard<T>(
@@ -862,6 +1029,34 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         }
         Ok(())
     }
+
+    /// Removes the transactions of each address starting at the given transaction from an index of
+    /// transactions by address, like [`tables::SenderTransactions`].
+    fn unwind_transactions_index<T>(
+        &self,
+        first_transactions: BTreeMap<Address, TxNumber>,
+    ) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for (address, rem_index) in first_transactions {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
@@ -875,13 +1070,11 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -899,16 +1092,13 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -921,11 +1111,69 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
         let range = block_number..=block_number;
         let storage_range = BlockNumberAddress::range(range);
-        self.tx
+        self.storage_changesets_range(storage_range)
+    }
+
+    fn get_storage_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        if self
+            .static_file_provider
+            .has_change_sets(StaticFileSegment::StorageChangeSets, block_number)
+        {
+            return Ok(self
+                .static_file_provider
+                .storage_change(block_number, &address, &storage_key)?
+                .map(|change| StorageEntry { key: change.key, value: change.value }))
+        }
+
+        Ok(self
+            .tx
             .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+            .seek_by_key_subkey((block_number, address).into(), storage_key)?
+            .filter(|entry| entry.key == storage_key))
+    }
+
+    /// Changesets of blocks that were moved to static files are read from them, and the rest from
+    /// the database.
+    fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        let highest_static_file_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets);
+
+        let mut change_sets = Vec::new();
+        if highest_static_file_block.is_some() {
+            // Static files are queried by whole blocks, so the blocks at the range bounds are
+            // filtered by address afterwards.
+            let block_bound = |bound: Bound<&BlockNumberAddress>| match bound {
+                Bound::Included(key) | Bound::Excluded(key) => Bound::Included(key.block_number()),
+                Bound::Unbounded => Bound::Unbounded,
+            };
+            let block_range = (block_bound(range.start_bound()), block_bound(range.end_bound()));
+            for entry in self.static_file_provider.storage_change_sets_walker(to_range(block_range))
+            {
+                let entry = entry?;
+                if range.contains(&entry.0) {
+                    change_sets.push(entry);
+                }
+            }
+        }
+
+        for entry in self.tx.cursor_read::<tables::StorageChangeSets>()?.walk_range(range)? {
+            let (key, storage_entry) = entry?;
+            if highest_static_file_block.is_none_or(|highest| key.block_number() > highest) {
+                change_sets.push((key, storage_entry));
+            }
+        }
+
+        Ok(change_sets)
     }
 }
 
@@ -934,6 +1182,10 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        if let Some(changes) = self.static_file_provider.account_change_set(block_number)? {
+            return Ok(changes)
+        }
+
         let range = block_number..=block_number;
         self.tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -944,6 +1196,47 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             })
             .collect()
     }
+
+    fn get_account_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        if self
+            .static_file_provider
+            .has_change_sets(StaticFileSegment::AccountChangeSets, block_number)
+        {
+            return self.static_file_provider.account_change(block_number, &address)
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountChangeSets>()?
+            .seek_by_key_subkey(block_number, address)?
+            .filter(|change| change.address == address))
+    }
+
+    /// Changesets of blocks that were moved to static files are read from them, and the rest from
+    /// the database.
+    fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        let highest_static_file_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets);
+
+        let mut change_sets = self.static_file_provider.account_change_sets_range(range)?;
+        for entry in self.tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(range)? {
+            let (block_number, account_before) = entry?;
+            if highest_static_file_block.is_none_or(|highest| block_number > highest) {
+                change_sets.push((block_number, account_before));
+            }
+        }
+
+        Ok(change_sets)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -1519,6 +1812,101 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> SenderTransactionsProvider for DatabaseProvider<TX, N> {
+    fn sender_transaction_ids(
+        &self,
+        sender: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        if self.get_stage_checkpoint(StageId::IndexSenderTransactions)?.is_none() {
+            return Ok(None)
+        }
+
+        let mut skip = offset as u64;
+        let mut ids = Vec::new();
+        let mut cursor = self.tx.cursor_read::<tables::SenderTransactions>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(sender, 0)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != sender || ids.len() >= limit {
+                break
+            }
+
+            // Skip whole shards without decoding them.
+            if skip >= list.len() {
+                skip -= list.len();
+                continue
+            }
+            ids.extend(list.iter().skip(skip as usize).take(limit - ids.len()));
+            skip = 0;
+        }
+
+        Ok(Some(ids))
+    }
+
+    fn address_transaction_ids(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        if self.get_stage_checkpoint(StageId::IndexSenderTransactions)?.is_none() {
+            return Ok(None)
+        }
+
+        let sent = indexed_transaction_ids::<tables::SenderTransactions, _>(
+            &mut self.tx.cursor_read::<tables::SenderTransactions>()?,
+            address,
+            range.clone(),
+            limit,
+            reverse,
+        )?;
+        let received = indexed_transaction_ids::<tables::RecipientTransactions, _>(
+            &mut self.tx.cursor_read::<tables::RecipientTransactions>()?,
+            address,
+            range,
+            limit,
+            reverse,
+        )?;
+
+        // Transactions an address sends to itself are in both indices.
+        let ids = if reverse {
+            sent.into_iter().merge_by(received, |a, b| a > b).dedup().take(limit).collect()
+        } else {
+            sent.into_iter().merge(received).dedup().take(limit).collect()
+        };
+        Ok(Some(ids))
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> ContractDeploymentsProvider for DatabaseProvider<TX, N> {
+    fn contract_deployments_indexed(&self) -> ProviderResult<bool> {
+        Ok(self.get_stage_checkpoint(StageId::IndexContractDeployments)?.is_some())
+    }
+
+    fn contract_deployment(
+        &self,
+        address: Address,
+        code_hash: B256,
+    ) -> ProviderResult<Option<ContractDeployment>> {
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::ContractDeployments>()?
+            .seek_by_key_subkey(code_hash, address)?
+            .filter(|deployment| deployment.address == address))
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> RequestsProvider for DatabaseProvider<TX, N> {
+    fn requests_by_block(&self, block: BlockNumber) -> ProviderResult<Option<Requests>> {
+        Ok(self
+            .tx
+            .get::<tables::BlockRequests>(block)?
+            .map(|stored| Requests::new(stored.requests)))
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabaseProvider<TX, N> {
     type Receipt = ReceiptTy<N>;
 
@@ -1700,6 +2088,68 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> HashedStateRangeProvider
+    for DatabaseProvider<TX, N>
+{
+    fn persisted_state_root(&self) -> ProviderResult<B256> {
+        let number = self.best_block_number()?;
+        self.header_by_number(number)?
+            .map(|header| header.state_root())
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))
+    }
+
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>> {
+        let mut hashed_storages = self.tx.cursor_dup_read::<tables::HashedStorages>()?;
+        let mut storage_tries = self.tx.cursor_dup_read::<tables::StoragesTrie>()?;
+
+        let mut accounts = Vec::new();
+        for entry in self.tx.cursor_read::<tables::HashedAccounts>()?.walk(Some(start))?.take(limit)
+        {
+            let (hashed_address, account) = entry?;
+            let storage_root = if hashed_storages.seek_exact(hashed_address)?.is_none() {
+                EMPTY_ROOT_HASH
+            } else if let Some(root) = storage_tries
+                .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(Nibbles::default()))?
+                .filter(|entry| entry.nibbles.is_empty())
+                .and_then(|entry| entry.node.root_hash)
+            {
+                // the root node of the stored storage trie carries the root hash
+                root
+            } else {
+                // the root node of small storage tries isn't stored, so the root is computed
+                StorageRoot::from_tx_hashed(&self.tx, hashed_address)
+                    .root()
+                    .map_err(|err| ProviderError::Database(err.into()))?
+            };
+            accounts.push((hashed_address, account.into_trie_account(storage_root)));
+        }
+        Ok(accounts)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .walk_dup(Some(hashed_address), Some(start))?
+            .take(limit)
+            .map(|entry| entry.map(|(_, entry)| (entry.key, entry.value)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    fn persisted_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        LatestStateProviderRef::new(self).multiproof(TrieInput::default(), targets)
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N> {
     fn plain_state_storages(
         &self,
@@ -1728,36 +2178,36 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets_range(BlockNumberAddress::range(range))?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            .fold(
+                BTreeMap::new(),
+                |mut accounts: BTreeMap<Address, BTreeSet<B256>>,
+                 (BlockNumberAddress((_, address)), storage_entry)| {
+                    accounts.entry(address).or_default().insert(storage_entry.key);
+                    accounts
+                },
+            ))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
         let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
+            self.storage_changesets_range(BlockNumberAddress::range(range))?.into_iter().fold(
                 BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
+                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
                     storages
                         .entry((index.address(), storage.key))
                         .or_default()
                         .push(index.block_number());
-                    Ok(storages)
+                    storages
                 },
-            )?;
+            );
 
         Ok(storage_changeset_lists)
     }
@@ -1820,19 +2270,25 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             .then(|| self.static_file_provider.get_writer(first_block, StaticFileSegment::Receipts))
             .transpose()?;
 
-        let has_contract_log_filter = !self.prune_modes.receipts_log_filter.is_empty();
+        let has_contract_log_filter = self.prune_modes.has_receipts_log_filter();
         let contract_log_pruner = self.prune_modes.receipts_log_filter.group_by_block(tip, None)?;
+        let topic_log_pruner =
+            self.prune_modes.receipts_log_topic_filter.group_by_block(tip, None)?;
 
         // All receipts from the last 128 blocks are required for blockchain tree, even with
         // [`PruneSegment::ContractLogs`].
         let prunable_receipts =
             PruneMode::Distance(MINIMUM_PRUNING_DISTANCE).should_prune(first_block, tip);
 
-        // Prepare set of addresses which logs should not be pruned.
+        // Prepare set of addresses and topics which logs should not be pruned.
         let mut allowed_addresses: HashSet<Address, _> = HashSet::new();
         for (_, addresses) in contract_log_pruner.range(..first_block) {
             allowed_addresses.extend(addresses.iter().copied());
         }
+        let mut allowed_topics: HashSet<B256, _> = HashSet::new();
+        for (_, topics) in topic_log_pruner.range(..first_block) {
+            allowed_topics.extend(topics.iter().copied());
+        }
 
         for (idx, (receipts, first_tx_index)) in
             execution_outcome.receipts.iter().zip(block_indices).enumerate()
@@ -1853,10 +2309,13 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 continue
             }
 
-            // If there are new addresses to retain after this block number, track them
+            // If there are new addresses or topics to retain after this block number, track them
             if let Some(new_addresses) = contract_log_pruner.get(&block_number) {
                 allowed_addresses.extend(new_addresses.iter().copied());
             }
+            if let Some(new_topics) = topic_log_pruner.get(&block_number) {
+                allowed_topics.extend(new_topics.iter().copied());
+            }
 
             for (idx, receipt) in receipts.iter().enumerate() {
                 let receipt_idx = first_tx_index + idx as u64;
@@ -1864,7 +2323,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 // retain
                 if prunable_receipts &&
                     has_contract_log_filter &&
-                    !receipt.logs().iter().any(|log| allowed_addresses.contains(&log.address))
+                    !receipt.logs().iter().any(|log| {
+                        allowed_addresses.contains(&log.address) ||
+                            log.topics()
+                                .first()
+                                .is_some_and(|topic| allowed_topics.contains(topic))
+                    })
                 {
                     continue
                 }
@@ -1879,6 +2343,16 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             }
         }
 
+        for (idx, requests) in execution_outcome.requests.iter().enumerate() {
+            if requests.is_empty() {
+                continue
+            }
+            self.tx.put::<tables::BlockRequests>(
+                first_block + idx as u64,
+                StoredBlockRequests { requests: requests.iter().cloned().collect() },
+            )?;
+        }
+
         Ok(())
     }
 
@@ -2083,16 +2557,13 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         }
 
         // We are not removing block meta as it is used to get block changesets.
-        let block_bodies = self.block_body_indices_range(range.clone())?;
+        let block_bodies = self.block_body_indices_range(range)?;
 
         // get transaction receipts
         let from_transaction_num =
             block_bodies.first().expect("already checked if there are blocks").first_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) = self.take_change_sets_above(block)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2141,6 +2612,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         }
 
         self.remove_receipts_from(from_transaction_num, block, remove_receipts_from)?;
+        self.remove::<tables::BlockRequests>(block + 1..)?;
 
         Ok(())
     }
@@ -2179,7 +2651,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let start_block_number = *range.start();
 
         // We are not removing block meta as it is used to get block changesets.
-        let block_bodies = self.block_body_indices_range(range.clone())?;
+        let block_bodies = self.block_body_indices_range(range)?;
 
         // get transaction receipts
         let from_transaction_num =
@@ -2187,10 +2659,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) = self.take_change_sets_above(block)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2277,13 +2746,20 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
 
         self.remove_receipts_from(from_transaction_num, block, remove_receipts_from)?;
 
+        // blocks without requests have no entry, so fill the gaps
+        let mut requests = Vec::new();
+        for (block_number, stored) in self.take::<tables::BlockRequests>(start_block_number..)? {
+            requests.resize_with((block_number - start_block_number) as usize, Requests::default);
+            requests.push(Requests::new(stored.requests));
+        }
+
         Ok(ExecutionOutcome::new_init(
             state,
             reverts,
             Vec::new(),
             receipts,
             start_block_number,
-            Vec::new(),
+            requests,
         ))
     }
 }
@@ -2408,11 +2884,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_hashing(changesets.iter())
     }
 
@@ -2472,11 +2944,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_range(range)?;
         self.unwind_storage_hashing(changesets.into_iter())
     }
 
@@ -2600,7 +3068,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
     }
 }
 
-impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvider<TX, N> {
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> HistoryWriter
+    for DatabaseProvider<TX, N>
+{
     fn unwind_account_history_indices<'a>(
         &self,
         changesets: impl Iterator<Item = &'a (BlockNumber, AccountBeforeTx)>,
@@ -2639,11 +3109,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_history_indices(changesets.iter())
     }
 
@@ -2697,11 +3163,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_range(range)?;
         self.unwind_storage_history_indices(changesets.into_iter())
     }
 
@@ -2717,6 +3179,94 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         )
     }
 
+    fn unwind_sender_transactions_index_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<usize> {
+        // The first transaction of each sender and recipient in the range, which is where their
+        // indices are cut off.
+        let mut first_sent = BTreeMap::<Address, TxNumber>::new();
+        let mut tx_range = None::<RangeInclusive<TxNumber>>;
+        for entry in self.tx.cursor_read::<tables::TransactionSenders>()?.walk_range(range)? {
+            let (tx_number, sender) = entry?;
+            first_sent.entry(sender).or_insert(tx_number);
+            tx_range = Some(tx_range.map_or(tx_number, |range| *range.start())..=tx_number);
+        }
+
+        let Some(tx_range) = tx_range else { return Ok(0) };
+        let mut first_received = BTreeMap::<Address, TxNumber>::new();
+        for (tx_number, transaction) in
+            tx_range.clone().zip(self.transactions_by_tx_range(tx_range.clone())?)
+        {
+            if let Some(recipient) = transaction.to() {
+                first_received.entry(recipient).or_insert(tx_number);
+            }
+        }
+
+        self.unwind_transactions_index::<tables::SenderTransactions>(first_sent)?;
+        self.unwind_transactions_index::<tables::RecipientTransactions>(first_received)?;
+
+        Ok(tx_range.count())
+    }
+
+    fn insert_sender_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::SenderTransactions>(index_updates, ShardedKey::new)
+    }
+
+    fn insert_recipient_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::RecipientTransactions>(
+            index_updates,
+            ShardedKey::new,
+        )
+    }
+
+    fn unwind_contract_deployments_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let deployments = self.contract_deployments_in_range(range)?;
+
+        let mut cursor = self.tx.cursor_dup_write::<tables::ContractDeployments>()?;
+        for (code_hash, deployment) in &deployments {
+            if cursor
+                .seek_by_key_subkey(*code_hash, deployment.address)?
+                .is_some_and(|entry| entry.address == deployment.address)
+            {
+                cursor.delete_current()?;
+            }
+        }
+
+        Ok(deployments.len())
+    }
+
+    fn insert_contract_deployments_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let deployments = self.contract_deployments_in_range(range)?;
+
+        let mut cursor = self.tx.cursor_dup_write::<tables::ContractDeployments>()?;
+        for (code_hash, deployment) in &deployments {
+            // A contract can be redeployed at the same address with the same code after a
+            // self-destruct, replace the previous deployment in that case.
+            if cursor
+                .seek_by_key_subkey(*code_hash, deployment.address)?
+                .is_some_and(|entry| entry.address == deployment.address)
+            {
+                cursor.delete_current()?;
+            }
+            cursor.upsert(*code_hash, deployment)?;
+        }
+
+        Ok(deployments.len())
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2726,10 +3276,61 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // sender transactions index stage, if enabled
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexSenderTransactions)? {
+            // Only extend the index if it is up to date. Otherwise, the stage catches up on the
+            // next pipeline run.
+            if checkpoint.block_number + 1 == *range.start() {
+                let body_indices = |block| {
+                    self.tx
+                        .get::<tables::BlockBodyIndices>(block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(block))
+                };
+                let tx_range = body_indices(*range.start())?.first_tx_num()..
+                    body_indices(*range.end())?.next_tx_num();
+                let mut indices = BTreeMap::<Address, Vec<TxNumber>>::new();
+                for entry in self
+                    .tx
+                    .cursor_read::<tables::TransactionSenders>()?
+                    .walk_range(tx_range.clone())?
+                {
+                    let (tx_number, sender) = entry?;
+                    indices.entry(sender).or_default().push(tx_number);
+                }
+                self.insert_sender_transactions_index(indices)?;
+
+                let mut indices = BTreeMap::<Address, Vec<TxNumber>>::new();
+                for (tx_number, transaction) in
+                    tx_range.clone().zip(self.transactions_by_tx_range(tx_range)?)
+                {
+                    if let Some(recipient) = transaction.to() {
+                        indices.entry(recipient).or_default().push(tx_number);
+                    }
+                }
+                self.insert_recipient_transactions_index(indices)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexSenderTransactions,
+                    StageCheckpoint::new(*range.end()),
+                )?;
+            }
+        }
+
+        // contract deployments index stage, if enabled
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexContractDeployments)? {
+            // The account history of the range was inserted above.
+            if checkpoint.block_number + 1 == *range.start() {
+                self.insert_contract_deployments_index(range.clone())?;
+                self.save_stage_checkpoint(
+                    StageId::IndexContractDeployments,
+                    StageCheckpoint::new(*range.end()),
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -2744,6 +3345,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<Chain<Self::Primitives>> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.unwind_contract_deployments_above(block)?;
         self.unwind_trie_state_range(range.clone())?;
 
         // get execution res
@@ -2768,6 +3370,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<()> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.unwind_contract_deployments_above(block)?;
         self.unwind_trie_state_range(range)?;
 
         // remove execution res
@@ -2994,6 +3597,17 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             }
         }
 
+        // Unwind the sender transactions index while the senders are still available.
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexSenderTransactions)? {
+            if checkpoint.block_number > block {
+                self.unwind_sender_transactions_index_range(unwind_tx_from..)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexSenderTransactions,
+                    StageCheckpoint::new(block),
+                )?;
+            }
+        }
+
         self.remove::<tables::TransactionSenders>(unwind_tx_from..)?;
 
         self.remove_bodies_above(block, remove_from)?;
@@ -3103,6 +3717,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> PruneCheckpointReader for DatabaseProvide
             .walk(None)?
             .collect::<Result<_, _>>()?)
     }
+
+    fn prune_modes(&self) -> ProviderResult<PruneModes> {
+        Ok(self.prune_modes.clone())
+    }
 }
 
 impl<TX: DbTxMut, N: NodeTypes> PruneCheckpointWriter for DatabaseProvider<TX, N> {
@@ -3195,6 +3813,8 @@ mod tests {
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
         BlockWriter,
     };
+    use alloy_consensus::Transaction as _;
+    use alloy_primitives::Bytes;
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
     #[test]
@@ -3440,4 +4060,292 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_sender_transactions_index_live_updates() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        let mut expected = BTreeMap::<Address, Vec<TxNumber>>::new();
+        let mut received = BTreeMap::<Address, Vec<TxNumber>>::new();
+        let mut tx_number = 0;
+        for i in 0..3 {
+            let block = &data.blocks[i].0;
+            provider_rw.insert_block(block.clone(), crate::StorageLocation::Database).unwrap();
+            for (sender, transaction) in block.transactions_with_sender() {
+                expected.entry(*sender).or_default().push(tx_number);
+                if let Some(recipient) = transaction.to() {
+                    received.entry(recipient).or_default().push(tx_number);
+                }
+                tx_number += 1;
+            }
+        }
+        let index = || {
+            expected
+                .keys()
+                .map(|sender| {
+                    let ids = provider_rw.sender_transaction_ids(*sender, 0, 10).unwrap();
+                    (*sender, ids.unwrap_or_default())
+                })
+                .filter(|(_, ids)| !ids.is_empty())
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        // the index is not maintained unless it's enabled
+        provider_rw.update_history_indices(1..=3).unwrap();
+        let sender = data.blocks[0].0.senders()[0];
+        assert_eq!(provider_rw.sender_transaction_ids(sender, 0, 10).unwrap(), None);
+
+        // enabled index is extended with the persisted blocks
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexSenderTransactions, StageCheckpoint::new(0))
+            .unwrap();
+        provider_rw.update_history_indices(1..=3).unwrap();
+        assert_eq!(index(), expected);
+        assert_eq!(
+            provider_rw.get_stage_checkpoint(StageId::IndexSenderTransactions).unwrap(),
+            Some(StageCheckpoint::new(3))
+        );
+        for (recipient, ids) in &received {
+            let mut all = ids.clone();
+            all.extend(expected.get(recipient).into_iter().flatten());
+            all.sort_unstable();
+            all.dedup();
+            assert_eq!(
+                provider_rw.address_transaction_ids(*recipient, 0..tx_number, 10, false).unwrap(),
+                Some(all.clone())
+            );
+            all.reverse();
+            assert_eq!(
+                provider_rw.address_transaction_ids(*recipient, 0..tx_number, 10, true).unwrap(),
+                Some(all)
+            );
+        }
+
+        // removed blocks are unwound from the index
+        provider_rw.remove_blocks_above(1, crate::StorageLocation::Database).unwrap();
+        assert_eq!(index(), BTreeMap::from([(sender, vec![0])]));
+        assert_eq!(
+            provider_rw.get_stage_checkpoint(StageId::IndexSenderTransactions).unwrap(),
+            Some(StageCheckpoint::new(1))
+        );
+    }
+
+    #[test]
+    fn test_contract_deployments_index() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+
+        // the contract is deployed in block 1, the EOA is created in block 1 and changes in block
+        // 2, the funded contract is funded in block 1 and deployed in block 2
+        let code_hash = B256::with_last_byte(1);
+        let contract = Address::with_last_byte(1);
+        let eoa = Address::with_last_byte(2);
+        let funded_contract = Address::with_last_byte(3);
+        let eoa_account = Account { nonce: 0, balance: U256::from(1), bytecode_hash: None };
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: contract, info: None })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: eoa, info: None })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: funded_contract, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            2,
+            AccountBeforeTx { address: eoa, info: Some(eoa_account) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            2,
+            AccountBeforeTx { address: funded_contract, info: Some(eoa_account) },
+        )
+        .unwrap();
+        let contract_account =
+            Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(code_hash) };
+        tx.put::<tables::PlainAccountState>(contract, contract_account).unwrap();
+        tx.put::<tables::PlainAccountState>(eoa, Account { nonce: 1, ..eoa_account }).unwrap();
+        tx.put::<tables::PlainAccountState>(funded_contract, contract_account).unwrap();
+        provider_rw
+            .insert_account_history_index([
+                (contract, vec![1]),
+                (eoa, vec![1, 2]),
+                (funded_contract, vec![1, 2]),
+            ])
+            .unwrap();
+
+        assert!(!provider_rw.contract_deployments_indexed().unwrap());
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexContractDeployments, StageCheckpoint::new(0))
+            .unwrap();
+        assert!(provider_rw.contract_deployments_indexed().unwrap());
+
+        assert_eq!(provider_rw.insert_contract_deployments_index(1..=2).unwrap(), 2);
+        assert_eq!(
+            provider_rw.contract_deployment(contract, code_hash).unwrap(),
+            Some(ContractDeployment { address: contract, block_number: 1 })
+        );
+        assert_eq!(
+            provider_rw.contract_deployment(funded_contract, code_hash).unwrap(),
+            Some(ContractDeployment { address: funded_contract, block_number: 2 })
+        );
+        assert_eq!(provider_rw.contract_deployment(eoa, code_hash).unwrap(), None);
+
+        assert_eq!(provider_rw.unwind_contract_deployments_index_range(1..=2).unwrap(), 2);
+        assert_eq!(provider_rw.contract_deployment(contract, code_hash).unwrap(), None);
+    }
+
+    #[test]
+    fn test_block_requests() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        let requests = Requests::new(vec![Bytes::from_static(&[0x01, 0xaa])]);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        // only blocks 1 and 3 have requests
+        for i in 0..3 {
+            provider_rw
+                .insert_block(data.blocks[i].0.clone(), crate::StorageLocation::Database)
+                .unwrap();
+            let block_requests = if i == 1 { Requests::default() } else { requests.clone() };
+            provider_rw
+                .write_state(
+                    &data.blocks[i].1.clone().with_requests(vec![block_requests]),
+                    crate::OriginalValuesKnown::No,
+                    crate::StorageLocation::Database,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(provider_rw.requests_by_block(1).unwrap(), Some(requests.clone()));
+        assert_eq!(provider_rw.requests_by_block(2).unwrap(), None);
+        assert_eq!(provider_rw.requests_by_block(3).unwrap(), Some(requests.clone()));
+
+        let outcome = provider_rw.take_state_above(1, crate::StorageLocation::Database).unwrap();
+        assert_eq!(outcome.requests, vec![Requests::default(), requests.clone()]);
+        assert_eq!(provider_rw.requests_by_block(1).unwrap(), Some(requests));
+        assert_eq!(provider_rw.requests_by_block(3).unwrap(), None);
+
+        provider_rw.remove_state_above(0, crate::StorageLocation::Database).unwrap();
+        assert_eq!(provider_rw.requests_by_block(1).unwrap(), None);
+    }
+
+    #[test]
+    fn change_sets_in_static_files() {
+        use reth_db_api::models::{
+            StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+        };
+
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let account_change = |block: u64| AccountBeforeTx {
+            address,
+            info: Some(Account { nonce: block, ..Default::default() }),
+        };
+        let storage_change =
+            |block: u64| StorageEntry { key: B256::with_last_byte(1), value: U256::from(block) };
+
+        // Blocks 0..=4 were moved to static files, and block 4 wasn't pruned from the database yet.
+        {
+            let static_file_provider = factory.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=4 {
+                let changes = vec![account_change(block)];
+                writer
+                    .append_account_change_set(block, &StaticFileAccountChangeSet { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+            drop(writer);
+
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=4 {
+                let StorageEntry { key, value } = storage_change(block);
+                let changes = vec![StorageBeforeTx { address, key, value }];
+                writer
+                    .append_storage_change_set(block, &StaticFileStorageChangeSet { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 4..=6 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(block, account_change(block))
+                .unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block, address)),
+                    storage_change(block),
+                )
+                .unwrap();
+        }
+
+        let expected_accounts = |range: RangeInclusive<u64>| {
+            range.map(|block| (block, account_change(block))).collect::<Vec<_>>()
+        };
+        let expected_storages = |range: RangeInclusive<u64>| {
+            range
+                .map(|block| (BlockNumberAddress((block, address)), storage_change(block)))
+                .collect::<Vec<_>>()
+        };
+
+        // Reads are served from static files first, without duplicates.
+        assert_eq!(provider_rw.account_changesets_range(2..).unwrap(), expected_accounts(2..=6));
+        assert_eq!(
+            provider_rw.storage_changesets_range(BlockNumberAddress((2, Address::ZERO))..).unwrap(),
+            expected_storages(2..=6)
+        );
+        for block in 0..=6 {
+            assert_eq!(
+                provider_rw.get_account_before_block(block, address).unwrap(),
+                Some(account_change(block))
+            );
+            assert_eq!(
+                provider_rw
+                    .get_storage_before_block(block, address, B256::with_last_byte(1))
+                    .unwrap(),
+                Some(storage_change(block))
+            );
+        }
+
+        // Unwinding below the migrated range takes the changesets from both the static files and
+        // the database, and truncates the static files right away.
+        let (accounts, storages) = provider_rw.take_change_sets_above(2).unwrap();
+        assert_eq!(accounts, expected_accounts(3..=6));
+        assert_eq!(storages, expected_storages(3..=6));
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            assert_eq!(
+                factory.static_file_provider().get_highest_static_file_block(segment),
+                Some(2)
+            );
+        }
+        assert_eq!(provider_rw.account_changesets_range(..).unwrap(), expected_accounts(0..=2));
+        assert_eq!(
+            provider_rw.storage_changesets_range(BlockNumberAddress((0, Address::ZERO))..).unwrap(),
+            expected_storages(0..=2)
+        );
+        assert_eq!(provider_rw.tx_ref().entries::<tables::AccountChangeSets>().unwrap(), 0);
+        assert_eq!(provider_rw.tx_ref().entries::<tables::StorageChangeSets>().unwrap(), 0);
+    }
 }
@@ -1,18 +1,26 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
+    providers::state::macros::delegate_provider_impls, to_range, AccountReader, BlockHashReader,
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Map},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     tables,
     transaction::DbTx,
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode};
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
     BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider,
 };
@@ -22,11 +30,11 @@ use reth_trie::{
     updates::TrieUpdates,
     witness::TrieWitness,
     AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher, MultiProof, MultiProofTargets,
-    StateRoot, StorageMultiProof, StorageRoot, TrieInput,
+    StateRoot, StorageMultiProof, StorageRoot, TrieInput, WitnessSink,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
+    DatabaseTrieWitness,
 };
 
 use std::fmt::Debug;
@@ -40,8 +48,8 @@ use std::fmt::Debug;
 /// - [`tables::AccountsHistory`]
 /// - [`tables::Bytecodes`]
 /// - [`tables::StoragesHistory`]
-/// - [`tables::AccountChangeSets`]
-/// - [`tables::StorageChangeSets`]
+/// - [`tables::AccountChangeSets`], or the account changesets static file segment
+/// - [`tables::StorageChangeSets`], or the storage changesets static file segment
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -60,7 +68,9 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, Provider> {
+impl<'b, Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProviderRef<'b, Provider>
+{
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
         Self { provider, block_number, lowest_available_blocks: Default::default() }
@@ -133,7 +143,66 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
             );
         }
 
-        Ok(HashedPostState::from_reverts::<KeccakKeyHasher>(self.tx(), self.block_number)?)
+        // Changesets of the blocks in static files are older than the ones left in the database,
+        // so they're visited first. Only the first change of every account and slot is kept.
+        let static_file_provider = self.provider.static_file_provider();
+        let mut accounts = AddressMap::<Option<Account>>::default();
+        for change in static_file_provider.account_change_sets_walker(to_range(self.block_number..))
+        {
+            let (_, AccountBeforeTx { address, info }) = change?;
+            accounts.entry(address).or_insert(info);
+        }
+        let database_start = self.database_change_sets_start(StaticFileSegment::AccountChangeSets);
+        for entry in
+            self.tx().cursor_read::<tables::AccountChangeSets>()?.walk_range(database_start..)?
+        {
+            let (_, AccountBeforeTx { address, info }) = entry?;
+            accounts.entry(address).or_insert(info);
+        }
+
+        let mut storages = AddressMap::<B256Map<U256>>::default();
+        for change in static_file_provider.storage_change_sets_walker(to_range(self.block_number..))
+        {
+            let (BlockNumberAddress((_, address)), entry) = change?;
+            storages.entry(address).or_default().entry(entry.key).or_insert(entry.value);
+        }
+        let database_start = self.database_change_sets_start(StaticFileSegment::StorageChangeSets);
+        for entry in self
+            .tx()
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress((database_start, Address::ZERO))..)?
+        {
+            let (BlockNumberAddress((_, address)), entry) = entry?;
+            storages.entry(address).or_default().entry(entry.key).or_insert(entry.value);
+        }
+
+        Ok(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (keccak256(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the first block whose changesets of the segment have to be read from the database,
+    /// because they were not moved to static files.
+    fn database_change_sets_start(&self, segment: StaticFileSegment) -> BlockNumber {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .map_or(self.block_number, |highest| self.block_number.max(highest + 1))
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -150,7 +219,31 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        // Changesets of the blocks in static files are older than the ones left in the database,
+        // so they're visited first. Only the first change of every slot is kept.
+        let mut storage = HashedStorage::new(false);
+        for change in self
+            .provider
+            .static_file_provider()
+            .storage_change_sets_walker(to_range(self.block_number..))
+        {
+            let (BlockNumberAddress((_, changed_address)), entry) = change?;
+            if changed_address == address {
+                storage.storage.entry(keccak256(entry.key)).or_insert(entry.value);
+            }
+        }
+        let database_start = self.database_change_sets_start(StaticFileSegment::StorageChangeSets);
+        for entry in self
+            .tx()
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress((database_start, address))..)?
+        {
+            let (BlockNumberAddress((_, changed_address)), entry) = entry?;
+            if changed_address == address {
+                storage.storage.entry(keccak256(entry.key)).or_insert(entry.value);
+            }
+        }
+        Ok(storage)
     }
 
     fn history_info<T, K>(
@@ -235,29 +328,40 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provider> {
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProviderRef<'_, Provider>
+{
     fn tx(&self) -> &Provider::Tx {
         self.provider.tx_ref()
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> AccountReader
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> AccountReader
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, *address)?
-                .filter(|acc| &acc.address == address)
-                .ok_or(ProviderError::AccountChangesetNotFound {
-                    block_number: changeset_block_number,
-                    address: *address,
-                })?
-                .info),
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let static_file_provider = self.provider.static_file_provider();
+                let account = if static_file_provider
+                    .has_change_sets(StaticFileSegment::AccountChangeSets, changeset_block_number)
+                {
+                    static_file_provider.account_change(changeset_block_number, address)?
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::AccountChangeSets>()?
+                        .seek_by_key_subkey(changeset_block_number, *address)?
+                        .filter(|acc| &acc.address == address)
+                };
+                Ok(account
+                    .ok_or(ProviderError::AccountChangesetNotFound {
+                        block_number: changeset_block_number,
+                        address: *address,
+                    })?
+                    .info)
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
                 Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
             }
@@ -265,8 +369,8 @@ impl<Provider: DBProvider + BlockNumReader> AccountReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StaticFileProviderFactory>
+    BlockHashReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
@@ -282,7 +386,7 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StateRootProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StateRootProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
@@ -318,7 +422,7 @@ impl<Provider: DBProvider + BlockNumReader> StateRootProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StorageRootProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StorageRootProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
@@ -357,7 +461,7 @@ impl<Provider: DBProvider + BlockNumReader> StorageRootProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> StateProofProvider
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> StateProofProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
@@ -386,6 +490,17 @@ impl<Provider: DBProvider + BlockNumReader> StateProofProvider
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
     }
+
+    fn witness_into(
+        &self,
+        mut input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        input.prepend(self.revert_state()?);
+        TrieWitness::overlay_witness_into(self.tx(), input, target, witness)
+            .map_err(ProviderError::from)
+    }
 }
 
 impl<Provider: Sync> HashedPostStateProvider for HistoricalStateProviderRef<'_, Provider> {
@@ -394,8 +509,8 @@ impl<Provider: Sync> HashedPostStateProvider for HistoricalStateProviderRef<'_,
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StaticFileProviderFactory>
+    StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -405,18 +520,27 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
-                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
-                        block_number: changeset_block_number,
-                        address,
-                        storage_key: Box::new(storage_key),
-                    })?
-                    .value,
-            )),
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let static_file_provider = self.provider.static_file_provider();
+                let value = if static_file_provider
+                    .has_change_sets(StaticFileSegment::StorageChangeSets, changeset_block_number)
+                {
+                    static_file_provider
+                        .storage_change(changeset_block_number, &address, &storage_key)?
+                        .map(|entry| entry.value)
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::StorageChangeSets>()?
+                        .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                        .map(|entry| entry.value)
+                };
+                Ok(Some(value.ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                    block_number: changeset_block_number,
+                    address,
+                    storage_key: Box::new(storage_key),
+                })?))
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
                 .tx()
                 .cursor_dup_read::<tables::PlainStorageState>()?
@@ -428,7 +552,7 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader> BytecodeReader
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory> BytecodeReader
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account code by its hash
@@ -449,7 +573,9 @@ pub struct HistoricalStateProvider<Provider> {
     lowest_available_blocks: LowestAvailableBlocks,
 }
 
-impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
+impl<Provider: DBProvider + BlockNumReader + StaticFileProviderFactory>
+    HistoricalStateProvider<Provider>
+{
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
         Self { provider, block_number, lowest_available_blocks: Default::default() }
@@ -485,7 +611,7 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader ]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StaticFileProviderFactory ]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -521,6 +647,7 @@ mod tests {
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StaticFileProviderFactory,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db_api::{
@@ -3,7 +3,11 @@ use crate::{
     HashedPostStateProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -12,7 +16,7 @@ use reth_trie::{
     updates::TrieUpdates,
     witness::TrieWitness,
     AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher, MultiProof, MultiProofTargets,
-    StateRoot, StorageMultiProof, StorageRoot, TrieInput,
+    StateRoot, StorageMultiProof, StorageRoot, TrieInput, WitnessSink,
 };
 use reth_trie_db::{
     DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
@@ -140,6 +144,16 @@ impl<Provider: DBProvider + Sync> StateProofProvider for LatestStateProviderRef<
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
     }
+
+    fn witness_into(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        TrieWitness::overlay_witness_into(self.tx(), input, target, witness)
+            .map_err(ProviderError::from)
+    }
 }
 
 impl<Provider: DBProvider + Sync> HashedPostStateProvider for LatestStateProviderRef<'_, Provider> {
@@ -165,6 +179,42 @@ impl<Provider: DBProvider + BlockHashReader> StateProvider
         }
         Ok(None)
     }
+
+    /// Get basic account information for multiple accounts, using a single cursor.
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let mut cursor = self.tx().cursor_read::<tables::PlainAccountState>()?;
+        let mut accounts = vec![None; addresses.len()];
+        for index in sorted_indices(addresses) {
+            accounts[index] = cursor.seek_exact(addresses[index])?.map(|(_, account)| account);
+        }
+        Ok(accounts)
+    }
+
+    /// Get multiple storage slots, using a single cursor.
+    fn storage_batch(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        let mut cursor = self.tx().cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut values = vec![None; slots.len()];
+        for index in sorted_indices(slots) {
+            let (account, storage_key) = slots[index];
+            values[index] = cursor
+                .seek_by_key_subkey(account, storage_key)?
+                .filter(|entry| entry.key == storage_key)
+                .map(|entry| entry.value);
+        }
+        Ok(values)
+    }
+}
+
+/// Returns the indices of the given keys in ascending key order.
+///
+/// Looking up keys in order keeps the cursor movements short.
+fn sorted_indices<K: Ord>(keys: &[K]) -> Vec<usize> {
+    let mut indices = (0..keys.len()).collect::<Vec<_>>();
+    indices.sort_unstable_by_key(|&index| &keys[index]);
+    indices
 }
 
 impl<Provider: DBProvider + BlockHashReader> BytecodeReader
@@ -199,10 +249,55 @@ delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvid
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{address, b256, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::StorageEntry;
+    use reth_storage_api::DatabaseProviderFactory;
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_latest_state_provider<T: DBProvider + BlockHashReader>() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
+
+    #[test]
+    fn batch_reads() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let alice = address!("0x0000000000000000000000000000000000000001");
+        let bob = address!("0x0000000000000000000000000000000000000002");
+        let missing = address!("0x0000000000000000000000000000000000000003");
+        let slot = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let other_slot =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+
+        let alice_account = Account { nonce: 1, ..Default::default() };
+        let bob_account = Account { nonce: 2, ..Default::default() };
+        tx.put::<tables::PlainAccountState>(alice, alice_account).unwrap();
+        tx.put::<tables::PlainAccountState>(bob, bob_account).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            alice,
+            StorageEntry { key: slot, value: U256::from(10) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainStorageState>(bob, StorageEntry { key: slot, value: U256::from(20) })
+            .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.database_provider_ro().unwrap();
+        let provider = LatestStateProviderRef::new(&db);
+        assert_eq!(
+            provider.basic_accounts(&[bob, missing, alice, bob]).unwrap(),
+            vec![Some(bob_account), None, Some(alice_account), Some(bob_account)]
+        );
+        assert_eq!(
+            provider
+                .storage_batch(&[(bob, slot), (alice, other_slot), (alice, slot), (missing, slot)])
+                .unwrap(),
+            vec![Some(U256::from(20)), None, Some(U256::from(10)), None]
+        );
+        assert_eq!(provider.basic_accounts(&[]).unwrap(), vec![]);
+    }
 }
@@ -39,6 +39,8 @@ macro_rules! delegate_provider_impls {
             }
             StateProvider $(where [$($generics)*])? {
                 fn storage(&self, account: alloy_primitives::Address, storage_key: alloy_primitives::StorageKey) -> reth_storage_errors::provider::ProviderResult<Option<alloy_primitives::StorageValue>>;
+                fn basic_accounts(&self, addresses: &[alloy_primitives::Address]) -> reth_storage_errors::provider::ProviderResult<Vec<Option<reth_primitives_traits::Account>>>;
+                fn storage_batch(&self, slots: &[(alloy_primitives::Address, alloy_primitives::StorageKey)]) -> reth_storage_errors::provider::ProviderResult<Vec<Option<alloy_primitives::StorageValue>>>;
             }
             BytecodeReader $(where [$($generics)*])? {
                 fn bytecode_by_hash(&self, code_hash: &alloy_primitives::B256) -> reth_storage_errors::provider::ProviderResult<Option<reth_primitives_traits::Bytecode>>;
@@ -58,6 +60,7 @@ macro_rules! delegate_provider_impls {
                 fn proof(&self, input: reth_trie::TrieInput, address: alloy_primitives::Address, slots: &[alloy_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<reth_trie::AccountProof>;
                 fn multiproof(&self, input: reth_trie::TrieInput, targets: reth_trie::MultiProofTargets) -> reth_storage_errors::provider::ProviderResult<reth_trie::MultiProof>;
                 fn witness(&self, input: reth_trie::TrieInput, target: reth_trie::HashedPostState) -> reth_storage_errors::provider::ProviderResult<Vec<alloy_primitives::Bytes>>;
+                fn witness_into(&self, input: reth_trie::TrieInput, target: reth_trie::HashedPostState, witness: &mut dyn reth_trie::WitnessSink) -> reth_storage_errors::provider::ProviderResult<()>;
             }
             HashedPostStateProvider $(where [$($generics)*])? {
                 fn hashed_post_state(&self, bundle_state: &revm_database::BundleState) -> reth_trie::HashedPostState;
@@ -0,0 +1,123 @@
+use crate::{
+    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
+    HashedPostStateProvider, StateProvider, StateProviderBox, StateRootProvider,
+};
+use reth_chain_state::{
+    ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates, MemoryOverlayStateProvider,
+};
+use reth_primitives_traits::NodePrimitives;
+use reth_storage_api::{BytecodeReader, StateProofProvider, StorageRootProvider};
+
+/// State provider that layers the outputs of executed blocks, e.g. a pending block, over a
+/// historical state provider.
+///
+/// State of the executed blocks takes precedence over the historical state, so the historical
+/// provider is expected to be at the parent of the oldest executed block.
+#[expect(missing_debug_implementations)]
+pub struct OverlayStateProvider<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives>(
+    MemoryOverlayStateProvider<N>,
+);
+
+impl<N: NodePrimitives> OverlayStateProvider<N> {
+    /// Creates a new provider that layers the given blocks over the historical state.
+    ///
+    /// Blocks are expected to be ordered from newest to oldest.
+    pub fn new(historical: StateProviderBox, blocks: Vec<ExecutedBlock<N>>) -> Self {
+        Self::with_trie_updates(
+            historical,
+            blocks
+                .into_iter()
+                .map(|block| ExecutedBlockWithTrieUpdates {
+                    block,
+                    trie: ExecutedTrieUpdates::Missing,
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a new provider that layers the given blocks over the historical state.
+    ///
+    /// Blocks are expected to be ordered from newest to oldest. Their trie updates are reused for
+    /// state root and proof computations.
+    pub fn with_trie_updates(
+        historical: StateProviderBox,
+        blocks: Vec<ExecutedBlockWithTrieUpdates<N>>,
+    ) -> Self {
+        Self(MemoryOverlayStateProvider::new(historical, blocks))
+    }
+
+    /// Creates a new provider that layers a single block, e.g. the pending block, over the
+    /// historical state of its parent.
+    pub fn pending(historical: StateProviderBox, block: ExecutedBlock<N>) -> Self {
+        Self::new(historical, vec![block])
+    }
+
+    /// Turns this provider into a [`StateProviderBox`].
+    pub fn boxed(self) -> StateProviderBox {
+        Box::new(self)
+    }
+
+    #[inline(always)]
+    const fn as_ref(&self) -> &MemoryOverlayStateProvider<N> {
+        &self.0
+    }
+}
+
+// Delegates all provider impls to [MemoryOverlayStateProvider]
+delegate_provider_impls!(OverlayStateProvider<N> where [N: NodePrimitives]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, ExecutionOutcome};
+    use alloy_primitives::{Address, U256};
+    use reth_db_api::{tables, transaction::DbTxMut};
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_storage_api::DatabaseProviderFactory;
+    use revm_database::BundleState;
+    use std::sync::Arc;
+
+    #[test]
+    fn overlays_executed_block() {
+        let factory = create_test_provider_factory();
+        let unchanged = Address::with_last_byte(1);
+        let changed = Address::with_last_byte(2);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::PlainAccountState>(unchanged, account).unwrap();
+        tx.put::<tables::PlainAccountState>(changed, account).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            changed,
+            StorageEntry { key: Default::default(), value: U256::from(1) },
+        )
+        .unwrap();
+        provider_rw.commit().unwrap();
+
+        let changed_account = Account { nonce: 2, balance: U256::from(5), bytecode_hash: None };
+        let block: ExecutedBlock = ExecutedBlock {
+            execution_output: Arc::new(ExecutionOutcome {
+                bundle: BundleState::builder(0..=0)
+                    .state_original_account_info(changed, account.into())
+                    .state_present_account_info(changed, changed_account.into())
+                    .state_storage(
+                        changed,
+                        std::iter::once((U256::ZERO, (U256::from(1), U256::from(2)))).collect(),
+                    )
+                    .build(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let overlay = OverlayStateProvider::pending(factory.latest().unwrap(), block);
+        assert_eq!(overlay.basic_account(&unchanged).unwrap(), Some(account));
+        assert_eq!(overlay.basic_account(&changed).unwrap(), Some(changed_account));
+        assert_eq!(overlay.storage(changed, Default::default()).unwrap(), Some(U256::from(2)));
+        assert_eq!(
+            overlay.boxed().basic_accounts(&[changed, unchanged]).unwrap(),
+            vec![Some(changed_account), Some(account)]
+        );
+    }
+}
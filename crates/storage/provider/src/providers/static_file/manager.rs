@@ -16,27 +16,32 @@ use alloy_primitives::{
     b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
 };
 use dashmap::DashMap;
+use itertools::Either;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask,
-        StaticFileCursor, TDWithHashMask, TransactionMask,
+        iter_static_files, AccountChangeSetMask, BlockHashMask, ColumnSelectorOne, HeaderMask,
+        HeaderWithHashMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask,
+        TransactionMask,
     },
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::StoredBlockBodyIndices,
+    models::{
+        AccountBeforeTx, BlockNumberAddress, StaticFileAccountChangeSet,
+        StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices,
+    },
     table::{Decompress, Table, Value},
     tables,
     transaction::DbTx,
 };
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
-use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use reth_nippy_jar::{NippyJar, NippyJarChecker, NippyJarError, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
-use reth_primitives_traits::{RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_primitives_traits::{RecoveredBlock, SealedHeader, SignedTransaction, StorageEntry};
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_static_file_types::{
     find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
@@ -872,6 +877,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_tx,
                         highest_block,
                     )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.ensure_change_sets_invariants(provider, segment, highest_block)?
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -894,6 +902,27 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Verifies every static file against its checksum manifest, see
+    /// [`NippyJar::verify_checksums`].
+    ///
+    /// Returns the path and the verification result of each static file. Static files that were
+    /// created before checksum manifests were introduced fail with
+    /// [`NippyJarError::MissingChecksums`].
+    pub fn verify_checksums(&self) -> ProviderResult<Vec<(PathBuf, Result<(), NippyJarError>)>> {
+        let mut results = Vec::new();
+        for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
+            for (block_range, _) in ranges {
+                let file_path = self
+                    .directory()
+                    .join(segment.filename(&self.find_fixed_range(block_range.start())));
+                let result = NippyJar::<SegmentHeader>::load(&file_path)
+                    .and_then(|jar| jar.verify_checksums());
+                results.push((file_path, result));
+            }
+        }
+        Ok(results)
+    }
+
     /// Check invariants for each corresponding table and static file segment:
     ///
     /// * the corresponding database table should overlap or have continuity in their keys
@@ -958,7 +987,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -1006,6 +1037,45 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Check invariants of a changesets static file segment.
+    ///
+    /// Changesets are only moved to static files up to the execution stage checkpoint, and they're
+    /// deleted from the database only after being moved, so the database always holds the
+    /// changesets above the highest static file block. However, if the checkpoint is behind the
+    /// highest static file block, we failed to do a database commit **but committed** to static
+    /// files on unwinding, so the extra static file rows are pruned.
+    ///
+    /// Never requires a pipeline unwind, hence always returns [`None`].
+    fn ensure_change_sets_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: StageCheckpointReader,
+    {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            writer.prune_change_sets(highest_static_file_block - checkpoint_block_number)?;
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
     /// Returns the earliest available block number that has not been expired and is still
     /// available.
     ///
@@ -1058,6 +1128,233 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            account_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+        }
+    }
+
+    /// Returns the part of the block range that is available in the static files of a block-based
+    /// segment, or [`None`] if there is no overlap.
+    fn available_block_range(
+        &self,
+        segment: StaticFileSegment,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> Option<Range<BlockNumber>> {
+        let range = to_range(range);
+        let lowest = self.static_files_min_block.read().get(&segment)?.start();
+        let highest = self.get_highest_static_file_block(segment)?;
+
+        let available = range.start.max(lowest)..range.end.min(highest + 1);
+        (!available.is_empty()).then_some(available)
+    }
+
+    /// Returns `true` if the changesets of the block are in the static files of the changesets
+    /// segment.
+    pub fn has_change_sets(&self, segment: StaticFileSegment, block_number: BlockNumber) -> bool {
+        debug_assert!(segment.is_change_sets());
+        self.available_block_range(segment, block_number..=block_number).is_some()
+    }
+
+    /// Returns the account changes of a block, or [`None`] if the block is not in static files.
+    pub fn account_change_set(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Vec<AccountBeforeTx>>> {
+        if !self.has_change_sets(StaticFileSegment::AccountChangeSets, block_number) {
+            return Ok(None)
+        }
+
+        Ok(self
+            .change_set_row::<AccountChangeSetMask, _>(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |row| StaticFileAccountChangeSet::decompress(row),
+            )?
+            .transpose()?
+            .map(|change_set| change_set.changes))
+    }
+
+    /// Returns the change of the account in a block whose changesets are in static files, or
+    /// [`None`] if the account didn't change in the block.
+    ///
+    /// Only the matching entry of the block row is decoded, see
+    /// [`StaticFileAccountChangeSet::find`].
+    pub fn account_change(
+        &self,
+        block_number: BlockNumber,
+        address: &Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        Ok(self
+            .change_set_row::<AccountChangeSetMask, _>(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |row| StaticFileAccountChangeSet::find(row, address),
+            )?
+            .flatten())
+    }
+
+    /// Returns an iterator over the account changes of the blocks of the range that are in static
+    /// files, keyed by block number the same way as [`tables::AccountChangeSets`].
+    ///
+    /// Rows are read and decoded one block at a time.
+    pub fn account_change_sets_walker(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_ {
+        self.change_set_rows::<AccountChangeSetMask>(StaticFileSegment::AccountChangeSets, range)
+            .flat_map(|row| match row {
+                Ok((number, change_set)) => Either::Left(
+                    change_set.changes.into_iter().map(move |change| Ok((number, change))),
+                ),
+                Err(err) => Either::Right(std::iter::once(Err(err))),
+            })
+    }
+
+    /// Returns the account changes of the blocks of the range that are in static files, keyed by
+    /// block number the same way as [`tables::AccountChangeSets`].
+    pub fn account_change_sets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.account_change_sets_walker(to_range(range)).collect()
+    }
+
+    /// Returns the storage changes of a block, or [`None`] if the block is not in static files.
+    pub fn storage_change_set(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Vec<StorageBeforeTx>>> {
+        if !self.has_change_sets(StaticFileSegment::StorageChangeSets, block_number) {
+            return Ok(None)
+        }
+
+        Ok(self
+            .change_set_row::<StorageChangeSetMask, _>(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                |row| StaticFileStorageChangeSet::decompress(row),
+            )?
+            .transpose()?
+            .map(|change_set| change_set.changes))
+    }
+
+    /// Returns the change of the storage slot in a block whose changesets are in static files, or
+    /// [`None`] if the slot didn't change in the block.
+    ///
+    /// Only the matching entry of the block row is decoded, see
+    /// [`StaticFileStorageChangeSet::find`].
+    pub fn storage_change(
+        &self,
+        block_number: BlockNumber,
+        address: &Address,
+        key: &B256,
+    ) -> ProviderResult<Option<StorageBeforeTx>> {
+        Ok(self
+            .change_set_row::<StorageChangeSetMask, _>(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                |row| StaticFileStorageChangeSet::find(row, address, key),
+            )?
+            .flatten())
+    }
+
+    /// Returns an iterator over the storage changes of the blocks of the range that are in static
+    /// files, keyed by block number and address the same way as [`tables::StorageChangeSets`].
+    ///
+    /// Rows are read and decoded one block at a time.
+    pub fn storage_change_sets_walker(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_ {
+        self.change_set_rows::<StorageChangeSetMask>(StaticFileSegment::StorageChangeSets, range)
+            .flat_map(|row| match row {
+                Ok((number, change_set)) => {
+                    Either::Left(change_set.changes.into_iter().map(move |change| {
+                        Ok((
+                            BlockNumberAddress((number, change.address)),
+                            StorageEntry { key: change.key, value: change.value },
+                        ))
+                    }))
+                }
+                Err(err) => Either::Right(std::iter::once(Err(err))),
+            })
+    }
+
+    /// Returns the storage changes of the blocks of the range that are in static files, keyed by
+    /// block number and address the same way as [`tables::StorageChangeSets`].
+    pub fn storage_change_sets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.storage_change_sets_walker(to_range(range)).collect()
+    }
+
+    /// Reads the raw row of a block from a changesets segment and maps it with `f`.
+    ///
+    /// Returns [`None`] if the block is not in static files.
+    fn change_set_row<M: ColumnSelectorOne, T>(
+        &self,
+        segment: StaticFileSegment,
+        block_number: BlockNumber,
+        f: impl FnOnce(&[u8]) -> T,
+    ) -> ProviderResult<Option<T>> {
+        if !self.has_change_sets(segment, block_number) {
+            return Ok(None)
+        }
+
+        let provider = self.get_segment_provider_from_block(segment, block_number, None)?;
+        let mut cursor = provider.cursor()?;
+        Ok(cursor.get(block_number.into(), M::MASK)?.map(|row| f(row[0])))
+    }
+
+    /// Returns an iterator over the decoded rows of the blocks of the range that are in the static
+    /// files of a changesets segment.
+    fn change_set_rows<M: ColumnSelectorOne + 'static>(
+        &self,
+        segment: StaticFileSegment,
+        range: Range<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<(BlockNumber, M::FIRST)>> + '_ {
+        self.available_block_range(segment, range).unwrap_or_default().map(move |number| {
+            self.get_segment_provider_from_block(segment, number, None)?
+                .cursor()?
+                .get_one::<M>(number.into())?
+                .map(|row| (number, row))
+                .ok_or(ProviderError::MissingStaticFileBlock(segment, number))
+        })
+    }
+
+    /// Deletes all static files of a segment whose whole block range is below `block`.
+    ///
+    /// Only static files that are completely below `block` are deleted, so the lowest remaining
+    /// static file might still hold data below it.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        loop {
+            let Some(block_height) = self.get_lowest_static_file_block(segment) else {
+                return Ok(())
+            };
+
+            // Never delete the highest static file, since it's the one being appended to.
+            if block_height >= block ||
+                self.get_highest_static_file_block(segment)
+                    .is_none_or(|highest| highest <= block_height)
+            {
+                return Ok(())
+            }
+
+            debug!(
+                target: "provider::static_file",
+                ?segment,
+                ?block_height,
+                "Deleting static file below block"
+            );
+
+            self.delete_jar(segment, block_height)?;
         }
     }
 
@@ -58,13 +58,19 @@ mod tests {
         test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
-    use alloy_primitives::{BlockHash, Signature, TxNumber, B256, U256};
+    use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::test_utils::create_test_static_files_dir;
     use reth_db_api::{
-        transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
+        models::{
+            AccountBeforeTx, BlockNumberAddress, StaticFileAccountChangeSet,
+            StaticFileStorageChangeSet, StorageBeforeTx,
+        },
+        transaction::DbTxMut,
+        CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
     };
     use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
+    use reth_primitives_traits::{Account, StorageEntry};
     use reth_static_file_types::{
         find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     };
@@ -163,7 +169,7 @@ mod tests {
         let (static_dir, _) = create_test_static_files_dir();
 
         let blocks_per_file = 10; // Number of headers per file
-        let files_per_range = 3; // Number of files per range (data/conf/offset files)
+        let files_per_range = 4; // Number of files per range (data/conf/offset/checksum files)
         let file_set_count = 3; // Number of sets of files to create
         let initial_file_count = files_per_range * file_set_count;
         let tip = blocks_per_file * file_set_count - 1; // Initial highest block (29 in this case)
@@ -409,7 +415,7 @@ mod tests {
     fn test_tx_based_truncation() {
         let segments = [StaticFileSegment::Transactions, StaticFileSegment::Receipts];
         let blocks_per_file = 10; // Number of blocks per file
-        let files_per_range = 3; // Number of files per range (data/conf/offset files)
+        let files_per_range = 4; // Number of files per range (data/conf/offset/checksum files)
         let file_set_count = 3; // Number of sets of files to create
         let initial_file_count = files_per_range * file_set_count;
 
@@ -550,6 +556,139 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_change_sets() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let blocks_per_file = 10;
+        let tip = blocks_per_file * 3 - 1;
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+
+        // Every block changes two accounts and one storage slot of each, except for every fifth
+        // block, which doesn't change anything.
+        let account_changes = |block: u64| {
+            (block % 5 != 0)
+                .then(|| {
+                    [block, block + 1].map(|byte| AccountBeforeTx {
+                        address: Address::with_last_byte(byte as u8),
+                        info: Some(Account { nonce: block, ..Default::default() }),
+                    })
+                })
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        let storage_changes = |block: u64| {
+            account_changes(block)
+                .into_iter()
+                .map(|change| StorageBeforeTx {
+                    address: change.address,
+                    key: B256::with_last_byte(1),
+                    value: U256::from(block),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // [ Write ]
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=tip {
+                let changes = account_changes(block);
+                writer
+                    .append_account_change_set(block, &StaticFileAccountChangeSet { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=tip {
+                let changes = storage_changes(block);
+                writer
+                    .append_storage_change_set(block, &StaticFileStorageChangeSet { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // [ Read ]
+        for block in 0..=tip {
+            assert_eq!(sf_rw.account_change_set(block).unwrap(), Some(account_changes(block)));
+            assert_eq!(sf_rw.storage_change_set(block).unwrap(), Some(storage_changes(block)));
+            for change in account_changes(block) {
+                assert_eq!(sf_rw.account_change(block, &change.address).unwrap(), Some(change));
+            }
+            for change in storage_changes(block) {
+                assert_eq!(
+                    sf_rw.storage_change(block, &change.address, &change.key).unwrap(),
+                    Some(change)
+                );
+            }
+            assert_eq!(sf_rw.account_change(block, &Address::ZERO).unwrap(), None);
+        }
+        assert_eq!(sf_rw.account_change_set(tip + 1).unwrap(), None);
+
+        // Ranges span multiple static files.
+        let expected_accounts = |range: Range<u64>| {
+            range
+                .flat_map(|block| account_changes(block).into_iter().map(move |c| (block, c)))
+                .collect::<Vec<_>>()
+        };
+        let expected_storages = |range: Range<u64>| {
+            range
+                .flat_map(|block| {
+                    storage_changes(block).into_iter().map(move |change| {
+                        (
+                            BlockNumberAddress((block, change.address)),
+                            StorageEntry { key: change.key, value: change.value },
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sf_rw.account_change_sets_range(5..25).unwrap(), expected_accounts(5..25));
+        assert_eq!(sf_rw.storage_change_sets_range(5..25).unwrap(), expected_storages(5..25));
+        assert_eq!(sf_rw.account_change_sets_range(25..).unwrap(), expected_accounts(25..tip + 1));
+
+        // [ Unwind ]
+        let unwind_to = tip - 12;
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            let mut writer = sf_rw.latest_writer(segment).unwrap();
+            writer.prune_change_sets(tip - unwind_to).unwrap();
+            writer.commit().unwrap();
+            assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(unwind_to));
+        }
+        assert_eq!(sf_rw.account_change_set(unwind_to + 1).unwrap(), None);
+        assert_eq!(sf_rw.storage_change_set(unwind_to + 1).unwrap(), None);
+        assert_eq!(
+            sf_rw.account_change_sets_range(..).unwrap(),
+            expected_accounts(0..unwind_to + 1)
+        );
+        assert_eq!(
+            sf_rw.storage_change_sets_range(..).unwrap(),
+            expected_storages(0..unwind_to + 1)
+        );
+
+        // [ Prune ]
+        // Only the static files that are completely below the block are deleted.
+        sf_rw.delete_segment_below_block(StaticFileSegment::AccountChangeSets, 15).unwrap();
+        assert_eq!(
+            sf_rw.get_lowest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(19)
+        );
+        assert_eq!(sf_rw.account_change_set(9).unwrap(), None);
+        assert_eq!(sf_rw.account_change_set(10).unwrap(), Some(account_changes(10)));
+        assert_eq!(
+            sf_rw.account_change_sets_range(..).unwrap(),
+            expected_accounts(10..unwind_to + 1)
+        );
+        // The storage changesets are pruned separately.
+        assert_eq!(sf_rw.storage_change_set(9).unwrap(), Some(storage_changes(9)));
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
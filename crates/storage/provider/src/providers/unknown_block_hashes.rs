@@ -0,0 +1,91 @@
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use schnellru::{ByLength, LruMap};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The default number of unknown block hashes that are remembered.
+pub(crate) const DEFAULT_UNKNOWN_BLOCK_HASHES_CAPACITY: u32 = 1024;
+
+/// The default duration for which a block hash is considered unknown after a failed lookup.
+pub(crate) const DEFAULT_UNKNOWN_BLOCK_HASH_TTL: Duration = Duration::from_secs(12);
+
+/// A negative lookup cache of block hashes that were recently found to be unknown.
+///
+/// Consensus clients and searchers repeatedly query the same nonexistent hashes during reorgs, so
+/// remembering them saves an index lookup per query.
+///
+/// A block hash can only become known if the canonical chain changes, so entries are only valid as
+/// long as the chain head they were recorded at is still the head. They additionally expire after
+/// a short time, to account for blocks that are written to the database without moving the head,
+/// e.g. by the pipeline.
+#[derive(Debug, Clone)]
+pub(crate) struct UnknownBlockHashes {
+    /// The unknown block hashes, mapped to the chain head and the time they were recorded at.
+    ///
+    /// Lookups only take the read lock, so they don't contend with each other.
+    hashes: Arc<RwLock<LruMap<B256, (B256, Instant)>>>,
+    /// How long an entry is valid for.
+    ttl: Duration,
+}
+
+impl Default for UnknownBlockHashes {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNKNOWN_BLOCK_HASHES_CAPACITY, DEFAULT_UNKNOWN_BLOCK_HASH_TTL)
+    }
+}
+
+impl UnknownBlockHashes {
+    /// Creates a new cache that remembers up to `capacity` hashes for the given duration.
+    pub(crate) fn new(capacity: u32, ttl: Duration) -> Self {
+        Self { hashes: Arc::new(RwLock::new(LruMap::new(ByLength::new(capacity)))), ttl }
+    }
+
+    /// Returns true if the block hash is known to be unknown at the given chain head.
+    pub(crate) fn contains(&self, hash: &B256, head: &B256) -> bool {
+        let Some((recorded_head, recorded_at)) = self.hashes.read().peek(hash).copied() else {
+            return false
+        };
+        if recorded_head == *head && recorded_at.elapsed() < self.ttl {
+            return true
+        }
+        self.hashes.write().remove(hash);
+        false
+    }
+
+    /// Records that the block hash is unknown at the given chain head.
+    pub(crate) fn insert(&self, hash: B256, head: B256) {
+        self.hashes.write().insert(hash, (head, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidates_entries() {
+        let hashes = UnknownBlockHashes::new(2, Duration::from_secs(60));
+        let (hash, head) = (B256::random(), B256::random());
+
+        assert!(!hashes.contains(&hash, &head));
+        hashes.insert(hash, head);
+        assert!(hashes.contains(&hash, &head));
+
+        // the hash may have become known with the new head
+        assert!(!hashes.contains(&hash, &B256::random()));
+        assert!(!hashes.contains(&hash, &head));
+
+        // the least recently used hash is evicted
+        hashes.insert(hash, head);
+        hashes.insert(B256::random(), head);
+        hashes.insert(B256::random(), head);
+        assert!(!hashes.contains(&hash, &head));
+
+        let hashes = UnknownBlockHashes::new(2, Duration::ZERO);
+        hashes.insert(hash, head);
+        assert!(!hashes.contains(&hash, &head));
+    }
+}
@@ -2,34 +2,38 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     ChainSpecProvider, ChangeSetReader, HeaderProvider, ReceiptProviderIdExt, StateProvider,
-    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
-    TransactionsProvider,
+    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageChangeSetReader,
+    TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{constants::EMPTY_ROOT_HASH, transaction::TransactionMeta, BlockHeader};
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip7685::Requests, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
     keccak256, map::HashMap, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue,
     TxHash, TxNumber, B256, U256,
 };
 use parking_lot::Mutex;
-use reth_chain_state::{CanonStateNotifications, CanonStateSubscriptions};
+use reth_chain_state::{
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions,
+};
 use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, BlockNumberAddress, ContractDeployment, StoredBlockBodyIndices},
 };
 use reth_ethereum_primitives::EthPrimitives;
-use reth_execution_types::ExecutionOutcome;
+use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_primitives_traits::{
     Account, Block, BlockBody, Bytecode, GotExpected, NodePrimitives, RecoveredBlock, SealedHeader,
-    SignedTransaction, SignerRecoverable,
+    SignedTransaction, SignerRecoverable, StorageEntry,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageRootProvider,
+    BlockBodyIndicesProvider, BytecodeReader, ContractDeploymentsProvider, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, NodePrimitivesProvider,
+    PruneCheckpointReader, RequestsProvider, SenderTransactionsProvider, StageCheckpointReader,
+    StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -44,6 +48,9 @@ use std::{
 };
 use tokio::sync::broadcast;
 
+/// The storage slots changed in a block, with their values from before the block.
+type StorageChangeset = Vec<(Address, StorageEntry)>;
+
 /// A mock implementation for Provider interfaces.
 #[derive(Debug)]
 pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_chainspec::ChainSpec>
@@ -54,6 +61,8 @@ pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_c
     pub headers: Arc<Mutex<HashMap<B256, <T::Block as Block>::Header>>>,
     /// Local receipt store indexed by block number
     pub receipts: Arc<Mutex<HashMap<BlockNumber, Vec<T::Receipt>>>>,
+    /// Local requests store indexed by block number
+    pub requests: Arc<Mutex<HashMap<BlockNumber, Requests>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
     /// Local chain spec
@@ -62,6 +71,14 @@ pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_c
     pub state_roots: Arc<Mutex<Vec<B256>>>,
     /// Local block body indices store
     pub block_body_indices: Arc<Mutex<HashMap<BlockNumber, StoredBlockBodyIndices>>>,
+    /// Local account changeset store indexed by block number
+    pub account_changesets: Arc<Mutex<HashMap<BlockNumber, Vec<AccountBeforeTx>>>>,
+    /// Local storage changeset store indexed by block number
+    pub storage_changesets: Arc<Mutex<HashMap<BlockNumber, StorageChangeset>>>,
+    /// Local prune checkpoint store
+    pub prune_checkpoints: Arc<Mutex<HashMap<PruneSegment, PruneCheckpoint>>>,
+    /// Sender for canonical state notifications, e.g. of simulated reorgs
+    canon_state_notification_sender: CanonStateNotificationSender<T>,
     tx: TxMock,
     prune_modes: Arc<PruneModes>,
 }
@@ -75,10 +92,15 @@ where
             blocks: self.blocks.clone(),
             headers: self.headers.clone(),
             receipts: self.receipts.clone(),
+            requests: self.requests.clone(),
             accounts: self.accounts.clone(),
             chain_spec: self.chain_spec.clone(),
             state_roots: self.state_roots.clone(),
             block_body_indices: self.block_body_indices.clone(),
+            account_changesets: self.account_changesets.clone(),
+            storage_changesets: self.storage_changesets.clone(),
+            prune_checkpoints: self.prune_checkpoints.clone(),
+            canon_state_notification_sender: self.canon_state_notification_sender.clone(),
             tx: self.tx.clone(),
             prune_modes: self.prune_modes.clone(),
         }
@@ -92,10 +114,18 @@ impl<T: NodePrimitives> MockEthProvider<T, reth_chainspec::ChainSpec> {
             blocks: Default::default(),
             headers: Default::default(),
             receipts: Default::default(),
+            requests: Default::default(),
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
             block_body_indices: Default::default(),
+            account_changesets: Default::default(),
+            storage_changesets: Default::default(),
+            prune_checkpoints: Default::default(),
+            canon_state_notification_sender: broadcast::channel(
+                CANON_STATE_NOTIFICATION_CHANNEL_SIZE,
+            )
+            .0,
             tx: Default::default(),
             prune_modes: Default::default(),
         }
@@ -156,6 +186,11 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
         }
     }
 
+    /// Add the requests of a block to local requests store
+    pub fn add_requests(&self, block_number: BlockNumber, requests: Requests) {
+        self.requests.lock().insert(block_number, requests);
+    }
+
     /// Add block body indices to local store
     pub fn add_block_body_indices(
         &self,
@@ -170,22 +205,132 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
         self.state_roots.lock().push(state_root);
     }
 
+    /// Add the account changeset of a block to the local changeset store
+    pub fn add_account_changeset(
+        &self,
+        block_number: BlockNumber,
+        changeset: Vec<AccountBeforeTx>,
+    ) {
+        self.account_changesets.lock().insert(block_number, changeset);
+    }
+
+    /// Add the storage changeset of a block to the local changeset store
+    pub fn add_storage_changeset(&self, block_number: BlockNumber, changeset: StorageChangeset) {
+        self.storage_changesets.lock().insert(block_number, changeset);
+    }
+
+    /// Add the prune checkpoint of a segment to the local prune checkpoint store
+    pub fn add_prune_checkpoint(&self, segment: PruneSegment, checkpoint: PruneCheckpoint) {
+        self.prune_checkpoints.lock().insert(segment, checkpoint);
+    }
+
+    /// Set the prune modes the provider is configured with.
+    pub fn with_prune_modes(mut self, prune_modes: PruneModes) -> Self {
+        self.prune_modes = Arc::new(prune_modes);
+        self
+    }
+
+    /// Simulates a reorg to the given chain of blocks and their receipts.
+    ///
+    /// All blocks at or above the first block of the new chain are removed, together with their
+    /// receipts, requests and changesets, and replaced by the new chain. Subscribers of the
+    /// canonical state are notified with a [`CanonStateNotification::Reorg`], or a
+    /// [`CanonStateNotification::Commit`] if no blocks were removed.
+    pub fn reorg(&self, new_chain: impl IntoIterator<Item = (B256, T::Block, Vec<T::Receipt>)>) {
+        let mut new_chain = new_chain.into_iter().collect::<Vec<_>>();
+        new_chain.sort_by_key(|(_, block, _)| block.header().number());
+        let Some(fork_block) = new_chain.first().map(|(_, block, _)| block.header().number())
+        else {
+            return
+        };
+
+        // remove the old chain
+        let old_hashes = self
+            .headers
+            .lock()
+            .iter()
+            .filter(|(_, header)| header.number() >= fork_block)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        let mut old_receipts = self.receipts.lock();
+        let mut old_chain = Vec::with_capacity(old_hashes.len());
+        for hash in old_hashes {
+            self.headers.lock().remove(&hash);
+            if let Some(block) = self.blocks.lock().remove(&hash) {
+                let receipts = old_receipts.remove(&block.header().number()).unwrap_or_default();
+                old_chain.push((hash, block, receipts));
+            }
+        }
+        old_receipts.retain(|number, _| *number < fork_block);
+        drop(old_receipts);
+        self.requests.lock().retain(|number, _| *number < fork_block);
+        self.account_changesets.lock().retain(|number, _| *number < fork_block);
+        self.storage_changesets.lock().retain(|number, _| *number < fork_block);
+
+        // insert the new chain
+        for (hash, block, receipts) in &new_chain {
+            self.add_receipts(block.header().number(), receipts.clone());
+            self.add_block(*hash, block.clone());
+        }
+
+        let new = mock_chain::<T>(new_chain).expect("new chain is not empty");
+        let notification = match mock_chain::<T>(old_chain) {
+            Some(old) => CanonStateNotification::Reorg { old, new },
+            None => CanonStateNotification::Commit { new },
+        };
+        // there may be no subscribers
+        let _ = self.canon_state_notification_sender.send(notification);
+    }
+
     /// Set chain spec.
     pub fn with_chain_spec<C>(self, chain_spec: C) -> MockEthProvider<T, C> {
         MockEthProvider {
             blocks: self.blocks,
             headers: self.headers,
             receipts: self.receipts,
+            requests: self.requests,
             accounts: self.accounts,
             chain_spec: Arc::new(chain_spec),
             state_roots: self.state_roots,
             block_body_indices: self.block_body_indices,
+            account_changesets: self.account_changesets,
+            storage_changesets: self.storage_changesets,
+            prune_checkpoints: self.prune_checkpoints,
+            canon_state_notification_sender: self.canon_state_notification_sender,
             tx: self.tx,
             prune_modes: self.prune_modes,
         }
     }
 }
 
+/// The size of the broadcast channel for canonical state notifications.
+const CANON_STATE_NOTIFICATION_CHANNEL_SIZE: usize = 256;
+
+/// Creates a [`Chain`] from the given blocks and their receipts, or `None` if there are no blocks.
+///
+/// Senders that cannot be recovered are set to the zero address.
+fn mock_chain<T: NodePrimitives>(
+    mut blocks: Vec<(B256, T::Block, Vec<T::Receipt>)>,
+) -> Option<Arc<Chain<T>>> {
+    blocks.sort_by_key(|(_, block, _)| block.header().number());
+    let first_block = blocks.first()?.1.header().number();
+    let (blocks, receipts): (Vec<_>, Vec<_>) = blocks
+        .into_iter()
+        .map(|(hash, block, receipts)| {
+            let senders = block
+                .body()
+                .transactions()
+                .iter()
+                .map(|tx| tx.recover_signer().unwrap_or_default())
+                .collect();
+            (RecoveredBlock::new(block, senders, hash), receipts)
+        })
+        .unzip();
+    let execution_outcome =
+        ExecutionOutcome::new(Default::default(), receipts, first_block, vec![]);
+    Some(Arc::new(Chain::new(blocks, execution_outcome, None)))
+}
+
 impl Default for MockEthProvider {
     fn default() -> Self {
         Self::new()
@@ -490,16 +635,30 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + 'static> TransactionsProvider
 impl<T, ChainSpec> ReceiptProvider for MockEthProvider<T, ChainSpec>
 where
     T: NodePrimitives,
-    ChainSpec: Send + Sync + 'static,
+    ChainSpec: EthChainSpec + 'static,
 {
     type Receipt = T::Receipt;
 
-    fn receipt(&self, _id: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
-        Ok(None)
+    fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
+        let Some(tx) = self.transaction_by_id(id)? else { return Ok(None) };
+        self.receipt_by_hash(*tx.tx_hash())
     }
 
-    fn receipt_by_hash(&self, _hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
-        Ok(None)
+    fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
+        let location = self.blocks.lock().values().find_map(|block| {
+            block
+                .body()
+                .transactions_iter()
+                .position(|tx| *tx.tx_hash() == hash)
+                .map(|index| (block.header().number(), index))
+        });
+        let Some((block_number, index)) = location else { return Ok(None) };
+        Ok(self
+            .receipts
+            .lock()
+            .get(&block_number)
+            .and_then(|receipts| receipts.get(index))
+            .cloned())
     }
 
     fn receipts_by_block(
@@ -524,9 +683,13 @@ where
 
     fn receipts_by_tx_range(
         &self,
-        _range: impl RangeBounds<TxNumber>,
+        range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Receipt>> {
-        Ok(vec![])
+        let mut receipts = Vec::new();
+        for tx in self.transactions_by_tx_range(range)? {
+            receipts.extend(self.receipt_by_hash(*tx.tx_hash())?);
+        }
+        Ok(receipts)
     }
 
     fn receipts_by_block_range(
@@ -669,18 +832,20 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + Send + Sync + 'static> BlockRe
 
     fn recovered_block(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.block(id)?
+            .map(|block| block.try_into_recovered().map_err(|_| ProviderError::SenderRecoveryError))
+            .transpose()
     }
 
     fn sealed_block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
-        _transaction_kind: TransactionVariant,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.recovered_block(id, transaction_kind)
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
@@ -760,6 +925,30 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> StageCheckpointReader
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> PruneCheckpointReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn get_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(self.prune_checkpoints.lock().get(&segment).copied())
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(self
+            .prune_checkpoints
+            .lock()
+            .iter()
+            .map(|(segment, checkpoint)| (*segment, *checkpoint))
+            .collect())
+    }
+
+    fn prune_modes(&self) -> ProviderResult<PruneModes> {
+        Ok(self.prune_modes.as_ref().clone())
+    }
+}
+
 impl<T, ChainSpec> StateRootProvider for MockEthProvider<T, ChainSpec>
 where
     T: NodePrimitives,
@@ -963,9 +1152,98 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
-        _block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        Ok(Vec::default())
+        Ok(self.account_changesets.lock().get(&block_number).cloned().unwrap_or_default())
+    }
+
+    fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let mut changesets = self
+            .account_changesets
+            .lock()
+            .iter()
+            .filter(|(block_number, _)| range.contains(*block_number))
+            .flat_map(|(block_number, changes)| {
+                changes.iter().map(|change| (*block_number, change.clone()))
+            })
+            .collect::<Vec<_>>();
+        changesets.sort_by_key(|(block_number, change)| (*block_number, change.address));
+        Ok(changesets)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> SenderTransactionsProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn sender_transaction_ids(
+        &self,
+        _sender: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> RequestsProvider for MockEthProvider<T, ChainSpec> {
+    fn requests_by_block(&self, block: BlockNumber) -> ProviderResult<Option<Requests>> {
+        Ok(self.requests.lock().get(&block).cloned())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> ContractDeploymentsProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn contract_deployments_indexed(&self) -> ProviderResult<bool> {
+        Ok(false)
+    }
+
+    fn contract_deployment(
+        &self,
+        _address: Address,
+        _code_hash: B256,
+    ) -> ProviderResult<Option<ContractDeployment>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> StorageChangeSetReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .storage_changesets
+            .lock()
+            .get(&block_number)
+            .into_iter()
+            .flatten()
+            .map(|(address, entry)| (BlockNumberAddress((block_number, *address)), *entry))
+            .collect())
+    }
+
+    fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let mut changesets = self
+            .storage_changesets
+            .lock()
+            .iter()
+            .flat_map(|(block_number, changes)| {
+                changes
+                    .iter()
+                    .map(|(address, entry)| (BlockNumberAddress((*block_number, *address)), *entry))
+            })
+            .filter(|(key, _)| range.contains(key))
+            .collect::<Vec<_>>();
+        changesets.sort_by_key(|(key, entry)| (*key, entry.key));
+        Ok(changesets)
     }
 }
 
@@ -984,7 +1262,7 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> CanonStateSubscriptions
     for MockEthProvider<T, ChainSpec>
 {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<T> {
-        broadcast::channel(1).1
+        self.canon_state_notification_sender.subscribe()
     }
 }
 
@@ -1063,4 +1341,64 @@ mod tests {
         assert_eq!(partial_range.len(), 1);
         assert_eq!(partial_range[0], receipts1);
     }
+
+    #[test]
+    fn test_mock_provider_changesets() {
+        let provider = MockEthProvider::<EthPrimitives>::new();
+
+        let address = Address::random();
+        let account_changeset = vec![AccountBeforeTx { address, info: Some(Account::default()) }];
+        let storage_entry = StorageEntry { key: B256::random(), value: U256::from(1) };
+        provider.add_account_changeset(1, account_changeset.clone());
+        provider.add_storage_changeset(1, vec![(address, storage_entry)]);
+
+        assert_eq!(provider.account_block_changeset(1).unwrap(), account_changeset);
+        assert_eq!(
+            provider.storage_changeset(1).unwrap(),
+            vec![(BlockNumberAddress((1, address)), storage_entry)]
+        );
+        assert!(provider.account_block_changeset(2).unwrap().is_empty());
+        assert!(provider.storage_changeset(2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mock_provider_reorg() {
+        let provider = MockEthProvider::<EthPrimitives>::new();
+        let mut notifications = provider.subscribe_to_canonical_state();
+
+        let block = |number: u64, extra_data: &'static [u8]| reth_ethereum_primitives::Block {
+            header: Header { number, extra_data: extra_data.into(), ..Default::default() },
+            body: Default::default(),
+        };
+        let receipt = |cumulative_gas_used| {
+            vec![Receipt { cumulative_gas_used, success: true, ..Default::default() }]
+        };
+
+        let (hash1, hash2, reorg_hash2, reorg_hash3) =
+            (B256::random(), B256::random(), B256::random(), B256::random());
+        provider.add_block(hash1, block(1, b""));
+        provider.add_block(hash2, block(2, b""));
+        provider.add_receipts(2, receipt(1));
+        provider.add_account_changeset(2, vec![AccountBeforeTx::default()]);
+
+        provider.reorg([
+            (reorg_hash2, block(2, b"reorg"), receipt(2)),
+            (reorg_hash3, block(3, b"reorg"), receipt(3)),
+        ]);
+
+        assert_eq!(provider.block_hash(1).unwrap(), Some(hash1));
+        assert_eq!(provider.block_hash(2).unwrap(), Some(reorg_hash2));
+        assert_eq!(provider.block_hash(3).unwrap(), Some(reorg_hash3));
+        assert_eq!(provider.header(&hash2).unwrap(), None);
+        assert_eq!(provider.receipts_by_block(2.into()).unwrap(), Some(receipt(2)));
+        assert!(provider.account_block_changeset(2).unwrap().is_empty());
+
+        let CanonStateNotification::Reorg { old, new } = notifications.try_recv().unwrap() else {
+            panic!("expected reorg notification")
+        };
+        assert_eq!(old.tip().hash(), hash2);
+        assert_eq!(old.execution_outcome().receipts(), &vec![receipt(1)]);
+        assert_eq!(new.fork_block().number, 1);
+        assert_eq!(new.tip().hash(), reorg_hash3);
+    }
 }
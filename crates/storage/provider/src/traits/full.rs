@@ -2,8 +2,10 @@
 
 use crate::{
     AccountReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, StageCheckpointReader, StateProviderFactory,
-    StateReader, StaticFileProviderFactory,
+    ContractDeploymentsProvider, DatabaseProviderFactory, HashedPostStateProvider,
+    HashedStateRangeProviderFactory, PruneCheckpointReader, RequestsProvider,
+    SenderTransactionsProvider, StageCheckpointReader, StateProviderFactory, StateReader,
+    StaticFileProviderFactory, StorageChangeSetReader,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
@@ -12,8 +14,10 @@ use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
 pub trait FullProvider<N: NodeTypesWithDB>:
-    DatabaseProviderFactory<DB = N::DB, Provider: BlockReader>
-    + NodePrimitivesProvider<Primitives = N::Primitives>
+    DatabaseProviderFactory<
+        DB = N::DB,
+        Provider: BlockReader + ChangeSetReader + StorageChangeSetReader,
+    > + NodePrimitivesProvider<Primitives = N::Primitives>
     + StaticFileProviderFactory<Primitives = N::Primitives>
     + BlockReaderIdExt<
         Transaction = TxTy<N>,
@@ -24,11 +28,16 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + StateProviderFactory
     + StateReader
     + HashedPostStateProvider
+    + HashedStateRangeProviderFactory
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + SenderTransactionsProvider
+    + ContractDeploymentsProvider
+    + RequestsProvider
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + PruneCheckpointReader
     + Clone
     + Debug
     + Unpin
@@ -37,8 +46,10 @@ pub trait FullProvider<N: NodeTypesWithDB>:
 }
 
 impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
-    T: DatabaseProviderFactory<DB = N::DB, Provider: BlockReader>
-        + NodePrimitivesProvider<Primitives = N::Primitives>
+    T: DatabaseProviderFactory<
+            DB = N::DB,
+            Provider: BlockReader + ChangeSetReader + StorageChangeSetReader,
+        > + NodePrimitivesProvider<Primitives = N::Primitives>
         + StaticFileProviderFactory<Primitives = N::Primitives>
         + BlockReaderIdExt<
             Transaction = TxTy<N>,
@@ -49,11 +60,16 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + StateProviderFactory
         + StateReader
         + HashedPostStateProvider
+        + HashedStateRangeProviderFactory
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + SenderTransactionsProvider
+        + ContractDeploymentsProvider
+        + RequestsProvider
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + PruneCheckpointReader
         + Clone
         + Debug
         + Unpin
@@ -25,7 +25,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
-use alloy_eips::{BlockHashOrNumber, BlockNumberOrTag};
+use alloy_eips::{eip7685::Requests, BlockHashOrNumber, BlockNumberOrTag};
 use alloy_network::{primitives::HeaderResponse, BlockResponse};
 use alloy_primitives::{
     map::HashMap, Address, BlockHash, BlockNumber, StorageKey, TxHash, TxNumber, B256, U256,
@@ -56,14 +56,15 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_rpc_convert::{TryFromBlockResponse, TryFromReceiptResponse, TryFromTransactionResponse};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockReaderIdExt, BlockSource, DBProvider, NodePrimitivesProvider,
-    ReceiptProviderIdExt, StatsReader,
+    BlockBodyIndicesProvider, BlockReaderIdExt, BlockSource, ContractDeploymentsProvider,
+    DBProvider, NodePrimitivesProvider, ReceiptProviderIdExt, RequestsProvider,
+    SenderTransactionsProvider, StatsReader,
 };
 use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, MultiProof, TrieInput};
 use std::{
     collections::BTreeMap,
     future::{Future, IntoFuture},
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     sync::Arc,
 };
 use tokio::{runtime::Handle, sync::broadcast};
@@ -1715,6 +1716,10 @@ where
     fn get_prune_checkpoints(&self) -> Result<Vec<(PruneSegment, PruneCheckpoint)>, ProviderError> {
         Err(ProviderError::UnsupportedProvider)
     }
+
+    fn prune_modes(&self) -> Result<reth_prune_types::PruneModes, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 impl<P, Node, N> StageCheckpointReader for RpcBlockchainStateProvider<P, Node, N>
@@ -1753,6 +1758,62 @@ where
     }
 }
 
+impl<P, Node, N> SenderTransactionsProvider for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn sender_transaction_ids(
+        &self,
+        _sender: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> Result<Option<Vec<TxNumber>>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn address_transaction_ids(
+        &self,
+        _address: Address,
+        _range: Range<TxNumber>,
+        _limit: usize,
+        _reverse: bool,
+    ) -> Result<Option<Vec<TxNumber>>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl<P, Node, N> RequestsProvider for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn requests_by_block(&self, _block: BlockNumber) -> Result<Option<Requests>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl<P, Node, N> ContractDeploymentsProvider for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn contract_deployments_indexed(&self) -> Result<bool, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn contract_deployment(
+        &self,
+        _address: Address,
+        _code_hash: B256,
+    ) -> Result<Option<reth_db_api::models::ContractDeployment>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
 impl<P, Node, N> StateProviderFactory for RpcBlockchainStateProvider<P, Node, N>
 where
     P: Provider<N> + Clone + 'static + Send + Sync,
@@ -2,12 +2,12 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, B256};
 use auto_impl::auto_impl;
-use core::ops::{RangeBounds, RangeInclusive};
-use reth_db_models::AccountBeforeTx;
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+use reth_db_models::{AccountBeforeTx, ContractDeployment};
 use reth_primitives_traits::Account;
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// Account reader
 #[auto_impl(&, Arc, Box)]
@@ -54,4 +54,75 @@ pub trait ChangeSetReader {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the state of the account from before the given block, if the account changed in the
+    /// block.
+    fn get_account_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        Ok(self
+            .account_block_changeset(block_number)?
+            .into_iter()
+            .find(|change| change.address == address))
+    }
+
+    /// Returns the account changesets of the block range, ordered by block number.
+    ///
+    /// The default implementation reads the changesets block by block, so it doesn't support
+    /// ranges without an end bound.
+    fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let mut changesets = Vec::new();
+        for block_number in bounded_block_range(range)? {
+            changesets.extend(
+                self.account_block_changeset(block_number)?
+                    .into_iter()
+                    .map(|change| (block_number, change)),
+            );
+        }
+        Ok(changesets)
+    }
+}
+
+/// Converts the block range to an inclusive range, failing if it has no end bound.
+pub(crate) fn bounded_block_range(
+    range: impl RangeBounds<BlockNumber>,
+) -> ProviderResult<RangeInclusive<BlockNumber>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&0) =>
+        {
+            #[expect(clippy::reversed_empty_ranges)]
+            return Ok(1..=0)
+        }
+        Bound::Excluded(&end) => end - 1,
+        Bound::Unbounded => return Err(ProviderError::UnsupportedProvider),
+    };
+    Ok(start..=end)
+}
+
+/// Client trait for reading the index of the contracts deployed with each code hash.
+///
+/// The index is opt-in, see the `IndexContractDeployments` stage.
+#[auto_impl(&, Arc)]
+pub trait ContractDeploymentsProvider: Send + Sync {
+    /// Returns `true` if the contract deployments index is enabled.
+    fn contract_deployments_indexed(&self) -> ProviderResult<bool>;
+
+    /// Returns the indexed deployment of the contract at the given address with the given code
+    /// hash.
+    fn contract_deployment(
+        &self,
+        address: Address,
+        code_hash: B256,
+    ) -> ProviderResult<Option<ContractDeployment>>;
 }
@@ -1,3 +1,4 @@
+use crate::{HashedStateRangeProvider, HashedStateRangeProviderFactory};
 use alloc::vec::Vec;
 use core::ops::{Bound, RangeBounds};
 use reth_db_api::{
@@ -159,6 +160,17 @@ pub trait DatabaseProviderFactory: Send + Sync {
     fn database_provider_rw(&self) -> ProviderResult<Self::ProviderRW>;
 }
 
+impl<T> HashedStateRangeProviderFactory for T
+where
+    T: DatabaseProviderFactory<Provider: HashedStateRangeProvider>,
+{
+    type Provider = T::Provider;
+
+    fn persisted_state_provider(&self) -> ProviderResult<Self::Provider> {
+        self.database_provider_ro()
+    }
+}
+
 /// Helper type alias to get the associated transaction type from a [`DatabaseProviderFactory`].
 pub type FactoryTx<F> = <<F as DatabaseProviderFactory>::DB as Database>::TX;
 
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -53,6 +53,51 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the sender and recipient transactions indices for the transactions in a
+    /// given range.
+    ///
+    /// Returns number of transactions walked.
+    fn unwind_sender_transactions_index_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert sender transactions index to database. Used inside `IndexSenderTransactions` stage
+    fn insert_sender_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()>;
+
+    /// Insert recipient transactions index to database. Used inside `IndexSenderTransactions`
+    /// stage
+    fn insert_recipient_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the contract deployments index for the contracts deployed in a given block
+    /// range.
+    ///
+    /// Returns number of removed deployments.
+    fn unwind_contract_deployments_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert the contracts deployed in a given block range into the contract deployments index.
+    /// Used inside `IndexContractDeployments` stage
+    ///
+    /// Requires the account history indices of the range to be up to date.
+    ///
+    /// Returns number of inserted deployments.
+    fn insert_contract_deployments_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// If the sender transactions or contract deployments indices are enabled, they're updated
+    /// with the given blocks as well.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -3,8 +3,9 @@
 use crate::{
     AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
     BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader, ChangeSetReader,
-    HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider,
+    ContractDeploymentsProvider, HashedPostStateProvider, HashedStateRangeProvider, HeaderProvider,
+    NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    RequestsProvider, SenderTransactionsProvider, StageCheckpointReader, StateProofProvider,
     StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
     StorageRootProvider, TransactionVariant, TransactionsProvider,
 };
@@ -13,7 +14,7 @@ use crate::{
 use crate::{DBProvider, DatabaseProviderFactory};
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use alloy_consensus::transaction::TransactionMeta;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip7685::Requests, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
     Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256, U256,
 };
@@ -25,7 +26,7 @@ use core::{
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, MAINNET};
 #[cfg(feature = "db-api")]
 use reth_db_api::mock::{DatabaseMock, TxMock};
-use reth_db_models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_models::{AccountBeforeTx, ContractDeployment, StoredBlockBodyIndices};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives, RecoveredBlock, SealedHeader};
@@ -36,7 +37,7 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieAccount, TrieInput,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -397,6 +398,37 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> SenderTransactionsProvider for NoopProvider<C, N> {
+    fn sender_transaction_ids(
+        &self,
+        _sender: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> RequestsProvider for NoopProvider<C, N> {
+    fn requests_by_block(&self, _block: BlockNumber) -> ProviderResult<Option<Requests>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> ContractDeploymentsProvider for NoopProvider<C, N> {
+    fn contract_deployments_indexed(&self) -> ProviderResult<bool> {
+        Ok(false)
+    }
+
+    fn contract_deployment(
+        &self,
+        _address: Address,
+        _code_hash: B256,
+    ) -> ProviderResult<Option<ContractDeployment>> {
+        Ok(None)
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -472,6 +504,33 @@ impl<C: Send + Sync, N: NodePrimitives> StateProofProvider for NoopProvider<C, N
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> HashedStateRangeProvider for NoopProvider<C, N> {
+    fn persisted_state_root(&self) -> ProviderResult<B256> {
+        Ok(B256::default())
+    }
+
+    fn hashed_account_range(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>> {
+        Ok(Vec::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::default())
+    }
+
+    fn persisted_multiproof(&self, _targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        Ok(MultiProof::default())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> HashedPostStateProvider for NoopProvider<C, N> {
     fn hashed_post_state(&self, _bundle_state: &revm_database::BundleState) -> HashedPostState {
         HashedPostState::default()
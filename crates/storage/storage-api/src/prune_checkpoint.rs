@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_storage_errors::provider::ProviderResult;
 
 /// The trait for fetching prune checkpoint related data.
@@ -13,6 +13,16 @@ pub trait PruneCheckpointReader: Send + Sync {
 
     /// Fetch all the prune checkpoints.
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>>;
+
+    /// Fetch the prune modes the node is configured with.
+    ///
+    /// The checkpoints alone don't tell which receipts are retained by the receipts log filters,
+    /// e.g. to find out whether the logs of a partially pruned range are complete.
+    ///
+    /// By default, no prune modes are configured.
+    fn prune_modes(&self) -> ProviderResult<PruneModes> {
+        Ok(PruneModes::none())
+    }
 }
 
 /// The trait for updating prune checkpoint related data.
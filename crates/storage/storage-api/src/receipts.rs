@@ -1,6 +1,6 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip7685::Requests, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, TxHash, TxNumber};
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
@@ -91,3 +91,15 @@ pub trait ReceiptProviderIdExt: ReceiptProvider + BlockIdReader {
         self.receipts_by_block_id(number_or_tag.into())
     }
 }
+
+/// Client trait for fetching the [EIP-7685] requests produced by executing canonical blocks.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+#[auto_impl::auto_impl(&, Arc)]
+pub trait RequestsProvider: Send + Sync {
+    /// Get the requests of the canonical block with the given number.
+    ///
+    /// Returns `None` if no requests are stored for the block, which is the case for blocks without
+    /// requests and for blocks that were executed before requests were stored.
+    fn requests_by_block(&self, block: BlockNumber) -> ProviderResult<Option<Requests>>;
+}
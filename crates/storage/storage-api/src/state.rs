@@ -2,13 +2,13 @@ use super::{
     AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
     StorageRootProvider,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue, B256, U256};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives_traits::Bytecode;
+use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::HashedPostState;
 use revm_database::BundleState;
@@ -49,6 +49,27 @@ pub trait StateProvider:
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>>;
 
+    /// Get basic account information for multiple accounts.
+    ///
+    /// Returns the accounts in the order of the given addresses, `None` for accounts that don't
+    /// exist. Implementations may override this to amortize the lookup costs, which is more
+    /// efficient than calling [`AccountReader::basic_account`] repeatedly.
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        addresses.iter().map(|address| self.basic_account(address)).collect()
+    }
+
+    /// Get multiple storage slots.
+    ///
+    /// Returns the storage values in the order of the given slots, `None` for slots that don't
+    /// exist. Implementations may override this to amortize the lookup costs, which is more
+    /// efficient than calling [`StateProvider::storage`] repeatedly.
+    fn storage_batch(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        slots.iter().map(|(address, storage_key)| self.storage(*address, *storage_key)).collect()
+    }
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
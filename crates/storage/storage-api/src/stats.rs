@@ -1,4 +1,4 @@
-use reth_db_api::table::Table;
+use reth_db_api::{stats::StorageStats, table::Table};
 
 /// The trait for fetching provider statistics.
 #[auto_impl::auto_impl(&, Arc)]
@@ -7,3 +7,10 @@ pub trait StatsReader: Send + Sync {
     /// route to different data sources other than [Table].
     fn count_entries<T: Table>(&self) -> reth_storage_errors::provider::ProviderResult<usize>;
 }
+
+/// The trait for fetching table-level storage statistics of the database.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StorageStatsProvider: Send + Sync {
+    /// Returns page and entry counts of all database tables, including the freelist.
+    fn storage_stats(&self) -> reth_storage_errors::provider::ProviderResult<StorageStats>;
+}
@@ -41,6 +41,50 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns the value of the storage slot from before the given block, if the slot changed in
+    /// the block.
+    fn get_storage_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        Ok(self
+            .storage_changeset(block_number)?
+            .into_iter()
+            .find(|(key, entry)| key.address() == address && entry.key == storage_key)
+            .map(|(_, entry)| entry))
+    }
+
+    /// Returns the storage changesets of the range, ordered by block number and address.
+    ///
+    /// The default implementation reads the changesets block by block, so it doesn't support
+    /// ranges without an end bound.
+    fn storage_changesets_range(
+        &self,
+        range: impl core::ops::RangeBounds<reth_db_api::models::BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>> {
+        // The range bounds might be in the middle of a block, so the whole blocks are read.
+        let block_bound =
+            |bound: core::ops::Bound<&reth_db_api::models::BlockNumberAddress>| match bound {
+                core::ops::Bound::Included(key) | core::ops::Bound::Excluded(key) => {
+                    core::ops::Bound::Included(key.block_number())
+                }
+                core::ops::Bound::Unbounded => core::ops::Bound::Unbounded,
+            };
+        let block_range = (block_bound(range.start_bound()), block_bound(range.end_bound()));
+
+        let mut changesets = Vec::new();
+        for block_number in crate::account::bounded_block_range(block_range)? {
+            changesets.extend(
+                self.storage_changeset(block_number)?
+                    .into_iter()
+                    .filter(|(key, _)| range.contains(key)),
+            );
+        }
+        Ok(changesets)
+    }
 }
 
 /// An enum that represents the storage location for a piece of data.
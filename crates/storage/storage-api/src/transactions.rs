@@ -112,3 +112,38 @@ pub trait TransactionsProviderExt: BlockReader {
         tx_range: Range<TxNumber>,
     ) -> ProviderResult<Vec<(TxHash, TxNumber)>>;
 }
+
+/// Client trait for reading the index of transactions sent by an address.
+///
+/// The index is opt-in, see the `IndexSenderTransactions` stage.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SenderTransactionsProvider: Send + Sync {
+    /// Returns the ids of the transactions sent by the given address in ascending order, skipping
+    /// the first `offset` transactions and returning at most `limit` of them.
+    ///
+    /// Returns `None` if the index is not enabled.
+    fn sender_transaction_ids(
+        &self,
+        sender: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>>;
+
+    /// Returns at most `limit` ids of the transactions sent or received by the given address in the
+    /// given range.
+    ///
+    /// The ids are returned in ascending order from the start of the range, or in descending order
+    /// from the end of the range if `reverse` is set.
+    ///
+    /// Returns `None` if the index is not enabled.
+    fn address_transaction_ids(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        let _ = (address, range, limit, reverse);
+        Ok(None)
+    }
+}
@@ -1,10 +1,10 @@
 use alloc::vec::Vec;
-use alloy_primitives::{map::B256Map, Address, Bytes, B256};
-use reth_storage_errors::provider::ProviderResult;
+use alloy_primitives::{keccak256, map::B256Map, Address, Bytes, B256, U256};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
     AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-    StorageProof, TrieInput,
+    StorageProof, TrieAccount, TrieInput, WitnessSink,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -87,6 +87,69 @@ pub trait StateProofProvider: Send + Sync {
 
     /// Get trie witness for provided state.
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
+
+    /// Records the trie witness for provided state to the given sink.
+    ///
+    /// Implementations should record the nodes as they are gathered, so that a sink that writes
+    /// them to disk avoids holding the entire witness in memory. The default implementation
+    /// records the nodes returned by [`Self::witness`].
+    fn witness_into(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut dyn WitnessSink,
+    ) -> ProviderResult<()> {
+        for node in self.witness(input, target)? {
+            witness
+                .record(keccak256(&node), node)
+                .map_err(|err| ProviderError::TrieWitnessError(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// A type that provides ordered access to the hashed state and proofs of the latest persisted
+/// state, e.g. to serve `snap` sync requests.
+///
+/// Note: unlike the [`StateProofProvider`] of the latest state, this only operates on the state
+/// that has been persisted to the database and doesn't include any in-memory blocks.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait HashedStateRangeProvider: Send + Sync {
+    /// Returns the state root of the latest persisted state.
+    fn persisted_state_root(&self) -> ProviderResult<B256>;
+
+    /// Returns up to `limit` hashed accounts ordered by hashed address, starting at `start`
+    /// (inclusive), together with their storage roots.
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>>;
+
+    /// Returns up to `limit` storage slots of the given hashed account ordered by hashed slot,
+    /// starting at `start` (inclusive).
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Generates a [`MultiProof`] for the target hashed accounts and corresponding hashed storage
+    /// slot keys against the latest persisted state.
+    fn persisted_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof>;
+}
+
+/// A type that opens [`HashedStateRangeProvider`]s over the latest persisted state.
+pub trait HashedStateRangeProviderFactory: Send + Sync {
+    /// The provider type returned by the factory.
+    type Provider: HashedStateRangeProvider;
+
+    /// Returns a provider over the latest persisted state.
+    ///
+    /// All reads through the returned provider observe the same state, even if more blocks are
+    /// persisted while it's in use.
+    fn persisted_state_provider(&self) -> ProviderResult<Self::Provider>;
 }
 
 /// Trie Writer
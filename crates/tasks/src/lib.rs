@@ -26,7 +26,7 @@ use std::{
     fmt::{Display, Formatter},
     pin::{pin, Pin},
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc, OnceLock,
     },
     task::{ready, Context, Poll},
@@ -40,8 +40,11 @@ use tracing::{debug, error};
 use tracing_futures::Instrument;
 
 pub mod metrics;
+pub mod runtime;
 pub mod shutdown;
 
+pub use runtime::{DedicatedThreadsRuntime, TaskRuntime, TokioRuntime};
+
 #[cfg(feature = "rayon")]
 pub mod pool;
 
@@ -148,8 +151,9 @@ impl TaskSpawner for TokioTaskExecutor {
 /// that handle the traffic to and from a peer.
 ///
 /// To unify how tasks are created, the [`TaskManager`] provides access to the configured Tokio
-/// runtime. A [`TaskManager`] stores the [`tokio::runtime::Handle`] it is associated with. In this
-/// way it is possible to configure on which runtime a task is executed.
+/// runtime. A [`TaskManager`] stores the [`tokio::runtime::Handle`] it is associated with, and
+/// optionally a [`TaskRuntime`] the tasks are spawned onto instead. In this way it is possible to
+/// configure on which runtime a task is executed.
 ///
 /// The main purpose of this type is to be able to monitor if a critical task panicked, for
 /// diagnostic purposes, since tokio task essentially fail silently. Therefore, this type is a
@@ -162,6 +166,8 @@ pub struct TaskManager {
     ///
     /// See [`Handle`] docs.
     handle: Handle,
+    /// The runtime tasks are spawned onto, if not spawned directly onto the tokio runtime.
+    runtime: Option<Arc<dyn TaskRuntime>>,
     /// Sender half for sending task events to this type
     task_events_tx: UnboundedSender<TaskEvent>,
     /// Receiver for task events
@@ -174,6 +180,10 @@ pub struct TaskManager {
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Minimum time in milliseconds to wait for [`GracefulShutdown`] tasks on shutdown.
+    ///
+    /// See [`TaskExecutor::extend_graceful_shutdown_timeout`].
+    min_graceful_shutdown_timeout: Arc<AtomicU64>,
 }
 
 // === impl TaskManager ===
@@ -197,15 +207,31 @@ impl TaskManager {
     ///
     /// This also sets the global [`TaskExecutor`].
     pub fn new(handle: Handle) -> Self {
+        Self::new_with_runtime(handle, None)
+    }
+
+    /// Create a new instance that spawns all tasks onto the given [`TaskRuntime`].
+    ///
+    /// The tokio runtime of the given handle is still used to track the spawned tasks, see
+    /// [`TaskExecutor::handle`].
+    ///
+    /// This also sets the global [`TaskExecutor`].
+    pub fn with_runtime(handle: Handle, runtime: impl TaskRuntime) -> Self {
+        Self::new_with_runtime(handle, Some(Arc::new(runtime)))
+    }
+
+    fn new_with_runtime(handle: Handle, runtime: Option<Arc<dyn TaskRuntime>>) -> Self {
         let (task_events_tx, task_events_rx) = unbounded_channel();
         let (signal, on_shutdown) = signal();
         let manager = Self {
             handle,
+            runtime,
             task_events_tx,
             task_events_rx,
             signal: Some(signal),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            min_graceful_shutdown_timeout: Arc::new(AtomicU64::new(0)),
         };
 
         let _ = GLOBAL_EXECUTOR
@@ -215,15 +241,17 @@ impl TaskManager {
         manager
     }
 
-    /// Returns a new [`TaskExecutor`] that can spawn new tasks onto the tokio runtime this type is
+    /// Returns a new [`TaskExecutor`] that can spawn new tasks onto the runtime this type is
     /// connected to.
     pub fn executor(&self) -> TaskExecutor {
         TaskExecutor {
             handle: self.handle.clone(),
+            runtime: self.runtime.clone(),
             on_shutdown: self.on_shutdown.clone(),
             task_events_tx: self.task_events_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            min_graceful_shutdown_timeout: Arc::clone(&self.min_graceful_shutdown_timeout),
         }
     }
 
@@ -234,9 +262,15 @@ impl TaskManager {
 
     /// Fires the shutdown signal and awaits until all tasks are shutdown.
     ///
+    /// The timeout is raised to the minimum requested via
+    /// [`TaskExecutor::extend_graceful_shutdown_timeout`], if that is higher.
+    ///
     /// Returns true if all tasks were shutdown before the timeout elapsed.
     pub fn graceful_shutdown_with_timeout(self, timeout: std::time::Duration) -> bool {
-        self.do_graceful_shutdown(Some(timeout))
+        let min_timeout = std::time::Duration::from_millis(
+            self.min_graceful_shutdown_timeout.load(Ordering::Relaxed),
+        );
+        self.do_graceful_shutdown(Some(timeout.max(min_timeout)))
     }
 
     fn do_graceful_shutdown(self, timeout: Option<std::time::Duration>) -> bool {
@@ -322,6 +356,8 @@ pub struct TaskExecutor {
     ///
     /// See [`Handle`] docs.
     handle: Handle,
+    /// The runtime tasks are spawned onto, if not spawned directly onto the tokio runtime.
+    runtime: Option<Arc<dyn TaskRuntime>>,
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
     /// Sender half for sending task events to this type
@@ -330,6 +366,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Minimum time in milliseconds to wait for [`GracefulShutdown`] tasks on shutdown.
+    min_graceful_shutdown_timeout: Arc<AtomicU64>,
 }
 
 // === impl TaskExecutor ===
@@ -357,25 +395,71 @@ impl TaskExecutor {
         &self.handle
     }
 
+    /// Returns the [`TaskRuntime`] tasks are spawned onto, if any.
+    ///
+    /// If this returns `None`, tasks are spawned directly onto the tokio runtime.
+    pub fn runtime(&self) -> Option<&dyn TaskRuntime> {
+        self.runtime.as_deref()
+    }
+
     /// Returns the receiver of the shutdown signal.
     pub const fn on_shutdown_signal(&self) -> &Shutdown {
         &self.on_shutdown
     }
 
-    /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
+    /// Spawns a future on the runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        match task_kind {
-            TaskKind::Default => self.handle.spawn(fut),
-            TaskKind::Blocking => {
+        match (&self.runtime, task_kind) {
+            (None, TaskKind::Default) => self.handle.spawn(fut),
+            (None, TaskKind::Blocking) => {
                 let handle = self.handle.clone();
                 self.handle.spawn_blocking(move || handle.block_on(fut))
             }
+            (Some(runtime), TaskKind::Default) => self.spawn_remote(fut, |fut| runtime.spawn(fut)),
+            (Some(runtime), TaskKind::Blocking) => {
+                self.spawn_remote(fut, |fut| runtime.spawn_blocking(fut))
+            }
         }
     }
 
+    /// Spawns a critical future on the runtime depending on the [`TaskKind`]
+    fn spawn_critical_on_rt<F>(
+        &self,
+        name: &'static str,
+        fut: F,
+        task_kind: TaskKind,
+    ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match (&self.runtime, task_kind) {
+            (Some(runtime), TaskKind::Default) => {
+                self.spawn_remote(fut, |fut| runtime.spawn_critical(name, fut))
+            }
+            (Some(runtime), TaskKind::Blocking) => {
+                self.spawn_remote(fut, |fut| runtime.spawn_critical_blocking(name, fut))
+            }
+            (None, task_kind) => self.spawn_on_rt(fut, task_kind),
+        }
+    }
+
+    /// Hands the future to the [`TaskRuntime`] with the given spawn function and returns a
+    /// [`JoinHandle`] of the tokio runtime that tracks it.
+    ///
+    /// Aborting the returned handle cancels the task on the [`TaskRuntime`], and if the task
+    /// panics, the panic is propagated to the returned handle.
+    fn spawn_remote<F>(&self, fut: F, spawn: impl FnOnce(BoxFuture<'static, ()>)) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let (remote, handle) = fut.remote_handle();
+        spawn(Box::pin(remote));
+        self.handle.spawn(handle)
+    }
+
     /// Spawns a regular task depending on the given [`TaskKind`]
     fn spawn_task_as<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -436,7 +520,7 @@ impl TaskExecutor {
 
         let task = fut.in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_rt(task, TaskKind::Default)
     }
 
     /// Spawns a critical task depending on the given [`TaskKind`]
@@ -472,7 +556,7 @@ impl TaskExecutor {
             let _ = select(on_shutdown, task).await;
         };
 
-        self.spawn_on_rt(task, task_kind)
+        self.spawn_critical_on_rt(name, task, task_kind)
     }
 
     /// This spawns a critical blocking task onto the runtime.
@@ -523,7 +607,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_critical_on_rt(name, task, TaskKind::Default)
     }
 
     /// This spawns a critical task onto the runtime.
@@ -572,7 +656,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_critical_on_rt(name, task, TaskKind::Default)
     }
 
     /// This spawns a regular task onto the runtime.
@@ -607,7 +691,17 @@ impl TaskExecutor {
         );
         let fut = f(on_shutdown);
 
-        self.handle.spawn(fut)
+        self.spawn_on_rt(fut, TaskKind::Default)
+    }
+
+    /// Requests the [`TaskManager`] to wait at least the given time for tasks spawned with a
+    /// [`GracefulShutdown`] signal to complete when shutting down with a timeout.
+    ///
+    /// This is useful for tasks that are known to require more time to shut down than the
+    /// default timeout allows.
+    pub fn extend_graceful_shutdown_timeout(&self, timeout: std::time::Duration) {
+        let millis = timeout.as_millis().try_into().unwrap_or(u64::MAX);
+        self.min_graceful_shutdown_timeout.fetch_max(millis, Ordering::Relaxed);
     }
 
     /// Sends a request to the `TaskManager` to initiate a graceful shutdown.
@@ -707,9 +801,10 @@ impl TaskSpawnerExt for TaskExecutor {
 
 /// Determines how a task is spawned
 enum TaskKind {
-    /// Spawn the task to the default executor [`Handle::spawn`]
+    /// Spawn the task to the default executor [`Handle::spawn`], or [`TaskRuntime::spawn`]
     Default,
-    /// Spawn the task to the blocking executor [`Handle::spawn_blocking`]
+    /// Spawn the task to the blocking executor [`Handle::spawn_blocking`], or
+    /// [`TaskRuntime::spawn_blocking`]
     Blocking,
 }
 
@@ -862,6 +957,107 @@ mod tests {
         assert!(!val.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_manager_graceful_shutdown_extended_timeout() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let timeout = Duration::from_millis(100);
+        executor.extend_graceful_shutdown_timeout(timeout * 10);
+
+        let val = Arc::new(AtomicBool::new(false));
+        let val2 = val.clone();
+        executor.spawn_critical_with_graceful_shutdown_signal("grace", |shutdown| async move {
+            let _guard = shutdown.await;
+            tokio::time::sleep(timeout * 3).await;
+            val2.store(true, Ordering::Relaxed);
+        });
+
+        assert!(manager.graceful_shutdown_with_timeout(timeout));
+        assert!(val.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_dedicated_critical_task_thread() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let started = Arc::new(AtomicBool::new(false));
+        let started2 = started.clone();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::with_runtime(
+            handle.clone(),
+            DedicatedThreadsRuntime::new(handle.clone(), TokioRuntime::new(handle))
+                .with_task("dedicated")
+                .on_thread_start(move |name| {
+                    assert_eq!(name, "dedicated");
+                    started2.store(true, Ordering::Relaxed);
+                }),
+        );
+        let executor = manager.executor();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let task = executor.spawn_critical("dedicated", async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            tx.send(std::thread::current().name().map(ToString::to_string)).unwrap();
+        });
+        runtime.block_on(task).unwrap();
+
+        assert!(started.load(Ordering::Relaxed));
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("dedicated"));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let task = executor.spawn_critical("shared", async move {
+            tx.send(std::thread::current().name().map(ToString::to_string)).unwrap();
+        });
+        runtime.block_on(task).unwrap();
+        assert_ne!(rx.recv().unwrap().as_deref(), Some("shared"));
+    }
+
+    #[test]
+    fn test_abort_dedicated_task() {
+        struct OnDrop(std::sync::mpsc::Sender<()>);
+
+        impl Drop for OnDrop {
+            fn drop(&mut self) {
+                let _ = self.0.send(());
+            }
+        }
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::with_runtime(
+            handle.clone(),
+            DedicatedThreadsRuntime::new(handle.clone(), TokioRuntime::new(handle))
+                .with_task("dedicated"),
+        );
+        let executor = manager.executor();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let task = executor.spawn_critical("dedicated", async move {
+            let _on_drop = OnDrop(tx);
+            std::future::pending::<()>().await;
+        });
+        task.abort();
+
+        rx.recv_timeout(Duration::from_secs(5)).expect("task was not dropped");
+        assert!(runtime.block_on(task).unwrap_err().is_cancelled());
+    }
+
+    #[test]
+    fn test_runtime_task_panic() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::with_runtime(handle.clone(), TokioRuntime::new(handle));
+        let executor = manager.executor();
+
+        let task = executor.spawn(async { panic!("intentionally panicking") });
+        assert!(runtime.block_on(task).unwrap_err().is_panic());
+
+        let task = executor.spawn_blocking(async { panic!("intentionally panicking") });
+        assert!(runtime.block_on(task).unwrap_err().is_panic());
+    }
+
     #[test]
     fn can_access_global() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -37,6 +37,21 @@ impl BlockingTaskGuard {
     pub async fn acquire_many_owned(self, n: u32) -> Result<OwnedSemaphorePermit, AcquireError> {
         self.0.acquire_many_owned(n).await
     }
+
+    /// Acquires up to `n` permits without waiting, returning as many as are currently available.
+    ///
+    /// Returns `None` if no permit is available or `n` is zero.
+    pub fn try_acquire_up_to_owned(&self, n: u32) -> Option<OwnedSemaphorePermit> {
+        let mut acquired: Option<OwnedSemaphorePermit> = None;
+        for _ in 0..n {
+            let Ok(permit) = self.0.clone().try_acquire_owned() else { break };
+            match acquired.as_mut() {
+                Some(acquired) => acquired.merge(permit),
+                None => acquired = Some(permit),
+            }
+        }
+        acquired
+    }
 }
 
 /// Used to execute blocking tasks on a rayon threadpool from within a tokio runtime.
@@ -0,0 +1,199 @@
+//! Runtime abstraction the [`TaskExecutor`](crate::TaskExecutor) spawns tasks on.
+
+use futures_util::future::BoxFuture;
+use std::{collections::HashSet, fmt, sync::Arc};
+use tokio::runtime::Handle;
+
+/// Name of the critical task that drives the consensus engine.
+///
+/// The CLI runner runs this task on a dedicated thread, see [`DedicatedThreadsRuntime`].
+pub const CONSENSUS_ENGINE_TASK: &str = "consensus engine";
+
+/// A runtime that executes the tasks spawned by the [`TaskExecutor`](crate::TaskExecutor).
+///
+/// By default tasks are spawned directly onto the tokio runtime of the
+/// [`TaskManager`](crate::TaskManager). Embedders can provide their own implementation to control
+/// where tasks are executed, for example to share a runtime with custom thread naming or
+/// prioritization.
+///
+/// Critical tasks are spawned with their name, which allows dedicating threads to specific
+/// critical tasks, see [`DedicatedThreadsRuntime`].
+///
+/// Note: the spawned futures are already instrumented, wrapped for panic and shutdown handling
+/// and tracked by the [`TaskExecutor`](crate::TaskExecutor), which also handles aborting them.
+/// Most tasks rely on tokio IO and timers, so they must be polled within the context of the tokio
+/// runtime of the [`TaskManager`](crate::TaskManager).
+pub trait TaskRuntime: Send + Sync + fmt::Debug + 'static {
+    /// Spawns the task onto the runtime.
+    fn spawn(&self, fut: BoxFuture<'static, ()>);
+
+    /// Spawns a task that is allowed to block onto the runtime.
+    fn spawn_blocking(&self, fut: BoxFuture<'static, ()>);
+
+    /// Spawns a critical task onto the runtime.
+    ///
+    /// By default, this is the same as [`TaskRuntime::spawn`].
+    fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) {
+        let _ = name;
+        self.spawn(fut)
+    }
+
+    /// Spawns a critical task that is allowed to block onto the runtime.
+    ///
+    /// By default, this is the same as [`TaskRuntime::spawn_blocking`].
+    fn spawn_critical_blocking(&self, name: &'static str, fut: BoxFuture<'static, ()>) {
+        let _ = name;
+        self.spawn_blocking(fut)
+    }
+}
+
+/// [`TaskRuntime`] that spawns all tasks onto a tokio runtime.
+///
+/// Blocking tasks are spawned with [`Handle::spawn_blocking`].
+#[derive(Debug, Clone)]
+pub struct TokioRuntime {
+    handle: Handle,
+}
+
+impl TokioRuntime {
+    /// Creates a new [`TokioRuntime`] spawning onto the runtime of the given [`Handle`].
+    pub const fn new(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Creates a new [`TokioRuntime`] spawning onto the current tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if called outside the context of a Tokio runtime.
+    pub fn current() -> Self {
+        Self::new(Handle::current())
+    }
+}
+
+impl TaskRuntime for TokioRuntime {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        self.handle.spawn(fut);
+    }
+
+    fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) {
+        let handle = self.handle.clone();
+        self.handle.spawn_blocking(move || handle.block_on(fut));
+    }
+}
+
+/// Hook that is called on a dedicated thread with the name of its task before the task is run.
+type OnThreadStart = Arc<dyn Fn(&'static str) + Send + Sync>;
+
+/// [`TaskRuntime`] that runs the selected critical tasks on dedicated threads, and delegates all
+/// other tasks to the inner runtime.
+///
+/// This is useful to isolate latency sensitive tasks, like the consensus engine, from the worker
+/// threads shared by all other tasks. The threads are named after their task, and can be
+/// configured, e.g. to set a priority or CPU affinity, with
+/// [`DedicatedThreadsRuntime::on_thread_start`].
+///
+/// The dedicated threads drive their task within the context of the given tokio runtime, so the
+/// task can use its IO and timers.
+///
+/// # Example
+///
+/// ```
+/// use reth_tasks::{
+///     runtime::CONSENSUS_ENGINE_TASK, DedicatedThreadsRuntime, TaskManager, TokioRuntime,
+/// };
+///
+/// let rt = tokio::runtime::Runtime::new().unwrap();
+/// let handle = rt.handle().clone();
+/// let runtime = DedicatedThreadsRuntime::new(handle.clone(), TokioRuntime::new(handle.clone()))
+///     .with_task(CONSENSUS_ENGINE_TASK);
+/// let manager = TaskManager::with_runtime(handle, runtime);
+/// ```
+pub struct DedicatedThreadsRuntime<R> {
+    handle: Handle,
+    inner: R,
+    tasks: HashSet<&'static str>,
+    on_thread_start: Option<OnThreadStart>,
+}
+
+impl<R> DedicatedThreadsRuntime<R> {
+    /// Creates a new [`DedicatedThreadsRuntime`] that drives the dedicated tasks within the tokio
+    /// runtime of the given [`Handle`], and delegates all other tasks to the given runtime.
+    pub fn new(handle: Handle, inner: R) -> Self {
+        Self { handle, inner, tasks: HashSet::new(), on_thread_start: None }
+    }
+
+    /// Runs the critical task with the given name on a dedicated thread.
+    pub fn with_task(mut self, name: &'static str) -> Self {
+        self.tasks.insert(name);
+        self
+    }
+
+    /// Sets a hook that is called on each dedicated thread, with the name of its task, before the
+    /// task is run.
+    pub fn on_thread_start<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&'static str) + Send + Sync + 'static,
+    {
+        self.on_thread_start = Some(Arc::new(f));
+        self
+    }
+
+    /// Runs the task on a new thread, or returns it if it isn't a dedicated task.
+    ///
+    /// The thread exits once the task is finished or aborted.
+    fn spawn_dedicated(
+        &self,
+        name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> Result<(), BoxFuture<'static, ()>> {
+        if !self.tasks.contains(name) {
+            return Err(fut)
+        }
+
+        let handle = self.handle.clone();
+        let on_thread_start = self.on_thread_start.clone();
+        std::thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                if let Some(on_thread_start) = on_thread_start {
+                    on_thread_start(name);
+                }
+                handle.block_on(fut);
+            })
+            .expect("failed to spawn dedicated task thread");
+
+        Ok(())
+    }
+}
+
+impl<R: TaskRuntime> TaskRuntime for DedicatedThreadsRuntime<R> {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        self.inner.spawn(fut)
+    }
+
+    fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) {
+        self.inner.spawn_blocking(fut)
+    }
+
+    fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) {
+        if let Err(fut) = self.spawn_dedicated(name, fut) {
+            self.inner.spawn_critical(name, fut)
+        }
+    }
+
+    fn spawn_critical_blocking(&self, name: &'static str, fut: BoxFuture<'static, ()>) {
+        if let Err(fut) = self.spawn_dedicated(name, fut) {
+            self.inner.spawn_critical_blocking(name, fut)
+        }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for DedicatedThreadsRuntime<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedicatedThreadsRuntime")
+            .field("inner", &self.inner)
+            .field("tasks", &self.tasks)
+            .finish_non_exhaustive()
+    }
+}
@@ -10,7 +10,7 @@ use std::{
     sync::{atomic::AtomicUsize, Arc},
     task::{ready, Context, Poll},
 };
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Notify};
 
 /// A Future that resolves when the shutdown event has been fired.
 #[derive(Debug)]
@@ -20,7 +20,9 @@ pub struct GracefulShutdown {
 }
 
 impl GracefulShutdown {
-    pub(crate) const fn new(shutdown: Shutdown, guard: GracefulShutdownGuard) -> Self {
+    /// Creates a new [`GracefulShutdown`] that resolves to the given guard once the [`Shutdown`]
+    /// fired.
+    pub const fn new(shutdown: Shutdown, guard: GracefulShutdownGuard) -> Self {
         Self { shutdown, guard: Some(guard) }
     }
 
@@ -45,7 +47,7 @@ impl Clone for GracefulShutdown {
     fn clone(&self) -> Self {
         Self {
             shutdown: self.shutdown.clone(),
-            guard: self.guard.as_ref().map(|g| GracefulShutdownGuard::new(Arc::clone(&g.0))),
+            guard: self.guard.as_ref().map(GracefulShutdownGuard::clone_guard),
         }
     }
 }
@@ -54,18 +56,41 @@ impl Clone for GracefulShutdown {
 /// [`GracefulShutdown`] has completed.
 #[derive(Debug)]
 #[must_use = "if unused the task will not be gracefully shutdown"]
-pub struct GracefulShutdownGuard(Arc<AtomicUsize>);
+pub struct GracefulShutdownGuard {
+    counter: Arc<AtomicUsize>,
+    /// Notified once the last guard of the counter is dropped.
+    on_complete: Option<Arc<Notify>>,
+}
 
 impl GracefulShutdownGuard {
-    pub(crate) fn new(counter: Arc<AtomicUsize>) -> Self {
+    /// Creates a new guard that increments the given counter until it is dropped.
+    pub fn new(counter: Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Self { counter, on_complete: None }
+    }
+
+    /// Creates a new guard that increments the given counter until it is dropped, and notifies
+    /// the waiters of `on_complete` once the counter drops to zero.
+    pub fn with_notify(counter: Arc<AtomicUsize>, on_complete: Arc<Notify>) -> Self {
         counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        Self(counter)
+        Self { counter, on_complete: Some(on_complete) }
+    }
+
+    /// Creates another guard for the same counter.
+    fn clone_guard(&self) -> Self {
+        self.counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Self { counter: Arc::clone(&self.counter), on_complete: self.on_complete.clone() }
     }
 }
 
 impl Drop for GracefulShutdownGuard {
     fn drop(&mut self) {
-        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        let remaining = self.counter.fetch_sub(1, std::sync::atomic::Ordering::SeqCst) - 1;
+        if remaining == 0 {
+            if let Some(on_complete) = &self.on_complete {
+                on_complete.notify_waiters();
+            }
+        }
     }
 }
 
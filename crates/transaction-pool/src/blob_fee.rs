@@ -0,0 +1,135 @@
+//! Projection of the blob base fee of upcoming blocks based on the blob demand in the pool.
+
+use alloy_eips::eip7840::BlobParams;
+
+/// Default number of upcoming blocks the blob base fee is projected for.
+pub const DEFAULT_BLOB_FEE_PROJECTION_BLOCKS: u64 = 5;
+
+/// Maximum number of upcoming blocks the blob base fee can be projected for.
+pub const MAX_BLOB_FEE_PROJECTION_BLOCKS: u64 = 64;
+
+/// The projected blob base fees of upcoming blocks.
+///
+/// The projection models the trajectory of the excess blob gas under the assumption that every
+/// upcoming block includes as much of the pending blob gas demand as fits into the assumed blob gas
+/// capacity of a block. Blocks that include more blob gas than the target raise the excess blob
+/// gas, and with it the blob base fee, while blocks below the target lower it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlobFeeProjection {
+    /// The projected blob base fee of each upcoming block, starting with the next block.
+    blob_fees: Vec<u128>,
+    /// The blob gas demand that is left after the projected blocks.
+    remaining_blob_gas: u64,
+}
+
+impl BlobFeeProjection {
+    /// Projects the blob base fee for the given number of blocks following a block with the given
+    /// `excess_blob_gas` and `blob_gas_used`.
+    ///
+    /// `blob_gas_demand` is the combined blob gas of all blob transactions waiting for inclusion
+    /// and `blob_gas_per_block` is the blob gas each upcoming block is assumed to include at most.
+    pub fn new(
+        blob_params: BlobParams,
+        excess_blob_gas: u64,
+        blob_gas_used: u64,
+        mut blob_gas_demand: u64,
+        blob_gas_per_block: u64,
+        blocks: u64,
+    ) -> Self {
+        let blob_gas_per_block = blob_gas_per_block.min(blob_params.max_blob_gas_per_block());
+        let mut blob_fees = Vec::with_capacity(blocks as usize);
+
+        let mut excess_blob_gas =
+            blob_params.next_block_excess_blob_gas(excess_blob_gas, blob_gas_used);
+        for _ in 0..blocks {
+            blob_fees.push(blob_params.calc_blob_fee(excess_blob_gas));
+
+            let included = blob_gas_demand.min(blob_gas_per_block);
+            blob_gas_demand -= included;
+            excess_blob_gas = blob_params.next_block_excess_blob_gas(excess_blob_gas, included);
+        }
+
+        Self { blob_fees, remaining_blob_gas: blob_gas_demand }
+    }
+
+    /// Returns the projected blob base fee of each upcoming block, starting with the next block.
+    pub const fn blob_fees(&self) -> &[u128] {
+        self.blob_fees.as_slice()
+    }
+
+    /// Returns the blob base fee of the next block.
+    pub fn next_block_blob_fee(&self) -> Option<u128> {
+        self.blob_fees.first().copied()
+    }
+
+    /// Returns the suggested max fee per blob gas, which is the highest projected blob base fee.
+    ///
+    /// A blob transaction that pays this fee remains includable in all projected blocks.
+    pub fn suggested_max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.blob_fees.iter().max().copied()
+    }
+
+    /// Returns the blob gas demand that is not included in any of the projected blocks.
+    pub const fn remaining_blob_gas(&self) -> u64 {
+        self.remaining_blob_gas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+
+    #[test]
+    fn projection_without_demand_decreases() {
+        let params = BlobParams::cancun();
+        let excess = 10 * params.target_blob_gas_per_block();
+        let projection = BlobFeeProjection::new(
+            params,
+            excess,
+            params.target_blob_gas_per_block(),
+            0,
+            params.max_blob_gas_per_block(),
+            3,
+        );
+
+        assert_eq!(projection.blob_fees().len(), 3);
+        assert_eq!(projection.next_block_blob_fee(), Some(params.calc_blob_fee(excess)));
+        assert!(projection.blob_fees().windows(2).all(|fees| fees[0] >= fees[1]));
+        assert_eq!(projection.suggested_max_fee_per_blob_gas(), projection.next_block_blob_fee());
+        assert_eq!(projection.remaining_blob_gas(), 0);
+    }
+
+    #[test]
+    fn projection_with_demand_above_target_increases() {
+        let params = BlobParams::cancun();
+        let excess = 100 * params.target_blob_gas_per_block();
+        let projection = BlobFeeProjection::new(
+            params,
+            excess,
+            params.target_blob_gas_per_block(),
+            100 * params.max_blob_gas_per_block(),
+            params.max_blob_gas_per_block(),
+            5,
+        );
+
+        assert!(projection.blob_fees().windows(2).all(|fees| fees[0] < fees[1]));
+        assert_eq!(
+            projection.suggested_max_fee_per_blob_gas(),
+            projection.blob_fees().last().copied()
+        );
+        assert_eq!(projection.remaining_blob_gas(), 95 * params.max_blob_gas_per_block());
+    }
+
+    #[test]
+    fn projection_at_target_clears_backlog() {
+        let params = BlobParams::cancun();
+        let target = params.target_blob_gas_per_block();
+        let projection =
+            BlobFeeProjection::new(params, 0, 0, 2 * target + DATA_GAS_PER_BLOB, target, 2);
+        assert_eq!(projection.remaining_blob_gas(), DATA_GAS_PER_BLOB);
+
+        let projection = BlobFeeProjection::new(params, 0, 0, 2 * target, target, 2);
+        assert_eq!(projection.remaining_blob_gas(), 0);
+    }
+}
@@ -67,6 +67,10 @@ impl BlobStore for DiskFileBlobStore {
         self.inner.insert_many(txs)
     }
 
+    fn replace(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
+        self.inner.replace_one(tx, data)
+    }
+
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
         if self.inner.contains(tx)? {
             self.inner.txs_to_delete.write().insert(tx);
@@ -390,6 +394,38 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Replaces the blob of a transaction that is already in the store, in the blob cache and on
+    /// disk.
+    fn replace_one(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
+        if !self.contains(tx)? {
+            return Ok(())
+        }
+
+        let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
+        data.rlp_encode_fields(&mut buf);
+
+        {
+            let mut map = self.versioned_hashes_to_txhash.lock();
+            data.versioned_hashes().for_each(|hash| {
+                map.insert(hash, tx);
+            });
+        }
+
+        self.blob_cache.lock().insert(tx, Arc::new(data));
+
+        let path = self.blob_disk_file(tx);
+        let _lock = self.file_lock.write();
+        let previous = fs::metadata(&path).map(|meta| meta.len() as usize).unwrap_or_default();
+        fs::write(&path, &buf).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        self.size_tracker.sub_size(previous);
+        self.size_tracker.add_size(buf.len());
+        Ok(())
+    }
+
     /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
         if self.blob_cache.lock().get(&tx).is_some() {
@@ -721,6 +757,28 @@ mod tests {
         assert_eq!(retrieved_blob, blob);
     }
 
+    #[test]
+    fn disk_replace() {
+        let (store, _dir) = tmp_store();
+
+        let (tx, blob) = rng_blobs(1).into_iter().next().unwrap();
+        let replacement = BlobTransactionSidecarVariant::Eip7594(
+            alloy_eips::eip7594::BlobTransactionSidecarEip7594::new(vec![], vec![], vec![]),
+        );
+
+        // missing blobs are not inserted
+        store.replace(tx, replacement.clone()).unwrap();
+        assert!(!store.contains(tx).unwrap());
+
+        store.insert(tx, blob).unwrap();
+        store.replace(tx, replacement.clone()).unwrap();
+        store.clear_cache();
+
+        let retrieved_blob = store.get(tx).unwrap().map(Arc::unwrap_or_clone).unwrap();
+        assert_eq!(retrieved_blob, replacement);
+        assert_eq!(store.inner.size_tracker.num_blobs.load(Ordering::Relaxed), 1);
+    }
+
     #[test]
     fn disk_delete_blob() {
         let (store, _dir) = tmp_store();
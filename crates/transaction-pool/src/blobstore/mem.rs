@@ -52,6 +52,16 @@ impl BlobStore for InMemoryBlobStore {
         Ok(())
     }
 
+    fn replace(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
+        let mut store = self.inner.store.write();
+        if let Some(existing) = store.get_mut(&tx) {
+            self.inner.size_tracker.sub_size(existing.size());
+            self.inner.size_tracker.add_size(data.size());
+            *existing = Arc::new(data);
+        }
+        Ok(())
+    }
+
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
         let sub = remove_size(&mut store, &tx);
@@ -38,6 +38,18 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
         txs: Vec<(B256, BlobTransactionSidecarVariant)>,
     ) -> Result<(), BlobStoreError>;
 
+    /// Replaces the blob sidecar of a transaction that is already in the store, e.g. after it was
+    /// converted into a different sidecar format.
+    ///
+    /// Does nothing if the store has no blob sidecar for the transaction.
+    fn replace(
+        &self,
+        _tx: B256,
+        _data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
+        Ok(())
+    }
+
     /// Deletes the blob sidecar from the store
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError>;
 
@@ -6,7 +6,7 @@ use crate::{
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use std::{collections::HashSet, ops::Mul, time::Duration};
+use std::{collections::HashSet, fmt, ops::Mul, sync::Arc, time::Duration};
 
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
@@ -48,6 +48,10 @@ pub struct PoolConfig {
     pub max_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
+    /// Custom policy for the replacement of transactions with the same sender and nonce.
+    ///
+    /// If unset, the [`PriceBumpConfig`] of `price_bumps` is used.
+    pub replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
     /// Minimum base fee required by the protocol.
     pub minimal_protocol_basefee: u64,
     /// Minimum priority fee required for transaction acceptance into the pool.
@@ -65,9 +69,51 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Maximum number of nonces a non-local transaction may be ahead of the on-chain nonce of its
+    /// sender.
+    ///
+    /// Transactions that exceed this gap are rejected. `None` disables the limit.
+    pub max_nonce_gap: Option<u64>,
+    /// Maximum lifetime of non-local transactions that are queued because of a nonce gap.
+    ///
+    /// The lifetime decays with the distance to the sender's on-chain nonce: a transaction that is
+    /// `n` nonces ahead is evicted after `nonce_gap_lifetime / n`. `None` disables the eviction.
+    pub nonce_gap_lifetime: Option<Duration>,
+    /// Number of blocks a local transaction can remain pending before it is re-broadcast.
+    ///
+    /// `None` disables the re-broadcast.
+    pub local_rebroadcast_blocks: Option<u64>,
+    /// Maximum lifetime of local transactions, after which they are dropped from the pool.
+    ///
+    /// `None` keeps local transactions until they are mined or replaced.
+    pub local_lifetime: Option<Duration>,
 }
 
 impl PoolConfig {
+    /// Returns the configured [`ReplacementPolicy`].
+    pub fn replacement_policy(&self) -> Arc<dyn ReplacementPolicy> {
+        self.replacement_policy.clone().unwrap_or_else(|| Arc::new(self.price_bumps))
+    }
+
+    /// Returns the size limits of the sub-pools.
+    pub const fn subpool_limits(&self) -> SubPoolLimits {
+        SubPoolLimits {
+            pending: self.pending_limit,
+            basefee: self.basefee_limit,
+            queued: self.queued_limit,
+            blob: self.blob_limit,
+        }
+    }
+
+    /// Sets the size limits of the sub-pools.
+    pub const fn set_subpool_limits(&mut self, limits: SubPoolLimits) {
+        let SubPoolLimits { pending, basefee, queued, blob } = limits;
+        self.pending_limit = pending;
+        self.basefee_limit = basefee;
+        self.queued_limit = queued;
+        self.blob_limit = blob;
+    }
+
     /// Returns whether the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
@@ -88,6 +134,7 @@ impl Default for PoolConfig {
             blob_cache_size: None,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
+            replacement_policy: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             minimum_priority_fee: None,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -96,12 +143,18 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_nonce_gap: None,
+            nonce_gap_lifetime: None,
+            local_rebroadcast_blocks: None,
+            local_lifetime: None,
         }
     }
 }
 
 /// Size limits for a sub-pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct SubPoolLimit {
     /// Maximum amount of transaction in the pool.
     pub max_txs: usize,
@@ -146,6 +199,47 @@ impl Default for SubPoolLimit {
     }
 }
 
+/// Size limits of all sub-pools.
+///
+/// Every sub-pool is limited independently, so that e.g. a flood of queued transactions only
+/// evicts queued transactions and never pending ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubPoolLimits {
+    /// Limits of the pending sub-pool.
+    pub pending: SubPoolLimit,
+    /// Limits of the basefee sub-pool.
+    pub basefee: SubPoolLimit,
+    /// Limits of the queued sub-pool.
+    pub queued: SubPoolLimit,
+    /// Limits of the blob sub-pool.
+    pub blob: SubPoolLimit,
+}
+
+/// The fees of a transaction that are relevant for its replacement, see [`ReplacementPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplacementFees {
+    /// The EIP-2718 type of the transaction.
+    pub tx_type: u8,
+    /// The max fee per gas of the transaction.
+    pub max_fee_per_gas: u128,
+    /// The max priority fee per gas of the transaction, if it's an EIP-1559 transaction.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The max fee per blob gas of the transaction, if it's a blob transaction.
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+/// Policy that decides whether a transaction can replace a pooled transaction with the same
+/// sender and nonce.
+///
+/// By default, replacements are subject to the price bumps of the [`PriceBumpConfig`], a custom
+/// policy can be configured with [`PoolConfig::replacement_policy`].
+pub trait ReplacementPolicy: fmt::Debug + Send + Sync {
+    /// Returns true if the `replacement` is underpriced compared to the `existing` transaction,
+    /// in which case it's rejected.
+    fn is_underpriced(&self, existing: &ReplacementFees, replacement: &ReplacementFees) -> bool;
+}
+
 /// Price bump config (in %) for the transaction pool underpriced check.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct PriceBumpConfig {
@@ -153,6 +247,8 @@ pub struct PriceBumpConfig {
     pub default_price_bump: u128,
     /// Replace blob price bump (in %) for the transaction pool underpriced check.
     pub replace_blob_tx_price_bump: u128,
+    /// Price bump (in %) of the max fee per blob gas required to replace a blob transaction.
+    pub replace_blob_fee_price_bump: u128,
 }
 
 impl PriceBumpConfig {
@@ -164,6 +260,27 @@ impl PriceBumpConfig {
         }
         self.default_price_bump
     }
+
+    /// Returns the minimum fees a transaction needs to replace a transaction with the given fees.
+    pub const fn bumped_fees(&self, existing: &ReplacementFees) -> ReplacementFees {
+        const fn bumped(fee: u128, price_bump: u128) -> u128 {
+            fee * (100 + price_bump) / 100
+        }
+
+        let price_bump = self.price_bump(existing.tx_type);
+        ReplacementFees {
+            tx_type: existing.tx_type,
+            max_fee_per_gas: bumped(existing.max_fee_per_gas, price_bump),
+            max_priority_fee_per_gas: match existing.max_priority_fee_per_gas {
+                Some(fee) => Some(bumped(fee, price_bump)),
+                None => None,
+            },
+            max_fee_per_blob_gas: match existing.max_fee_per_blob_gas {
+                Some(fee) => Some(bumped(fee, self.replace_blob_fee_price_bump)),
+                None => None,
+            },
+        }
+    }
 }
 
 impl Default for PriceBumpConfig {
@@ -171,7 +288,55 @@ impl Default for PriceBumpConfig {
         Self {
             default_price_bump: DEFAULT_PRICE_BUMP,
             replace_blob_tx_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            replace_blob_fee_price_bump: REPLACE_BLOB_PRICE_BUMP,
+        }
+    }
+}
+
+impl ReplacementPolicy for PriceBumpConfig {
+    /// A replacement is underpriced if it doesn't bump the fees of the existing transaction by
+    /// the price bump of its type. This applies to both standard gas fees and, for blob-carrying
+    /// transactions (EIP-4844), the blob-specific fees.
+    fn is_underpriced(&self, existing: &ReplacementFees, replacement: &ReplacementFees) -> bool {
+        let bumped = |fee: u128, price_bump: u128| fee * (100 + price_bump) / 100;
+
+        // Retrieve the required price bump percentage for this type of transaction.
+        //
+        // The bump is different for EIP-4844 and other transactions.
+        let price_bump = self.price_bump(existing.tx_type);
+
+        // Check if the max fee per gas is underpriced.
+        if replacement.max_fee_per_gas < bumped(existing.max_fee_per_gas, price_bump) {
+            return true
+        }
+
+        let existing_max_priority_fee_per_gas =
+            existing.max_priority_fee_per_gas.unwrap_or_default();
+        let replacement_max_priority_fee_per_gas =
+            replacement.max_priority_fee_per_gas.unwrap_or_default();
+
+        // Check max priority fee per gas (relevant for EIP-1559 transactions only)
+        if existing_max_priority_fee_per_gas != 0 &&
+            replacement_max_priority_fee_per_gas != 0 &&
+            replacement_max_priority_fee_per_gas <
+                bumped(existing_max_priority_fee_per_gas, price_bump)
+        {
+            return true
+        }
+
+        // Check max blob fee per gas
+        if let Some(existing_max_blob_fee_per_gas) = existing.max_fee_per_blob_gas {
+            // This enforces that blob txs can only be replaced by blob txs
+            let replacement_max_blob_fee_per_gas =
+                replacement.max_fee_per_blob_gas.unwrap_or_default();
+            if replacement_max_blob_fee_per_gas <
+                bumped(existing_max_blob_fee_per_gas, self.replace_blob_fee_price_bump)
+            {
+                return true
+            }
         }
+
+        false
     }
 }
 
@@ -357,4 +522,24 @@ mod tests {
             SubPoolLimit { max_txs: limit.max_txs * 2, max_size: limit.max_size * 2 }
         )
     }
+
+    #[test]
+    fn bumped_fees_replace_existing() {
+        let bumps = PriceBumpConfig::default();
+        let existing = ReplacementFees {
+            tx_type: EIP4844_TX_TYPE_ID,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: Some(10),
+            max_fee_per_blob_gas: Some(50),
+        };
+
+        let bumped = bumps.bumped_fees(&existing);
+        assert_eq!(bumped.max_fee_per_gas, 200);
+        assert_eq!(bumped.max_priority_fee_per_gas, Some(20));
+        assert_eq!(bumped.max_fee_per_blob_gas, Some(100));
+        assert!(!bumps.is_underpriced(&existing, &bumped));
+
+        let underpriced = ReplacementFees { max_fee_per_gas: 199, ..bumped };
+        assert!(bumps.is_underpriced(&existing, &underpriced));
+    }
 }
@@ -60,6 +60,10 @@ pub enum PoolErrorKind {
     /// respect the size limits of the pool.
     #[error("transaction discarded outright due to pool size constraints")]
     DiscardedOnInsert,
+    /// Thrown when the nonce of a transaction is further ahead of the sender's on-chain nonce than
+    /// the configured maximum nonce gap.
+    #[error("transaction nonce exceeds the sender's nonce by more than {0}")]
+    ExceededMaxNonceGap(u64),
     /// Thrown when the transaction is considered invalid.
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidPoolTransactionError),
@@ -130,6 +134,11 @@ impl PoolError {
                 // valid tx but dropped due to size constraints
                 false
             }
+            PoolErrorKind::ExceededMaxNonceGap(_) => {
+                // valid tx but rejected by the pool's nonce gap policy, the peer may not be aware
+                // of the sender's latest nonce
+                false
+            }
             PoolErrorKind::InvalidTransaction(err) => {
                 // transaction rejected because it violates constraints
                 err.is_bad_transaction()
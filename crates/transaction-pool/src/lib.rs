@@ -272,9 +272,13 @@
 
 pub use crate::{
     batcher::{BatchTxProcessor, BatchTxRequest},
+    blob_fee::{
+        BlobFeeProjection, DEFAULT_BLOB_FEE_PROJECTION_BLOCKS, MAX_BLOB_FEE_PROJECTION_BLOCKS,
+    },
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, ReplacementFees, ReplacementPolicy,
+        SubPoolLimit, SubPoolLimits, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
@@ -283,16 +287,17 @@ pub use crate::{
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        AllTransactionsEvents, DropReason, FullTransactionEvent, NewTransactionEvent, PoolEvent,
+        PoolEvents, PoolEventsLagged, SequencedPoolEvent, TransactionEvent, TransactionEvents,
+        TransactionListenerKind,
     },
     traits::*,
     validate::{
-        EthTransactionValidator, TransactionValidationOutcome, TransactionValidationTaskExecutor,
-        TransactionValidator, ValidPoolTransaction,
+        EthTransactionValidator, PendingStateView, TransactionValidationOutcome,
+        TransactionValidationTaskExecutor, TransactionValidator, ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{identifier::TransactionId, pool::PoolInner, validate::PendingStateProvider};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2},
     eip7594::BlobTransactionSidecarVariant,
@@ -316,6 +321,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod batcher;
+mod blob_fee;
 pub mod blobstore;
 mod config;
 pub mod identifier;
@@ -363,6 +369,18 @@ where
         self.inner().config()
     }
 
+    /// Binds the [`PendingStateView`] of the validator to this pool, so that transactions are
+    /// validated against the pending view of this pool.
+    ///
+    /// Returns `false` if the view was already bound.
+    pub fn bind_pending_state_view(&self, view: &PendingStateView) -> bool
+    where
+        V: 'static,
+    {
+        let pool: Arc<dyn PendingStateProvider> = self.pool.clone();
+        view.bind(Arc::downgrade(&pool))
+    }
+
     /// Validates the given transaction
     async fn validate(
         &self,
@@ -484,10 +502,22 @@ where
         self.pool.size()
     }
 
+    fn pool_stats(&self, percentiles: &[f64]) -> PoolStats {
+        self.pool.stats(percentiles)
+    }
+
     fn block_info(&self) -> BlockInfo {
         self.pool.block_info()
     }
 
+    fn subpool_limits(&self) -> SubPoolLimits {
+        self.pool.subpool_limits()
+    }
+
+    fn set_subpool_limits(&self, limits: SubPoolLimits) -> SubPoolLimits {
+        self.pool.set_subpool_limits(limits)
+    }
+
     async fn add_transaction_and_subscribe(
         &self,
         origin: TransactionOrigin,
@@ -540,6 +570,10 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn pool_events(&self) -> PoolEvents<Self::Transaction> {
+        self.pool.add_pool_events_listener()
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -629,6 +663,10 @@ where
         self.pool.all_transactions()
     }
 
+    fn blob_gas_demand(&self) -> u64 {
+        self.pool.blob_gas_demand()
+    }
+
     fn all_transaction_hashes(&self) -> Vec<TxHash> {
         self.pool.all_transaction_hashes()
     }
@@ -816,6 +854,22 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn convert_blob_sidecars(&self) {
+        self.pool.convert_blob_sidecars()
+    }
+
+    fn remove_timed_out_transactions(&self, hashes: Vec<TxHash>) {
+        self.pool.remove_timed_out_transactions(hashes);
+    }
+
+    fn rebroadcast_transactions(&self, transactions: Vec<(TxHash, u64)>) {
+        let transactions = transactions
+            .into_iter()
+            .filter_map(|(hash, pending_blocks)| Some((self.pool.get(&hash)?, pending_blocks)))
+            .collect();
+        self.pool.rebroadcast_transactions(transactions)
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
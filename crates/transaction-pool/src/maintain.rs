@@ -5,18 +5,18 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin, ValidPoolTransaction,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{BlockNumberOrTag, Decodable2718, Encodable2718};
-use alloy_primitives::{Address, BlockHash, BlockNumber};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash};
 use alloy_rlp::{Bytes, Encodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
 use reth_chain_state::CanonStateNotification;
-use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_execution_types::ChangedAccount;
 use reth_fs_util::FsPathError;
 use reth_primitives_traits::{
@@ -27,7 +27,7 @@ use reth_tasks::TaskSpawner;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
     sync::Arc,
@@ -64,6 +64,16 @@ pub struct MaintainPoolConfig {
     ///   - no price exemptions
     ///   - no eviction exemptions
     pub no_local_exemptions: bool,
+
+    /// Number of blocks a local transaction can remain pending before it is re-broadcast.
+    ///
+    /// Default: disabled
+    pub local_rebroadcast_blocks: Option<u64>,
+
+    /// Maximum amount of time local transactions are kept in the pool.
+    ///
+    /// Default: disabled
+    pub local_tx_lifetime: Option<Duration>,
 }
 
 impl Default for MaintainPoolConfig {
@@ -73,6 +83,8 @@ impl Default for MaintainPoolConfig {
             max_reload_accounts: 100,
             max_tx_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             no_local_exemptions: false,
+            local_rebroadcast_blocks: None,
+            local_tx_lifetime: None,
         }
     }
 }
@@ -103,7 +115,7 @@ where
     N: NodePrimitives,
     Client: StateProviderFactory
         + BlockReaderIdExt<Header = N::BlockHeader>
-        + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader>>
+        + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader> + EthereumHardforks>
         + Clone
         + 'static,
     P: TransactionPoolExt<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
@@ -129,7 +141,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     N: NodePrimitives,
     Client: StateProviderFactory
         + BlockReaderIdExt<Header = N::BlockHeader>
-        + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader>>
+        + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader> + EthereumHardforks>
         + Clone
         + 'static,
     P: TransactionPoolExt<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
@@ -174,9 +186,15 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     // eviction interval for stale non local txs
     let mut stale_eviction_interval = time::interval(config.max_tx_lifetime);
 
+    // keeps track of pending local transactions that need to be re-broadcast
+    let mut local_tx_tracker = LocalTransactionTracker::default();
+
     // toggle for the first notification
     let mut first_event = true;
 
+    // whether the blob sidecars of the pool have been converted to the Osaka format
+    let mut osaka_sidecars_converted = false;
+
     // The update loop that waits for new blocks and reorgs and performs pool updated
     // Listen for new chain events and derive the update action for the pool
     loop {
@@ -307,6 +325,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 
         // handle the new block or reorg
         let Some(event) = event else { continue };
+        let tip_timestamp = event.tip().timestamp();
         match event {
             CanonStateNotification::Reorg { old, new } => {
                 let (old_blocks, old_state) = old.inner();
@@ -498,6 +517,87 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
         }
+
+        // blob transactions that entered the pool before Osaka carry EIP-4844 sidecars that can no
+        // longer be propagated, so they are converted once the pool moved past the fork
+        if !osaka_sidecars_converted &&
+            client.chain_spec().is_osaka_active_at_timestamp(tip_timestamp)
+        {
+            osaka_sidecars_converted = true;
+            let pool = pool.clone();
+            task_spawner.spawn_blocking(Box::pin(async move {
+                debug!(target: "txpool", "converting blob sidecars to eip-7594");
+                pool.convert_blob_sidecars();
+            }));
+        }
+
+        // drop local transactions that exceeded their lifetime
+        if let Some(lifetime) = config.local_tx_lifetime {
+            let timed_out: Vec<_> = pool
+                .get_local_transactions()
+                .into_iter()
+                .filter(|tx| tx.timestamp.elapsed() > lifetime)
+                .map(|tx| *tx.hash())
+                .collect();
+            if !timed_out.is_empty() {
+                debug!(target: "txpool", count=%timed_out.len(), "removing timed out local transactions");
+                metrics.inc_timed_out_transactions(timed_out.len());
+                pool.remove_timed_out_transactions(timed_out);
+            }
+        }
+
+        // re-broadcast local transactions that remain pending
+        if let Some(rebroadcast_blocks) = config.local_rebroadcast_blocks {
+            let rebroadcast = local_tx_tracker.on_new_block(
+                pool.block_info().last_seen_block_number,
+                pool.get_local_pending_transactions(),
+                rebroadcast_blocks,
+            );
+            if !rebroadcast.is_empty() {
+                debug!(target: "txpool", count=%rebroadcast.len(), "re-broadcasting pending local transactions");
+                metrics.inc_rebroadcast_transactions(rebroadcast.len());
+                pool.rebroadcast_transactions(
+                    rebroadcast.into_iter().map(|(tx, blocks)| (*tx.hash(), blocks)).collect(),
+                );
+            }
+        }
+    }
+}
+
+/// Tracks how long local transactions have been pending, to re-broadcast those that are not
+/// included in a block.
+#[derive(Debug, Default)]
+struct LocalTransactionTracker {
+    /// The block at which each pending local transaction was first seen and the block at which it
+    /// was last broadcast.
+    pending: HashMap<TxHash, (BlockNumber, BlockNumber)>,
+}
+
+impl LocalTransactionTracker {
+    /// Updates the tracker with the pending local transactions at the given block.
+    ///
+    /// Returns the transactions that have not been broadcast for `rebroadcast_blocks` blocks,
+    /// paired with the number of blocks they have been pending for.
+    fn on_new_block<T: PoolTransaction>(
+        &mut self,
+        block: BlockNumber,
+        pending: Vec<Arc<ValidPoolTransaction<T>>>,
+        rebroadcast_blocks: u64,
+    ) -> Vec<(Arc<ValidPoolTransaction<T>>, u64)> {
+        let mut tracked = HashMap::with_capacity(pending.len());
+        let mut rebroadcast = Vec::new();
+        for tx in pending {
+            let (first_seen, mut last_broadcast) =
+                self.pending.get(tx.hash()).copied().unwrap_or((block, block));
+            if block.saturating_sub(last_broadcast) >= rebroadcast_blocks {
+                last_broadcast = block;
+                rebroadcast.push((Arc::clone(&tx), block.saturating_sub(first_seen)));
+            }
+            tracked.insert(*tx.hash(), (first_seen, last_broadcast));
+        }
+        // transactions that are no longer pending are no longer tracked
+        self.pending = tracked;
+        rebroadcast
     }
 }
 
@@ -582,21 +682,31 @@ where
     I: IntoIterator<Item = Address>,
     Client: StateProviderFactory,
 {
-    let addresses = addresses.into_iter();
+    let addresses = addresses.into_iter().collect::<Vec<_>>();
     let mut res = LoadedAccounts::default();
     let state = match client.history_by_block_hash(at) {
         Ok(state) => state,
-        Err(err) => return Err(Box::new((addresses.collect(), err))),
+        Err(err) => return Err(Box::new((addresses.into_iter().collect(), err))),
     };
-    for addr in addresses {
-        if let Ok(maybe_acc) = state.basic_account(&addr) {
-            let acc = maybe_acc
-                .map(|acc| ChangedAccount { address: addr, nonce: acc.nonce, balance: acc.balance })
-                .unwrap_or_else(|| ChangedAccount::empty(addr));
-            res.accounts.push(acc)
-        } else {
-            // failed to load account.
-            res.failed_to_load.push(addr);
+    match state.basic_accounts(&addresses) {
+        Ok(accounts) => {
+            res.accounts = addresses
+                .into_iter()
+                .zip(accounts)
+                .map(|(addr, maybe_acc)| {
+                    maybe_acc
+                        .map(|acc| ChangedAccount {
+                            address: addr,
+                            nonce: acc.nonce,
+                            balance: acc.balance,
+                        })
+                        .unwrap_or_else(|| ChangedAccount::empty(addr))
+                })
+                .collect();
+        }
+        Err(_) => {
+            // failed to load accounts.
+            res.failed_to_load = addresses;
         }
     }
     Ok(res)
@@ -763,6 +873,7 @@ mod tests {
     use super::*;
     use crate::{
         blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        test_utils::{MockTransaction, MockTransactionFactory},
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use alloy_eips::eip2718::Decodable2718;
@@ -872,4 +983,28 @@ mod tests {
         assert_eq!(tracker.update(None), None);
         assert_eq!(tracker.last_finalized_block, None);
     }
+
+    #[test]
+    fn test_rebroadcast_local_pending_transactions() {
+        let mut factory = MockTransactionFactory::default();
+        let tx = factory.validated_arc(MockTransaction::eip1559());
+        let mut tracker = LocalTransactionTracker::default();
+
+        // first seen, nothing to re-broadcast yet
+        assert!(tracker.on_new_block(10, vec![Arc::clone(&tx)], 3).is_empty());
+        assert!(tracker.on_new_block(12, vec![Arc::clone(&tx)], 3).is_empty());
+
+        let rebroadcast = tracker.on_new_block(13, vec![Arc::clone(&tx)], 3);
+        assert_eq!(rebroadcast.len(), 1);
+        assert_eq!(rebroadcast[0].1, 3);
+
+        // next re-broadcast is due 3 blocks after the last one
+        assert!(tracker.on_new_block(15, vec![Arc::clone(&tx)], 3).is_empty());
+        let rebroadcast = tracker.on_new_block(16, vec![Arc::clone(&tx)], 3);
+        assert_eq!(rebroadcast[0].1, 6);
+
+        // no longer pending
+        assert!(tracker.on_new_block(17, vec![], 3).is_empty());
+        assert!(tracker.pending.is_empty());
+    }
 }
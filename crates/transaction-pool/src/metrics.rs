@@ -15,6 +15,8 @@ pub struct TxPoolMetrics {
     pub(crate) invalid_transactions: Counter,
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
+    /// Number of nonce gapped transactions evicted from the pool after exceeding their lifetime
+    pub(crate) evicted_nonce_gapped_transactions: Counter,
 
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
@@ -91,6 +93,10 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of pending local transactions that have been re-broadcast.
+    pub(crate) rebroadcast_transactions: Counter,
+    /// Counter for the number of local transactions dropped after exceeding their lifetime.
+    pub(crate) timed_out_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -109,6 +115,16 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_rebroadcast_transactions(&self, count: usize) {
+        self.rebroadcast_transactions.increment(count as u64);
+    }
+
+    #[inline]
+    pub(crate) fn inc_timed_out_transactions(&self, count: usize) {
+        self.timed_out_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
@@ -10,9 +10,10 @@ use crate::{
     traits::{BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar},
     validate::ValidTransaction,
     AddedTransactionOutcome, AllPoolTransactions, AllTransactionsEvents, BestTransactions,
-    BlockInfo, EthPoolTransaction, EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize,
-    PoolTransaction, PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
-    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    BlockInfo, EthPoolTransaction, EthPooledTransaction, NewTransactionEvent, PoolEvents,
+    PoolResult, PoolSize, PoolTransaction, PropagatedTransactions, TransactionEvents,
+    TransactionOrigin, TransactionPool, TransactionValidationOutcome, TransactionValidator,
+    ValidPoolTransaction,
 };
 use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -23,7 +24,7 @@ use alloy_primitives::{Address, TxHash, B256, U256};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives_traits::Recovered;
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
-use tokio::sync::{mpsc, mpsc::Receiver};
+use tokio::sync::{broadcast, mpsc, mpsc::Receiver};
 
 /// A [`TransactionPool`] implementation that does nothing.
 ///
@@ -119,6 +120,10 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         AllTransactionsEvents::new(mpsc::channel(1).1)
     }
 
+    fn pool_events(&self) -> PoolEvents<Self::Transaction> {
+        PoolEvents::new(broadcast::channel(1).1)
+    }
+
     fn pending_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
@@ -1,6 +1,8 @@
 use super::txpool::PendingFees;
 use crate::{
-    identifier::TransactionId, pool::size::SizeTracker, traits::BestTransactionsAttributes,
+    identifier::TransactionId,
+    pool::{size::SizeTracker, stats::FeeStatsTracker},
+    traits::{BestTransactionsAttributes, SubPoolStats},
     PoolTransaction, SubPoolLimit, ValidPoolTransaction,
 };
 use std::{
@@ -32,6 +34,8 @@ pub struct BlobTransactions<T: PoolTransaction> {
     ///
     /// See also [`reth_primitives_traits::InMemorySize::size`].
     size_of: SizeTracker,
+    /// Keeps track of the fees, gas and blobs of the transactions in this pool.
+    stats: FeeStatsTracker,
 }
 
 // === impl BlobTransactions ===
@@ -51,6 +55,7 @@ impl<T: PoolTransaction> BlobTransactions<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.stats.add(&tx);
 
         // set transaction, which will also calculate priority based on current pending fees
         let transaction = BlobTransaction::new(tx, submission_id, &self.pending_fees);
@@ -77,6 +82,7 @@ impl<T: PoolTransaction> BlobTransactions<T> {
 
         // keep track of size
         self.size_of -= tx.transaction.size();
+        self.stats.remove(&tx.transaction);
 
         Some(tx.transaction)
     }
@@ -125,6 +131,11 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         limit.is_exceeded(self.len(), self.size())
     }
 
+    /// Returns the fee and gas stats of this pool with the fees at the given percentiles.
+    pub(crate) fn stats(&self, percentiles: &[f64]) -> SubPoolStats {
+        self.stats.stats(percentiles)
+    }
+
     /// The reported size of all transactions in this pool.
     pub(crate) fn size(&self) -> usize {
         self.size_of.into()
@@ -253,6 +264,7 @@ impl<T: PoolTransaction> Default for BlobTransactions<T> {
             by_id: Default::default(),
             all: Default::default(),
             size_of: Default::default(),
+            stats: Default::default(),
             pending_fees: Default::default(),
         }
     }
@@ -1,4 +1,6 @@
-use crate::{traits::PropagateKind, PoolTransaction, SubPool, ValidPoolTransaction};
+use crate::{
+    traits::PropagateKind, PoolTransaction, ReplacementFees, SubPool, ValidPoolTransaction,
+};
 use alloy_primitives::{TxHash, B256};
 use std::sync::Arc;
 
@@ -105,3 +107,130 @@ impl<T: PoolTransaction> Clone for NewTransactionEvent<T> {
         Self { subpool: self.subpool, transaction: self.transaction.clone() }
     }
 }
+
+/// An event emitted on the pool event bus, see
+/// [`TransactionPool::pool_events`](crate::TransactionPool::pool_events).
+#[derive(Debug)]
+pub enum PoolEvent<T: PoolTransaction> {
+    /// A new transaction was added to the pool.
+    ///
+    /// This is followed by a [`PoolEvent::Pending`] or [`PoolEvent::Queued`] event for the
+    /// transaction.
+    Added {
+        /// The added transaction.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The subpool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// Transaction was moved to the pending pool, either on insertion or by a promotion.
+    Pending(TxHash),
+    /// Transaction was moved to the queued pool.
+    Queued(TxHash),
+    /// Transaction has been included in the block belonging to this hash.
+    Mined {
+        /// The hash of the mined transaction.
+        tx_hash: TxHash,
+        /// The hash of the mined block that contains the transaction.
+        block_hash: B256,
+    },
+    /// Transaction was dropped from the pool.
+    Dropped {
+        /// The hash of the dropped transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was dropped.
+        reason: DropReason,
+    },
+    /// Transaction has been replaced by the transaction belonging to the hash.
+    ///
+    /// E.g. same (sender + nonce) pair
+    Replaced {
+        /// The transaction that was replaced.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The transaction that replaced the event subject.
+        replaced_by: TxHash,
+    },
+    /// A local transaction remained pending for a number of blocks and was re-broadcast.
+    Rebroadcast {
+        /// The hash of the re-broadcast transaction.
+        tx_hash: TxHash,
+        /// The number of blocks the transaction has been pending for.
+        pending_blocks: u64,
+        /// The minimum fees of a replacement transaction that bumps the fees of this transaction.
+        suggested_fees: ReplacementFees,
+    },
+}
+
+impl<T: PoolTransaction> PoolEvent<T> {
+    /// Returns the hash of the transaction this event is about.
+    pub fn tx_hash(&self) -> &TxHash {
+        match self {
+            Self::Added { transaction, .. } | Self::Replaced { transaction, .. } => {
+                transaction.hash()
+            }
+            Self::Pending(tx_hash) |
+            Self::Queued(tx_hash) |
+            Self::Mined { tx_hash, .. } |
+            Self::Dropped { tx_hash, .. } |
+            Self::Rebroadcast { tx_hash, .. } => tx_hash,
+        }
+    }
+}
+
+impl<T: PoolTransaction> Clone for PoolEvent<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added { transaction, subpool } => {
+                Self::Added { transaction: Arc::clone(transaction), subpool: *subpool }
+            }
+            Self::Pending(hash) => Self::Pending(*hash),
+            Self::Queued(hash) => Self::Queued(*hash),
+            Self::Mined { tx_hash, block_hash } => {
+                Self::Mined { tx_hash: *tx_hash, block_hash: *block_hash }
+            }
+            Self::Dropped { tx_hash, reason } => {
+                Self::Dropped { tx_hash: *tx_hash, reason: *reason }
+            }
+            Self::Replaced { transaction, replaced_by } => {
+                Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
+            }
+            Self::Rebroadcast { tx_hash, pending_blocks, suggested_fees } => Self::Rebroadcast {
+                tx_hash: *tx_hash,
+                pending_blocks: *pending_blocks,
+                suggested_fees: *suggested_fees,
+            },
+        }
+    }
+}
+
+/// The reason a transaction was dropped from the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DropReason {
+    /// Transaction was discarded due to the configured limits of the pool or because it could not
+    /// be validated.
+    Discarded,
+    /// Transaction became invalid indefinitely.
+    Invalid,
+    /// Transaction was explicitly removed from the pool.
+    Removed,
+    /// Local transaction exceeded its configured lifetime.
+    Timeout,
+}
+
+/// A [`PoolEvent`] with its position in the sequence of all events emitted by the pool.
+///
+/// Sequence numbers are assigned consecutively, so a gap between the sequence numbers of two
+/// received events means that the events in between were missed.
+#[derive(Debug)]
+pub struct SequencedPoolEvent<T: PoolTransaction> {
+    /// The sequence number of the event.
+    pub seq: u64,
+    /// The event.
+    pub event: PoolEvent<T>,
+}
+
+impl<T: PoolTransaction> Clone for SequencedPoolEvent<T> {
+    fn clone(&self) -> Self {
+        Self { seq: self.seq, event: self.event.clone() }
+    }
+}
@@ -1,25 +1,35 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+    pool::events::{
+        DropReason, FullTransactionEvent, NewTransactionEvent, PoolEvent, SequencedPoolEvent,
+        TransactionEvent,
+    },
     traits::{NewBlobSidecar, PropagateKind},
-    PoolTransaction, ValidPoolTransaction,
+    PoolTransaction, ReplacementFees, SubPool, ValidPoolTransaction,
 };
 use alloy_primitives::{TxHash, B256};
-use futures_util::Stream;
+use futures_util::{ready, Stream};
 use std::{
     collections::{hash_map::Entry, HashMap},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::mpsc::{
-    self as mpsc, error::TrySendError, Receiver, Sender, UnboundedReceiver, UnboundedSender,
+use tokio::sync::{
+    broadcast,
+    mpsc::{
+        self as mpsc, error::TrySendError, Receiver, Sender, UnboundedReceiver, UnboundedSender,
+    },
 };
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 use tracing::debug;
 /// The size of the event channel used to propagate transaction events.
 const TX_POOL_EVENT_CHANNEL_SIZE: usize = 1024;
 
+/// The number of events the pool event bus buffers for each receiver before it starts lagging.
+const TX_POOL_EVENT_BUS_SIZE: usize = 4096;
+
 /// A Stream that receives [`TransactionEvent`] only for the transaction with the given hash.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
@@ -68,6 +78,65 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     }
 }
 
+/// A Stream that receives the [`SequencedPoolEvent`]s of _all_ transactions.
+///
+/// If the receiver falls too far behind, the oldest events are dropped and the stream yields
+/// [`PoolEventsLagged`] with the number of missed events before it resumes with the oldest event
+/// that is still buffered.
+#[derive(Debug)]
+#[pin_project::pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct PoolEvents<T: PoolTransaction> {
+    #[pin]
+    events: BroadcastStream<SequencedPoolEvent<T>>,
+    /// The sequence number of the next event.
+    next_seq: Option<u64>,
+}
+
+impl<T: PoolTransaction + 'static> PoolEvents<T> {
+    /// Create a new instance of this stream.
+    pub fn new(events: broadcast::Receiver<SequencedPoolEvent<T>>) -> Self {
+        Self { events: BroadcastStream::new(events), next_seq: None }
+    }
+}
+
+impl<T: PoolTransaction> PoolEvents<T> {
+    /// Returns the sequence number of the next event yielded by this stream.
+    ///
+    /// Returns `None` if no event was received yet.
+    pub const fn next_seq(&self) -> Option<u64> {
+        self.next_seq
+    }
+}
+
+impl<T: PoolTransaction + 'static> Stream for PoolEvents<T> {
+    type Item = Result<SequencedPoolEvent<T>, PoolEventsLagged>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = match ready!(this.events.poll_next(cx)) {
+            Some(Ok(event)) => {
+                *this.next_seq = Some(event.seq + 1);
+                Ok(event)
+            }
+            Some(Err(BroadcastStreamRecvError::Lagged(missed))) => {
+                *this.next_seq = this.next_seq.map(|seq| seq + missed);
+                Err(PoolEventsLagged { missed })
+            }
+            None => return Poll::Ready(None),
+        };
+        Poll::Ready(Some(item))
+    }
+}
+
+/// Returned by [`PoolEvents`] if the receiver lagged behind and events were dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("pool events receiver lagged behind, missed {missed} events")]
+pub struct PoolEventsLagged {
+    /// The number of missed events.
+    pub missed: u64,
+}
+
 /// A type that broadcasts [`TransactionEvent`] to installed listeners.
 ///
 /// This is essentially a multi-producer, multi-consumer channel where each event is broadcast to
@@ -78,6 +147,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// The bus for the sequenced events of all transactions.
+    bus: PoolEventBus<T>,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -85,6 +156,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            bus: PoolEventBus::default(),
         }
     }
 }
@@ -113,7 +185,9 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     /// Returns true if no listeners are installed
     #[inline]
     pub(crate) fn is_empty(&self) -> bool {
-        self.all_events_broadcaster.is_empty() && self.broadcasters_by_hash.is_empty()
+        self.all_events_broadcaster.is_empty() &&
+            self.broadcasters_by_hash.is_empty() &&
+            self.bus.is_empty()
     }
 
     /// Create a new subscription for the given transaction hash.
@@ -138,9 +212,23 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         AllTransactionsEvents::new(rx)
     }
 
+    /// Create a new subscription to the sequenced events of all transactions.
+    pub(crate) fn subscribe_bus(&self) -> PoolEvents<T>
+    where
+        T: 'static,
+    {
+        PoolEvents::new(self.bus.sender.subscribe())
+    }
+
+    /// Notify listeners about a new transaction that was added to the given subpool.
+    pub(crate) fn added(&mut self, tx: Arc<ValidPoolTransaction<T>>, subpool: SubPool) {
+        self.bus.publish(PoolEvent::Added { transaction: tx, subpool });
+    }
+
     /// Notify listeners about a transaction that was added to the pending queue.
     pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
         self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
+        self.bus.publish(PoolEvent::Pending(*tx));
 
         if let Some(replaced) = replaced {
             // notify listeners that this transaction was replaced
@@ -156,11 +244,13 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Replaced(replaced_by),
             FullTransactionEvent::Replaced { transaction, replaced_by },
         );
+        self.bus.publish(PoolEvent::Replaced { transaction: tx, replaced_by });
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
     pub(crate) fn queued(&mut self, tx: &TxHash) {
         self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx));
+        self.bus.publish(PoolEvent::Queued(*tx));
     }
 
     /// Notify listeners about a transaction that was propagated.
@@ -186,12 +276,54 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about a transaction that was discarded.
     pub(crate) fn discarded(&mut self, tx: &TxHash) {
+        self.dropped(tx, DropReason::Discarded);
+    }
+
+    /// Notify listeners about all explicitly removed transactions.
+    #[inline]
+    pub(crate) fn removed_many(&mut self, removed: &[Arc<ValidPoolTransaction<T>>]) {
+        if self.is_empty() {
+            return
+        }
+        for tx in removed {
+            self.dropped(tx.hash(), DropReason::Removed);
+        }
+    }
+
+    /// Notify listeners about all local transactions that exceeded their lifetime.
+    #[inline]
+    pub(crate) fn timed_out_many(&mut self, timed_out: &[Arc<ValidPoolTransaction<T>>]) {
+        if self.is_empty() {
+            return
+        }
+        for tx in timed_out {
+            self.dropped(tx.hash(), DropReason::Timeout);
+        }
+    }
+
+    /// Notify listeners about a local transaction that was re-broadcast.
+    pub(crate) fn rebroadcast(
+        &mut self,
+        tx: &TxHash,
+        pending_blocks: u64,
+        suggested_fees: ReplacementFees,
+    ) {
+        self.bus.publish(PoolEvent::Rebroadcast { tx_hash: *tx, pending_blocks, suggested_fees });
+    }
+
+    /// Notify listeners about a transaction that was dropped from the pool.
+    ///
+    /// Listeners for specific transactions and all transactions receive a discarded event,
+    /// regardless of the reason.
+    fn dropped(&mut self, tx: &TxHash, reason: DropReason) {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+        self.bus.publish(PoolEvent::Dropped { tx_hash: *tx, reason });
     }
 
     /// Notify listeners about a transaction that was invalid.
     pub(crate) fn invalid(&mut self, tx: &TxHash) {
         self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
+        self.bus.publish(PoolEvent::Dropped { tx_hash: *tx, reason: DropReason::Invalid });
     }
 
     /// Notify listeners that the transaction was mined
@@ -201,6 +333,45 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Mined(block_hash),
             FullTransactionEvent::Mined { tx_hash: *tx, block_hash },
         );
+        self.bus.publish(PoolEvent::Mined { tx_hash: *tx, block_hash });
+    }
+}
+
+/// Sender half of the event bus for all transactions.
+///
+/// Every published event is assigned the next sequence number, so that receivers can detect
+/// missed events.
+#[derive(Debug)]
+struct PoolEventBus<T: PoolTransaction> {
+    /// The sender half of the bus.
+    sender: broadcast::Sender<SequencedPoolEvent<T>>,
+    /// The sequence number of the next event.
+    next_seq: u64,
+}
+
+impl<T: PoolTransaction> Default for PoolEventBus<T> {
+    fn default() -> Self {
+        Self { sender: broadcast::channel(TX_POOL_EVENT_BUS_SIZE).0, next_seq: 0 }
+    }
+}
+
+impl<T: PoolTransaction> PoolEventBus<T> {
+    /// Publishes the event to all receivers.
+    ///
+    /// Events are not sequenced if there are no receivers.
+    fn publish(&mut self, event: PoolEvent<T>) {
+        if self.is_empty() {
+            return
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let _ = self.sender.send(SequencedPoolEvent { seq, event });
+    }
+
+    /// Returns true if there are no receivers.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.sender.receiver_count() == 0
     }
 }
 
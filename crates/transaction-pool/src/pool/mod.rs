@@ -81,10 +81,14 @@ use crate::{
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, GetPooledTransactionLimit,
-        NewBlobSidecar, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        NewBlobSidecar, PoolSize, PoolStats, PoolTransaction, PropagatedTransactions,
+        TransactionOrigin,
     },
-    validate::{TransactionValidationOutcome, ValidPoolTransaction, ValidTransaction},
-    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
+    validate::{
+        PendingBalanceChanges, PendingStateProvider, TransactionValidationOutcome,
+        ValidPoolTransaction, ValidTransaction,
+    },
+    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, SubPoolLimits, TransactionOrdering,
     TransactionValidator,
 };
 
@@ -103,8 +107,13 @@ use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
-pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
+pub use events::{
+    DropReason, FullTransactionEvent, NewTransactionEvent, PoolEvent, SequencedPoolEvent,
+    TransactionEvent,
+};
+pub use listener::{
+    AllTransactionsEvents, PoolEvents, PoolEventsLagged, TransactionEvents, TransactionListenerKind,
+};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 use reth_primitives_traits::Block;
@@ -116,6 +125,7 @@ mod parked;
 pub(crate) mod pending;
 pub(crate) mod size;
 pub(crate) mod state;
+pub(crate) mod stats;
 pub mod txpool;
 mod update;
 
@@ -187,6 +197,11 @@ where
         self.get_pool_data().size()
     }
 
+    /// Returns the fee and gas stats of all sub-pools with the fees at the given percentiles.
+    pub fn stats(&self, percentiles: &[f64]) -> PoolStats {
+        self.get_pool_data().stats(percentiles)
+    }
+
     /// Returns the currently tracked block
     pub fn block_info(&self) -> BlockInfo {
         self.get_pool_data().block_info()
@@ -228,6 +243,9 @@ where
     }
 
     /// Get the config the pool was configured with.
+    ///
+    /// Note: this doesn't reflect sub-pool limits that were updated with
+    /// [`PoolInner::set_subpool_limits`].
     pub const fn config(&self) -> &PoolConfig {
         &self.config
     }
@@ -278,6 +296,11 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for the sequenced events of all transactions.
+    pub fn add_pool_events_listener(&self) -> PoolEvents<T::Transaction> {
+        self.event_listener.read().subscribe_bus()
+    }
+
     /// Returns a read lock to the pool's data.
     pub fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
@@ -738,6 +761,8 @@ where
             return
         }
 
+        listener.added(tx.transaction().clone(), tx.subpool());
+
         match tx {
             AddedTransaction::Pending(tx) => {
                 let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
@@ -801,6 +826,15 @@ where
         }
     }
 
+    /// Returns the combined blob gas of all blob transactions in the pool.
+    pub fn blob_gas_demand(&self) -> u64 {
+        self.get_pool_data()
+            .all()
+            .transactions_iter()
+            .filter_map(|tx| alloy_consensus::Transaction::blob_gas_used(&tx.transaction))
+            .sum()
+    }
+
     /// Returns _all_ transactions in the pool
     pub fn all_transaction_hashes(&self) -> Vec<TxHash> {
         self.get_pool_data().all().transactions_iter().map(|tx| *tx.hash()).collect()
@@ -819,7 +853,7 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
-        self.event_listener.write().discarded_many(&removed);
+        self.event_listener.write().removed_many(&removed);
 
         removed
     }
@@ -835,11 +869,7 @@ where
         }
         let removed = self.pool.write().remove_transactions_and_descendants(hashes);
 
-        let mut listener = self.event_listener.write();
-
-        for tx in &removed {
-            listener.discarded(tx.hash());
-        }
+        self.event_listener.write().removed_many(&removed);
 
         removed
     }
@@ -852,11 +882,51 @@ where
         let sender_id = self.get_sender_id(sender);
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
-        self.event_listener.write().discarded_many(&removed);
+        self.event_listener.write().removed_many(&removed);
 
         removed
     }
 
+    /// Removes and returns all matching local transactions that exceeded their lifetime.
+    ///
+    /// Listeners are notified with a [`DropReason::Timeout`].
+    pub fn remove_timed_out_transactions(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        if hashes.is_empty() {
+            return Vec::new()
+        }
+        let removed = self.pool.write().remove_transactions(hashes);
+
+        self.event_listener.write().timed_out_many(&removed);
+
+        removed
+    }
+
+    /// Notifies the listeners of the pool event bus about the given pending local transactions,
+    /// each paired with the number of blocks it has been pending for.
+    ///
+    /// Each [`PoolEvent::Rebroadcast`](crate::PoolEvent::Rebroadcast) carries a fee bump
+    /// suggestion according to the configured [`PriceBumpConfig`](crate::PriceBumpConfig). The
+    /// network propagates these transactions again to all peers.
+    pub fn rebroadcast_transactions(
+        &self,
+        transactions: Vec<(Arc<ValidPoolTransaction<T::Transaction>>, u64)>,
+    ) {
+        let mut listener = self.event_listener.write();
+        if listener.is_empty() {
+            return
+        }
+        for (tx, pending_blocks) in transactions {
+            if !tx.origin.is_local() {
+                continue
+            }
+            let suggested_fees = self.config.price_bumps.bumped_fees(&tx.replacement_fees());
+            listener.rebroadcast(tx.hash(), pending_blocks, suggested_fees);
+        }
+    }
+
     /// Removes and returns all transactions that are present in the pool.
     pub fn retain_unknown<A>(&self, announcement: &mut A)
     where
@@ -1009,6 +1079,29 @@ where
         self.pool.read().is_exceeded()
     }
 
+    /// Returns the current size limits of the sub-pools.
+    pub fn subpool_limits(&self) -> SubPoolLimits {
+        self.pool.read().subpool_limits()
+    }
+
+    /// Updates the size limits of the sub-pools and returns the previous limits.
+    ///
+    /// Transactions that exceed the new limits are discarded.
+    pub fn set_subpool_limits(&self, limits: SubPoolLimits) -> SubPoolLimits {
+        let (previous, discarded) = {
+            let mut pool = self.pool.write();
+            let previous = pool.subpool_limits();
+            (previous, pool.set_subpool_limits(limits))
+        };
+
+        if !discarded.is_empty() {
+            self.delete_discarded_blobs(discarded.iter());
+            self.event_listener.write().discarded_many(&discarded);
+        }
+
+        previous
+    }
+
     /// Inserts a blob transaction into the blob store
     fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecarVariant) {
         debug!(target: "txpool", "[{:?}] storing blob sidecar", hash);
@@ -1036,6 +1129,32 @@ where
         self.update_blob_store_metrics();
     }
 
+    /// Converts the stored blob sidecars of all blob transactions in the pool into the format
+    /// required by the active fork, see [`TransactionValidator::convert_blob_sidecar`].
+    pub fn convert_blob_sidecars(&self) {
+        let blob_txs = self
+            .get_pool_data()
+            .all()
+            .transactions_iter()
+            .filter(|tx| tx.is_eip4844())
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+
+        let mut converted = 0;
+        for tx_hash in blob_txs {
+            let Ok(Some(sidecar)) = self.blob_store.get(tx_hash) else { continue };
+            let Some(sidecar) = self.validator.convert_blob_sidecar(&sidecar) else { continue };
+            match self.blob_store.replace(tx_hash, sidecar) {
+                Ok(()) => converted += 1,
+                Err(err) => {
+                    debug!(target: "txpool", %err, %tx_hash, "failed to replace converted blob sidecar")
+                }
+            }
+        }
+        debug!(target: "txpool", %converted, "converted blob sidecars");
+        self.update_blob_store_metrics();
+    }
+
     fn update_blob_store_metrics(&self) {
         if let Some(data_size) = self.blob_store.data_size_hint() {
             self.blob_store_metrics.blobstore_byte_size.set(data_size as f64);
@@ -1057,6 +1176,24 @@ where
     }
 }
 
+impl<V, T, S> PendingStateProvider for PoolInner<V, T, S>
+where
+    V: TransactionValidator,
+    T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
+{
+    fn pending_balance_changes(&self, address: Address, nonce: u64) -> PendingBalanceChanges {
+        let mut changes = PendingBalanceChanges::default();
+        for tx in self.get_transactions_by_sender(address) {
+            if tx.nonce() < nonce {
+                changes.outgoing = changes.outgoing.saturating_add(*tx.cost());
+            }
+        }
+        changes.incoming = self.get_pool_data().pending_incoming_value(&address);
+        changes
+    }
+}
+
 impl<V, T: TransactionOrdering, S> fmt::Debug for PoolInner<V, T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolInner").field("config", &self.config).finish_non_exhaustive()
@@ -1193,14 +1330,19 @@ impl<T: PoolTransaction> AddedTransaction<T> {
         self.replaced().filter(|tx| tx.transaction.is_eip4844()).map(|tx| *tx.transaction.hash())
     }
 
-    /// Returns the hash of the transaction
-    pub(crate) fn hash(&self) -> &TxHash {
+    /// Returns the added transaction
+    pub(crate) const fn transaction(&self) -> &Arc<ValidPoolTransaction<T>> {
         match self {
-            Self::Pending(tx) => tx.transaction.hash(),
-            Self::Parked { transaction, .. } => transaction.hash(),
+            Self::Pending(tx) => &tx.transaction,
+            Self::Parked { transaction, .. } => transaction,
         }
     }
 
+    /// Returns the hash of the transaction
+    pub(crate) fn hash(&self) -> &TxHash {
+        self.transaction().hash()
+    }
+
     /// Converts this type into the event type for listeners
     pub(crate) fn into_new_transaction_event(self) -> NewTransactionEvent<T> {
         match self {
@@ -1,6 +1,7 @@
 use crate::{
     identifier::{SenderId, TransactionId},
-    pool::size::SizeTracker,
+    pool::{size::SizeTracker, stats::FeeStatsTracker},
+    traits::SubPoolStats,
     PoolTransaction, SubPoolLimit, ValidPoolTransaction, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
 };
 use rustc_hash::FxHashMap;
@@ -38,6 +39,8 @@ pub struct ParkedPool<T: ParkedOrd> {
     ///
     /// See also [`reth_primitives_traits::InMemorySize::size`].
     size_of: SizeTracker,
+    /// Keeps track of the fees, gas and blobs of the transactions in this pool.
+    stats: FeeStatsTracker,
 }
 
 // === impl ParkedPool ===
@@ -59,6 +62,7 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.stats.add(&tx);
 
         // update or create sender entry
         self.add_sender_count(tx.sender_id(), submission_id);
@@ -138,6 +142,7 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of -= tx.transaction.size();
+        self.stats.remove(&tx.transaction);
 
         Some(tx.transaction.into())
     }
@@ -213,6 +218,11 @@ impl<T: ParkedOrd> ParkedPool<T> {
         id
     }
 
+    /// Returns the fee and gas stats of this pool with the fees at the given percentiles.
+    pub(crate) fn stats(&self, percentiles: &[f64]) -> SubPoolStats {
+        self.stats.stats(percentiles)
+    }
+
     /// The reported size of all transactions in this pool.
     pub(crate) fn size(&self) -> usize {
         self.size_of.into()
@@ -319,6 +329,7 @@ impl<T: ParkedOrd> Default for ParkedPool<T> {
             last_sender_submission: Default::default(),
             sender_transaction_count: Default::default(),
             size_of: Default::default(),
+            stats: Default::default(),
         }
     }
 }
@@ -3,9 +3,12 @@ use crate::{
     pool::{
         best::{BestTransactions, BestTransactionsWithFees},
         size::SizeTracker,
+        stats::FeeStatsTracker,
     },
+    traits::SubPoolStats,
     Priority, SubPoolLimit, TransactionOrdering, ValidPoolTransaction,
 };
+use alloy_primitives::{Address, U256};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{
     cmp::Ordering,
@@ -45,6 +48,11 @@ pub struct PendingPool<T: TransactionOrdering> {
     ///
     /// See also [`reth_primitives_traits::InMemorySize::size`].
     size_of: SizeTracker,
+    /// Keeps track of the fees, gas and blobs of the transactions in this pool.
+    stats: FeeStatsTracker,
+    /// The value transferred to each address by the transactions in this pool, excluding
+    /// transfers of a sender to itself.
+    incoming_value: FxHashMap<Address, U256>,
     /// Used to broadcast new transactions that have been added to the `PendingPool` to existing
     /// `static_files` of this pool.
     new_transaction_notifier: broadcast::Sender<PendingTransaction<T>>,
@@ -68,6 +76,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
             independent_transactions: Default::default(),
             highest_nonces: Default::default(),
             size_of: Default::default(),
+            stats: Default::default(),
+            incoming_value: Default::default(),
             new_transaction_notifier,
         }
     }
@@ -82,6 +92,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.independent_transactions.clear();
         self.highest_nonces.clear();
         self.size_of.reset();
+        self.stats.reset();
+        self.incoming_value.clear();
         std::mem::take(&mut self.by_id)
     }
 
@@ -197,6 +209,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 }
             } else {
                 self.size_of += tx.transaction.size();
+                self.stats.add(&tx.transaction);
+                self.add_incoming_value(&tx.transaction);
                 self.update_independents_and_highest_nonces(&tx);
                 self.by_id.insert(id, tx);
             }
@@ -242,6 +256,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
 
                 self.size_of += tx.transaction.size();
+                self.stats.add(&tx.transaction);
+                self.add_incoming_value(&tx.transaction);
                 self.update_independents_and_highest_nonces(&tx);
                 self.by_id.insert(id, tx);
             }
@@ -275,6 +291,34 @@ impl<T: TransactionOrdering> PendingPool<T> {
         }
     }
 
+    /// Adds the value the transaction transfers to the incoming value of its recipient.
+    fn add_incoming_value(&mut self, tx: &ValidPoolTransaction<T::Transaction>) {
+        if let Some(to) = tx.to().filter(|to| to != tx.sender_ref()) {
+            let incoming = self.incoming_value.entry(to).or_default();
+            *incoming = incoming.saturating_add(tx.transaction.value());
+        }
+    }
+
+    /// Removes the value the transaction transfers from the incoming value of its recipient.
+    fn remove_incoming_value(&mut self, tx: &ValidPoolTransaction<T::Transaction>) {
+        if let Some(to) = tx.to().filter(|to| to != tx.sender_ref()) {
+            if let Entry::Occupied(mut entry) = self.incoming_value.entry(to) {
+                let incoming = entry.get().saturating_sub(tx.transaction.value());
+                if incoming.is_zero() {
+                    entry.remove();
+                } else {
+                    entry.insert(incoming);
+                }
+            }
+        }
+    }
+
+    /// Returns the value transferred to the address by transactions of other senders in this
+    /// pool.
+    pub(crate) fn incoming_value(&self, address: &Address) -> U256 {
+        self.incoming_value.get(address).copied().unwrap_or_default()
+    }
+
     /// Returns the ancestor the given transaction, the transaction with `nonce - 1`.
     ///
     /// Note: for a transaction with nonce higher than the current on chain nonce this will always
@@ -301,6 +345,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.stats.add(&tx);
+        self.add_incoming_value(&tx);
 
         let tx_id = *tx.id();
 
@@ -338,6 +384,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
         let tx = self.by_id.remove(id)?;
         self.size_of -= tx.transaction.size();
+        self.stats.remove(&tx.transaction);
+        self.remove_incoming_value(&tx.transaction);
 
         if let Some(highest) = self.highest_nonces.get(&id.sender) {
             if highest.transaction.nonce() == id.nonce {
@@ -500,6 +548,11 @@ impl<T: TransactionOrdering> PendingPool<T> {
         limit.is_exceeded(self.len(), self.size())
     }
 
+    /// Returns the fee and gas stats of this pool with the fees at the given percentiles.
+    pub(crate) fn stats(&self, percentiles: &[f64]) -> SubPoolStats {
+        self.stats.stats(percentiles)
+    }
+
     /// The reported size of all transactions in this pool.
     pub(crate) fn size(&self) -> usize {
         self.size_of.into()
@@ -925,6 +978,32 @@ mod tests {
         assert_eq!(pool.len(), 0);
     }
 
+    #[test]
+    fn tracks_incoming_value() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+
+        let tx = MockTransaction::eip1559().with_value(U256::from(10));
+        let recipient = tx.to().unwrap();
+        let next = tx.next().with_value(U256::from(5));
+        let tx = f.validated_arc(tx);
+        let next = f.validated_arc(next);
+        pool.add_transaction(tx.clone(), 0);
+        pool.add_transaction(next.clone(), 0);
+        assert_eq!(pool.incoming_value(&recipient), U256::from(15));
+        assert_eq!(pool.incoming_value(tx.sender_ref()), U256::ZERO);
+
+        // re-inserted transactions are counted once
+        assert!(pool.update_base_fee(0).is_empty());
+        assert_eq!(pool.incoming_value(&recipient), U256::from(15));
+
+        pool.remove_transaction(tx.id());
+        assert_eq!(pool.incoming_value(&recipient), U256::from(5));
+        pool.remove_transaction(next.id());
+        assert_eq!(pool.incoming_value(&recipient), U256::ZERO);
+        assert!(pool.incoming_value.is_empty());
+    }
+
     #[test]
     fn test_reorder_on_basefee_update() {
         let mut f = MockTransactionFactory::default();
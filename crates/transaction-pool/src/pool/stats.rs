@@ -0,0 +1,154 @@
+//! Tracks fee and gas statistics of a sub-pool.
+
+use crate::{traits::SubPoolStats, PoolTransaction, ValidPoolTransaction};
+use std::collections::BTreeMap;
+
+/// Keeps track of the fee distribution, gas and blobs of the transactions in a sub-pool.
+///
+/// This is updated incrementally whenever a transaction enters or leaves the sub-pool, so that
+/// stats can be queried without iterating over all transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FeeStatsTracker {
+    /// Number of transactions per max fee per gas.
+    max_fees: BTreeMap<u128, usize>,
+    /// Number of transactions per priority fee, or gas price for legacy transactions.
+    priority_fees: BTreeMap<u128, usize>,
+    /// Number of tracked transactions.
+    transactions: usize,
+    /// Accumulated gas limit of all transactions.
+    gas: u64,
+    /// Number of tracked blob transactions.
+    blob_transactions: usize,
+    /// Accumulated number of blobs of all blob transactions.
+    blobs: u64,
+}
+
+impl FeeStatsTracker {
+    /// Adds the transaction to the stats.
+    pub(crate) fn add<T: PoolTransaction>(&mut self, tx: &ValidPoolTransaction<T>) {
+        *self.max_fees.entry(tx.max_fee_per_gas()).or_default() += 1;
+        *self.priority_fees.entry(tx.priority_fee_or_price()).or_default() += 1;
+        self.transactions += 1;
+        self.gas = self.gas.saturating_add(tx.gas_limit());
+        if let Some(blobs) = tx.transaction.blob_count() {
+            self.blob_transactions += 1;
+            self.blobs += blobs;
+        }
+    }
+
+    /// Removes the transaction from the stats.
+    ///
+    /// Note: this expects that the transaction was previously added.
+    pub(crate) fn remove<T: PoolTransaction>(&mut self, tx: &ValidPoolTransaction<T>) {
+        decrement(&mut self.max_fees, tx.max_fee_per_gas());
+        decrement(&mut self.priority_fees, tx.priority_fee_or_price());
+        self.transactions = self.transactions.saturating_sub(1);
+        self.gas = self.gas.saturating_sub(tx.gas_limit());
+        if let Some(blobs) = tx.transaction.blob_count() {
+            self.blob_transactions = self.blob_transactions.saturating_sub(1);
+            self.blobs = self.blobs.saturating_sub(blobs);
+        }
+    }
+
+    /// Reset the stats.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the stats with the fees at the given percentiles.
+    ///
+    /// Percentiles are expected to be in `0.0..=100.0`.
+    pub(crate) fn stats(&self, percentiles: &[f64]) -> SubPoolStats {
+        SubPoolStats {
+            transactions: self.transactions as u64,
+            gas: self.gas,
+            blob_transactions: self.blob_transactions as u64,
+            blobs: self.blobs,
+            max_fee_percentiles: fee_percentiles(&self.max_fees, self.transactions, percentiles),
+            priority_fee_percentiles: fee_percentiles(
+                &self.priority_fees,
+                self.transactions,
+                percentiles,
+            ),
+        }
+    }
+}
+
+fn decrement(fees: &mut BTreeMap<u128, usize>, fee: u128) {
+    if let Some(count) = fees.get_mut(&fee) {
+        *count -= 1;
+        if *count == 0 {
+            fees.remove(&fee);
+        }
+    }
+}
+
+/// Returns the fee at each percentile of the given fee histogram, using the nearest-rank method.
+///
+/// Returns `0` for all percentiles if the histogram is empty.
+fn fee_percentiles(fees: &BTreeMap<u128, usize>, total: usize, percentiles: &[f64]) -> Vec<u128> {
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * total as f64).ceil() as usize;
+            let mut seen = 0;
+            fees.iter()
+                .find_map(|(fee, count)| {
+                    seen += count;
+                    (seen >= rank.max(1)).then_some(*fee)
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn track_fee_percentiles() {
+        let mut f = MockTransactionFactory::default();
+        let mut tracker = FeeStatsTracker::default();
+
+        let txs = (1..=10u128)
+            .map(|fee| {
+                f.validated(
+                    MockTransaction::eip1559()
+                        .with_priority_fee(fee)
+                        .with_max_fee(fee * 10)
+                        .with_gas_limit(1_000),
+                )
+            })
+            .collect::<Vec<_>>();
+        for tx in &txs {
+            tracker.add(tx);
+        }
+
+        let stats = tracker.stats(&[0.0, 25.0, 50.0, 100.0]);
+        assert_eq!(stats.transactions, 10);
+        assert_eq!(stats.gas, 10_000);
+        assert_eq!(stats.priority_fee_percentiles, vec![1, 3, 5, 10]);
+        assert_eq!(stats.max_fee_percentiles, vec![10, 30, 50, 100]);
+
+        for tx in &txs[..5] {
+            tracker.remove(tx);
+        }
+        let stats = tracker.stats(&[0.0, 50.0]);
+        assert_eq!(stats.transactions, 5);
+        assert_eq!(stats.gas, 5_000);
+        assert_eq!(stats.priority_fee_percentiles, vec![6, 8]);
+
+        let blob = f.validated(MockTransaction::eip4844());
+        tracker.add(&blob);
+        assert_eq!(tracker.stats(&[]).blob_transactions, 1);
+        tracker.remove(&blob);
+
+        for tx in &txs[5..] {
+            tracker.remove(tx);
+        }
+        assert_eq!(tracker, FeeStatsTracker::default());
+        assert_eq!(tracker.stats(&[50.0]).priority_fee_percentiles, vec![0]);
+    }
+}
@@ -1,7 +1,7 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{LocalTransactionConfig, SubPoolLimits, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
         PoolError, PoolErrorKind,
@@ -17,9 +17,9 @@ use crate::{
         update::{Destination, PoolUpdate, UpdateOutcome},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    traits::{BestTransactionsAttributes, BlockInfo, PoolSize, PoolStats},
+    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, ReplacementPolicy,
+    TransactionOrdering, ValidPoolTransaction, U256,
 };
 use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, KECCAK_EMPTY,
@@ -209,6 +209,16 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Returns the fee and gas stats of all sub-pools with the fees at the given percentiles.
+    pub fn stats(&self, percentiles: &[f64]) -> PoolStats {
+        PoolStats {
+            pending: self.pending_pool.stats(percentiles),
+            basefee: self.basefee_pool.stats(percentiles),
+            blob: self.blob_pool.stats(percentiles),
+            queued: self.queued_pool.stats(percentiles),
+        }
+    }
+
     /// Returns the currently tracked block values
     pub const fn block_info(&self) -> BlockInfo {
         BlockInfo {
@@ -434,6 +444,12 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.all()
     }
 
+    /// Returns the value transferred to the address by transactions of other senders in the
+    /// pending sub-pool.
+    pub(crate) fn pending_incoming_value(&self, address: &Address) -> U256 {
+        self.pending_pool.incoming_value(address)
+    }
+
     /// Returns the number of transactions from the pending sub-pool
     pub(crate) fn pending_transactions_count(&self) -> usize {
         self.pending_pool.len()
@@ -510,6 +526,23 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.config.is_exceeded(self.size())
     }
 
+    /// Returns the size limits of the sub-pools.
+    pub(crate) const fn subpool_limits(&self) -> SubPoolLimits {
+        self.config.subpool_limits()
+    }
+
+    /// Updates the size limits of the sub-pools.
+    ///
+    /// This returns all transactions that were removed from the pool because they exceed the new
+    /// limits, see [`TxPool::discard_worst`].
+    pub(crate) fn set_subpool_limits(
+        &mut self,
+        limits: SubPoolLimits,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.config.set_subpool_limits(limits);
+        self.discard_worst()
+    }
+
     /// Returns the transaction for the given hash.
     pub(crate) fn get(
         &self,
@@ -578,7 +611,10 @@ impl<T: TransactionOrdering> TxPool<T> {
         // Update removed transactions metric
         self.metrics.removed_transactions.increment(removed_txs_count);
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, mut discarded } = self.update_accounts(changed_senders);
+
+        // Evict nonce gapped transactions that have been queued for too long
+        discarded.extend(self.evict_stale_nonce_gapped_transactions());
 
         self.update_transaction_type_metrics();
         self.metrics.performed_state_updates.increment(1);
@@ -720,6 +756,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                             PoolErrorKind::SpammerExceededCapacity(transaction.sender()),
                         ))
                     }
+                    InsertErr::ExceededMaxNonceGap { transaction, max_nonce_gap } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::ExceededMaxNonceGap(max_nonce_gap),
+                        ))
+                    }
                     InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                         transaction,
                         block_gas_limit,
@@ -896,6 +938,45 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs
     }
 
+    /// Removes and returns all non-local transactions that are queued because of a nonce gap and
+    /// exceeded their lifetime.
+    ///
+    /// The lifetime decays with the distance to the sender's on-chain nonce, see
+    /// [`PoolConfig::nonce_gap_lifetime`].
+    pub(crate) fn evict_stale_nonce_gapped_transactions(
+        &mut self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let Some(lifetime) = self.config.nonce_gap_lifetime else { return Vec::new() };
+
+        let stale = self
+            .all_transactions
+            .txs
+            .iter()
+            .filter(|(_, tx)| {
+                tx.state.has_nonce_gap() &&
+                    !self
+                        .all_transactions
+                        .local_transactions_config
+                        .is_local(tx.transaction.origin, tx.transaction.sender_ref())
+            })
+            .filter_map(|(id, tx)| {
+                let on_chain_nonce = self.sender_info.get(&id.sender)?.state_nonce;
+                let nonce_gap = id.nonce.saturating_sub(on_chain_nonce).max(1);
+                (tx.transaction.timestamp.elapsed() > lifetime.div_f64(nonce_gap as f64))
+                    .then_some(*id)
+            })
+            .collect::<Vec<_>>();
+
+        if stale.is_empty() {
+            return Vec::new()
+        }
+
+        let removed = stale.iter().filter_map(|id| self.remove_transaction(id)).collect::<Vec<_>>();
+        self.metrics.evicted_nonce_gapped_transactions.increment(removed.len() as u64);
+        self.update_size_metrics();
+        removed
+    }
+
     /// Removes and returns all matching transactions and their descendants from the pool.
     pub(crate) fn remove_transactions_and_descendants(
         &mut self,
@@ -1223,6 +1304,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Max number of nonces a non-local transaction may be ahead of the sender's on-chain nonce.
+    max_nonce_gap: Option<u64>,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
@@ -1235,8 +1318,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     last_seen_block_hash: B256,
     /// Expected blob and base fee for the pending block.
     pending_fees: PendingFees,
-    /// Configured price bump settings for replacements
-    price_bumps: PriceBumpConfig,
+    /// Policy for the replacement of transactions with the same sender and nonce
+    replacement_policy: Arc<dyn ReplacementPolicy>,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// All accounts with a pooled authorization
@@ -1250,7 +1333,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
-            price_bumps: config.price_bumps,
+            max_nonce_gap: config.max_nonce_gap,
+            replacement_policy: config.replacement_policy(),
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
             block_gas_limit: config.gas_limit,
@@ -1701,6 +1785,16 @@ impl<T: PoolTransaction> AllTransactions<T> {
                     transaction: Arc::new(transaction),
                 })
             }
+
+            // Reject transactions that are too far ahead of the sender's on-chain nonce
+            if let Some(max_nonce_gap) = self.max_nonce_gap {
+                if transaction.nonce() - on_chain_nonce > max_nonce_gap {
+                    return Err(InsertErr::ExceededMaxNonceGap {
+                        transaction: Arc::new(transaction),
+                        max_nonce_gap,
+                    })
+                }
+            }
         }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
@@ -1892,7 +1986,9 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let maybe_replacement = transaction.as_ref();
 
                 // Ensure the new transaction is not underpriced
-                if existing_transaction.is_underpriced(maybe_replacement, &self.price_bumps) {
+                if existing_transaction
+                    .is_underpriced(maybe_replacement, self.replacement_policy.as_ref())
+                {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
@@ -2027,6 +2123,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_nonce_gap: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
             by_hash: Default::default(),
@@ -2035,7 +2132,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
-            price_bumps: Default::default(),
+            replacement_policy: Arc::new(PriceBumpConfig::default()),
             local_transactions_config: Default::default(),
             auths: Default::default(),
             metrics: Default::default(),
@@ -2083,6 +2180,8 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     ///
     /// The sender can be considered a spammer at this point.
     ExceededSenderTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
+    /// The transaction's nonce is too far ahead of the sender's on-chain nonce.
+    ExceededMaxNonceGap { transaction: Arc<ValidPoolTransaction<T>>, max_nonce_gap: u64 },
     /// Transaction gas limit exceeds block's gas limit
     TxGasLimitMoreThanAvailableBlockGas {
         transaction: Arc<ValidPoolTransaction<T>>,
@@ -2159,10 +2258,11 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        ReplacementFees, SubPoolLimit,
     };
     use alloy_consensus::{Transaction, TxType};
     use alloy_primitives::address;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_insert_blob() {
@@ -2705,6 +2805,50 @@ mod tests {
         assert_eq!(pool.len(), 1);
     }
 
+    #[test]
+    fn insert_replace_custom_replacement_policy() {
+        /// Only accepts replacements that double the max fee.
+        #[derive(Debug)]
+        struct DoubleFee;
+
+        impl ReplacementPolicy for DoubleFee {
+            fn is_underpriced(
+                &self,
+                existing: &ReplacementFees,
+                replacement: &ReplacementFees,
+            ) -> bool {
+                replacement.max_fee_per_gas < existing.max_fee_per_gas * 2
+            }
+        }
+
+        let on_chain_balance = U256::ZERO;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::new(&PoolConfig {
+            replacement_policy: Some(Arc::new(DoubleFee)),
+            ..Default::default()
+        });
+        let mut tx = MockTransaction::eip1559().inc_limit();
+        tx.set_priority_fee(100);
+        tx.set_max_fee(100);
+        let first = f.validated(tx.clone());
+        pool.insert_tx(first.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        // a price bump of 50% is enough for the default policy, but not for the custom one
+        let mut replacement = f.validated(tx.rng_hash());
+        replacement.transaction.set_priority_fee(150);
+        replacement.transaction.set_max_fee(150);
+        let err =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::Underpriced { .. }));
+        assert!(pool.contains(first.hash()));
+
+        replacement.transaction.set_max_fee(200);
+        pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(pool.contains(replacement.hash()));
+        assert!(!pool.contains(first.hash()));
+    }
+
     #[test]
     fn insert_conflicting_type_normal_to_blob() {
         let on_chain_balance = U256::from(10_000);
@@ -2860,6 +3004,21 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn rejects_exceeded_nonce_gap() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions { max_nonce_gap: Some(2), ..Default::default() };
+
+        let tx = MockTransaction::eip1559();
+        let err =
+            pool.insert_tx(f.validated(tx.skip(2)), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededMaxNonceGap { max_nonce_gap: 2, .. }));
+
+        assert!(pool.insert_tx(f.validated(tx.skip(1)), on_chain_balance, on_chain_nonce).is_ok());
+    }
+
     #[test]
     fn allow_local_spamming() {
         let on_chain_balance = U256::from(1_000);
@@ -3062,6 +3221,52 @@ mod tests {
         assert_eq!(pool.pending_pool.len(), 1);
     }
 
+    #[test]
+    fn evict_stale_nonce_gapped_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            nonce_gap_lifetime: Some(Duration::from_secs(3_000)),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        // both transactions are queued for 1_000s, the one further ahead decays faster
+        let tx = MockTransaction::eip1559();
+        let added_at = Instant::now() - Duration::from_secs(1_000);
+        let near = f.validated(tx.skip(1)).with_timestamp(added_at);
+        let far = f.validated(tx.skip(3)).with_timestamp(added_at);
+        let far_hash = *far.hash();
+
+        pool.add_transaction(near, U256::from(1_000), 0, None).unwrap();
+        pool.add_transaction(far, U256::from(1_000), 0, None).unwrap();
+        assert_eq!(pool.queued_pool.len(), 2);
+
+        let evicted = pool.evict_stale_nonce_gapped_transactions();
+        assert_eq!(evicted.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(), vec![far_hash]);
+        assert_eq!(pool.queued_pool.len(), 1);
+    }
+
+    #[test]
+    fn reject_tx_from_reserved_authority() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let authority = Address::random();
+        let tx = f.validated(MockTransaction::eip1559().with_sender(authority));
+        pool.add_transaction(tx, U256::from(1_000), 0, None).unwrap();
+
+        // the authority already has a transaction in the pool
+        let delegation = f.validated_with_authorities(MockTransaction::eip7702(), [authority]);
+        assert_eq!(delegation.authority_ids, Some(f.authority_ids([authority])));
+        let err = pool.add_transaction(delegation, U256::from(1_000), 0, None).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                Eip7702PoolTransactionError::AuthorityReserved
+            ))
+        ));
+    }
+
     #[test]
     fn discard_with_large_blob_txs() {
         // init tracing
@@ -3172,6 +3377,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_subpool_limits_discards_queued_only() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx = MockTransaction::eip1559().inc_price_by(10);
+        pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
+
+        // insert a bunch of transactions into the queued pool
+        for _ in 0..10 {
+            let tx = MockTransaction::eip1559().inc_price_by(10).inc_nonce();
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
+        }
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 10);
+
+        let mut limits = pool.subpool_limits();
+        limits.queued = SubPoolLimit::new(5, usize::MAX);
+        let removed = pool.set_subpool_limits(limits);
+
+        assert_eq!(removed.len(), 5);
+        assert_eq!(pool.subpool_limits(), limits);
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 5);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
@@ -1,7 +1,7 @@
 //! Mock types.
 
 use crate::{
-    identifier::{SenderIdentifiers, TransactionId},
+    identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::txpool::TxPool,
     traits::TransactionOrigin,
     CoinbaseTipOrdering, EthBlobTransactionSidecar, EthPoolTransaction, PoolTransaction,
@@ -1318,6 +1318,22 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
     type Strategy = proptest::strategy::BoxedStrategy<Self>;
 }
 
+// === impl MockValidTx ===
+
+impl MockValidTx {
+    /// Sets the timestamp at which the transaction was added to the pool.
+    pub const fn with_timestamp(mut self, timestamp: Instant) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets whether the transaction is allowed to be propagated.
+    pub const fn with_propagate(mut self, propagate: bool) -> Self {
+        self.propagate = propagate;
+        self
+    }
+}
+
 /// A factory for creating and managing various types of mock transactions.
 #[derive(Debug, Default)]
 pub struct MockTransactionFactory {
@@ -1359,6 +1375,27 @@ impl MockTransactionFactory {
         }
     }
 
+    /// Converts the EIP-7702 transaction into a validated transaction with the given recovered
+    /// authorities.
+    pub fn validated_with_authorities(
+        &mut self,
+        transaction: MockTransaction,
+        authorities: impl IntoIterator<Item = Address>,
+    ) -> MockValidTx {
+        let authority_ids = self.authority_ids(authorities);
+        let mut tx = self.validated(transaction);
+        tx.authority_ids = Some(authority_ids);
+        tx
+    }
+
+    /// Returns the sender ids of the given 7702 authorities, creating new ids if necessary.
+    pub fn authority_ids(
+        &mut self,
+        authorities: impl IntoIterator<Item = Address>,
+    ) -> Vec<SenderId> {
+        self.ids.sender_ids_or_create(authorities)
+    }
+
     /// Creates a validated legacy [`MockTransaction`].
     pub fn create_legacy(&mut self) -> MockValidTx {
         self.validated(MockTransaction::legacy())
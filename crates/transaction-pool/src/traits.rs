@@ -54,11 +54,11 @@ use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
+        state::SubPool, BestTransactionFilter, NewTransactionEvent, PoolEvents, TransactionEvents,
         TransactionListenerKind,
     },
     validate::ValidPoolTransaction,
-    AddedTransactionOutcome, AllTransactionsEvents,
+    AddedTransactionOutcome, AllTransactionsEvents, SubPoolLimits,
 };
 use alloy_consensus::{error::ValueError, BlockHeader, Signed, Typed2718};
 use alloy_eips::{
@@ -115,11 +115,40 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns stats about the pool and all sub-pools.
     fn pool_size(&self) -> PoolSize;
 
+    /// Returns fee and gas stats of all sub-pools, with the fees at the given percentiles.
+    ///
+    /// The stats are tracked incrementally as transactions enter and leave the sub-pools, so this
+    /// is cheap compared to iterating over the content of the pool.
+    ///
+    /// Percentiles are expected to be in `0.0..=100.0`, e.g. `[25.0, 50.0, 75.0]`.
+    ///
+    /// By default this returns empty stats for all sub-pools.
+    fn pool_stats(&self, percentiles: &[f64]) -> PoolStats {
+        PoolStats::empty(percentiles.len())
+    }
+
     /// Returns the block the pool is currently tracking.
     ///
     /// This tracks the block that the pool has last seen.
     fn block_info(&self) -> BlockInfo;
 
+    /// Returns the size limits of the sub-pools.
+    ///
+    /// By default this returns the default limits.
+    fn subpool_limits(&self) -> SubPoolLimits {
+        SubPoolLimits::default()
+    }
+
+    /// Updates the size limits of the sub-pools and returns the previous limits.
+    ///
+    /// Transactions that exceed the new limits are discarded right away.
+    ///
+    /// By default the limits can't be adjusted and this returns the current limits.
+    fn set_subpool_limits(&self, limits: SubPoolLimits) -> SubPoolLimits {
+        let _ = limits;
+        self.subpool_limits()
+    }
+
     /// Imports an _external_ transaction.
     ///
     /// This is intended to be used by the network to insert incoming transactions received over the
@@ -231,6 +260,13 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new stream of the sequenced [`PoolEvent`](crate::PoolEvent)s of _all_ transactions
+    /// in the pool.
+    ///
+    /// Unlike [`Self::all_transactions_event_listener`], the stream reports if events were
+    /// missed because the consumer lagged behind, see [`PoolEvents`].
+    fn pool_events(&self) -> PoolEvents<Self::Transaction>;
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -418,6 +454,17 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns the combined blob gas of all blob transactions in the pool.
+    ///
+    /// This is the blob gas demand used for [`BlobFeeProjection`](crate::BlobFeeProjection)s.
+    ///
+    /// Defaults to `0`, for pools that don't hold blob transactions.
+    ///
+    /// Consumer: RPC, Block production
+    fn blob_gas_demand(&self) -> u64 {
+        0
+    }
+
     /// Returns the _hashes_ of all transactions regardless of whether they can be propagated or
     /// not.
     ///
@@ -662,6 +709,25 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Maintenance function to convert the stored blob sidecars of pooled transactions into the
+    /// format required by the active fork, e.g. EIP-4844 sidecars into EIP-7594 sidecars with cell
+    /// proofs once Osaka is active.
+    fn convert_blob_sidecars(&self) {}
+
+    /// Removes the given local transactions because they exceeded their lifetime.
+    ///
+    /// Listeners are notified with a [`DropReason::Timeout`](crate::DropReason::Timeout).
+    fn remove_timed_out_transactions(&self, hashes: Vec<TxHash>);
+
+    /// Re-broadcasts the given pending local transactions, each paired with the number of blocks
+    /// it has been pending for.
+    ///
+    /// Transactions that are no longer in the pool or are not local are skipped. Listeners of the
+    /// pool event bus receive a [`PoolEvent::Rebroadcast`](crate::PoolEvent::Rebroadcast) with the
+    /// minimum fees required to replace the transaction, the network propagates them again to all
+    /// peers in response.
+    fn rebroadcast_transactions(&self, transactions: Vec<(TxHash, u64)>);
 }
 
 /// A Helper type that bundles all transactions in the pool.
@@ -1580,6 +1646,58 @@ impl PoolSize {
     }
 }
 
+/// Fee and gas stats of all sub-pools, see [`TransactionPool::pool_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PoolStats {
+    /// Stats of the _pending_ sub-pool.
+    pub pending: SubPoolStats,
+    /// Stats of the _basefee_ sub-pool.
+    pub basefee: SubPoolStats,
+    /// Stats of the _blob_ sub-pool.
+    pub blob: SubPoolStats,
+    /// Stats of the _queued_ sub-pool.
+    pub queued: SubPoolStats,
+}
+
+impl PoolStats {
+    /// Returns the stats of an empty pool with the given number of fee percentiles.
+    pub fn empty(percentiles: usize) -> Self {
+        let empty = SubPoolStats {
+            max_fee_percentiles: vec![0; percentiles],
+            priority_fee_percentiles: vec![0; percentiles],
+            ..Default::default()
+        };
+        Self { pending: empty.clone(), basefee: empty.clone(), blob: empty.clone(), queued: empty }
+    }
+}
+
+/// Fee and gas stats of a sub-pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SubPoolStats {
+    /// Number of transactions in the sub-pool.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub transactions: u64,
+    /// Accumulated gas limit of all transactions in the sub-pool.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub gas: u64,
+    /// Number of blob transactions in the sub-pool.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub blob_transactions: u64,
+    /// Accumulated number of blobs of all blob transactions in the sub-pool.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub blobs: u64,
+    /// Max fee per gas at each requested percentile, `0` if the sub-pool is empty.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity::vec"))]
+    pub max_fee_percentiles: Vec<u128>,
+    /// Priority fee per gas, or gas price for legacy transactions, at each requested percentile,
+    /// `0` if the sub-pool is empty.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity::vec"))]
+    pub priority_fee_percentiles: Vec<u128>,
+}
+
 /// Represents the current status of the pool.
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
 pub struct BlockInfo {
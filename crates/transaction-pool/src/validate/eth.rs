@@ -8,7 +8,10 @@ use crate::{
     },
     metrics::TxPoolValidationMetrics,
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        convert_to_eip7594_sidecar, KzgVerifier, KzgVerifierConfig, PendingStateView,
+        ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -21,11 +24,11 @@ use alloy_consensus::{
 };
 use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M, eip4844::env_settings::EnvKzgSettings,
-    eip7840::BlobParams,
+    eip7594::BlobTransactionSidecarVariant, eip7840::BlobParams,
 };
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_primitives_traits::{
-    constants::MAX_TX_GAS_LIMIT_OSAKA, transaction::error::InvalidTransactionError, Block,
+    constants::MAX_TX_GAS_LIMIT_OSAKA, transaction::error::InvalidTransactionError, Account, Block,
     GotExpected, SealedBlock,
 };
 use reth_storage_api::{AccountInfoReader, StateProviderFactory};
@@ -36,9 +39,9 @@ use std::{
         atomic::{AtomicBool, AtomicU64},
         Arc,
     },
-    time::Instant,
 };
 use tokio::sync::Mutex;
+use tracing::debug;
 
 /// Validator for Ethereum transactions.
 /// It is a [`TransactionValidator`] implementation that validates ethereum transaction.
@@ -99,7 +102,12 @@ impl<Client, Tx> EthTransactionValidator<Client, Tx> {
 
     /// Returns the setup and parameters needed for validating KZG proofs.
     pub fn kzg_settings(&self) -> &EnvKzgSettings {
-        &self.inner.kzg_settings
+        self.inner.kzg_verifier.kzg_settings()
+    }
+
+    /// Returns the verifier of blob sidecars.
+    pub fn kzg_verifier(&self) -> &KzgVerifier {
+        &self.inner.kzg_verifier
     }
 
     /// Returns the config to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions..
@@ -187,6 +195,13 @@ where
     {
         self.inner.on_new_head_block(new_tip_block.header())
     }
+
+    fn convert_blob_sidecar(
+        &self,
+        sidecar: &BlobTransactionSidecarVariant,
+    ) -> Option<BlobTransactionSidecarVariant> {
+        self.inner.convert_blob_sidecar(sidecar)
+    }
 }
 
 /// A [`TransactionValidator`] implementation that validates ethereum transaction.
@@ -225,14 +240,16 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     tx_fee_cap: Option<u128>,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
-    /// Stores the setup and parameters needed for validating KZG proofs.
-    kzg_settings: EnvKzgSettings,
+    /// Verifies blob sidecars on a dedicated thread pool.
+    kzg_verifier: KzgVerifier,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
     max_tx_gas_limit: Option<u64>,
+    /// Pending view of the pool to check the balance of the sender against, if enabled.
+    pending_state: Option<PendingStateView>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
     /// Metrics for tsx pool validation
@@ -267,6 +284,20 @@ where
         transaction: Tx,
         maybe_state: &mut Option<Box<dyn AccountInfoReader>>,
     ) -> TransactionValidationOutcome<Tx> {
+        let validated = self.validate_one_without_blob(origin, transaction, maybe_state);
+        self.verify_blobs(vec![validated]).pop().expect("one outcome per transaction")
+    }
+
+    /// Performs all checks of [`validate_one_with_provider`](Self::validate_one_with_provider)
+    /// except for the verification of the blob sidecar, which is deferred to
+    /// [`verify_blobs`](Self::verify_blobs) so that the sidecars of multiple transactions can be
+    /// verified together.
+    fn validate_one_without_blob(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+        maybe_state: &mut Option<Box<dyn AccountInfoReader>>,
+    ) -> Result<StateValidatedTransaction<Tx>, TransactionValidationOutcome<Tx>> {
         match self.validate_one_no_state(origin, transaction) {
             Ok(transaction) => {
                 // stateless checks passed, pass transaction down stateful validation pipeline
@@ -277,10 +308,10 @@ where
                             *maybe_state = Some(Box::new(new_state));
                         }
                         Err(err) => {
-                            return TransactionValidationOutcome::Error(
+                            return Err(TransactionValidationOutcome::Error(
                                 *transaction.hash(),
                                 Box::new(err),
-                            )
+                            ))
                         }
                     }
                 }
@@ -289,7 +320,7 @@ where
 
                 self.validate_one_against_state(origin, transaction, state)
             }
-            Err(invalid_outcome) => invalid_outcome,
+            Err(invalid_outcome) => Err(invalid_outcome),
         }
     }
 
@@ -550,12 +581,15 @@ where
     }
 
     /// Validates a single transaction using given state provider.
+    ///
+    /// The blob sidecar of the transaction, if any, is not verified yet, see
+    /// [`verify_blobs`](Self::verify_blobs).
     fn validate_one_against_state<P>(
         &self,
         origin: TransactionOrigin,
         mut transaction: Tx,
         state: P,
-    ) -> TransactionValidationOutcome<Tx>
+    ) -> Result<StateValidatedTransaction<Tx>, TransactionValidationOutcome<Tx>>
     where
         P: AccountInfoReader,
     {
@@ -563,7 +597,7 @@ where
         let account = match state.basic_account(transaction.sender_ref()) {
             Ok(account) => account.unwrap_or_default(),
             Err(err) => {
-                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+                return Err(TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err)))
             }
         };
 
@@ -578,10 +612,10 @@ where
                 match state.bytecode_by_hash(code_hash) {
                     Ok(bytecode) => bytecode.unwrap_or_default().is_eip7702(),
                     Err(err) => {
-                        return TransactionValidationOutcome::Error(
+                        return Err(TransactionValidationOutcome::Error(
                             *transaction.hash(),
                             Box::new(err),
-                        )
+                        ))
                     }
                 }
             } else {
@@ -589,10 +623,10 @@ where
             };
 
             if !is_eip7702 {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::SignerAccountHasBytecode.into(),
-                )
+                ))
             }
         }
 
@@ -600,25 +634,33 @@ where
 
         // Checks for nonce
         if tx_nonce < account.nonce {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::NonceNotConsistent { tx: tx_nonce, state: account.nonce }
                     .into(),
-            )
+            ))
         }
 
         let cost = transaction.cost();
 
-        // Checks for max cost
-        if cost > &account.balance {
+        // Checks for max cost, against the pending view of the pool if the latest balance does
+        // not suffice and the pending view is enabled
+        if cost > &account.balance &&
+            self.pending_state
+                .as_ref()
+                .and_then(|pending| {
+                    pending.pending_balance(*transaction.sender_ref(), tx_nonce, account.balance)
+                })
+                .is_none_or(|pending_balance| cost > &pending_balance)
+        {
             let expected = *cost;
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::InsufficientFunds(
                     GotExpected { got: account.balance, expected }.into(),
                 )
                 .into(),
-            )
+            ))
         }
 
         let mut maybe_blob_sidecar = None;
@@ -629,10 +671,10 @@ where
             match transaction.take_blob() {
                 EthBlobTransactionSidecar::None => {
                     // this should not happen
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::TxTypeNotSupported.into(),
-                    )
+                    ))
                 }
                 EthBlobTransactionSidecar::Missing => {
                     // This can happen for re-injected blob transactions (on re-org), since the blob
@@ -642,37 +684,74 @@ where
                     if matches!(self.blob_store.contains(*transaction.hash()), Ok(true)) {
                         // validated transaction is already in the store
                     } else {
-                        return TransactionValidationOutcome::Invalid(
+                        return Err(TransactionValidationOutcome::Invalid(
                             transaction,
                             InvalidPoolTransactionError::Eip4844(
                                 Eip4844PoolTransactionError::MissingEip4844BlobSidecar,
                             ),
-                        )
+                        ))
                     }
                 }
                 EthBlobTransactionSidecar::Present(sidecar) => {
-                    let now = Instant::now();
-
                     if self.fork_tracker.is_osaka_activated() {
                         if sidecar.is_eip4844() {
-                            return TransactionValidationOutcome::Invalid(
+                            return Err(TransactionValidationOutcome::Invalid(
                                 transaction,
                                 InvalidPoolTransactionError::Eip4844(
                                     Eip4844PoolTransactionError::UnexpectedEip4844SidecarAfterOsaka,
                                 ),
-                            )
+                            ))
                         }
                     } else if sidecar.is_eip7594() {
-                        return TransactionValidationOutcome::Invalid(
+                        return Err(TransactionValidationOutcome::Invalid(
                             transaction,
                             InvalidPoolTransactionError::Eip4844(
                                 Eip4844PoolTransactionError::UnexpectedEip7594SidecarBeforeOsaka,
                             ),
-                        )
+                        ))
                     }
 
-                    // validate the blob
-                    if let Err(err) = transaction.validate_blob(&sidecar, self.kzg_settings.get()) {
+                    // the blob is verified later, together with the blobs of other transactions
+                    maybe_blob_sidecar = Some(sidecar);
+                }
+            }
+        }
+
+        Ok(StateValidatedTransaction { origin, transaction, account, sidecar: maybe_blob_sidecar })
+    }
+
+    /// Verifies the blob sidecars of the given transactions on the [`KzgVerifier`] and returns the
+    /// final outcome for each of them, in the same order.
+    fn verify_blobs(
+        &self,
+        validated: Vec<Result<StateValidatedTransaction<Tx>, TransactionValidationOutcome<Tx>>>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        let mut jobs = Vec::new();
+        let validated = validated
+            .into_iter()
+            .map(|validated| {
+                let mut validated = validated?;
+                match validated.sidecar.take() {
+                    Some(sidecar) => {
+                        let StateValidatedTransaction { origin, transaction, account, .. } =
+                            validated;
+                        jobs.push((transaction, sidecar));
+                        Ok(Err((origin, account)))
+                    }
+                    None => Ok(Ok(validated)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut verified = self.kzg_verifier.verify(jobs).into_iter();
+        validated
+            .into_iter()
+            .map(|validated| match validated {
+                Ok(Ok(validated)) => self.valid_outcome(validated),
+                Ok(Err((origin, account))) => {
+                    let (transaction, sidecar, result, elapsed) =
+                        verified.next().expect("one verification per blob transaction");
+                    if let Err(err) = result {
                         return TransactionValidationOutcome::Invalid(
                             transaction,
                             InvalidPoolTransactionError::Eip4844(
@@ -681,13 +760,26 @@ where
                         )
                     }
                     // Record the duration of successful blob validation as histogram
-                    self.validation_metrics.blob_validation_duration.record(now.elapsed());
-                    // store the extracted blob
-                    maybe_blob_sidecar = Some(sidecar);
+                    self.validation_metrics.blob_validation_duration.record(elapsed);
+                    self.valid_outcome(StateValidatedTransaction {
+                        origin,
+                        transaction,
+                        account,
+                        sidecar: Some(sidecar),
+                    })
                 }
-            }
-        }
+                Err(outcome) => outcome,
+            })
+            .collect()
+    }
 
+    /// Returns the [`TransactionValidationOutcome::Valid`] for a transaction that passed all
+    /// checks.
+    fn valid_outcome(
+        &self,
+        validated: StateValidatedTransaction<Tx>,
+    ) -> TransactionValidationOutcome<Tx> {
+        let StateValidatedTransaction { origin, transaction, account, sidecar } = validated;
         let authorities = transaction.authorization_list().map(|auths| {
             auths.iter().flat_map(|auth| auth.recover_authority()).collect::<Vec<_>>()
         });
@@ -696,7 +788,7 @@ where
             balance: account.balance,
             state_nonce: account.nonce,
             bytecode_hash: account.bytecode_hash,
-            transaction: ValidTransaction::new(transaction, maybe_blob_sidecar),
+            transaction: ValidTransaction::new(transaction, sidecar),
             // by this point assume all external transactions should be propagated
             propagate: match origin {
                 TransactionOrigin::External => true,
@@ -715,10 +807,11 @@ where
         transactions: Vec<(TransactionOrigin, Tx)>,
     ) -> Vec<TransactionValidationOutcome<Tx>> {
         let mut provider = None;
-        transactions
+        let validated = transactions
             .into_iter()
-            .map(|(origin, tx)| self.validate_one_with_provider(origin, tx, &mut provider))
-            .collect()
+            .map(|(origin, tx)| self.validate_one_without_blob(origin, tx, &mut provider))
+            .collect();
+        self.verify_blobs(validated)
     }
 
     /// Validates all given transactions with origin.
@@ -728,10 +821,28 @@ where
         transactions: impl IntoIterator<Item = Tx> + Send,
     ) -> Vec<TransactionValidationOutcome<Tx>> {
         let mut provider = None;
-        transactions
+        let validated = transactions
             .into_iter()
-            .map(|tx| self.validate_one_with_provider(origin, tx, &mut provider))
-            .collect()
+            .map(|tx| self.validate_one_without_blob(origin, tx, &mut provider))
+            .collect();
+        self.verify_blobs(validated)
+    }
+
+    /// Converts an EIP-4844 sidecar into an EIP-7594 sidecar with cell proofs if Osaka is active.
+    fn convert_blob_sidecar(
+        &self,
+        sidecar: &BlobTransactionSidecarVariant,
+    ) -> Option<BlobTransactionSidecarVariant> {
+        if !self.fork_tracker.is_osaka_activated() {
+            return None
+        }
+        let sidecar = sidecar.as_eip4844()?;
+        convert_to_eip7594_sidecar(sidecar, self.kzg_verifier.kzg_settings().get())
+            .inspect_err(|err| {
+                debug!(target: "txpool", %err, "failed to convert blob sidecar to eip-7594");
+            })
+            .ok()
+            .map(BlobTransactionSidecarVariant::Eip7594)
     }
 
     fn on_new_head_block<T: BlockHeader>(&self, new_tip_block: &T) {
@@ -768,6 +879,20 @@ where
     }
 }
 
+/// A transaction that passed all validation checks except for the verification of its blob
+/// sidecar.
+#[derive(Debug)]
+struct StateValidatedTransaction<Tx> {
+    /// The origin of the transaction.
+    origin: TransactionOrigin,
+    /// The validated transaction.
+    transaction: Tx,
+    /// The sender's account.
+    account: Account,
+    /// The extracted blob sidecar that still needs to be verified, if any.
+    sidecar: Option<BlobTransactionSidecarVariant>,
+}
+
 /// A builder for [`EthTransactionValidator`] and [`TransactionValidationTaskExecutor`]
 #[derive(Debug)]
 pub struct EthTransactionValidatorBuilder<Client> {
@@ -803,12 +928,16 @@ pub struct EthTransactionValidatorBuilder<Client> {
 
     /// Stores the setup and parameters needed for validating KZG proofs.
     kzg_settings: EnvKzgSettings,
+    /// Configuration of the thread pool verifying blob sidecars.
+    kzg_verifier_config: KzgVerifierConfig,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
     max_tx_gas_limit: Option<u64>,
+    /// Pending view of the pool to check the balance of the sender against, if enabled.
+    pending_state: Option<PendingStateView>,
 }
 
 impl<Client> EthTransactionValidatorBuilder<Client> {
@@ -828,10 +957,12 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             minimum_priority_fee: None,
             additional_tasks: 1,
             kzg_settings: EnvKzgSettings::Default,
+            kzg_verifier_config: KzgVerifierConfig::default(),
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             tx_fee_cap: Some(1e18 as u128),
             max_tx_gas_limit: None,
+            pending_state: None,
             // by default all transaction types are allowed
             eip2718: true,
             eip1559: true,
@@ -947,6 +1078,15 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self
     }
 
+    /// Sets the [`KzgVerifierConfig`] of the thread pool that verifies blob sidecars.
+    pub const fn with_kzg_verifier_config(
+        mut self,
+        kzg_verifier_config: KzgVerifierConfig,
+    ) -> Self {
+        self.kzg_verifier_config = kzg_verifier_config;
+        self
+    }
+
     /// Sets a minimum priority fee that's enforced for acceptance into the pool.
     pub const fn with_minimum_priority_fee(mut self, minimum_priority_fee: Option<u128>) -> Self {
         self.minimum_priority_fee = minimum_priority_fee;
@@ -1007,6 +1147,17 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self
     }
 
+    /// Sets the pending view of the pool to check the balance of the sender against, if any.
+    ///
+    /// A transaction whose cost exceeds the balance of the sender in the latest state is still
+    /// accepted if the balance suffices once the transactions pending in the pool are applied,
+    /// e.g. because a pending transaction funds the sender. The view must be bound to the pool
+    /// with [`Pool::bind_pending_state_view`](crate::Pool::bind_pending_state_view).
+    pub fn with_pending_state_view(mut self, pending_state: Option<PendingStateView>) -> Self {
+        self.pending_state = pending_state;
+        self
+    }
+
     /// Builds a the [`EthTransactionValidator`] without spawning validator tasks.
     pub fn build<Tx, S>(self, blob_store: S) -> EthTransactionValidator<Client, Tx>
     where
@@ -1026,9 +1177,11 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             tx_fee_cap,
             minimum_priority_fee,
             kzg_settings,
+            kzg_verifier_config,
             local_transactions_config,
             max_tx_input_bytes,
             max_tx_gas_limit,
+            pending_state,
             ..
         } = self;
 
@@ -1057,10 +1210,11 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             tx_fee_cap,
             minimum_priority_fee,
             blob_store: Box::new(blob_store),
-            kzg_settings,
+            kzg_verifier: KzgVerifier::new(kzg_settings, kzg_verifier_config),
             local_transactions_config,
             max_tx_input_bytes,
             max_tx_gas_limit,
+            pending_state,
             _marker: Default::default(),
             validation_metrics: TxPoolValidationMetrics::default(),
         };
@@ -1193,8 +1347,8 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
+        blobstore::InMemoryBlobStore, error::PoolErrorKind, test_utils::MockTransaction,
+        traits::PoolTransaction, CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use alloy_consensus::Transaction;
     use alloy_eips::eip2718::Decodable2718;
@@ -1610,4 +1764,53 @@ mod tests {
         let invalid = outcome.as_invalid().unwrap();
         assert!(invalid.is_oversized());
     }
+
+    #[tokio::test]
+    async fn validate_against_pending_state() {
+        let funding = MockTransaction::eip1559()
+            .with_gas_limit(21_000)
+            .with_value(U256::from(1_000_000_000_000u64));
+        let recipient = funding.to().unwrap();
+        let chained = MockTransaction::eip1559()
+            .with_sender(recipient)
+            .with_gas_limit(21_000)
+            .with_value(U256::from(500_000_000_000u64));
+
+        let provider = MockEthProvider::default();
+        provider.add_account(funding.sender(), ExtendedAccount::new(0, U256::MAX));
+
+        // the recipient has no balance in the latest state
+        let validator = EthTransactionValidatorBuilder::new(provider.clone())
+            .build::<MockTransaction, _>(InMemoryBlobStore::default());
+        let outcome = validator.validate_one(TransactionOrigin::External, chained.clone());
+        assert!(matches!(
+            outcome.as_invalid(),
+            Some(InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::InsufficientFunds(_)
+            ))
+        ));
+
+        let pending_state_view = PendingStateView::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider)
+            .with_pending_state_view(Some(pending_state_view.clone()))
+            .build(blob_store.clone());
+        let pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        assert!(pool.bind_pending_state_view(&pending_state_view));
+
+        // without the funding transaction, the pending view does not help either
+        let err = pool.add_external_transaction(chained.clone()).await.unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::InvalidTransaction(_)));
+
+        // once the funding transaction is pending, the chained transfer is accepted
+        pool.add_external_transaction(funding).await.unwrap();
+        pool.add_external_transaction(chained.clone()).await.unwrap();
+        assert!(pool.get(chained.hash()).is_some());
+
+        // a transfer exceeding the pending balance is still rejected
+        let overdraft = chained.next().with_value(U256::from(600_000_000_000u64));
+        let err = pool.add_external_transaction(overdraft).await.unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::InvalidTransaction(_)));
+    }
 }
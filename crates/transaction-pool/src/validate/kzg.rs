@@ -0,0 +1,295 @@
+//! Verification of blob sidecars on a dedicated thread pool.
+
+use crate::EthPoolTransaction;
+use alloy_eips::{
+    eip4844::{
+        env_settings::EnvKzgSettings, BlobTransactionSidecar, BlobTransactionValidationError,
+        Bytes48,
+    },
+    eip7594::{BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant},
+};
+use c_kzg::KzgSettings;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+use std::{
+    sync::{mpsc, OnceLock},
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// Default number of threads of the KZG verification pool.
+pub const DEFAULT_KZG_VERIFIER_THREADS: usize = 2;
+
+/// Default time window within which incoming blob sidecar verifications are batched.
+pub const DEFAULT_KZG_BATCH_WINDOW: Duration = Duration::from_millis(2);
+
+/// Default maximum number of blob sidecar verifications in a single batch.
+pub const DEFAULT_KZG_MAX_BATCH_SIZE: usize = 64;
+
+/// A verification job executed on the KZG verification pool.
+type VerificationJob = Box<dyn FnOnce() + Send>;
+
+/// The outcome of verifying the blob sidecar of a transaction.
+pub type BlobVerificationOutcome<Tx> =
+    (Tx, BlobTransactionSidecarVariant, Result<(), BlobTransactionValidationError>, Duration);
+
+/// Configuration of the [`KzgVerifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgVerifierConfig {
+    /// Number of threads of the verification pool.
+    ///
+    /// If zero, blob sidecars are verified on the calling thread.
+    pub threads: usize,
+    /// Time window within which incoming verifications are collected into a single batch.
+    pub batch_window: Duration,
+    /// Maximum number of verifications in a single batch.
+    pub max_batch_size: usize,
+}
+
+impl Default for KzgVerifierConfig {
+    fn default() -> Self {
+        Self {
+            threads: DEFAULT_KZG_VERIFIER_THREADS,
+            batch_window: DEFAULT_KZG_BATCH_WINDOW,
+            max_batch_size: DEFAULT_KZG_MAX_BATCH_SIZE,
+        }
+    }
+}
+
+/// Verifies the KZG proofs of blob sidecars on a dedicated rayon pool.
+///
+/// Blob sidecar verification is CPU heavy, so running it on the validation tasks or the global
+/// rayon pool makes blob-heavy periods contend with payload validation. Instead, verifications are
+/// sent to a batching thread that collects all verifications arriving within
+/// [`KzgVerifierConfig::batch_window`] and verifies them in parallel on a pool of
+/// [`KzgVerifierConfig::threads`] threads.
+///
+/// The batching thread and the pool are spawned on first use and shut down once the verifier and
+/// all of its clones are dropped.
+#[derive(Debug, Clone)]
+pub struct KzgVerifier {
+    /// The setup and parameters needed for validating KZG proofs.
+    kzg_settings: EnvKzgSettings,
+    /// The configuration of the pool and the batching.
+    config: KzgVerifierConfig,
+    /// Sender half to the batching thread, initialized on first use.
+    to_batcher: OnceLock<Option<mpsc::Sender<VerificationJob>>>,
+}
+
+impl KzgVerifier {
+    /// Creates a new verifier with the given settings and configuration.
+    pub const fn new(kzg_settings: EnvKzgSettings, config: KzgVerifierConfig) -> Self {
+        Self { kzg_settings, config, to_batcher: OnceLock::new() }
+    }
+
+    /// Returns the setup and parameters needed for validating KZG proofs.
+    pub const fn kzg_settings(&self) -> &EnvKzgSettings {
+        &self.kzg_settings
+    }
+
+    /// Returns the configuration of the verifier.
+    pub const fn config(&self) -> &KzgVerifierConfig {
+        &self.config
+    }
+
+    /// Verifies the blob sidecars of the given transactions and returns the outcomes in the same
+    /// order.
+    ///
+    /// This blocks the calling thread until all sidecars have been verified.
+    pub fn verify<Tx>(
+        &self,
+        jobs: Vec<(Tx, BlobTransactionSidecarVariant)>,
+    ) -> Vec<BlobVerificationOutcome<Tx>>
+    where
+        Tx: EthPoolTransaction + 'static,
+    {
+        let Some(to_batcher) = self.batcher() else {
+            return jobs
+                .into_iter()
+                .map(|(tx, sidecar)| verify_blob(tx, sidecar, &self.kzg_settings))
+                .collect()
+        };
+
+        let len = jobs.len();
+        let (tx, rx) = mpsc::channel();
+        let mut inline = Vec::new();
+        for (index, (transaction, sidecar)) in jobs.into_iter().enumerate() {
+            let kzg_settings = self.kzg_settings.clone();
+            let tx = tx.clone();
+            let job = Box::new(move || {
+                let _ = tx.send((index, verify_blob(transaction, sidecar, &kzg_settings)));
+            }) as VerificationJob;
+
+            if let Err(mpsc::SendError(job)) = to_batcher.send(job) {
+                // the batching thread is gone, verify on the calling thread instead
+                inline.push(job);
+            }
+        }
+        drop(tx);
+        for job in inline {
+            job();
+        }
+
+        let mut outcomes = rx.into_iter().collect::<Vec<_>>();
+        debug_assert_eq!(outcomes.len(), len, "all verifications are sent");
+        outcomes.sort_unstable_by_key(|(index, _)| *index);
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    /// Returns the sender half to the batching thread, spawning the thread and the pool on first
+    /// use.
+    ///
+    /// Returns `None` if verification should happen on the calling thread.
+    fn batcher(&self) -> Option<&mpsc::Sender<VerificationJob>> {
+        self.to_batcher
+            .get_or_init(|| {
+                if self.config.threads == 0 {
+                    return None
+                }
+
+                let pool = match ThreadPoolBuilder::new()
+                    .num_threads(self.config.threads)
+                    .thread_name(|i| format!("kzg-verifier-{i:02}"))
+                    .build()
+                {
+                    Ok(pool) => pool,
+                    Err(err) => {
+                        warn!(target: "txpool", %err, "Failed to build KZG verification pool");
+                        return None
+                    }
+                };
+
+                let (to_batcher, jobs) = mpsc::channel();
+                let config = self.config;
+                if let Err(err) = std::thread::Builder::new()
+                    .name("kzg-batcher".to_string())
+                    .spawn(move || run_batcher(jobs, pool, config))
+                {
+                    warn!(target: "txpool", %err, "Failed to spawn KZG batching thread");
+                    return None
+                }
+
+                Some(to_batcher)
+            })
+            .as_ref()
+    }
+}
+
+impl Default for KzgVerifier {
+    fn default() -> Self {
+        Self::new(EnvKzgSettings::Default, KzgVerifierConfig::default())
+    }
+}
+
+/// Collects incoming jobs into batches and executes them on the pool until all senders are
+/// dropped.
+fn run_batcher(jobs: mpsc::Receiver<VerificationJob>, pool: ThreadPool, config: KzgVerifierConfig) {
+    while let Ok(first) = jobs.recv() {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + config.batch_window;
+        while batch.len() < config.max_batch_size {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match jobs.recv_timeout(timeout) {
+                Ok(job) => batch.push(job),
+                Err(_) => break,
+            }
+        }
+
+        debug!(target: "txpool", batch_size = batch.len(), "Verifying blob sidecars");
+        // Jobs arriving while the batch is verified are collected into the next batch.
+        pool.install(|| batch.into_par_iter().for_each(|job| job()));
+    }
+}
+
+/// Verifies the blob sidecar of the given transaction.
+fn verify_blob<Tx: EthPoolTransaction>(
+    transaction: Tx,
+    sidecar: BlobTransactionSidecarVariant,
+    kzg_settings: &EnvKzgSettings,
+) -> BlobVerificationOutcome<Tx> {
+    let now = Instant::now();
+    let result = transaction.validate_blob(&sidecar, kzg_settings.get());
+    (transaction, sidecar, result, now.elapsed())
+}
+
+/// Converts an EIP-4844 blob sidecar into an EIP-7594 sidecar by computing the cell proofs of
+/// its blobs.
+///
+/// The blobs and commitments are kept as is, so the sidecar must have been verified before.
+pub fn convert_to_eip7594_sidecar(
+    sidecar: &BlobTransactionSidecar,
+    kzg_settings: &KzgSettings,
+) -> Result<BlobTransactionSidecarEip7594, c_kzg::Error> {
+    let mut cell_proofs = Vec::with_capacity(sidecar.blobs.len() * c_kzg::CELLS_PER_EXT_BLOB);
+    for blob in &sidecar.blobs {
+        let blob = c_kzg::Blob::from_bytes(blob.as_slice())?;
+        let (_, proofs) = kzg_settings.compute_cells_and_kzg_proofs(&blob)?;
+        cell_proofs.extend(proofs.iter().map(|proof| Bytes48::from(proof.to_bytes().into_inner())));
+    }
+    Ok(BlobTransactionSidecarEip7594::new(
+        sidecar.blobs.clone(),
+        sidecar.commitments.clone(),
+        cell_proofs,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_eips::eip4844::BlobTransactionSidecar;
+
+    fn jobs() -> Vec<(MockTransaction, BlobTransactionSidecarVariant)> {
+        (0..10)
+            .map(|i| {
+                let tx =
+                    if i % 3 == 0 { MockTransaction::legacy() } else { MockTransaction::eip4844() };
+                (tx, BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::default()))
+            })
+            .collect()
+    }
+
+    fn assert_verifies_in_order(verifier: &KzgVerifier) {
+        let jobs = jobs();
+        let outcomes = verifier.verify(jobs.clone());
+        assert_eq!(outcomes.len(), jobs.len());
+        for ((tx, _), (verified_tx, _, result, _)) in jobs.into_iter().zip(outcomes) {
+            assert_eq!(tx, verified_tx);
+            assert_eq!(result.is_ok(), tx.is_eip4844());
+        }
+    }
+
+    #[test]
+    fn verify_on_pool() {
+        let verifier = KzgVerifier::default();
+        assert_verifies_in_order(&verifier);
+        // the pool is reused
+        assert_verifies_in_order(&verifier);
+    }
+
+    #[test]
+    fn verify_inline() {
+        let verifier = KzgVerifier::new(
+            EnvKzgSettings::Default,
+            KzgVerifierConfig { threads: 0, ..Default::default() },
+        );
+        assert_verifies_in_order(&verifier);
+    }
+
+    #[test]
+    fn convert_sidecar_to_eip7594() {
+        let kzg_settings = EnvKzgSettings::Default;
+        let sidecar = alloy_consensus::SidecarBuilder::<alloy_consensus::SimpleCoder>::from_slice(
+            b"blob data",
+        )
+        .build()
+        .unwrap();
+
+        let converted = convert_to_eip7594_sidecar(&sidecar, kzg_settings.get()).unwrap();
+        assert_eq!(converted.blobs, sidecar.blobs);
+        assert_eq!(converted.commitments, sidecar.commitments);
+        assert_eq!(converted.cell_proofs.len(), sidecar.blobs.len() * c_kzg::CELLS_PER_EXT_BLOB);
+
+        let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        converted.validate(&versioned_hashes, kzg_settings.get()).unwrap();
+    }
+}
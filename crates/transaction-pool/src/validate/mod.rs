@@ -4,7 +4,7 @@ use crate::{
     error::InvalidPoolTransactionError,
     identifier::{SenderId, TransactionId},
     traits::{PoolTransaction, TransactionOrigin},
-    PriceBumpConfig,
+    ReplacementFees, ReplacementPolicy,
 };
 use alloy_eips::{eip7594::BlobTransactionSidecarVariant, eip7702::SignedAuthorization};
 use alloy_primitives::{Address, TxHash, B256, U256};
@@ -14,10 +14,19 @@ use std::{fmt, fmt::Debug, future::Future, time::Instant};
 
 mod constants;
 mod eth;
+mod kzg;
+mod pending;
 mod task;
 
 pub use eth::*;
 
+pub use kzg::{
+    convert_to_eip7594_sidecar, BlobVerificationOutcome, KzgVerifier, KzgVerifierConfig,
+    DEFAULT_KZG_BATCH_WINDOW, DEFAULT_KZG_MAX_BATCH_SIZE, DEFAULT_KZG_VERIFIER_THREADS,
+};
+
+pub use pending::{PendingBalanceChanges, PendingStateProvider, PendingStateView};
+
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
 /// Validation constants.
@@ -233,6 +242,20 @@ pub trait TransactionValidator: Debug + Send + Sync {
         B: Block,
     {
     }
+
+    /// Converts the blob sidecar of a pooled transaction into the format required by the active
+    /// fork, e.g. an EIP-4844 sidecar into an EIP-7594 sidecar with cell proofs once Osaka is
+    /// active.
+    ///
+    /// This is invoked by the pool maintenance task once the fork activates, so that blob
+    /// transactions that entered the pool before the fork can still be propagated after it.
+    /// Returns `None` if the sidecar doesn't need to be converted.
+    fn convert_blob_sidecar(
+        &self,
+        _sidecar: &BlobTransactionSidecarVariant,
+    ) -> Option<BlobTransactionSidecarVariant> {
+        None
+    }
 }
 
 impl<A, B> TransactionValidator for Either<A, B>
@@ -283,6 +306,16 @@ where
             Self::Right(v) => v.on_new_head_block(new_tip_block),
         }
     }
+
+    fn convert_blob_sidecar(
+        &self,
+        sidecar: &BlobTransactionSidecarVariant,
+    ) -> Option<BlobTransactionSidecarVariant> {
+        match self {
+            Self::Left(v) => v.convert_blob_sidecar(sidecar),
+            Self::Right(v) => v.convert_blob_sidecar(sidecar),
+        }
+    }
 }
 
 /// A valid transaction in the pool.
@@ -445,55 +478,26 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.clone_into_consensus()
     }
 
+    /// Returns the fees of the transaction that are relevant for its replacement.
+    pub fn replacement_fees(&self) -> ReplacementFees {
+        ReplacementFees {
+            tx_type: self.tx_type(),
+            max_fee_per_gas: self.max_fee_per_gas(),
+            max_priority_fee_per_gas: self.transaction.max_priority_fee_per_gas(),
+            max_fee_per_blob_gas: self.transaction.max_fee_per_blob_gas(),
+        }
+    }
+
     /// Determines whether a candidate transaction (`maybe_replacement`) is underpriced compared to
-    /// an existing transaction in the pool.
-    ///
-    /// A transaction is considered underpriced if it doesn't meet the required fee bump threshold.
-    /// This applies to both standard gas fees and, for blob-carrying transactions (EIP-4844),
-    /// the blob-specific fees.
+    /// an existing transaction in the pool according to the given [`ReplacementPolicy`].
     #[inline]
     pub(crate) fn is_underpriced(
         &self,
         maybe_replacement: &Self,
-        price_bumps: &PriceBumpConfig,
+        replacement_policy: &dyn ReplacementPolicy,
     ) -> bool {
-        // Retrieve the required price bump percentage for this type of transaction.
-        //
-        // The bump is different for EIP-4844 and other transactions. See `PriceBumpConfig`.
-        let price_bump = price_bumps.price_bump(self.tx_type());
-
-        // Check if the max fee per gas is underpriced.
-        if maybe_replacement.max_fee_per_gas() < self.max_fee_per_gas() * (100 + price_bump) / 100 {
-            return true
-        }
-
-        let existing_max_priority_fee_per_gas =
-            self.transaction.max_priority_fee_per_gas().unwrap_or_default();
-        let replacement_max_priority_fee_per_gas =
-            maybe_replacement.transaction.max_priority_fee_per_gas().unwrap_or_default();
-
-        // Check max priority fee per gas (relevant for EIP-1559 transactions only)
-        if existing_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas <
-                existing_max_priority_fee_per_gas * (100 + price_bump) / 100
-        {
-            return true
-        }
-
-        // Check max blob fee per gas
-        if let Some(existing_max_blob_fee_per_gas) = self.transaction.max_fee_per_blob_gas() {
-            // This enforces that blob txs can only be replaced by blob txs
-            let replacement_max_blob_fee_per_gas =
-                maybe_replacement.transaction.max_fee_per_blob_gas().unwrap_or_default();
-            if replacement_max_blob_fee_per_gas <
-                existing_max_blob_fee_per_gas * (100 + price_bump) / 100
-            {
-                return true
-            }
-        }
-
-        false
+        replacement_policy
+            .is_underpriced(&self.replacement_fees(), &maybe_replacement.replacement_fees())
     }
 }
 
@@ -0,0 +1,100 @@
+//! Pending view of the pool for validating transactions against the state after the transactions
+//! that are pending in the pool.
+
+use alloy_primitives::{Address, U256};
+use std::{
+    fmt,
+    sync::{Arc, OnceLock, Weak},
+};
+
+/// The changes the transactions in the pool apply to the balance of an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingBalanceChanges {
+    /// Value transferred to the account by transactions of other senders in the pending sub-pool.
+    pub incoming: U256,
+    /// Cost of the transactions of the account itself that are in the pool.
+    pub outgoing: U256,
+}
+
+/// Provides the pending view of a transaction pool.
+pub trait PendingStateProvider: Send + Sync {
+    /// Returns the changes of the pending transactions to the balance of the account.
+    ///
+    /// Only the transactions of the account with a nonce lower than `nonce` count towards
+    /// [`PendingBalanceChanges::outgoing`].
+    fn pending_balance_changes(&self, address: Address, nonce: u64) -> PendingBalanceChanges;
+}
+
+/// A handle to the pending view of the transaction pool, for validators that check the balance of
+/// the sender against the latest state with the pending transactions of the pool applied.
+///
+/// The validator is created before the pool, so the handle is empty until it is bound to the pool
+/// with [`Pool::bind_pending_state_view`](crate::Pool::bind_pending_state_view).
+#[derive(Clone, Default)]
+pub struct PendingStateView {
+    provider: Arc<OnceLock<Weak<dyn PendingStateProvider>>>,
+}
+
+impl PendingStateView {
+    /// Binds the view to the given provider.
+    ///
+    /// Returns `false` if the view was already bound.
+    pub fn bind(&self, provider: Weak<dyn PendingStateProvider>) -> bool {
+        self.provider.set(provider).is_ok()
+    }
+
+    /// Returns `true` if the view is bound to a pool.
+    pub fn is_bound(&self) -> bool {
+        self.provider.get().is_some()
+    }
+
+    /// Returns the balance of the account in the pending view, given its balance in the latest
+    /// state.
+    ///
+    /// This is the latest balance plus the value transferred to the account by pending
+    /// transactions, minus the cost of its own transactions in the pool with a nonce lower than
+    /// `nonce`. Returns `None` if the view is not bound to a pool that is still alive.
+    pub fn pending_balance(&self, address: Address, nonce: u64, balance: U256) -> Option<U256> {
+        let provider = self.provider.get()?.upgrade()?;
+        let changes = provider.pending_balance_changes(address, nonce);
+        Some(balance.saturating_add(changes.incoming).saturating_sub(changes.outgoing))
+    }
+}
+
+impl fmt::Debug for PendingStateView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingStateView").field("bound", &self.is_bound()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticChanges(PendingBalanceChanges);
+
+    impl PendingStateProvider for StaticChanges {
+        fn pending_balance_changes(&self, _: Address, _: u64) -> PendingBalanceChanges {
+            self.0
+        }
+    }
+
+    #[test]
+    fn pending_balance() {
+        let view = PendingStateView::default();
+        assert_eq!(view.pending_balance(Address::ZERO, 0, U256::from(10)), None);
+
+        let provider: Arc<dyn PendingStateProvider> =
+            Arc::new(StaticChanges(PendingBalanceChanges {
+                incoming: U256::from(5),
+                outgoing: U256::from(12),
+            }));
+        assert!(view.bind(Arc::downgrade(&provider)));
+        assert!(!view.bind(Arc::downgrade(&provider)));
+        assert_eq!(view.pending_balance(Address::ZERO, 0, U256::from(10)), Some(U256::from(3)));
+        assert_eq!(view.pending_balance(Address::ZERO, 0, U256::from(5)), Some(U256::ZERO));
+
+        drop(provider);
+        assert_eq!(view.pending_balance(Address::ZERO, 0, U256::from(10)), None);
+    }
+}
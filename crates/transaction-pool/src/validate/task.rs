@@ -6,6 +6,7 @@ use crate::{
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
     TransactionValidator,
 };
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
 use futures_util::{lock::Mutex, StreamExt};
 use reth_primitives_traits::{Block, SealedBlock};
 use reth_tasks::TaskSpawner;
@@ -250,4 +251,11 @@ where
     {
         self.validator.on_new_head_block(new_tip_block)
     }
+
+    fn convert_blob_sidecar(
+        &self,
+        sidecar: &BlobTransactionSidecarVariant,
+    ) -> Option<BlobTransactionSidecarVariant> {
+        self.validator.convert_blob_sidecar(sidecar)
+    }
 }
@@ -1,11 +1,14 @@
+use alloy_primitives::B256;
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
-    test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    test_utils::{MockTransaction, MockTransactionFactory, TestPoolBuilder},
+    DropReason, FullTransactionEvent, PoolEvent, PoolEvents, PoolEventsLagged, PoolTransaction,
+    SequencedPoolEvent, SubPool, TransactionEvent, TransactionListenerKind, TransactionOrigin,
+    TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
+use tokio::sync::broadcast;
 use tokio_stream::StreamExt;
 
 #[tokio::test(flavor = "multi_thread")]
@@ -192,3 +195,47 @@ async fn txpool_listener_blob_sidecar() {
     let inserted = listener_blob.recv().await.unwrap();
     assert_eq!(*inserted.tx_hash, expected);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_pool_events() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+    let hash = *transaction.get_hash();
+
+    let mut pool_events = txpool.pool_events();
+    assert_eq!(pool_events.next_seq(), None);
+
+    let result = txpool.add_transaction(TransactionOrigin::External, transaction).await;
+    assert_matches!(result, Ok(_));
+
+    assert_matches!(
+        pool_events.next().await,
+        Some(Ok(SequencedPoolEvent { seq: 0, event: PoolEvent::Added { transaction, subpool: SubPool::Pending } })) if *transaction.hash() == hash
+    );
+    assert_matches!(
+        pool_events.next().await,
+        Some(Ok(SequencedPoolEvent { seq: 1, event: PoolEvent::Pending(tx_hash) })) if tx_hash == hash
+    );
+
+    txpool.remove_transactions(vec![hash]);
+    assert_matches!(
+        pool_events.next().await,
+        Some(Ok(SequencedPoolEvent { seq: 2, event: PoolEvent::Dropped { tx_hash, reason: DropReason::Removed } })) if tx_hash == hash
+    );
+    assert_eq!(pool_events.next_seq(), Some(3));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_pool_events_lagged() {
+    let (tx, rx) = broadcast::channel(2);
+    let mut pool_events = PoolEvents::<MockTransaction>::new(rx);
+
+    for seq in 0..5 {
+        tx.send(SequencedPoolEvent { seq, event: PoolEvent::Pending(B256::random()) }).unwrap();
+    }
+
+    assert_matches!(pool_events.next().await, Some(Err(PoolEventsLagged { missed: 3 })));
+    assert_matches!(pool_events.next().await, Some(Ok(SequencedPoolEvent { seq: 3, .. })));
+    assert_eq!(pool_events.next_seq(), Some(4));
+}
@@ -1,4 +1,4 @@
-use core::ops::Not;
+use core::{mem::size_of, ops::Not};
 
 use crate::{
     prefix_set::{PrefixSetMut, TriePrefixSetsMut},
@@ -13,7 +13,7 @@ use alloy_primitives::{
 use itertools::Itertools;
 #[cfg(feature = "rayon")]
 pub use rayon::*;
-use reth_primitives_traits::Account;
+use reth_primitives_traits::{Account, InMemorySize};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
@@ -381,6 +381,21 @@ pub struct HashedStorage {
     pub storage: B256Map<U256>,
 }
 
+impl InMemorySize for HashedPostState {
+    fn size(&self) -> usize {
+        let accounts = self.accounts.len() * size_of::<(B256, Option<Account>)>();
+        let storages =
+            self.storages.values().map(|storage| size_of::<B256>() + storage.size()).sum::<usize>();
+        accounts + storages
+    }
+}
+
+impl InMemorySize for HashedStorage {
+    fn size(&self) -> usize {
+        size_of::<Self>() + self.storage.len() * size_of::<(B256, U256)>()
+    }
+}
+
 impl HashedStorage {
     /// Create new instance of [`HashedStorage`].
     pub fn new(wiped: bool) -> Self {
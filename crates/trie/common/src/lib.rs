@@ -55,6 +55,10 @@ pub mod root;
 /// Buffer for trie updates.
 pub mod updates;
 
+/// Sink for the nodes of a trie witness.
+mod witness;
+pub use witness::WitnessSink;
+
 /// Bincode-compatible serde implementations for trie types.
 ///
 /// `bincode` crate allows for more efficient serialization of trie types, because it allows
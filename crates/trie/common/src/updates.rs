@@ -1,3 +1,5 @@
+use core::mem::size_of;
+
 use crate::{BranchNodeCompact, HashBuilder, Nibbles};
 use alloc::{
     collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -7,6 +9,7 @@ use alloy_primitives::{
     map::{B256Map, B256Set, HashMap, HashSet},
     FixedBytes, B256,
 };
+use reth_primitives_traits::InMemorySize;
 
 /// The aggregation of trie updates.
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
@@ -22,6 +25,17 @@ pub struct TrieUpdates {
     pub storage_tries: B256Map<StorageTrieUpdates>,
 }
 
+impl InMemorySize for TrieUpdates {
+    fn size(&self) -> usize {
+        let storage_tries = self
+            .storage_tries
+            .values()
+            .map(|updates| size_of::<B256>() + updates.size())
+            .sum::<usize>();
+        nodes_size(&self.account_nodes, &self.removed_nodes) + storage_tries
+    }
+}
+
 impl TrieUpdates {
     /// Returns `true` if the updates are empty.
     pub fn is_empty(&self) -> bool {
@@ -180,6 +194,12 @@ pub struct StorageTrieUpdates {
     pub removed_nodes: HashSet<Nibbles>,
 }
 
+impl InMemorySize for StorageTrieUpdates {
+    fn size(&self) -> usize {
+        size_of::<Self>() + nodes_size(&self.storage_nodes, &self.removed_nodes)
+    }
+}
+
 #[cfg(feature = "test-utils")]
 impl StorageTrieUpdates {
     /// Creates a new storage trie updates that are not marked as deleted.
@@ -491,6 +511,17 @@ impl StorageTrieUpdatesSorted {
     }
 }
 
+/// Returns a heuristic for the in-memory size of updated and removed trie nodes.
+fn nodes_size(nodes: &HashMap<Nibbles, BranchNodeCompact>, removed: &HashSet<Nibbles>) -> usize {
+    let nodes = nodes
+        .values()
+        .map(|node| {
+            size_of::<(Nibbles, BranchNodeCompact)>() + node.hashes.len() * size_of::<B256>()
+        })
+        .sum::<usize>();
+    nodes + removed.len() * size_of::<Nibbles>()
+}
+
 /// Excludes empty nibbles from the given iterator.
 fn exclude_empty(iter: impl IntoIterator<Item = Nibbles>) -> impl Iterator<Item = Nibbles> {
     iter.into_iter().filter(|n| !n.is_empty())
@@ -0,0 +1,30 @@
+use alloc::boxed::Box;
+use alloy_primitives::{
+    map::{B256Map, Entry},
+    Bytes, B256,
+};
+
+/// A sink the nodes of a trie witness are recorded to as they are gathered.
+pub trait WitnessSink {
+    /// Records a witness node with the given hash.
+    ///
+    /// Recording a node that was already recorded has no effect.
+    fn record(
+        &mut self,
+        hash: B256,
+        node: Bytes,
+    ) -> Result<(), Box<dyn core::error::Error + Send + Sync>>;
+}
+
+impl WitnessSink for B256Map<Bytes> {
+    fn record(
+        &mut self,
+        hash: B256,
+        node: Bytes,
+    ) -> Result<(), Box<dyn core::error::Error + Send + Sync>> {
+        if let Entry::Vacant(entry) = self.entry(hash) {
+            entry.insert(node);
+        }
+        Ok(())
+    }
+}
@@ -21,3 +21,15 @@ pub use trie_cursor::{
     DatabaseAccountTrieCursor, DatabaseStorageTrieCursor, DatabaseTrieCursorFactory,
 };
 pub use witness::DatabaseTrieWitness;
+
+use reth_db_api::DatabaseError;
+use reth_storage_errors::provider::ProviderError;
+
+/// Converts an error of a changeset read through a provider into a [`DatabaseError`], which is the
+/// error surfaced by the trie computations.
+pub(crate) fn into_database_error(err: ProviderError) -> DatabaseError {
+    match err {
+        ProviderError::Database(err) => err,
+        err => DatabaseError::Other(err.to_string()),
+    }
+}
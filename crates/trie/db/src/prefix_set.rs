@@ -11,46 +11,53 @@ use reth_db_api::{
     models::{AccountBeforeTx, BlockNumberAddress},
     tables,
     transaction::DbTx,
-    DatabaseError,
 };
 use reth_primitives_traits::StorageEntry;
+use reth_storage_api::{ChangeSetReader, DBProvider, StorageChangeSetReader};
+use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::{PrefixSetMut, TriePrefixSets},
     KeyHasher, Nibbles,
 };
 
-/// A wrapper around a database transaction that loads prefix sets within a given block range.
+/// A wrapper around a database provider that loads prefix sets within a given block range.
+///
+/// Changesets are read through the provider, so the ones that were moved to static files are
+/// loaded as well.
 #[derive(Debug)]
-pub struct PrefixSetLoader<'a, TX, KH>(&'a TX, PhantomData<KH>);
+pub struct PrefixSetLoader<'a, Provider, KH>(&'a Provider, PhantomData<KH>);
 
-impl<'a, TX, KH> PrefixSetLoader<'a, TX, KH> {
+impl<'a, Provider, KH> PrefixSetLoader<'a, Provider, KH> {
     /// Create a new loader.
-    pub const fn new(tx: &'a TX) -> Self {
-        Self(tx, PhantomData)
+    pub const fn new(provider: &'a Provider) -> Self {
+        Self(provider, PhantomData)
     }
 }
 
-impl<TX, KH> Deref for PrefixSetLoader<'_, TX, KH> {
-    type Target = TX;
+impl<Provider, KH> Deref for PrefixSetLoader<'_, Provider, KH> {
+    type Target = Provider;
 
     fn deref(&self) -> &Self::Target {
         self.0
     }
 }
 
-impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
+impl<Provider, KH> PrefixSetLoader<'_, Provider, KH>
+where
+    Provider: DBProvider + ChangeSetReader + StorageChangeSetReader,
+    KH: KeyHasher,
+{
     /// Load all account and storage changes for the given block range.
-    pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
+    pub fn load(self, range: RangeInclusive<BlockNumber>) -> ProviderResult<TriePrefixSets> {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
         let mut destroyed_accounts = HashSet::default();
 
         // Walk account changeset and insert account prefixes.
-        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
-        let mut account_hashed_state_cursor = self.cursor_read::<tables::HashedAccounts>()?;
-        for account_entry in account_changeset_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, .. }) = account_entry?;
+        let mut account_hashed_state_cursor =
+            self.tx_ref().cursor_read::<tables::HashedAccounts>()?;
+        for (_, AccountBeforeTx { address, .. }) in self.account_changesets_range(range.clone())? {
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
@@ -61,10 +68,10 @@ impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
 
         // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
         // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
         let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
-            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+        for (BlockNumberAddress((_, address)), StorageEntry { key, .. }) in
+            self.storage_changesets_range(storage_range)?
+        {
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
             storage_prefix_sets
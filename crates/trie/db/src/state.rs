@@ -1,16 +1,17 @@
-use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, PrefixSetLoader};
+use crate::{
+    into_database_error, DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, PrefixSetLoader,
+};
 use alloy_primitives::{
     map::{AddressMap, B256Map},
     Address, BlockNumber, B256, U256,
 };
 use reth_db_api::{
-    cursor::DbCursorRO,
     models::{AccountBeforeTx, BlockNumberAddress},
-    tables,
     transaction::DbTx,
-    DatabaseError,
 };
 use reth_execution_errors::StateRootError;
+use reth_storage_api::{ChangeSetReader, DBProvider, StorageChangeSetReader};
+use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory, trie_cursor::InMemoryTrieCursorFactory,
     updates::TrieUpdates, HashedPostState, HashedStorage, KeccakKeyHasher, KeyHasher, StateRoot,
@@ -25,15 +26,18 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     fn from_tx(tx: &'a TX) -> Self;
 
     /// Given a block number range, identifies all the accounts and storage keys that
-    /// have changed.
+    /// have changed. Changesets are read through the provider, so the ones that were moved to
+    /// static files are included.
     ///
     /// # Returns
     ///
     /// An instance of state root calculator with account and storage prefixes loaded.
-    fn incremental_root_calculator(
-        tx: &'a TX,
+    fn incremental_root_calculator<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<Self, StateRootError>;
+    ) -> Result<Self, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader;
 
     /// Computes the state root of the trie with the changed account and storage prefixes and
     /// existing trie nodes.
@@ -41,10 +45,12 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     /// # Returns
     ///
     /// The updated state root.
-    fn incremental_root(
-        tx: &'a TX,
+    fn incremental_root<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<B256, StateRootError>;
+    ) -> Result<B256, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader;
 
     /// Computes the state root of the trie with the changed account and storage prefixes and
     /// existing trie nodes collecting updates in the process.
@@ -54,10 +60,12 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     /// # Returns
     ///
     /// The updated state root and the trie updates.
-    fn incremental_root_with_updates(
-        tx: &'a TX,
+    fn incremental_root_with_updates<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<(B256, TrieUpdates), StateRootError>;
+    ) -> Result<(B256, TrieUpdates), StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader;
 
     /// Computes the state root of the trie with the changed account and storage prefixes and
     /// existing trie nodes collecting updates in the process.
@@ -65,10 +73,12 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     /// # Returns
     ///
     /// The intermediate progress of state root computation.
-    fn incremental_root_with_progress(
-        tx: &'a TX,
+    fn incremental_root_with_progress<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<StateRootProgress, StateRootError>;
+    ) -> Result<StateRootProgress, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader;
 
     /// Calculate the state root for this [`HashedPostState`].
     /// Internally, this method retrieves prefixsets and uses them
@@ -122,11 +132,17 @@ pub trait DatabaseStateRoot<'a, TX>: Sized {
     ) -> Result<(B256, TrieUpdates), StateRootError>;
 }
 
-/// Extends [`HashedPostState`] with operations specific for working with a database transaction.
-pub trait DatabaseHashedPostState<TX>: Sized {
+/// Extends [`HashedPostState`] with operations specific for working with a database provider.
+pub trait DatabaseHashedPostState: Sized {
     /// Initializes [`HashedPostState`] from reverts. Iterates over state reverts from the specified
     /// block up to the current tip and aggregates them into hashed state in reverse.
-    fn from_reverts<KH: KeyHasher>(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError>;
+    ///
+    /// Changesets are read through the provider, so the ones that were moved to static files are
+    /// included.
+    fn from_reverts<KH: KeyHasher>(
+        provider: &(impl ChangeSetReader + StorageChangeSetReader),
+        from: BlockNumber,
+    ) -> ProviderResult<Self>;
 }
 
 impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
@@ -136,36 +152,50 @@ impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
         Self::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
     }
 
-    fn incremental_root_calculator(
-        tx: &'a TX,
+    fn incremental_root_calculator<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<Self, StateRootError> {
-        let loaded_prefix_sets = PrefixSetLoader::<_, KeccakKeyHasher>::new(tx).load(range)?;
-        Ok(Self::from_tx(tx).with_prefix_sets(loaded_prefix_sets))
+    ) -> Result<Self, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader,
+    {
+        let loaded_prefix_sets = PrefixSetLoader::<_, KeccakKeyHasher>::new(provider)
+            .load(range)
+            .map_err(into_database_error)?;
+        Ok(Self::from_tx(provider.tx_ref()).with_prefix_sets(loaded_prefix_sets))
     }
 
-    fn incremental_root(
-        tx: &'a TX,
+    fn incremental_root<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<B256, StateRootError> {
+    ) -> Result<B256, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader,
+    {
         debug!(target: "trie::loader", ?range, "incremental state root");
-        Self::incremental_root_calculator(tx, range)?.root()
+        Self::incremental_root_calculator(provider, range)?.root()
     }
 
-    fn incremental_root_with_updates(
-        tx: &'a TX,
+    fn incremental_root_with_updates<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<(B256, TrieUpdates), StateRootError> {
+    ) -> Result<(B256, TrieUpdates), StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader,
+    {
         debug!(target: "trie::loader", ?range, "incremental state root");
-        Self::incremental_root_calculator(tx, range)?.root_with_updates()
+        Self::incremental_root_calculator(provider, range)?.root_with_updates()
     }
 
-    fn incremental_root_with_progress(
-        tx: &'a TX,
+    fn incremental_root_with_progress<Provider>(
+        provider: &'a Provider,
         range: RangeInclusive<BlockNumber>,
-    ) -> Result<StateRootProgress, StateRootError> {
+    ) -> Result<StateRootProgress, StateRootError>
+    where
+        Provider: DBProvider<Tx = TX> + ChangeSetReader + StorageChangeSetReader,
+    {
         debug!(target: "trie::loader", ?range, "incremental state root with progress");
-        Self::incremental_root_calculator(tx, range)?.root_with_progress()
+        Self::incremental_root_calculator(provider, range)?.root_with_progress()
     }
 
     fn overlay_root(tx: &'a TX, post_state: HashedPostState) -> Result<B256, StateRootError> {
@@ -219,23 +249,22 @@ impl<'a, TX: DbTx> DatabaseStateRoot<'a, TX>
     }
 }
 
-impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
-    fn from_reverts<KH: KeyHasher>(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
+impl DatabaseHashedPostState for HashedPostState {
+    fn from_reverts<KH: KeyHasher>(
+        provider: &(impl ChangeSetReader + StorageChangeSetReader),
+        from: BlockNumber,
+    ) -> ProviderResult<Self> {
         // Iterate over account changesets and record value before first occurring account change.
         let mut accounts = HashMap::new();
-        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
-        for entry in account_changesets_cursor.walk_range(from..)? {
-            let (_, AccountBeforeTx { address, info }) = entry?;
+        for (_, AccountBeforeTx { address, info }) in provider.account_changesets_range(from..)? {
             accounts.entry(address).or_insert(info);
         }
 
         // Iterate over storage changesets and record value before first occurring storage change.
         let mut storages = AddressMap::<B256Map<U256>>::default();
-        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in
-            storage_changesets_cursor.walk_range(BlockNumberAddress((from, Address::ZERO))..)?
+        for (BlockNumberAddress((_, address)), storage) in
+            provider.storage_changesets_range(BlockNumberAddress((from, Address::ZERO))..)?
         {
-            let (BlockNumberAddress((_, address)), storage) = entry?;
             let account_storage = storages.entry(address).or_default();
             account_storage.entry(storage.key).or_insert(storage.value);
         }
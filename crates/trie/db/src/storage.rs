@@ -1,9 +1,9 @@
 use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use alloy_primitives::{keccak256, map::hash_map, Address, BlockNumber, B256};
-use reth_db_api::{
-    cursor::DbCursorRO, models::BlockNumberAddress, tables, transaction::DbTx, DatabaseError,
-};
+use reth_db_api::{models::BlockNumberAddress, transaction::DbTx};
 use reth_execution_errors::StorageRootError;
+use reth_storage_api::StorageChangeSetReader;
+use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRoot,
 };
@@ -27,11 +27,18 @@ pub trait DatabaseStorageRoot<'a, TX> {
     ) -> Result<B256, StorageRootError>;
 }
 
-/// Extends [`HashedStorage`] with operations specific for working with a database transaction.
-pub trait DatabaseHashedStorage<TX>: Sized {
+/// Extends [`HashedStorage`] with operations specific for working with a database provider.
+pub trait DatabaseHashedStorage: Sized {
     /// Initializes [`HashedStorage`] from reverts. Iterates over storage reverts from the specified
     /// block up to the current tip and aggregates them into hashed storage in reverse.
-    fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
+    ///
+    /// Changesets are read through the provider, so the ones that were moved to static files are
+    /// included.
+    fn from_reverts(
+        provider: &impl StorageChangeSetReader,
+        address: Address,
+        from: BlockNumber,
+    ) -> ProviderResult<Self>;
 }
 
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
@@ -79,12 +86,16 @@ impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
     }
 }
 
-impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
-    fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError> {
+impl DatabaseHashedStorage for HashedStorage {
+    fn from_reverts(
+        provider: &impl StorageChangeSetReader,
+        address: Address,
+        from: BlockNumber,
+    ) -> ProviderResult<Self> {
         let mut storage = Self::new(false);
-        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in storage_changesets_cursor.walk_range(BlockNumberAddress((from, address))..)? {
-            let (BlockNumberAddress((_, storage_address)), storage_change) = entry?;
+        for (BlockNumberAddress((_, storage_address)), storage_change) in
+            provider.storage_changesets_range(BlockNumberAddress((from, address))..)?
+        {
             if storage_address == address {
                 let hashed_slot = keccak256(storage_change.key);
                 if let hash_map::Entry::Vacant(entry) = storage.storage.entry(hashed_slot) {
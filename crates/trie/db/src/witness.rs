@@ -3,8 +3,10 @@ use alloy_primitives::{map::B256Map, Bytes};
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::TrieWitnessError;
 use reth_trie::{
-    hashed_cursor::HashedPostStateCursorFactory, trie_cursor::InMemoryTrieCursorFactory,
-    witness::TrieWitness, HashedPostState, TrieInput,
+    hashed_cursor::HashedPostStateCursorFactory,
+    trie_cursor::InMemoryTrieCursorFactory,
+    witness::{TrieWitness, WitnessSink},
+    HashedPostState, TrieInput,
 };
 
 /// Extends [`TrieWitness`] with operations specific for working with a database transaction.
@@ -18,6 +20,15 @@ pub trait DatabaseTrieWitness<'a, TX> {
         input: TrieInput,
         target: HashedPostState,
     ) -> Result<B256Map<Bytes>, TrieWitnessError>;
+
+    /// Generates trie witness for target state based on [`TrieInput`] and records the witness
+    /// nodes to the given sink as they are gathered.
+    fn overlay_witness_into<W: WitnessSink + ?Sized>(
+        tx: &'a TX,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut W,
+    ) -> Result<(), TrieWitnessError>;
 }
 
 impl<'a, TX: DbTx> DatabaseTrieWitness<'a, TX>
@@ -32,6 +43,17 @@ impl<'a, TX: DbTx> DatabaseTrieWitness<'a, TX>
         input: TrieInput,
         target: HashedPostState,
     ) -> Result<B256Map<Bytes>, TrieWitnessError> {
+        let mut witness = B256Map::default();
+        Self::overlay_witness_into(tx, input, target, &mut witness)?;
+        Ok(witness)
+    }
+
+    fn overlay_witness_into<W: WitnessSink + ?Sized>(
+        tx: &'a TX,
+        input: TrieInput,
+        target: HashedPostState,
+        witness: &mut W,
+    ) -> Result<(), TrieWitnessError> {
         let nodes_sorted = input.nodes.into_sorted();
         let state_sorted = input.state.into_sorted();
         Self::from_tx(tx)
@@ -44,6 +66,6 @@ impl<'a, TX: DbTx> DatabaseTrieWitness<'a, TX>
                 &state_sorted,
             ))
             .with_prefix_sets_mut(input.prefix_sets)
-            .compute(target)
+            .compute_into(target, witness)
     }
 }
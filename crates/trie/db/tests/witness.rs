@@ -10,10 +10,14 @@ use alloy_rlp::EMPTY_STRING_CODE;
 use reth_db::{cursor::DbCursorRW, tables};
 use reth_db_api::transaction::DbTxMut;
 use reth_primitives_traits::{Account, StorageEntry};
-use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
+use reth_provider::{
+    test_utils::create_test_provider_factory, HashingWriter, LatestStateProviderRef,
+    StateProofProvider,
+};
 use reth_trie::{
-    proof::Proof, witness::TrieWitness, HashedPostState, HashedStorage, MultiProofTargets,
-    StateRoot,
+    proof::Proof,
+    witness::{TrieWitness, WitnessFileWriter},
+    HashedPostState, HashedStorage, MultiProofTargets, StateRoot,
 };
 use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseTrieWitness};
 
@@ -157,3 +161,44 @@ fn correctly_decodes_branch_node_values() {
         assert_eq!(witness.get(&keccak256(node)), Some(node));
     }
 }
+
+#[test]
+fn witness_file_matches_in_memory_witness() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+
+    let address = Address::random();
+    let hashed_address = keccak256(address);
+    provider.insert_account_for_hashing([(address, Some(Account::default()))]).unwrap();
+    let mut hashed_storage_cursor =
+        provider.tx_ref().cursor_dup_write::<tables::HashedStorages>().unwrap();
+    for slot in 1..=16 {
+        hashed_storage_cursor
+            .upsert(
+                hashed_address,
+                &StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) },
+            )
+            .unwrap();
+    }
+
+    let state = HashedPostState {
+        accounts: HashMap::from_iter([(hashed_address, Some(Account::default()))]),
+        storages: HashMap::from_iter([(
+            hashed_address,
+            HashedStorage::from_iter(false, [(B256::with_last_byte(3), U256::ZERO)]),
+        )]),
+    };
+    let witness = LatestStateProviderRef::new(&provider).witness(Default::default(), state.clone());
+    let witness = witness
+        .unwrap()
+        .into_iter()
+        .map(|node| (keccak256(&node), node))
+        .collect::<HashMap<_, _>>();
+
+    let mut writer = WitnessFileWriter::new().unwrap();
+    LatestStateProviderRef::new(&provider)
+        .witness_into(Default::default(), state, &mut writer)
+        .unwrap();
+    let file = writer.finish().unwrap();
+    assert_eq!(file.nodes().unwrap().collect::<Result<HashMap<_, _>, _>>().unwrap(), witness);
+}
@@ -0,0 +1,185 @@
+//! Streaming of trie witnesses to a temporary file.
+
+use super::WitnessSink;
+use alloy_primitives::{map::B256Set, Bytes, B256};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+/// A [`WitnessSink`] that spills the witness nodes to a temporary file as they are recorded,
+/// instead of holding the entire witness in memory.
+///
+/// Only the hashes of the recorded nodes are kept in memory, to skip nodes that were already
+/// recorded. Every node is written as its hash, followed by the length of the node as big-endian
+/// `u32` and the node itself.
+///
+/// The temporary file is removed once the writer, or the [`WitnessFile`] it is finished into, is
+/// dropped.
+#[derive(Debug)]
+pub struct WitnessFileWriter {
+    /// The temporary file the nodes are written to.
+    file: BufWriter<File>,
+    /// Hashes of the recorded nodes.
+    hashes: B256Set,
+    /// Total size of the recorded nodes in bytes.
+    size: u64,
+}
+
+impl WitnessFileWriter {
+    /// Creates a writer to a temporary file in the default temporary directory.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self::from_file(tempfile::tempfile()?))
+    }
+
+    /// Creates a writer to a temporary file in the given directory.
+    pub fn new_in(dir: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_file(tempfile::tempfile_in(dir)?))
+    }
+
+    fn from_file(file: File) -> Self {
+        Self { file: BufWriter::new(file), hashes: B256Set::default(), size: 0 }
+    }
+
+    /// Returns the number of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if no node was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Returns the total size of the recorded nodes in bytes.
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Flushes the recorded nodes and returns the file to read them from.
+    pub fn finish(self) -> io::Result<WitnessFile> {
+        let file = self.file.into_inner().map_err(|err| err.into_error())?;
+        Ok(WitnessFile { file, len: self.hashes.len() })
+    }
+
+    fn write_node(&mut self, hash: &B256, node: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(node.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "witness node too large"))?;
+        self.file.write_all(hash.as_slice())?;
+        self.file.write_all(&len.to_be_bytes())?;
+        self.file.write_all(node)?;
+        self.size += node.len() as u64;
+        Ok(())
+    }
+}
+
+impl WitnessSink for WitnessFileWriter {
+    fn record(
+        &mut self,
+        hash: B256,
+        node: Bytes,
+    ) -> Result<(), Box<dyn core::error::Error + Send + Sync>> {
+        if self.hashes.insert(hash) {
+            self.write_node(&hash, &node)?;
+        }
+        Ok(())
+    }
+}
+
+/// A trie witness written to a temporary file by a [`WitnessFileWriter`].
+#[derive(Debug)]
+pub struct WitnessFile {
+    /// The temporary file holding the nodes.
+    file: File,
+    /// Number of nodes in the file.
+    len: usize,
+}
+
+impl WitnessFile {
+    /// Returns the number of nodes in the witness.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the witness has no nodes.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the hashes and nodes of the witness, in the order they were
+    /// recorded.
+    pub fn nodes(&self) -> io::Result<WitnessFileNodes<'_>> {
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(0))?;
+        Ok(WitnessFileNodes { reader: BufReader::new(file), remaining: self.len })
+    }
+}
+
+/// Iterator over the nodes of a [`WitnessFile`].
+#[derive(Debug)]
+pub struct WitnessFileNodes<'a> {
+    reader: BufReader<&'a File>,
+    remaining: usize,
+}
+
+impl WitnessFileNodes<'_> {
+    fn read_node(&mut self) -> io::Result<(B256, Bytes)> {
+        let mut hash = B256::ZERO;
+        self.reader.read_exact(hash.as_mut_slice())?;
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let mut node = vec![0u8; u32::from_be_bytes(len) as usize];
+        self.reader.read_exact(&mut node)?;
+        Ok((hash, node.into()))
+    }
+}
+
+impl Iterator for WitnessFileNodes<'_> {
+    type Item = io::Result<(B256, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        self.remaining -= 1;
+        Some(self.read_node())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, map::B256Map};
+
+    #[test]
+    fn witness_file_roundtrip() {
+        let nodes = (0..100u8).map(|i| Bytes::from(vec![i; i as usize + 1])).collect::<Vec<_>>();
+
+        let mut writer = WitnessFileWriter::new().unwrap();
+        let mut witness = B256Map::<Bytes>::default();
+        for node in nodes.iter().chain(&nodes) {
+            writer.record(keccak256(node), node.clone()).unwrap();
+            witness.record(keccak256(node), node.clone()).unwrap();
+        }
+        assert_eq!(writer.len(), nodes.len());
+        assert_eq!(writer.size(), nodes.iter().map(|node| node.len() as u64).sum::<u64>());
+
+        let file = writer.finish().unwrap();
+        assert_eq!(file.len(), nodes.len());
+
+        let read = file.nodes().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            read.iter().map(|(_, node)| node).collect::<Vec<_>>(),
+            nodes.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(read.into_iter().collect::<B256Map<_>>(), witness);
+
+        // the nodes can be read again
+        assert_eq!(file.nodes().unwrap().count(), nodes.len());
+    }
+}
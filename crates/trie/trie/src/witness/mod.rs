@@ -11,7 +11,7 @@ use reth_trie_sparse::SparseTrieInterface;
 
 use alloy_primitives::{
     keccak256,
-    map::{B256Map, B256Set, Entry, HashMap},
+    map::{B256Map, B256Set},
     Bytes, B256,
 };
 use itertools::Itertools;
@@ -26,6 +26,10 @@ use reth_trie_sparse::{
 };
 use std::sync::{mpsc, Arc};
 
+mod file;
+pub use file::{WitnessFile, WitnessFileNodes, WitnessFileWriter};
+pub use reth_trie_common::WitnessSink;
+
 /// State transition witness for the trie.
 #[derive(Debug)]
 pub struct TrieWitness<T, H> {
@@ -40,8 +44,6 @@ pub struct TrieWitness<T, H> {
     /// parent state root.
     /// Set to `false` by default.
     always_include_root_node: bool,
-    /// Recorded witness.
-    witness: B256Map<Bytes>,
 }
 
 impl<T, H> TrieWitness<T, H> {
@@ -52,7 +54,6 @@ impl<T, H> TrieWitness<T, H> {
             hashed_cursor_factory,
             prefix_sets: TriePrefixSetsMut::default(),
             always_include_root_node: false,
-            witness: HashMap::default(),
         }
     }
 
@@ -63,7 +64,6 @@ impl<T, H> TrieWitness<T, H> {
             hashed_cursor_factory: self.hashed_cursor_factory,
             prefix_sets: self.prefix_sets,
             always_include_root_node: self.always_include_root_node,
-            witness: self.witness,
         }
     }
 
@@ -74,7 +74,6 @@ impl<T, H> TrieWitness<T, H> {
             hashed_cursor_factory,
             prefix_sets: self.prefix_sets,
             always_include_root_node: self.always_include_root_node,
-            witness: self.witness,
         }
     }
 
@@ -104,10 +103,25 @@ where
     /// # Arguments
     ///
     /// `state` - state transition containing both modified and touched accounts and storage slots.
-    pub fn compute(mut self, state: HashedPostState) -> Result<B256Map<Bytes>, TrieWitnessError> {
+    pub fn compute(self, state: HashedPostState) -> Result<B256Map<Bytes>, TrieWitnessError> {
+        let mut witness = B256Map::default();
+        self.compute_into(state, &mut witness)?;
+        Ok(witness)
+    }
+
+    /// Compute the state transition witness for the trie like [`compute`](Self::compute), but
+    /// record the witness nodes to the given sink as they are gathered.
+    ///
+    /// With a [`WitnessFileWriter`], the witness is spilled to disk instead of being held in
+    /// memory entirely.
+    pub fn compute_into<W: WitnessSink + ?Sized>(
+        self,
+        state: HashedPostState,
+        witness: &mut W,
+    ) -> Result<(), TrieWitnessError> {
         let is_state_empty = state.is_empty();
         if is_state_empty && !self.always_include_root_node {
-            return Ok(Default::default())
+            return Ok(())
         }
 
         let proof_targets = if is_state_empty {
@@ -130,19 +144,19 @@ where
             } else {
                 (EMPTY_ROOT_HASH, Bytes::from([EMPTY_STRING_CODE]))
             };
-            return Ok(B256Map::from_iter([(root_hash, root_node)]))
+            return witness.record(root_hash, root_node).map_err(TrieWitnessError::Sink)
         }
 
         // Record all nodes from multiproof in the witness
         for account_node in multiproof.account_subtree.values() {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(account_node.as_ref())) {
-                entry.insert(account_node.clone());
-            }
+            witness
+                .record(keccak256(account_node.as_ref()), account_node.clone())
+                .map_err(TrieWitnessError::Sink)?;
         }
         for storage_node in multiproof.storages.values().flat_map(|s| s.subtree.values()) {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(storage_node.as_ref())) {
-                entry.insert(storage_node.clone());
-            }
+            witness
+                .record(keccak256(storage_node.as_ref()), storage_node.clone())
+                .map_err(TrieWitnessError::Sink)?;
         }
 
         let (tx, rx) = mpsc::channel();
@@ -199,11 +213,11 @@ where
             sparse_trie.update_account(hashed_address, account, &blinded_provider_factory)?;
 
             while let Ok(node) = rx.try_recv() {
-                self.witness.insert(keccak256(&node), node);
+                witness.record(keccak256(&node), node).map_err(TrieWitnessError::Sink)?;
             }
         }
 
-        Ok(self.witness)
+        Ok(())
     }
 
     /// Retrieve proof targets for incoming hashed state.
@@ -317,6 +317,10 @@ export const sidebar: SidebarItem[] = [
                         text: "reth export-era",
                         link: "/cli/reth/export-era"
                     },
+                    {
+                        text: "reth export-state",
+                        link: "/cli/reth/export-state"
+                    },
                     {
                         text: "reth dump-genesis",
                         link: "/cli/reth/dump-genesis"
@@ -376,6 +380,22 @@ export const sidebar: SidebarItem[] = [
                                     }
                                 ]
                             },
+                            {
+                                text: "reth db unwind-table",
+                                link: "/cli/reth/db/unwind-table"
+                            },
+                            {
+                                text: "reth db verify",
+                                link: "/cli/reth/db/verify"
+                            },
+                            {
+                                text: "reth db migrate-change-sets",
+                                link: "/cli/reth/db/migrate-change-sets"
+                            },
+                            {
+                                text: "reth db repair",
+                                link: "/cli/reth/db/repair"
+                            },
                             {
                                 text: "reth db version",
                                 link: "/cli/reth/db/version"
@@ -466,6 +486,10 @@ export const sidebar: SidebarItem[] = [
                                         link: "/cli/reth/p2p/rlpx/ping"
                                     }
                                 ]
+                            },
+                            {
+                                text: "reth p2p replay",
+                                link: "/cli/reth/p2p/replay"
                             }
                         ]
                     },
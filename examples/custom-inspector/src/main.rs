@@ -67,6 +67,7 @@ fn main() {
                                     call_request,
                                     BlockNumberOrTag::Latest.into(),
                                     EvmOverrides::default(),
+                                    false,
                                     move |db, evm_env, tx_env| {
                                         let mut dummy_inspector = DummyInspector::default();
                                         let mut evm = evm_config.evm_with_env_and_inspector(